@@ -7,6 +7,7 @@ use ggstd::crypto::sha256;
 use ggstd::encoding::hex;
 use std::fs::File;
 use std::io::{Read, Write};
+use std::time::Instant;
 
 fn main() {
     let mut file = File::open("src/testdata/Isaac.Newton-Opticks.txt").unwrap();
@@ -17,15 +18,42 @@ fn main() {
     let mut w = flate::Writer::new(&mut buffer, flate::BEST_COMPRESSION).unwrap();
 
     let repeats = 200;
+    let start = Instant::now();
     for _i in 0..repeats {
         w.write_all(&content).unwrap();
     }
 
     w.close().unwrap();
+    report("compression", content.len() * repeats, start);
 
     let compressed_data = bytes::Buffer::bytes(&buffer);
     let hash = sha256::sum256(compressed_data);
     let hash_str = hex::encode_to_string(&hash);
     println!("compressed data hash:");
     println!("{}", hash_str);
+
+    let start = Instant::now();
+    let mut r = flate::Reader::new(compressed_data);
+    let mut buf = vec![0; 64 * 1024];
+    let mut size = 0;
+    loop {
+        let n = r.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        size += n;
+    }
+    report("decompression", size, start);
+    assert_eq!(size, content.len() * repeats);
+}
+
+fn report(name: &str, size: usize, start: Instant) {
+    let elapsed = start.elapsed();
+    println!(
+        "{}: {} bytes in {:.3}s, {:.1} MB/s",
+        name,
+        size,
+        elapsed.as_secs_f64(),
+        size as f64 / elapsed.as_secs_f64() / 1e6
+    );
 }
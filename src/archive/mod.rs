@@ -0,0 +1,5 @@
+// Copyright 2023 The rust-ggstd authors.
+// SPDX-License-Identifier: BSD-3-Clause
+
+pub mod tar;
+pub mod zip;
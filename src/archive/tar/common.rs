@@ -0,0 +1,588 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2009 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::format::{block_padding, Format};
+use super::strconv::{
+    fits_in_base256, fits_in_octal, format_pax_time, has_nul, is_ascii, valid_pax_record,
+};
+use super::writer::split_ustar_path;
+use crate::time;
+use std::collections::BTreeMap;
+
+pub const ERR_HEADER: &str = "archive/tar: invalid tar header";
+pub const ERR_WRITE_TOO_LONG: &str = "archive/tar: write too long";
+pub const ERR_FIELD_TOO_LONG: &str = "archive/tar: header field too long";
+pub const ERR_WRITE_AFTER_CLOSE: &str = "archive/tar: write after close";
+pub(super) const ERR_MISS_DATA: &str = "archive/tar: sparse file references non-existent data";
+pub(super) const ERR_UNREF_DATA: &str = "archive/tar: sparse file contains unreferenced data";
+pub(super) const ERR_WRITE_HOLE: &str = "archive/tar: write non-NUL byte in sparse hole";
+
+/// new_header_error returns the error reported for an invalid tar header.
+pub(super) fn new_header_error() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, ERR_HEADER)
+}
+
+/// new_input_error returns an error reported for an invalid use of the Writer.
+pub(super) fn new_input_error(msg: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, msg)
+}
+
+/// new_encode_error returns the error reported when a Header cannot be encoded
+/// in any of the allowed formats. It lists all the non-empty reasons.
+fn new_encode_error(reasons: &[&str]) -> std::io::Error {
+    const PREFIX: &str = "archive/tar: cannot encode header";
+    let ss: Vec<&str> = reasons.iter().copied().filter(|s| !s.is_empty()).collect();
+    if ss.is_empty() {
+        return new_input_error(PREFIX);
+    }
+    new_input_error(&format!("{}: {}", PREFIX, ss.join("; and ")))
+}
+
+// Type flags for Header.typeflag.
+
+/// TYPE_REG indicates a regular file.
+pub const TYPE_REG: u8 = b'0';
+
+/// TYPE_REG_A is the legacy flag for regular files, which the Reader and
+/// the Writer promote to TYPE_REG, or to TYPE_DIR if the name has a trailing slash.
+pub const TYPE_REG_A: u8 = b'\x00';
+
+// Type '1' to '6' are header-only flags and may not have a data body.
+
+/// TYPE_LINK is a hard link.
+pub const TYPE_LINK: u8 = b'1';
+/// TYPE_SYMLINK is a symbolic link.
+pub const TYPE_SYMLINK: u8 = b'2';
+/// TYPE_CHAR is a character device node.
+pub const TYPE_CHAR: u8 = b'3';
+/// TYPE_BLOCK is a block device node.
+pub const TYPE_BLOCK: u8 = b'4';
+/// TYPE_DIR is a directory.
+pub const TYPE_DIR: u8 = b'5';
+/// TYPE_FIFO is a FIFO node.
+pub const TYPE_FIFO: u8 = b'6';
+
+/// TYPE_CONT is reserved.
+pub const TYPE_CONT: u8 = b'7';
+
+/// TYPE_XHEADER is used by the PAX format to store key-value records that
+/// are only relevant to the next file.
+/// This package transparently handles these types.
+pub const TYPE_XHEADER: u8 = b'x';
+
+/// TYPE_XGLOBAL_HEADER is used by the PAX format to store key-value records
+/// that are relevant to all subsequent files.
+/// This package only supports parsing and composing such headers,
+/// but does not currently support persisting the global state across files.
+pub const TYPE_XGLOBAL_HEADER: u8 = b'g';
+
+/// TYPE_GNU_SPARSE indicates a sparse file in the GNU format.
+pub const TYPE_GNU_SPARSE: u8 = b'S';
+
+/// TYPE_GNU_LONG_NAME is used by the GNU format for a meta file to store
+/// the path of the next file.
+/// This package transparently handles these types.
+pub const TYPE_GNU_LONG_NAME: u8 = b'L';
+
+/// TYPE_GNU_LONG_LINK is used by the GNU format for a meta file to store
+/// the link name of the next file.
+/// This package transparently handles these types.
+pub const TYPE_GNU_LONG_LINK: u8 = b'K';
+
+// Keywords for PAX extended header records.
+pub(super) const PAX_NONE: &str = ""; // Indicates that no PAX key is suitable
+pub(super) const PAX_PATH: &str = "path";
+pub(super) const PAX_LINKPATH: &str = "linkpath";
+pub(super) const PAX_SIZE: &str = "size";
+pub(super) const PAX_UID: &str = "uid";
+pub(super) const PAX_GID: &str = "gid";
+pub(super) const PAX_UNAME: &str = "uname";
+pub(super) const PAX_GNAME: &str = "gname";
+pub(super) const PAX_MTIME: &str = "mtime";
+pub(super) const PAX_ATIME: &str = "atime";
+pub(super) const PAX_CTIME: &str = "ctime"; // Removed from later revision of PAX spec, but was valid
+
+pub(super) const PAX_SCHILY_XATTR: &str = "SCHILY.xattr.";
+
+// Keywords for GNU sparse files in a PAX extended header.
+pub(super) const PAX_GNU_SPARSE: &str = "GNU.sparse.";
+pub(super) const PAX_GNU_SPARSE_NUM_BLOCKS: &str = "GNU.sparse.numblocks";
+pub(super) const PAX_GNU_SPARSE_OFFSET: &str = "GNU.sparse.offset";
+pub(super) const PAX_GNU_SPARSE_NUM_BYTES: &str = "GNU.sparse.numbytes";
+pub(super) const PAX_GNU_SPARSE_MAP: &str = "GNU.sparse.map";
+pub(super) const PAX_GNU_SPARSE_NAME: &str = "GNU.sparse.name";
+pub(super) const PAX_GNU_SPARSE_MAJOR: &str = "GNU.sparse.major";
+pub(super) const PAX_GNU_SPARSE_MINOR: &str = "GNU.sparse.minor";
+pub(super) const PAX_GNU_SPARSE_SIZE: &str = "GNU.sparse.size";
+pub(super) const PAX_GNU_SPARSE_REAL_SIZE: &str = "GNU.sparse.realsize";
+
+/// is_basic_key reports whether k is one of the PAX keys that describe
+/// the basic fields of a Header.
+fn is_basic_key(k: &str) -> bool {
+    matches!(
+        k,
+        PAX_PATH
+            | PAX_LINKPATH
+            | PAX_SIZE
+            | PAX_UID
+            | PAX_GID
+            | PAX_UNAME
+            | PAX_GNAME
+            | PAX_MTIME
+            | PAX_ATIME
+            | PAX_CTIME
+    )
+}
+
+/// A Header represents a single header in a tar archive.
+/// Some fields may not be populated.
+///
+/// For forward compatibility, users that retrieve a Header from Reader::next,
+/// mutate it in some ways, and then pass it back to Writer::write_header
+/// should do so by creating a new Header and copying the fields
+/// that they are interested in preserving.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Header {
+    /// typeflag is the type of header entry.
+    /// The zero value is automatically promoted to either TYPE_REG or TYPE_DIR
+    /// depending on the presence of a trailing slash in name.
+    pub typeflag: u8,
+
+    /// name is the name of the file entry.
+    pub name: String,
+    /// linkname is the target name of the link (valid for TYPE_LINK or TYPE_SYMLINK).
+    pub linkname: String,
+
+    /// size is the logical file size in bytes.
+    pub size: i64,
+    /// mode is the permission and mode bits.
+    pub mode: i64,
+    /// uid is the user id of the owner.
+    pub uid: isize,
+    /// gid is the group id of the owner.
+    pub gid: isize,
+    /// uname is the user name of the owner.
+    pub uname: String,
+    /// gname is the group name of the owner.
+    pub gname: String,
+
+    /// If the format is unspecified, then Writer::write_header rounds mod_time
+    /// to the nearest second and ignores the access_time and change_time fields.
+    ///
+    /// To use access_time or change_time, specify the format as PAX or GNU.
+    /// To use sub-second resolution, specify the format as PAX.
+    pub mod_time: time::Time,
+    /// access_time requires either PAX or GNU.
+    pub access_time: time::Time,
+    /// change_time requires either PAX or GNU.
+    pub change_time: time::Time,
+
+    /// devmajor is the major device number (valid for TYPE_CHAR or TYPE_BLOCK).
+    pub devmajor: i64,
+    /// devminor is the minor device number (valid for TYPE_CHAR or TYPE_BLOCK).
+    pub devminor: i64,
+
+    /// xattrs stores extended attributes as PAX records under the
+    /// "SCHILY.xattr." namespace.
+    ///
+    /// The following are semantically equivalent:
+    ///  h.xattrs.insert(key, value)
+    ///  h.pax_records.insert("SCHILY.xattr.".to_string() + key, value)
+    ///
+    /// When Writer::write_header is called, the contents of xattrs will take
+    /// precedence over those in pax_records.
+    pub xattrs: BTreeMap<String, String>,
+
+    /// pax_records is a map of PAX extended header records.
+    ///
+    /// User-defined records should have keys of the following form:
+    ///  VENDOR.keyword
+    /// Where VENDOR is some namespace in all uppercase, and keyword may
+    /// not contain the '=' character (e.g., "GOLANG.pkg.version").
+    /// The key and value should be non-empty UTF-8 strings.
+    ///
+    /// When Writer::write_header is called, PAX records derived from the
+    /// other fields in Header take precedence over pax_records.
+    pub pax_records: BTreeMap<String, String>,
+
+    /// sparse_holes represents the hole regions of a sparse file.
+    /// Each entry is a region of the logical file that contains only zeros.
+    /// The entries must be sorted, non-overlapping and within size.
+    ///
+    /// Sparse files require either PAX or GNU. With the GNU format the
+    /// typeflag must be TYPE_GNU_SPARSE. The Writer aligns the holes to
+    /// block boundaries, so some zero bytes may still be stored.
+    pub sparse_holes: Vec<SparseEntry>,
+
+    /// format specifies the format of the tar header.
+    ///
+    /// This is set by Reader::next as a best-effort guess at the format.
+    /// Since the Reader liberally reads some non-compliant files,
+    /// it is possible for this to be Format::UNKNOWN.
+    ///
+    /// If the format is unspecified when Writer::write_header is called,
+    /// then it uses the first format (in the order of USTAR, PAX, GNU)
+    /// capable of encoding this Header (see Format).
+    pub format: Format,
+}
+
+/// SparseEntry represents a fragment of a sparse file: length bytes
+/// starting at offset.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SparseEntry {
+    pub offset: i64,
+    pub length: i64,
+}
+
+impl SparseEntry {
+    pub fn end_offset(&self) -> i64 {
+        self.offset + self.length
+    }
+}
+
+// A sparse file can be represented as either a list of data fragments or
+// a list of hole fragments; the two representations are complements of
+// each other.
+//
+// The Header.sparse_holes field uses the hole representation, while the
+// tar format stores the data fragments.
+//
+// As an example, if the underlying raw file contains the 10-byte data:
+//
+//	let compressed_data = "abcdefgh";
+//
+// And the sparse map has the following entries:
+//
+//	let spd = [
+//		SparseEntry{offset: 2,  length: 5},  // Data fragment for 2..7
+//		SparseEntry{offset: 18, length: 3},  // Data fragment for 18..21
+//	];
+//	let sph = [
+//		SparseEntry{offset: 0,  length: 2},  // Hole fragment for 0..2
+//		SparseEntry{offset: 7,  length: 11}, // Hole fragment for 7..18
+//		SparseEntry{offset: 21, length: 4},  // Hole fragment for 21..25
+//	];
+//
+// Then the content of the resulting sparse file with a Header.size of 25 is:
+//
+//	let sparse_data = "\x00".repeat(2) + "abcde" + &"\x00".repeat(11) + "fgh" + &"\x00".repeat(4);
+
+/// validate_sparse_entries reports whether sp is a valid sparse map.
+/// It does not matter whether sp represents data fragments or hole fragments.
+pub(super) fn validate_sparse_entries(sp: &[SparseEntry], size: i64) -> bool {
+    // Validate all sparse entries. These are the same checks as performed by
+    // the BSD tar utility.
+    if size < 0 {
+        return false;
+    }
+    let mut pre = SparseEntry::default();
+    for &cur in sp {
+        if cur.offset < 0 || cur.length < 0 {
+            return false; // Negative values are never okay
+        } else if cur.offset > i64::MAX - cur.length {
+            return false; // Integer overflow with large length
+        } else if cur.end_offset() > size {
+            return false; // Region extends beyond the actual size
+        } else if pre.end_offset() > cur.offset {
+            return false; // Regions cannot overlap and must be in order
+        }
+        pre = cur;
+    }
+    true
+}
+
+/// align_sparse_entries returns the fragments of src where each fragment's
+/// starting offset is aligned up to the nearest block edge, and each
+/// ending offset is aligned down to the nearest block edge.
+///
+/// Even though the tar Reader and the BSD tar utility can handle entries
+/// with arbitrary offsets and lengths, the GNU tar utility can only handle
+/// offsets and lengths that are multiples of BLOCK_SIZE.
+pub(super) fn align_sparse_entries(src: &[SparseEntry], size: i64) -> Vec<SparseEntry> {
+    let mut dst = Vec::with_capacity(src.len());
+    for s in src {
+        let (mut pos, mut end) = (s.offset, s.end_offset());
+        pos += block_padding(pos); // Round-up to nearest BLOCK_SIZE
+        if end != size {
+            end -= block_padding(-end); // Round-down to nearest BLOCK_SIZE
+        }
+        if pos < end {
+            dst.push(SparseEntry {
+                offset: pos,
+                length: end - pos,
+            });
+        }
+    }
+    dst
+}
+
+/// invert_sparse_entries converts a sparse map from one form to the other.
+/// If the input is sparse holes, then it will output sparse datas and vice-versa.
+/// The input must have been already validated.
+///
+/// This function returns a normalized map where:
+///   - adjacent fragments are coalesced together
+///   - only the last fragment may be empty
+///   - the end_offset of the last fragment is the total size
+pub(super) fn invert_sparse_entries(src: &[SparseEntry], size: i64) -> Vec<SparseEntry> {
+    let mut dst = Vec::with_capacity(src.len() + 1);
+    let mut pre = SparseEntry::default();
+    for cur in src {
+        if cur.length == 0 {
+            continue; // Skip empty fragments
+        }
+        pre.length = cur.offset - pre.offset;
+        if pre.length > 0 {
+            dst.push(pre); // Only add non-empty fragments
+        }
+        pre.offset = cur.end_offset();
+    }
+    pre.length = size - pre.offset; // Possibly the only empty fragment
+    dst.push(pre);
+    dst
+}
+
+/// is_header_only_type checks if the given type flag is of the type that has no
+/// data section even if a size is specified.
+pub(super) fn is_header_only_type(flag: u8) -> bool {
+    matches!(
+        flag,
+        TYPE_LINK | TYPE_SYMLINK | TYPE_CHAR | TYPE_BLOCK | TYPE_DIR | TYPE_FIFO
+    )
+}
+
+impl Header {
+    /// allowed_formats determines which formats can be used.
+    /// The value returned is the logical OR of multiple possible formats.
+    /// If the value is Format::UNKNOWN, then the input Header cannot be encoded
+    /// and an error is returned explaining why.
+    ///
+    /// As a by-product of checking the fields, this function returns pax_hdrs, which
+    /// contain all fields that could not be directly encoded.
+    /// A value receiver ensures that this method does not mutate the source Header.
+    pub(super) fn allowed_formats(&self) -> std::io::Result<(Format, BTreeMap<String, String>)> {
+        let mut format = Format::USTAR | Format::PAX | Format::GNU;
+        let mut pax_hdrs = BTreeMap::new();
+
+        let mut why_no_ustar = String::new();
+        let mut why_no_pax = String::new();
+        let mut why_no_gnu = String::new();
+        let mut prefer_pax = false; // Prefer PAX over USTAR
+
+        let mut verify_string = |s: &str, size: usize, name: &str, pax_key: &str| {
+            // NUL-terminator is optional for path and linkpath.
+            // Technically, it is required for uname and gname,
+            // but neither GNU nor BSD tar checks for it.
+            let too_long = s.len() > size;
+            let allow_long_gnu = pax_key == PAX_PATH || pax_key == PAX_LINKPATH;
+            if has_nul(s) || (too_long && !allow_long_gnu) {
+                why_no_gnu = format!("GNU cannot encode {}={:?}", name, s);
+                format.must_not_be(Format::GNU);
+            }
+            if !is_ascii(s) || too_long {
+                let can_split_ustar = pax_key == PAX_PATH;
+                if !can_split_ustar || split_ustar_path(s).is_none() {
+                    why_no_ustar = format!("USTAR cannot encode {}={:?}", name, s);
+                    format.must_not_be(Format::USTAR);
+                }
+                if pax_key == PAX_NONE {
+                    why_no_pax = format!("PAX cannot encode {}={:?}", name, s);
+                    format.must_not_be(Format::PAX);
+                } else {
+                    pax_hdrs.insert(pax_key.to_string(), s.to_string());
+                }
+            }
+            if let Some(v) = self.pax_records.get(pax_key) {
+                if v == s {
+                    pax_hdrs.insert(pax_key.to_string(), v.clone());
+                }
+            }
+        };
+        verify_string(&self.name, 100, "Name", PAX_PATH);
+        verify_string(&self.linkname, 100, "Linkname", PAX_LINKPATH);
+        verify_string(&self.uname, 32, "Uname", PAX_UNAME);
+        verify_string(&self.gname, 32, "Gname", PAX_GNAME);
+
+        let mut verify_numeric = |n: i64, size: usize, name: &str, pax_key: &str| {
+            if !fits_in_base256(size, n) {
+                why_no_gnu = format!("GNU cannot encode {}={}", name, n);
+                format.must_not_be(Format::GNU);
+            }
+            if !fits_in_octal(size, n) {
+                why_no_ustar = format!("USTAR cannot encode {}={}", name, n);
+                format.must_not_be(Format::USTAR);
+                if pax_key == PAX_NONE {
+                    why_no_pax = format!("PAX cannot encode {}={}", name, n);
+                    format.must_not_be(Format::PAX);
+                } else {
+                    pax_hdrs.insert(pax_key.to_string(), n.to_string());
+                }
+            }
+            if let Some(v) = self.pax_records.get(pax_key) {
+                if *v == n.to_string() {
+                    pax_hdrs.insert(pax_key.to_string(), v.clone());
+                }
+            }
+        };
+        verify_numeric(self.mode, 8, "Mode", PAX_NONE);
+        verify_numeric(self.uid as i64, 8, "Uid", PAX_UID);
+        verify_numeric(self.gid as i64, 8, "Gid", PAX_GID);
+        verify_numeric(self.size, 12, "Size", PAX_SIZE);
+        verify_numeric(self.devmajor, 8, "Devmajor", PAX_NONE);
+        verify_numeric(self.devminor, 8, "Devminor", PAX_NONE);
+
+        let mut verify_time = |ts: &time::Time, size: usize, name: &str, pax_key: &str| {
+            if ts.is_zero() {
+                return; // Always okay
+            }
+            if !fits_in_base256(size, ts.unix()) {
+                why_no_gnu = format!("GNU cannot encode {}={:?}", name, ts);
+                format.must_not_be(Format::GNU);
+            }
+            let is_mtime = pax_key == PAX_MTIME;
+            let fits_octal = fits_in_octal(size, ts.unix());
+            if !is_mtime || !fits_octal {
+                why_no_ustar = format!("USTAR cannot encode {}={:?}", name, ts);
+                format.must_not_be(Format::USTAR);
+            }
+            let needs_nano = ts.nanosecond() != 0;
+            if !is_mtime || !fits_octal || needs_nano {
+                prefer_pax = true; // USTAR may truncate sub-second measurements
+                if pax_key == PAX_NONE {
+                    why_no_pax = format!("PAX cannot encode {}={:?}", name, ts);
+                    format.must_not_be(Format::PAX);
+                } else {
+                    pax_hdrs.insert(pax_key.to_string(), format_pax_time(ts));
+                }
+            }
+            if let Some(v) = self.pax_records.get(pax_key) {
+                if *v == format_pax_time(ts) {
+                    pax_hdrs.insert(pax_key.to_string(), v.clone());
+                }
+            }
+        };
+        verify_time(&self.mod_time, 12, "ModTime", PAX_MTIME);
+        verify_time(&self.access_time, 12, "AccessTime", PAX_ATIME);
+        verify_time(&self.change_time, 12, "ChangeTime", PAX_CTIME);
+
+        // Check for header-only types.
+        let mut why_only_pax = "";
+        let mut why_only_gnu = "";
+        match self.typeflag {
+            // Exclude TYPE_LINK and TYPE_SYMLINK, since they may reference directories.
+            TYPE_REG | TYPE_CHAR | TYPE_BLOCK | TYPE_FIFO | TYPE_GNU_SPARSE
+                if self.name.ends_with('/') =>
+            {
+                return Err(new_encode_error(&["filename may not have trailing slash"]));
+            }
+            TYPE_XHEADER | TYPE_GNU_LONG_NAME | TYPE_GNU_LONG_LINK => {
+                return Err(new_encode_error(&["cannot manually encode TypeXHeader, TypeGNULongName, or TypeGNULongLink headers"]));
+            }
+            TYPE_XGLOBAL_HEADER => {
+                let h2 = Header {
+                    name: self.name.clone(),
+                    typeflag: self.typeflag,
+                    xattrs: self.xattrs.clone(),
+                    pax_records: self.pax_records.clone(),
+                    format: self.format,
+                    ..Default::default()
+                };
+                if *self != h2 {
+                    return Err(new_encode_error(&[
+                        "only PAXRecords should be set for TypeXGlobalHeader",
+                    ]));
+                }
+                why_only_pax = "only PAX supports TypeXGlobalHeader";
+                format.may_only_be(Format::PAX);
+            }
+            _ => {}
+        }
+        if !is_header_only_type(self.typeflag) && self.size < 0 {
+            return Err(new_encode_error(&["negative size on header-only type"]));
+        }
+
+        // Check PAX records.
+        if !self.xattrs.is_empty() {
+            for (k, v) in &self.xattrs {
+                pax_hdrs.insert(PAX_SCHILY_XATTR.to_string() + k, v.clone());
+            }
+            why_only_pax = "only PAX supports Xattrs";
+            format.may_only_be(Format::PAX);
+        }
+        if !self.pax_records.is_empty() {
+            for (k, v) in &self.pax_records {
+                if pax_hdrs.contains_key(k) {
+                    continue; // Do not overwrite existing records
+                } else if self.typeflag == TYPE_XGLOBAL_HEADER {
+                    pax_hdrs.insert(k.clone(), v.clone()); // Copy all records
+                } else if !is_basic_key(k) && !k.starts_with(PAX_GNU_SPARSE) {
+                    pax_hdrs.insert(k.clone(), v.clone()); // Ignore local records that may conflict
+                }
+            }
+            why_only_pax = "only PAX supports PAXRecords";
+            format.may_only_be(Format::PAX);
+        }
+        for (k, v) in &pax_hdrs {
+            if !valid_pax_record(k, v) {
+                return Err(new_encode_error(&[&format!(
+                    "invalid PAX record: {:?}",
+                    k.clone() + " = " + v
+                )]));
+            }
+        }
+
+        // Check sparse files.
+        if !self.sparse_holes.is_empty() || self.typeflag == TYPE_GNU_SPARSE {
+            if is_header_only_type(self.typeflag) {
+                return Err(new_encode_error(&["header-only type cannot be sparse"]));
+            }
+            if !validate_sparse_entries(&self.sparse_holes, self.size) {
+                return Err(new_encode_error(&["invalid sparse holes"]));
+            }
+            if self.typeflag == TYPE_GNU_SPARSE {
+                why_only_gnu = "only GNU supports TypeGNUSparse";
+                format.may_only_be(Format::GNU);
+            } else {
+                why_no_gnu = "GNU supports sparse files only with TypeGNUSparse".to_string();
+                format.must_not_be(Format::GNU);
+            }
+            why_no_ustar = "USTAR does not support sparse files".to_string();
+            format.must_not_be(Format::USTAR);
+        }
+
+        // Check desired format.
+        let mut want_format = self.format;
+        if want_format != Format::UNKNOWN {
+            if want_format.has(Format::PAX) && !prefer_pax {
+                want_format.may_be(Format::USTAR); // PAX implies USTAR allowed too
+            }
+            format.may_only_be(want_format); // Set union of formats allowed and format wanted
+        }
+        if format == Format::UNKNOWN {
+            return Err(match self.format {
+                Format::USTAR => new_encode_error(&[
+                    "Format specifies USTAR",
+                    &why_no_ustar,
+                    why_only_pax,
+                    why_only_gnu,
+                ]),
+                Format::PAX => {
+                    new_encode_error(&["Format specifies PAX", &why_no_pax, why_only_gnu])
+                }
+                Format::GNU => {
+                    new_encode_error(&["Format specifies GNU", &why_no_gnu, why_only_pax])
+                }
+                _ => new_encode_error(&[
+                    &why_no_ustar,
+                    &why_no_pax,
+                    &why_no_gnu,
+                    why_only_pax,
+                    why_only_gnu,
+                ]),
+            });
+        }
+        Ok((format, pax_hdrs))
+    }
+}
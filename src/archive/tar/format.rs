@@ -0,0 +1,401 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2016 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::strconv::{Formatter, Parser};
+
+/// Format represents the tar archive format.
+///
+/// The original tar format was introduced in Unix V7.
+/// Since then, there have been multiple competing formats attempting to
+/// standardize or extend the V7 format to overcome its limitations.
+/// The most common formats are the USTAR, PAX, and GNU formats,
+/// each with their own advantages and limitations.
+///
+/// The following table captures the capabilities of each format:
+///
+/// ```text
+///                   |  USTAR |       PAX |       GNU
+///  ------------------+--------+-----------+----------
+///  Name              |   256B | unlimited | unlimited
+///  Linkname          |   100B | unlimited | unlimited
+///  Size              | uint33 | unlimited |    uint89
+///  Mode              | uint21 |    uint21 |    uint57
+///  Uid/Gid           | uint21 | unlimited |    uint57
+///  Uname/Gname       |    32B | unlimited |       32B
+///  ModTime           | uint33 | unlimited |     int89
+///  AccessTime        |    n/a | unlimited |     int89
+///  ChangeTime        |    n/a | unlimited |     int89
+///  Devmajor/Devminor | uint21 |    uint21 |    uint57
+///  ------------------+--------+-----------+----------
+///  string encoding   |  ASCII |     UTF-8 |    binary
+///  sub-second times  |     no |       yes |        no
+///  sparse files      |     no |       yes |       yes
+/// ```
+///
+/// The table's upper portion shows the Header fields, where each format reports
+/// the maximum number of bytes allowed for each string field and
+/// the integer type used to store each numeric field
+/// (where timestamps are stored as the number of seconds since the Unix epoch).
+///
+/// The table's lower portion shows specialized features of each format,
+/// such as supported string encodings, support for sub-second timestamps,
+/// or support for sparse files.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub struct Format(u8);
+
+impl Format {
+    /// UNKNOWN indicates that the format is unknown.
+    pub const UNKNOWN: Format = Format(0);
+
+    /// V7 is the format from Unix V7.
+    ///
+    /// While the V7 format is not explicitly selectable by users, it is
+    /// detected when reading archives that predate USTAR.
+    pub(super) const V7: Format = Format(1);
+
+    /// USTAR represents the USTAR header format defined in POSIX.1-1988.
+    ///
+    /// While this format is compatible with most tar readers,
+    /// the format has several limitations making it unsuitable for some usages.
+    /// Most notably, it cannot support sparse files, files larger than 8GiB,
+    /// filenames larger than 256 characters, and non-ASCII filenames.
+    ///
+    /// Reference:
+    /// <http://pubs.opengroup.org/onlinepubs/9699919799/utilities/pax.html#tag_20_92_13_06>
+    pub const USTAR: Format = Format(2);
+
+    /// PAX represents the PAX header format defined in POSIX.1-2001.
+    ///
+    /// PAX extends USTAR by writing a special file with Typeflag TYPE_XHEADER
+    /// preceding the original header. This file contains a set of key-value
+    /// records, which are used to overcome USTAR's shortcomings, in addition to
+    /// providing the ability to have sub-second resolution for timestamps.
+    ///
+    /// Some newer formats add their own extensions to PAX by defining their
+    /// own keys and assigning certain semantic meaning to the associated values.
+    /// For example, sparse file support in PAX is implemented using keys
+    /// defined by the GNU manual (e.g., "GNU.sparse.map").
+    ///
+    /// Reference:
+    /// <http://pubs.opengroup.org/onlinepubs/009695399/utilities/pax.html>
+    pub const PAX: Format = Format(4);
+
+    /// GNU represents the GNU header format.
+    ///
+    /// The GNU header format is older than the USTAR and PAX standards and
+    /// is not compatible with them. The GNU format supports
+    /// arbitrary file sizes, filenames of arbitrary encoding and length,
+    /// sparse files, and other features.
+    ///
+    /// It is recommended that PAX be chosen over GNU unless the target
+    /// application can only parse GNU formatted archives.
+    ///
+    /// Reference:
+    /// <https://www.gnu.org/software/tar/manual/html_node/Standard.html>
+    pub const GNU: Format = Format(8);
+
+    /// STAR is Schily's tar format, which is incompatible with USTAR.
+    /// This does not cover STAR extensions to the PAX format; these fall under
+    /// the PAX format.
+    pub(super) const STAR: Format = Format(16);
+
+    const MAX: Format = Format(32);
+
+    /// has reports whether any of the formats in f2 are in f.
+    pub fn has(self, f2: Format) -> bool {
+        self.0 & f2.0 != 0
+    }
+
+    /// may_be adds the formats in f2 to f.
+    pub(super) fn may_be(&mut self, f2: Format) {
+        self.0 |= f2.0
+    }
+
+    /// may_only_be removes all formats from f except those in f2.
+    pub(super) fn may_only_be(&mut self, f2: Format) {
+        self.0 &= f2.0
+    }
+
+    /// must_not_be removes the formats in f2 from f.
+    pub(super) fn must_not_be(&mut self, f2: Format) {
+        self.0 &= !f2.0
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Format::V7 => "V7",
+            Format::USTAR => "USTAR",
+            Format::PAX => "PAX",
+            Format::GNU => "GNU",
+            Format::STAR => "STAR",
+            _ => "<unknown>",
+        }
+    }
+}
+
+impl std::ops::BitOr for Format {
+    type Output = Format;
+
+    fn bitor(self, rhs: Format) -> Format {
+        Format(self.0 | rhs.0)
+    }
+}
+
+impl std::fmt::Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut ss = Vec::new();
+        let mut f2 = Format(1);
+        while f2.0 < Format::MAX.0 {
+            if self.has(f2) {
+                ss.push(f2.name());
+            }
+            f2 = Format(f2.0 << 1);
+        }
+        match ss.len() {
+            0 => write!(f, "<unknown>"),
+            1 => write!(f, "{}", ss[0]),
+            _ => write!(f, "({})", ss.join(" | ")),
+        }
+    }
+}
+
+impl std::fmt::Debug for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
+// Magics used to identify various formats.
+pub(super) const MAGIC_GNU: &[u8] = b"ustar ";
+pub(super) const VERSION_GNU: &[u8] = b" \x00";
+pub(super) const MAGIC_USTAR: &[u8] = b"ustar\x00";
+pub(super) const VERSION_USTAR: &[u8] = b"00";
+pub(super) const TRAILER_STAR: &[u8] = b"tar\x00";
+
+// Size constants from various tar specifications.
+
+/// BLOCK_SIZE is the size of each block in a tar stream.
+pub(super) const BLOCK_SIZE: usize = 512;
+/// NAME_SIZE is the max length of the name field in USTAR format.
+pub(super) const NAME_SIZE: usize = 100;
+/// PREFIX_SIZE is the max length of the prefix field in USTAR format.
+pub(super) const PREFIX_SIZE: usize = 155;
+
+/// MAX_SPECIAL_FILE_SIZE is the max length of a special file (PAX header,
+/// GNU long name or link). This matches the limit used by libarchive.
+pub(super) const MAX_SPECIAL_FILE_SIZE: usize = 1 << 20;
+
+pub(super) const ZERO_BLOCK: [u8; BLOCK_SIZE] = [0; BLOCK_SIZE];
+
+/// block_padding computes the number of bytes needed to pad offset up to the
+/// nearest block edge where 0 <= n < BLOCK_SIZE.
+pub(super) fn block_padding(offset: i64) -> i64 {
+    -offset & (BLOCK_SIZE as i64 - 1)
+}
+
+/// Block is a single block of a tar stream.
+///
+/// The accessors return the fields of the V7 header and of the USTAR, GNU
+/// and STAR extensions. The fields of different formats overlap, so the
+/// format of the block determines which of the accessors are meaningful.
+pub(super) struct Block(pub(super) [u8; BLOCK_SIZE]);
+
+impl Block {
+    pub(super) fn new() -> Self {
+        Self([0; BLOCK_SIZE])
+    }
+
+    fn field(&mut self, offset: usize, len: usize) -> &mut [u8] {
+        &mut self.0[offset..offset + len]
+    }
+
+    // Fields of the V7 header, shared by all formats.
+
+    pub(super) fn name(&mut self) -> &mut [u8] {
+        self.field(0, 100)
+    }
+    pub(super) fn mode(&mut self) -> &mut [u8] {
+        self.field(100, 8)
+    }
+    pub(super) fn uid(&mut self) -> &mut [u8] {
+        self.field(108, 8)
+    }
+    pub(super) fn gid(&mut self) -> &mut [u8] {
+        self.field(116, 8)
+    }
+    pub(super) fn size(&mut self) -> &mut [u8] {
+        self.field(124, 12)
+    }
+    pub(super) fn mod_time(&mut self) -> &mut [u8] {
+        self.field(136, 12)
+    }
+    pub(super) fn chksum(&mut self) -> &mut [u8] {
+        self.field(148, 8)
+    }
+    pub(super) fn type_flag(&mut self) -> &mut u8 {
+        &mut self.0[156]
+    }
+    pub(super) fn link_name(&mut self) -> &mut [u8] {
+        self.field(157, 100)
+    }
+
+    // Fields shared by the USTAR, GNU and STAR headers.
+
+    pub(super) fn magic(&mut self) -> &mut [u8] {
+        self.field(257, 6)
+    }
+    pub(super) fn version(&mut self) -> &mut [u8] {
+        self.field(263, 2)
+    }
+    pub(super) fn user_name(&mut self) -> &mut [u8] {
+        self.field(265, 32)
+    }
+    pub(super) fn group_name(&mut self) -> &mut [u8] {
+        self.field(297, 32)
+    }
+    pub(super) fn dev_major(&mut self) -> &mut [u8] {
+        self.field(329, 8)
+    }
+    pub(super) fn dev_minor(&mut self) -> &mut [u8] {
+        self.field(337, 8)
+    }
+
+    // Fields of the USTAR header.
+
+    pub(super) fn ustar_prefix(&mut self) -> &mut [u8] {
+        self.field(345, 155)
+    }
+
+    // Fields of the GNU header.
+
+    pub(super) fn gnu_access_time(&mut self) -> &mut [u8] {
+        self.field(345, 12)
+    }
+    pub(super) fn gnu_change_time(&mut self) -> &mut [u8] {
+        self.field(357, 12)
+    }
+    pub(super) fn gnu_sparse(&mut self) -> SparseArray<'_> {
+        SparseArray(self.field(386, 24 * 4 + 1))
+    }
+    pub(super) fn gnu_real_size(&mut self) -> &mut [u8] {
+        self.field(483, 12)
+    }
+
+    // Fields of the STAR header.
+
+    pub(super) fn star_prefix(&mut self) -> &mut [u8] {
+        self.field(345, 131)
+    }
+    pub(super) fn star_access_time(&mut self) -> &mut [u8] {
+        self.field(476, 12)
+    }
+    pub(super) fn star_change_time(&mut self) -> &mut [u8] {
+        self.field(488, 12)
+    }
+    pub(super) fn star_trailer(&mut self) -> &mut [u8] {
+        self.field(508, 4)
+    }
+
+    /// as_sparse interprets the whole block as a GNU sparse extension header.
+    pub(super) fn as_sparse(&mut self) -> SparseArray<'_> {
+        SparseArray(self.field(0, 24 * 21 + 1))
+    }
+
+    /// get_format checks that the block is a valid tar header based on the checksum.
+    /// It then attempts to guess the specific format based on magic values.
+    /// If the checksum fails, then Format::UNKNOWN is returned.
+    pub(super) fn get_format(&mut self) -> Format {
+        // Verify checksum.
+        let mut p = Parser::default();
+        let value = p.parse_octal(self.chksum());
+        let (chksum1, chksum2) = self.compute_checksum();
+        if p.err || (value != chksum1 && value != chksum2) {
+            return Format::UNKNOWN;
+        }
+
+        // Guess the magic values.
+        let magic = self.magic().to_vec();
+        let version = self.version().to_vec();
+        let trailer = self.star_trailer().to_vec();
+        if magic == MAGIC_USTAR && trailer == TRAILER_STAR {
+            Format::STAR
+        } else if magic == MAGIC_USTAR {
+            Format::USTAR | Format::PAX
+        } else if magic == MAGIC_GNU && version == VERSION_GNU {
+            Format::GNU
+        } else {
+            Format::V7
+        }
+    }
+
+    /// set_format writes the magic values necessary for specified format
+    /// and then updates the checksum accordingly.
+    pub(super) fn set_format(&mut self, format: Format) {
+        // Set the magic values.
+        if format.has(Format::V7) {
+            // Do nothing.
+        } else if format.has(Format::GNU) {
+            self.magic().copy_from_slice(MAGIC_GNU);
+            self.version().copy_from_slice(VERSION_GNU);
+        } else if format.has(Format::STAR) {
+            self.magic().copy_from_slice(MAGIC_USTAR);
+            self.version().copy_from_slice(VERSION_USTAR);
+            self.star_trailer().copy_from_slice(TRAILER_STAR);
+        } else if format.has(Format::USTAR | Format::PAX) {
+            self.magic().copy_from_slice(MAGIC_USTAR);
+            self.version().copy_from_slice(VERSION_USTAR);
+        } else {
+            panic!("invalid format");
+        }
+
+        // Update checksum.
+        // This field is special in that it is terminated by a NULL then space.
+        let mut f = Formatter::default();
+        let (chksum, _) = self.compute_checksum(); // Possible values are 256..128776
+        let field = self.chksum();
+        f.format_octal(&mut field[..7], chksum); // Never fails since 128776 < 262143
+        field[7] = b' ';
+    }
+
+    /// compute_checksum computes the checksum for the header block.
+    /// POSIX specifies a sum of the unsigned byte values, but the Sun tar used
+    /// signed byte values.
+    /// We compute and return both.
+    pub(super) fn compute_checksum(&self) -> (i64, i64) {
+        let (mut unsigned, mut signed) = (0, 0);
+        for (i, &c) in self.0.iter().enumerate() {
+            // Treat the checksum field itself as all spaces.
+            let c = if (148..156).contains(&i) { b' ' } else { c };
+            unsigned += c as i64;
+            signed += c as i8 as i64;
+        }
+        (unsigned, signed)
+    }
+
+    /// reset clears the block with all zeros.
+    pub(super) fn reset(&mut self) {
+        self.0.fill(0);
+    }
+}
+
+/// SparseArray is the list of sparse entries stored in a GNU header or in
+/// a GNU sparse extension header, followed by the "is extended" flag.
+pub(super) struct SparseArray<'a>(&'a mut [u8]);
+
+impl SparseArray<'_> {
+    pub(super) fn offset(&mut self, i: usize) -> &mut [u8] {
+        &mut self.0[i * 24..i * 24 + 12]
+    }
+    pub(super) fn length(&mut self, i: usize) -> &mut [u8] {
+        &mut self.0[i * 24 + 12..i * 24 + 24]
+    }
+    pub(super) fn is_extended(&mut self) -> &mut u8 {
+        let n = self.max_entries();
+        &mut self.0[24 * n]
+    }
+    pub(super) fn max_entries(&self) -> usize {
+        self.0.len() / 24
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright 2023 The rust-ggstd authors.
+// Copyright 2009 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! Package tar implements access to tar archives.
+//!
+//! Tape archives (tar) are a file format for storing a sequence of files that
+//! can be read and written in a streaming manner.
+//! This package aims to cover most variations of the format,
+//! including those produced by GNU and BSD tar tools.
+
+mod common;
+mod format;
+mod reader;
+mod strconv;
+mod writer;
+
+pub use common::{
+    Header, SparseEntry, ERR_FIELD_TOO_LONG, ERR_HEADER, ERR_WRITE_AFTER_CLOSE, ERR_WRITE_TOO_LONG,
+    TYPE_BLOCK, TYPE_CHAR, TYPE_CONT, TYPE_DIR, TYPE_FIFO, TYPE_GNU_LONG_LINK, TYPE_GNU_LONG_NAME,
+    TYPE_GNU_SPARSE, TYPE_LINK, TYPE_REG, TYPE_REG_A, TYPE_SYMLINK, TYPE_XGLOBAL_HEADER,
+    TYPE_XHEADER,
+};
+pub use format::Format;
+pub use reader::Reader;
+pub use writer::Writer;
+
+#[cfg(test)]
+mod reader_test;
+#[cfg(test)]
+mod strconv_test;
+#[cfg(test)]
+mod tar_test;
+#[cfg(test)]
+mod writer_test;
@@ -0,0 +1,838 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2009 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::common::{
+    invert_sparse_entries, is_header_only_type, new_header_error, validate_sparse_entries, Header,
+    SparseEntry, ERR_FIELD_TOO_LONG, ERR_MISS_DATA, ERR_UNREF_DATA, PAX_ATIME, PAX_CTIME, PAX_GID,
+    PAX_GNAME, PAX_GNU_SPARSE_MAJOR, PAX_GNU_SPARSE_MAP, PAX_GNU_SPARSE_MINOR, PAX_GNU_SPARSE_NAME,
+    PAX_GNU_SPARSE_NUM_BLOCKS, PAX_GNU_SPARSE_NUM_BYTES, PAX_GNU_SPARSE_OFFSET,
+    PAX_GNU_SPARSE_REAL_SIZE, PAX_GNU_SPARSE_SIZE, PAX_LINKPATH, PAX_MTIME, PAX_PATH,
+    PAX_SCHILY_XATTR, PAX_SIZE, PAX_UID, PAX_UNAME, TYPE_DIR, TYPE_GNU_LONG_LINK,
+    TYPE_GNU_LONG_NAME, TYPE_GNU_SPARSE, TYPE_REG, TYPE_REG_A, TYPE_XGLOBAL_HEADER, TYPE_XHEADER,
+};
+use super::format::{block_padding, Block, Format, BLOCK_SIZE, MAX_SPECIAL_FILE_SIZE, ZERO_BLOCK};
+use super::strconv::{is_ascii, parse_pax_record, parse_pax_time, Parser};
+use crate::errors;
+use crate::time;
+use std::collections::BTreeMap;
+use std::io::Read;
+
+/// Reader provides sequential access to the contents of a tar archive.
+/// Reader::next advances to the next file in the archive (including the first),
+/// and then Reader can be treated as a std::io::Read to access the file's data.
+pub struct Reader<Input: std::io::Read> {
+    r: Input,
+    /// pad is the amount of padding (ignored) after the current file entry.
+    pad: i64,
+    /// nb is the number of remaining bytes of the current file entry
+    /// stored in the archive.
+    nb: i64,
+    /// sph is the list of holes of the current file entry if it is a sparse
+    /// file, and is empty otherwise. It is normalized, so that only the last
+    /// fragment may be empty and its end_offset is the logical file size.
+    /// sph_idx is the index of the current fragment.
+    sph: Vec<SparseEntry>,
+    sph_idx: usize,
+    /// pos is the current position in the sparse file.
+    pos: i64,
+    /// blk is the buffer to use as temporary local storage.
+    blk: Block,
+
+    /// err is a persistent error.
+    /// It is only the responsibility of every exported method of Reader to
+    /// ensure that this error is sticky.
+    err: Option<std::io::Error>,
+    /// eof is set when the end of the archive has been reached.
+    eof: bool,
+}
+
+impl<Input: std::io::Read> Reader<Input> {
+    /// new creates a new Reader reading from r.
+    /// To keep using r after the Reader is done, pass a mutable reference.
+    pub fn new(r: Input) -> Self {
+        Self {
+            r,
+            pad: 0,
+            nb: 0,
+            sph: Vec::new(),
+            sph_idx: 0,
+            pos: 0,
+            blk: Block::new(),
+            err: None,
+            eof: false,
+        }
+    }
+
+    /// get_ref returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &Input {
+        &self.r
+    }
+
+    /// get_mut returns a mutable reference to the underlying reader.
+    /// Reading from it directly corrupts the archive stream.
+    pub fn get_mut(&mut self) -> &mut Input {
+        &mut self.r
+    }
+
+    /// into_inner returns the underlying reader. The Reader doesn't buffer
+    /// its input, so the underlying reader is positioned right after the
+    /// last byte of the archive that was read.
+    pub fn into_inner(self) -> Input {
+        self.r
+    }
+
+    /// next advances to the next entry in the tar archive.
+    /// The Header::size determines how many bytes can be read for the next file.
+    /// Any remaining data in the current file is automatically discarded.
+    /// At the end of the archive, next returns None.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> std::io::Result<Option<Header>> {
+        if let Some(err) = &self.err {
+            return Err(errors::copy_stdio_error(err));
+        }
+        if self.eof {
+            return Ok(None);
+        }
+        match self.next_header() {
+            Ok(Some(hdr)) => Ok(Some(hdr)),
+            Ok(None) => {
+                self.eof = true;
+                Ok(None)
+            }
+            Err(err) => {
+                self.err = Some(errors::copy_stdio_error(&err));
+                Err(err)
+            }
+        }
+    }
+
+    fn next_header(&mut self) -> std::io::Result<Option<Header>> {
+        let mut pax_hdrs = BTreeMap::new();
+        let mut gnu_long_name = String::new();
+        let mut gnu_long_link = String::new();
+
+        // Externally, next iterates through the tar archive as if it is a series of
+        // files. Internally, the tar format often uses fake "files" to add meta
+        // data that describes the next file. These meta data "files" should not
+        // normally be visible to the outside. As such, this loop iterates through
+        // one or more "header files" until it finds a "normal file".
+        let mut format = Format::USTAR | Format::PAX | Format::GNU;
+        loop {
+            // Discard the remainder of the file and any padding.
+            discard(&mut self.r, self.nb)?;
+            self.nb = 0;
+            let pad = self.pad as usize;
+            if try_read_full(&mut self.r, &mut self.blk.0[..pad])? < pad {
+                return Ok(None);
+            }
+            self.pad = 0;
+
+            let Some(mut hdr) = self.read_header()? else {
+                return Ok(None);
+            };
+            self.handle_regular_file(&hdr)?;
+            format.may_only_be(hdr.format);
+
+            // Check for PAX/GNU special headers and files.
+            match hdr.typeflag {
+                TYPE_XHEADER | TYPE_XGLOBAL_HEADER => {
+                    format.may_only_be(Format::PAX);
+                    pax_hdrs = self.parse_pax()?;
+                    if hdr.typeflag == TYPE_XGLOBAL_HEADER {
+                        merge_pax(&mut hdr, pax_hdrs)?;
+                        return Ok(Some(Header {
+                            name: hdr.name,
+                            typeflag: hdr.typeflag,
+                            xattrs: hdr.xattrs,
+                            pax_records: hdr.pax_records,
+                            format,
+                            ..Default::default()
+                        }));
+                    }
+                    continue; // This is a meta header affecting the next header
+                }
+                TYPE_GNU_LONG_NAME | TYPE_GNU_LONG_LINK => {
+                    format.may_only_be(Format::GNU);
+                    let realname = self.read_special_file()?;
+
+                    let p = Parser::default();
+                    if hdr.typeflag == TYPE_GNU_LONG_NAME {
+                        gnu_long_name = p.parse_string(&realname);
+                    } else {
+                        gnu_long_link = p.parse_string(&realname);
+                    }
+                    continue; // This is a meta header affecting the next header
+                }
+                _ => {
+                    // The old GNU sparse format is handled here since it is technically
+                    // just a regular file with additional attributes.
+
+                    merge_pax(&mut hdr, pax_hdrs)?;
+
+                    // For sparse formats, we need to be aware of the intermediate
+                    // headers, such as GNU LongName, and PAX headers.
+                    if !gnu_long_name.is_empty() {
+                        hdr.name = gnu_long_name;
+                    }
+                    if !gnu_long_link.is_empty() {
+                        hdr.linkname = gnu_long_link;
+                    }
+                    if hdr.typeflag == TYPE_REG_A {
+                        if hdr.name.ends_with('/') {
+                            hdr.typeflag = TYPE_DIR; // Legacy archives use trailing slash for directories
+                        } else {
+                            hdr.typeflag = TYPE_REG;
+                        }
+                    }
+
+                    // The extended headers may have updated the size.
+                    // Thus, setup the regular file reader again after merging PAX headers.
+                    self.handle_regular_file(&hdr)?;
+
+                    // Sparse formats rely on being able to read from the logical data
+                    // section; there must be a preceding call to handle_regular_file.
+                    self.handle_sparse_file(&mut hdr)?;
+
+                    // Set the final guess at the format.
+                    if format.has(Format::USTAR) && format.has(Format::PAX) {
+                        format.may_only_be(Format::USTAR);
+                    }
+                    hdr.format = format;
+                    return Ok(Some(hdr)); // This is a file, so stop
+                }
+            }
+        }
+    }
+
+    /// handle_regular_file sets up the current file reader and padding such that it
+    /// can only read the following logical data section. It will properly handle
+    /// special headers that contain no data section.
+    fn handle_regular_file(&mut self, hdr: &Header) -> std::io::Result<()> {
+        let nb = if is_header_only_type(hdr.typeflag) {
+            0
+        } else {
+            hdr.size
+        };
+        if nb < 0 {
+            return Err(new_header_error());
+        }
+
+        self.pad = block_padding(nb);
+        self.nb = nb;
+        self.sph.clear();
+        self.sph_idx = 0;
+        self.pos = 0;
+        Ok(())
+    }
+
+    /// handle_sparse_file checks if the current file is a sparse format of any type
+    /// and sets the current file reader to be a sparse file reader.
+    fn handle_sparse_file(&mut self, hdr: &mut Header) -> std::io::Result<()> {
+        let spd = if hdr.typeflag == TYPE_GNU_SPARSE {
+            self.read_old_gnu_sparse_map(hdr)?
+        } else {
+            self.read_gnu_sparse_pax_headers(hdr)?
+        };
+
+        // If spd is not None, then this is a sparse file.
+        // Note that it is possible for spd to be empty.
+        if let Some(spd) = spd {
+            if is_header_only_type(hdr.typeflag) || !validate_sparse_entries(&spd, hdr.size) {
+                return Err(new_header_error());
+            }
+            self.sph = invert_sparse_entries(&spd, hdr.size);
+            hdr.sparse_holes = self.sph.iter().copied().filter(|s| s.length > 0).collect();
+        }
+        Ok(())
+    }
+
+    /// read_gnu_sparse_pax_headers checks the PAX headers for GNU sparse headers.
+    /// If they are found, then this function reads the sparse map and returns it.
+    /// This assumes that 0.0 headers have already been converted to 0.1 headers
+    /// by the PAX header parsing logic.
+    fn read_gnu_sparse_pax_headers(
+        &mut self,
+        hdr: &mut Header,
+    ) -> std::io::Result<Option<Vec<SparseEntry>>> {
+        let record = |k: &str| hdr.pax_records.get(k).map_or("", |v| v.as_str());
+
+        // Identify the version of GNU headers.
+        let (major, minor) = (record(PAX_GNU_SPARSE_MAJOR), record(PAX_GNU_SPARSE_MINOR));
+        let is_1x0 = if major == "0" && (minor == "0" || minor == "1") {
+            false
+        } else if major == "1" && minor == "0" {
+            true
+        } else if !major.is_empty() || !minor.is_empty() {
+            return Ok(None); // Unknown GNU sparse PAX version
+        } else if !record(PAX_GNU_SPARSE_MAP).is_empty() {
+            false // 0.0 and 0.1 did not have explicit version records, so guess
+        } else {
+            return Ok(None); // Not a PAX format GNU sparse file.
+        };
+
+        // Update hdr from GNU sparse PAX headers.
+        let name = record(PAX_GNU_SPARSE_NAME).to_string();
+        let mut size = record(PAX_GNU_SPARSE_SIZE);
+        if size.is_empty() {
+            size = record(PAX_GNU_SPARSE_REAL_SIZE);
+        }
+        let size = if size.is_empty() {
+            None
+        } else {
+            Some(size.parse::<i64>().map_err(|_| new_header_error())?)
+        };
+        hdr.format.may_only_be(Format::PAX);
+        if !name.is_empty() {
+            hdr.name = name;
+        }
+        if let Some(size) = size {
+            hdr.size = size;
+        }
+
+        // Read the sparse map according to the appropriate format.
+        if is_1x0 {
+            return self.read_gnu_sparse_map_1x0().map(Some);
+        }
+        read_gnu_sparse_map_0x1(&hdr.pax_records).map(Some)
+    }
+
+    /// parse_pax parses PAX headers.
+    /// If an extended header (type 'x') is invalid, ERR_HEADER is returned.
+    fn parse_pax(&mut self) -> std::io::Result<BTreeMap<String, String>> {
+        let buf = self.read_special_file()?;
+        let mut sbuf = buf.as_slice();
+
+        // For GNU PAX sparse format 0.0 support.
+        // This function transforms the sparse format 0.0 headers into format 0.1
+        // headers since 0.0 headers were not PAX compliant.
+        let mut sparse_map: Vec<String> = Vec::new();
+
+        let mut pax_hdrs = BTreeMap::new();
+        while !sbuf.is_empty() {
+            let (key, value, residual) = parse_pax_record(sbuf)?;
+            sbuf = residual;
+
+            match key.as_str() {
+                PAX_GNU_SPARSE_OFFSET | PAX_GNU_SPARSE_NUM_BYTES => {
+                    // Validate sparse header order and value.
+                    let n = sparse_map.len();
+                    if (n & 1 == 0 && key != PAX_GNU_SPARSE_OFFSET)
+                        || (n & 1 == 1 && key != PAX_GNU_SPARSE_NUM_BYTES)
+                        || value.contains(',')
+                    {
+                        return Err(new_header_error());
+                    }
+                    sparse_map.push(value);
+                }
+                _ => {
+                    pax_hdrs.insert(key, value);
+                }
+            }
+        }
+        if !sparse_map.is_empty() {
+            pax_hdrs.insert(PAX_GNU_SPARSE_MAP.to_string(), sparse_map.join(","));
+        }
+        Ok(pax_hdrs)
+    }
+
+    /// read_header reads the next block header and assumes that the underlying reader
+    /// is already aligned to a block boundary. The raw block of the header is
+    /// left in self.blk in case further processing is required.
+    ///
+    /// None is returned only when one of the following occurs:
+    ///   - Exactly 0 bytes are read and EOF is hit.
+    ///   - Exactly 1 block of zeros is read and EOF is hit.
+    ///   - At least 2 blocks of zeros are read.
+    fn read_header(&mut self) -> std::io::Result<Option<Header>> {
+        // Two blocks of zero bytes marks the end of the archive.
+        if !read_block(&mut self.r, &mut self.blk)? {
+            return Ok(None); // EOF is okay here; exactly 0 bytes read
+        }
+        if self.blk.0 == ZERO_BLOCK {
+            if !read_block(&mut self.r, &mut self.blk)? {
+                return Ok(None); // EOF is okay here; exactly 1 block read
+            }
+            if self.blk.0 == ZERO_BLOCK {
+                return Ok(None); // normal EOF; exactly 2 block read
+            }
+            return Err(new_header_error()); // Zero block and then non-zero block
+        }
+
+        // Verify the header matches a known format.
+        let format = self.blk.get_format();
+        if format == Format::UNKNOWN {
+            return Err(new_header_error());
+        }
+
+        let mut p = Parser::default();
+        let blk = &mut self.blk;
+        let mut hdr = Header {
+            // Unpack the V7 header.
+            typeflag: *blk.type_flag(),
+            name: p.parse_string(blk.name()),
+            linkname: p.parse_string(blk.link_name()),
+            size: p.parse_numeric(blk.size()),
+            mode: p.parse_numeric(blk.mode()),
+            uid: p.parse_numeric(blk.uid()) as isize,
+            gid: p.parse_numeric(blk.gid()) as isize,
+            mod_time: time::unix(p.parse_numeric(blk.mod_time()), 0),
+            ..Default::default()
+        };
+
+        // Unpack format specific fields.
+        if format != Format::V7 {
+            hdr.uname = p.parse_string(blk.user_name());
+            hdr.gname = p.parse_string(blk.group_name());
+            hdr.devmajor = p.parse_numeric(blk.dev_major());
+            hdr.devminor = p.parse_numeric(blk.dev_minor());
+
+            let mut prefix = String::new();
+            if format.has(Format::USTAR | Format::PAX) {
+                hdr.format = format;
+                prefix = p.parse_string(blk.ustar_prefix());
+
+                // For Format detection, check if block is properly formatted since
+                // the parser is more liberal than what USTAR actually permits.
+                if blk.0.iter().any(|&c| c >= 0x80) {
+                    hdr.format = Format::UNKNOWN; // Non-ASCII characters in block.
+                }
+                let nul = |b: &mut [u8]| b[b.len() - 1] == 0;
+                if !(nul(blk.size())
+                    && nul(blk.mode())
+                    && nul(blk.uid())
+                    && nul(blk.gid())
+                    && nul(blk.mod_time())
+                    && nul(blk.dev_major())
+                    && nul(blk.dev_minor()))
+                {
+                    hdr.format = Format::UNKNOWN; // Numeric fields must end in NUL
+                }
+            } else if format.has(Format::STAR) {
+                prefix = p.parse_string(blk.star_prefix());
+                hdr.access_time = time::unix(p.parse_numeric(blk.star_access_time()), 0);
+                hdr.change_time = time::unix(p.parse_numeric(blk.star_change_time()), 0);
+            } else if format.has(Format::GNU) {
+                hdr.format = format;
+                let mut p2 = Parser::default();
+                if blk.gnu_access_time()[0] != 0 {
+                    hdr.access_time = time::unix(p2.parse_numeric(blk.gnu_access_time()), 0);
+                }
+                if blk.gnu_change_time()[0] != 0 {
+                    hdr.change_time = time::unix(p2.parse_numeric(blk.gnu_change_time()), 0);
+                }
+
+                // Prior to Go1.8, the Go Writer had a bug where it would output
+                // an invalid tar file in certain rare situations because the logic
+                // incorrectly believed that the old GNU format had a prefix field.
+                // This is wrong and leads to an output file that mangles the
+                // atime and ctime fields, which are often left unused.
+                //
+                // In order to continue reading tar files created by such buggy
+                // writers, we skeptically parse the atime and ctime fields.
+                // If we are unable to parse them and the prefix field looks like
+                // an ASCII string, then we fallback on treating these fields as
+                // the USTAR prefix field.
+                //
+                // Note that this will not use the fallback logic for all possible
+                // files generated by such writers. If the generated file
+                // happened to have a prefix field that parses as valid
+                // atime and ctime fields (e.g., when they are valid octal strings),
+                // then it is impossible to distinguish between a valid GNU file
+                // and an invalid one.
+                if p2.err {
+                    hdr.access_time = time::Time::default();
+                    hdr.change_time = time::Time::default();
+                    let s = p.parse_string(blk.ustar_prefix());
+                    if is_ascii(&s) {
+                        prefix = s;
+                    }
+                    hdr.format = Format::UNKNOWN; // Buggy file is not GNU
+                }
+            }
+            if !prefix.is_empty() {
+                hdr.name = prefix + "/" + &hdr.name;
+            }
+        }
+        if p.err {
+            return Err(new_header_error());
+        }
+        Ok(Some(hdr))
+    }
+
+    /// read_old_gnu_sparse_map reads the sparse map from the old GNU sparse format.
+    /// The sparse map is stored in the tar header if it's small enough.
+    /// If it's larger than four entries, then one or more extension headers are used
+    /// to store the rest of the sparse map.
+    ///
+    /// The Header::size does not reflect the size of any extended headers used.
+    /// Thus, this function will read from the raw reader to fetch extra headers.
+    /// This method mutates self.blk in the process.
+    fn read_old_gnu_sparse_map(
+        &mut self,
+        hdr: &mut Header,
+    ) -> std::io::Result<Option<Vec<SparseEntry>>> {
+        // Make sure that the input format is GNU.
+        // Unfortunately, the STAR format also has a sparse header format that uses
+        // the same type flag but has a completely different layout.
+        if self.blk.get_format() != Format::GNU {
+            return Err(new_header_error());
+        }
+        hdr.format.may_only_be(Format::GNU);
+
+        let mut p = Parser::default();
+        hdr.size = p.parse_numeric(self.blk.gnu_real_size());
+        if p.err {
+            return Err(new_header_error());
+        }
+        let mut spd = Vec::new();
+        let mut s = self.blk.gnu_sparse();
+        loop {
+            for i in 0..s.max_entries() {
+                // This termination condition is identical to GNU and BSD tar.
+                if s.offset(i)[0] == 0x00 {
+                    break; // Don't return, need to process extended headers (even if empty)
+                }
+                let offset = p.parse_numeric(s.offset(i));
+                let length = p.parse_numeric(s.length(i));
+                if p.err {
+                    return Err(new_header_error());
+                }
+                spd.push(SparseEntry { offset, length });
+            }
+
+            if *s.is_extended() > 0 {
+                // There are more entries. Read an extension header and parse its entries.
+                if !read_block(&mut self.r, &mut self.blk)? {
+                    return Err(errors::new_unexpected_eof());
+                }
+                s = self.blk.as_sparse();
+                continue;
+            }
+            return Ok(Some(spd)); // Done
+        }
+    }
+
+    /// read_gnu_sparse_map_1x0 reads the sparse map as stored in GNU's PAX sparse format
+    /// version 1.0. The format of the sparse map consists of a series of
+    /// newline-terminated numeric fields. The first field is the number of entries
+    /// and is always present. Following this are the entries, consisting of two
+    /// fields (offset, length). This function must stop reading at the end
+    /// boundary of the block containing the last newline.
+    ///
+    /// Note that the GNU manual says that numeric values should be encoded in octal
+    /// format. However, the GNU tar utility itself outputs these values in decimal.
+    /// As such, this library treats values as being encoded in decimal.
+    fn read_gnu_sparse_map_1x0(&mut self) -> std::io::Result<Vec<SparseEntry>> {
+        let mut cnt_newline = 0;
+        let mut buf: Vec<u8> = Vec::new();
+        let mut pos = 0;
+        let mut blk = [0; BLOCK_SIZE];
+
+        // feed_tokens copies data in blocks from the current file into buf until
+        // there are at least n newlines in buf. It will not read more blocks
+        // than needed.
+        let mut feed_tokens = |tr: &mut Self, buf: &mut Vec<u8>, n: usize| {
+            while cnt_newline < n {
+                if tr.read_reg_full(&mut blk)? < BLOCK_SIZE {
+                    return Err(errors::new_unexpected_eof());
+                }
+                buf.extend_from_slice(&blk);
+                cnt_newline += blk.iter().filter(|&&c| c == b'\n').count();
+            }
+            cnt_newline -= n;
+            Ok(())
+        };
+
+        // next_token gets the next token delimited by a newline. This assumes that
+        // at least one newline exists in the buffer.
+        let mut next_token = |buf: &[u8]| {
+            let end = pos + buf[pos..].iter().position(|&c| c == b'\n').unwrap();
+            let tok = String::from_utf8_lossy(&buf[pos..end]).into_owned();
+            pos = end + 1;
+            tok
+        };
+
+        // Parse for the number of entries.
+        // Use integer overflow resistant math to check this.
+        feed_tokens(self, &mut buf, 1)?;
+        let num_entries = next_token(&buf)
+            .parse::<usize>()
+            .ok()
+            .filter(|n| n.checked_mul(2).is_some())
+            .ok_or_else(new_header_error)?;
+
+        // Parse for all member entries.
+        // num_entries is trusted after this since a potential attacker must have
+        // committed resources proportional to what this library used.
+        feed_tokens(self, &mut buf, 2 * num_entries)?;
+        let mut spd = Vec::with_capacity(num_entries);
+        for _ in 0..num_entries {
+            let offset = next_token(&buf).parse::<i64>();
+            let length = next_token(&buf).parse::<i64>();
+            let (Ok(offset), Ok(length)) = (offset, length) else {
+                return Err(new_header_error());
+            };
+            spd.push(SparseEntry { offset, length });
+        }
+        Ok(spd)
+    }
+
+    /// read_special_file reads the content of a special file (PAX header,
+    /// GNU long name or link), which must not exceed MAX_SPECIAL_FILE_SIZE.
+    fn read_special_file(&mut self) -> std::io::Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        self.take(MAX_SPECIAL_FILE_SIZE as u64 + 1)
+            .read_to_end(&mut buf)?;
+        if buf.len() > MAX_SPECIAL_FILE_SIZE {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                ERR_FIELD_TOO_LONG,
+            ));
+        }
+        Ok(buf)
+    }
+
+    /// read_reg reads from the data section of the current file entry,
+    /// reporting an unexpected EOF if the archive ends before it does.
+    fn read_reg(&mut self, b: &mut [u8]) -> std::io::Result<usize> {
+        let n = b.len().min(self.nb as usize);
+        if n == 0 {
+            return Ok(0);
+        }
+        let n = self.r.read(&mut b[..n])?;
+        if n == 0 {
+            return Err(errors::new_unexpected_eof());
+        }
+        self.nb -= n as i64;
+        Ok(n)
+    }
+
+    /// read_reg_full is like read_reg, but reads until b is full or the end
+    /// of the data section is reached.
+    fn read_reg_full(&mut self, b: &mut [u8]) -> std::io::Result<usize> {
+        let mut n = 0;
+        while n < b.len() {
+            let nn = self.read_reg(&mut b[n..])?;
+            if nn == 0 {
+                break;
+            }
+            n += nn;
+        }
+        Ok(n)
+    }
+
+    /// logical_remaining returns the number of bytes remaining in the
+    /// current sparse file.
+    fn logical_remaining(&self) -> i64 {
+        self.sph[self.sph.len() - 1].end_offset() - self.pos
+    }
+
+    /// read_sparse reads from the current sparse file, filling the holes with zeros.
+    /// The first error encountered after some bytes were read is saved and
+    /// returned by the next read.
+    fn read_sparse(&mut self, b: &mut [u8]) -> std::io::Result<usize> {
+        let n = b.len().min(self.logical_remaining() as usize);
+        let b = &mut b[..n];
+
+        let mut n = 0;
+        let mut res = Ok(());
+        while n < b.len() {
+            let hole = self.sph[self.sph_idx];
+            let (hole_start, hole_end) = (hole.offset, hole.end_offset());
+            let nf; // Bytes read in fragment
+            if self.pos < hole_start {
+                // In a data fragment
+                let bf_len = (b.len() - n).min((hole_start - self.pos) as usize);
+                match self.read_reg_full(&mut b[n..n + bf_len]) {
+                    Ok(nn) => {
+                        nf = nn;
+                        if nn < bf_len {
+                            // Less data in dense file than sparse file
+                            res = Err(std::io::Error::new(
+                                std::io::ErrorKind::InvalidData,
+                                ERR_MISS_DATA,
+                            ));
+                        }
+                    }
+                    Err(err) => {
+                        nf = 0;
+                        res = Err(err);
+                    }
+                }
+            } else {
+                // In a hole fragment
+                nf = (b.len() - n).min((hole_end - self.pos) as usize);
+                b[n..n + nf].fill(0);
+            }
+            n += nf;
+            self.pos += nf as i64;
+            if self.pos >= hole_end && self.sph_idx + 1 < self.sph.len() {
+                self.sph_idx += 1; // Ensure last fragment always remains
+            }
+            if res.is_err() {
+                break;
+            }
+        }
+
+        if res.is_ok() && self.logical_remaining() == 0 && self.nb > 0 {
+            // More data in dense file than sparse file
+            res = Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                ERR_UNREF_DATA,
+            ));
+        }
+        match res {
+            Ok(()) => Ok(n),
+            Err(err) if n == 0 => Err(err),
+            Err(err) => {
+                self.err = Some(err);
+                Ok(n)
+            }
+        }
+    }
+}
+
+impl<Input: std::io::Read> std::io::Read for Reader<Input> {
+    /// read reads from the current file in the tar archive.
+    /// It returns Ok(0) when it reaches the end of that file,
+    /// until next is called to advance to the next file.
+    ///
+    /// If the current file is sparse, then the regions marked as a hole
+    /// are read back as NUL-bytes.
+    ///
+    /// Calling read on special types like TYPE_LINK, TYPE_SYMLINK, TYPE_CHAR,
+    /// TYPE_BLOCK, TYPE_DIR, and TYPE_FIFO returns Ok(0) regardless of what
+    /// the Header::size claims.
+    fn read(&mut self, b: &mut [u8]) -> std::io::Result<usize> {
+        if let Some(err) = &self.err {
+            return Err(errors::copy_stdio_error(err));
+        }
+        let res = if self.sph.is_empty() {
+            self.read_reg(b)
+        } else {
+            self.read_sparse(b)
+        };
+        if let Err(err) = &res {
+            self.err = Some(errors::copy_stdio_error(err));
+        }
+        res
+    }
+}
+
+/// merge_pax merges pax_hdrs into hdr for all relevant fields of Header.
+fn merge_pax(hdr: &mut Header, pax_hdrs: BTreeMap<String, String>) -> std::io::Result<()> {
+    for (k, v) in &pax_hdrs {
+        if v.is_empty() {
+            continue; // Keep the original USTAR value
+        }
+        let ok = match k.as_str() {
+            PAX_PATH => {
+                hdr.name = v.clone();
+                true
+            }
+            PAX_LINKPATH => {
+                hdr.linkname = v.clone();
+                true
+            }
+            PAX_UNAME => {
+                hdr.uname = v.clone();
+                true
+            }
+            PAX_GNAME => {
+                hdr.gname = v.clone();
+                true
+            }
+            PAX_UID => v.parse::<i64>().map(|id| hdr.uid = id as isize).is_ok(), // Integer overflow possible
+            PAX_GID => v.parse::<i64>().map(|id| hdr.gid = id as isize).is_ok(), // Integer overflow possible
+            PAX_ATIME => parse_pax_time(v).map(|t| hdr.access_time = t).is_ok(),
+            PAX_MTIME => parse_pax_time(v).map(|t| hdr.mod_time = t).is_ok(),
+            PAX_CTIME => parse_pax_time(v).map(|t| hdr.change_time = t).is_ok(),
+            PAX_SIZE => v.parse::<i64>().map(|n| hdr.size = n).is_ok(),
+            _ => {
+                if let Some(name) = k.strip_prefix(PAX_SCHILY_XATTR) {
+                    hdr.xattrs.insert(name.to_string(), v.clone());
+                }
+                true
+            }
+        };
+        if !ok {
+            return Err(new_header_error());
+        }
+    }
+    hdr.pax_records = pax_hdrs;
+    Ok(())
+}
+
+/// read_gnu_sparse_map_0x1 reads the sparse map as stored in GNU's PAX sparse format
+/// version 0.1. The sparse map is stored in the PAX headers.
+fn read_gnu_sparse_map_0x1(
+    pax_hdrs: &BTreeMap<String, String>,
+) -> std::io::Result<Vec<SparseEntry>> {
+    // Get number of entries.
+    // Use integer overflow resistant math to check this.
+    let num_entries = pax_hdrs
+        .get(PAX_GNU_SPARSE_NUM_BLOCKS)
+        .and_then(|s| s.parse::<usize>().ok())
+        .filter(|n| n.checked_mul(2).is_some())
+        .ok_or_else(new_header_error)?;
+
+    // There should be two numbers in sparse_map for each entry.
+    let sparse_map = pax_hdrs.get(PAX_GNU_SPARSE_MAP).map_or("", |s| s.as_str());
+    let sparse_map: Vec<&str> = if sparse_map.is_empty() {
+        Vec::new()
+    } else {
+        sparse_map.split(',').collect()
+    };
+    if sparse_map.len() != 2 * num_entries {
+        return Err(new_header_error());
+    }
+
+    // Loop through the entries in the sparse map.
+    // num_entries is trusted now.
+    let mut spd = Vec::with_capacity(num_entries);
+    for pair in sparse_map.chunks(2) {
+        let (Ok(offset), Ok(length)) = (pair[0].parse::<i64>(), pair[1].parse::<i64>()) else {
+            return Err(new_header_error());
+        };
+        spd.push(SparseEntry { offset, length });
+    }
+    Ok(spd)
+}
+
+/// read_block reads a whole block from r. It returns false if EOF is hit
+/// before any bytes are read, and an unexpected EOF error if it is hit
+/// in the middle of the block.
+fn read_block<R: std::io::Read + ?Sized>(r: &mut R, blk: &mut Block) -> std::io::Result<bool> {
+    match try_read_full(r, &mut blk.0)? {
+        0 => Ok(false),
+        BLOCK_SIZE => Ok(true),
+        _ => Err(errors::new_unexpected_eof()),
+    }
+}
+
+/// try_read_full is like read_exact except it returns the number of bytes read
+/// when EOF is hit before b is full.
+fn try_read_full<R: std::io::Read + ?Sized>(r: &mut R, b: &mut [u8]) -> std::io::Result<usize> {
+    let mut n = 0;
+    while n < b.len() {
+        match r.read(&mut b[n..]) {
+            Ok(0) => break,
+            Ok(nn) => n += nn,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(n)
+}
+
+/// discard skips n bytes in r, reporting an error if unable to do so.
+fn discard<R: std::io::Read + ?Sized>(r: &mut R, n: i64) -> std::io::Result<()> {
+    let n = n as u64;
+    let copied = std::io::copy(&mut r.take(n), &mut std::io::sink())?;
+    if copied < n {
+        return Err(errors::new_unexpected_eof());
+    }
+    Ok(())
+}
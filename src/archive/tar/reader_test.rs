@@ -0,0 +1,287 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2009 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::{
+    Format, Header, Reader, SparseEntry, ERR_HEADER, TYPE_GNU_SPARSE, TYPE_REG, TYPE_SYMLINK,
+};
+use crate::compress::gzip;
+use crate::time;
+use std::io::Read;
+
+const LONG_NAME: &str = "longname/longname/longname/longname/longname/longname/longname/longname/longname/longname/longname/longname/file.txt";
+
+/// read_entries reads all the entries of the tar archive in r,
+/// returning the headers along with the file contents.
+fn read_entries(r: &mut impl Read) -> std::io::Result<Vec<(Header, Vec<u8>)>> {
+    let mut tr = Reader::new(r);
+    let mut entries = Vec::new();
+    while let Some(hdr) = tr.next()? {
+        let mut data = Vec::new();
+        tr.read_to_end(&mut data)?;
+        entries.push((hdr, data));
+    }
+    Ok(entries)
+}
+
+fn read_file(filename: &str) -> Vec<(Header, Vec<u8>)> {
+    let mut f = std::fs::File::open(filename).unwrap();
+    read_entries(&mut f).unwrap()
+}
+
+fn check_long_names(entries: &[(Header, Vec<u8>)], format: Format) {
+    assert_eq!(entries.len(), 2);
+    let (hdr, data) = &entries[0];
+    assert_eq!(
+        *hdr,
+        Header {
+            typeflag: TYPE_REG,
+            name: LONG_NAME.to_string(),
+            size: 5,
+            mode: 0o644,
+            uid: 1000,
+            gid: 1000,
+            mod_time: time::unix(1500000000, 0),
+            format,
+            ..Default::default()
+        }
+    );
+    assert_eq!(data, b"hello");
+    let (hdr, data) = &entries[1];
+    assert_eq!(hdr.typeflag, TYPE_SYMLINK);
+    assert_eq!(hdr.name, "link");
+    assert_eq!(hdr.linkname, LONG_NAME);
+    assert_eq!(hdr.size, 0);
+    assert_eq!(hdr.format, format);
+    assert!(data.is_empty());
+}
+
+#[test]
+fn test_reader_ustar() {
+    let entries = read_file("src/archive/tar/testdata/ustar.tar");
+    assert_eq!(entries.len(), 2);
+    let want = [
+        ("small.txt", b"Kilts" as &[u8]),
+        ("small2.txt", b"Google.com\n" as &[u8]),
+    ];
+    for ((hdr, data), (name, contents)) in entries.iter().zip(want) {
+        assert_eq!(
+            *hdr,
+            Header {
+                typeflag: TYPE_REG,
+                name: name.to_string(),
+                size: contents.len() as i64,
+                mode: 0o644,
+                uid: 1000,
+                gid: 1000,
+                mod_time: time::unix(1500000000, 0),
+                format: Format::USTAR,
+                ..Default::default()
+            }
+        );
+        assert_eq!(data, contents);
+    }
+}
+
+#[test]
+fn test_reader_gnu_long_names() {
+    let entries = read_file("src/archive/tar/testdata/gnu-long.tar");
+    check_long_names(&entries, Format::GNU);
+}
+
+#[test]
+fn test_reader_pax_long_names() {
+    let entries = read_file("src/archive/tar/testdata/pax-long.tar");
+    assert_eq!(entries[0].0.pax_records["path"], LONG_NAME);
+    assert_eq!(entries[1].0.pax_records["linkpath"], LONG_NAME);
+    let entries: Vec<(Header, Vec<u8>)> = entries
+        .into_iter()
+        .map(|(hdr, data)| {
+            let hdr = Header {
+                pax_records: Default::default(),
+                ..hdr
+            };
+            (hdr, data)
+        })
+        .collect();
+    check_long_names(&entries, Format::PAX);
+}
+
+#[test]
+fn test_reader_pax_xattrs() {
+    let entries = read_file("src/archive/tar/testdata/pax-xattr.tar");
+    assert_eq!(entries.len(), 1);
+    let (hdr, data) = &entries[0];
+    assert_eq!(hdr.name, "small.txt");
+    assert_eq!(hdr.format, Format::PAX);
+    assert_eq!(hdr.mod_time, time::unix(1500000000, 250000000));
+    assert_eq!(hdr.xattrs.len(), 2);
+    assert_eq!(hdr.xattrs["user.key"], "value");
+    assert_eq!(hdr.xattrs["security.selinux"], "unconfined_u");
+    assert_eq!(hdr.pax_records["SCHILY.xattr.user.key"], "value");
+    assert_eq!(hdr.pax_records["mtime"], "1500000000.25");
+    assert_eq!(data, b"Kilts");
+}
+
+#[test]
+fn test_reader_sparse() {
+    let tests = [
+        ("gnu-sparse.tar", TYPE_GNU_SPARSE, Format::GNU),
+        ("pax-sparse-0.0.tar", TYPE_REG, Format::PAX),
+        ("pax-sparse-0.1.tar", TYPE_REG, Format::PAX),
+        ("pax-sparse-1.0.tar", TYPE_REG, Format::PAX),
+    ];
+    let mut want = vec![0; 400000];
+    want[..3].copy_from_slice(b"abc");
+    want[200000..200003].copy_from_slice(b"xyz");
+    for (file, typeflag, format) in tests {
+        let entries = read_file(&format!("src/archive/tar/testdata/{}", file));
+        assert_eq!(entries.len(), 1, "{}", file);
+        let (hdr, data) = &entries[0];
+        assert_eq!(hdr.name, "sparse", "{}", file);
+        assert_eq!(hdr.typeflag, typeflag, "{}", file);
+        assert_eq!(hdr.format, format, "{}", file);
+        assert_eq!(hdr.size, 400000, "{}", file);
+        assert_eq!(
+            hdr.sparse_holes,
+            vec![
+                SparseEntry {
+                    offset: 4096,
+                    length: 192512
+                },
+                SparseEntry {
+                    offset: 200704,
+                    length: 199296
+                },
+            ],
+            "{}",
+            file
+        );
+        assert!(*data == want, "{}: mismatching sparse file contents", file);
+    }
+}
+
+#[test]
+fn test_reader_gzip() {
+    let f = std::fs::File::open("src/archive/tar/testdata/gnu-long.tar.gz").unwrap();
+    let mut br = std::io::BufReader::new(f);
+    let mut zr = gzip::Reader::new(&mut br).unwrap();
+    let entries = read_entries(&mut zr).unwrap();
+    check_long_names(&entries, Format::GNU);
+}
+
+#[test]
+fn test_reader_partial_read() {
+    // Reading only a part of an entry skips the rest of it on next.
+    let mut f = std::fs::File::open("src/archive/tar/testdata/ustar.tar").unwrap();
+    let mut tr = Reader::new(&mut f);
+    let hdr = tr.next().unwrap().unwrap();
+    assert_eq!(hdr.name, "small.txt");
+    let mut buf = [0; 2];
+    tr.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"Ki");
+    let hdr = tr.next().unwrap().unwrap();
+    assert_eq!(hdr.name, "small2.txt");
+    let mut buf = [0; 4];
+    tr.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"Goog");
+    assert!(tr.next().unwrap().is_none());
+}
+
+#[test]
+fn test_reader_truncated() {
+    let input = std::fs::read("src/archive/tar/testdata/ustar.tar").unwrap();
+    struct Test {
+        cnt: usize,
+        header_ok: bool, // Whether the first header can be read
+        data_ok: bool,   // Whether the first file can be read
+        entries: usize,  // Number of entries read until io::EOF
+    }
+    let tests = [
+        Test {
+            cnt: 0,
+            header_ok: false,
+            data_ok: false,
+            entries: 0,
+        },
+        Test {
+            cnt: 100,
+            header_ok: false,
+            data_ok: false,
+            entries: 0,
+        },
+        Test {
+            cnt: 512,
+            header_ok: true,
+            data_ok: false,
+            entries: 0,
+        },
+        Test {
+            cnt: 512 + 3,
+            header_ok: true,
+            data_ok: false,
+            entries: 0,
+        },
+        Test {
+            cnt: 1024,
+            header_ok: true,
+            data_ok: true,
+            entries: 1,
+        },
+        Test {
+            cnt: 2048,
+            header_ok: true,
+            data_ok: true,
+            entries: 2,
+        },
+        Test {
+            cnt: input.len(),
+            header_ok: true,
+            data_ok: true,
+            entries: 2,
+        },
+    ];
+    for v in tests {
+        let mut r = &input[..v.cnt];
+        let mut tr = Reader::new(&mut r);
+        match tr.next() {
+            Ok(Some(_)) => assert!(v.header_ok, "cnt {}: unexpected header", v.cnt),
+            Ok(None) => {
+                assert_eq!(v.cnt, 0, "cnt {}: unexpected EOF", v.cnt);
+                continue;
+            }
+            Err(err) => {
+                assert!(!v.header_ok, "cnt {}: unexpected error {}", v.cnt, err);
+                assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+                continue;
+            }
+        }
+        let mut data = Vec::new();
+        match tr.read_to_end(&mut data) {
+            Ok(_) => assert!(v.data_ok, "cnt {}: unexpected data", v.cnt),
+            Err(err) => {
+                assert!(!v.data_ok, "cnt {}: unexpected error {}", v.cnt, err);
+                assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+                continue;
+            }
+        }
+        let mut r = &input[..v.cnt];
+        let entries = read_entries(&mut r).unwrap();
+        assert_eq!(entries.len(), v.entries, "cnt {}", v.cnt);
+    }
+}
+
+#[test]
+fn test_reader_invalid_checksum() {
+    let mut input = std::fs::read("src/archive/tar/testdata/ustar.tar").unwrap();
+    input[0] ^= 0x01; // Corrupt the name of the first file
+    let mut r = input.as_slice();
+    let mut tr = Reader::new(&mut r);
+    let err = tr.next().unwrap_err();
+    assert_eq!(err.to_string(), ERR_HEADER);
+
+    // The error is persistent.
+    let err = tr.next().unwrap_err();
+    assert_eq!(err.to_string(), ERR_HEADER);
+}
@@ -0,0 +1,323 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2016 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::common::{new_header_error, PAX_GNAME, PAX_LINKPATH, PAX_PATH, PAX_UNAME};
+use crate::time;
+
+/// has_nul reports whether the NUL character exists within s.
+pub(super) fn has_nul(s: &str) -> bool {
+    s.contains('\x00')
+}
+
+/// is_ascii reports whether the input is an ASCII C-style string.
+pub(super) fn is_ascii(s: &str) -> bool {
+    s.bytes().all(|c| c < 0x80 && c != 0x00)
+}
+
+/// to_ascii converts the input to an ASCII C-style string.
+/// This is a best effort conversion, so invalid characters are dropped.
+pub(super) fn to_ascii(s: &str) -> String {
+    s.chars().filter(|&c| c < '\u{80}' && c != '\x00').collect()
+}
+
+/// Parser parses the fields of a tar header.
+/// err is set if any of the parsed fields were invalid.
+#[derive(Default)]
+pub(super) struct Parser {
+    pub(super) err: bool,
+}
+
+/// Formatter formats the fields of a tar header.
+/// err is set if any of the values did not fit into its field.
+#[derive(Default)]
+pub(super) struct Formatter {
+    pub(super) err: bool,
+}
+
+impl Parser {
+    /// parse_string parses bytes as a NUL-terminated C-style string.
+    /// If a NUL byte is not found then the whole slice is returned as a string.
+    pub(super) fn parse_string(&self, b: &[u8]) -> String {
+        let n = b.iter().position(|&c| c == 0).unwrap_or(b.len());
+        String::from_utf8_lossy(&b[..n]).into_owned()
+    }
+
+    /// parse_numeric parses the input as being encoded in either base-256 or octal.
+    /// This function may return negative numbers.
+    /// If parsing fails or an integer overflow occurs, err will be set.
+    pub(super) fn parse_numeric(&mut self, b: &[u8]) -> i64 {
+        // Check for base-256 (binary) format first.
+        // If the first bit is set, then all following bits constitute a two's
+        // complement encoded number in big-endian byte order.
+        if !b.is_empty() && b[0] & 0x80 != 0 {
+            // Handling negative numbers relies on the following identity:
+            //	-a-1 == !a
+            //
+            // If the number is negative, we use an inversion mask to invert the
+            // data bytes and treat the value as an unsigned number.
+            let inv = if b[0] & 0x40 != 0 { 0xff } else { 0x00 };
+
+            let mut x: u64 = 0;
+            for (i, &c) in b.iter().enumerate() {
+                let mut c = c ^ inv; // Inverts c only if inv is 0xff, otherwise does nothing
+                if i == 0 {
+                    c &= 0x7f; // Ignore signal bit in first byte
+                }
+                if (x >> 56) > 0 {
+                    self.err = true; // Integer overflow
+                    return 0;
+                }
+                x = x << 8 | c as u64;
+            }
+            if (x >> 63) > 0 {
+                self.err = true; // Integer overflow
+                return 0;
+            }
+            if inv == 0xff {
+                return !(x as i64);
+            }
+            return x as i64;
+        }
+
+        // Normal case is base-8 (octal) format.
+        self.parse_octal(b)
+    }
+
+    pub(super) fn parse_octal(&mut self, b: &[u8]) -> i64 {
+        // Because unused fields are filled with NULs, we need
+        // to skip leading NULs. Fields may also be padded with
+        // spaces or NULs.
+        // So we remove leading and trailing NULs and spaces to
+        // be sure.
+        let is_pad = |c: &u8| *c == b' ' || *c == 0;
+        let start = b.iter().position(|c| !is_pad(c)).unwrap_or(b.len());
+        let end = b.iter().rposition(|c| !is_pad(c)).map_or(start, |i| i + 1);
+        let b = &b[start..end];
+
+        if b.is_empty() {
+            return 0;
+        }
+        match u64::from_str_radix(&self.parse_string(b), 8) {
+            Ok(x) => x as i64,
+            Err(_) => {
+                self.err = true;
+                0
+            }
+        }
+    }
+}
+
+impl Formatter {
+    /// format_string copies s into b, NUL-terminating if possible.
+    pub(super) fn format_string(&mut self, b: &mut [u8], s: &str) {
+        let s = s.as_bytes();
+        if s.len() > b.len() {
+            self.err = true;
+        }
+        let n = s.len().min(b.len());
+        b[..n].copy_from_slice(&s[..n]);
+        if s.len() < b.len() {
+            b[s.len()] = 0;
+        }
+
+        // Some buggy readers treat regular files with a trailing slash
+        // in the V7 path field as a directory even though the full path
+        // recorded elsewhere (e.g., via PAX record) contains no trailing slash.
+        if s.len() > b.len() && b[b.len() - 1] == b'/' {
+            let mut n = b.len() - 1;
+            while n > 0 && s[n - 1] == b'/' {
+                n -= 1;
+            }
+            b[n] = 0; // Replace trailing slash with NUL terminator
+        }
+    }
+
+    /// format_numeric encodes x into b using base-8 (octal) encoding if possible.
+    /// Otherwise it will attempt to use base-256 (binary) encoding.
+    pub(super) fn format_numeric(&mut self, b: &mut [u8], x: i64) {
+        if fits_in_octal(b.len(), x) {
+            self.format_octal(b, x);
+            return;
+        }
+
+        if fits_in_base256(b.len(), x) {
+            let mut x = x;
+            for c in b.iter_mut().rev() {
+                *c = x as u8;
+                x >>= 8;
+            }
+            b[0] |= 0x80; // Highest bit indicates binary format
+            return;
+        }
+
+        self.format_octal(b, 0); // Last resort, just write zero
+        self.err = true;
+    }
+
+    pub(super) fn format_octal(&mut self, b: &mut [u8], x: i64) {
+        let mut x = x;
+        if !fits_in_octal(b.len(), x) {
+            x = 0; // Last resort, just write zero
+            self.err = true;
+        }
+
+        // Add leading zeros, but leave room for a NUL.
+        let s = format!("{:0width$o}", x, width = b.len().max(1) - 1);
+        self.format_string(b, &s);
+    }
+}
+
+/// fits_in_base256 reports whether x can be encoded into n bytes using base-256
+/// encoding. Unlike octal encoding, base-256 encoding does not require that the
+/// string ends with a NUL character. Thus, all n bytes are available for output.
+///
+/// If operating in binary mode, this assumes strict GNU binary mode; which means
+/// that the first byte can only be either 0x80 or 0xff. Thus, the first byte is
+/// equivalent to the sign bit in two's complement form.
+pub(super) fn fits_in_base256(n: usize, x: i64) -> bool {
+    let bin_bits = (n as u32).saturating_sub(1) * 8;
+    n >= 9 || (x >= -1 << bin_bits && x < 1 << bin_bits)
+}
+
+/// fits_in_octal reports whether the integer x fits in a field n-bytes long
+/// using octal encoding with the appropriate NUL terminator.
+pub(super) fn fits_in_octal(n: usize, x: i64) -> bool {
+    let oct_bits = (n as u32).saturating_sub(1) * 3;
+    x >= 0 && (n >= 22 || x < 1 << oct_bits)
+}
+
+/// parse_pax_time takes a string of the form %d.%d as described in the PAX
+/// specification. Note that this implementation allows for negative timestamps,
+/// which is allowed for by the PAX specification, but not always portable.
+pub(super) fn parse_pax_time(s: &str) -> std::io::Result<time::Time> {
+    const MAX_NANO_SECOND_DIGITS: usize = 9;
+
+    // Split string into seconds and sub-seconds parts.
+    let (ss, sn) = s.split_once('.').unwrap_or((s, ""));
+
+    // Parse the seconds.
+    let secs: i64 = ss.parse().map_err(|_| new_header_error())?;
+    if sn.is_empty() {
+        return Ok(time::unix(secs, 0)); // No sub-second values
+    }
+
+    // Parse the nanoseconds.
+    if !sn.bytes().all(|c| c.is_ascii_digit()) {
+        return Err(new_header_error());
+    }
+    let sn = if sn.len() < MAX_NANO_SECOND_DIGITS {
+        format!("{:0<width$}", sn, width = MAX_NANO_SECOND_DIGITS) // Right pad
+    } else {
+        sn[..MAX_NANO_SECOND_DIGITS].to_string() // Right truncate
+    };
+    let nsecs: i64 = sn.parse().unwrap(); // Must succeed
+    if ss.starts_with('-') {
+        return Ok(time::unix(secs, -nsecs)); // Negative correction
+    }
+    Ok(time::unix(secs, nsecs))
+}
+
+/// format_pax_time converts ts into a time of the form %d.%d as described in the
+/// PAX specification. This function is capable of negative timestamps.
+pub(super) fn format_pax_time(ts: &time::Time) -> String {
+    let (mut secs, mut nsecs) = (ts.unix(), ts.nanosecond() as i64);
+    if nsecs == 0 {
+        return secs.to_string();
+    }
+
+    // If seconds is negative, then perform correction.
+    let mut sign = "";
+    if secs < 0 {
+        sign = "-"; // Remember sign
+        secs = -(secs + 1); // Add a second to secs
+        nsecs = -(nsecs - 1_000_000_000); // Take that second away from nsecs
+    }
+    format!("{}{}.{:09}", sign, secs, nsecs)
+        .trim_end_matches('0')
+        .to_string()
+}
+
+/// parse_pax_record parses the input PAX record string into a key-value pair.
+/// If parsing is successful, it will slice off the currently read record and
+/// return the remainder as the third value.
+pub(super) fn parse_pax_record(s: &[u8]) -> std::io::Result<(String, String, &[u8])> {
+    // The size field ends at the first space.
+    let sp = s
+        .iter()
+        .position(|&c| c == b' ')
+        .ok_or_else(new_header_error)?;
+    let (n_str, rest) = (&s[..sp], &s[sp + 1..]);
+
+    // Parse the first token as a decimal integer.
+    let n = std::str::from_utf8(n_str)
+        .ok()
+        .and_then(|n| n.parse::<isize>().ok())
+        .ok_or_else(new_header_error)?;
+    if n < 5 || n as usize > s.len() {
+        return Err(new_header_error());
+    }
+    let n = n - (n_str.len() as isize + 1); // convert from index in s to index in rest
+    if n <= 0 {
+        return Err(new_header_error());
+    }
+    let n = n as usize;
+
+    // Extract everything between the space and the final newline.
+    let (rec, nl, rem) = (&rest[..n - 1], rest[n - 1], &rest[n..]);
+    if nl != b'\n' {
+        return Err(new_header_error());
+    }
+
+    // The first equals separates the key from the value.
+    let eq = rec
+        .iter()
+        .position(|&c| c == b'=')
+        .ok_or_else(new_header_error)?;
+    let k = String::from_utf8_lossy(&rec[..eq]).into_owned();
+    let v = String::from_utf8_lossy(&rec[eq + 1..]).into_owned();
+
+    if !valid_pax_record(&k, &v) {
+        return Err(new_header_error());
+    }
+    Ok((k, v, rem))
+}
+
+/// format_pax_record formats a single PAX record, prefixing it with the
+/// appropriate length.
+pub(super) fn format_pax_record(k: &str, v: &str) -> std::io::Result<String> {
+    if !valid_pax_record(k, v) {
+        return Err(new_header_error());
+    }
+
+    const PADDING: usize = 3; // Extra padding for ' ', '=', and '\n'
+    let mut size = k.len() + v.len() + PADDING;
+    size += size.to_string().len();
+    let mut record = format!("{} {}={}\n", size, k, v);
+
+    // Final adjustment if adding size field increased the record size.
+    if record.len() != size {
+        size = record.len();
+        record = format!("{} {}={}\n", size, k, v);
+    }
+    Ok(record)
+}
+
+/// valid_pax_record reports whether the key-value pair is valid where each
+/// record is formatted as `"%d %s=%s\n" % (size, key, value)`.
+///
+/// Keys and values should be UTF-8, but the number of bad writers out there
+/// forces us to be a more liberal.
+/// Thus, we only reject all keys with NUL, and only reject NULs in values
+/// for the PAX version of the USTAR string fields.
+/// The key must not contain an '=' character.
+pub(super) fn valid_pax_record(k: &str, v: &str) -> bool {
+    if k.is_empty() || k.contains('=') {
+        return false;
+    }
+    match k {
+        PAX_PATH | PAX_LINKPATH | PAX_UNAME | PAX_GNAME => !has_nul(v),
+        _ => !has_nul(k),
+    }
+}
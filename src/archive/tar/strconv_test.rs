@@ -0,0 +1,445 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2016 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::strconv::{
+    fits_in_base256, fits_in_octal, format_pax_record, format_pax_time, parse_pax_record,
+    parse_pax_time, Formatter, Parser,
+};
+use crate::time;
+
+#[test]
+fn test_fits_in_base256() {
+    let tests: &[(i64, usize, bool)] = &[
+        (1, 8, true),
+        (0, 8, true),
+        (-1, 8, true),
+        (1 << 56, 8, false),
+        ((1 << 56) - 1, 8, true),
+        (-1 << 56, 8, true),
+        ((-1 << 56) - 1, 8, false),
+        (121654, 8, true),
+        (-9849849, 8, true),
+        (i64::MAX, 9, true),
+        (0, 9, true),
+        (i64::MIN, 9, true),
+        (i64::MAX, 12, true),
+        (0, 12, true),
+        (i64::MIN, 12, true),
+    ];
+    for &(input, width, ok) in tests {
+        assert_eq!(
+            fits_in_base256(width, input),
+            ok,
+            "fits_in_base256({}, {})",
+            input,
+            width
+        );
+    }
+}
+
+#[test]
+fn test_parse_numeric() {
+    let tests: &[(&[u8], i64, bool)] = &[
+        // Test base-256 (binary) encoded values.
+        (b"", 0, true),
+        (b"\x80", 0, true),
+        (b"\x80\x00", 0, true),
+        (b"\x80\x00\x00", 0, true),
+        (b"\xbf", (1 << 6) - 1, true),
+        (b"\xbf\xff", (1 << 14) - 1, true),
+        (b"\xbf\xff\xff", (1 << 22) - 1, true),
+        (b"\xff", -1, true),
+        (b"\xff\xff", -1, true),
+        (b"\xff\xff\xff", -1, true),
+        (b"\xc0", -1 << 6, true),
+        (b"\xc0\x00", -1 << 14, true),
+        (b"\xc0\x00\x00", -1 << 22, true),
+        (
+            b"\x87\x76\xa2\x22\xeb\x8a\x72\x61",
+            537795476381659745,
+            true,
+        ),
+        (
+            b"\x80\x00\x00\x00\x07\x76\xa2\x22\xeb\x8a\x72\x61",
+            537795476381659745,
+            true,
+        ),
+        (
+            b"\xf7\x76\xa2\x22\xeb\x8a\x72\x61",
+            -615126028225187231,
+            true,
+        ),
+        (
+            b"\xff\xff\xff\xff\xf7\x76\xa2\x22\xeb\x8a\x72\x61",
+            -615126028225187231,
+            true,
+        ),
+        (b"\x80\x7f\xff\xff\xff\xff\xff\xff\xff", i64::MAX, true),
+        (b"\x80\x80\x00\x00\x00\x00\x00\x00\x00", 0, false),
+        (b"\xff\x80\x00\x00\x00\x00\x00\x00\x00", i64::MIN, true),
+        (b"\xff\x7f\xff\xff\xff\xff\xff\xff\xff", 0, false),
+        (
+            b"\xf5\xec\xd1\xc7\x7e\x5f\x26\x48\x81\x9f\x8f\x9b",
+            0,
+            false,
+        ),
+        // Test base-8 (octal) encoded values.
+        (b"0000000\x00", 0, true),
+        (b" \x0000000\x00", 0, true),
+        (b" \x0000003\x00", 3, true),
+        (b"00000000227\x00", 0o227, true),
+        (b"032033\x00 ", 0o32033, true),
+        (b"320330\x00 ", 0o320330, true),
+        (b"0000660\x00 ", 0o660, true),
+        (b"\x00 0000660\x00 ", 0o660, true),
+        (b"0123456789abcdef", 0, false),
+        (b"0123456789\x00abcdef", 0, false),
+        (b"01234567\x0089abcdef", 342391, true),
+        (b"0123\x7e\x5f\x264123", 0, false),
+    ];
+    for &(input, want, ok) in tests {
+        let mut p = Parser::default();
+        let got = p.parse_numeric(input);
+        assert_eq!(!p.err, ok, "parse_numeric({:?}) error", input);
+        if ok {
+            assert_eq!(got, want, "parse_numeric({:?})", input);
+        }
+    }
+}
+
+#[test]
+fn test_format_numeric() {
+    let tests: &[(i64, &[u8], bool)] = &[
+        // Test base-8 (octal) encoded values.
+        (0, b"0\x00", true),
+        (7, b"7\x00", true),
+        (8, b"\x80\x08", true),
+        (0o77, b"77\x00", true),
+        (0o100, b"\x80\x00\x40", true),
+        (0, b"0000000\x00", true),
+        (0o123, b"0000123\x00", true),
+        (0o7654321, b"7654321\x00", true),
+        (0o7777777, b"7777777\x00", true),
+        (0o10000000, b"\x80\x00\x00\x00\x00\x20\x00\x00", true),
+        (0, b"00000000000\x00", true),
+        (0o77777777777, b"77777777777\x00", true),
+        // Test base-256 (binary) encoded values.
+        (-1, b"\xff", true),
+        (-1, b"\xff\xff", true),
+        (-1, b"\xff\xff\xff", true),
+        (1, b"0", false),
+        (1 << 8, b"\x80\x00", false),
+        (1 << 8, b"\x80\x01\x00", true),
+        (1 << 16, b"\x80\x00\x00", false),
+        (-1, b"\xff", true),
+        (-1, b"\xff\xff", true),
+        (-1 << 8, b"\xff\xff\x00", true),
+        (-1 << 16, b"\xff\x00\x00", true),
+        (-1 << 16, b"\xff\xff\x00\x00", true),
+        (537795476381659745, b"0000000\x00", false),
+        (
+            537795476381659745,
+            b"\x80\x00\x00\x00\x07\x76\xa2\x22\xeb\x8a\x72\x61",
+            true,
+        ),
+        (
+            -615126028225187231,
+            b"\xff\xff\xff\xff\xf7\x76\xa2\x22\xeb\x8a\x72\x61",
+            true,
+        ),
+        (
+            i64::MAX,
+            b"\x80\x00\x00\x00\x7f\xff\xff\xff\xff\xff\xff\xff",
+            true,
+        ),
+        (
+            i64::MIN,
+            b"\xff\xff\xff\xff\x80\x00\x00\x00\x00\x00\x00\x00",
+            true,
+        ),
+        (i64::MAX, b"\x80\x7f\xff\xff\xff\xff\xff\xff\xff", true),
+        (i64::MIN, b"\xff\x80\x00\x00\x00\x00\x00\x00\x00", true),
+    ];
+    for &(input, want, ok) in tests {
+        let mut f = Formatter::default();
+        let mut got = vec![0; want.len()];
+        f.format_numeric(&mut got, input);
+        assert_eq!(!f.err, ok, "format_numeric({}) error", input);
+        if ok {
+            assert_eq!(got, want, "format_numeric({})", input);
+        }
+    }
+}
+
+#[test]
+fn test_fits_in_octal() {
+    let tests: &[(i64, usize, bool)] = &[
+        (-1, 1, false),
+        (-1, 2, false),
+        (-1, 3, false),
+        (0, 1, true),
+        (1, 1, false),
+        (0, 2, true),
+        (0o7, 2, true),
+        (0o7 + 1, 2, false),
+        (0, 4, true),
+        (0o777, 4, true),
+        (0o777 + 1, 4, false),
+        (0, 8, true),
+        (0o7777777, 8, true),
+        (0o7777777 + 1, 8, false),
+        (0, 12, true),
+        (0o77777777777, 12, true),
+        (0o77777777777 + 1, 12, false),
+        (i64::MAX, 22, true),
+        (0o12345670123, 12, true),
+        (0o1564164, 12, true),
+        (-0o12345670123, 12, false),
+        (-0o1564164, 12, false),
+        (-1564164, 30, false),
+    ];
+    for &(input, width, ok) in tests {
+        assert_eq!(
+            fits_in_octal(width, input),
+            ok,
+            "fits_in_octal({}, {})",
+            input,
+            width
+        );
+    }
+}
+
+#[test]
+fn test_parse_pax_time() {
+    let tests: &[(&str, (i64, i64), bool)] = &[
+        ("1350244992.023960108", (1350244992, 23960108), true),
+        ("1350244992.02396010", (1350244992, 23960100), true),
+        ("1350244992.0239601089", (1350244992, 23960108), true),
+        ("1350244992.3", (1350244992, 300000000), true),
+        ("1350244992", (1350244992, 0), true),
+        ("-1.000000001", (-1, -1e0 as i64), true),
+        ("-1.000001", (-1, -1e3 as i64), true),
+        ("-1.001000", (-1, -1e6 as i64), true),
+        ("-1", (-1, 0), true),
+        ("-1.999000", (-1, -1e9 as i64 + 1e6 as i64), true),
+        ("-1.999999999", (-1, -999999999), true),
+        ("0.000000001", (0, 1), true),
+        ("0.000001", (0, 1_000), true),
+        ("0.001000", (0, 1_000_000), true),
+        ("0", (0, 0), true),
+        ("0.999000", (0, 999_000_000), true),
+        ("0.999999999", (0, 999999999), true),
+        ("1.000000001", (1, 1), true),
+        ("1.000001", (1, 1_000), true),
+        ("1.001000", (1, 1_000_000), true),
+        ("1", (1, 0), true),
+        ("1.999000", (1, 999_000_000), true),
+        ("1.999999999", (1, 999999999), true),
+        ("-1350244992.023960108", (-1350244992, -23960108), true),
+        ("-1350244992", (-1350244992, 0), true),
+        ("", (0, 0), false),
+        ("0", (0, 0), true),
+        ("1.", (1, 0), true),
+        ("0.0", (0, 0), true),
+        (".5", (0, 0), false),
+        ("-1.3", (-1, -300000000), true),
+        ("-0.3", (0, -300000000), true),
+        ("-1.-3", (0, 0), false),
+        ("-1.3.", (0, 0), false),
+        ("1.+3", (0, 0), false),
+        ("1.a", (0, 0), false),
+    ];
+    for &(input, (sec, nsec), ok) in tests {
+        match parse_pax_time(input) {
+            Ok(ts) => {
+                assert!(ok, "parse_pax_time({:?}): unexpected success", input);
+                assert_eq!(ts, time::unix(sec, nsec), "parse_pax_time({:?})", input);
+            }
+            Err(_) => assert!(!ok, "parse_pax_time({:?}): unexpected error", input),
+        }
+    }
+}
+
+#[test]
+fn test_format_pax_time() {
+    let tests: &[((i64, i64), &str)] = &[
+        ((1350244992, 0), "1350244992"),
+        ((1350244992, 300000000), "1350244992.3"),
+        ((1350244992, 23960100), "1350244992.0239601"),
+        ((1350244992, 23960108), "1350244992.023960108"),
+        ((0, 1), "0.000000001"),
+        ((0, 999999999), "0.999999999"),
+        ((1, 0), "1"),
+        ((1, 1_000), "1.000001"),
+        ((-1, 0), "-1"),
+        ((-1, -1), "-1.000000001"),
+        ((-1, -999999999), "-1.999999999"),
+        ((-1350244992, 0), "-1350244992"),
+        ((-1350244992, -300000000), "-1350244992.3"),
+        ((-1350244992, -23960100), "-1350244992.0239601"),
+    ];
+    for &((sec, nsec), want) in tests {
+        let got = format_pax_time(&time::unix(sec, nsec));
+        assert_eq!(got, want, "format_pax_time({}, {})", sec, nsec);
+    }
+}
+
+#[test]
+fn test_parse_pax_record() {
+    let med_name = "CD".repeat(50);
+    let long_name = "AB".repeat(100);
+    let rec_med = format!("110 path={}\n", med_name);
+    let rec_long = format!("210 path={}\n", long_name);
+    // (input, residual, (key, value), ok)
+    type Test<'a> = (&'a [u8], &'a [u8], (&'a str, String), bool);
+    let tests: Vec<Test> = vec![
+        (b"6 k=v\n\n", b"\n", ("k", "v".to_string()), true),
+        (
+            b"19 path=/etc/hosts\n",
+            b"",
+            ("path", "/etc/hosts".to_string()),
+            true,
+        ),
+        (
+            b"210 path=" as &[u8],
+            b"",
+            ("", String::new()),
+            false,
+        ),
+        (
+            rec_med.as_bytes(),
+            b"",
+            ("path", med_name.clone()),
+            true,
+        ),
+        (
+            rec_long.as_bytes(),
+            b"",
+            ("path", long_name.clone()),
+            true,
+        ),
+        (
+            "3 \u{2603}=\u{2603}\n".as_bytes(),
+            b"",
+            ("", String::new()),
+            false,
+        ),
+        (
+            "11 \u{2603}=\u{2603}\n".as_bytes(),
+            b"",
+            ("\u{2603}", "\u{2603}".to_string()),
+            true,
+        ),
+        (
+            b"30 mtime=1432668921.098285006\n30 ctime=2147483649.15163319",
+            b"30 ctime=2147483649.15163319",
+            ("mtime", "1432668921.098285006".to_string()),
+            true,
+        ),
+        (b"0000000000000000000000000000000030 mtime=1432668921.098285006\n30 ctime=2147483649.15163319", b"", ("", String::new()), false),
+        (b"0 k=v\n", b"", ("", String::new()), false),
+        (b"1 k=v\n", b"", ("", String::new()), false),
+        (b"6 k=v\n", b"", ("k", "v".to_string()), true),
+        (b"6 k=v\n" as &[u8], b"", ("k", "v".to_string()), true),
+        (b"6 k=v \n", b"", ("", String::new()), false),
+        (b"5 k=v\n", b"", ("", String::new()), false),
+        (b"7 k=v\n", b"", ("", String::new()), false),
+        (b"-1 k=v\n", b"", ("", String::new()), false),
+        (b"6 k=\n", b"", ("", String::new()), false),
+        (b"5 k=\n", b"", ("k", String::new()), true),
+        (b"6 =v\n", b"", ("", String::new()), false),
+        (b"3 =\n", b"", ("", String::new()), false),
+        (b"16 linkpath=\x00\n", b"", ("", String::new()), false),
+        (b"16 linkpath=v\x00\n", b"", ("", String::new()), false),
+        (b"20 foo\x00bar=baz\n", b"", ("", String::new()), false),
+        (b"15 foo=bar\x00baz\n", b"", ("foo", "bar\x00baz".to_string()), true),
+    ];
+    for (input, want_res, (want_key, want_val), ok) in tests {
+        match parse_pax_record(input) {
+            Ok((key, val, res)) => {
+                assert!(ok, "parse_pax_record({:?}): unexpected success", input);
+                assert_eq!(key, want_key, "parse_pax_record({:?})", input);
+                assert_eq!(val, want_val, "parse_pax_record({:?})", input);
+                assert_eq!(res, want_res, "parse_pax_record({:?})", input);
+            }
+            Err(_) => assert!(!ok, "parse_pax_record({:?}): unexpected error", input),
+        }
+    }
+}
+
+#[test]
+fn test_format_pax_record() {
+    let med_name = "CD".repeat(50);
+    let long_name = "AB".repeat(100);
+    let tests: Vec<(&str, String, String, bool)> = vec![
+        ("k", "v".to_string(), "6 k=v\n".to_string(), true),
+        (
+            "path",
+            "/etc/hosts".to_string(),
+            "19 path=/etc/hosts\n".to_string(),
+            true,
+        ),
+        (
+            "path",
+            long_name.clone(),
+            format!("210 path={}\n", long_name),
+            true,
+        ),
+        (
+            "path",
+            med_name.clone(),
+            format!("110 path={}\n", med_name),
+            true,
+        ),
+        ("foo", "ba".to_string(), "9 foo=ba\n".to_string(), true),
+        ("foo", "bar".to_string(), "11 foo=bar\n".to_string(), true),
+        (
+            "foo",
+            "b=\nar=\n==\x00".to_string(),
+            "18 foo=b=\nar=\n==\x00\n".to_string(),
+            true,
+        ),
+        (
+            "foo",
+            "hello9 foo=ba\nworld".to_string(),
+            "27 foo=hello9 foo=ba\nworld\n".to_string(),
+            true,
+        ),
+        (
+            "\u{2603}",
+            "\u{2603}".to_string(),
+            "11 \u{2603}=\u{2603}\n".to_string(),
+            true,
+        ),
+        ("xhello", "\x00world".to_string(), String::new(), true),
+        ("path", "null\x00".to_string(), String::new(), false),
+        ("null\x00", "value".to_string(), String::new(), false),
+        (
+            "mtime",
+            "1432668921.098285006".to_string(),
+            "30 mtime=1432668921.098285006\n".to_string(),
+            true,
+        ),
+    ];
+    for (key, val, want, ok) in tests {
+        match format_pax_record(key, &val) {
+            Ok(got) => {
+                assert!(
+                    ok,
+                    "format_pax_record({:?}, {:?}): unexpected success",
+                    key, val
+                );
+                if !want.is_empty() {
+                    assert_eq!(got, want, "format_pax_record({:?}, {:?})", key, val);
+                }
+            }
+            Err(_) => assert!(
+                !ok,
+                "format_pax_record({:?}, {:?}): unexpected error",
+                key, val
+            ),
+        }
+    }
+}
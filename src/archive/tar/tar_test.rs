@@ -0,0 +1,585 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2012 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::common::{align_sparse_entries, invert_sparse_entries, validate_sparse_entries};
+use super::{
+    Format, Header, SparseEntry, TYPE_DIR, TYPE_GNU_LONG_NAME, TYPE_GNU_SPARSE, TYPE_REG,
+    TYPE_SYMLINK, TYPE_XGLOBAL_HEADER,
+};
+use crate::time;
+use std::collections::BTreeMap;
+
+fn sp(entries: &[(i64, i64)]) -> Vec<SparseEntry> {
+    entries
+        .iter()
+        .map(|&(offset, length)| SparseEntry { offset, length })
+        .collect()
+}
+
+#[test]
+fn test_sparse_entries() {
+    struct Test {
+        input: Vec<SparseEntry>,
+        size: i64,
+        want_valid: bool,                // Result of validate_sparse_entries
+        want_aligned: Vec<SparseEntry>,  // Result of align_sparse_entries
+        want_inverted: Vec<SparseEntry>, // Result of invert_sparse_entries
+    }
+    let tests = vec![
+        Test {
+            input: sp(&[]),
+            size: 0,
+            want_valid: true,
+            want_aligned: sp(&[]),
+            want_inverted: sp(&[(0, 0)]),
+        },
+        Test {
+            input: sp(&[]),
+            size: 5000,
+            want_valid: true,
+            want_aligned: sp(&[]),
+            want_inverted: sp(&[(0, 5000)]),
+        },
+        Test {
+            input: sp(&[(0, 5000)]),
+            size: 5000,
+            want_valid: true,
+            want_aligned: sp(&[(0, 5000)]),
+            want_inverted: sp(&[(5000, 0)]),
+        },
+        Test {
+            input: sp(&[(1000, 4000)]),
+            size: 5000,
+            want_valid: true,
+            want_aligned: sp(&[(1024, 3976)]),
+            want_inverted: sp(&[(0, 1000), (5000, 0)]),
+        },
+        Test {
+            input: sp(&[(0, 3000)]),
+            size: 5000,
+            want_valid: true,
+            want_aligned: sp(&[(0, 2560)]),
+            want_inverted: sp(&[(3000, 2000)]),
+        },
+        Test {
+            input: sp(&[(3000, 2000)]),
+            size: 5000,
+            want_valid: true,
+            want_aligned: sp(&[(3072, 1928)]),
+            want_inverted: sp(&[(0, 3000), (5000, 0)]),
+        },
+        Test {
+            input: sp(&[(2000, 2000)]),
+            size: 5000,
+            want_valid: true,
+            want_aligned: sp(&[(2048, 1536)]),
+            want_inverted: sp(&[(0, 2000), (4000, 1000)]),
+        },
+        Test {
+            input: sp(&[(0, 2000), (8000, 2000)]),
+            size: 10000,
+            want_valid: true,
+            want_aligned: sp(&[(0, 1536), (8192, 1808)]),
+            want_inverted: sp(&[(2000, 6000), (10000, 0)]),
+        },
+        Test {
+            input: sp(&[
+                (0, 2000),
+                (2000, 2000),
+                (4000, 0),
+                (4000, 3000),
+                (7000, 1000),
+                (8000, 0),
+                (8000, 2000),
+            ]),
+            size: 10000,
+            want_valid: true,
+            want_aligned: sp(&[
+                (0, 1536),
+                (2048, 1536),
+                (4096, 2560),
+                (7168, 512),
+                (8192, 1808),
+            ]),
+            want_inverted: sp(&[(10000, 0)]),
+        },
+        Test {
+            input: sp(&[
+                (0, 0),
+                (1000, 0),
+                (2000, 0),
+                (3000, 0),
+                (4000, 0),
+                (5000, 0),
+            ]),
+            size: 5000,
+            want_valid: true,
+            want_aligned: sp(&[]),
+            want_inverted: sp(&[(0, 5000)]),
+        },
+        Test {
+            input: sp(&[(1, 0)]),
+            size: 0,
+            want_valid: false,
+            want_aligned: vec![],
+            want_inverted: vec![],
+        },
+        Test {
+            input: sp(&[(-1, 0)]),
+            size: 100,
+            want_valid: false,
+            want_aligned: vec![],
+            want_inverted: vec![],
+        },
+        Test {
+            input: sp(&[(0, -1)]),
+            size: 100,
+            want_valid: false,
+            want_aligned: vec![],
+            want_inverted: vec![],
+        },
+        Test {
+            input: sp(&[(0, 0)]),
+            size: -100,
+            want_valid: false,
+            want_aligned: vec![],
+            want_inverted: vec![],
+        },
+        Test {
+            input: sp(&[(i64::MAX, 3), (6, -5)]),
+            size: 35,
+            want_valid: false,
+            want_aligned: vec![],
+            want_inverted: vec![],
+        },
+        Test {
+            input: sp(&[(1, 3), (6, -5)]),
+            size: 35,
+            want_valid: false,
+            want_aligned: vec![],
+            want_inverted: vec![],
+        },
+        Test {
+            input: sp(&[(i64::MAX, i64::MAX)]),
+            size: i64::MAX,
+            want_valid: false,
+            want_aligned: vec![],
+            want_inverted: vec![],
+        },
+        Test {
+            input: sp(&[(3, 3)]),
+            size: 5,
+            want_valid: false,
+            want_aligned: vec![],
+            want_inverted: vec![],
+        },
+        Test {
+            input: sp(&[(2, 0), (1, 0), (0, 0)]),
+            size: 3,
+            want_valid: false,
+            want_aligned: vec![],
+            want_inverted: vec![],
+        },
+        Test {
+            input: sp(&[(1, 3), (2, 2)]),
+            size: 10,
+            want_valid: false,
+            want_aligned: vec![],
+            want_inverted: vec![],
+        },
+    ];
+
+    for (i, v) in tests.iter().enumerate() {
+        let got_valid = validate_sparse_entries(&v.input, v.size);
+        assert_eq!(
+            got_valid, v.want_valid,
+            "test {}, validate_sparse_entries()",
+            i
+        );
+        if !v.want_valid {
+            continue;
+        }
+        let got_aligned = align_sparse_entries(&v.input, v.size);
+        assert_eq!(
+            got_aligned, v.want_aligned,
+            "test {}, align_sparse_entries()",
+            i
+        );
+        let got_inverted = invert_sparse_entries(&v.input, v.size);
+        assert_eq!(
+            got_inverted, v.want_inverted,
+            "test {}, invert_sparse_entries()",
+            i
+        );
+    }
+}
+
+#[test]
+fn test_header_allowed_formats() {
+    let vec = |k: &str, v: &str| {
+        let mut m = BTreeMap::new();
+        m.insert(k.to_string(), v.to_string());
+        m
+    };
+    struct Test {
+        header: Header,                     // Input header
+        pax_hdrs: BTreeMap<String, String>, // Expected PAX headers that may be needed
+        formats: Format,                    // Expected formats that can encode the header
+    }
+    let tests = vec![
+        Test {
+            header: Header::default(),
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::USTAR | Format::PAX | Format::GNU,
+        },
+        Test {
+            header: Header {
+                size: 0o77777777777,
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::USTAR | Format::PAX | Format::GNU,
+        },
+        Test {
+            header: Header {
+                size: 0o77777777777,
+                format: Format::USTAR,
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::USTAR,
+        },
+        Test {
+            header: Header {
+                size: 0o77777777777 + 1,
+                ..Default::default()
+            },
+            pax_hdrs: vec("size", "8589934592"),
+            formats: Format::PAX | Format::GNU,
+        },
+        Test {
+            header: Header {
+                size: 0o77777777777 + 1,
+                format: Format::GNU,
+                ..Default::default()
+            },
+            pax_hdrs: vec("size", "8589934592"),
+            formats: Format::GNU,
+        },
+        Test {
+            header: Header {
+                mode: 0o7777777,
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::USTAR | Format::PAX | Format::GNU,
+        },
+        Test {
+            header: Header {
+                mode: 0o7777777 + 1,
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::GNU,
+        },
+        Test {
+            header: Header {
+                devmajor: -123,
+                format: Format::GNU,
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::GNU,
+        },
+        Test {
+            header: Header {
+                devmajor: (1 << 56) - 1,
+                format: Format::GNU,
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::GNU,
+        },
+        Test {
+            header: Header {
+                devmajor: 1 << 56,
+                format: Format::GNU,
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::UNKNOWN,
+        },
+        Test {
+            header: Header {
+                devmajor: -1 << 56,
+                format: Format::GNU,
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::GNU,
+        },
+        Test {
+            header: Header {
+                devmajor: (-1 << 56) - 1,
+                format: Format::GNU,
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::UNKNOWN,
+        },
+        Test {
+            header: Header {
+                uid: 0o7777777,
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::USTAR | Format::PAX | Format::GNU,
+        },
+        Test {
+            header: Header {
+                uid: 0o7777777 + 1,
+                ..Default::default()
+            },
+            pax_hdrs: vec("uid", "2097152"),
+            formats: Format::PAX | Format::GNU,
+        },
+        Test {
+            header: Header {
+                xattrs: vec("foo", ""),
+                ..Default::default()
+            },
+            pax_hdrs: vec("SCHILY.xattr.foo", ""),
+            formats: Format::PAX,
+        },
+        Test {
+            header: Header {
+                xattrs: vec("foo", ""),
+                format: Format::GNU,
+                ..Default::default()
+            },
+            pax_hdrs: vec("SCHILY.xattr.foo", ""),
+            formats: Format::UNKNOWN,
+        },
+        Test {
+            header: Header {
+                pax_records: vec("GOLANG.pkg", "tar"),
+                ..Default::default()
+            },
+            pax_hdrs: vec("GOLANG.pkg", "tar"),
+            formats: Format::PAX,
+        },
+        Test {
+            header: Header {
+                pax_records: vec("path", "foo"),
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::PAX,
+        },
+        Test {
+            header: Header {
+                name: "foo/".to_string(),
+                typeflag: TYPE_DIR,
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::USTAR | Format::PAX | Format::GNU,
+        },
+        Test {
+            header: Header {
+                name: "foo/".to_string(),
+                typeflag: TYPE_REG,
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::UNKNOWN,
+        },
+        Test {
+            header: Header {
+                name: "foo/".to_string(),
+                typeflag: TYPE_SYMLINK,
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::USTAR | Format::PAX | Format::GNU,
+        },
+        Test {
+            header: Header {
+                name: "/".repeat(101),
+                typeflag: TYPE_DIR,
+                ..Default::default()
+            },
+            pax_hdrs: vec("path", &"/".repeat(101)),
+            formats: Format::USTAR | Format::PAX | Format::GNU,
+        },
+        Test {
+            header: Header {
+                name: "abc/".repeat(30) + &"f".repeat(101),
+                ..Default::default()
+            },
+            pax_hdrs: vec("path", &("abc/".repeat(30) + &"f".repeat(101))),
+            formats: Format::PAX | Format::GNU,
+        },
+        Test {
+            header: Header {
+                name: "abc/".repeat(30) + "file",
+                ..Default::default()
+            },
+            pax_hdrs: vec("path", &("abc/".repeat(30) + "file")),
+            formats: Format::USTAR | Format::PAX | Format::GNU,
+        },
+        Test {
+            header: Header {
+                name: "résumé".to_string(),
+                ..Default::default()
+            },
+            pax_hdrs: vec("path", "résumé"),
+            formats: Format::PAX | Format::GNU,
+        },
+        Test {
+            header: Header {
+                typeflag: TYPE_XGLOBAL_HEADER,
+                pax_records: vec("path", "foo"),
+                ..Default::default()
+            },
+            pax_hdrs: vec("path", "foo"),
+            formats: Format::PAX,
+        },
+        Test {
+            header: Header {
+                typeflag: TYPE_XGLOBAL_HEADER,
+                size: 5,
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::UNKNOWN,
+        },
+        Test {
+            header: Header {
+                typeflag: TYPE_GNU_LONG_NAME,
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::UNKNOWN,
+        },
+        Test {
+            header: Header {
+                mod_time: time::unix(-1, 0),
+                ..Default::default()
+            },
+            pax_hdrs: vec("mtime", "-1"),
+            formats: Format::PAX | Format::GNU,
+        },
+        Test {
+            header: Header {
+                mod_time: time::unix(0o77777777777, 0),
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::USTAR | Format::PAX | Format::GNU,
+        },
+        Test {
+            header: Header {
+                mod_time: time::unix(0o77777777777 + 1, 0),
+                ..Default::default()
+            },
+            pax_hdrs: vec("mtime", "8589934592"),
+            formats: Format::PAX | Format::GNU,
+        },
+        Test {
+            header: Header {
+                mod_time: time::unix(1, 500),
+                ..Default::default()
+            },
+            pax_hdrs: vec("mtime", "1.0000005"),
+            formats: Format::USTAR | Format::PAX | Format::GNU,
+        },
+        Test {
+            header: Header {
+                mod_time: time::unix(1, 500),
+                format: Format::PAX,
+                ..Default::default()
+            },
+            pax_hdrs: vec("mtime", "1.0000005"),
+            formats: Format::PAX,
+        },
+        Test {
+            header: Header {
+                access_time: time::unix(1, 0),
+                ..Default::default()
+            },
+            pax_hdrs: vec("atime", "1"),
+            formats: Format::PAX | Format::GNU,
+        },
+        Test {
+            header: Header {
+                access_time: time::unix(1, 500),
+                format: Format::GNU,
+                ..Default::default()
+            },
+            pax_hdrs: vec("atime", "1.0000005"),
+            formats: Format::GNU,
+        },
+        Test {
+            header: Header {
+                name: "sparse".to_string(),
+                size: 1000,
+                sparse_holes: sp(&[(0, 500)]),
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::PAX,
+        },
+        Test {
+            header: Header {
+                name: "sparse".to_string(),
+                typeflag: TYPE_GNU_SPARSE,
+                size: 1000,
+                sparse_holes: sp(&[(0, 500)]),
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::GNU,
+        },
+        Test {
+            header: Header {
+                name: "sparse".to_string(),
+                size: 1000,
+                sparse_holes: sp(&[(600, 500)]),
+                ..Default::default()
+            },
+            pax_hdrs: BTreeMap::new(),
+            formats: Format::UNKNOWN,
+        },
+    ];
+
+    for (i, v) in tests.iter().enumerate() {
+        let (formats, pax_hdrs, err) = match v.header.allowed_formats() {
+            Ok((formats, pax_hdrs)) => (formats, pax_hdrs, None),
+            Err(err) => (Format::UNKNOWN, BTreeMap::new(), Some(err)),
+        };
+        assert_eq!(formats, v.formats, "test {}, allowed_formats()", i);
+        if formats.has(Format::PAX) {
+            assert_eq!(pax_hdrs, v.pax_hdrs, "test {}, allowed_formats()", i);
+        }
+        assert_eq!(
+            err.is_some(),
+            formats == Format::UNKNOWN,
+            "test {}, unexpected error state: {:?}",
+            i,
+            err
+        );
+    }
+}
+
+#[test]
+fn test_format_display() {
+    assert_eq!(Format::UNKNOWN.to_string(), "<unknown>");
+    assert_eq!(Format::PAX.to_string(), "PAX");
+    assert_eq!(
+        (Format::USTAR | Format::PAX | Format::GNU).to_string(),
+        "(USTAR | PAX | GNU)"
+    );
+}
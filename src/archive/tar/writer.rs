@@ -0,0 +1,595 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2009 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::common::{
+    align_sparse_entries, invert_sparse_entries, is_header_only_type, new_input_error, Header,
+    SparseEntry, ERR_FIELD_TOO_LONG, ERR_UNREF_DATA, ERR_WRITE_AFTER_CLOSE, ERR_WRITE_HOLE,
+    ERR_WRITE_TOO_LONG, PAX_GNU_SPARSE_MAJOR, PAX_GNU_SPARSE_MINOR, PAX_GNU_SPARSE_NAME,
+    PAX_GNU_SPARSE_REAL_SIZE, PAX_PATH, PAX_SIZE, TYPE_DIR, TYPE_GNU_LONG_LINK, TYPE_GNU_LONG_NAME,
+    TYPE_GNU_SPARSE, TYPE_REG, TYPE_REG_A, TYPE_XGLOBAL_HEADER, TYPE_XHEADER,
+};
+use super::format::{
+    block_padding, Block, Format, SparseArray, MAX_SPECIAL_FILE_SIZE, NAME_SIZE, PREFIX_SIZE,
+    ZERO_BLOCK,
+};
+use super::strconv::{format_pax_record, is_ascii, to_ascii, Formatter};
+use crate::errors;
+use crate::time;
+use std::collections::BTreeMap;
+
+/// Writer provides sequential writing of a tar archive.
+/// Writer::write_header begins a new file with the provided Header,
+/// and then Writer can be treated as a std::io::Write to supply that file's data.
+pub struct Writer<Output: std::io::Write> {
+    w: Output,
+    /// pad is the amount of padding to write after the current file entry.
+    pad: i64,
+    /// nb is the number of remaining bytes of the current file entry
+    /// to be stored in the archive.
+    nb: i64,
+    /// spd is the list of data fragments of the current file entry if it is
+    /// a sparse file, and is empty otherwise. It is normalized, so that only
+    /// the last fragment may be empty and its end_offset is the logical file size.
+    /// spd_idx is the index of the current fragment.
+    spd: Vec<SparseEntry>,
+    spd_idx: usize,
+    /// pos is the current position in the sparse file.
+    pos: i64,
+    /// blk is the buffer to use as temporary local storage.
+    blk: Block,
+
+    /// err is a persistent error.
+    /// It is only the responsibility of every exported method of Writer to
+    /// ensure that this error is sticky.
+    err: Option<std::io::Error>,
+    /// closed is set when close was called.
+    closed: bool,
+}
+
+type StringFormatter = fn(&mut Formatter, &mut [u8], &str);
+type NumberFormatter = fn(&mut Formatter, &mut [u8], i64);
+
+impl<Output: std::io::Write> Writer<Output> {
+    /// new creates a new Writer writing to w.
+    /// To keep using w after the Writer is done, pass a mutable reference.
+    pub fn new(w: Output) -> Self {
+        Self {
+            w,
+            pad: 0,
+            nb: 0,
+            spd: Vec::new(),
+            spd_idx: 0,
+            pos: 0,
+            blk: Block::new(),
+            err: None,
+            closed: false,
+        }
+    }
+
+    /// get_ref returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Output {
+        &self.w
+    }
+
+    /// get_mut returns a mutable reference to the underlying writer.
+    /// Writing to it directly corrupts the archive.
+    pub fn get_mut(&mut self) -> &mut Output {
+        &mut self.w
+    }
+
+    /// into_inner returns the underlying writer. close must be called before
+    /// to write the padding of the last entry and the archive footer.
+    pub fn into_inner(self) -> Output {
+        self.w
+    }
+
+    /// check_err returns the persistent error, if any.
+    fn check_err(&self) -> std::io::Result<()> {
+        if self.closed {
+            return Err(new_input_error(ERR_WRITE_AFTER_CLOSE));
+        }
+        if let Some(err) = &self.err {
+            return Err(errors::copy_stdio_error(err));
+        }
+        Ok(())
+    }
+
+    /// set_err makes err persistent if res is an error.
+    fn set_err<T>(&mut self, res: std::io::Result<T>) -> std::io::Result<T> {
+        if let Err(err) = &res {
+            self.err = Some(errors::copy_stdio_error(err));
+        }
+        res
+    }
+
+    /// flush_entry finishes writing the current file's block padding.
+    /// The current file must be fully written before flush_entry can be called.
+    fn flush_entry(&mut self) -> std::io::Result<()> {
+        self.check_err()?;
+        let nb = self.logical_remaining();
+        if nb > 0 {
+            return Err(new_input_error(&format!(
+                "archive/tar: missed writing {} bytes",
+                nb
+            )));
+        }
+        let res = self.w.write_all(&ZERO_BLOCK[..self.pad as usize]);
+        self.set_err(res)?;
+        self.pad = 0;
+        Ok(())
+    }
+
+    /// write_header writes hdr and prepares to accept the file's contents.
+    /// The Header::size determines how many bytes can be written for the next file.
+    /// If the current file is not fully written, then this returns an error.
+    /// This implicitly flushes any padding necessary before writing the header.
+    pub fn write_header(&mut self, hdr: &Header) -> std::io::Result<()> {
+        self.flush_entry()?;
+        let mut hdr = hdr.clone();
+
+        // Avoid usage of the legacy TYPE_REG_A flag, and automatically promote
+        // it to use TYPE_REG or TYPE_DIR.
+        if hdr.typeflag == TYPE_REG_A {
+            if hdr.name.ends_with('/') {
+                hdr.typeflag = TYPE_DIR;
+            } else {
+                hdr.typeflag = TYPE_REG;
+            }
+        }
+
+        // Round mod_time and ignore access_time and change_time unless
+        // the format is explicitly chosen.
+        // This ensures nominal usage of write_header (without specifying the format)
+        // does not always result in the PAX format being chosen, which
+        // causes a 1KiB hit to every header.
+        if hdr.format == Format::UNKNOWN {
+            let round_up = hdr.mod_time.nanosecond() >= 500_000_000;
+            hdr.mod_time = time::unix(hdr.mod_time.unix() + round_up as i64, 0);
+            hdr.access_time = time::Time::default();
+            hdr.change_time = time::Time::default();
+        }
+
+        let (allowed_formats, pax_hdrs) = hdr.allowed_formats()?; // Non-fatal error
+        let res = if allowed_formats.has(Format::USTAR) {
+            self.write_ustar_header(&mut hdr)
+        } else if allowed_formats.has(Format::PAX) {
+            self.write_pax_header(&mut hdr, pax_hdrs)
+        } else {
+            self.write_gnu_header(&mut hdr)
+        };
+        self.set_err(res)
+    }
+
+    fn write_ustar_header(&mut self, hdr: &mut Header) -> std::io::Result<()> {
+        // Check if we can use USTAR prefix/suffix splitting.
+        let mut name_prefix = String::new();
+        if let Some((prefix, suffix)) = split_ustar_path(&hdr.name) {
+            (name_prefix, hdr.name) = (prefix.to_string(), suffix.to_string());
+        }
+
+        // Pack the main header.
+        let mut f = Formatter::default();
+        self.template_v7_plus(
+            hdr,
+            &mut f,
+            Formatter::format_string,
+            Formatter::format_octal,
+        );
+        f.format_string(self.blk.ustar_prefix(), &name_prefix);
+        self.blk.set_format(Format::USTAR);
+        if f.err {
+            return Err(new_input_error(ERR_FIELD_TOO_LONG)); // Should never happen since header is validated
+        }
+        self.write_raw_header(hdr.size, hdr.typeflag)
+    }
+
+    fn write_pax_header(
+        &mut self,
+        hdr: &mut Header,
+        mut pax_hdrs: BTreeMap<String, String>,
+    ) -> std::io::Result<()> {
+        let (real_name, real_size) = (hdr.name.clone(), hdr.size);
+
+        // Handle sparse files.
+        let mut spd = Vec::new();
+        let mut spb = Vec::new();
+        if !hdr.sparse_holes.is_empty() {
+            let sph = align_sparse_entries(&hdr.sparse_holes, hdr.size);
+            spd = invert_sparse_entries(&sph, hdr.size);
+
+            // Format the sparse map.
+            hdr.size = 0; // Replace with encoded size
+            spb.extend_from_slice(format!("{}\n", spd.len()).as_bytes());
+            for s in &spd {
+                hdr.size += s.length;
+                spb.extend_from_slice(format!("{}\n{}\n", s.offset, s.length).as_bytes());
+            }
+            let pad = block_padding(spb.len() as i64) as usize;
+            spb.extend_from_slice(&ZERO_BLOCK[..pad]);
+            hdr.size += spb.len() as i64; // Accounts for encoded sparse map
+
+            // Add and modify appropriate PAX records.
+            hdr.name = join_meta_name(&real_name, "GNUSparseFile.0");
+            pax_hdrs.insert(PAX_GNU_SPARSE_MAJOR.to_string(), "1".to_string());
+            pax_hdrs.insert(PAX_GNU_SPARSE_MINOR.to_string(), "0".to_string());
+            pax_hdrs.insert(PAX_GNU_SPARSE_NAME.to_string(), real_name.clone());
+            pax_hdrs.insert(PAX_GNU_SPARSE_REAL_SIZE.to_string(), real_size.to_string());
+            pax_hdrs.insert(PAX_SIZE.to_string(), hdr.size.to_string());
+            pax_hdrs.remove(PAX_PATH); // Recorded by PAX_GNU_SPARSE_NAME
+        }
+
+        // Write PAX records to the output.
+        let is_global = hdr.typeflag == TYPE_XGLOBAL_HEADER;
+        if !pax_hdrs.is_empty() || is_global {
+            // Write each record to a buffer.
+            // The keys of a BTreeMap are sorted, so the ordering is deterministic.
+            let mut buf = String::new();
+            for (k, v) in &pax_hdrs {
+                buf += &format_pax_record(k, v)?;
+            }
+
+            // Write the extended header file.
+            let (name, flag) = if is_global {
+                let name = if real_name.is_empty() {
+                    "GlobalHead.0.0".to_string()
+                } else {
+                    real_name
+                };
+                (name, TYPE_XGLOBAL_HEADER)
+            } else {
+                (join_meta_name(&real_name, "PaxHeaders.0"), TYPE_XHEADER)
+            };
+            if buf.len() > MAX_SPECIAL_FILE_SIZE {
+                return Err(new_input_error(ERR_FIELD_TOO_LONG));
+            }
+            self.write_raw_file(&name, buf.as_bytes(), flag, Format::PAX)?;
+            if is_global {
+                return Ok(()); // Global headers return here
+            }
+        }
+
+        // Pack the main header.
+        let mut f = Formatter::default(); // Ignore errors since they are expected
+        let fmt_str: StringFormatter = |f, b, s| f.format_string(b, &to_ascii(s));
+        self.template_v7_plus(hdr, &mut f, fmt_str, Formatter::format_octal);
+        self.blk.set_format(Format::PAX);
+        self.write_raw_header(hdr.size, hdr.typeflag)?;
+
+        // Write the sparse map and setup the sparse writer if necessary.
+        if !spd.is_empty() {
+            // Use write_reg since the sparse map is accounted for in hdr.size.
+            self.write_reg(&spb)?;
+            self.spd = spd;
+        }
+        Ok(())
+    }
+
+    fn write_gnu_header(&mut self, hdr: &mut Header) -> std::io::Result<()> {
+        // Use long-link files if name or linkname exceeds the field size.
+        const LONG_NAME: &str = "././@LongLink";
+        if hdr.name.len() > NAME_SIZE {
+            let data = hdr.name.clone() + "\x00";
+            self.write_raw_file(LONG_NAME, data.as_bytes(), TYPE_GNU_LONG_NAME, Format::GNU)?;
+        }
+        if hdr.linkname.len() > NAME_SIZE {
+            let data = hdr.linkname.clone() + "\x00";
+            self.write_raw_file(LONG_NAME, data.as_bytes(), TYPE_GNU_LONG_LINK, Format::GNU)?;
+        }
+
+        // Pack the main header.
+        let mut f = Formatter::default(); // Ignore errors since they are expected
+        let mut spd = Vec::new();
+        let mut spb = Vec::new();
+        self.template_v7_plus(
+            hdr,
+            &mut f,
+            Formatter::format_string,
+            Formatter::format_numeric,
+        );
+        if !hdr.access_time.is_zero() {
+            f.format_numeric(self.blk.gnu_access_time(), hdr.access_time.unix());
+        }
+        if !hdr.change_time.is_zero() {
+            f.format_numeric(self.blk.gnu_change_time(), hdr.change_time.unix());
+        }
+        if hdr.typeflag == TYPE_GNU_SPARSE {
+            let sph = align_sparse_entries(&hdr.sparse_holes, hdr.size);
+            spd = invert_sparse_entries(&sph, hdr.size);
+
+            // Format the sparse map.
+            let mut sp = format_spd(&mut f, &spd, self.blk.gnu_sparse());
+            while !sp.is_empty() {
+                let mut sp_hdr = Block::new();
+                sp = format_spd(&mut f, sp, sp_hdr.as_sparse());
+                spb.extend_from_slice(&sp_hdr.0);
+            }
+
+            // Update size fields in the header block.
+            let real_size = hdr.size;
+            hdr.size = spd.iter().map(|s| s.length).sum(); // Encoded size; does not account for encoded sparse map
+            self.blk.size().fill(0); // Reset field
+            f.format_numeric(self.blk.size(), hdr.size);
+            f.format_numeric(self.blk.gnu_real_size(), real_size);
+        }
+        self.blk.set_format(Format::GNU);
+        self.write_raw_header(hdr.size, hdr.typeflag)?;
+
+        // Write the extended sparse map and setup the sparse writer if necessary.
+        if !spd.is_empty() {
+            // Use self.w since the sparse map is not accounted for in hdr.size.
+            self.w.write_all(&spb)?;
+            self.spd = spd;
+        }
+        Ok(())
+    }
+
+    /// template_v7_plus fills out the V7 fields of self.blk using values from hdr.
+    /// It also fills out fields (uname, gname, devmajor, devminor) that are
+    /// shared in the USTAR, PAX, and GNU formats using the provided formatters.
+    fn template_v7_plus(
+        &mut self,
+        hdr: &Header,
+        f: &mut Formatter,
+        fmt_str: StringFormatter,
+        fmt_num: NumberFormatter,
+    ) {
+        self.blk.reset();
+
+        let mod_time = if hdr.mod_time.is_zero() {
+            time::unix(0, 0)
+        } else {
+            hdr.mod_time
+        };
+
+        let blk = &mut self.blk;
+        *blk.type_flag() = hdr.typeflag;
+        fmt_str(f, blk.name(), &hdr.name);
+        fmt_str(f, blk.link_name(), &hdr.linkname);
+        fmt_num(f, blk.mode(), hdr.mode);
+        fmt_num(f, blk.uid(), hdr.uid as i64);
+        fmt_num(f, blk.gid(), hdr.gid as i64);
+        fmt_num(f, blk.size(), hdr.size);
+        fmt_num(f, blk.mod_time(), mod_time.unix());
+
+        fmt_str(f, blk.user_name(), &hdr.uname);
+        fmt_str(f, blk.group_name(), &hdr.gname);
+        fmt_num(f, blk.dev_major(), hdr.devmajor);
+        fmt_num(f, blk.dev_minor(), hdr.devminor);
+    }
+
+    /// write_raw_file writes a minimal file with the given name and flag type.
+    /// It uses format to encode the header format and will write data as the body.
+    /// It uses default values for all of the other fields (as BSD and GNU tar does).
+    fn write_raw_file(
+        &mut self,
+        name: &str,
+        data: &[u8],
+        flag: u8,
+        format: Format,
+    ) -> std::io::Result<()> {
+        self.blk.reset();
+
+        // Best effort for the filename.
+        let mut name = to_ascii(name);
+        name.truncate(NAME_SIZE);
+        let name = name.trim_end_matches('/');
+
+        let mut f = Formatter::default();
+        let blk = &mut self.blk;
+        *blk.type_flag() = flag;
+        f.format_string(blk.name(), name);
+        f.format_octal(blk.mode(), 0);
+        f.format_octal(blk.uid(), 0);
+        f.format_octal(blk.gid(), 0);
+        f.format_octal(blk.size(), data.len() as i64); // Must be < 8GiB
+        f.format_octal(blk.mod_time(), 0);
+        blk.set_format(format);
+        if f.err {
+            return Err(new_input_error(ERR_FIELD_TOO_LONG)); // Only occurs if size condition is violated
+        }
+
+        // Write the header and data.
+        self.write_raw_header(data.len() as i64, flag)?;
+        self.write_reg(data)?;
+        Ok(())
+    }
+
+    /// write_raw_header writes the value of self.blk, regardless of its value.
+    /// It sets up the Writer such that it can accept a file of the given size.
+    /// If the flag is a special header-only flag, then the size is treated as zero.
+    fn write_raw_header(&mut self, size: i64, flag: u8) -> std::io::Result<()> {
+        self.flush_entry()?;
+        self.w.write_all(&self.blk.0)?;
+        let size = if is_header_only_type(flag) { 0 } else { size };
+        self.nb = size;
+        self.spd.clear();
+        self.spd_idx = 0;
+        self.pos = 0;
+        self.pad = block_padding(size);
+        Ok(())
+    }
+
+    /// logical_remaining returns the number of bytes of the current file
+    /// that remain to be written.
+    fn logical_remaining(&self) -> i64 {
+        match self.spd.last() {
+            Some(last) => last.end_offset() - self.pos,
+            None => self.nb,
+        }
+    }
+
+    /// write_reg writes b, which must fit in the remaining data section of
+    /// the current file entry.
+    fn write_reg(&mut self, b: &[u8]) -> std::io::Result<()> {
+        self.w.write_all(b)?;
+        self.nb -= b.len() as i64;
+        Ok(())
+    }
+
+    /// write_sparse writes b to the current sparse file, skipping the holes,
+    /// which must only be written with NULs.
+    /// The first error encountered after some bytes were written is saved and
+    /// returned by the next write.
+    fn write_sparse(&mut self, b: &[u8]) -> std::io::Result<usize> {
+        let mut n = 0;
+        let mut res = Ok(());
+        while n < b.len() {
+            let data = self.spd[self.spd_idx];
+            let (data_start, data_end) = (data.offset, data.end_offset());
+            let mut nf; // Bytes written in fragment
+            if self.pos < data_start {
+                // In a hole fragment
+                nf = (b.len() - n).min((data_start - self.pos) as usize);
+                if let Some(i) = b[n..n + nf].iter().position(|&c| c != 0) {
+                    nf = i;
+                    res = Err(new_input_error(ERR_WRITE_HOLE));
+                }
+            } else {
+                // In a data fragment
+                nf = (b.len() - n).min((data_end - self.pos) as usize);
+                if let Err(err) = self.write_reg(&b[n..n + nf]) {
+                    nf = 0;
+                    res = Err(err);
+                }
+            }
+            n += nf;
+            self.pos += nf as i64;
+            if self.pos >= data_end && self.spd_idx + 1 < self.spd.len() {
+                self.spd_idx += 1; // Ensure last fragment always remains
+            }
+            if res.is_err() {
+                break;
+            }
+        }
+
+        if res.is_ok() && self.logical_remaining() == 0 && self.nb > 0 {
+            // Not possible; implies bug in validation logic
+            res = Err(new_input_error(ERR_UNREF_DATA));
+        }
+        match res {
+            Ok(()) => Ok(n),
+            Err(err) if n == 0 => Err(err),
+            Err(err) => {
+                self.err = Some(err);
+                Ok(n)
+            }
+        }
+    }
+
+    /// close closes the tar archive by flushing the padding, and writing the footer.
+    /// If the current file (from a prior call to write_header) is not fully written,
+    /// then this returns an error.
+    pub fn close(&mut self) -> std::io::Result<()> {
+        if self.closed {
+            return Ok(());
+        }
+        if let Some(err) = &self.err {
+            return Err(errors::copy_stdio_error(err));
+        }
+
+        // Trailer: two zero blocks.
+        let mut res = self.flush_entry();
+        for _ in 0..2 {
+            if res.is_ok() {
+                res = self.w.write_all(&ZERO_BLOCK);
+            }
+        }
+
+        // Ensure all future actions are invalid.
+        self.closed = true;
+        res // Report IO errors
+    }
+}
+
+impl<Output: std::io::Write> std::io::Write for Writer<Output> {
+    /// write writes to the current file in the tar archive.
+    /// write returns the ERR_WRITE_TOO_LONG error if more than
+    /// Header::size bytes are written after write_header.
+    ///
+    /// If the current file is sparse, then the regions marked as a hole
+    /// must be written as NUL-bytes.
+    ///
+    /// Calling write on special types like TYPE_LINK, TYPE_SYMLINK, TYPE_CHAR,
+    /// TYPE_BLOCK, TYPE_DIR, and TYPE_FIFO returns ERR_WRITE_TOO_LONG regardless
+    /// of what the Header::size claims.
+    fn write(&mut self, b: &[u8]) -> std::io::Result<usize> {
+        self.check_err()?;
+        if b.is_empty() {
+            return Ok(0);
+        }
+        let remaining = self.logical_remaining();
+        if remaining == 0 {
+            return Err(new_input_error(ERR_WRITE_TOO_LONG));
+        }
+        let b = &b[..b.len().min(remaining as usize)];
+        if self.spd.is_empty() {
+            let res = self.write_reg(b).map(|_| b.len());
+            self.set_err(res)
+        } else {
+            let res = self.write_sparse(b);
+            self.set_err(res)
+        }
+    }
+
+    /// flush flushes the underlying writer.
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.check_err()?;
+        let res = self.w.flush();
+        self.set_err(res)
+    }
+}
+
+/// split_ustar_path splits a path according to USTAR prefix and suffix rules.
+/// If the path is not splittable, then it will return None.
+pub(super) fn split_ustar_path(name: &str) -> Option<(&str, &str)> {
+    let mut length = name.len();
+    if length <= NAME_SIZE || !is_ascii(name) {
+        return None;
+    } else if length > PREFIX_SIZE + 1 {
+        length = PREFIX_SIZE + 1;
+    } else if name.as_bytes()[length - 1] == b'/' {
+        length -= 1;
+    }
+
+    let i = name[..length].rfind('/')?;
+    let nlen = name.len() - i - 1; // nlen is length of suffix
+    let plen = i; // plen is length of prefix
+    if i == 0 || nlen > NAME_SIZE || nlen == 0 || plen > PREFIX_SIZE {
+        return None;
+    }
+    Some((&name[..i], &name[i + 1..]))
+}
+
+/// join_meta_name returns the name of a meta file describing the file name,
+/// which is name with the meta directory inserted before its last element.
+fn join_meta_name(name: &str, meta: &str) -> String {
+    let (dir, file) = match name.rfind('/') {
+        Some(i) => name.split_at(i + 1),
+        None => ("", name),
+    };
+    if file.is_empty() {
+        return format!("{}{}", dir, meta);
+    }
+    format!("{}{}/{}", dir, meta, file)
+}
+
+/// format_spd formats as many of the sparse data fragments sp as fit into
+/// sa, setting its extended flag if there are more. It returns the fragments
+/// that did not fit.
+fn format_spd<'b>(
+    f: &mut Formatter,
+    sp: &'b [SparseEntry],
+    mut sa: SparseArray<'_>,
+) -> &'b [SparseEntry] {
+    let n = sp.len().min(sa.max_entries());
+    for (i, s) in sp[..n].iter().enumerate() {
+        f.format_numeric(sa.offset(i), s.offset);
+        f.format_numeric(sa.length(i), s.length);
+    }
+    if n < sp.len() {
+        *sa.is_extended() = 1;
+    }
+    &sp[n..]
+}
@@ -0,0 +1,438 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2009 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::writer::split_ustar_path;
+use super::{
+    Format, Header, Reader, SparseEntry, Writer, ERR_WRITE_AFTER_CLOSE, ERR_WRITE_TOO_LONG,
+    TYPE_DIR, TYPE_GNU_SPARSE, TYPE_REG, TYPE_REG_A, TYPE_SYMLINK, TYPE_XGLOBAL_HEADER,
+};
+use crate::compress::gzip;
+use crate::time;
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+/// write_entries writes the entries into a new tar archive.
+fn write_entries(entries: &[(Header, Vec<u8>)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut tw = Writer::new(&mut out);
+    for (hdr, data) in entries {
+        tw.write_header(hdr).unwrap();
+        tw.write_all(data).unwrap();
+    }
+    tw.close().unwrap();
+    out
+}
+
+/// read_entries reads all the entries of the tar archive.
+fn read_entries(mut input: &[u8]) -> Vec<(Header, Vec<u8>)> {
+    let mut tr = Reader::new(&mut input);
+    let mut entries = Vec::new();
+    while let Some(hdr) = tr.next().unwrap() {
+        let mut data = Vec::new();
+        tr.read_to_end(&mut data).unwrap();
+        entries.push((hdr, data));
+    }
+    entries
+}
+
+fn file(name: &str, data: &[u8]) -> (Header, Vec<u8>) {
+    let hdr = Header {
+        typeflag: TYPE_REG,
+        name: name.to_string(),
+        size: data.len() as i64,
+        mode: 0o644,
+        uid: 1000,
+        gid: 1000,
+        uname: "user".to_string(),
+        gname: "group".to_string(),
+        mod_time: time::unix(1500000000, 0),
+        ..Default::default()
+    };
+    (hdr, data.to_vec())
+}
+
+#[test]
+fn test_writer_ustar() {
+    let entries = vec![
+        file("small.txt", b"Kilts"),
+        file("small2.txt", b"Google.com\n"),
+        file("empty", b""),
+    ];
+    let out = write_entries(&entries);
+    assert_eq!(
+        out.len(),
+        512 * 7,
+        "header and data blocks plus the trailer"
+    );
+    assert!(out[512 * 5..].iter().all(|&c| c == 0));
+
+    let got = read_entries(&out);
+    assert_eq!(got.len(), entries.len());
+    for ((got_hdr, got_data), (hdr, data)) in got.iter().zip(&entries) {
+        assert_eq!(got_hdr.format, Format::USTAR);
+        let want_hdr = Header {
+            format: Format::USTAR,
+            ..hdr.clone()
+        };
+        assert_eq!(*got_hdr, want_hdr);
+        assert_eq!(got_data, data);
+    }
+}
+
+#[test]
+fn test_writer_format_selection() {
+    let long_name = "longname/".repeat(12) + "file.txt";
+    let long_base = "a".repeat(120);
+    let tests = [
+        // Splittable into USTAR prefix and suffix.
+        (long_name.as_str(), Format::UNKNOWN, Format::USTAR),
+        // Not splittable, so PAX is chosen before GNU.
+        (long_base.as_str(), Format::UNKNOWN, Format::PAX),
+        (long_base.as_str(), Format::GNU, Format::GNU),
+        (long_base.as_str(), Format::PAX, Format::PAX),
+        ("résumé.txt", Format::UNKNOWN, Format::PAX),
+    ];
+    for (name, format, want_format) in tests {
+        let (mut hdr, data) = file(name, b"hello");
+        hdr.format = format;
+        let out = write_entries(&[(hdr.clone(), data.clone())]);
+        let got = read_entries(&out);
+        assert_eq!(got.len(), 1, "{}", name);
+        let (got_hdr, got_data) = &got[0];
+        assert_eq!(got_hdr.name, name);
+        assert_eq!(got_hdr.format, want_format, "{}", name);
+        assert_eq!(*got_data, data, "{}", name);
+    }
+}
+
+#[test]
+fn test_writer_long_link() {
+    let long_name = "longname/".repeat(12) + "file.txt";
+    for format in [Format::PAX, Format::GNU] {
+        let hdr = Header {
+            typeflag: TYPE_SYMLINK,
+            name: "link".to_string(),
+            linkname: long_name.clone(),
+            mode: 0o777,
+            mod_time: time::unix(1500000000, 0),
+            format,
+            ..Default::default()
+        };
+        let out = write_entries(&[(hdr, Vec::new())]);
+        let got = read_entries(&out);
+        assert_eq!(got.len(), 1);
+        assert_eq!(got[0].0.typeflag, TYPE_SYMLINK);
+        assert_eq!(got[0].0.linkname, long_name, "{}", format);
+        assert_eq!(got[0].0.format, format);
+    }
+}
+
+#[test]
+fn test_writer_pax_records() {
+    let mut xattrs = BTreeMap::new();
+    xattrs.insert("user.key".to_string(), "value".to_string());
+    xattrs.insert("security.selinux".to_string(), "unconfined_u".to_string());
+    let mut pax_records = BTreeMap::new();
+    pax_records.insert("GOLANG.pkg".to_string(), "tar".to_string());
+    pax_records.insert("comment".to_string(), "Hello, 世界".to_string());
+    let (mut hdr, data) = file("small.txt", b"Kilts");
+    hdr.xattrs = xattrs.clone();
+    hdr.pax_records = pax_records;
+    hdr.mod_time = time::unix(1500000000, 250000000);
+    hdr.access_time = time::unix(1500000001, 0);
+    hdr.change_time = time::unix(1500000002, 500);
+    hdr.format = Format::PAX;
+
+    let out = write_entries(&[(hdr, data)]);
+    let got = read_entries(&out);
+    assert_eq!(got.len(), 1);
+    let (got_hdr, got_data) = &got[0];
+    assert_eq!(got_hdr.format, Format::PAX);
+    assert_eq!(got_hdr.xattrs, xattrs);
+    assert_eq!(got_hdr.pax_records["GOLANG.pkg"], "tar");
+    assert_eq!(got_hdr.pax_records["comment"], "Hello, 世界");
+    assert_eq!(
+        got_hdr.pax_records["SCHILY.xattr.security.selinux"],
+        "unconfined_u"
+    );
+    assert_eq!(got_hdr.mod_time, time::unix(1500000000, 250000000));
+    assert_eq!(got_hdr.access_time, time::unix(1500000001, 0));
+    assert_eq!(got_hdr.change_time, time::unix(1500000002, 500));
+    assert_eq!(got_data, b"Kilts");
+}
+
+#[test]
+fn test_writer_rounds_mod_time() {
+    // Without an explicit format, sub-second times are rounded,
+    // and the access and change times are dropped.
+    let (mut hdr, data) = file("small.txt", b"Kilts");
+    hdr.mod_time = time::unix(1500000000, 600000000);
+    hdr.access_time = time::unix(1500000001, 0);
+    let out = write_entries(&[(hdr, data)]);
+    let got = read_entries(&out);
+    assert_eq!(got[0].0.format, Format::USTAR);
+    assert_eq!(got[0].0.mod_time, time::unix(1500000001, 0));
+    assert!(got[0].0.access_time.is_zero());
+}
+
+#[test]
+fn test_writer_global_header() {
+    let mut pax_records = BTreeMap::new();
+    pax_records.insert("path".to_string(), "global".to_string());
+    let global = Header {
+        typeflag: TYPE_XGLOBAL_HEADER,
+        pax_records,
+        ..Default::default()
+    };
+    let out = write_entries(&[(global, Vec::new()), file("small.txt", b"Kilts")]);
+    let got = read_entries(&out);
+    assert_eq!(got.len(), 2);
+    assert_eq!(got[0].0.typeflag, TYPE_XGLOBAL_HEADER);
+    assert_eq!(got[0].0.pax_records["path"], "global");
+    // Global records are not applied to the following files.
+    assert_eq!(got[1].0.name, "small.txt");
+    assert_eq!(got[1].1, b"Kilts");
+}
+
+#[test]
+fn test_writer_reg_a() {
+    let out = write_entries(&[
+        (
+            Header {
+                typeflag: TYPE_REG_A,
+                name: "dir/".to_string(),
+                ..Default::default()
+            },
+            Vec::new(),
+        ),
+        (
+            Header {
+                typeflag: TYPE_REG_A,
+                name: "file".to_string(),
+                size: 2,
+                ..Default::default()
+            },
+            b"hi".to_vec(),
+        ),
+    ]);
+    let got = read_entries(&out);
+    assert_eq!(got[0].0.typeflag, TYPE_DIR);
+    assert_eq!(got[1].0.typeflag, TYPE_REG);
+    assert_eq!(got[1].1, b"hi");
+}
+
+#[test]
+fn test_writer_sparse() {
+    let mut data = vec![0; 400000];
+    data[..3].copy_from_slice(b"abc");
+    data[200000..200003].copy_from_slice(b"xyz");
+    let sparse_holes = vec![
+        SparseEntry {
+            offset: 4096,
+            length: 192512,
+        },
+        SparseEntry {
+            offset: 200704,
+            length: 199296,
+        },
+    ];
+    // Many holes do not fit into the GNU header and need extension blocks.
+    let many_holes: Vec<SparseEntry> = (0..30)
+        .map(|i| SparseEntry {
+            offset: 4096 + i * 10000,
+            length: 5000,
+        })
+        .collect();
+    let tests = [
+        (TYPE_REG, &sparse_holes, Format::PAX),
+        (TYPE_GNU_SPARSE, &sparse_holes, Format::GNU),
+        (TYPE_REG, &many_holes, Format::PAX),
+        (TYPE_GNU_SPARSE, &many_holes, Format::GNU),
+    ];
+    for (typeflag, holes, want_format) in tests {
+        let hdr = Header {
+            typeflag,
+            name: "sparse".to_string(),
+            size: data.len() as i64,
+            mode: 0o644,
+            mod_time: time::unix(1500000000, 0),
+            sparse_holes: holes.clone(),
+            ..Default::default()
+        };
+        let out = write_entries(&[(hdr, data.clone())]);
+        assert!(out.len() < data.len(), "holes must not be stored");
+        let got = read_entries(&out);
+        assert_eq!(got.len(), 1);
+        let (got_hdr, got_data) = &got[0];
+        assert_eq!(got_hdr.name, "sparse");
+        assert_eq!(got_hdr.format, want_format);
+        assert_eq!(got_hdr.size, data.len() as i64);
+        assert!(*got_data == data, "{}: mismatching contents", want_format);
+    }
+}
+
+#[test]
+fn test_writer_sparse_hole_error() {
+    let mut out = Vec::new();
+    let mut tw = Writer::new(&mut out);
+    tw.write_header(&Header {
+        typeflag: TYPE_REG,
+        name: "sparse".to_string(),
+        size: 1024,
+        sparse_holes: vec![SparseEntry {
+            offset: 0,
+            length: 512,
+        }],
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(tw.write(b"\x00\x00x").unwrap(), 2);
+    let err = tw.write(b"x").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "archive/tar: write non-NUL byte in sparse hole"
+    );
+}
+
+#[test]
+fn test_writer_errors() {
+    let mut out = Vec::new();
+    let mut tw = Writer::new(&mut out);
+
+    // Invalid headers are rejected, but are not fatal.
+    let (mut hdr, _) = file("dir/", b"");
+    hdr.typeflag = TYPE_REG;
+    assert!(tw.write_header(&hdr).is_err());
+
+    let (hdr, _) = file("small.txt", b"Kilts");
+    tw.write_header(&hdr).unwrap();
+    assert_eq!(tw.write(b"Kil").unwrap(), 3);
+
+    // The current file must be completely written.
+    let err = tw.write_header(&hdr).unwrap_err();
+    assert_eq!(err.to_string(), "archive/tar: missed writing 2 bytes");
+
+    // Writes are truncated to the file size.
+    assert_eq!(tw.write(b"tsXYZ").unwrap(), 2);
+    let err = tw.write(b"XYZ").unwrap_err();
+    assert_eq!(err.to_string(), ERR_WRITE_TOO_LONG);
+
+    // Header-only types have no data.
+    tw.write_header(&Header {
+        typeflag: TYPE_DIR,
+        name: "dir/".to_string(),
+        size: 5,
+        ..Default::default()
+    })
+    .unwrap();
+    let err = tw.write(b"x").unwrap_err();
+    assert_eq!(err.to_string(), ERR_WRITE_TOO_LONG);
+
+    tw.close().unwrap();
+    tw.close().unwrap();
+    let err = tw.write_header(&hdr).unwrap_err();
+    assert_eq!(err.to_string(), ERR_WRITE_AFTER_CLOSE);
+    let err = tw.write(b"x").unwrap_err();
+    assert_eq!(err.to_string(), ERR_WRITE_AFTER_CLOSE);
+
+    let got = read_entries(&out);
+    assert_eq!(got.len(), 2);
+    assert_eq!(got[0].1, b"Kilts");
+}
+
+#[test]
+fn test_writer_close_incomplete() {
+    let mut out = Vec::new();
+    let mut tw = Writer::new(&mut out);
+    let (hdr, _) = file("small.txt", b"Kilts");
+    tw.write_header(&hdr).unwrap();
+    assert!(tw.close().is_err());
+}
+
+#[test]
+fn test_writer_gzip() {
+    let entries = vec![file("small.txt", b"Kilts"), file("small2.txt", b"Google")];
+    let mut compressed = Vec::new();
+    {
+        let mut zw = gzip::Writer::new(&mut compressed);
+        let mut tw = Writer::new(&mut zw);
+        for (hdr, data) in &entries {
+            tw.write_header(hdr).unwrap();
+            tw.write_all(data).unwrap();
+        }
+        tw.close().unwrap();
+        zw.close().unwrap();
+    }
+    let mut input = compressed.as_slice();
+    let mut zr = gzip::Reader::new(&mut input).unwrap();
+    let mut tr = Reader::new(&mut zr);
+    for (hdr, data) in &entries {
+        let got_hdr = tr.next().unwrap().unwrap();
+        assert_eq!(got_hdr.name, hdr.name);
+        let mut got_data = Vec::new();
+        tr.read_to_end(&mut got_data).unwrap();
+        assert_eq!(got_data, *data);
+    }
+    assert!(tr.next().unwrap().is_none());
+}
+
+#[test]
+fn test_writer_owns_output() {
+    // Writer and Reader can own their streams, and the streams can be
+    // taken back once the archive is done.
+    let (hdr, data) = file("small.txt", b"Kilts");
+    let mut tw = Writer::new(gzip::Writer::new(Vec::new()));
+    tw.write_header(&hdr).unwrap();
+    tw.write_all(&data).unwrap();
+    tw.get_mut().flush().unwrap();
+    tw.close().unwrap();
+    let mut zw = tw.into_inner();
+    zw.close().unwrap();
+    let compressed = zw.into_inner();
+    let compressed_len = compressed.len();
+
+    let zr = gzip::Reader::new(std::io::Cursor::new(compressed)).unwrap();
+    let mut tr = Reader::new(zr);
+    assert_eq!(tr.next().unwrap().unwrap().name, "small.txt");
+    let mut got = Vec::new();
+    tr.read_to_end(&mut got).unwrap();
+    assert_eq!(got, data);
+    assert!(tr.next().unwrap().is_none());
+    assert_eq!(tr.get_ref().get_ref().get_ref().len(), compressed_len);
+    // Only the zero padding after the end of the archive is left.
+    let mut rest = Vec::new();
+    tr.into_inner().read_to_end(&mut rest).unwrap();
+    assert!(rest.iter().all(|&b| b == 0));
+}
+
+#[test]
+fn test_split_ustar_path() {
+    let sr = |s: &str, n: usize| s.repeat(n);
+    let tests: Vec<(String, Option<(String, String)>)> = vec![
+        (String::new(), None),
+        ("abc".to_string(), None),
+        ("用戶名".to_string(), None),
+        (sr("a", 100), None),
+        (sr("a", 100) + "/", None),
+        (sr("a", 100) + "/a", Some((sr("a", 100), "a".to_string()))),
+        (
+            sr("a", 100) + "/" + &sr("a", 100),
+            Some((sr("a", 100), sr("a", 100))),
+        ),
+        (sr("a/", 50), None),
+        (sr("a/", 60), Some((sr("a/", 58) + "a", "a/".to_string()))),
+        (sr("a/", 80), Some((sr("a/", 77) + "a", "a/a/".to_string()))),
+        (
+            sr("a", 155) + "/" + &sr("a", 100),
+            Some((sr("a", 155), sr("a", 100))),
+        ),
+        (sr("a", 155) + "/" + &sr("a", 101), None),
+        (sr("a", 156) + "/" + &sr("a", 100), None),
+    ];
+    for (input, want) in tests {
+        let got = split_ustar_path(&input).map(|(p, s)| (p.to_string(), s.to_string()));
+        assert_eq!(got, want, "split_ustar_path({:?})", input);
+    }
+}
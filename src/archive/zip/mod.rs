@@ -0,0 +1,35 @@
+// Copyright 2023 The rust-ggstd authors.
+// Copyright 2010 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! Package zip provides support for reading and writing ZIP archives.
+//!
+//! See the [ZIP specification](https://www.pkware.com/appnote) for details.
+//!
+//! This package does not support disk spanning.
+//!
+//! A note about ZIP64:
+//!
+//! To be backwards compatible the FileHeader has both 32 and 64 bit Size
+//! fields in the ZIP format. The 64 bit fields will always contain the
+//! correct value and for normal archives both fields will be the same.
+//! For files requiring the ZIP64 format the 32 bit fields will be
+//! 0xffffffff and the 64 bit fields must be used instead. FileHeader
+//! only exposes the 64 bit fields.
+
+mod reader;
+mod struct_;
+mod writer;
+
+pub use reader::{File, FileReader, Reader, ERR_ALGORITHM, ERR_CHECKSUM, ERR_FORMAT};
+pub use struct_::{
+    FileHeader, DEFLATE, DEFLATE64, STORE, S_IFBLK, S_IFCHR, S_IFDIR, S_IFIFO, S_IFLNK, S_IFMT,
+    S_IFREG, S_IFSOCK,
+};
+pub use writer::{FileWriter, Writer};
+
+#[cfg(test)]
+mod reader_test;
+#[cfg(test)]
+mod writer_test;
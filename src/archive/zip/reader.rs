@@ -0,0 +1,686 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2010 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::struct_::{
+    ms_dos_time_to_time, FileHeader, ReadBuf, DATA_DESCRIPTOR_LEN, DATA_DESCRIPTOR_SIGNATURE,
+    DEFLATE, DEFLATE64, DIRECTORY64_END_LEN, DIRECTORY64_END_SIGNATURE, DIRECTORY64_LOC_LEN,
+    DIRECTORY64_LOC_SIGNATURE, DIRECTORY_END_LEN, DIRECTORY_HEADER_LEN, DIRECTORY_HEADER_SIGNATURE,
+    EXT_TIME_EXTRA_ID, FILE_HEADER_LEN, FILE_HEADER_SIGNATURE, INFO_ZIP_UNIX_EXTRA_ID,
+    NTFS_EXTRA_ID, STORE, UNIX_EXTRA_ID, ZIP64_EXTRA_ID,
+};
+use super::writer::detect_utf8;
+use crate::compress::flate;
+use crate::hash::{crc32, Hash32};
+use crate::time;
+use std::io::{Read, Seek, SeekFrom, Take};
+
+/// ERR_FORMAT is the error message returned when the input is not a valid zip file.
+pub const ERR_FORMAT: &str = "zip: not a valid zip file";
+/// ERR_ALGORITHM is the error message returned for an unsupported compression method.
+pub const ERR_ALGORITHM: &str = "zip: unsupported compression algorithm";
+/// ERR_CHECKSUM is the error message returned when the file contents do not
+/// match the checksum.
+pub const ERR_CHECKSUM: &str = "zip: checksum error";
+
+pub(super) fn new_format_error() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, ERR_FORMAT)
+}
+
+pub(super) fn new_algorithm_error() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::Unsupported, ERR_ALGORITHM)
+}
+
+fn new_checksum_error() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, ERR_CHECKSUM)
+}
+
+/// Reader provides read access to the contents of a ZIP archive.
+pub struct Reader<Input: Read + Seek> {
+    r: Input,
+    base_offset: i64,
+    /// file lists the files of the archive in the central directory order.
+    pub file: Vec<File>,
+    /// comment is the archive comment.
+    pub comment: String,
+}
+
+/// File is a single file in a ZIP archive.
+/// The file information is in the header field.
+/// The file content can be accessed by calling Reader::open.
+#[derive(Debug, Clone)]
+pub struct File {
+    pub header: FileHeader,
+    header_offset: i64,
+}
+
+struct DirectoryEnd {
+    dir_records: u64,
+    directory_size: u64,
+    directory_offset: u64, // relative to file
+    comment: String,
+}
+
+impl<Input: Read + Seek> Reader<Input> {
+    /// new returns a new Reader reading from r.
+    /// The size of the archive is determined by seeking to the end of r.
+    /// To keep using r after the Reader is done, pass a mutable reference.
+    pub fn new(mut r: Input) -> std::io::Result<Self> {
+        let size = r.seek(SeekFrom::End(0))? as i64;
+        let mut zr = Self {
+            r,
+            base_offset: 0,
+            file: Vec::new(),
+            comment: String::new(),
+        };
+        zr.init(size)?;
+        Ok(zr)
+    }
+
+    /// get_ref returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &Input {
+        &self.r
+    }
+
+    /// get_mut returns a mutable reference to the underlying reader.
+    /// The Reader seeks before reading each file, so moving the
+    /// underlying reader between calls to open doesn't affect it.
+    pub fn get_mut(&mut self) -> &mut Input {
+        &mut self.r
+    }
+
+    /// into_inner returns the underlying reader.
+    pub fn into_inner(self) -> Input {
+        self.r
+    }
+
+    fn init(&mut self, size: i64) -> std::io::Result<()> {
+        let (end, base_offset) = read_directory_end(&mut self.r, size)?;
+        self.base_offset = base_offset;
+        self.comment = end.comment;
+        let directory_offset = self
+            .base_offset
+            .checked_add(end.directory_offset as i64)
+            .ok_or_else(new_format_error)?;
+        self.r.seek(SeekFrom::Start(directory_offset as u64))?;
+        let mut buf = std::io::BufReader::new(&mut self.r);
+
+        // The count of files inside a zip is truncated to fit in a u16.
+        // Gloss over this by reading headers until we encounter
+        // a bad one, and then only report an ERR_FORMAT or UnexpectedEof if
+        // the file count modulo 65536 is incorrect.
+        let err = loop {
+            match read_directory_header(&mut buf) {
+                Ok(mut f) => {
+                    f.header_offset = f
+                        .header_offset
+                        .checked_add(self.base_offset)
+                        .ok_or_else(new_format_error)?;
+                    self.file.push(f);
+                }
+                Err(err)
+                    if err.kind() == std::io::ErrorKind::InvalidData
+                        || err.kind() == std::io::ErrorKind::UnexpectedEof =>
+                {
+                    break err
+                }
+                Err(err) => return Err(err),
+            }
+        };
+        if self.file.len() as u16 != end.dir_records as u16 {
+            // only compare 16 bits here
+            // Return the read_directory_header error if we read
+            // the wrong number of directory entries.
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// open returns a FileReader that provides access to the contents
+    /// of the i-th file of the archive.
+    /// Multiple files may not be read concurrently.
+    pub fn open(&mut self, i: usize) -> std::io::Result<FileReader<'_, Input>> {
+        let f = &self.file[i];
+        let body_offset = find_body_offset(&mut self.r, f)?;
+        if f.header.name.ends_with('/') {
+            // The ZIP specification (APPNOTE.TXT) specifies that directories, which
+            // are technically zero-byte files, must not have any associated file
+            // data. A number of implementations (namely, the Java jar tool) don't
+            // properly set the storage method on directories resulting in a file
+            // with compressed size > 0 but uncompressed size == 0. We still want
+            // to fail when a directory has associated uncompressed data, but we
+            // are tolerant of cases where the uncompressed size is zero but
+            // compressed size is not.
+            return Ok(FileReader {
+                rc: Decompressor::Dir,
+                hash: crc32::new_ieee(),
+                header: &f.header,
+                nread: 0,
+                desr_offset: None,
+                err: if f.header.uncompressed_size64 != 0 {
+                    Some(new_format_error())
+                } else {
+                    None
+                },
+            });
+        }
+        let size = f.header.compressed_size64;
+        let offset = (f.header_offset + body_offset) as u64;
+        self.r.seek(SeekFrom::Start(offset))?;
+        let r = (&mut self.r).take(size);
+        let rc = match f.header.method {
+            STORE => Decompressor::Store(r),
+            DEFLATE => {
+                Decompressor::Deflate(Box::new(flate::Reader::new(std::io::BufReader::new(r))))
+            }
+            DEFLATE64 => Decompressor::Deflate(Box::new(flate::Reader::new_deflate64(
+                std::io::BufReader::new(r),
+            ))),
+            _ => return Err(new_algorithm_error()),
+        };
+        let desr_offset = if f.header.has_data_descriptor() {
+            Some(offset + size)
+        } else {
+            None
+        };
+        Ok(FileReader {
+            rc,
+            hash: crc32::new_ieee(),
+            header: &f.header,
+            nread: 0,
+            desr_offset,
+            err: None,
+        })
+    }
+
+    /// open_raw returns a reader that provides access to the i-th file's
+    /// contents without decompression.
+    pub fn open_raw(&mut self, i: usize) -> std::io::Result<Take<&mut Input>> {
+        let f = &self.file[i];
+        let body_offset = find_body_offset(&mut self.r, f)?;
+        self.r
+            .seek(SeekFrom::Start((f.header_offset + body_offset) as u64))?;
+        Ok((&mut self.r).take(f.header.compressed_size64))
+    }
+
+    /// data_offset returns the offset of the i-th file's possibly-compressed
+    /// data, relative to the beginning of the zip file.
+    ///
+    /// Most callers should instead use open, which transparently
+    /// decompresses data and verifies checksums.
+    pub fn data_offset(&mut self, i: usize) -> std::io::Result<i64> {
+        let f = &self.file[i];
+        let body_offset = find_body_offset(&mut self.r, f)?;
+        Ok(f.header_offset + body_offset)
+    }
+}
+
+enum Decompressor<'a, Input: Read> {
+    Dir,
+    Store(Take<&'a mut Input>),
+    Deflate(Box<flate::Reader<std::io::BufReader<Take<&'a mut Input>>>>),
+}
+
+impl<Input: Read> Decompressor<'_, Input> {
+    fn input(&mut self) -> Option<&mut Input> {
+        match self {
+            Decompressor::Dir => None,
+            Decompressor::Store(r) => Some(r.get_mut()),
+            Decompressor::Deflate(r) => Some(r.input_reader().get_mut().get_mut()),
+        }
+    }
+}
+
+/// FileReader reads the contents of a file in a ZIP archive,
+/// verifying its size and CRC-32 checksum when the end is reached.
+pub struct FileReader<'a, Input: Read + Seek> {
+    rc: Decompressor<'a, Input>,
+    hash: crc32::Digest<'static>,
+    header: &'a FileHeader,
+    nread: u64,
+    desr_offset: Option<u64>, // if non-None, where to read the data descriptor
+    err: Option<std::io::Error>,
+}
+
+impl<Input: Read + Seek> FileReader<'_, Input> {
+    /// header returns the header of the file being read.
+    pub fn header(&self) -> &FileHeader {
+        self.header
+    }
+
+    fn finish(&mut self) -> std::io::Result<()> {
+        if self.nread != self.header.uncompressed_size64 {
+            return Err(crate::errors::new_unexpected_eof());
+        }
+        if let Some(offset) = self.desr_offset {
+            let r = self.rc.input().unwrap();
+            r.seek(SeekFrom::Start(offset))?;
+            read_data_descriptor(r, self.header)?;
+            if self.hash.sum32() != self.header.crc32 {
+                return Err(new_checksum_error());
+            }
+        } else {
+            // If there's not a data descriptor, we still compare
+            // the CRC32 of what we've read against the file header
+            // or TOC's CRC32, if it seems like it was set.
+            if self.header.crc32 != 0 && self.hash.sum32() != self.header.crc32 {
+                return Err(new_checksum_error());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<Input: Read + Seek> Read for FileReader<'_, Input> {
+    fn read(&mut self, b: &mut [u8]) -> std::io::Result<usize> {
+        if let Some(err) = &self.err {
+            return Err(crate::errors::copy_stdio_error(err));
+        }
+        let res = match &mut self.rc {
+            Decompressor::Dir => Ok(0),
+            Decompressor::Store(r) => r.read(b),
+            Decompressor::Deflate(r) => r.read(b),
+        };
+        let n = match res {
+            Ok(n) => n,
+            Err(err) => {
+                self.err = Some(crate::errors::copy_stdio_error(&err));
+                return Err(err);
+            }
+        };
+        std::io::Write::write_all(&mut self.hash, &b[..n]).unwrap();
+        self.nread += n as u64;
+        if self.nread > self.header.uncompressed_size64 {
+            self.err = Some(new_format_error());
+            return Err(new_format_error());
+        }
+        if n == 0 && !b.is_empty() {
+            if let Err(err) = self.finish() {
+                self.err = Some(crate::errors::copy_stdio_error(&err));
+                return Err(err);
+            }
+        }
+        Ok(n)
+    }
+}
+
+/// read_at reads exactly buf.len() bytes at the given offset of r.
+fn read_at<Input: Read + Seek>(r: &mut Input, buf: &mut [u8], offset: i64) -> std::io::Result<()> {
+    r.seek(SeekFrom::Start(offset as u64))?;
+    r.read_exact(buf)
+}
+
+/// find_body_offset does the minimum work to verify the file has a header
+/// and returns the file body offset.
+fn find_body_offset<Input: Read + Seek>(r: &mut Input, f: &File) -> std::io::Result<i64> {
+    let mut buf = [0; FILE_HEADER_LEN];
+    read_at(r, &mut buf, f.header_offset)?;
+    let mut b = ReadBuf(&buf);
+    if b.uint32() != FILE_HEADER_SIGNATURE {
+        return Err(new_format_error());
+    }
+    b.skip(22); // skip over most of the header
+    let filename_len = b.uint16() as i64;
+    let extra_len = b.uint16() as i64;
+    Ok(FILE_HEADER_LEN as i64 + filename_len + extra_len)
+}
+
+/// read_directory_header attempts to read a directory header from r.
+/// It returns an UnexpectedEof error if it cannot read a complete header,
+/// and ERR_FORMAT if it doesn't find a valid header signature.
+fn read_directory_header(r: &mut impl Read) -> std::io::Result<File> {
+    let mut buf = [0; DIRECTORY_HEADER_LEN];
+    r.read_exact(&mut buf)?;
+    let mut b = ReadBuf(&buf);
+    if b.uint32() != DIRECTORY_HEADER_SIGNATURE {
+        return Err(new_format_error());
+    }
+    let mut h = FileHeader {
+        creator_version: b.uint16(),
+        reader_version: b.uint16(),
+        flags: b.uint16(),
+        method: b.uint16(),
+        modified_time: b.uint16(),
+        modified_date: b.uint16(),
+        crc32: b.uint32(),
+        compressed_size64: b.uint32() as u64,
+        uncompressed_size64: b.uint32() as u64,
+        ..Default::default()
+    };
+    let filename_len = b.uint16() as usize;
+    let extra_len = b.uint16() as usize;
+    let comment_len = b.uint16() as usize;
+    b.skip(4); // skipped start disk number and internal attributes (2x u16)
+    h.external_attrs = b.uint32();
+    let mut header_offset = b.uint32() as i64;
+    let mut d = vec![0; filename_len + extra_len + comment_len];
+    r.read_exact(&mut d)?;
+    let name = &d[..filename_len];
+    let comment = &d[filename_len + extra_len..];
+    h.name = String::from_utf8_lossy(name).into_owned();
+    h.extra = d[filename_len..filename_len + extra_len].to_vec();
+    h.comment = String::from_utf8_lossy(comment).into_owned();
+
+    // Determine the character encoding.
+    let (utf8_valid1, utf8_require1) = detect_utf8(name);
+    let (utf8_valid2, utf8_require2) = detect_utf8(comment);
+    h.non_utf8 = if !utf8_valid1 || !utf8_valid2 {
+        // Name and Comment definitely not UTF-8.
+        true
+    } else if !utf8_require1 && !utf8_require2 {
+        // Name and Comment use only single-byte runes that overlap with UTF-8.
+        false
+    } else {
+        // Might be UTF-8, might be some other encoding; preserve existing flag.
+        // Some ZIP writers use UTF-8 encoding without setting the UTF-8 flag.
+        // Since it is impossible to always distinguish valid UTF-8 from some
+        // other encoding (e.g., GBK or Shift-JIS), we trust the flag.
+        h.flags & 0x800 == 0
+    };
+
+    let mut need_usize = h.uncompressed_size64 == u32::MAX as u64;
+    let mut need_csize = h.compressed_size64 == u32::MAX as u64;
+    let mut need_header_offset = header_offset == u32::MAX as i64;
+
+    // Best effort to find what we need.
+    // Other zip authors might not even follow the basic format,
+    // and we'll just ignore the extra content in that case.
+    let mut modified = None;
+    let mut extra = ReadBuf(&h.extra);
+    'parse_extras: while extra.len() >= 4 {
+        // need at least tag and size
+        let field_tag = extra.uint16();
+        let field_size = extra.uint16() as usize;
+        if extra.len() < field_size {
+            break;
+        }
+        let mut field_buf = extra.sub(field_size);
+
+        match field_tag {
+            ZIP64_EXTRA_ID => {
+                // update directory values from the zip64 extra block.
+                // They should only be consulted if the sizes read earlier
+                // are maxed out.
+                // See golang.org/issue/13367.
+                if need_usize {
+                    need_usize = false;
+                    if field_buf.len() < 8 {
+                        return Err(new_format_error());
+                    }
+                    h.uncompressed_size64 = field_buf.uint64();
+                }
+                if need_csize {
+                    need_csize = false;
+                    if field_buf.len() < 8 {
+                        return Err(new_format_error());
+                    }
+                    h.compressed_size64 = field_buf.uint64();
+                }
+                if need_header_offset {
+                    need_header_offset = false;
+                    if field_buf.len() < 8 {
+                        return Err(new_format_error());
+                    }
+                    header_offset = field_buf.uint64() as i64;
+                }
+            }
+            NTFS_EXTRA_ID => {
+                if field_buf.len() < 4 {
+                    continue 'parse_extras;
+                }
+                field_buf.uint32(); // reserved (ignored)
+                while field_buf.len() >= 4 {
+                    // need at least tag and size
+                    let attr_tag = field_buf.uint16();
+                    let attr_size = field_buf.uint16() as usize;
+                    if field_buf.len() < attr_size {
+                        continue 'parse_extras;
+                    }
+                    let mut attr_buf = field_buf.sub(attr_size);
+                    if attr_tag != 1 || attr_size != 24 {
+                        continue; // Ignore irrelevant attributes
+                    }
+
+                    const TICKS_PER_SECOND: i64 = 10_000_000; // Windows timestamp resolution
+                    let ts = attr_buf.uint64() as i64; // ModTime since Windows epoch
+                    let secs = ts / TICKS_PER_SECOND;
+                    let nsecs = (1_000_000_000 / TICKS_PER_SECOND) * (ts % TICKS_PER_SECOND);
+                    let epoch = time::date(1601, 1, 1, 0, 0, 0, 0);
+                    modified = Some(time::unix(epoch.unix() + secs, nsecs));
+                }
+            }
+            UNIX_EXTRA_ID | INFO_ZIP_UNIX_EXTRA_ID => {
+                if field_buf.len() < 8 {
+                    continue 'parse_extras;
+                }
+                field_buf.uint32(); // AcTime (ignored)
+                let ts = field_buf.uint32() as i64; // ModTime since Unix epoch
+                modified = Some(time::unix(ts, 0));
+            }
+            EXT_TIME_EXTRA_ID => {
+                if field_buf.len() < 5 || field_buf.uint8() & 1 == 0 {
+                    continue 'parse_extras;
+                }
+                let ts = field_buf.uint32() as i64; // ModTime since Unix epoch
+                modified = Some(time::unix(ts, 0));
+            }
+            _ => {}
+        }
+    }
+
+    // The legacy MS-DOS timestamp has no time zone and is reported as UTC.
+    // time::Time has no Location, so unlike Go no time zone is derived
+    // from the difference with the extended timestamp.
+    h.modified = match modified {
+        Some(t) if !t.is_zero() => t,
+        _ => ms_dos_time_to_time(h.modified_date, h.modified_time),
+    };
+
+    // Assume that uncompressed size 2³²-1 could plausibly happen in
+    // an old zip32 file that was sharding inputs into the largest chunks
+    // possible (or is just malicious; search the web for 42.zip).
+    // If need_usize is true still, it means we didn't see a zip64 extension.
+    // As long as the compressed size is not also 2³²-1 (implausible)
+    // and the header is not also 2³²-1 (equally implausible),
+    // accept the uncompressed size 2³²-1 as valid.
+    // If nothing else, this keeps archive/zip working with 42.zip.
+    let _ = need_usize;
+
+    if need_csize || need_header_offset {
+        return Err(new_format_error());
+    }
+    Ok(File {
+        header: h,
+        header_offset,
+    })
+}
+
+fn read_data_descriptor(r: &mut impl Read, h: &FileHeader) -> std::io::Result<()> {
+    let mut buf = [0; DATA_DESCRIPTOR_LEN];
+    // The spec says: "Although not originally assigned a
+    // signature, the value 0x08074b50 has commonly been adopted
+    // as a signature value for the data descriptor record.
+    // Implementers should be aware that ZIP files may be
+    // encountered with or without this signature marking data
+    // descriptors and should account for either case when reading
+    // ZIP files to ensure compatibility."
+    //
+    // dataDescriptorLen includes the size of the signature but
+    // first read just those 4 bytes to see if it exists.
+    r.read_exact(&mut buf[..4])?;
+    let mut off = 0;
+    let maybe_sig = ReadBuf(&buf[..4]).uint32();
+    if maybe_sig != DATA_DESCRIPTOR_SIGNATURE {
+        // No data descriptor signature. Keep these four
+        // bytes.
+        off += 4;
+    }
+    r.read_exact(&mut buf[off..12])?;
+    let mut b = ReadBuf(&buf[..12]);
+    if b.uint32() != h.crc32 {
+        return Err(new_checksum_error());
+    }
+
+    // The two sizes that follow here can be either 32 bits or 64 bits
+    // but the spec is not very clear on this and different
+    // interpretations has been made causing incompatibilities. We
+    // already have the sizes from the central directory so we can
+    // just ignore them.
+
+    Ok(())
+}
+
+fn read_directory_end<Input: Read + Seek>(
+    r: &mut Input,
+    size: i64,
+) -> std::io::Result<(DirectoryEnd, i64)> {
+    // look for DIRECTORY_END_SIGNATURE in the last 1k, then in the last 65k
+    let mut buf = Vec::new();
+    let mut directory_end_offset = 0;
+    for (i, b_len) in [1024, 65 * 1024].into_iter().enumerate() {
+        let b_len = std::cmp::min(b_len, size);
+        buf = vec![0; b_len as usize];
+        read_at(r, &mut buf, size - b_len)?;
+        if let Some(p) = find_signature_in_block(&buf) {
+            buf.drain(..p);
+            directory_end_offset = size - b_len + p as i64;
+            break;
+        }
+        if i == 1 || b_len == size {
+            return Err(new_format_error());
+        }
+    }
+
+    // read header into struct
+    let mut b = ReadBuf(&buf[4..]); // skip signature
+    b.skip(4); // skip disk number and directory disk number (2x u16)
+    b.skip(2); // skip number of directory records on this disk
+    let mut d = DirectoryEnd {
+        dir_records: b.uint16() as u64,
+        directory_size: b.uint32() as u64,
+        directory_offset: b.uint32() as u64,
+        comment: String::new(),
+    };
+    let l = b.uint16() as usize;
+    if l > b.len() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "zip: invalid comment length",
+        ));
+    }
+    d.comment = String::from_utf8_lossy(&b.0[..l]).into_owned();
+
+    // These values mean that the file can be a zip64 file
+    if d.dir_records == 0xffff || d.directory_size == 0xffff || d.directory_offset == 0xffffffff {
+        if let Some(p) = find_directory64_end(r, directory_end_offset)? {
+            directory_end_offset = p;
+            read_directory64_end(r, p, &mut d)?;
+        }
+    }
+
+    let max_int64 = i64::MAX as u64;
+    if d.directory_size > max_int64 || d.directory_offset > max_int64 {
+        return Err(new_format_error());
+    }
+
+    // The sizes come from the file, so the arithmetic on them can overflow.
+    let mut base_offset = directory_end_offset
+        .checked_sub(d.directory_size as i64)
+        .and_then(|o| o.checked_sub(d.directory_offset as i64))
+        .ok_or_else(new_format_error)?;
+
+    // Make sure directory_offset points to somewhere in our file.
+    let o = base_offset
+        .checked_add(d.directory_offset as i64)
+        .ok_or_else(new_format_error)?;
+    if o < 0 || o >= size {
+        return Err(new_format_error());
+    }
+
+    // If the directory end data tells us to use a non-zero base_offset,
+    // but we would find a valid directory entry if we assume that the
+    // base_offset is 0, then just use a base_offset of 0.
+    // We've seen files in which the directory end data gives us
+    // an incorrect base_offset.
+    if base_offset > 0 {
+        r.seek(SeekFrom::Start(d.directory_offset))?;
+        if read_directory_header(r).is_ok() {
+            base_offset = 0;
+        }
+    }
+
+    Ok((d, base_offset))
+}
+
+/// find_directory64_end tries to read the zip64 locator just before the
+/// directory end and returns the offset of the zip64 directory end if
+/// found.
+fn find_directory64_end<Input: Read + Seek>(
+    r: &mut Input,
+    directory_end_offset: i64,
+) -> std::io::Result<Option<i64>> {
+    let loc_offset = directory_end_offset - DIRECTORY64_LOC_LEN as i64;
+    if loc_offset < 0 {
+        return Ok(None); // no need to look for a header outside the file
+    }
+    let mut buf = [0; DIRECTORY64_LOC_LEN];
+    read_at(r, &mut buf, loc_offset)?;
+    let mut b = ReadBuf(&buf);
+    if b.uint32() != DIRECTORY64_LOC_SIGNATURE {
+        return Ok(None);
+    }
+    if b.uint32() != 0 {
+        // number of the disk with the start of the zip64 end of central directory
+        return Ok(None); // the file is not a valid zip64-file
+    }
+    let p = b.uint64(); // relative offset of the zip64 end of central directory record
+    if b.uint32() != 1 {
+        // total number of disks
+        return Ok(None); // the file is not a valid zip64-file
+    }
+    Ok(Some(p as i64))
+}
+
+/// read_directory64_end reads the zip64 directory end and updates the
+/// directory end with the zip64 directory end values.
+fn read_directory64_end<Input: Read + Seek>(
+    r: &mut Input,
+    offset: i64,
+    d: &mut DirectoryEnd,
+) -> std::io::Result<()> {
+    let mut buf = [0; DIRECTORY64_END_LEN];
+    read_at(r, &mut buf, offset)?;
+
+    let mut b = ReadBuf(&buf);
+    if b.uint32() != DIRECTORY64_END_SIGNATURE {
+        return Err(new_format_error());
+    }
+
+    b.skip(12); // skip dir size, version and version needed (u64 + 2x u16)
+    b.skip(8); // skip number of this disk and of the disk with the start of the central directory
+    b.skip(8); // skip total number of entries in the central directory on this disk
+    d.dir_records = b.uint64(); // total number of entries in the central directory
+    d.directory_size = b.uint64(); // size of the central directory
+    d.directory_offset = b.uint64(); // offset of start of central directory with respect to the starting disk number
+
+    Ok(())
+}
+
+fn find_signature_in_block(b: &[u8]) -> Option<usize> {
+    if b.len() < DIRECTORY_END_LEN {
+        return None;
+    }
+    for i in (0..=b.len() - DIRECTORY_END_LEN).rev() {
+        // defined from DIRECTORY_END_SIGNATURE in struct_.rs
+        if b[i] == b'P' && b[i + 1] == b'K' && b[i + 2] == 0x05 && b[i + 3] == 0x06 {
+            // n is length of comment
+            let n = b[i + DIRECTORY_END_LEN - 2] as usize
+                | (b[i + DIRECTORY_END_LEN - 1] as usize) << 8;
+            if n + DIRECTORY_END_LEN + i <= b.len() {
+                return Some(i);
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,245 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2010 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::{
+    FileHeader, Reader, Writer, DEFLATE, DEFLATE64, ERR_ALGORITHM, ERR_CHECKSUM, ERR_FORMAT, STORE,
+};
+use super::{S_IFDIR, S_IFMT, S_IFREG};
+use crate::hash::crc32;
+use crate::time;
+use std::io::{Cursor, Read, Write};
+
+const TEST_TXT: &[u8] = b"This is a test text file.\n";
+
+fn big_txt() -> Vec<u8> {
+    b"Go zip test. ".repeat(100)
+}
+
+/// read_all reads the names and contents of all the files in the archive.
+fn read_all(input: &[u8]) -> std::io::Result<Vec<(String, Vec<u8>)>> {
+    let mut r = Cursor::new(input);
+    let mut zr = Reader::new(&mut r)?;
+    let mut res = Vec::new();
+    for i in 0..zr.file.len() {
+        let name = zr.file[i].header.name.clone();
+        let mut data = Vec::new();
+        zr.open(i)?.read_to_end(&mut data)?;
+        res.push((name, data));
+    }
+    Ok(res)
+}
+
+#[test]
+fn test_reader() {
+    let mut f = std::fs::File::open("src/archive/zip/testdata/test.zip").unwrap();
+    let mut zr = Reader::new(&mut f).unwrap();
+    assert_eq!(zr.comment, "archive comment");
+    assert_eq!(zr.file.len(), 3);
+    let modified = time::date(2017, 7, 14, 2, 40, 0, 0);
+    let want = [
+        ("test.txt", STORE, TEST_TXT.to_vec(), 0xc3edd7c0),
+        ("big.txt", DEFLATE, big_txt(), 0x1b69496b),
+        ("dir/", STORE, Vec::new(), 0),
+    ];
+    for (i, (name, method, contents, crc)) in want.into_iter().enumerate() {
+        let h = &zr.file[i].header;
+        assert_eq!(h.name, name);
+        assert_eq!(h.method, method, "{}", name);
+        assert_eq!(h.crc32, crc, "{}", name);
+        assert_eq!(h.uncompressed_size64, contents.len() as u64, "{}", name);
+        assert_eq!(h.modified, modified, "{}", name);
+        assert!(!h.non_utf8, "{}", name);
+        let mut data = Vec::new();
+        zr.open(i).unwrap().read_to_end(&mut data).unwrap();
+        assert!(data == contents, "{}: mismatching contents", name);
+    }
+    assert_eq!(zr.file[0].header.mode() & S_IFMT, S_IFREG);
+    assert_eq!(zr.file[2].header.mode() & S_IFMT, S_IFDIR);
+}
+
+#[test]
+fn test_reader_data_descriptor() {
+    let input = std::fs::read("src/archive/zip/testdata/dd.zip").unwrap();
+    let mut r = Cursor::new(&input);
+    let zr = Reader::new(&mut r).unwrap();
+    for f in zr.file.iter() {
+        assert!(f.header.has_data_descriptor(), "{}", f.header.name);
+    }
+    let files = read_all(&input).unwrap();
+    assert_eq!(
+        files,
+        vec![
+            ("test.txt".to_string(), TEST_TXT.to_vec()),
+            ("big.txt".to_string(), big_txt()),
+        ]
+    );
+}
+
+#[test]
+fn test_reader_zip64() {
+    let mut f = std::fs::File::open("src/archive/zip/testdata/zip64.zip").unwrap();
+    let mut zr = Reader::new(&mut f).unwrap();
+    assert_eq!(zr.file.len(), 1);
+    assert_eq!(zr.file[0].header.name, "test.txt");
+    assert_eq!(zr.file[0].header.uncompressed_size64, TEST_TXT.len() as u64);
+    let mut data = Vec::new();
+    zr.open(0).unwrap().read_to_end(&mut data).unwrap();
+    assert_eq!(data, TEST_TXT);
+}
+
+#[test]
+fn test_reader_open_raw() {
+    let mut f = std::fs::File::open("src/archive/zip/testdata/test.zip").unwrap();
+    let mut zr = Reader::new(&mut f).unwrap();
+    let compressed_size = zr.file[1].header.compressed_size64;
+    let mut raw = Vec::new();
+    zr.open_raw(1).unwrap().read_to_end(&mut raw).unwrap();
+    assert_eq!(raw.len() as u64, compressed_size);
+    assert_eq!(zr.data_offset(0).unwrap(), 30 + "test.txt".len() as i64);
+}
+
+#[test]
+fn test_reader_invalid_checksum() {
+    let mut input = std::fs::read("src/archive/zip/testdata/test.zip").unwrap();
+    // Corrupt the contents of the stored test.txt.
+    let off = input
+        .windows(TEST_TXT.len())
+        .position(|w| w == TEST_TXT)
+        .unwrap();
+    input[off] ^= 0x01;
+    let mut r = Cursor::new(&input);
+    let mut zr = Reader::new(&mut r).unwrap();
+    let mut data = Vec::new();
+    let err = zr.open(0).unwrap().read_to_end(&mut data).unwrap_err();
+    assert_eq!(err.to_string(), ERR_CHECKSUM);
+
+    // The data descriptor checksum is verified too.
+    let mut input = std::fs::read("src/archive/zip/testdata/dd.zip").unwrap();
+    let off = input
+        .windows(4)
+        .position(|w| w == [0x50, 0x4b, 0x07, 0x08])
+        .unwrap();
+    input[off + 4] ^= 0x01;
+    let err = read_all(&input).unwrap_err();
+    assert_eq!(err.to_string(), ERR_CHECKSUM);
+}
+
+#[test]
+fn test_reader_invalid() {
+    let input = std::fs::read("src/archive/zip/testdata/test.zip").unwrap();
+    let tests: &[&[u8]] = &[
+        b"",
+        b"not a zip file",
+        &input[..input.len() / 2],
+        // the end record without the central directory
+        &input[input.len() - 22 - 16..],
+    ];
+    for t in tests {
+        let mut r = Cursor::new(t);
+        match Reader::new(&mut r) {
+            Ok(_) => panic!("unexpected success on {} bytes", t.len()),
+            Err(err) => assert_eq!(err.to_string(), ERR_FORMAT, "{} bytes", t.len()),
+        }
+    }
+}
+
+#[test]
+fn test_reader_zip64_overflow() {
+    // A zip64 directory end whose size and offset are both i64::MAX,
+    // which used to overflow when computing the base offset.
+    let mut input = Vec::new();
+    // zip64 directory end
+    input.extend_from_slice(&[0x50, 0x4b, 0x06, 0x06]);
+    input.extend_from_slice(&[0; 28]);
+    input.extend_from_slice(&1u64.to_le_bytes()); // entries
+    input.extend_from_slice(&(i64::MAX as u64).to_le_bytes()); // directory size
+    input.extend_from_slice(&(i64::MAX as u64).to_le_bytes()); // directory offset
+
+    // zip64 directory end locator
+    input.extend_from_slice(&[0x50, 0x4b, 0x06, 0x07]);
+    input.extend_from_slice(&0u32.to_le_bytes()); // disk
+    input.extend_from_slice(&0u64.to_le_bytes()); // offset of the zip64 directory end
+    input.extend_from_slice(&1u32.to_le_bytes()); // total disks
+
+    // directory end
+    input.extend_from_slice(&[0x50, 0x4b, 0x05, 0x06]);
+    input.extend_from_slice(&[0; 4]);
+    input.extend_from_slice(&[0xff; 12]);
+    input.extend_from_slice(&[0; 2]);
+
+    let mut r = Cursor::new(&input);
+    match Reader::new(&mut r) {
+        Ok(_) => panic!("unexpected success"),
+        Err(err) => assert_eq!(err.to_string(), ERR_FORMAT),
+    }
+}
+
+#[test]
+fn test_reader_truncated_data() {
+    // Truncate the data of the deflated file in the middle.
+    let mut buf = Vec::new();
+    let mut zw = Writer::new(&mut buf);
+    let mut fw = zw.create("big.txt").unwrap();
+    fw.write_all(&big_txt()).unwrap();
+    fw.close().unwrap();
+    zw.close().unwrap();
+
+    let mut r = Cursor::new(&buf);
+    let mut zr = Reader::new(&mut r).unwrap();
+    zr.file[0].header.compressed_size64 /= 2;
+    let mut data = Vec::new();
+    let err = zr.open(0).unwrap().read_to_end(&mut data).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_reader_deflate64() {
+    // "a" followed by a copy of length 40003 at distance 1, which needs
+    // the Deflate64 length code 285 with 16 extra bits.
+    let compressed = b"\x4b\x1c\x05\xe2\x04\x00";
+    let contents = vec![b'a'; 40004];
+    let mut buf = Vec::new();
+    let mut zw = Writer::new(&mut buf);
+    let fh = FileHeader {
+        name: "a.txt".to_string(),
+        method: DEFLATE64,
+        crc32: crc32::checksum_ieee(&contents),
+        compressed_size64: compressed.len() as u64,
+        uncompressed_size64: contents.len() as u64,
+        ..Default::default()
+    };
+    let mut fw = zw.create_raw(&fh).unwrap();
+    fw.write_all(compressed).unwrap();
+    fw.close().unwrap();
+    zw.close().unwrap();
+
+    assert_eq!(
+        read_all(&buf).unwrap(),
+        vec![("a.txt".to_string(), contents)]
+    );
+}
+
+#[test]
+fn test_reader_unsupported_method() {
+    let mut buf = Vec::new();
+    let mut zw = Writer::new(&mut buf);
+    let fh = FileHeader {
+        name: "file".to_string(),
+        method: 99,
+        compressed_size64: 4,
+        uncompressed_size64: 4,
+        ..Default::default()
+    };
+    let mut fw = zw.create_raw(&fh).unwrap();
+    fw.write_all(b"data").unwrap();
+    fw.close().unwrap();
+    zw.close().unwrap();
+
+    let mut r = Cursor::new(&buf);
+    let mut zr = Reader::new(&mut r).unwrap();
+    assert_eq!(zr.file[0].header.method, 99);
+    let err = zr.open(0).err().unwrap();
+    assert_eq!(err.to_string(), ERR_ALGORITHM);
+}
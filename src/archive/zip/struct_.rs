@@ -0,0 +1,294 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2010 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use crate::time;
+
+// Compression methods.
+
+/// STORE is the method for entries stored without compression.
+pub const STORE: u16 = 0;
+/// DEFLATE is the method for DEFLATE compressed entries.
+pub const DEFLATE: u16 = 8;
+/// DEFLATE64 is the method for entries compressed with Deflate64,
+/// also known as enhanced deflate. It is only supported for reading.
+pub const DEFLATE64: u16 = 9;
+
+pub(super) const FILE_HEADER_SIGNATURE: u32 = 0x04034b50;
+pub(super) const DIRECTORY_HEADER_SIGNATURE: u32 = 0x02014b50;
+pub(super) const DIRECTORY_END_SIGNATURE: u32 = 0x06054b50;
+pub(super) const DIRECTORY64_LOC_SIGNATURE: u32 = 0x07064b50;
+pub(super) const DIRECTORY64_END_SIGNATURE: u32 = 0x06064b50;
+pub(super) const DATA_DESCRIPTOR_SIGNATURE: u32 = 0x08074b50; // de-facto standard; required by OS X Finder
+pub(super) const FILE_HEADER_LEN: usize = 30; // + filename + extra
+pub(super) const DIRECTORY_HEADER_LEN: usize = 46; // + filename + extra + comment
+pub(super) const DIRECTORY_END_LEN: usize = 22; // + comment
+pub(super) const DATA_DESCRIPTOR_LEN: usize = 16; // four u32: descriptor signature, crc32, compressed size, size
+pub(super) const DATA_DESCRIPTOR64_LEN: usize = 24; // two u32: signature, crc32 | two u64: compressed size, size
+pub(super) const DIRECTORY64_LOC_LEN: usize = 20;
+pub(super) const DIRECTORY64_END_LEN: usize = 56; // + extra
+
+// Constants for the first byte in creator_version.
+pub(super) const CREATOR_FAT: u16 = 0;
+pub(super) const CREATOR_UNIX: u16 = 3;
+pub(super) const CREATOR_NTFS: u16 = 11;
+pub(super) const CREATOR_VFAT: u16 = 14;
+pub(super) const CREATOR_MACOSX: u16 = 19;
+
+// Version numbers.
+pub(super) const ZIP_VERSION20: u16 = 20; // 2.0
+pub(super) const ZIP_VERSION45: u16 = 45; // 4.5 (reads and writes zip64 archives)
+
+// Limits for non zip64 files.
+pub(super) const UINT16MAX: u64 = (1 << 16) - 1;
+pub(super) const UINT32MAX: u64 = (1 << 32) - 1;
+
+// Extra header IDs.
+//
+// IDs 0..31 are reserved for official use by PKWARE.
+// IDs above that range are defined by third-party vendors.
+// Since ZIP lacked high precision timestamps (nor an official specification
+// of the timezone used for the date fields), many competing extra fields
+// have been invented. Pervasive use effectively makes them "official".
+//
+// See http://mdfs.net/Docs/Comp/Archiving/Zip/ExtraField
+pub(super) const ZIP64_EXTRA_ID: u16 = 0x0001; // Zip64 extended information
+pub(super) const NTFS_EXTRA_ID: u16 = 0x000a; // NTFS
+pub(super) const UNIX_EXTRA_ID: u16 = 0x000d; // UNIX
+pub(super) const EXT_TIME_EXTRA_ID: u16 = 0x5455; // Extended timestamp
+pub(super) const INFO_ZIP_UNIX_EXTRA_ID: u16 = 0x5855; // Info-ZIP Unix extension
+
+// Unix file mode bits as used by the mode and set_mode methods.
+pub const S_IFMT: u32 = 0o170000;
+pub const S_IFSOCK: u32 = 0o140000;
+pub const S_IFLNK: u32 = 0o120000;
+pub const S_IFREG: u32 = 0o100000;
+pub const S_IFBLK: u32 = 0o060000;
+pub const S_IFDIR: u32 = 0o040000;
+pub const S_IFCHR: u32 = 0o020000;
+pub const S_IFIFO: u32 = 0o010000;
+
+// MS-DOS file attributes.
+const MSDOS_DIR: u32 = 0x10;
+const MSDOS_READ_ONLY: u32 = 0x01;
+
+/// FileHeader describes a file within a ZIP file.
+/// See the [ZIP specification](https://www.pkware.com/appnote) for details.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FileHeader {
+    /// name is the name of the file.
+    ///
+    /// It must be a relative path, not start with a drive letter (such as "C:"),
+    /// and must use forward slashes instead of back slashes. A trailing slash
+    /// indicates that this file is a directory and should have no data.
+    pub name: String,
+
+    /// comment is any arbitrary user-defined string shorter than 64KiB.
+    pub comment: String,
+
+    /// non_utf8 indicates that name and comment are not encoded in UTF-8.
+    ///
+    /// By specification, the only other encoding permitted should be CP-437,
+    /// but historically many ZIP readers interpret name and comment as whatever
+    /// the system's local character encoding happens to be.
+    ///
+    /// This flag should only be set if the user intends to encode a non-portable
+    /// ZIP file for a specific localized region. Otherwise, the Writer
+    /// automatically sets the ZIP format's UTF-8 flag for valid UTF-8 strings.
+    pub non_utf8: bool,
+
+    pub creator_version: u16,
+    pub reader_version: u16,
+    pub flags: u16,
+
+    /// method is the compression method. If zero, STORE is used.
+    pub method: u16,
+
+    /// modified is the modified time of the file.
+    ///
+    /// When reading, an extended timestamp is preferred over the legacy MS-DOS
+    /// date field. When writing, an extended timestamp (which is
+    /// timezone-agnostic) is always emitted. The legacy MS-DOS date field is
+    /// encoded in UTC.
+    pub modified: time::Time,
+
+    /// modified_time is an MS-DOS-encoded time.
+    pub modified_time: u16,
+
+    /// modified_date is an MS-DOS-encoded date.
+    pub modified_date: u16,
+
+    /// crc32 is the CRC32 checksum of the file content.
+    pub crc32: u32,
+
+    /// compressed_size64 is the compressed size of the file in bytes.
+    pub compressed_size64: u64,
+
+    /// uncompressed_size64 is the uncompressed size of the file in bytes.
+    pub uncompressed_size64: u64,
+
+    pub extra: Vec<u8>,
+    pub external_attrs: u32, // Meaning depends on creator_version
+}
+
+impl FileHeader {
+    /// new returns a FileHeader for the file name, which
+    /// is compressed using the DEFLATE method.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            method: DEFLATE,
+            ..Default::default()
+        }
+    }
+
+    /// mode returns the Unix permission and file type bits for the FileHeader.
+    pub fn mode(&self) -> u32 {
+        let mut mode = match self.creator_version >> 8 {
+            CREATOR_UNIX | CREATOR_MACOSX => self.external_attrs >> 16,
+            CREATOR_NTFS | CREATOR_VFAT | CREATOR_FAT => {
+                msdos_mode_to_file_mode(self.external_attrs)
+            }
+            _ => 0,
+        };
+        if self.name.ends_with('/') && mode & S_IFMT == 0 {
+            mode |= S_IFDIR;
+        }
+        mode
+    }
+
+    /// set_mode changes the permission and file type bits for the FileHeader
+    /// to the Unix mode bits given.
+    pub fn set_mode(&mut self, mode: u32) {
+        self.creator_version = self.creator_version & 0xff | CREATOR_UNIX << 8;
+        self.external_attrs = mode << 16;
+
+        // set MSDOS attributes too, as the original zip does.
+        if mode & S_IFMT == S_IFDIR {
+            self.external_attrs |= MSDOS_DIR;
+        }
+        if mode & 0o200 == 0 {
+            self.external_attrs |= MSDOS_READ_ONLY;
+        }
+    }
+
+    /// is_zip64 reports whether the file size exceeds the 32 bit limit
+    pub(super) fn is_zip64(&self) -> bool {
+        self.compressed_size64 >= UINT32MAX || self.uncompressed_size64 >= UINT32MAX
+    }
+
+    pub(super) fn has_data_descriptor(&self) -> bool {
+        self.flags & 0x8 != 0
+    }
+}
+
+fn msdos_mode_to_file_mode(m: u32) -> u32 {
+    let mut mode = if m & MSDOS_DIR != 0 {
+        S_IFDIR | 0o777
+    } else {
+        S_IFREG | 0o666
+    };
+    if m & MSDOS_READ_ONLY != 0 {
+        mode &= !0o222;
+    }
+    mode
+}
+
+/// ms_dos_time_to_time converts an MS-DOS date and time into a time::Time.
+/// The resolution is 2s.
+/// See: https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-dosdatetimetofiletime
+pub(super) fn ms_dos_time_to_time(dos_date: u16, dos_time: u16) -> time::Time {
+    time::date(
+        // date bits 0-4: day of month; 5-8: month; 9-15: years since 1980
+        (dos_date >> 9) as isize + 1980,
+        (dos_date >> 5 & 0xf) as isize,
+        (dos_date & 0x1f) as isize,
+        // time bits 0-4: second/2; 5-10: minute; 11-15: hour
+        (dos_time >> 11) as isize,
+        (dos_time >> 5 & 0x3f) as isize,
+        (dos_time & 0x1f) as isize * 2,
+        0, // nanoseconds
+    )
+}
+
+/// time_to_ms_dos_time converts a time::Time to an MS-DOS date and time.
+/// The resolution is 2s.
+/// See: https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-filetimetodosdatetime
+pub(super) fn time_to_ms_dos_time(t: &time::Time) -> (u16, u16) {
+    let f_date = t.day() as isize + ((t.month() as isize) << 5) + ((t.year() - 1980) << 9);
+    let f_time =
+        t.second() as isize / 2 + ((t.minute() as isize) << 5) + ((t.hour() as isize) << 11);
+    (f_date as u16, f_time as u16)
+}
+
+/// ReadBuf is a little-endian cursor over a byte slice.
+/// Reading past the end of the slice panics, so the caller must
+/// check the length first.
+pub(super) struct ReadBuf<'a>(pub(super) &'a [u8]);
+
+impl<'a> ReadBuf<'a> {
+    pub(super) fn uint8(&mut self) -> u8 {
+        let v = self.0[0];
+        self.0 = &self.0[1..];
+        v
+    }
+
+    pub(super) fn uint16(&mut self) -> u16 {
+        let v = u16::from_le_bytes(self.0[..2].try_into().unwrap());
+        self.0 = &self.0[2..];
+        v
+    }
+
+    pub(super) fn uint32(&mut self) -> u32 {
+        let v = u32::from_le_bytes(self.0[..4].try_into().unwrap());
+        self.0 = &self.0[4..];
+        v
+    }
+
+    pub(super) fn uint64(&mut self) -> u64 {
+        let v = u64::from_le_bytes(self.0[..8].try_into().unwrap());
+        self.0 = &self.0[8..];
+        v
+    }
+
+    pub(super) fn skip(&mut self, n: usize) {
+        self.0 = &self.0[n..];
+    }
+
+    pub(super) fn sub(&mut self, n: usize) -> ReadBuf<'a> {
+        let (b, rest) = self.0.split_at(n);
+        self.0 = rest;
+        ReadBuf(b)
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// WriteBuf is a little-endian builder of a byte buffer.
+#[derive(Default)]
+pub(super) struct WriteBuf(pub(super) Vec<u8>);
+
+impl WriteBuf {
+    pub(super) fn uint8(&mut self, v: u8) {
+        self.0.push(v);
+    }
+
+    pub(super) fn uint16(&mut self, v: u16) {
+        self.0.extend_from_slice(&v.to_le_bytes());
+    }
+
+    pub(super) fn uint32(&mut self, v: u32) {
+        self.0.extend_from_slice(&v.to_le_bytes());
+    }
+
+    pub(super) fn uint64(&mut self, v: u64) {
+        self.0.extend_from_slice(&v.to_le_bytes());
+    }
+
+    pub(super) fn skip(&mut self, n: usize) {
+        self.0.resize(self.0.len() + n, 0);
+    }
+}
@@ -0,0 +1,580 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::reader::new_algorithm_error;
+use super::struct_::{
+    time_to_ms_dos_time, FileHeader, WriteBuf, DATA_DESCRIPTOR64_LEN, DATA_DESCRIPTOR_LEN,
+    DATA_DESCRIPTOR_SIGNATURE, DEFLATE, DIRECTORY64_END_LEN, DIRECTORY64_END_SIGNATURE,
+    DIRECTORY64_LOC_LEN, DIRECTORY64_LOC_SIGNATURE, DIRECTORY_END_LEN, DIRECTORY_END_SIGNATURE,
+    DIRECTORY_HEADER_LEN, DIRECTORY_HEADER_SIGNATURE, EXT_TIME_EXTRA_ID, FILE_HEADER_LEN,
+    FILE_HEADER_SIGNATURE, STORE, UINT16MAX, UINT32MAX, ZIP64_EXTRA_ID, ZIP_VERSION20,
+    ZIP_VERSION45,
+};
+use crate::compress::flate;
+use crate::errors;
+use crate::hash::{crc32, Hash32};
+use std::io::Write;
+
+/// new_input_error returns an error reported for an invalid use of the Writer.
+fn new_input_error(msg: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, msg)
+}
+
+/// Writer implements a zip file writer.
+pub struct Writer<Output: Write> {
+    cw: CountWriter<Output>,
+    dir: Vec<Header>,
+    err: Option<std::io::Error>, // error of closing the last file, reported by the next call
+    closed: bool,
+    comment: String,
+}
+
+struct Header {
+    fh: FileHeader,
+    offset: u64,
+    raw: bool,
+}
+
+impl<Output: Write> Writer<Output> {
+    /// new returns a new Writer writing a zip file to w.
+    /// To keep using w after the Writer is done, pass a mutable reference.
+    pub fn new(w: Output) -> Self {
+        Self {
+            cw: CountWriter {
+                w,
+                buf: Vec::with_capacity(BUF_SIZE),
+                count: 0,
+            },
+            dir: Vec::new(),
+            err: None,
+            closed: false,
+            comment: String::new(),
+        }
+    }
+
+    /// get_ref returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Output {
+        &self.cw.w
+    }
+
+    /// get_mut returns a mutable reference to the underlying writer.
+    /// Writing to it directly corrupts the archive.
+    pub fn get_mut(&mut self) -> &mut Output {
+        &mut self.cw.w
+    }
+
+    /// into_inner returns the underlying writer. close must be called before
+    /// to write the central directory and flush the buffered data.
+    pub fn into_inner(self) -> Output {
+        self.cw.w
+    }
+
+    /// set_offset sets the offset of the beginning of the zip data within the
+    /// underlying writer. It should be used when the zip data is appended to an
+    /// existing file, such as a binary executable.
+    /// It must be called before any data is written.
+    pub fn set_offset(&mut self, n: u64) {
+        if self.cw.count != 0 {
+            panic!("zip: set_offset called after data was written");
+        }
+        self.cw.count = n;
+    }
+
+    /// flush flushes any buffered data to the underlying writer.
+    /// Calling flush is not normally necessary; calling close is sufficient.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.cw.flush()
+    }
+
+    /// set_comment sets the end-of-central-directory comment field.
+    /// It can only be called before close.
+    pub fn set_comment(&mut self, comment: &str) -> std::io::Result<()> {
+        if comment.len() as u64 > UINT16MAX {
+            return Err(new_input_error("zip: Writer.Comment too long"));
+        }
+        self.comment = comment.to_string();
+        Ok(())
+    }
+
+    /// close finishes writing the zip file by writing the central directory.
+    /// It does not close the underlying writer.
+    pub fn close(&mut self) -> std::io::Result<()> {
+        if let Some(err) = self.err.take() {
+            return Err(err);
+        }
+        if self.closed {
+            return Err(new_input_error("zip: writer closed twice"));
+        }
+        self.closed = true;
+
+        // write central directory
+        let start = self.cw.count;
+        for h in self.dir.iter_mut() {
+            let mut b = WriteBuf::default();
+            b.uint32(DIRECTORY_HEADER_SIGNATURE);
+            b.uint16(h.fh.creator_version);
+            b.uint16(h.fh.reader_version);
+            b.uint16(h.fh.flags);
+            b.uint16(h.fh.method);
+            b.uint16(h.fh.modified_time);
+            b.uint16(h.fh.modified_date);
+            b.uint32(h.fh.crc32);
+            if h.fh.is_zip64() || h.offset >= UINT32MAX {
+                // the file needs a zip64 header. store maxint in both
+                // 32 bit size fields (and offset later) to signal that the
+                // zip64 extra header should be used.
+                b.uint32(UINT32MAX as u32); // compressed size
+                b.uint32(UINT32MAX as u32); // uncompressed size
+
+                // append a zip64 extra block to extra
+                let mut eb = WriteBuf::default(); // 2x u16 + 3x u64
+                eb.uint16(ZIP64_EXTRA_ID);
+                eb.uint16(24); // size = 3x u64
+                eb.uint64(h.fh.uncompressed_size64);
+                eb.uint64(h.fh.compressed_size64);
+                eb.uint64(h.offset);
+                h.fh.extra.extend_from_slice(&eb.0);
+            } else {
+                b.uint32(h.fh.compressed_size64 as u32);
+                b.uint32(h.fh.uncompressed_size64 as u32);
+            }
+
+            b.uint16(h.fh.name.len() as u16);
+            b.uint16(h.fh.extra.len() as u16);
+            b.uint16(h.fh.comment.len() as u16);
+            b.skip(4); // skip disk number start and internal file attr (2x u16)
+            b.uint32(h.fh.external_attrs);
+            b.uint32(std::cmp::min(h.offset, UINT32MAX) as u32);
+            debug_assert_eq!(b.0.len(), DIRECTORY_HEADER_LEN);
+            self.cw.write_all(&b.0)?;
+            self.cw.write_all(h.fh.name.as_bytes())?;
+            self.cw.write_all(&h.fh.extra)?;
+            self.cw.write_all(h.fh.comment.as_bytes())?;
+        }
+        let end = self.cw.count;
+
+        let mut records = self.dir.len() as u64;
+        let mut size = end - start;
+        let mut offset = start;
+
+        if records >= UINT16MAX || size >= UINT32MAX || offset >= UINT32MAX {
+            let mut b = WriteBuf::default();
+
+            // zip64 end of central directory record
+            b.uint32(DIRECTORY64_END_SIGNATURE);
+            b.uint64(DIRECTORY64_END_LEN as u64 - 12); // length minus signature (u32) and length fields (u64)
+            b.uint16(ZIP_VERSION45); // version made by
+            b.uint16(ZIP_VERSION45); // version needed to extract
+            b.uint32(0); // number of this disk
+            b.uint32(0); // number of the disk with the start of the central directory
+            b.uint64(records); // total number of entries in the central directory on this disk
+            b.uint64(records); // total number of entries in the central directory
+            b.uint64(size); // size of the central directory
+            b.uint64(offset); // offset of start of central directory with respect to the starting disk number
+
+            // zip64 end of central directory locator
+            b.uint32(DIRECTORY64_LOC_SIGNATURE);
+            b.uint32(0); // number of the disk with the start of the zip64 end of central directory
+            b.uint64(end); // relative offset of the zip64 end of central directory record
+            b.uint32(1); // total number of disks
+
+            debug_assert_eq!(b.0.len(), DIRECTORY64_END_LEN + DIRECTORY64_LOC_LEN);
+            self.cw.write_all(&b.0)?;
+
+            // store max values in the regular end record to signal
+            // that the zip64 values should be used instead
+            records = UINT16MAX;
+            size = UINT32MAX;
+            offset = UINT32MAX;
+        }
+
+        // write end record
+        let mut b = WriteBuf::default();
+        b.uint32(DIRECTORY_END_SIGNATURE);
+        b.skip(4); // skip over disk number and first disk number (2x u16)
+        b.uint16(records as u16); // number of entries this disk
+        b.uint16(records as u16); // number of entries total
+        b.uint32(size as u32); // size of directory
+        b.uint32(offset as u32); // start of directory
+        b.uint16(self.comment.len() as u16); // byte size of EOCD comment
+        debug_assert_eq!(b.0.len(), DIRECTORY_END_LEN);
+        self.cw.write_all(&b.0)?;
+        self.cw.write_all(self.comment.as_bytes())?;
+
+        self.cw.flush()
+    }
+
+    /// create adds a file to the zip file using the provided name.
+    /// It returns a FileWriter to which the file contents should be written.
+    /// The file contents will be compressed using the DEFLATE method.
+    /// The name must be a relative path: it must not start with a drive
+    /// letter (e.g. C:) or leading slash, and only forward slashes are
+    /// allowed. To create a directory instead of a file, add a trailing
+    /// slash to the name.
+    /// The file is finished when the FileWriter is closed or dropped.
+    pub fn create(&mut self, name: &str) -> std::io::Result<FileWriter<'_, Output>> {
+        self.create_header(&FileHeader::new(name))
+    }
+
+    /// prepare reports the error of closing the previous file, if any.
+    fn prepare(&mut self) -> std::io::Result<()> {
+        if let Some(err) = self.err.take() {
+            return Err(err);
+        }
+        if self.closed {
+            return Err(new_input_error("zip: write to closed writer"));
+        }
+        Ok(())
+    }
+
+    /// create_header adds a file to the zip archive using the provided FileHeader
+    /// for the file metadata. The FileHeader is copied, so the Writer
+    /// fills the flags, versions, sizes and checksum in its own copy.
+    ///
+    /// This returns a FileWriter to which the file contents should be written.
+    /// The file is finished when the FileWriter is closed or dropped.
+    pub fn create_header(&mut self, fh: &FileHeader) -> std::io::Result<FileWriter<'_, Output>> {
+        self.prepare()?;
+        let mut fh = fh.clone();
+
+        // The ZIP format has a sad state of affairs regarding character encoding.
+        // Officially, the name and comment fields are supposed to be encoded
+        // in CP-437 (which is mostly compatible with ASCII), unless the UTF-8
+        // flag bit is set. However, there are several problems:
+        //
+        //	* Many ZIP readers still do not support UTF-8.
+        //	* If the UTF-8 flag is cleared, several readers simply interpret the
+        //	name and comment fields as whatever the local system encoding is.
+        //
+        // In order to avoid breaking readers without UTF-8 support,
+        // we avoid setting the UTF-8 flag if the strings are CP-437 compatible.
+        // However, if the strings require multibyte UTF-8 encoding and is a
+        // valid UTF-8 string, then we set the UTF-8 bit.
+        //
+        // For the case, where the user explicitly wants to specify the encoding
+        // as UTF-8, they will need to set the flag bit themselves.
+        let (utf8_valid1, utf8_require1) = detect_utf8(fh.name.as_bytes());
+        let (utf8_valid2, utf8_require2) = detect_utf8(fh.comment.as_bytes());
+        if fh.non_utf8 {
+            fh.flags &= !0x800;
+        } else if (utf8_require1 || utf8_require2) && (utf8_valid1 && utf8_valid2) {
+            fh.flags |= 0x800;
+        }
+
+        fh.creator_version = fh.creator_version & 0xff00 | ZIP_VERSION20; // preserve compatibility byte
+        fh.reader_version = ZIP_VERSION20;
+
+        // If modified is set, this takes precedence over MS-DOS timestamp fields.
+        if !fh.modified.is_zero() {
+            (fh.modified_date, fh.modified_time) = time_to_ms_dos_time(&fh.modified);
+
+            // Use "extended timestamp" format since this is what Info-ZIP uses.
+            // Nearly every major ZIP implementation uses a different format,
+            // but at least most seem to be able to understand the other formats.
+            //
+            // This format happens to be identical for both local and central header
+            // if modification time is the only timestamp being encoded.
+            let mt = fh.modified.unix() as u32;
+            let mut eb = WriteBuf::default(); // 2x u16 + u8 + u32
+            eb.uint16(EXT_TIME_EXTRA_ID);
+            eb.uint16(5); // Size: u8 + u32
+            eb.uint8(1); // Flags: ModTime
+            eb.uint32(mt); // ModTime
+            fh.extra.extend_from_slice(&eb.0);
+        }
+
+        let is_dir = fh.name.ends_with('/');
+        if is_dir {
+            // Set the compression method to Store to ensure data length is truly zero,
+            // which the write_header method always encodes for the size fields.
+            // This is necessary as most compression formats have non-zero lengths
+            // even when compressing an empty string.
+            fh.method = STORE;
+            fh.flags &= !0x8; // we will not write a data descriptor
+
+            // Explicitly clear sizes as they have no meaning for directories.
+            fh.compressed_size64 = 0;
+            fh.uncompressed_size64 = 0;
+        } else {
+            fh.flags |= 0x8; // we will write a data descriptor
+            if fh.method != STORE && fh.method != DEFLATE {
+                return Err(new_algorithm_error());
+            }
+        }
+
+        let h = Header {
+            fh,
+            offset: self.cw.count,
+            raw: false,
+        };
+        write_header(&mut self.cw, &h)?;
+        self.dir.push(h);
+        Ok(self.file_writer(is_dir))
+    }
+
+    /// create_raw adds a file to the zip archive using the provided FileHeader and
+    /// returns a FileWriter to which the file contents should be written. The
+    /// file's contents must be written to the FileWriter before the next call
+    /// to create, create_header, create_raw, or close.
+    ///
+    /// In contrast to create_header, the bytes passed to FileWriter are not
+    /// compressed, and the crc32, compressed_size64 and uncompressed_size64
+    /// fields of the header are written as they are.
+    pub fn create_raw(&mut self, fh: &FileHeader) -> std::io::Result<FileWriter<'_, Output>> {
+        self.prepare()?;
+        let h = Header {
+            fh: fh.clone(),
+            offset: self.cw.count,
+            raw: true,
+        };
+        write_header(&mut self.cw, &h)?;
+        let is_dir = h.fh.name.ends_with('/');
+        self.dir.push(h);
+        Ok(self.file_writer(is_dir))
+    }
+
+    /// file_writer returns the FileWriter for the last header of the directory.
+    fn file_writer(&mut self, is_dir: bool) -> FileWriter<'_, Output> {
+        let header = self.dir.last_mut().unwrap();
+        let start = self.cw.count;
+        let comp = if is_dir {
+            Compressor::Dir
+        } else if header.raw || header.fh.method == STORE {
+            Compressor::Store(&mut self.cw)
+        } else {
+            // Same level as used by Go for the default DEFLATE compressor.
+            Compressor::Deflate(Box::new(flate::Writer::new(&mut self.cw, 5).unwrap()))
+        };
+        FileWriter {
+            header,
+            comp,
+            crc32: crc32::new_ieee(),
+            start,
+            raw_count: 0,
+            closed: false,
+            err: &mut self.err,
+        }
+    }
+}
+
+/// write_header writes the local file header of h.
+fn write_header(w: &mut dyn Write, h: &Header) -> std::io::Result<()> {
+    if h.fh.name.len() as u64 > UINT16MAX {
+        return Err(new_input_error("zip: FileHeader.Name too long"));
+    }
+    if h.fh.extra.len() as u64 > UINT16MAX {
+        return Err(new_input_error("zip: FileHeader.Extra too long"));
+    }
+
+    let mut b = WriteBuf::default();
+    b.uint32(FILE_HEADER_SIGNATURE);
+    b.uint16(h.fh.reader_version);
+    b.uint16(h.fh.flags);
+    b.uint16(h.fh.method);
+    b.uint16(h.fh.modified_time);
+    b.uint16(h.fh.modified_date);
+    // In raw mode (caller does the compression), the values are either
+    // written here or in the trailing data descriptor based on the header
+    // flags.
+    if h.raw && !h.fh.has_data_descriptor() {
+        b.uint32(h.fh.crc32);
+        b.uint32(std::cmp::min(h.fh.compressed_size64, UINT32MAX) as u32);
+        b.uint32(std::cmp::min(h.fh.uncompressed_size64, UINT32MAX) as u32);
+    } else {
+        // When this package handle the compression, these values are
+        // always written to the trailing data descriptor.
+        b.uint32(0); // crc32
+        b.uint32(0); // compressed size
+        b.uint32(0); // uncompressed size
+    }
+    b.uint16(h.fh.name.len() as u16);
+    b.uint16(h.fh.extra.len() as u16);
+    debug_assert_eq!(b.0.len(), FILE_HEADER_LEN);
+    w.write_all(&b.0)?;
+    w.write_all(h.fh.name.as_bytes())?;
+    w.write_all(&h.fh.extra)
+}
+
+/// detect_utf8 reports whether s is a valid UTF-8 string, and whether the string
+/// must be considered UTF-8 encoding (i.e., not compatible with CP-437, ASCII,
+/// or any other common encoding).
+pub(super) fn detect_utf8(s: &[u8]) -> (bool, bool) {
+    let s = match std::str::from_utf8(s) {
+        Ok(s) => s,
+        Err(_) => return (false, false),
+    };
+    let mut require = false;
+    for r in s.chars() {
+        // Officially, ZIP uses CP-437, but many readers use the system's
+        // local character encoding. Most encoding are compatible with a large
+        // subset of CP-437, which itself is ASCII-like.
+        //
+        // Forbid 0x7e and 0x5c since EUC-KR and Shift-JIS replace those
+        // characters with localized currency and overline characters.
+        if !('\x20'..='\x7d').contains(&r) || r == '\x5c' {
+            require = true;
+        }
+    }
+    (true, require)
+}
+
+/// BUF_SIZE is the size of the output buffer.
+const BUF_SIZE: usize = 4096;
+
+/// CountWriter buffers the output and counts the bytes written to it.
+/// Unlike std::io::BufWriter, it doesn't flush when dropped, so the
+/// Writer doesn't hold on to the output after close.
+struct CountWriter<Output: Write> {
+    w: Output,
+    buf: Vec<u8>,
+    count: u64,
+}
+
+impl<Output: Write> Write for CountWriter<Output> {
+    fn write(&mut self, p: &[u8]) -> std::io::Result<usize> {
+        if self.buf.len() + p.len() > BUF_SIZE {
+            self.w.write_all(&self.buf)?;
+            self.buf.clear();
+        }
+        if p.len() >= BUF_SIZE {
+            self.w.write_all(p)?;
+        } else {
+            self.buf.extend_from_slice(p);
+        }
+        self.count += p.len() as u64;
+        Ok(p.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.w.write_all(&self.buf)?;
+        self.buf.clear();
+        self.w.flush()
+    }
+}
+
+enum Compressor<'w, Output: Write> {
+    Dir,
+    Store(&'w mut CountWriter<Output>),
+    Deflate(Box<flate::Writer<&'w mut CountWriter<Output>>>),
+}
+
+/// FileWriter writes the contents of a single file of the zip archive.
+///
+/// The file is finished by calling close. If the FileWriter is dropped
+/// without being closed, it is closed automatically and an error,
+/// if any, is returned by the next call to a method of the Writer.
+pub struct FileWriter<'w, Output: Write> {
+    header: &'w mut Header,
+    comp: Compressor<'w, Output>,
+    crc32: crc32::Digest<'static>,
+    start: u64,     // output count when the file data started
+    raw_count: u64, // count of uncompressed bytes
+    closed: bool,
+    err: &'w mut Option<std::io::Error>,
+}
+
+impl<Output: Write> FileWriter<'_, Output> {
+    /// close finishes writing the file by flushing the compressor
+    /// and writing the data descriptor.
+    pub fn close(mut self) -> std::io::Result<()> {
+        self.close_file()
+    }
+
+    fn close_file(&mut self) -> std::io::Result<()> {
+        if self.closed {
+            return Err(new_input_error("zip: file closed twice"));
+        }
+        self.closed = true;
+        let cw = match &mut self.comp {
+            Compressor::Dir => return Ok(()),
+            Compressor::Store(cw) => &mut **cw,
+            Compressor::Deflate(w) => {
+                w.close()?;
+                w.output()
+            }
+        };
+        let compressed = cw.count - self.start;
+        if self.header.raw {
+            return write_data_descriptor(cw, &self.header.fh);
+        }
+
+        // update FileHeader
+        let fh = &mut self.header.fh;
+        fh.crc32 = self.crc32.sum32();
+        fh.compressed_size64 = compressed;
+        fh.uncompressed_size64 = self.raw_count;
+
+        if fh.is_zip64() {
+            fh.reader_version = ZIP_VERSION45; // requires 4.5 - File uses ZIP64 format extensions
+        }
+
+        write_data_descriptor(cw, fh)
+    }
+}
+
+impl<Output: Write> Write for FileWriter<'_, Output> {
+    fn write(&mut self, p: &[u8]) -> std::io::Result<usize> {
+        if self.closed {
+            return Err(new_input_error("zip: write to closed file"));
+        }
+        let n = match &mut self.comp {
+            Compressor::Dir => {
+                if p.is_empty() {
+                    return Ok(0);
+                }
+                return Err(new_input_error("zip: write to directory"));
+            }
+            Compressor::Store(w) => w.write(p)?,
+            Compressor::Deflate(w) => w.write(p)?,
+        };
+        if !self.header.raw {
+            self.crc32.write_all(&p[..n])?;
+            self.raw_count += n as u64;
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match &mut self.comp {
+            Compressor::Dir => Ok(()),
+            Compressor::Store(w) => w.flush(),
+            Compressor::Deflate(w) => w.flush(),
+        }
+    }
+}
+
+impl<Output: Write> Drop for FileWriter<'_, Output> {
+    fn drop(&mut self) {
+        if !self.closed {
+            if let Err(err) = self.close_file() {
+                *self.err = Some(errors::copy_stdio_error(&err));
+            }
+        }
+    }
+}
+
+fn write_data_descriptor(w: &mut dyn Write, fh: &FileHeader) -> std::io::Result<()> {
+    if !fh.has_data_descriptor() {
+        return Ok(());
+    }
+    // Write data descriptor. This is more complicated than one would
+    // think, see e.g. comments in zipfile.c:putextended() and
+    // http://bugs.sun.com/bugdatabase/view_bug.do?bug_id=7073588.
+    // The approach here is to write 8 byte sizes if needed without
+    // adding a zip64 extra in the local header (too late anyway).
+    let mut b = WriteBuf::default();
+    b.uint32(DATA_DESCRIPTOR_SIGNATURE); // de-facto standard, required by OS X
+    b.uint32(fh.crc32);
+    if fh.is_zip64() {
+        b.uint64(fh.compressed_size64);
+        b.uint64(fh.uncompressed_size64);
+        debug_assert_eq!(b.0.len(), DATA_DESCRIPTOR64_LEN);
+    } else {
+        b.uint32(fh.compressed_size64 as u32);
+        b.uint32(fh.uncompressed_size64 as u32);
+        debug_assert_eq!(b.0.len(), DATA_DESCRIPTOR_LEN);
+    }
+    w.write_all(&b.0)
+}
@@ -0,0 +1,282 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::{FileHeader, Reader, Writer, DEFLATE, STORE};
+use super::{S_IFDIR, S_IFREG};
+use crate::time;
+use std::io::{Cursor, Read, Write};
+
+struct WriteTest {
+    name: &'static str,
+    data: Vec<u8>,
+    method: u16,
+    mode: u32,
+}
+
+fn write_tests() -> Vec<WriteTest> {
+    vec![
+        WriteTest {
+            name: "foo",
+            data: b"Rabbits, guinea pigs, gophers, marsupial rats, and quolls.".to_vec(),
+            method: STORE,
+            mode: 0o666,
+        },
+        WriteTest {
+            name: "bar",
+            data: (0..1 << 16).map(|i| (i * 7 % 256) as u8).collect(),
+            method: DEFLATE,
+            mode: 0o644,
+        },
+        WriteTest {
+            name: "wren.txt",
+            data: b"It was the best of times, it was the worst of times.".to_vec(),
+            method: DEFLATE,
+            mode: 0o755,
+        },
+        WriteTest {
+            name: "dir/",
+            data: Vec::new(),
+            method: DEFLATE,
+            mode: S_IFDIR | 0o755,
+        },
+    ]
+}
+
+#[test]
+fn test_writer() {
+    let modified = time::date(2023, 3, 4, 10, 20, 30, 0);
+    let tests = write_tests();
+
+    // write a zip file
+    let mut buf = Vec::new();
+    let mut w = Writer::new(&mut buf);
+    for wt in tests.iter() {
+        let mut fh = FileHeader {
+            name: wt.name.to_string(),
+            method: wt.method,
+            modified,
+            ..Default::default()
+        };
+        fh.set_mode(wt.mode);
+        let mut fw = w.create_header(&fh).unwrap();
+        fw.write_all(&wt.data).unwrap();
+        fw.close().unwrap();
+    }
+    w.set_comment("comment").unwrap();
+    w.close().unwrap();
+
+    // read it back
+    let mut r = Cursor::new(&buf);
+    let mut zr = Reader::new(&mut r).unwrap();
+    assert_eq!(zr.comment, "comment");
+    assert_eq!(zr.file.len(), tests.len());
+    for (i, wt) in tests.iter().enumerate() {
+        let h = &zr.file[i].header;
+        assert_eq!(h.name, wt.name);
+        assert_eq!(h.mode(), wt.mode, "{}", wt.name);
+        assert_eq!(h.modified, modified, "{}", wt.name);
+        assert_eq!(h.uncompressed_size64, wt.data.len() as u64, "{}", wt.name);
+        if wt.name.ends_with('/') {
+            assert_eq!(h.method, STORE, "{}", wt.name);
+            assert!(!h.has_data_descriptor(), "{}", wt.name);
+        } else {
+            assert_eq!(h.method, wt.method, "{}", wt.name);
+            assert!(h.has_data_descriptor(), "{}", wt.name);
+        }
+        let mut data = Vec::new();
+        zr.open(i).unwrap().read_to_end(&mut data).unwrap();
+        assert!(data == wt.data, "{}: mismatching contents", wt.name);
+    }
+}
+
+#[test]
+fn test_writer_drop_closes_file() {
+    let mut buf = Vec::new();
+    let mut w = Writer::new(&mut buf);
+    w.create("a").unwrap().write_all(b"hello").unwrap();
+    w.create("b").unwrap().write_all(b"world").unwrap();
+    w.close().unwrap();
+
+    let mut r = Cursor::new(&buf);
+    let mut zr = Reader::new(&mut r).unwrap();
+    for (i, want) in [b"hello", b"world"].iter().enumerate() {
+        let mut data = Vec::new();
+        zr.open(i).unwrap().read_to_end(&mut data).unwrap();
+        assert_eq!(&data, want);
+    }
+}
+
+#[test]
+fn test_writer_utf8() {
+    let utf8_tests = [
+        ("hi, hello", "in the world", false, 0x8),
+        ("hi, こんにちわ", "in the world", false, 0x808),
+        ("hi, こんにちわ", "in the world", true, 0x8),
+        ("hi, hello", "in the 世界", false, 0x808),
+        ("hi, こんにちわ", "in the 世界", false, 0x808),
+        ("hi, hello", "in the world", true, 0x8),
+    ];
+    let mut buf = Vec::new();
+    let mut w = Writer::new(&mut buf);
+    for (name, comment, non_utf8, _) in utf8_tests.iter() {
+        let fh = FileHeader {
+            name: name.to_string(),
+            comment: comment.to_string(),
+            non_utf8: *non_utf8,
+            method: DEFLATE,
+            ..Default::default()
+        };
+        w.create_header(&fh).unwrap();
+    }
+    w.close().unwrap();
+
+    let mut r = Cursor::new(&buf);
+    let zr = Reader::new(&mut r).unwrap();
+    for (i, (name, comment, _, flags)) in utf8_tests.iter().enumerate() {
+        let h = &zr.file[i].header;
+        assert_eq!(h.name, *name);
+        assert_eq!(h.comment, *comment);
+        assert_eq!(h.flags, *flags, "{}/{}", name, comment);
+    }
+}
+
+#[test]
+fn test_writer_dir() {
+    let mut buf = Vec::new();
+    let mut w = Writer::new(&mut buf);
+    let mut dw = w.create("dir/").unwrap();
+    dw.write_all(b"").unwrap();
+    assert!(dw.write_all(b"hello").is_err());
+}
+
+#[test]
+fn test_writer_offset() {
+    let largeness = b"Go zip test. ".repeat(300);
+    let mut buf = largeness.clone();
+    let mut w = Writer::new(&mut buf);
+    w.set_offset(largeness.len() as u64);
+    let mut fw = w.create("hello").unwrap();
+    fw.write_all(b"hello, world").unwrap();
+    fw.close().unwrap();
+    w.close().unwrap();
+
+    let mut r = Cursor::new(&buf);
+    let mut zr = Reader::new(&mut r).unwrap();
+    let mut data = Vec::new();
+    zr.open(0).unwrap().read_to_end(&mut data).unwrap();
+    assert_eq!(data, b"hello, world");
+}
+
+#[test]
+fn test_writer_raw() {
+    // Copy the compressed files from one archive into another.
+    let mut f = std::fs::File::open("src/archive/zip/testdata/test.zip").unwrap();
+    let mut zr = Reader::new(&mut f).unwrap();
+    let mut buf = Vec::new();
+    let mut w = Writer::new(&mut buf);
+    for i in 0..zr.file.len() {
+        let fh = zr.file[i].header.clone();
+        let mut raw = Vec::new();
+        zr.open_raw(i).unwrap().read_to_end(&mut raw).unwrap();
+        let mut fw = w.create_raw(&fh).unwrap();
+        fw.write_all(&raw).unwrap();
+        fw.close().unwrap();
+    }
+    w.close().unwrap();
+
+    let mut r = Cursor::new(&buf);
+    let mut zr2 = Reader::new(&mut r).unwrap();
+    assert_eq!(zr2.file.len(), zr.file.len());
+    for i in 0..zr.file.len() {
+        assert_eq!(zr2.file[i].header, zr.file[i].header);
+        let mut want = Vec::new();
+        zr.open(i).unwrap().read_to_end(&mut want).unwrap();
+        let mut data = Vec::new();
+        zr2.open(i).unwrap().read_to_end(&mut data).unwrap();
+        assert_eq!(data, want);
+    }
+}
+
+#[test]
+fn test_writer_zip64_many_records() {
+    // More than 0xffff records require the zip64 end of central directory.
+    const N: usize = (1 << 16) + 1;
+    let mut buf = Vec::new();
+    let mut w = Writer::new(&mut buf);
+    for i in 0..N {
+        let fh = FileHeader {
+            name: format!("{}", i),
+            method: STORE,
+            ..Default::default()
+        };
+        w.create_header(&fh).unwrap();
+    }
+    w.close().unwrap();
+    assert!(
+        buf.windows(4).any(|b| b == [0x50, 0x4b, 0x06, 0x06]),
+        "missing zip64 end of central directory"
+    );
+
+    let mut r = Cursor::new(&buf);
+    let zr = Reader::new(&mut r).unwrap();
+    assert_eq!(zr.file.len(), N);
+    assert_eq!(zr.file[N - 1].header.name, format!("{}", N - 1));
+}
+
+#[test]
+fn test_writer_errors() {
+    let mut buf = Vec::new();
+    let mut w = Writer::new(&mut buf);
+    assert!(w.set_comment(&"a".repeat(1 << 16)).is_err());
+    let fh = FileHeader {
+        name: "a".to_string(),
+        method: 99,
+        ..Default::default()
+    };
+    assert!(w.create_header(&fh).is_err());
+    w.close().unwrap();
+    assert!(w.close().is_err());
+    assert!(w.create("b").is_err());
+}
+
+#[test]
+fn test_writer_owns_output() {
+    // Writer and Reader can own their streams, and the streams can be
+    // taken back once the archive is done.
+    let mut w = Writer::new(Vec::new());
+    w.create("a").unwrap().write_all(b"hello").unwrap();
+    w.get_mut().flush().unwrap();
+    w.close().unwrap();
+    assert!(!w.get_ref().is_empty());
+    let buf = w.into_inner();
+
+    let mut zr = Reader::new(Cursor::new(buf.clone())).unwrap();
+    let mut data = Vec::new();
+    zr.open(0).unwrap().read_to_end(&mut data).unwrap();
+    assert_eq!(data, b"hello");
+    // Moving the underlying reader doesn't affect the next open.
+    zr.get_mut().set_position(0);
+    data.clear();
+    zr.open(0).unwrap().read_to_end(&mut data).unwrap();
+    assert_eq!(data, b"hello");
+    assert_eq!(zr.get_ref().get_ref().len(), buf.len());
+    assert_eq!(zr.into_inner().into_inner(), buf);
+}
+
+#[test]
+fn test_file_header_mode() {
+    for mode in [S_IFREG | 0o644, S_IFREG | 0o400, S_IFDIR | 0o755] {
+        let mut fh = FileHeader::new("file");
+        fh.set_mode(mode);
+        assert_eq!(fh.mode(), mode, "{:o}", mode);
+    }
+}
+
+#[test]
+fn test_ms_dos_time() {
+    let t = time::date(2017, 7, 14, 2, 40, 6, 0);
+    let (d, tm) = super::struct_::time_to_ms_dos_time(&t);
+    assert_eq!(super::struct_::ms_dos_time_to_time(d, tm), t);
+}
@@ -0,0 +1,93 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::io::BufRead;
+
+/// BitReader wraps an std::io::BufRead and provides the ability to read values,
+/// bit-by-bit, from it. Its read_bits* methods don't return the usual error
+/// because the error handling was verbose. Instead, any error is kept and can
+/// be checked afterwards.
+pub(super) struct BitReader<Input: BufRead> {
+    pub(super) r: Input,
+    pub(super) n: u64,
+    pub(super) bits: usize,
+    pub(super) err: Option<std::io::Error>,
+}
+
+impl<Input: BufRead> BitReader<Input> {
+    /// new returns a new BitReader reading from r.
+    pub(super) fn new(r: Input) -> Self {
+        Self {
+            r,
+            n: 0,
+            bits: 0,
+            err: None,
+        }
+    }
+
+    /// read_byte reads the next byte from the underlying reader,
+    /// returning None at EOF.
+    pub(super) fn read_byte(&mut self) -> std::io::Result<Option<u8>> {
+        let buf = self.r.fill_buf()?;
+        if buf.is_empty() {
+            return Ok(None);
+        }
+        let b = buf[0];
+        self.r.consume(1);
+        Ok(Some(b))
+    }
+
+    /// read_bits64 reads the given number of bits and returns them in the
+    /// least-significant part of a u64. In the event of an error, it returns 0
+    /// and the error can be obtained from the err field.
+    pub(super) fn read_bits64(&mut self, bits: usize) -> u64 {
+        if self.err.is_some() {
+            return 0;
+        }
+        while bits > self.bits {
+            match self.read_byte() {
+                Ok(Some(b)) => {
+                    self.n <<= 8;
+                    self.n |= b as u64;
+                    self.bits += 8;
+                }
+                Ok(None) => {
+                    self.err = Some(std::io::ErrorKind::UnexpectedEof.into());
+                    return 0;
+                }
+                Err(err) => {
+                    self.err = Some(err);
+                    return 0;
+                }
+            }
+        }
+
+        // self.n looks like this (assuming that self.bits = 14 and bits = 6):
+        // Bit: 111111
+        //      5432109876543210
+        //
+        //         (6 bits, the desired output)
+        //        |-----|
+        //        V     V
+        //      0101101101001110
+        //        ^            ^
+        //        |------------|
+        //           self.bits (num valid bits)
+        //
+        // The next line right shifts the desired bits into the
+        // least-significant places and masks off anything above.
+        let n = (self.n >> (self.bits - bits)) & ((1 << bits) - 1);
+        self.bits -= bits;
+        n
+    }
+
+    pub(super) fn read_bits(&mut self, bits: usize) -> usize {
+        self.read_bits64(bits) as usize
+    }
+
+    pub(super) fn read_bit(&mut self) -> bool {
+        self.read_bits(1) != 0
+    }
+}
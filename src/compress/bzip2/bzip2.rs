@@ -0,0 +1,591 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::bit_reader::BitReader;
+use super::huffman::HuffmanTree;
+use super::move_to_front::MoveToFrontDecoder;
+use crate::compat;
+use crate::compress::copy_error;
+use std::io::BufRead;
+
+// There's no RFC for bzip2. I used the Wikipedia page for reference and a lot
+// of guessing: https://en.wikipedia.org/wiki/Bzip2
+// The source code to pyflate was useful for debugging:
+// http://www.paul.sladen.org/projects/pyflate
+
+/// Error describes invalid bzip2 data. Reader returns it wrapped
+/// in a std::io::Error of the ErrorKind::InvalidData kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// StructuralError is returned when the bzip2 data is found to be
+    /// syntactically invalid, including block and stream checksum mismatches.
+    StructuralError(&'static str),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::StructuralError(s) => write!(f, "bzip2 data invalid: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for std::io::Error {
+    fn from(e: Error) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, e)
+    }
+}
+
+/// Reader decompresses bzip2 data read from the input reader.
+/// Concatenated bzip2 streams are read as a single stream,
+/// and both the block and the stream checksums are verified.
+pub struct Reader<Input: BufRead> {
+    br: BitReader<Input>,
+    file_crc: u32,
+    block_crc: u32,
+    want_block_crc: u32,
+    setup_done: bool, // true if we have parsed the bzip2 header.
+    eof: bool,
+    err: Option<std::io::Error>,
+    // buf holds the data decompressed by fill_buf, buf[buf_pos..buf_end]
+    // hasn't been consumed yet.
+    buf: Vec<u8>,
+    buf_pos: usize,
+    buf_end: usize,
+    block_size: usize, // block_size in bytes, i.e. 900 * 1000.
+    c: [usize; 256],   // the ``C'' array for the inverse BWT.
+    tt: Vec<u32>, // mirrors the ``tt'' array in the bzip2 source and contains the P array in the upper 24 bits.
+    t_pos: u32,   // Index of the next output byte in tt.
+
+    pre_rle_len: usize, // the number of entries of tt containing the RLE data still to be processed.
+    pre_rle_used: usize, // number of entries of pre_rle used.
+    last_byte: i32,     // the last byte value seen.
+    byte_repeats: usize, // the number of repeats of last_byte seen.
+    repeats: usize,     // the number of copies of last_byte to output.
+}
+
+const BZIP2_FILE_MAGIC: usize = 0x425a; // "BZ"
+const BZIP2_BLOCK_MAGIC: u64 = 0x314159265359;
+const BZIP2_FINAL_MAGIC: u64 = 0x177245385090;
+
+/// BUF_SIZE is the size of the buffer used by fill_buf.
+const BUF_SIZE: usize = 32 * 1024;
+
+impl<Input: BufRead> Reader<Input> {
+    /// new returns a Reader which decompresses bzip2 data from r.
+    pub fn new(r: Input) -> Self {
+        Self {
+            br: BitReader::new(r),
+            file_crc: 0,
+            block_crc: 0,
+            want_block_crc: 0,
+            setup_done: false,
+            eof: false,
+            err: None,
+            buf: Vec::new(),
+            buf_pos: 0,
+            buf_end: 0,
+            block_size: 0,
+            c: [0; 256],
+            tt: Vec::new(),
+            t_pos: 0,
+            pre_rle_len: 0,
+            pre_rle_used: 0,
+            last_byte: 0,
+            byte_repeats: 0,
+            repeats: 0,
+        }
+    }
+
+    /// get_ref returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &Input {
+        &self.br.r
+    }
+
+    /// get_mut returns a mutable reference to the underlying reader.
+    /// Reading from it directly corrupts the decompressed stream.
+    pub fn get_mut(&mut self) -> &mut Input {
+        &mut self.br.r
+    }
+
+    /// into_inner returns the underlying reader. The Reader consumes its
+    /// input one byte at a time, so the underlying reader is positioned
+    /// right after the last byte that was decoded.
+    pub fn into_inner(self) -> Input {
+        self.br.r
+    }
+
+    /// setup parses the bzip2 header.
+    fn setup(&mut self, need_magic: bool) -> std::io::Result<()> {
+        let br = &mut self.br;
+
+        if need_magic {
+            let magic = br.read_bits(16);
+            if magic != BZIP2_FILE_MAGIC {
+                return Err(Error::StructuralError("bad magic value").into());
+            }
+        }
+
+        let t = br.read_bits(8);
+        if t != b'h' as usize {
+            return Err(Error::StructuralError("non-Huffman entropy encoding").into());
+        }
+
+        let level = br.read_bits(8);
+        if level < b'1' as usize || level > b'9' as usize {
+            return Err(Error::StructuralError("invalid compression level").into());
+        }
+
+        self.file_crc = 0;
+        self.block_size = 100 * 1000 * (level - b'0' as usize);
+        if self.block_size > self.tt.len() {
+            self.tt = vec![0; self.block_size];
+        }
+        Ok(())
+    }
+
+    /// decode decompresses data into buf. It returns 0 at the end
+    /// of the input.
+    fn decode(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if let Some(err) = &self.err {
+            return Err(copy_error(err));
+        }
+        if self.eof {
+            return Ok(0);
+        }
+
+        if !self.setup_done {
+            let mut res = self.setup(true);
+            if let Some(err) = self.br.err.take() {
+                res = Err(err);
+            }
+            if let Err(err) = res {
+                self.err = Some(copy_error(&err));
+                return Err(err);
+            }
+            self.setup_done = true;
+        }
+
+        let mut res = self.read_(buf);
+        if let Some(err) = self.br.err.take() {
+            res = Err(err);
+        }
+        if let Err(err) = &res {
+            self.err = Some(copy_error(err));
+        }
+        res
+    }
+
+    fn read_from_block(&mut self, buf: &mut [u8]) -> usize {
+        // bzip2 is a block based compressor, except that it has a run-length
+        // preprocessing step. The block based nature means that we can
+        // preallocate fixed-size buffers and reuse them. However, the RLE
+        // preprocessing would require allocating huge buffers to store the
+        // maximum expansion. Thus we process blocks all at once, except for
+        // the RLE which we decompress as required.
+        let mut n = 0;
+        while (self.repeats > 0 || self.pre_rle_used < self.pre_rle_len) && n < buf.len() {
+            // We have RLE data pending.
+
+            // The run-length encoding works like this:
+            // Any sequence of four equal bytes is followed by a length
+            // byte which contains the number of repeats of that byte to
+            // include. (The number of repeats can be zero.) Because we are
+            // decompressing on-demand our state is kept in the reader
+            // object.
+
+            if self.repeats > 0 {
+                buf[n] = self.last_byte as u8;
+                n += 1;
+                self.repeats -= 1;
+                if self.repeats == 0 {
+                    self.last_byte = -1;
+                }
+                continue;
+            }
+
+            self.t_pos = self.tt[self.t_pos as usize];
+            let b = self.t_pos as u8;
+            self.t_pos >>= 8;
+            self.pre_rle_used += 1;
+
+            if self.byte_repeats == 3 {
+                self.repeats = b as usize;
+                self.byte_repeats = 0;
+                continue;
+            }
+
+            if self.last_byte == b as i32 {
+                self.byte_repeats += 1;
+            } else {
+                self.byte_repeats = 0;
+            }
+            self.last_byte = b as i32;
+
+            buf[n] = b;
+            n += 1;
+        }
+
+        n
+    }
+
+    fn read_(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            let n = self.read_from_block(buf);
+            if n > 0 || buf.is_empty() {
+                self.block_crc = update_crc(self.block_crc, &buf[..n]);
+                return Ok(n);
+            }
+
+            // End of block. Check CRC.
+            if self.block_crc != self.want_block_crc {
+                return Err(Error::StructuralError("block checksum mismatch").into());
+            }
+
+            // Find next block.
+            match self.br.read_bits64(48) {
+                BZIP2_BLOCK_MAGIC => {
+                    // Start of block.
+                    self.read_block()?;
+                }
+                BZIP2_FINAL_MAGIC => {
+                    // Check end-of-file CRC.
+                    let want_file_crc = self.br.read_bits64(32) as u32;
+                    if let Some(err) = self.br.err.take() {
+                        return Err(err);
+                    }
+                    if self.file_crc != want_file_crc {
+                        return Err(Error::StructuralError("file checksum mismatch").into());
+                    }
+
+                    // Skip ahead to byte boundary.
+                    // Is there a file concatenated to this one?
+                    // It would start with BZ.
+                    if self.br.bits & 7 != 0 {
+                        self.br.read_bits(self.br.bits & 7);
+                    }
+                    let b = match self.br.read_byte()? {
+                        Some(b) => b,
+                        None => {
+                            self.eof = true;
+                            return Ok(0);
+                        }
+                    };
+                    let z = match self.br.read_byte()? {
+                        Some(z) => z,
+                        None => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+                    };
+                    if b != b'B' || z != b'Z' {
+                        return Err(
+                            Error::StructuralError("bad magic value in continuation file").into(),
+                        );
+                    }
+                    self.setup(false)?;
+                }
+                _ => {
+                    return Err(Error::StructuralError("bad magic value found").into());
+                }
+            }
+        }
+    }
+
+    /// read_block reads a bzip2 block. The magic number should already have been consumed.
+    fn read_block(&mut self) -> std::io::Result<()> {
+        let br = &mut self.br;
+        self.want_block_crc = br.read_bits64(32) as u32;
+        self.block_crc = 0;
+        self.file_crc = self.file_crc.rotate_left(1) ^ self.want_block_crc;
+        let randomized = br.read_bits(1);
+        if randomized != 0 {
+            return Err(Error::StructuralError("deprecated randomized files").into());
+        }
+        let orig_ptr = br.read_bits(24);
+
+        // If not every byte value is used in the block (i.e., it's text) then
+        // the symbol set is reduced. The symbols used are stored as a
+        // two-level, 16x16 bitmap.
+        let symbol_range_used_bitmap = br.read_bits(16);
+        let mut symbol_present = [false; 256];
+        let mut num_symbols = 0;
+        for sym_range in 0..16 {
+            if symbol_range_used_bitmap & (1 << (15 - sym_range)) != 0 {
+                let bits = br.read_bits(16);
+                for symbol in 0..16 {
+                    if bits & (1 << (15 - symbol)) != 0 {
+                        symbol_present[16 * sym_range + symbol] = true;
+                        num_symbols += 1;
+                    }
+                }
+            }
+        }
+
+        if num_symbols == 0 {
+            // There must be an EOF symbol.
+            return Err(Error::StructuralError("no symbols in input").into());
+        }
+
+        // A block uses between two and six different Huffman trees.
+        let num_huffman_trees = br.read_bits(3);
+        if !(2..=6).contains(&num_huffman_trees) {
+            return Err(Error::StructuralError("invalid number of Huffman trees").into());
+        }
+
+        // The Huffman tree can switch every 50 symbols so there's a list of
+        // tree indexes telling us which tree to use for each 50 symbol block.
+        let num_selectors = br.read_bits(15);
+        if num_selectors == 0 {
+            return Err(Error::StructuralError("no tree selectors given").into());
+        }
+
+        // The tree indexes are move-to-front transformed and stored as unary
+        // numbers.
+        let mut mtf_tree_decoder = MoveToFrontDecoder::new_with_range(num_huffman_trees);
+        let mut tree_indexes = vec![0u8; num_selectors];
+        for ti in tree_indexes.iter_mut() {
+            let mut c = 0;
+            while br.read_bit() {
+                c += 1;
+                if c >= num_huffman_trees {
+                    return Err(Error::StructuralError("tree index too large").into());
+                }
+            }
+            *ti = mtf_tree_decoder.decode(c);
+        }
+
+        // The list of symbols for the move-to-front transform is taken from
+        // the previously decoded symbol bitmap.
+        let symbols: Vec<u8> = (0..256)
+            .filter(|&i| symbol_present[i])
+            .map(|i| i as u8)
+            .collect();
+        let mut mtf = MoveToFrontDecoder::new(&symbols);
+
+        num_symbols += 2; // to account for RUNA and RUNB symbols
+        let mut huffman_trees = Vec::with_capacity(num_huffman_trees);
+
+        // Now we decode the arrays of code-lengths for each tree.
+        let mut lengths = vec![0u8; num_symbols];
+        for _ in 0..num_huffman_trees {
+            // The code lengths are delta encoded from a 5-bit base value.
+            let mut length = br.read_bits(5);
+            for l in lengths.iter_mut() {
+                loop {
+                    if !(1..=20).contains(&length) {
+                        return Err(Error::StructuralError("Huffman length out of range").into());
+                    }
+                    if !br.read_bit() {
+                        break;
+                    }
+                    if br.read_bit() {
+                        length -= 1;
+                    } else {
+                        length += 1;
+                    }
+                }
+                *l = length as u8;
+            }
+            huffman_trees.push(HuffmanTree::new(&lengths)?);
+        }
+
+        let mut selector_index = 1; // the next tree index to use
+        if tree_indexes[0] as usize >= huffman_trees.len() {
+            return Err(Error::StructuralError("tree selector out of range").into());
+        }
+        let mut current_huffman_tree = &huffman_trees[tree_indexes[0] as usize];
+        let mut buf_index = 0; // indexes self.tt, the output buffer.
+
+        // The output of the move-to-front transform is run-length encoded and
+        // we merge the decoding into the Huffman parsing loop. These two
+        // variables accumulate the repeat count. See the Wikipedia page for
+        // details.
+        let mut repeat = 0;
+        let mut repeat_power = 0;
+
+        // The `C' array (used by the inverse BWT) needs to be zero initialized.
+        self.c = [0; 256];
+
+        let mut decoded = 0; // counts the number of symbols decoded by the current tree.
+        loop {
+            if decoded == 50 {
+                if selector_index >= num_selectors {
+                    return Err(Error::StructuralError(
+                        "insufficient selector indices for number of symbols",
+                    )
+                    .into());
+                }
+                if tree_indexes[selector_index] as usize >= huffman_trees.len() {
+                    return Err(Error::StructuralError("tree selector out of range").into());
+                }
+                current_huffman_tree = &huffman_trees[tree_indexes[selector_index] as usize];
+                selector_index += 1;
+                decoded = 0;
+            }
+
+            let v = current_huffman_tree.decode(br) as usize;
+            decoded += 1;
+
+            if v < 2 {
+                // This is either the RUNA or RUNB symbol.
+                if repeat == 0 {
+                    repeat_power = 1;
+                }
+                repeat += repeat_power << v;
+                repeat_power <<= 1;
+
+                // This limit of 2 million comes from the bzip2 source
+                // code. It prevents repeat from overflowing.
+                if repeat > 2 * 1024 * 1024 {
+                    return Err(Error::StructuralError("repeat count too large").into());
+                }
+                continue;
+            }
+
+            if repeat > 0 {
+                // We have decoded a complete run-length so we need to
+                // replicate the last output symbol.
+                if repeat > self.block_size - buf_index {
+                    return Err(Error::StructuralError("repeats past end of block").into());
+                }
+                for _ in 0..repeat {
+                    let b = mtf.first();
+                    self.tt[buf_index] = b as u32;
+                    self.c[b as usize] += 1;
+                    buf_index += 1;
+                }
+                repeat = 0;
+            }
+
+            if v == num_symbols - 1 {
+                // This is the EOF symbol. Because it's always at the
+                // end of the move-to-front list, and never gets moved
+                // to the front, it has this unique value.
+                break;
+            }
+
+            // Since two metasymbols (RUNA and RUNB) have values 0 and 1,
+            // one would expect |v-2| to be passed to the MTF decoder.
+            // However, the front of the MTF list is never referenced as 0,
+            // it's always referenced with a run-length of 1. Thus 0
+            // doesn't need to be encoded and we have |v-1| in the next
+            // line.
+            let b = mtf.decode(v - 1);
+            if buf_index >= self.block_size {
+                return Err(Error::StructuralError("data exceeds block size").into());
+            }
+            self.tt[buf_index] = b as u32;
+            self.c[b as usize] += 1;
+            buf_index += 1;
+        }
+
+        if orig_ptr >= buf_index {
+            return Err(Error::StructuralError("orig_ptr out of bounds").into());
+        }
+
+        // We have completed the entropy decoding. Now we can perform the
+        // inverse BWT and setup the RLE buffer.
+        self.pre_rle_len = buf_index;
+        self.pre_rle_used = 0;
+        self.t_pos = inverse_bwt(&mut self.tt[..buf_index], orig_ptr, &mut self.c);
+        self.last_byte = -1;
+        self.byte_repeats = 0;
+        self.repeats = 0;
+
+        Ok(())
+    }
+}
+
+impl<Input: BufRead> std::io::Read for Reader<Input> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.buf_pos == self.buf_end {
+            // Nothing is buffered, decompress straight into buf.
+            return self.decode(buf);
+        }
+        let n = compat::copy(buf, &self.buf[self.buf_pos..self.buf_end]);
+        self.buf_pos += n;
+        Ok(n)
+    }
+}
+
+impl<Input: BufRead> BufRead for Reader<Input> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        if self.buf_pos == self.buf_end {
+            let mut buf = std::mem::take(&mut self.buf);
+            buf.resize(BUF_SIZE, 0);
+            let res = self.decode(&mut buf);
+            self.buf = buf;
+            self.buf_pos = 0;
+            self.buf_end = res?;
+        }
+        Ok(&self.buf[self.buf_pos..self.buf_end])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.buf_pos = (self.buf_pos + amt).min(self.buf_end);
+    }
+}
+
+/// inverse_bwt implements the inverse Burrows-Wheeler transform as described in
+/// http://www.hpl.hp.com/techreports/Compaq-DEC/SRC-RR-124.pdf, section 4.2.
+/// In that document, orig_ptr is called “I” and c is the “C” array after the
+/// first pass over the data. It's an argument here because we merge the first
+/// pass with the Huffman decoding.
+///
+/// This also implements the “single array” method from the bzip2 source code
+/// which leaves the output, still shuffled, in the bottom 8 bits of tt with the
+/// index of the next byte in the top 24-bits. The index of the first byte is
+/// returned.
+pub(super) fn inverse_bwt(tt: &mut [u32], orig_ptr: usize, c: &mut [usize]) -> u32 {
+    let mut sum = 0;
+    for ci in c.iter_mut().take(256) {
+        sum += *ci;
+        *ci = sum - *ci;
+    }
+
+    for i in 0..tt.len() {
+        let b = (tt[i] & 0xff) as usize;
+        tt[c[b]] |= (i as u32) << 8;
+        c[b] += 1;
+    }
+
+    tt[orig_ptr] >> 8
+}
+
+// This is a standard CRC32 like in hash::crc32 except that all the shifts are reversed,
+// causing the bits in the input to be processed in the reverse of the usual order.
+
+const CRCTAB: [u32; 256] = make_crc_table();
+
+const fn make_crc_table() -> [u32; 256] {
+    const POLY: u32 = 0x04C11DB7;
+    let mut tab = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = (i as u32) << 24;
+        let mut j = 0;
+        while j < 8 {
+            if crc & 0x80000000 != 0 {
+                crc = (crc << 1) ^ POLY;
+            } else {
+                crc <<= 1;
+            }
+            j += 1;
+        }
+        tab[i] = crc;
+        i += 1;
+    }
+    tab
+}
+
+/// update_crc updates the crc value to incorporate the data in b.
+/// The initial value is 0.
+pub(super) fn update_crc(val: u32, b: &[u8]) -> u32 {
+    let mut crc = !val;
+    for &v in b {
+        crc = CRCTAB[((crc >> 24) as u8 ^ v) as usize] ^ (crc << 8);
+    }
+    !crc
+}
@@ -0,0 +1,351 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::bit_reader::BitReader;
+use super::bzip2::{inverse_bwt, update_crc};
+use super::move_to_front::MoveToFrontDecoder;
+use super::{Error, Reader};
+use crate::encoding::hex;
+use std::io::Read;
+
+fn must_decode_hex(s: &str) -> Vec<u8> {
+    let (b, err) = hex::decode_string(s);
+    assert!(err.is_none(), "invalid hex string");
+    b
+}
+
+fn must_load_file(f: &str) -> Vec<u8> {
+    std::fs::read(f).unwrap()
+}
+
+fn decompress(input: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut r = input;
+    let mut br = Reader::new(&mut r);
+    let mut output = Vec::new();
+    let mut buf = [0; 4096];
+    loop {
+        let n = br.read(&mut buf)?;
+        if n == 0 {
+            return Ok(output);
+        }
+        output.extend_from_slice(&buf[..n]);
+    }
+}
+
+/// random_data returns the pseudo-random bytes compressed
+/// in testdata/random.data.bz2.
+fn random_data() -> Vec<u8> {
+    let mut x: u64 = 1;
+    (0..120000)
+        .map(|_| {
+            x = x
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (x >> 56) as u8
+        })
+        .collect()
+}
+
+const HELLO_WORLD_BZ2: &str = "425a68393141592653594eece83600000251800010400006449080200031064c4101a7a9a580bb9431f8bb9229c28482776741b0";
+
+struct ReaderTest {
+    desc: &'static str,
+    input: Vec<u8>,
+    output: Vec<u8>,
+    err: Option<std::io::Error>,
+}
+
+#[test]
+fn test_reader() {
+    let mut corrupt_block_crc = must_decode_hex(HELLO_WORLD_BZ2);
+    corrupt_block_crc[10] ^= 0x01;
+    let mut corrupt_file_crc = must_decode_hex(HELLO_WORLD_BZ2);
+    *corrupt_file_crc.last_mut().unwrap() ^= 0x80;
+    let hello = must_decode_hex(HELLO_WORLD_BZ2);
+
+    let vectors = vec![
+        ReaderTest {
+            desc: "hello world",
+            input: must_decode_hex(HELLO_WORLD_BZ2),
+            output: b"hello world\n".to_vec(),
+            err: None,
+        },
+        ReaderTest {
+            desc: "concatenated files",
+            input: [must_decode_hex(HELLO_WORLD_BZ2), must_decode_hex(HELLO_WORLD_BZ2)].concat(),
+            output: b"hello world\nhello world\n".to_vec(),
+            err: None,
+        },
+        ReaderTest {
+            desc: "300000 zeros",
+            input: must_decode_hex(
+                "425a6831314159265359df474d6f00024c4080c00004000008200030cc0529a61446c4511e2ee48a70a121be8e9ade",
+            ),
+            output: vec![0; 300000],
+            err: None,
+        },
+        ReaderTest {
+            desc: "empty file",
+            input: must_decode_hex("425a683917724538509000000000"),
+            output: Vec::new(),
+            err: None,
+        },
+        ReaderTest {
+            desc: "e.txt",
+            input: must_load_file("src/compress/bzip2/testdata/e.txt.bz2"),
+            output: must_load_file("src/compress/testdata/e.txt"),
+            err: None,
+        },
+        ReaderTest {
+            desc: "random data (multiple blocks)",
+            input: must_load_file("src/compress/bzip2/testdata/random.data.bz2"),
+            output: random_data(),
+            err: None,
+        },
+        ReaderTest {
+            desc: "bad magic value",
+            input: b"not a bzip2 file".to_vec(),
+            output: Vec::new(),
+            err: Some(Error::StructuralError("bad magic value").into()),
+        },
+        ReaderTest {
+            desc: "invalid compression level",
+            input: must_decode_hex("425a683017724538509000000000"),
+            output: Vec::new(),
+            err: Some(Error::StructuralError("invalid compression level").into()),
+        },
+        ReaderTest {
+            desc: "block checksum mismatch",
+            input: corrupt_block_crc,
+            output: Vec::new(),
+            err: Some(Error::StructuralError("block checksum mismatch").into()),
+        },
+        ReaderTest {
+            desc: "file checksum mismatch",
+            input: corrupt_file_crc,
+            output: Vec::new(),
+            err: Some(Error::StructuralError("file checksum mismatch").into()),
+        },
+        ReaderTest {
+            desc: "truncated file",
+            input: hello[..hello.len() - 10].to_vec(),
+            output: Vec::new(),
+            err: Some(std::io::ErrorKind::UnexpectedEof.into()),
+        },
+        ReaderTest {
+            desc: "truncated continuation file",
+            input: [hello.clone(), b"B".to_vec()].concat(),
+            output: Vec::new(),
+            err: Some(std::io::ErrorKind::UnexpectedEof.into()),
+        },
+        ReaderTest {
+            desc: "bad continuation file",
+            input: [hello.clone(), b"ZB".to_vec()].concat(),
+            output: Vec::new(),
+            err: Some(Error::StructuralError(
+                "bad magic value in continuation file",
+            ).into()),
+        },
+    ];
+
+    for v in vectors {
+        let res = decompress(&v.input);
+        match v.err {
+            None => {
+                let output =
+                    res.unwrap_or_else(|err| panic!("{}: unexpected error {}", v.desc, err));
+                assert!(output == v.output, "{}: output mismatch", v.desc);
+            }
+            Some(want) => match res {
+                Ok(_) => panic!("{}: unexpected success", v.desc),
+                Err(err) => {
+                    assert_eq!(err.kind(), want.kind(), "{}", v.desc);
+                    let want = want.get_ref().and_then(|e| e.downcast_ref::<Error>());
+                    let got = err.get_ref().and_then(|e| e.downcast_ref::<Error>());
+                    assert_eq!(got, want, "{}", v.desc);
+                }
+            },
+        }
+    }
+}
+
+#[test]
+fn test_reader_std_io() {
+    let input = must_load_file("src/compress/bzip2/testdata/e.txt.bz2");
+    let mut r = input.as_slice();
+    let mut br = Reader::new(&mut r);
+    let mut output = String::new();
+    br.read_to_string(&mut output).unwrap();
+    assert_eq!(
+        output.as_bytes(),
+        must_load_file("src/compress/testdata/e.txt")
+    );
+
+    // Errors are persistent and reported as std::io::Error.
+    let mut input = must_decode_hex(HELLO_WORLD_BZ2);
+    input[10] ^= 0x01;
+    let mut r = input.as_slice();
+    let mut br = Reader::new(&mut r);
+    let mut output = Vec::new();
+    let err = br.read_to_end(&mut output).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "bzip2 data invalid: block checksum mismatch"
+    );
+    let err = br.read_to_end(&mut output).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "bzip2 data invalid: block checksum mismatch"
+    );
+}
+
+#[test]
+fn test_reader_owns_input() {
+    fn open(input: Vec<u8>) -> Box<dyn std::io::Read> {
+        Box::new(Reader::new(std::io::Cursor::new(input)))
+    }
+    let mut output = String::new();
+    open(must_decode_hex(HELLO_WORLD_BZ2))
+        .read_to_string(&mut output)
+        .unwrap();
+    assert_eq!(output, "hello world\n");
+
+    let input = must_decode_hex(HELLO_WORLD_BZ2);
+    let mut br = Reader::new(std::io::Cursor::new(input.clone()));
+    assert_eq!(br.get_ref().position(), 0);
+    br.read_to_end(&mut Vec::new()).unwrap();
+    assert_eq!(br.get_mut().position(), input.len() as u64);
+    assert_eq!(br.into_inner().into_inner(), input);
+}
+
+#[test]
+fn test_zero_read() {
+    let input = must_decode_hex(HELLO_WORLD_BZ2);
+    let mut r = input.as_slice();
+    let mut br = Reader::new(&mut r);
+    let mut b = [0; 1];
+    assert_eq!(br.read(&mut b).unwrap(), 1);
+    assert_eq!(br.read(&mut []).unwrap(), 0);
+    let mut output = Vec::new();
+    std::io::Read::read_to_end(&mut br, &mut output).unwrap();
+    assert_eq!(output, b"ello world\n");
+}
+
+#[test]
+fn test_bit_reader() {
+    struct BitReaderTest {
+        n_bits: usize,
+        value: u64,
+        fail: bool,
+    }
+    let vectors = [
+        BitReaderTest {
+            n_bits: 1,
+            value: 1,
+            fail: false,
+        },
+        BitReaderTest {
+            n_bits: 1,
+            value: 0,
+            fail: false,
+        },
+        BitReaderTest {
+            n_bits: 1,
+            value: 1,
+            fail: false,
+        },
+        BitReaderTest {
+            n_bits: 5,
+            value: 11,
+            fail: false,
+        },
+        BitReaderTest {
+            n_bits: 32,
+            value: 0x12345678,
+            fail: false,
+        },
+        BitReaderTest {
+            n_bits: 15,
+            value: 14495,
+            fail: false,
+        },
+        BitReaderTest {
+            n_bits: 3,
+            value: 6,
+            fail: false,
+        },
+        BitReaderTest {
+            n_bits: 6,
+            value: 13,
+            fail: false,
+        },
+        BitReaderTest {
+            n_bits: 1,
+            value: 0,
+            fail: true,
+        },
+    ];
+
+    let input = must_decode_hex("ab12345678713f8d");
+    let mut r = input.as_slice();
+    let mut br = BitReader::new(&mut r);
+    for (i, v) in vectors.iter().enumerate() {
+        let val = br.read_bits64(v.n_bits);
+        if v.fail {
+            assert!(br.err.is_some(), "test {}: expected error", i);
+        } else {
+            assert!(br.err.is_none(), "test {}: unexpected error", i);
+            assert_eq!(val, v.value, "test {}", i);
+        }
+    }
+}
+
+#[test]
+fn test_mtf() {
+    let mut mtf = MoveToFrontDecoder::new_with_range(5);
+
+    // 0 1 2 3 4
+    let vectors = [
+        (1, 1), // 1 0 2 3 4
+        (0, 1), // 1 0 2 3 4
+        (1, 0), // 0 1 2 3 4
+        (4, 4), // 4 0 1 2 3
+        (0, 4), // 4 0 1 2 3
+    ];
+    for (i, (idx, sym)) in vectors.into_iter().enumerate() {
+        let got = mtf.decode(idx);
+        assert_eq!(got, sym, "test {}: Decode({})", i, idx);
+    }
+    assert_eq!(mtf.first(), 4);
+}
+
+#[test]
+fn test_inverse_bwt() {
+    // The sorted rotations of "banana" end with "nnbaaa",
+    // and the original string is at row 3.
+    let mut tt: Vec<u32> = b"nnbaaa".iter().map(|&b| b as u32).collect();
+    let mut c = [0usize; 256];
+    for &b in b"nnbaaa" {
+        c[b as usize] += 1;
+    }
+    let mut t_pos = inverse_bwt(&mut tt, 3, &mut c);
+    let mut output = Vec::new();
+    for _ in 0..tt.len() {
+        t_pos = tt[t_pos as usize];
+        output.push(t_pos as u8);
+        t_pos >>= 8;
+    }
+    assert_eq!(output, b"banana");
+}
+
+#[test]
+fn test_update_crc() {
+    // The bzip2 block CRC of "hello world\n" as stored in HELLO_WORLD_BZ2.
+    assert_eq!(update_crc(0, b"hello world\n"), 0x4eece836);
+    assert_eq!(
+        update_crc(update_crc(0, b"hello "), b"world\n"),
+        update_crc(0, b"hello world\n")
+    );
+}
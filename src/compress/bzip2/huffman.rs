@@ -0,0 +1,215 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::bit_reader::BitReader;
+use super::bzip2::Error;
+use std::io::BufRead;
+
+/// HuffmanTree is a binary tree which is navigated, bit-by-bit, to reach a
+/// symbol.
+pub(super) struct HuffmanTree {
+    // nodes contains all the non-leaf nodes in the tree. nodes[0] is the
+    // root of the tree and next_node contains the index of the next element
+    // of nodes to use when the tree is being constructed.
+    nodes: Vec<HuffmanNode>,
+    next_node: usize,
+}
+
+/// HuffmanNode is a node in the tree. left and right contain indexes into the
+/// nodes vector of the tree. If left or right is INVALID_NODE_VALUE then the child
+/// is a left node and its value is in left_value/right_value.
+///
+/// The symbols are u16s because bzip2 encodes not only MTF indexes in the
+/// tree, but also two magic values for run-length encoding and an EOF symbol.
+/// Thus there are more than 256 possible symbols.
+#[derive(Default, Clone, Copy)]
+struct HuffmanNode {
+    left: u16,
+    right: u16,
+    left_value: u16,
+    right_value: u16,
+}
+
+/// INVALID_NODE_VALUE is an invalid index which marks a leaf node in the tree.
+const INVALID_NODE_VALUE: u16 = 0xffff;
+
+impl HuffmanTree {
+    /// decode reads bits from the given BitReader and navigates the tree until a
+    /// symbol is found.
+    pub(super) fn decode<Input: BufRead>(&self, br: &mut BitReader<Input>) -> u16 {
+        let mut node_index = 0; // node 0 is the root of the tree.
+
+        loop {
+            let node = &self.nodes[node_index as usize];
+
+            let bit = if br.bits > 0 {
+                // Get next bit - fast path.
+                br.bits -= 1;
+                (br.n >> (br.bits & 63)) as u16 & 1
+            } else {
+                // Get next bit - slow path.
+                // Use read_bits to retrieve a single bit
+                // from the underling reader.
+                br.read_bits(1) as u16
+            };
+
+            // bzip2 encodes left as a true bit.
+            node_index = if bit == 1 { node.left } else { node.right };
+
+            if node_index == INVALID_NODE_VALUE {
+                // We found a leaf. Use the value of bit to decide
+                // whether is a left or a right value.
+                return if bit == 1 {
+                    node.left_value
+                } else {
+                    node.right_value
+                };
+            }
+        }
+    }
+
+    /// new builds a Huffman tree from a slice containing the code
+    /// lengths of each symbol. The maximum code length is 32 bits.
+    pub(super) fn new(lengths: &[u8]) -> std::io::Result<Self> {
+        // There are many possible trees that assign the same code length to
+        // each symbol (consider reflecting a tree down the middle, for
+        // example). Since the code length assignments determine the
+        // efficiency of the tree, each of these trees is equally good. In
+        // order to minimize the amount of information needed to build a tree
+        // bzip2 uses a canonical tree so that it can be reconstructed given
+        // only the code length assignments.
+
+        if lengths.len() < 2 {
+            panic!("HuffmanTree::new: too few symbols");
+        }
+
+        // First we sort the code length assignments by ascending code length,
+        // using the symbol value to break ties.
+        let mut pairs: Vec<HuffmanSymbolLengthPair> = lengths
+            .iter()
+            .enumerate()
+            .map(|(i, &length)| HuffmanSymbolLengthPair {
+                value: i as u16,
+                length,
+            })
+            .collect();
+        pairs.sort_by_key(|p| (p.length, p.value));
+
+        // Now we assign codes to the symbols, starting with the longest code.
+        // We keep the codes packed into a u32, at the most-significant end.
+        // So branches are taken from the MSB downwards. This makes it easy to
+        // sort them later.
+        let mut code = 0u32;
+        let mut length = 32u8;
+
+        let mut codes = vec![HuffmanCode::default(); lengths.len()];
+        for i in (0..pairs.len()).rev() {
+            if length > pairs[i].length {
+                length = pairs[i].length;
+            }
+            codes[i].code = code;
+            codes[i].value = pairs[i].value;
+            // We need to 'increment' the code, which means treating |code|
+            // like a |length| bit number.
+            code = code.wrapping_add(1 << (32 - length));
+        }
+
+        // Now we can sort by the code so that the left half of each branch are
+        // grouped together, recursively.
+        codes.sort_by_key(|c| c.code);
+
+        let mut t = HuffmanTree {
+            nodes: vec![HuffmanNode::default(); codes.len()],
+            next_node: 0,
+        };
+        t.build_node(&codes, 0)?;
+        Ok(t)
+    }
+
+    /// build_node takes a slice of sorted HuffmanCodes and builds a node in
+    /// the Huffman tree at the given level. It returns the index of the newly
+    /// constructed node.
+    fn build_node(&mut self, codes: &[HuffmanCode], level: u32) -> std::io::Result<u16> {
+        let test = 1u32 << (31 - level);
+
+        // We have to search the list of codes to find the divide between the left and right sides.
+        let first_right_index = codes
+            .iter()
+            .position(|c| c.code & test != 0)
+            .unwrap_or(codes.len());
+
+        let (left, right) = codes.split_at(first_right_index);
+
+        if left.is_empty() || right.is_empty() {
+            // There is a superfluous level in the Huffman tree indicating
+            // a bug in the encoder. However, this bug has been observed in
+            // the wild so we handle it.
+
+            // If this function was called recursively then we know that
+            // codes.len() >= 2 because, otherwise, we would have hit the
+            // "leaf node" case, below, and not recurred.
+            //
+            // However, for the initial call it's possible that codes.len()
+            // is zero or one. Both cases are invalid because a zero length
+            // tree cannot encode anything and a length-1 tree can only
+            // encode EOF and so is superfluous. We reject both.
+            if codes.len() < 2 {
+                return Err(Error::StructuralError("empty Huffman tree").into());
+            }
+
+            // In this case the recursion doesn't always reduce the length
+            // of codes so we need to ensure termination via another
+            // mechanism.
+            if level == 31 {
+                // Since codes.len() >= 2 the only way that the values
+                // can match at all 32 bits is if they are equal, which
+                // is invalid. This ensures that we never enter
+                // infinite recursion.
+                return Err(Error::StructuralError("equal symbols in Huffman tree").into());
+            }
+
+            if left.is_empty() {
+                return self.build_node(right, level + 1);
+            }
+            return self.build_node(left, level + 1);
+        }
+
+        let node_index = self.next_node;
+        self.next_node += 1;
+
+        let mut node = HuffmanNode::default();
+        if left.len() == 1 {
+            // leaf node
+            node.left = INVALID_NODE_VALUE;
+            node.left_value = left[0].value;
+        } else {
+            node.left = self.build_node(left, level + 1)?;
+        }
+
+        if right.len() == 1 {
+            // leaf node
+            node.right = INVALID_NODE_VALUE;
+            node.right_value = right[0].value;
+        } else {
+            node.right = self.build_node(right, level + 1)?;
+        }
+        self.nodes[node_index] = node;
+
+        Ok(node_index as u16)
+    }
+}
+
+/// HuffmanSymbolLengthPair contains a symbol and its code length.
+struct HuffmanSymbolLengthPair {
+    value: u16,
+    length: u8,
+}
+
+/// HuffmanCode contains a symbol and its code.
+#[derive(Default, Clone, Copy)]
+struct HuffmanCode {
+    code: u32,
+    value: u16,
+}
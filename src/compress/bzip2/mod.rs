@@ -0,0 +1,16 @@
+// Copyright 2023 The rust-ggstd authors.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! Package bzip2 implements bzip2 decompression.
+
+mod bit_reader;
+mod bzip2;
+mod huffman;
+mod move_to_front;
+
+pub use self::bzip2::{Error, Reader};
+
+#[cfg(test)]
+mod bzip2_test;
@@ -0,0 +1,48 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+/// MoveToFrontDecoder implements a move-to-front list. Such a list is an
+/// efficient way to transform a string with repeating elements into one with
+/// many small valued numbers, which is suitable for entropy encoding. It works
+/// by starting with an initial list of symbols and references symbols by their
+/// index into that list. When a symbol is referenced, it's moved to the front
+/// of the list. Thus, a repeated symbol ends up being encoded with many zeros,
+/// as the symbol will be at the front of the list after the first access.
+pub(super) struct MoveToFrontDecoder(Vec<u8>);
+
+impl MoveToFrontDecoder {
+    /// new creates a move-to-front list initialized with the given symbols.
+    pub(super) fn new(symbols: &[u8]) -> Self {
+        if symbols.len() > 256 {
+            panic!("too many symbols");
+        }
+        Self(symbols.to_vec())
+    }
+
+    /// new_with_range creates a move-to-front list with the symbols
+    /// in the range [0, n).
+    pub(super) fn new_with_range(n: usize) -> Self {
+        if n > 256 {
+            panic!("new_with_range: cannot have > 256 symbols");
+        }
+        Self((0..n).map(|i| i as u8).collect())
+    }
+
+    pub(super) fn decode(&mut self, n: usize) -> u8 {
+        // Implement move-to-front with a simple copy. This approach
+        // beats more sophisticated approaches in benchmarking, probably
+        // because it has high locality of reference inside of a
+        // single cache line (most move-to-front operations have n < 64).
+        let b = self.0[n];
+        self.0.copy_within(..n, 1);
+        self.0[0] = b;
+        b
+    }
+
+    /// first returns the symbol at the front of the list.
+    pub(super) fn first(&self) -> u8 {
+        self.0[0]
+    }
+}
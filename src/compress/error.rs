@@ -0,0 +1,35 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::{bzip2, flate, gzip, lzw, zlib};
+use crate::errors;
+
+/// copy_error returns a copy of err. Unlike errors::copy_stdio_error,
+/// it keeps the error types of the compress packages, so that the copy
+/// can still be inspected with get_ref and downcast_ref.
+pub(crate) fn copy_error(err: &std::io::Error) -> std::io::Error {
+    if let Some(inner) = err.get_ref() {
+        if let Some(e) = inner.downcast_ref::<flate::Error>() {
+            return std::io::Error::new(err.kind(), *e);
+        }
+        if let Some(e) = inner.downcast_ref::<zlib::Error>() {
+            return std::io::Error::new(err.kind(), *e);
+        }
+        if let Some(e) = inner.downcast_ref::<gzip::Error>() {
+            return std::io::Error::new(err.kind(), *e);
+        }
+        if let Some(e) = inner.downcast_ref::<bzip2::Error>() {
+            return std::io::Error::new(err.kind(), *e);
+        }
+        if let Some(e) = inner.downcast_ref::<lzw::Error>() {
+            return std::io::Error::new(err.kind(), *e);
+        }
+    }
+    errors::copy_stdio_error(err)
+}
+
+/// copy_option_error is like copy_error for an optional error.
+pub(crate) fn copy_option_error(err: &Option<std::io::Error>) -> Option<std::io::Error> {
+    err.as_ref().map(copy_error)
+}
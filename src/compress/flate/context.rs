@@ -0,0 +1,119 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::deflate::{check_level, Compressor};
+use super::inflate::Reader;
+use std::io::Read;
+
+/// compress_bound returns the maximum size of the DEFLATE stream produced
+/// by compressing n bytes at any level. compress allocates its output
+/// with this capacity, so that it is never reallocated.
+pub fn compress_bound(n: usize) -> usize {
+    // Incompressible data is written as stored blocks of at most 16K
+    // tokens, which cost 5 bytes each, or as Huffman blocks which are
+    // at most a few bytes longer than that. 8 bytes per 16 KiB leaves room
+    // for both. The stream ends with an empty stored block.
+    n + (n >> 11) + 16
+}
+
+/// compress returns the DEFLATE-compressed form of src at the given level.
+/// The level is as for Writer::new.
+pub fn compress(src: &[u8], level: isize) -> std::io::Result<Vec<u8>> {
+    Context::new(level)?.compress(src)
+}
+
+/// decompress returns the data decompressed from the DEFLATE stream src.
+/// Any data following the end of the stream is ignored.
+pub fn decompress(src: &[u8]) -> std::io::Result<Vec<u8>> {
+    Context::new(super::DEFAULT_COMPRESSION)?.decompress(src)
+}
+
+/// Context compresses and decompresses whole slices in memory.
+/// It keeps the compressor and decompressor state between calls,
+/// so that compressing many small inputs doesn't allocate the window
+/// and the hash chains for each one of them.
+pub struct Context {
+    level: isize,
+    compressor: Option<Compressor<Vec<u8>>>,
+    decompressor: Option<Reader<&'static [u8]>>,
+}
+
+impl Context {
+    /// new returns a Context compressing at the given level.
+    /// The level is as for Writer::new.
+    pub fn new(level: isize) -> std::io::Result<Self> {
+        check_level(level)?;
+        Ok(Self {
+            level,
+            compressor: None,
+            decompressor: None,
+        })
+    }
+
+    /// compress returns the compressed form of src.
+    pub fn compress(&mut self, src: &[u8]) -> std::io::Result<Vec<u8>> {
+        let mut dst = Vec::with_capacity(compress_bound(src.len()));
+        self.compress_to(src, &mut dst)?;
+        Ok(dst)
+    }
+
+    /// compress_to appends the compressed form of src to dst and returns
+    /// the number of bytes appended. The compressor writes straight into dst,
+    /// after reserving compress_bound(src.len()) bytes of its capacity.
+    pub fn compress_to(&mut self, src: &[u8], dst: &mut Vec<u8>) -> std::io::Result<usize> {
+        let start = dst.len();
+        dst.reserve(compress_bound(src.len()));
+        let output = std::mem::take(dst);
+        let d = match &mut self.compressor {
+            Some(d) => {
+                d.reset(output);
+                d
+            }
+            None => self.compressor.insert(Compressor::new(output, self.level)),
+        };
+        let res = d.write(src).and_then(|_| d.close());
+        std::mem::swap(dst, d.output());
+        res?;
+        Ok(dst.len() - start)
+    }
+
+    /// decompress returns the data decompressed from the DEFLATE stream src.
+    /// Any data following the end of the stream is ignored.
+    pub fn decompress(&mut self, src: &[u8]) -> std::io::Result<Vec<u8>> {
+        let mut dst = Vec::new();
+        self.decompress_to(src, &mut dst)?;
+        Ok(dst)
+    }
+
+    /// decompress_to appends the data decompressed from the DEFLATE stream
+    /// src to dst and returns the number of bytes appended.
+    /// Any data following the end of the stream is ignored.
+    pub fn decompress_to(&mut self, src: &[u8], dst: &mut Vec<u8>) -> std::io::Result<usize> {
+        let start = dst.len();
+        self.decompress_stream(src, dst)?;
+        Ok(dst.len() - start)
+    }
+
+    /// decompress_stream appends the data decompressed from the DEFLATE
+    /// stream at the start of src to dst and returns the rest of src.
+    pub(in crate::compress) fn decompress_stream<'b>(
+        &mut self,
+        src: &'b [u8],
+        dst: &mut Vec<u8>,
+    ) -> std::io::Result<&'b [u8]> {
+        let mut f = match self.decompressor.take() {
+            Some(f) => {
+                let (mut f, _) = f.replace_input(src);
+                f.reset_state(&[]);
+                f
+            }
+            None => Reader::new(src),
+        };
+        let res = f.read_to_end(dst);
+        let (f, rest) = f.replace_input(&[][..]);
+        self.decompressor = Some(f);
+        res?;
+        Ok(rest)
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::index_test::test_data;
+use super::{
+    compress, compress_bound, decompress, Context, Writer, BEST_COMPRESSION, HUFFMAN_ONLY,
+};
+use std::io::Write;
+
+/// random_data returns n pseudo-random bytes, which don't compress.
+pub(in crate::compress) fn random_data(n: usize) -> Vec<u8> {
+    let mut x: u64 = 7;
+    (0..n)
+        .map(|_| {
+            x = x
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (x >> 56) as u8
+        })
+        .collect()
+}
+
+#[test]
+fn test_compress() {
+    let data = test_data(200_000);
+    for level in HUFFMAN_ONLY..=BEST_COMPRESSION {
+        let mut want = Vec::new();
+        let mut w = Writer::new(&mut want, level).unwrap();
+        w.write_all(&data).unwrap();
+        w.close().unwrap();
+
+        let got = compress(&data, level).unwrap();
+        assert!(got == want, "level {}: output differs from Writer", level);
+        assert!(decompress(&got).unwrap() == data, "level {}", level);
+    }
+    assert!(compress(&data, 10).is_err());
+    assert!(Context::new(-3).is_err());
+}
+
+#[test]
+fn test_context_reuse() {
+    for level in HUFFMAN_ONLY..=BEST_COMPRESSION {
+        let mut ctx = Context::new(level).unwrap();
+        for n in [100_000, 0, 1, 70_000, 100_000] {
+            let data = test_data(n);
+            let want = compress(&data, level).unwrap();
+            let got = ctx.compress(&data).unwrap();
+            assert!(got == want, "level {}, size {}", level, n);
+            assert!(ctx.decompress(&got).unwrap() == data, "level {}", level);
+        }
+    }
+}
+
+#[test]
+fn test_compress_to() {
+    let mut ctx = Context::new(BEST_COMPRESSION).unwrap();
+    let data = test_data(10_000);
+    let mut dst = b"prefix".to_vec();
+    let n = ctx.compress_to(&data, &mut dst).unwrap();
+    assert_eq!(n, dst.len() - 6);
+    assert_eq!(&dst[..6], b"prefix");
+
+    let mut output = b"prefix".to_vec();
+    let n = ctx.decompress_to(&dst[6..], &mut output).unwrap();
+    assert_eq!(n, data.len());
+    assert!(output[6..] == data[..]);
+}
+
+#[test]
+fn test_compress_bound() {
+    for n in [0, 1, 16, 100, 1000, 16384, 65535, 65536, 100_000, 1_000_000] {
+        let data = random_data(n);
+        for level in HUFFMAN_ONLY..=BEST_COMPRESSION {
+            let mut ctx = Context::new(level).unwrap();
+            let compressed = ctx.compress(&data).unwrap();
+            assert!(
+                compressed.len() <= compress_bound(n),
+                "level {}, size {}: {} > {}",
+                level,
+                n,
+                compressed.len(),
+                compress_bound(n)
+            );
+            assert_eq!(compressed.capacity(), compress_bound(n));
+        }
+    }
+}
+
+#[test]
+fn test_decompress_errors() {
+    let data = test_data(50_000);
+    let compressed = compress(&data, BEST_COMPRESSION).unwrap();
+    let mut ctx = Context::new(BEST_COMPRESSION).unwrap();
+    let err = ctx
+        .decompress(&compressed[..compressed.len() / 2])
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert!(ctx.decompress(&[0xff; 100]).is_err());
+
+    // The context is usable after an error, and data after the end
+    // of the stream is ignored.
+    let mut input = compressed.clone();
+    input.extend_from_slice(b"trailing data");
+    assert!(ctx.decompress(&input).unwrap() == data);
+}
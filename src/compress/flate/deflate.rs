@@ -137,9 +137,9 @@ fn match_len(a: &[u8], b: &[u8], max: usize) -> usize {
     max
 }
 
-pub(super) struct Compressor<'a, Output: std::io::Write> {
-    compression_level: &'a CompressionLevel,
-    hbw: HuffmanBitWriter<'a, Output>,
+pub(super) struct Compressor<Output: std::io::Write> {
+    compression_level: &'static CompressionLevel,
+    hbw: HuffmanBitWriter<Output>,
     #[allow(clippy::type_complexity)]
     bulk_hasher: Option<fn(&[u8], &mut [u32])>,
 
@@ -194,7 +194,7 @@ enum StepFunc {
 }
 
 #[allow(dead_code)]
-impl<'a, Output: std::io::Write> Compressor<'a, Output> {
+impl<Output: std::io::Write> Compressor<Output> {
     fn fill_deflate(&mut self, b: &[u8]) -> usize {
         if self.index >= 2 * WINDOW_SIZE - (MIN_MATCH_LENGTH + MAX_MATCH_LENGTH) {
             // shift the window by WINDOW_SIZE
@@ -616,7 +616,7 @@ impl<'a, Output: std::io::Write> Compressor<'a, Output> {
     }
 
     // result of the operation will be stored in self.err, use self.error() to get it
-    fn write(&mut self, b: &[u8]) -> std::io::Result<usize> {
+    pub(super) fn write(&mut self, b: &[u8]) -> std::io::Result<usize> {
         if let Err(e) = self.error() {
             return Err(compat::copy_stdio_error(e));
         }
@@ -666,8 +666,8 @@ impl<'a, Output: std::io::Write> Compressor<'a, Output> {
         Ok(())
     }
 
-    fn new(w: &'a mut Output, level: isize) -> Self {
-        let compression_level: &'a CompressionLevel;
+    pub(super) fn new(w: Output, level: isize) -> Self {
+        let compression_level: &'static CompressionLevel;
         #[allow(clippy::type_complexity)]
         let mut bulk_hasher: Option<fn(&[u8], &mut [u32])> = None;
         let fill: FillFunc;
@@ -747,7 +747,7 @@ impl<'a, Output: std::io::Write> Compressor<'a, Output> {
         }
     }
 
-    fn reset(&mut self, w: &'a mut Output) {
+    pub(super) fn reset(&mut self, w: Output) {
         self.hbw.reset(w);
         self.sync = false;
         self.err = Ok(0);
@@ -767,11 +767,11 @@ impl<'a, Output: std::io::Write> Compressor<'a, Output> {
         self.offset = 0;
         self.max_insert_index = 0;
         self.err = Ok(0);
-        self.hash_match = vec![0; MAX_MATCH_LENGTH - 1];
+        self.hash_match.fill(0);
         self.writer_closed = false;
     }
 
-    fn close(&mut self) -> std::io::Result<()> {
+    pub(super) fn close(&mut self) -> std::io::Result<()> {
         if self.writer_closed {
             return Ok(());
         }
@@ -804,18 +804,45 @@ impl<'a, Output: std::io::Write> Compressor<'a, Output> {
     pub fn output(&mut self) -> &mut Output {
         self.hbw.output()
     }
+
+    /// Returns a reference to the output writer.
+    fn output_ref(&self) -> &Output {
+        self.hbw.output_ref()
+    }
+
+    /// Returns the output writer, consuming the compressor.
+    fn into_output(self) -> Output {
+        self.hbw.into_output()
+    }
+}
+
+/// check_level returns an error if level is not a valid compression level.
+pub(super) fn check_level(level: isize) -> std::io::Result<()> {
+    if level == NO_COMPRESSION
+        || level == HUFFMAN_ONLY
+        || level == BEST_SPEED
+        || level == DEFAULT_COMPRESSION
+        || (2..=9).contains(&level)
+    {
+        return Ok(());
+    }
+    let msg = format!(
+        "flate: invalid compression level {}: want value in range [-2, 9]",
+        level
+    );
+    Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, msg))
 }
 
 // var errWriterClosed = errors.New("flate: closed writer")
 
 // A Writer takes data written to it and writes the compressed
 // form of that data to the output writer.
-pub struct Writer<'a, Output: std::io::Write> {
-    d: Compressor<'a, Output>,
+pub struct Writer<Output: std::io::Write> {
+    d: Compressor<Output>,
     dict: Option<Vec<u8>>,
 }
 
-impl<'a, Output: std::io::Write> Writer<'a, Output> {
+impl<Output: std::io::Write> Writer<Output> {
     /// new returns a new Writer compressing data at the given level.
     /// Following zlib, levels range from 1 (BEST_SPEED) to 9 (BEST_COMPRESSION);
     /// higher levels typically run slower but compress more. Level 0
@@ -828,22 +855,8 @@ impl<'a, Output: std::io::Write> Writer<'a, Output> {
     ///
     /// If level is in the range [-2, 9] then the error returned will be nil.
     /// Otherwise the error returned will be non-nil.
-    pub fn new(w: &'a mut Output, level: isize) -> std::io::Result<Self> {
-        if level == NO_COMPRESSION
-            || level == HUFFMAN_ONLY
-            || level == BEST_SPEED
-            || level == DEFAULT_COMPRESSION
-            || (2..=9).contains(&level)
-        {
-            // correct level
-        } else {
-            let msg = format!(
-                "flate: invalid compression level {}: want value in range [-2, 9]",
-                level
-            );
-            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, msg));
-        }
-
+    pub fn new(w: Output, level: isize) -> std::io::Result<Self> {
+        check_level(level)?;
         return Ok(Self {
             d: Compressor::new(w, level),
             dict: None,
@@ -856,7 +869,7 @@ impl<'a, Output: std::io::Write> Writer<'a, Output> {
     /// any compressed output. The compressed data written to w
     /// can only be decompressed by a Reader initialized with the
     /// same dictionary.
-    pub fn new_dict(w: &'a mut Output, level: isize, dict: &[u8]) -> std::io::Result<Self> {
+    pub fn new_dict(w: Output, level: isize, dict: &[u8]) -> std::io::Result<Self> {
         let mut zw = Self::new(w, level)?;
         zw.d.fill_window(dict);
         zw.dict = Some(dict.to_vec()); // duplicate dictionary for Reset method.
@@ -871,7 +884,7 @@ impl<'a, Output: std::io::Write> Writer<'a, Output> {
     /// reset discards the writer's state and makes it equivalent to
     /// the result of Writer::new_dict called with dst
     /// and w's level and dictionary.
-    pub fn reset(&mut self, dst: &'a mut Output) {
+    pub fn reset(&mut self, dst: Output) {
         if self.dict.is_some() {
             // w was created with Writer::new_dict
             self.d.reset(dst);
@@ -886,9 +899,26 @@ impl<'a, Output: std::io::Write> Writer<'a, Output> {
     pub fn output(&mut self) -> &mut Output {
         self.d.output()
     }
+
+    /// get_ref returns a reference to the output writer.
+    pub fn get_ref(&self) -> &Output {
+        self.d.output_ref()
+    }
+
+    /// get_mut returns a mutable reference to the output writer.
+    /// Writing to it directly corrupts the compressed stream.
+    pub fn get_mut(&mut self) -> &mut Output {
+        self.d.output()
+    }
+
+    /// into_inner returns the output writer. close must be called before
+    /// to write the rest of the compressed data.
+    pub fn into_inner(self) -> Output {
+        self.d.into_output()
+    }
 }
 
-impl<Output: std::io::Write> std::io::Write for Writer<'_, Output> {
+impl<Output: std::io::Write> std::io::Write for Writer<Output> {
     /// write writes data to w, which will eventually write the
     /// compressed form of data to its underlying writer.
     fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
@@ -48,23 +48,33 @@ impl DictDecoder {
     /// size. If a preset dict is provided, it will initialize the dictionary with
     /// the contents of dict.
     pub fn new(size: usize, dict: &[u8]) -> Self {
-        let mut hist = vec![0; size];
-        let min_size = size.min(dict.len());
-        hist[0..min_size].copy_from_slice(&dict[0..min_size]);
-        let mut wr_pos = min_size;
-        let mut full = false;
-        if wr_pos == size {
-            wr_pos = 0;
-            full = true;
-        }
-        Self {
-            hist,
-            wr_pos,
-            rd_pos: wr_pos,
-            full,
+        let mut dd = Self {
+            hist: vec![0; size],
+            wr_pos: 0,
+            rd_pos: 0,
+            full: false,
             stash_start_pos: 0,
             stash_end_pos: 0,
+        };
+        dd.reset(dict);
+        dd
+    }
+
+    /// reset reinitializes the dictionary with the contents of dict,
+    /// keeping the allocated history buffer.
+    pub(super) fn reset(&mut self, dict: &[u8]) {
+        let size = self.hist.len();
+        let min_size = size.min(dict.len());
+        self.hist[0..min_size].copy_from_slice(&dict[0..min_size]);
+        self.wr_pos = min_size;
+        self.full = false;
+        if self.wr_pos == size {
+            self.wr_pos = 0;
+            self.full = true;
         }
+        self.rd_pos = self.wr_pos;
+        self.stash_start_pos = 0;
+        self.stash_end_pos = 0;
     }
 
     /// hist_size reports the total amount of historical data in the dictionary.
@@ -75,6 +85,14 @@ impl DictDecoder {
         self.wr_pos
     }
 
+    /// history returns a copy of the data in the dictionary, oldest first.
+    pub(super) fn history(&self) -> Vec<u8> {
+        if self.full {
+            return [&self.hist[self.wr_pos..], &self.hist[..self.wr_pos]].concat();
+        }
+        self.hist[..self.wr_pos].to_vec()
+    }
+
     /// avail_read reports the number of bytes that can be flushed by read_flush.
     pub fn avail_read(&self) -> usize {
         self.wr_pos - self.rd_pos
@@ -195,6 +213,14 @@ impl DictDecoder {
         dst_pos - dst_base
     }
 
+    /// hist_parts gives the fast path of the decoder direct access to the
+    /// history buffer. It returns the buffer, the current write position
+    /// and whether a full window length has been written.
+    /// The caller must call write_mark with the number of bytes written.
+    pub(super) fn hist_parts(&mut self) -> (&mut [u8], usize, bool) {
+        (&mut self.hist, self.wr_pos, self.full)
+    }
+
     // read_flush returns a slice of the historical buffer that is ready to be
     // emitted to the user. The data returned by read_flush must be fully consumed
     // before calling any other dictDecoder methods.
@@ -237,4 +263,60 @@ impl DictDecoder {
         self.stash_start_pos += n;
         n
     }
+
+    /// stash returns the stashed data that has not been read yet.
+    pub(super) fn stash(&self) -> &[u8] {
+        &self.hist[self.stash_start_pos..self.stash_end_pos]
+    }
+
+    /// stash_consume marks n bytes of the stash as read.
+    pub(super) fn stash_consume(&mut self, n: usize) {
+        self.stash_start_pos += n.min(self.stash_len());
+    }
+}
+
+/// copy_words copies length bytes from dist bytes back in the history
+/// buffer hist to position dst_pos. Overlapping copies with a distance of
+/// at least eight bytes are done a word at a time.
+///
+/// These invariants must be kept: 0 < dist <= history size and
+/// dst_pos + length <= hist.len()
+pub(super) fn copy_words(hist: &mut [u8], dst_pos: usize, dist: usize, length: usize) {
+    let end_pos = dst_pos + length;
+    if dst_pos < dist {
+        // The source wraps around the end of the buffer.
+        let mut src_pos = hist.len() + dst_pos - dist;
+        for dst_pos in dst_pos..end_pos {
+            hist[dst_pos] = hist[src_pos];
+            src_pos += 1;
+            if src_pos == hist.len() {
+                src_pos = 0;
+            }
+        }
+        return;
+    }
+    let mut src_pos = dst_pos - dist;
+    let mut dst_pos = dst_pos;
+    if dist == 1 {
+        let c = hist[src_pos];
+        hist[dst_pos..end_pos].fill(c);
+        return;
+    }
+    if dist >= length {
+        hist.copy_within(src_pos..src_pos + length, dst_pos);
+        return;
+    }
+    if dist >= 8 {
+        while dst_pos + 8 <= end_pos {
+            let w: [u8; 8] = hist[src_pos..src_pos + 8].try_into().unwrap();
+            hist[dst_pos..dst_pos + 8].copy_from_slice(&w);
+            src_pos += 8;
+            dst_pos += 8;
+        }
+    }
+    while dst_pos < end_pos {
+        hist[dst_pos] = hist[src_pos];
+        src_pos += 1;
+        dst_pos += 1;
+    }
 }
@@ -98,8 +98,8 @@ fn histogram(b: &[u8], h: &mut [i32]) {
     }
 }
 
-pub(super) struct HuffmanBitWriter<'a, Output: std::io::Write> {
-    writer: &'a mut Output,
+pub(super) struct HuffmanBitWriter<Output: std::io::Write> {
+    writer: Output,
     // Data waiting to be written is bytes[0:nbytes]
     // and then the low nbits of bits.  Data is always written
     // sequentially into the bytes array.
@@ -117,8 +117,8 @@ pub(super) struct HuffmanBitWriter<'a, Output: std::io::Write> {
     err: std::io::Result<usize>,
 }
 
-impl<'a, Output: std::io::Write> HuffmanBitWriter<'a, Output> {
-    pub(super) fn new(w: &'a mut Output) -> Self {
+impl<Output: std::io::Write> HuffmanBitWriter<Output> {
+    pub(super) fn new(w: Output) -> Self {
         Self {
             writer: w,
             bits: 0,
@@ -136,7 +136,7 @@ impl<'a, Output: std::io::Write> HuffmanBitWriter<'a, Output> {
         }
     }
 
-    pub fn reset(&mut self, writer: &'a mut Output) {
+    pub fn reset(&mut self, writer: Output) {
         self.writer = writer;
         self.bits = 0;
         self.nbits = 0;
@@ -799,6 +799,16 @@ impl<'a, Output: std::io::Write> HuffmanBitWriter<'a, Output> {
 
     /// output returns the output writer.
     pub fn output(&mut self) -> &mut Output {
+        &mut self.writer
+    }
+
+    /// output_ref returns the output writer.
+    pub(super) fn output_ref(&self) -> &Output {
+        &self.writer
+    }
+
+    /// into_output returns the output writer, consuming self.
+    pub(super) fn into_output(self) -> Output {
         self.writer
     }
 }
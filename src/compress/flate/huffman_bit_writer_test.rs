@@ -617,7 +617,7 @@ fn test_block(test: &HuffTest, ttype: &str) {
 
 fn write_to_type(
     ttype: &str,
-    bw: &mut HuffmanBitWriter<bytes::Buffer>,
+    bw: &mut HuffmanBitWriter<&mut bytes::Buffer>,
     tok: &[Token],
     input: Option<&[u8]>,
 ) {
@@ -687,7 +687,7 @@ fn test_writer_eof(ttype: &str, test: &HuffTest, use_input: bool) {
 #[test]
 fn test_structure_sizes() {
     assert!(
-        200 >= std::mem::size_of::<HuffmanBitWriter<bytes::Buffer>>(),
+        200 >= std::mem::size_of::<HuffmanBitWriter<&mut bytes::Buffer>>(),
         "consider reducing size of HuffmanBitWriter"
     );
 }
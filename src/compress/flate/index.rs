@@ -0,0 +1,242 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::deflate::{Writer, BEST_COMPRESSION, MAX_MATCH_OFFSET};
+use super::inflate::Reader;
+use crate::io as ggio;
+use std::io::{BufRead, Read, Seek};
+
+/// DEFAULT_SPAN is the default distance in uncompressed bytes
+/// between the checkpoints of an Index.
+pub const DEFAULT_SPAN: u64 = 1 << 20;
+
+// INDEX_MAGIC starts the binary form of an Index, the last byte is the version.
+const INDEX_MAGIC: &[u8] = b"GGFI\x01";
+
+/// Checkpoint is the state of the decompressor at the start of a DEFLATE
+/// block, which is enough to resume the decompression from there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkpoint {
+    /// out is the offset of the block in the uncompressed data.
+    pub out: u64,
+    /// in_bits is the offset in bits of the block in the DEFLATE stream.
+    pub in_bits: u64,
+    /// window is the uncompressed data preceding the block,
+    /// at most 32 KiB of it.
+    pub window: Vec<u8>,
+}
+
+/// Index allows random access to the uncompressed data of a DEFLATE stream.
+/// It holds checkpoints recorded at block boundaries while the stream
+/// was decompressed by build. The first checkpoint is always at the start
+/// of the stream.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Index {
+    /// offset is the offset in bytes of the DEFLATE stream in the input,
+    /// for example the size of the gzip header.
+    pub offset: u64,
+    /// size is the size of the uncompressed data.
+    pub size: u64,
+    /// checkpoints are sorted by offset.
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+impl Index {
+    /// build decompresses the DEFLATE stream read from r and returns its index.
+    /// The checkpoints are at least span uncompressed bytes apart.
+    pub fn build<Input: BufRead>(r: Input, span: u64) -> std::io::Result<Index> {
+        Self::build_from(&mut Reader::new(r), 0, span, |_| {})
+    }
+
+    /// build_from is like build, but reads the stream from the decompressor f,
+    /// which has not been read yet. The stream starts at offset in the input.
+    /// All the uncompressed data is passed to the function data.
+    pub(crate) fn build_from<Input: BufRead>(
+        f: &mut Reader<Input>,
+        offset: u64,
+        span: u64,
+        mut data: impl FnMut(&[u8]),
+    ) -> std::io::Result<Index> {
+        let mut index = Index {
+            offset,
+            ..Default::default()
+        };
+        let mut buf = vec![0; MAX_MATCH_OFFSET];
+        loop {
+            if let Some(in_bits) = f.block_start() {
+                // Data of the previous block may still be in the dictionary.
+                let out = index.size + f.dict.avail_read() as u64;
+                let due = match index.checkpoints.last() {
+                    Some(last) => out - last.out >= span,
+                    None => true,
+                };
+                if due {
+                    index.checkpoints.push(Checkpoint {
+                        out,
+                        in_bits,
+                        window: f.dict.history(),
+                    });
+                }
+            }
+            match f.read_step(&mut buf)? {
+                Some(n) => {
+                    data(&buf[..n]);
+                    index.size += n as u64;
+                }
+                None => return Ok(index),
+            }
+        }
+    }
+
+    /// find returns the last checkpoint at or before the uncompressed offset.
+    pub fn find(&self, offset: u64) -> Option<&Checkpoint> {
+        let i = self.checkpoints.partition_point(|c| c.out <= offset);
+        if i == 0 {
+            return None;
+        }
+        Some(&self.checkpoints[i - 1])
+    }
+
+    /// open returns a Reader of the uncompressed data starting at offset.
+    /// r is the input the index was built from. The decompression resumes
+    /// at the closest checkpoint, so at most span bytes plus the size of one
+    /// block are decompressed and discarded.
+    pub fn open<Input: Read + Seek>(
+        &self,
+        mut r: Input,
+        offset: u64,
+    ) -> std::io::Result<Reader<std::io::BufReader<Input>>> {
+        if offset > self.size {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "flate: offset is beyond the end of the indexed data",
+            ));
+        }
+        let c = match self.find(offset) {
+            Some(c) => c,
+            None => return Err(err_invalid_index()),
+        };
+        // The offsets may come from unmarshal_binary, so they can be anything.
+        let start = self
+            .offset
+            .checked_add(c.in_bits / 8)
+            .ok_or_else(err_invalid_index)?;
+        r.seek(std::io::SeekFrom::Start(start))?;
+        let mut f = Reader::new_at(
+            std::io::BufReader::new(r),
+            c.in_bits / 8,
+            (c.in_bits % 8) as usize,
+            &c.window,
+        )?;
+        let skip = offset - c.out;
+        if skip > 0 {
+            let (_, err) = ggio::copy_n(&mut ggio::Discard::new(), &mut f, skip as usize);
+            if let Some(err) = err {
+                return Err(err);
+            }
+        }
+        Ok(f)
+    }
+
+    /// marshal_binary encodes the index into a compact binary form.
+    /// The offsets are delta encoded as varints and the whole
+    /// encoding is compressed.
+    pub fn marshal_binary(&self) -> std::io::Result<Vec<u8>> {
+        let mut b = Vec::new();
+        append_uvarint(&mut b, self.offset);
+        append_uvarint(&mut b, self.size);
+        append_uvarint(&mut b, self.checkpoints.len() as u64);
+        let (mut out, mut in_bits) = (0, 0);
+        for c in self.checkpoints.iter() {
+            append_uvarint(&mut b, c.out - out);
+            append_uvarint(&mut b, c.in_bits - in_bits);
+            append_uvarint(&mut b, c.window.len() as u64);
+            b.extend_from_slice(&c.window);
+            (out, in_bits) = (c.out, c.in_bits);
+        }
+
+        let mut data = INDEX_MAGIC.to_vec();
+        let mut w = Writer::new(&mut data, BEST_COMPRESSION)?;
+        std::io::Write::write_all(&mut w, &b)?;
+        w.close()?;
+        Ok(data)
+    }
+
+    /// unmarshal_binary replaces the index with the one decoded from data,
+    /// which must have been produced by marshal_binary.
+    pub fn unmarshal_binary(&mut self, data: &[u8]) -> std::io::Result<()> {
+        if !data.starts_with(INDEX_MAGIC) {
+            return Err(err_invalid_index());
+        }
+        let mut b = Vec::new();
+        Reader::new(&data[INDEX_MAGIC.len()..]).read_to_end(&mut b)?;
+
+        let mut b = b.as_slice();
+        let offset = read_uvarint(&mut b)?;
+        let size = read_uvarint(&mut b)?;
+        let n = read_uvarint(&mut b)?;
+        let mut checkpoints = Vec::new();
+        let (mut out, mut in_bits) = (0u64, 0u64);
+        for _ in 0..n {
+            out = out
+                .checked_add(read_uvarint(&mut b)?)
+                .ok_or_else(err_invalid_index)?;
+            in_bits = in_bits
+                .checked_add(read_uvarint(&mut b)?)
+                .ok_or_else(err_invalid_index)?;
+            let window_len = read_uvarint(&mut b)? as usize;
+            if window_len > MAX_MATCH_OFFSET || window_len > b.len() || out > size {
+                return Err(err_invalid_index());
+            }
+            let (window, rest) = b.split_at(window_len);
+            b = rest;
+            checkpoints.push(Checkpoint {
+                out,
+                in_bits,
+                window: window.to_vec(),
+            });
+        }
+        if !b.is_empty() || !matches!(checkpoints.first(), Some(c) if c.out == 0) {
+            return Err(err_invalid_index());
+        }
+
+        *self = Index {
+            offset,
+            size,
+            checkpoints,
+        };
+        Ok(())
+    }
+}
+
+fn err_invalid_index() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, "flate: invalid index")
+}
+
+/// append_uvarint appends the varint-encoded form of x to b.
+fn append_uvarint(b: &mut Vec<u8>, mut x: u64) {
+    while x >= 0x80 {
+        b.push(x as u8 | 0x80);
+        x >>= 7;
+    }
+    b.push(x as u8);
+}
+
+/// read_uvarint decodes a varint from the start of b and advances b past it.
+fn read_uvarint(b: &mut &[u8]) -> std::io::Result<u64> {
+    let mut x = 0u64;
+    let mut s = 0;
+    for (i, &c) in b.iter().enumerate() {
+        if i == 10 || (i == 9 && c > 1) {
+            break; // overflow
+        }
+        if c < 0x80 {
+            *b = &b[i + 1..];
+            return Ok(x | (c as u64) << s);
+        }
+        x |= ((c & 0x7f) as u64) << s;
+        s += 7;
+    }
+    Err(err_invalid_index())
+}
@@ -0,0 +1,200 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::{Index, Writer, BEST_SPEED, DEFAULT_COMPRESSION, HUFFMAN_ONLY, NO_COMPRESSION};
+use std::io::{Read, Write};
+
+/// test_data returns compressible text made of pseudo-random words,
+/// so that back references often reach far into the window.
+pub(in crate::compress) fn test_data(n: usize) -> Vec<u8> {
+    const WORDS: &[&str] = &[
+        "lorem",
+        "ipsum",
+        "dolor",
+        "sit",
+        "amet",
+        "consectetur",
+        "adipiscing",
+        "elit",
+        "sed",
+        "do",
+        "eiusmod",
+        "tempor",
+        "incididunt",
+        "ut",
+        "labore",
+        "et",
+        "dolore",
+        "magna",
+    ];
+    let mut x: u64 = 1;
+    let mut data = Vec::with_capacity(n + 16);
+    while data.len() < n {
+        x = x
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        data.extend_from_slice(WORDS[(x >> 59) as usize % WORDS.len()].as_bytes());
+        data.push(if x >> 56 & 7 == 0 { b'\n' } else { b' ' });
+    }
+    data.truncate(n);
+    data
+}
+
+fn compress(data: &[u8], level: isize) -> Vec<u8> {
+    let mut compressed = Vec::new();
+    let mut w = Writer::new(&mut compressed, level).unwrap();
+    w.write_all(data).unwrap();
+    w.close().unwrap();
+    compressed
+}
+
+fn read_at(index: &Index, compressed: &[u8], offset: u64) -> Vec<u8> {
+    let mut r = index
+        .open(std::io::Cursor::new(compressed), offset)
+        .unwrap_or_else(|err| panic!("open at {}: {}", offset, err));
+    let mut output = Vec::new();
+    r.read_to_end(&mut output).unwrap();
+    output
+}
+
+#[test]
+fn test_index() {
+    let data = test_data(600_000);
+    for level in [
+        NO_COMPRESSION,
+        BEST_SPEED,
+        DEFAULT_COMPRESSION,
+        HUFFMAN_ONLY,
+    ] {
+        let compressed = compress(&data, level);
+        let index = Index::build(compressed.as_slice(), 64 * 1024).unwrap();
+        assert_eq!(index.size, data.len() as u64, "level {}", level);
+        assert!(index.checkpoints.len() > 2, "level {}", level);
+        assert_eq!(index.checkpoints[0].out, 0, "level {}", level);
+        assert!(index.checkpoints[0].window.is_empty(), "level {}", level);
+        for w in index.checkpoints.windows(2) {
+            assert!(w[1].out - w[0].out >= 64 * 1024, "level {}", level);
+            assert!(w[1].in_bits > w[0].in_bits, "level {}", level);
+        }
+
+        let mut offsets = vec![0, 1, 65535, 65536, 300_001, data.len() - 1, data.len()];
+        offsets.extend(index.checkpoints.iter().map(|c| c.out as usize));
+        offsets.extend(index.checkpoints.iter().skip(1).map(|c| c.out as usize - 1));
+        for offset in offsets {
+            let output = read_at(&index, &compressed, offset as u64);
+            assert!(
+                output == data[offset..],
+                "level {}: data mismatch at offset {}",
+                level,
+                offset
+            );
+        }
+    }
+}
+
+#[test]
+fn test_index_unaligned_blocks() {
+    // Compressed blocks are not byte aligned, so some checkpoints must start
+    // in the middle of a byte.
+    let data = test_data(600_000);
+    let compressed = compress(&data, DEFAULT_COMPRESSION);
+    let index = Index::build(compressed.as_slice(), 1).unwrap();
+    let c = index
+        .checkpoints
+        .iter()
+        .find(|c| c.in_bits % 8 != 0)
+        .expect("no unaligned checkpoints");
+    assert_eq!(c.window.len(), 32 * 1024);
+    assert_eq!(
+        c.window,
+        data[c.out as usize - c.window.len()..c.out as usize]
+    );
+    let output = read_at(&index, &compressed, c.out);
+    assert!(output == data[c.out as usize..]);
+}
+
+#[test]
+fn test_index_find() {
+    let data = test_data(300_000);
+    let compressed = compress(&data, BEST_SPEED);
+    let index = Index::build(compressed.as_slice(), 100_000).unwrap();
+    assert_eq!(index.find(0), Some(&index.checkpoints[0]));
+    for c in index.checkpoints.iter() {
+        assert_eq!(index.find(c.out), Some(c));
+        assert!(index.find(c.out + 1).unwrap().out <= c.out + 1);
+    }
+    assert_eq!(Index::default().find(0), None);
+}
+
+#[test]
+fn test_index_offset_out_of_range() {
+    let data = test_data(1000);
+    let compressed = compress(&data, BEST_SPEED);
+    let index = Index::build(compressed.as_slice(), 100).unwrap();
+    let err = index
+        .open(std::io::Cursor::new(&compressed), data.len() as u64 + 1)
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_index_marshal() {
+    let data = test_data(400_000);
+    let compressed = compress(&data, DEFAULT_COMPRESSION);
+    let index = Index::build(compressed.as_slice(), 64 * 1024).unwrap();
+    let b = index.marshal_binary().unwrap();
+    let windows: usize = index.checkpoints.iter().map(|c| c.window.len()).sum();
+    assert!(
+        b.len() < windows / 2,
+        "index is not compact: {} bytes",
+        b.len()
+    );
+
+    let mut index2 = Index::default();
+    index2.unmarshal_binary(&b).unwrap();
+    assert_eq!(index2, index);
+    let output = read_at(&index2, &compressed, 123_456);
+    assert!(output == data[123_456..]);
+
+    // Invalid data.
+    let mut index3 = Index::default();
+    for b in [&b"GGFI"[..], &b[..b.len() / 2], &b"GGFI\x02"[..]] {
+        assert!(index3.unmarshal_binary(b).is_err());
+    }
+    let mut b = b.clone();
+    b[0] = b'X';
+    assert!(index3.unmarshal_binary(&b).is_err());
+    assert_eq!(index3, Index::default());
+}
+
+#[test]
+fn test_index_offset_overflow() {
+    // unmarshal_binary accepts any stream offset, so open must not
+    // overflow when adding the checkpoint position to it.
+    let data = test_data(400_000);
+    let compressed = compress(&data, DEFAULT_COMPRESSION);
+    let mut index = Index::build(compressed.as_slice(), 64 * 1024).unwrap();
+    index.offset = u64::MAX;
+    let mut index2 = Index::default();
+    index2
+        .unmarshal_binary(&index.marshal_binary().unwrap())
+        .unwrap();
+    let last = index2.checkpoints.last().unwrap();
+    assert!(last.in_bits >= 8);
+    let err = index2
+        .open(std::io::Cursor::new(&compressed), last.out)
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_index_corrupted_input() {
+    let data = test_data(100_000);
+    let mut compressed = compress(&data, DEFAULT_COMPRESSION);
+    compressed.truncate(compressed.len() / 2);
+    let err = Index::build(compressed.as_slice(), 1000).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+}
@@ -10,7 +10,9 @@
 use super::deflate::MAX_MATCH_OFFSET;
 use super::dict_decoder::DictDecoder;
 use super::huffman_bit_writer::END_BLOCK_MARKER;
+use super::inflate_fast::FastTable;
 use crate::compat;
+use crate::compress::{copy_error, copy_option_error};
 use crate::errors;
 use crate::io as ggio;
 use crate::math::bits;
@@ -27,6 +29,12 @@ pub(super) const MAX_NUM_LIT: usize = 286;
 const MAX_NUM_DIST: usize = 30;
 const NUM_CODES: usize = 19; // number of codes in Huffman meta-code
 
+// Deflate64, also known as enhanced deflate, extends the format with
+// a 64 KiB window, the distance codes 30 and 31 for distances beyond 32 KiB,
+// and redefines the length code 285 to have 16 extra bits.
+const MAX_NUM_DIST_64: usize = 32;
+const DEFLATE64_WINDOW: usize = 1 << 16;
+
 // var fixedOnce sync.Once
 // var fixedHuffmanDecoder HuffmanDecoder
 /// Initialize the fixedHuffmanDecoder only once upon first use.
@@ -257,7 +265,7 @@ enum HDDecoder {
 }
 
 #[derive(PartialEq)]
-enum HLDecoder {
+pub(super) enum HLDecoder {
     Fixed,
     H1,
 }
@@ -270,16 +278,19 @@ enum HLDecoder {
 /// many 1-byte reads.
 pub struct Reader<Input: std::io::BufRead> {
     /// Input source.
-    r: Input,
-    roffset: u64,
+    pub(super) r: Input,
+    pub(super) roffset: u64,
     // Input bits, in top of b.
-    b: u32,
-    nb: usize,
+    pub(super) b: u32,
+    pub(super) nb: usize,
     // Huffman decoders for literal/length, distance.
     pub(super) h1: HuffmanDecoder,
     h2: HuffmanDecoder,
+    // Lookup tables for the fast path, built along with h1 and h2.
+    pub(super) fast_lit: FastTable,
+    pub(super) fast_dist: FastTable,
     // Length arrays used to define Huffman codes.
-    bits: Vec<u32>, // [isize; MAX_NUM_LIT + MAX_NUM_DIST],
+    bits: Vec<u32>, // [isize; MAX_NUM_LIT + MAX_NUM_DIST_64],
     codebits: [u32; NUM_CODES],
 
     // Output history, buffer.
@@ -294,10 +305,12 @@ pub struct Reader<Input: std::io::BufRead> {
     final_: bool,
     end_of_stream: bool,
     err: Option<std::io::Error>,
-    hl: HLDecoder,
+    pub(super) hl: HLDecoder,
     hd: HDDecoder,
     copy_len: usize,
     copy_dist: usize,
+    // Decode the Deflate64 format.
+    pub(super) deflate64: bool,
 }
 
 enum StepFunc {
@@ -325,13 +338,61 @@ const CODE_ORDER: [usize; 19] = [
     16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
 ];
 
+/// Error describes invalid DEFLATE data. Reader returns it wrapped
+/// in a std::io::Error of the ErrorKind::InvalidData kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// CorruptInput reports the presence of corrupt input at a given offset.
+    CorruptInput(u64),
+    /// Internal reports an error in the flate code itself.
+    Internal(&'static str),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::CorruptInput(offset) => {
+                write!(f, "flate: corrupt input before offset {}", offset)
+            }
+            Error::Internal(msg) => write!(f, "flate: internal error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for std::io::Error {
+    fn from(e: Error) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, e)
+    }
+}
+
 impl<Input: std::io::BufRead> std::io::Read for Reader<Input> {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         errors::iores_to_result(crate::io::Reader::read(self, buf))
     }
 }
 
-impl<'a, Input: std::io::BufRead> Reader<Input> {
+impl<Input: std::io::BufRead> std::io::BufRead for Reader<Input> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        while self.dict.stash_len() == 0 {
+            if let Some(err) = &self.err {
+                return Err(copy_error(err));
+            }
+            if self.end_of_stream {
+                break;
+            }
+            self.step();
+        }
+        Ok(self.dict.stash())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.dict.stash_consume(amt);
+    }
+}
+
+impl<Input: std::io::BufRead> Reader<Input> {
     /// new returns a new Reader that can be used
     /// to read the uncompressed version of r.
     /// The reader returns std::io::Error::EOF after the final block in the DEFLATE stream has
@@ -351,7 +412,24 @@ impl<'a, Input: std::io::BufRead> Reader<Input> {
     ///
     // ggrust: not implemented
     // The ReadCloser returned by new_reader also implements Resetter.
-    pub fn new_dict(r: Input, dict: &'a [u8]) -> Reader<Input> {
+    pub fn new_dict(r: Input, dict: &[u8]) -> Reader<Input> {
+        Self::new_format(r, dict, false)
+    }
+
+    /// new_deflate64 is like new but reads the Deflate64 format,
+    /// also known as enhanced deflate, as used by the zip method 9.
+    /// It has a window of 64 KiB, allows the distance codes 30 and 31,
+    /// and reads 16 extra bits after the length code 285.
+    pub fn new_deflate64(r: Input) -> Reader<Input> {
+        Self::new_format(r, &[], true)
+    }
+
+    fn new_format(r: Input, dict: &[u8], deflate64: bool) -> Reader<Input> {
+        let window = if deflate64 {
+            DEFLATE64_WINDOW
+        } else {
+            MAX_MATCH_OFFSET
+        };
         Reader {
             r,
             roffset: 0,
@@ -359,9 +437,11 @@ impl<'a, Input: std::io::BufRead> Reader<Input> {
             nb: 0,
             h1: HuffmanDecoder::new(),
             h2: HuffmanDecoder::new(),
-            bits: vec![0; MAX_NUM_LIT + MAX_NUM_DIST],
+            fast_lit: FastTable::new(),
+            fast_dist: FastTable::new(),
+            bits: vec![0; MAX_NUM_LIT + MAX_NUM_DIST_64],
             codebits: [0; NUM_CODES],
-            dict: DictDecoder::new(MAX_MATCH_OFFSET, dict),
+            dict: DictDecoder::new(window, dict),
             buf: [0; 4],
             step: StepFunc::NextBlock,
             step_state: StepState::StateInit,
@@ -372,6 +452,16 @@ impl<'a, Input: std::io::BufRead> Reader<Input> {
             hd: HDDecoder::None,
             copy_len: 0,
             copy_dist: 0,
+            deflate64,
+        }
+    }
+
+    /// max_num_dist returns the number of distance codes of the format.
+    fn max_num_dist(&self) -> usize {
+        if self.deflate64 {
+            MAX_NUM_DIST_64
+        } else {
+            MAX_NUM_DIST
         }
     }
 
@@ -434,7 +524,7 @@ impl<'a, Input: std::io::BufRead> Reader<Input> {
         }
         self.b >>= 5;
         let ndist = (self.b & 0x1F) as usize + 1;
-        if ndist > MAX_NUM_DIST {
+        if ndist > self.max_num_dist() {
             return Err(new_corrupted_input_error(self.roffset));
         }
         self.b >>= 5;
@@ -496,12 +586,7 @@ impl<'a, Input: std::io::BufRead> Reader<Input> {
                     nb = 7;
                     b = 0;
                 }
-                _ => {
-                    return Err(std::io::Error::new(
-                        std::io::ErrorKind::InvalidData,
-                        "unexpected length code",
-                    ))
-                }
+                _ => return Err(Error::Internal("unexpected length code").into()),
             };
             while self.nb < nb {
                 self.more_bits()?
@@ -521,6 +606,8 @@ impl<'a, Input: std::io::BufRead> Reader<Input> {
         if !self.h1.init(&self.bits[0..nlit]) || !self.h2.init(&self.bits[nlit..nlit + ndist]) {
             return Err(new_corrupted_input_error(self.roffset));
         }
+        self.fast_lit.init_lit(&self.bits[0..nlit], self.deflate64);
+        self.fast_dist.init_dist(&self.bits[nlit..nlit + ndist]);
 
         // As an optimization, we can initialize the min bits to read at a time
         // for the HLIT tree to the length of the EOB marker since we know that
@@ -548,6 +635,18 @@ impl<'a, Input: std::io::BufRead> Reader<Input> {
         loop {
             match next_step {
                 StateMachine::ReadLiteral => {
+                    // Decode as much as possible in the fast path first.
+                    if self.huffman_block_fast() {
+                        self.finish_block();
+                        return;
+                    }
+                    if self.dict.avail_write() == 0 {
+                        self.dict.stash_flush();
+                        self.step = StepFunc::HuffmanBlock;
+                        self.step_state = StepState::StateInit;
+                        return;
+                    }
+
                     // Read literal and/or (length, distance) according to RFC section 3.2.3.
                     let v = match self.huff_sym(DecoderToUse::HL) {
                         Ok(v) => v,
@@ -607,6 +706,10 @@ impl<'a, Input: std::io::BufRead> Reader<Input> {
                         length = v*32 - (281*32 - 131);
                         n = 5;
                     }
+                    285..=285 /* MAX_NUM_LIT-1 */ if self.deflate64 => {
+                        length = 3;
+                        n = 16;
+                    }
                     285..=285 /* MAX_NUM_LIT-1 */ => {
                         // 		case v < MAX_NUM_LIT:
                         length = 258;
@@ -658,7 +761,7 @@ impl<'a, Input: std::io::BufRead> Reader<Input> {
                     if (0..4).contains(&dist) {
                         // 		case dist < 4:
                         dist += 1;
-                    } else if (4..MAX_NUM_DIST).contains(&dist) {
+                    } else if (4..self.max_num_dist()).contains(&dist) {
                         // 		case dist < MAX_NUM_DIST:
                         let nb = (dist - 2) >> 1;
                         // have 1 bit in bottom of dist, need nb more.
@@ -825,7 +928,7 @@ impl<'a, Input: std::io::BufRead> Reader<Input> {
                     self.b = b;
                     self.nb = nb;
                     self.err = Some(new_corrupted_input_error(self.roffset));
-                    return Err(errors::copy_stdio_error(self.err.as_ref().unwrap()));
+                    return Err(copy_error(self.err.as_ref().unwrap()));
                 }
                 self.b = b >> (n & 31);
                 self.nb = nb - n;
@@ -844,18 +947,17 @@ impl<'a, Input: std::io::BufRead> Reader<Input> {
     // reset_state discards any buffered data and resets the instance if it was
     // newly initialized with the reader it already holds.
     pub fn reset_state(&mut self, dict: &[u8]) {
-        // keep following fields
-        // self.bits = vec![0; MAX_NUM_LIT + MAX_NUM_DIST];
+        // keep following fields, they are reinitialized before use
+        // self.bits = vec![0; MAX_NUM_LIT + MAX_NUM_DIST_64];
         // self.codebits = [0; NUM_CODES];
+        // self.h1, self.h2, self.fast_lit, self.fast_dist
 
         // reset everything else
-        self.dict = DictDecoder::new(MAX_MATCH_OFFSET, dict);
+        self.dict.reset(dict);
 
         self.roffset = 0;
         self.b = 0;
         self.nb = 0;
-        self.h1 = HuffmanDecoder::new();
-        self.h2 = HuffmanDecoder::new();
         self.buf = [0; 4];
         self.step = StepFunc::NextBlock;
         self.step_state = StepState::StateInit;
@@ -868,10 +970,136 @@ impl<'a, Input: std::io::BufRead> Reader<Input> {
         self.copy_dist = 0;
     }
 
+    /// buffer returns the decompressed data which has not been consumed yet,
+    /// the same slice as the last fill_buf call returned.
+    pub(in crate::compress) fn buffer(&self) -> &[u8] {
+        self.dict.stash()
+    }
+
     /// input_reader returns mutable reference to the underlying reader.
     pub fn input_reader(&mut self) -> &mut Input {
         &mut self.r
     }
+
+    /// get_ref returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &Input {
+        &self.r
+    }
+
+    /// get_mut returns a mutable reference to the underlying reader.
+    /// Reading from it directly corrupts the decompressed stream.
+    pub fn get_mut(&mut self) -> &mut Input {
+        &mut self.r
+    }
+
+    /// into_inner returns the underlying reader. The Reader never reads
+    /// past the end of the DEFLATE stream, so once the stream has been read
+    /// to the end the underlying reader is positioned right after it.
+    pub fn into_inner(self) -> Input {
+        self.r
+    }
+
+    /// replace_input returns a Reader decompressing from r which takes over
+    /// the buffers of self, and the input of self.
+    /// The returned Reader must be reset before use.
+    pub(super) fn replace_input<Input2: std::io::BufRead>(
+        self,
+        r: Input2,
+    ) -> (Reader<Input2>, Input) {
+        let f = Reader {
+            r,
+            roffset: self.roffset,
+            b: self.b,
+            nb: self.nb,
+            h1: self.h1,
+            h2: self.h2,
+            fast_lit: self.fast_lit,
+            fast_dist: self.fast_dist,
+            bits: self.bits,
+            codebits: self.codebits,
+            dict: self.dict,
+            buf: self.buf,
+            step: self.step,
+            step_state: self.step_state,
+            final_: self.final_,
+            end_of_stream: self.end_of_stream,
+            err: self.err,
+            hl: self.hl,
+            hd: self.hd,
+            copy_len: self.copy_len,
+            copy_dist: self.copy_dist,
+            deflate64: self.deflate64,
+        };
+        (f, self.r)
+    }
+
+    /// new_at returns a Reader that resumes decompression at a block boundary.
+    /// r must be positioned at the byte containing the first bit of the block,
+    /// which is at offset in the DEFLATE stream. The first skip bits of that
+    /// byte are discarded. window holds the uncompressed data preceding
+    /// the block.
+    pub(super) fn new_at(
+        r: Input,
+        offset: u64,
+        skip: usize,
+        window: &[u8],
+    ) -> std::io::Result<Reader<Input>> {
+        let mut f = Reader::new_dict(r, window);
+        f.roffset = offset;
+        if skip > 0 {
+            f.more_bits()?;
+            f.b >>= skip;
+            f.nb -= skip;
+        }
+        Ok(f)
+    }
+
+    /// block_start returns the offset in bits of the next DEFLATE block
+    /// if the decompressor is about to start it and all the data before
+    /// the block has been read.
+    pub(super) fn block_start(&self) -> Option<u64> {
+        if self.dict.stash_len() > 0
+            || self.end_of_stream
+            || self.err.is_some()
+            || !matches!(self.step, StepFunc::NextBlock)
+        {
+            return None;
+        }
+        Some(self.roffset * 8 - self.nb as u64)
+    }
+
+    /// read_step is like read but performs at most one step of the
+    /// decompression, so it may return Some(0) before the end of the stream.
+    /// This allows the caller to stop at block boundaries.
+    /// It returns None at the end of the stream.
+    pub(super) fn read_step(&mut self, p: &mut [u8]) -> std::io::Result<Option<usize>> {
+        if self.dict.stash_len() > 0 {
+            let n = self.dict.stash_read(p);
+            if n > 0 {
+                return Ok(Some(n));
+            }
+        }
+        if let Some(err) = &self.err {
+            return Err(copy_error(err));
+        }
+        if self.end_of_stream {
+            return Ok(None);
+        }
+        self.step();
+        Ok(Some(0))
+    }
+
+    /// step runs the next step of the decompression.
+    fn step(&mut self) {
+        match self.step {
+            StepFunc::NextBlock => self.next_block(),
+            StepFunc::HuffmanBlock => self.huffman_block(),
+            StepFunc::CopyData => self.copy_data(),
+        }
+        if self.err.is_some() && self.dict.stash_len() == 0 {
+            self.dict.stash_flush(); // Flush what's left in case of error
+        }
+    }
 }
 
 impl<Input: std::io::BufRead> crate::io::Reader for Reader<Input> {
@@ -885,19 +1113,12 @@ impl<Input: std::io::BufRead> crate::io::Reader for Reader<Input> {
                 }
             }
             if self.err.is_some() {
-                return (0, errors::copy_stdio_option_error(&self.err));
+                return (0, copy_option_error(&self.err));
             }
             if self.end_of_stream {
                 return ggio::EOF;
             }
-            match self.step {
-                StepFunc::NextBlock => self.next_block(),
-                StepFunc::HuffmanBlock => self.huffman_block(),
-                StepFunc::CopyData => self.copy_data(),
-            }
-            if self.err.is_some() && self.dict.stash_len() == 0 {
-                self.dict.stash_flush(); // Flush what's left in case of error
-            }
+            self.step();
         }
     }
 }
@@ -927,6 +1148,5 @@ fn fixed_huffman_decoder_init() -> HuffmanDecoder {
 }
 
 fn new_corrupted_input_error(offset: u64) -> std::io::Error {
-    let msg = format!("flate: corrupt input before offset {}", offset);
-    std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
+    Error::CorruptInput(offset).into()
 }
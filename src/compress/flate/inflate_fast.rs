@@ -0,0 +1,301 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::dict_decoder::copy_words;
+use super::inflate::{HLDecoder, Reader};
+use crate::math::bits;
+use std::sync::OnceLock;
+
+// The fast path decodes whole (length, distance) pairs using a 64-bit bit
+// buffer which is refilled 8 bytes at a time straight from the buffer of
+// the input reader. It only runs while the input buffer holds at least
+// FAST_MIN_INPUT bytes and the dictionary has room for the longest match.
+// Anything unusual, including all errors, is left to the generic decoder,
+// which decodes the symbol again from the same position. This keeps the
+// behavior of the Reader identical whichever path decodes the data.
+
+const FAST_MIN_INPUT: usize = 8;
+const FAST_MIN_OUTPUT: usize = 258; // the longest match
+
+// Each refill leaves at least 56 bits in the buffer, which is enough for
+// a length code with its extra bits (15 + 5) and a distance code with its
+// extra bits (15 + 13). The Deflate64 length code 285, with 16 extra bits
+// and up to 65538 bytes to copy, and the Deflate64 distance codes 30 and 31
+// are left to the generic decoder.
+
+// The tables are indexed by the next TABLE_BITS bits of the input.
+// Codes longer than that are resolved through a second-level table.
+const TABLE_BITS: u32 = 10;
+const TABLE_MASK: u64 = (1 << TABLE_BITS) - 1;
+
+// A table entry combines a symbol with the data needed to decode it:
+//   bits 0-3   code length
+//   bits 4-7   kind
+//   bits 8-15  number of extra bits
+//   bits 16-31 literal byte, base length or distance, or offset of the
+//              second-level table
+// For KIND_LINK entries the number of extra bits is the number of bits
+// indexing the second-level table.
+const KIND_INVALID: u32 = 0;
+const KIND_LITERAL: u32 = 1;
+const KIND_BASE: u32 = 2;
+const KIND_END: u32 = 3;
+const KIND_LINK: u32 = 4;
+
+// RFC 1951 section 3.2.5.
+const LENGTH_BASE: [u32; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u32; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u32; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u32; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+/// FastTable is a two-level lookup table for decoding a Huffman code
+/// together with the extra bits which follow its symbol.
+pub(super) struct FastTable {
+    entries: Vec<u32>,
+}
+
+impl FastTable {
+    pub(super) fn new() -> Self {
+        Self {
+            entries: vec![0; 1 << TABLE_BITS],
+        }
+    }
+
+    /// init_lit initializes the table for the literal/length code
+    /// from the code lengths.
+    pub(super) fn init_lit(&mut self, lengths: &[u32], deflate64: bool) {
+        self.init(lengths, |sym| match sym {
+            0..=255 => KIND_LITERAL << 4 | (sym as u32) << 16,
+            256 => KIND_END << 4,
+            285 if deflate64 => KIND_INVALID,
+            257..=285 => {
+                let i = sym - 257;
+                KIND_BASE << 4 | LENGTH_EXTRA[i] << 8 | LENGTH_BASE[i] << 16
+            }
+            _ => KIND_INVALID,
+        })
+    }
+
+    /// init_dist initializes the table for the distance code
+    /// from the code lengths.
+    pub(super) fn init_dist(&mut self, lengths: &[u32]) {
+        self.init(lengths, |sym| match sym {
+            0..=29 => KIND_BASE << 4 | DIST_EXTRA[sym] << 8 | DIST_BASE[sym] << 16,
+            _ => KIND_INVALID,
+        })
+    }
+
+    /// init fills the table with the canonical Huffman code defined by the
+    /// code lengths. The lengths must have been accepted by HuffmanDecoder::init,
+    /// so the code is complete or has a single symbol. Bit sequences which
+    /// don't decode to a symbol are left as invalid entries.
+    fn init(&mut self, lengths: &[u32], entry: impl Fn(usize) -> u32) {
+        self.entries.clear();
+        self.entries.resize(1 << TABLE_BITS, 0);
+
+        let mut count = [0_u32; 16];
+        let mut max = 0;
+        for &n in lengths {
+            if n > 0 {
+                count[n as usize] += 1;
+                max = max.max(n);
+            }
+        }
+
+        let mut code = 0_u32;
+        let mut next_code = [0_u32; 16];
+        for n in 1..=max as usize {
+            code = (code + count[n - 1]) << 1;
+            next_code[n] = code;
+        }
+
+        for (sym, &n) in lengths.iter().enumerate() {
+            if n == 0 {
+                continue;
+            }
+            let code = next_code[n as usize];
+            next_code[n as usize] += 1;
+            let reverse = (bits::reverse16(code as u16) >> (16 - n)) as usize;
+            let e = entry(sym) | n;
+            if n <= TABLE_BITS {
+                let mut i = reverse;
+                while i < 1 << TABLE_BITS {
+                    self.entries[i] = e;
+                    i += 1 << n;
+                }
+                continue;
+            }
+
+            let root = reverse & TABLE_MASK as usize;
+            let sub_bits = max - TABLE_BITS;
+            let mut link = self.entries[root];
+            if link >> 4 & 15 != KIND_LINK {
+                link = (self.entries.len() as u32) << 16 | sub_bits << 8 | KIND_LINK << 4;
+                self.entries[root] = link;
+                self.entries.resize(self.entries.len() + (1 << sub_bits), 0);
+            }
+            let base = (link >> 16) as usize;
+            let mut i = reverse >> TABLE_BITS;
+            while i < 1 << sub_bits {
+                self.entries[base + i] = e;
+                i += 1 << (n - TABLE_BITS);
+            }
+        }
+    }
+
+    /// lookup returns the entry for the code at the bottom of bitbuf.
+    #[inline(always)]
+    fn lookup(&self, bitbuf: u64) -> u32 {
+        let e = self.entries[(bitbuf & TABLE_MASK) as usize];
+        if e >> 4 & 15 != KIND_LINK {
+            return e;
+        }
+        let i = (bitbuf >> TABLE_BITS) & ((1 << (e >> 8 & 0xff)) - 1);
+        self.entries[(e >> 16) as usize + i as usize]
+    }
+}
+
+/// get_fixed_fast_tables returns the tables for the fixed Huffman codes
+/// of RFC 1951 section 3.2.6.
+fn get_fixed_fast_tables(deflate64: bool) -> &'static (FastTable, FastTable) {
+    static TABLES: OnceLock<(FastTable, FastTable)> = OnceLock::new();
+    static TABLES_64: OnceLock<(FastTable, FastTable)> = OnceLock::new();
+    let init = || {
+        let mut lengths = [8; 288];
+        lengths[144..256].fill(9);
+        lengths[256..280].fill(7);
+        let mut lit = FastTable::new();
+        lit.init_lit(&lengths, deflate64);
+        let mut dist = FastTable::new();
+        dist.init_dist(&[5; 32]);
+        (lit, dist)
+    };
+    if deflate64 {
+        TABLES_64.get_or_init(init)
+    } else {
+        TABLES.get_or_init(init)
+    }
+}
+
+impl<Input: std::io::BufRead> Reader<Input> {
+    /// huffman_block_fast decodes the current Huffman block while there is
+    /// enough buffered input and room in the dictionary. It returns true
+    /// if it has decoded the end of block marker.
+    pub(super) fn huffman_block_fast(&mut self) -> bool {
+        let (lit, dist) = if self.hl == HLDecoder::Fixed {
+            let tables = get_fixed_fast_tables(self.deflate64);
+            (&tables.0, &tables.1)
+        } else {
+            (&self.fast_lit, &self.fast_dist)
+        };
+        let (hist, start_pos, full) = self.dict.hist_parts();
+        if hist.len() - start_pos < FAST_MIN_OUTPUT {
+            return false;
+        }
+        let input = match self.r.fill_buf() {
+            Ok(input) if input.len() >= FAST_MIN_INPUT => input,
+            _ => return false,
+        };
+
+        // The bits above nbits are either zero or the next bits of the input.
+        let mut bitbuf = self.b as u64;
+        let mut nbits = self.nb as u32;
+        let mut pos = 0;
+        let mut wr_pos = start_pos;
+        let mut end_of_block = false;
+        while pos + 8 <= input.len() && wr_pos + FAST_MIN_OUTPUT <= hist.len() {
+            let saved = (bitbuf, nbits, pos);
+
+            bitbuf |= u64::from_le_bytes(input[pos..pos + 8].try_into().unwrap()) << nbits;
+            pos += ((63 - nbits) >> 3) as usize;
+            nbits |= 56;
+
+            let mut e = lit.lookup(bitbuf);
+            let n = e & 15;
+            match e >> 4 & 15 {
+                KIND_LITERAL => {
+                    // Up to three literals fit in the bit buffer without a refill.
+                    for i in 0..3 {
+                        let n = e & 15;
+                        bitbuf >>= n;
+                        nbits -= n;
+                        hist[wr_pos] = (e >> 16) as u8;
+                        wr_pos += 1;
+                        if i == 2 {
+                            break;
+                        }
+                        e = lit.lookup(bitbuf);
+                        if e >> 4 & 15 != KIND_LITERAL {
+                            break;
+                        }
+                    }
+                    continue;
+                }
+                KIND_BASE => {
+                    bitbuf >>= n;
+                    nbits -= n;
+                }
+                KIND_END => {
+                    bitbuf >>= n;
+                    nbits -= n;
+                    end_of_block = true;
+                    break;
+                }
+                _ => {
+                    (bitbuf, nbits, pos) = saved;
+                    break;
+                }
+            }
+
+            let extra = e >> 8 & 15;
+            let length = (e >> 16) as usize + (bitbuf & ((1 << extra) - 1)) as usize;
+            bitbuf >>= extra;
+            nbits -= extra;
+
+            let e = dist.lookup(bitbuf);
+            if e >> 4 & 15 != KIND_BASE {
+                (bitbuf, nbits, pos) = saved;
+                break;
+            }
+            let n = e & 15;
+            bitbuf >>= n;
+            nbits -= n;
+            let extra = e >> 8 & 15;
+            let distance = (e >> 16) as usize + (bitbuf & ((1 << extra) - 1)) as usize;
+            bitbuf >>= extra;
+            nbits -= extra;
+
+            let hist_size = if full { hist.len() } else { wr_pos };
+            if distance > hist_size {
+                (bitbuf, nbits, pos) = saved;
+                break;
+            }
+            copy_words(hist, wr_pos, distance, length);
+            wr_pos += length;
+        }
+        self.dict.write_mark(wr_pos - start_pos);
+
+        // Give the unused whole bytes back to the input,
+        // so that nothing is read past the end of the stream.
+        let unread = ((nbits / 8) as usize).min(pos);
+        pos -= unread;
+        nbits -= unread as u32 * 8;
+        self.b = (bitbuf & ((1 << nbits) - 1)) as u32;
+        self.nb = nbits as usize;
+        self.r.consume(pos);
+        self.roffset += pos as u64;
+        end_of_block
+    }
+}
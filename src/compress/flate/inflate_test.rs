@@ -7,6 +7,7 @@ use super::deflate::{Writer, DEFAULT_COMPRESSION};
 use super::inflate;
 use crate::bytes;
 use crate::io as ggio;
+use crate::math::bits;
 use std::io::Write;
 
 #[test]
@@ -164,3 +165,259 @@ fn test_reset_dict() {
         );
     }
 }
+
+/// decode_all decompresses the input read through a buffer of the given
+/// capacity and returns the output together with the error, if any.
+/// Buffers smaller than 8 bytes keep the decoder off the fast path.
+fn decode_all(input: &[u8], capacity: usize) -> (Vec<u8>, Option<String>) {
+    let mut f = inflate::Reader::new(std::io::BufReader::with_capacity(capacity, input));
+    let mut output = Vec::new();
+    let mut buf = [0; 1000];
+    loop {
+        match std::io::Read::read(&mut f, &mut buf) {
+            Ok(0) => return (output, None),
+            Ok(n) => output.extend_from_slice(&buf[..n]),
+            Err(err) => return (output, Some(err.to_string())),
+        }
+    }
+}
+
+#[test]
+fn test_fast_path() {
+    // The fast path must produce exactly the same output and errors
+    // as decoding symbol by symbol.
+    let mut data = Vec::new();
+    let mut x: u64 = 1;
+    while data.len() < 200_000 {
+        x = x
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        match x >> 62 {
+            0 => data.extend_from_slice(b"the quick brown fox "),
+            1 => data.resize(data.len() + (x >> 48) as usize % 300, (x >> 40) as u8),
+            2 => data.extend_from_slice(&x.to_le_bytes()),
+            _ => {
+                let n = data.len().min(40_000);
+                let start = data.len() - n + (x >> 20) as usize % (n + 1);
+                let end = (start + (x >> 10) as usize % 100).min(data.len());
+                data.extend_from_within(start..end);
+            }
+        }
+    }
+
+    for level in [1, 6, 9, -2] {
+        let mut compressed = Vec::new();
+        let mut w = Writer::new(&mut compressed, level).unwrap();
+        w.write_all(&data).unwrap();
+        w.close().unwrap();
+
+        let (output, err) = decode_all(&compressed, compressed.len());
+        assert!(err.is_none(), "level {}: {:?}", level, err);
+        assert!(output == data, "level {}: output mismatch", level);
+        assert_eq!(decode_all(&compressed, 1), (output.clone(), None));
+        assert_eq!(decode_all(&compressed, 13), (output, None));
+
+        for i in 0..50 {
+            let mut corrupted = compressed.clone();
+            x = x
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let pos = (x >> 32) as usize % corrupted.len();
+            corrupted[pos] ^= 1 << (x >> 61);
+            let want = decode_all(&corrupted, 1);
+            assert!(
+                decode_all(&corrupted, corrupted.len()) == want,
+                "level {}, corruption {}: mismatch",
+                level,
+                i
+            );
+            assert!(
+                decode_all(&corrupted, 13) == want,
+                "level {}, corruption {}: mismatch",
+                level,
+                i
+            );
+        }
+    }
+}
+
+/// BitWriter packs the fields of a hand-made DEFLATE stream.
+struct BitWriter {
+    out: Vec<u8>,
+    acc: u64,
+    n: u32,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        Self {
+            out: Vec::new(),
+            acc: 0,
+            n: 0,
+        }
+    }
+
+    /// bits writes the n low bits of v, least significant bit first.
+    fn bits(&mut self, v: u32, n: u32) {
+        self.acc |= (v as u64) << self.n;
+        self.n += n;
+        while self.n >= 8 {
+            self.out.push(self.acc as u8);
+            self.acc >>= 8;
+            self.n -= 8;
+        }
+    }
+
+    /// code writes a Huffman code of n bits, most significant bit first.
+    fn code(&mut self, c: u32, n: u32) {
+        self.bits(bits::reverse16((c << (16 - n)) as u16) as u32, n);
+    }
+
+    /// fixed writes the literal/length symbol v using the fixed Huffman code.
+    fn fixed(&mut self, v: u32) {
+        match v {
+            0..=143 => self.code(0x30 + v, 8),
+            144..=255 => self.code(0x190 + v - 144, 9),
+            256..=279 => self.code(v - 256, 7),
+            _ => self.code(0xc0 + v - 280, 8),
+        }
+    }
+
+    /// stored writes a non-final stored block holding data.
+    fn stored(&mut self, data: &[u8]) {
+        self.bits(0, 3);
+        if self.n > 0 {
+            self.bits(0, 8 - self.n);
+        }
+        let n = data.len() as u16;
+        self.out.extend_from_slice(&n.to_le_bytes());
+        self.out.extend_from_slice(&(!n).to_le_bytes());
+        self.out.extend_from_slice(data);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.n > 0 {
+            self.bits(0, 8 - self.n);
+        }
+        self.out
+    }
+}
+
+/// decode_all64 is like decode_all for the Deflate64 format.
+fn decode_all64(input: &[u8], capacity: usize) -> (Vec<u8>, Option<String>) {
+    let mut f = inflate::Reader::new_deflate64(std::io::BufReader::with_capacity(capacity, input));
+    let mut output = Vec::new();
+    match std::io::Read::read_to_end(&mut f, &mut output) {
+        Ok(_) => (output, None),
+        Err(err) => (output, Some(err.to_string())),
+    }
+}
+
+/// deflate64_streams returns hand-made Deflate64 streams together with
+/// the data they decompress to.
+fn deflate64_streams() -> Vec<(&'static str, Vec<u8>, Vec<u8>)> {
+    let mut streams = Vec::new();
+
+    // The length code 285 has 16 extra bits in Deflate64.
+    let mut w = BitWriter::new();
+    w.bits(1, 1); // BFINAL
+    w.bits(1, 2); // BTYPE: fixed Huffman codes
+    w.fixed(b'a' as u32);
+    w.fixed(285);
+    w.bits(40000, 16); // length 40003
+    w.code(0, 5); // distance 1
+    w.fixed(256);
+    streams.push(("long length", w.finish(), vec![b'a'; 40004]));
+
+    // The distance codes 30 and 31 reach beyond 32 KiB.
+    let mut data = Vec::new();
+    let mut x: u32 = 1;
+    while data.len() < 50000 {
+        x = x.wrapping_mul(1103515245).wrapping_add(12345);
+        data.push((x >> 16) as u8);
+    }
+    let mut w = BitWriter::new();
+    w.stored(&data);
+    w.bits(1, 1); // BFINAL
+    w.bits(1, 2); // BTYPE: fixed Huffman codes
+    w.fixed(264); // length 10
+    w.code(30, 5);
+    w.bits(100, 14); // distance 32869
+    w.fixed(257); // length 3
+    w.code(31, 5);
+    w.bits(5, 14); // distance 49158
+    w.fixed(256);
+    let mut want = data.clone();
+    for (length, dist) in [(10, 32869), (3, 49158)] {
+        let start = want.len() - dist;
+        want.extend_from_within(start..start + length);
+    }
+    streams.push(("far distances", w.finish(), want));
+
+    // Dynamic Huffman codes may define 32 distance codes.
+    let mut w = BitWriter::new();
+    w.bits(1, 1); // BFINAL
+    w.bits(2, 2); // BTYPE: dynamic Huffman codes
+    w.bits(286 - 257, 5); // HLIT
+    w.bits(32 - 1, 5); // HDIST
+    w.bits(19 - 4, 4); // HCLEN
+                       // The code length code: 5 -> 00, 18 -> 01, 0 -> 100, 1 -> 101,
+                       // 2 -> 110, 17 -> 111.
+    for sym in [
+        16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+    ] {
+        let n = match sym {
+            5 | 18 => 2,
+            0 | 1 | 2 | 17 => 3,
+            _ => 0,
+        };
+        w.bits(n, 3);
+    }
+    // The literal/length code: 285 -> 0, 'a' -> 10, 256 -> 11.
+    let zeros = |w: &mut BitWriter, n: u32| {
+        w.code(1, 2);
+        w.bits(n - 11, 7);
+    };
+    zeros(&mut w, 97);
+    w.code(6, 3); // 'a' has length 2
+    zeros(&mut w, 138);
+    zeros(&mut w, 20);
+    w.code(6, 3); // 256 has length 2
+    zeros(&mut w, 28);
+    w.code(5, 3); // 285 has length 1
+                  // All the 32 distance codes have length 5.
+    for _ in 0..32 {
+        w.code(0, 2);
+    }
+    w.code(2, 2); // 'a'
+    w.code(0, 1); // 285
+    w.bits(1000, 16); // length 1003
+    w.code(0, 5); // distance 1
+    w.code(3, 2); // 256
+    streams.push(("dynamic codes", w.finish(), vec![b'a'; 1004]));
+
+    streams
+}
+
+#[test]
+fn test_deflate64() {
+    for (desc, input, want) in deflate64_streams() {
+        for capacity in [1, 13, input.len()] {
+            let (output, err) = decode_all64(&input, capacity);
+            assert!(err.is_none(), "{} ({}): {:?}", desc, capacity, err);
+            assert!(output == want, "{} ({}): output mismatch", desc, capacity);
+        }
+
+        // These streams aren't valid DEFLATE.
+        let (output, err) = decode_all(&input, input.len());
+        assert!(err.is_some() || output != want, "{}", desc);
+    }
+
+    // The Reader keeps reading Deflate64 after reset.
+    let (_, input, want) = deflate64_streams().swap_remove(0);
+    let mut f = inflate::Reader::new_deflate64(&[][..]);
+    f.reset(input.as_slice(), &[]);
+    let mut output = Vec::new();
+    std::io::Read::read_to_end(&mut f, &mut output).unwrap();
+    assert!(output == want);
+}
@@ -1,19 +1,26 @@
 // Copyright 2023 The rust-ggstd authors.
 // SPDX-License-Identifier: BSD-3-Clause
 
+mod context;
 mod deflate;
 mod deflatefast;
 mod dict_decoder;
 mod huffman_bit_writer;
 mod huffman_code;
+mod index;
 mod inflate;
+mod inflate_fast;
 mod token;
 
+pub use context::{compress, compress_bound, decompress, Context};
 pub use deflate::{
     Writer, BEST_COMPRESSION, BEST_SPEED, DEFAULT_COMPRESSION, HUFFMAN_ONLY, NO_COMPRESSION,
 };
-pub use inflate::Reader;
+pub use index::{Checkpoint, Index, DEFAULT_SPAN};
+pub use inflate::{Error, Reader};
 
+#[cfg(test)]
+pub(super) mod context_test;
 #[cfg(test)]
 mod deflate_test;
 #[cfg(test)]
@@ -23,6 +30,8 @@ mod flate_test;
 #[cfg(test)]
 mod huffman_bit_writer_test;
 #[cfg(test)]
+pub(super) mod index_test;
+#[cfg(test)]
 mod inflate_test;
 #[cfg(test)]
 mod reader_test;
@@ -6,6 +6,7 @@
 use crate::bytes;
 use crate::compress::flate;
 use crate::io as ggio;
+use std::io::{BufRead, Read, Write};
 
 #[test]
 fn test_nlit_out_of_range() {
@@ -96,3 +97,59 @@ fn test_nlit_out_of_range() {
 // 		}
 // 	}
 // }
+
+#[test]
+fn test_reader_into_inner() {
+    // The Reader owns its input, so it can be returned from a function.
+    fn open(compressed: &[u8]) -> Box<dyn std::io::Read + '_> {
+        Box::new(flate::Reader::new(compressed))
+    }
+
+    let mut compressed = Vec::new();
+    let mut w = flate::Writer::new(&mut compressed, flate::BEST_SPEED).unwrap();
+    w.write_all(b"hello, world").unwrap();
+    w.close().unwrap();
+    compressed.extend_from_slice(b"trailer");
+
+    let mut output = String::new();
+    open(&compressed).read_to_string(&mut output).unwrap();
+    assert_eq!(output, "hello, world");
+
+    let mut r = flate::Reader::new(compressed.as_slice());
+    assert_eq!(r.get_ref().len(), compressed.len());
+    let mut output = Vec::new();
+    r.read_to_end(&mut output).unwrap();
+    assert_eq!(r.into_inner(), b"trailer");
+}
+
+#[test]
+fn test_reader_buf_read() {
+    let mut w = flate::Writer::new(Vec::new(), flate::BEST_SPEED).unwrap();
+    w.write_all(b"first line\nsecond line\n").unwrap();
+    w.close().unwrap();
+    let compressed = w.into_inner();
+
+    let r = flate::Reader::new(compressed.as_slice());
+    let lines: Vec<String> = r.lines().map(|line| line.unwrap()).collect();
+    assert_eq!(lines, ["first line", "second line"]);
+}
+
+#[test]
+fn test_reader_corrupt_input() {
+    // A block of the reserved type 3.
+    let mut r = flate::Reader::new(&b"\x07\x00"[..]);
+    for _ in 0..2 {
+        // The error is persistent and keeps its type.
+        let err = r.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        let inner = err.get_ref().and_then(|e| e.downcast_ref::<flate::Error>());
+        assert_eq!(inner, Some(&flate::Error::CorruptInput(1)));
+    }
+
+    // Failures of the underlying reader are passed through as they are.
+    let mut r = flate::Reader::new(&b"\x00\x05"[..]);
+    let err = r.read_to_end(&mut Vec::new()).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    let inner = err.get_ref().and_then(|e| e.downcast_ref::<flate::Error>());
+    assert_eq!(inner, None);
+}
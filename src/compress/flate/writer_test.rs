@@ -249,3 +249,20 @@ fn test_deterministic_int(level: isize) {
 // 		}
 // 	}
 // }
+
+#[test]
+fn test_writer_into_inner() {
+    // The Writer owns its output, so it can be returned from a function.
+    fn new_writer(level: isize) -> flate::Writer<Vec<u8>> {
+        flate::Writer::new(Vec::new(), level).unwrap()
+    }
+
+    let data = flate::index_test::test_data(100_000);
+    let mut w = new_writer(flate::BEST_COMPRESSION);
+    w.write_all(&data).unwrap();
+    w.close().unwrap();
+    assert!(!w.get_ref().is_empty());
+    w.get_mut().shrink_to_fit();
+    let compressed = w.into_inner();
+    assert!(flate::decompress(&compressed).unwrap() == data);
+}
@@ -0,0 +1,119 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::gunzip::{get_err_checksum, ReadState};
+use super::gzip::{default_header, header_bytes};
+use super::{BEST_COMPRESSION, DEFAULT_COMPRESSION, HUFFMAN_ONLY};
+use crate::compress::flate;
+use crate::encoding::binary::{ByteOrder, LITTLE_ENDIAN};
+use crate::errors;
+use crate::hash::crc32;
+
+/// compress_bound returns the maximum size of the gzip file produced
+/// by compressing n bytes at any level.
+pub fn compress_bound(n: usize) -> usize {
+    // 10 bytes of header and 8 bytes of checksum and size.
+    flate::compress_bound(n) + 18
+}
+
+/// compress returns the gzip-compressed form of src at the given level,
+/// with the default header.
+pub fn compress(src: &[u8], level: isize) -> std::io::Result<Vec<u8>> {
+    Context::new(level)?.compress(src)
+}
+
+/// decompress returns the data decompressed from the gzip file src
+/// and verifies the checksum and size of each of its members.
+/// The members are concatenated as by Reader.
+pub fn decompress(src: &[u8]) -> std::io::Result<Vec<u8>> {
+    Context::new(DEFAULT_COMPRESSION)?.decompress(src)
+}
+
+/// Context compresses and decompresses whole slices in memory,
+/// reusing the compressor and decompressor state between calls.
+pub struct Context {
+    level: isize,
+    flate: flate::Context,
+    state: ReadState,
+}
+
+impl Context {
+    /// new returns a Context compressing at the given level.
+    pub fn new(level: isize) -> std::io::Result<Self> {
+        if !(HUFFMAN_ONLY..=BEST_COMPRESSION).contains(&level) {
+            return Err(errors::new_stdio_other_error(format!(
+                "gzip: invalid compression level: {}",
+                level
+            )));
+        }
+        Ok(Self {
+            level,
+            flate: flate::Context::new(level)?,
+            state: ReadState::new(),
+        })
+    }
+
+    /// compress returns the compressed form of src with the default header.
+    pub fn compress(&mut self, src: &[u8]) -> std::io::Result<Vec<u8>> {
+        let mut dst = Vec::with_capacity(compress_bound(src.len()));
+        self.compress_to(src, &mut dst)?;
+        Ok(dst)
+    }
+
+    /// compress_to appends the compressed form of src with the default
+    /// header to dst and returns the number of bytes appended.
+    pub fn compress_to(&mut self, src: &[u8], dst: &mut Vec<u8>) -> std::io::Result<usize> {
+        let start = dst.len();
+        dst.reserve(compress_bound(src.len()));
+        dst.extend_from_slice(&header_bytes(&default_header(), self.level)?);
+        self.flate.compress_to(src, dst)?;
+        let mut trailer = [0; 8];
+        LITTLE_ENDIAN.put_uint32(&mut trailer[..4], crc32::checksum_ieee(src));
+        LITTLE_ENDIAN.put_uint32(&mut trailer[4..], src.len() as u32);
+        dst.extend_from_slice(&trailer);
+        Ok(dst.len() - start)
+    }
+
+    /// decompress returns the data decompressed from the gzip file src.
+    pub fn decompress(&mut self, src: &[u8]) -> std::io::Result<Vec<u8>> {
+        let mut dst = Vec::new();
+        self.decompress_to(src, &mut dst)?;
+        Ok(dst)
+    }
+
+    /// decompress_to appends the data decompressed from the gzip file src
+    /// to dst and returns the number of bytes appended.
+    pub fn decompress_to(&mut self, mut src: &[u8], dst: &mut Vec<u8>) -> std::io::Result<usize> {
+        let start = dst.len();
+        // The size of the last member is a good guess for the size of
+        // the output. A DEFLATE stream expands at most 1032 times.
+        if src.len() >= 4 {
+            let size = LITTLE_ENDIAN.uint32(&src[src.len() - 4..]) as usize;
+            dst.reserve(size.min(src.len().saturating_mul(1032)));
+        }
+        let mut first = true;
+        loop {
+            if self.state.read_header(&mut src)?.is_none() {
+                if first {
+                    return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+                }
+                return Ok(dst.len() - start);
+            }
+            first = false;
+
+            let member_start = dst.len();
+            src = self.flate.decompress_stream(src, dst)?;
+            let member = &dst[member_start..];
+            if src.len() < 8 {
+                return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+            }
+            if LITTLE_ENDIAN.uint32(&src[..4]) != crc32::checksum_ieee(member)
+                || LITTLE_ENDIAN.uint32(&src[4..8]) != member.len() as u32
+            {
+                return Err(get_err_checksum());
+            }
+            src = &src[8..];
+        }
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::{compress, compress_bound, decompress, Context, Writer};
+use super::{BEST_COMPRESSION, DEFAULT_COMPRESSION, ERR_CHECKSUM_MSG, ERR_INVALID_HEADER};
+use super::{BEST_SPEED, HUFFMAN_ONLY};
+use crate::compress::flate;
+use std::io::Write;
+
+#[test]
+fn test_compress() {
+    let data = flate::index_test::test_data(100_000);
+    let mut ctx = Context::new(DEFAULT_COMPRESSION).unwrap();
+    for level in HUFFMAN_ONLY..=BEST_COMPRESSION {
+        let mut want = Vec::new();
+        let mut w = Writer::new_level(&mut want, level).unwrap();
+        w.write_all(&data).unwrap();
+        w.close().unwrap();
+
+        let got = compress(&data, level).unwrap();
+        assert!(got == want, "level {}: output differs from Writer", level);
+        assert!(ctx.decompress(&got).unwrap() == data, "level {}", level);
+    }
+    assert!(compress(&data, 10).is_err());
+}
+
+#[test]
+fn test_compress_bound() {
+    let data = flate::context_test::random_data(100_000);
+    let compressed = compress(&data, BEST_SPEED).unwrap();
+    assert!(compressed.len() <= compress_bound(data.len()));
+    assert_eq!(compressed.capacity(), compress_bound(data.len()));
+}
+
+#[test]
+fn test_decompress_multistream() {
+    let mut ctx = Context::new(BEST_SPEED).unwrap();
+    let mut compressed = ctx.compress(b"hello, ").unwrap();
+    ctx.compress_to(b"", &mut compressed).unwrap();
+    ctx.compress_to(b"world", &mut compressed).unwrap();
+    assert_eq!(decompress(&compressed).unwrap(), b"hello, world");
+
+    let mut output = b">".to_vec();
+    let n = ctx.decompress_to(&compressed, &mut output).unwrap();
+    assert_eq!(n, 12);
+    assert_eq!(output, b">hello, world");
+}
+
+#[test]
+fn test_decompress_errors() {
+    let data = flate::index_test::test_data(10_000);
+    let compressed = compress(&data, DEFAULT_COMPRESSION).unwrap();
+    let n = compressed.len();
+
+    let mut bad = compressed.clone();
+    bad[n - 8] ^= 1;
+    let err = decompress(&bad).unwrap_err();
+    assert_eq!(err.to_string(), ERR_CHECKSUM_MSG);
+
+    let mut bad = compressed.clone();
+    bad[n - 1] ^= 1;
+    let err = decompress(&bad).unwrap_err();
+    assert_eq!(err.to_string(), ERR_CHECKSUM_MSG);
+
+    let mut bad = compressed.clone();
+    bad.extend_from_slice(b"trailing garbage");
+    let err = decompress(&bad).unwrap_err();
+    assert_eq!(err.to_string(), ERR_INVALID_HEADER);
+
+    for input in [&compressed[..0], &compressed[..n - 3]] {
+        let err = decompress(input).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -3,35 +3,61 @@
 // Use of this source code is governed by a BSD-style
 // license that can be found in the LICENSE file.
 
-use crate::compress::flate;
+use crate::compat;
+use crate::compress::{copy_error, flate};
 use crate::encoding::binary::{ByteOrder, LITTLE_ENDIAN};
-use crate::errors;
 use crate::hash::crc32;
 use crate::io as ggio;
 use crate::time;
+use std::io::BufRead;
 
 pub(super) const GZIP_ID1: u8 = 0x1f;
-const GZIP_ID2: u8 = 0x8b;
-const GZIP_DEFLATE: u8 = 8;
+pub(super) const GZIP_ID2: u8 = 0x8b;
+pub(super) const GZIP_DEFLATE: u8 = 8;
 const _FLAG_TEXT: u8 = 1 << 0;
 const FLAG_HDR_CRC: u8 = 1 << 1;
 const FLAG_EXTRA: u8 = 1 << 2;
 const FLAG_NAME: u8 = 1 << 3;
 const FLAG_COMMENT: u8 = 1 << 4;
 
-/// ERR_CHECKSUM_MSG is an error message that will be return as std::io::Error
-/// when the checksum is wrong.
+/// ERR_CHECKSUM_MSG is the message of Error::ErrChecksum.
 pub const ERR_CHECKSUM_MSG: &str = "gzip: invalid checksum";
-/// ERR_CHECKSUM_MSG is an error message that will be return as std::io::Error
-/// when the header is wrong.
+/// ERR_INVALID_HEADER is the message of Error::ErrHeader.
 pub const ERR_INVALID_HEADER: &str = "gzip: invalid header";
 
-fn get_err_checksum() -> std::io::Error {
-    errors::new_stdio_other_error(ERR_CHECKSUM_MSG.to_string())
+/// Error describes invalid GZIP data. Reader returns it wrapped
+/// in a std::io::Error of the ErrorKind::InvalidData kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// ErrChecksum is returned when reading GZIP data that has an invalid checksum.
+    ErrChecksum,
+    /// ErrHeader is returned when reading GZIP data that has an invalid header.
+    ErrHeader,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::ErrChecksum => write!(f, "{}", ERR_CHECKSUM_MSG),
+            Error::ErrHeader => write!(f, "{}", ERR_INVALID_HEADER),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for std::io::Error {
+    fn from(e: Error) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, e)
+    }
+}
+
+pub(super) fn get_err_checksum() -> std::io::Error {
+    Error::ErrChecksum.into()
 }
 
 fn get_err_invalid_header() -> std::io::Error {
-    errors::new_stdio_other_error(ERR_INVALID_HEADER.to_string())
+    Error::ErrHeader.into()
 }
 
 /// The gzip file stores a header giving metadata about the compressed file.
@@ -48,7 +74,7 @@ pub struct Header {
     pub os: u8,                  // operating system type
 }
 
-struct ReadState {
+pub(super) struct ReadState {
     digest: u32, // CRC-32, IEEE polynomial (section 8)
     buf: Vec<u8>,
 }
@@ -67,16 +93,18 @@ struct ReadState {
 /// have the expected length or checksum. Clients should treat data
 /// returned by Read as tentative until they receive the io.EOF
 /// marking the end of the data.
-pub struct Reader<'a, Input: std::io::BufRead> {
+pub struct Reader<Input: std::io::BufRead> {
     pub header: Option<Header>, // valid after Reader::new or Reader.reset
     read_state: ReadState,
-    decompressor: flate::Reader<&'a mut Input>,
+    decompressor: flate::Reader<Input>,
     size: u32, // Uncompressed size (section 2.3.1)
     err: Option<std::io::Error>,
     multistream: bool,
+    // done is set at the end of the stream when multistream is disabled.
+    done: bool,
 }
 
-impl<'a, Input: std::io::BufRead> Reader<'a, Input> {
+impl<Input: std::io::BufRead> Reader<Input> {
     /// new creates a new Reader reading the given reader.
     ///
     /// Make sure that the reader implements buffering otherwise the performance
@@ -90,7 +118,7 @@ impl<'a, Input: std::io::BufRead> Reader<'a, Input> {
     //
     /// The Reader.header fields will be valid in the Reader returned.
     /// If Reader.header is None, then there is no stream available.
-    pub fn new(r: &'a mut Input) -> std::io::Result<Self> {
+    pub fn new(mut r: Input) -> std::io::Result<Self> {
         // 	z := new(Reader)
         // 	if err := self.reset(r); err != nil {
         // 		return nil, err
@@ -98,7 +126,7 @@ impl<'a, Input: std::io::BufRead> Reader<'a, Input> {
         // 	return z, nil
 
         let mut read_state = ReadState::new();
-        let header = read_state.read_header(r)?;
+        let header = read_state.read_header(&mut r)?;
         let decompressor = flate::Reader::new(r);
 
         Ok(Self {
@@ -108,17 +136,19 @@ impl<'a, Input: std::io::BufRead> Reader<'a, Input> {
             size: 0,
             err: None,
             multistream: true,
+            done: false,
         })
     }
 
     /// reset discards the Reader self's state and makes it equivalent to the
     /// result of its original state from Reader::new, but reading from r instead.
     /// This permits reusing a Reader rather than allocating a new one.
-    pub fn reset(&mut self, r: &'a mut Input) -> std::io::Result<()> {
+    pub fn reset(&mut self, mut r: Input) -> std::io::Result<()> {
         self.read_state = ReadState::new();
-        self.header = self.read_state.read_header(r)?;
+        self.header = self.read_state.read_header(&mut r)?;
         self.decompressor.reset(r, &[]);
         self.multistream = true;
+        self.done = false;
         self.size = 0;
         self.err = None;
         Ok(())
@@ -132,6 +162,7 @@ impl<'a, Input: std::io::BufRead> Reader<'a, Input> {
             .read_header(self.decompressor.input_reader())?;
         self.decompressor.reset_state(&[]);
         self.multistream = true;
+        self.done = false;
         self.size = 0;
         self.err = None;
         Ok(())
@@ -168,84 +199,104 @@ impl<'a, Input: std::io::BufRead> Reader<'a, Input> {
     pub fn is_eof(&self) -> bool {
         self.header.is_none()
     }
+
+    /// get_ref returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &Input {
+        self.decompressor.get_ref()
+    }
+
+    /// get_mut returns a mutable reference to the underlying reader.
+    /// Reading from it directly corrupts the decompressed stream.
+    pub fn get_mut(&mut self) -> &mut Input {
+        self.decompressor.get_mut()
+    }
+
+    /// into_inner returns the underlying reader. With multistream disabled,
+    /// once the Reader has been read to the end the underlying reader is
+    /// positioned right after the gzip stream.
+    pub fn into_inner(self) -> Input {
+        self.decompressor.into_inner()
+    }
 }
 
-impl<Input: std::io::BufRead> crate::io::Reader for Reader<'_, Input> {
-    /// read implements io.Reader, reading uncompressed bytes from its underlying Reader.
-    fn read(&mut self, p: &mut [u8]) -> ggio::IoRes {
-        let mut n = 0;
-        if self.err.is_some() {
-            return (0, errors::copy_stdio_option_error(&self.err));
+impl<Input: std::io::BufRead> Reader<Input> {
+    /// end_member verifies the trailer of the gzip member which has just
+    /// been decompressed and reads the header of the next member, if any.
+    fn end_member(&mut self) -> std::io::Result<()> {
+        // Finished file; check checksum and size.
+        let mut buf = [0; 8];
+        self.decompressor.input_reader().read_exact(&mut buf)?;
+        let digest = LITTLE_ENDIAN.uint32(&buf[..4]);
+        let size = LITTLE_ENDIAN.uint32(&buf[4..8]);
+        if digest != self.read_state.digest || size != self.size {
+            return Err(get_err_checksum());
         }
+        self.read_state.digest = 0;
+        self.size = 0;
 
-        while n == 0 {
-            if self.is_eof() {
-                return ggio::EOF;
+        // File is ok; check if there is another.
+        if !self.multistream {
+            self.done = true;
+            return Ok(());
+        }
+        self.read_state = ReadState::new();
+        self.header = self
+            .read_state
+            .read_header(self.decompressor.input_reader())?;
+        self.decompressor.reset_state(&[]);
+        Ok(())
+    }
+}
+
+impl<Input: std::io::BufRead> std::io::BufRead for Reader<Input> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        loop {
+            if let Some(err) = &self.err {
+                return Err(copy_error(err));
             }
-            let res = crate::io::Reader::read(&mut self.decompressor, p);
-            self.read_state.digest =
-                crc32::update(self.read_state.digest, &crc32::IEEE_TABLE, &p[..res.0]);
-            self.size += res.0 as u32;
-            if !ggio::is_eof(&res) {
-                return res;
+            if self.done || self.is_eof() {
+                return Ok(&[]);
             }
-            (n, self.err) = res;
-
-            // Finished file; check checksum and size.
-            {
-                let mut buf = [0; 8];
-                if let Err(err) = self.decompressor.input_reader().read_exact(&mut buf) {
-                    self.err = Some(err);
-                    return (n, errors::copy_stdio_option_error(&self.err));
-                }
-                let digest = LITTLE_ENDIAN.uint32(&buf[..4]);
-                let size = LITTLE_ENDIAN.uint32(&buf[4..8]);
-                if digest != self.read_state.digest || size != self.size {
-                    self.err = Some(get_err_checksum());
-                    return (n, errors::copy_stdio_option_error(&self.err));
-                }
+            if !self.decompressor.fill_buf()?.is_empty() {
+                break;
             }
-            self.read_state.digest = 0;
-            self.size = 0;
-
-            // File is ok; check if there is another.
-            if !self.multistream {
-                return (n, None);
+            if let Err(err) = self.end_member() {
+                self.err = Some(copy_error(&err));
+                return Err(err);
             }
-            self.err = None; // Remove io.EOF
-
-            self.read_state = ReadState::new();
-            self.header = match self
-                .read_state
-                .read_header(self.decompressor.input_reader())
-            {
-                Ok(header) => header,
-                Err(err) => {
-                    return (n, Some(err));
-                }
-            };
-            self.decompressor.reset_state(&[]);
         }
+        Ok(self.decompressor.buffer())
+    }
 
-        (n, None)
+    fn consume(&mut self, amt: usize) {
+        let amt = amt.min(self.decompressor.buffer().len());
+        let p = &self.decompressor.buffer()[..amt];
+        self.read_state.digest = crc32::update(self.read_state.digest, &crc32::IEEE_TABLE, p);
+        self.size = self.size.wrapping_add(amt as u32);
+        self.decompressor.consume(amt);
     }
 }
 
-impl<Input: std::io::BufRead> std::io::Read for Reader<'_, Input> {
-    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        let res = crate::io::Reader::read(self, buf);
-        if res.0 > 0 {
-            Ok(res.0)
-        } else if ggio::is_eof(&res) {
-            return Ok(0);
-        } else {
-            return Err(res.1.unwrap());
+impl<Input: std::io::BufRead> crate::io::Reader for Reader<Input> {
+    /// read implements io.Reader, reading uncompressed bytes from its underlying Reader.
+    fn read(&mut self, p: &mut [u8]) -> ggio::IoRes {
+        match std::io::Read::read(self, p) {
+            Ok(n) => (n, None),
+            Err(err) => (0, Some(err)),
         }
     }
 }
 
+impl<Input: std::io::BufRead> std::io::Read for Reader<Input> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = compat::copy(buf, self.fill_buf()?);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
 impl ReadState {
-    fn new() -> Self {
+    pub(super) fn new() -> Self {
         Self {
             digest: 0,
             buf: vec![0; 512],
@@ -254,7 +305,7 @@ impl ReadState {
 
     /// read_header reads the GZIP header according to section 2.3.1.
     /// This method does not set self.err.
-    fn read_header<T: std::io::BufRead>(
+    pub(super) fn read_header<T: std::io::BufRead>(
         &mut self,
         r: &mut T,
     ) -> Result<Option<Header>, std::io::Error> {
@@ -3,7 +3,7 @@
 // Use of this source code is governed by a BSD-style
 // license that can be found in the LICENSE file.
 
-use super::Reader;
+use super::{Error, Reader};
 use crate::bytes;
 use crate::compat;
 use crate::encoding::base64;
@@ -13,7 +13,7 @@ use std::io::Read;
 
 #[derive(Debug)]
 enum GunzipTestError {
-    Custom(&'static str),
+    Gzip(Error),
     UnexpectedEof,
 }
 
@@ -215,7 +215,7 @@ Abraham Lincoln, November 19, 1863, Gettysburg, Pennsylvania
             0x2f, 0xca, 0x49, 0xe1, 0x02, 0x00, 0x2d, 0x3b, 0x08, 0xaf, 0x0c, 0x00, 0x00, 0x00,
             b'g', b'a', b'r', b'b', b'a', b'g', b'e', b'!', b'!', b'!',
         ],
-        Some(GunzipTestError::Custom(super::ERR_INVALID_HEADER)),
+        Some(GunzipTestError::Gzip(Error::ErrHeader)),
     ),
     GunzipTest::new(
         // has 1 non-empty fixed huffman block not enough header
@@ -279,7 +279,7 @@ Abraham Lincoln, November 19, 1863, Gettysburg, Pennsylvania
             0x6f, 0x2e, 0x74, 0x78, 0x74, 0x00, 0xcb, 0x48, 0xcd, 0xc9, 0xc9, 0x57, 0x28, 0xcf,
             0x2f, 0xca, 0x49, 0xe1, 0x02, 0x00, 0xff, 0xff, 0xff, 0xff, 0x0c, 0x00, 0x00, 0x00,
         ],
-        Some(GunzipTestError::Custom(super::ERR_CHECKSUM_MSG)),
+        Some(GunzipTestError::Gzip(Error::ErrChecksum)),
     ),
     GunzipTest::new(
         // has 1 non-empty fixed huffman block but corrupt size
@@ -291,7 +291,7 @@ Abraham Lincoln, November 19, 1863, Gettysburg, Pennsylvania
             0x6f, 0x2e, 0x74, 0x78, 0x74, 0x00, 0xcb, 0x48, 0xcd, 0xc9, 0xc9, 0x57, 0x28, 0xcf,
             0x2f, 0xca, 0x49, 0xe1, 0x02, 0x00, 0x2d, 0x3b, 0x08, 0xaf, 0xff, 0x00, 0x00, 0x00,
         ],
-        Some(GunzipTestError::Custom(super::ERR_CHECKSUM_MSG)),
+        Some(GunzipTestError::Gzip(Error::ErrChecksum)),
     ),
     GunzipTest::new(
         Some("f1l3n4m3.tXt"),
@@ -393,12 +393,15 @@ fn test_decompressor() {
         let e1 = e1.as_ref().unwrap();
         let e2 = e2.as_ref().unwrap();
         match e2 {
-            GunzipTestError::Custom(message) => e1.to_string() == *message,
+            GunzipTestError::Gzip(want) => {
+                e1.kind() == std::io::ErrorKind::InvalidData
+                    && e1.get_ref().and_then(|e| e.downcast_ref::<Error>()) == Some(want)
+            }
             GunzipTestError::UnexpectedEof => e1.kind() == std::io::ErrorKind::UnexpectedEof,
         }
     }
 
-    fn test(tt: &GunzipTest, r: &mut Reader<bytes::Reader>, b: &mut bytes::Buffer) {
+    fn test(tt: &GunzipTest, r: &mut Reader<&mut bytes::Reader>, b: &mut bytes::Buffer) {
         let header = r.header.as_ref().unwrap();
         assert_eq!(
             to_op_str(tt.name),
@@ -1,250 +1,265 @@
-// // Copyright 2010 The Go Authors. All rights reserved.
-// // Use of this source code is governed by a BSD-style
-// // license that can be found in the LICENSE file.
-
-// package gzip
-
-// import (
-// 	"compress/flate"
-// 	"errors"
-// 	"fmt"
-// 	"hash/crc32"
-// 	"io"
-// 	"time"
-// )
-
-// // These constants are copied from the flate package, so that code that imports
-// // "compress/gzip" does not also have to import "compress/flate".
-// const (
-// 	NoCompression      = flate.NoCompression
-// 	BestSpeed          = flate.BestSpeed
-// 	BestCompression    = flate.BestCompression
-// 	DefaultCompression = flate.DefaultCompression
-// 	HuffmanOnly        = flate.HuffmanOnly
-// )
-
-// // A Writer is an io.WriteCloser.
-// // Writes to a Writer are compressed and written to w.
-// type Writer struct {
-// 	Header      // written at first call to Write, Flush, or Close
-// 	w           io.Writer
-// 	level       int
-// 	wroteHeader bool
-// 	compressor  *flate.Writer
-// 	digest      uint32 // CRC-32, IEEE polynomial (section 8)
-// 	size        uint32 // Uncompressed size (section 2.3.1)
-// 	closed      bool
-// 	buf         [10]byte
-// 	err         error
-// }
-
-// // NewWriter returns a new Writer.
-// // Writes to the returned writer are compressed and written to w.
-// //
-// // It is the caller's responsibility to call Close on the Writer when done.
-// // Writes may be buffered and not flushed until Close.
-// //
-// // Callers that wish to set the fields in Writer.Header must do so before
-// // the first call to Write, Flush, or Close.
-// func NewWriter(w io.Writer) *Writer {
-// 	z, _ := NewWriterLevel(w, DefaultCompression)
-// 	return z
-// }
-
-// // NewWriterLevel is like NewWriter but specifies the compression level instead
-// // of assuming DefaultCompression.
-// //
-// // The compression level can be DefaultCompression, NoCompression, HuffmanOnly
-// // or any integer value between BestSpeed and BestCompression inclusive.
-// // The error returned will be nil if the level is valid.
-// func NewWriterLevel(w io.Writer, level int) (*Writer, error) {
-// 	if level < HuffmanOnly || level > BestCompression {
-// 		return nil, fmt.Errorf("gzip: invalid compression level: %d", level)
-// 	}
-// 	z := new(Writer)
-// 	z.init(w, level)
-// 	return z, nil
-// }
-
-// func (z *Writer) init(w io.Writer, level int) {
-// 	compressor := z.compressor
-// 	if compressor != nil {
-// 		compressor.Reset(w)
-// 	}
-// 	*z = Writer{
-// 		Header: Header{
-// 			OS: 255, // unknown
-// 		},
-// 		w:          w,
-// 		level:      level,
-// 		compressor: compressor,
-// 	}
-// }
-
-// // Reset discards the Writer z's state and makes it equivalent to the
-// // result of its original state from NewWriter or NewWriterLevel, but
-// // writing to w instead. This permits reusing a Writer rather than
-// // allocating a new one.
-// func (z *Writer) Reset(w io.Writer) {
-// 	z.init(w, z.level)
-// }
-
-// // writeBytes writes a length-prefixed byte slice to z.w.
-// func (z *Writer) writeBytes(b []byte) error {
-// 	if len(b) > 0xffff {
-// 		return errors.New("gzip.Write: Extra data is too large")
-// 	}
-// 	le.PutUint16(z.buf[:2], uint16(len(b)))
-// 	_, err := z.w.Write(z.buf[:2])
-// 	if err != nil {
-// 		return err
-// 	}
-// 	_, err = z.w.Write(b)
-// 	return err
-// }
-
-// // writeString writes a UTF-8 string s in GZIP's format to z.w.
-// // GZIP (RFC 1952) specifies that strings are NUL-terminated ISO 8859-1 (Latin-1).
-// func (z *Writer) writeString(s string) (err error) {
-// 	// GZIP stores Latin-1 strings; error if non-Latin-1; convert if non-ASCII.
-// 	needconv := false
-// 	for _, v := range s {
-// 		if v == 0 || v > 0xff {
-// 			return errors.New("gzip.Write: non-Latin-1 header string")
-// 		}
-// 		if v > 0x7f {
-// 			needconv = true
-// 		}
-// 	}
-// 	if needconv {
-// 		b := make([]byte, 0, len(s))
-// 		for _, v := range s {
-// 			b = append(b, byte(v))
-// 		}
-// 		_, err = z.w.Write(b)
-// 	} else {
-// 		_, err = io.WriteString(z.w, s)
-// 	}
-// 	if err != nil {
-// 		return err
-// 	}
-// 	// GZIP strings are NUL-terminated.
-// 	z.buf[0] = 0
-// 	_, err = z.w.Write(z.buf[:1])
-// 	return err
-// }
-
-// // Write writes a compressed form of p to the underlying io.Writer. The
-// // compressed bytes are not necessarily flushed until the Writer is closed.
-// func (z *Writer) Write(p []byte) (int, error) {
-// 	if z.err != nil {
-// 		return 0, z.err
-// 	}
-// 	var n int
-// 	// Write the GZIP header lazily.
-// 	if !z.wroteHeader {
-// 		z.wroteHeader = true
-// 		z.buf = [10]byte{0: gzipID1, 1: gzipID2, 2: gzipDeflate}
-// 		if z.Extra != nil {
-// 			z.buf[3] |= 0x04
-// 		}
-// 		if z.Name != "" {
-// 			z.buf[3] |= 0x08
-// 		}
-// 		if z.Comment != "" {
-// 			z.buf[3] |= 0x10
-// 		}
-// 		if z.ModTime.After(time.Unix(0, 0)) {
-// 			// Section 2.3.1, the zero value for MTIME means that the
-// 			// modified time is not set.
-// 			le.PutUint32(z.buf[4:8], uint32(z.ModTime.Unix()))
-// 		}
-// 		if z.level == BestCompression {
-// 			z.buf[8] = 2
-// 		} else if z.level == BestSpeed {
-// 			z.buf[8] = 4
-// 		}
-// 		z.buf[9] = z.OS
-// 		_, z.err = z.w.Write(z.buf[:10])
-// 		if z.err != nil {
-// 			return 0, z.err
-// 		}
-// 		if z.Extra != nil {
-// 			z.err = z.writeBytes(z.Extra)
-// 			if z.err != nil {
-// 				return 0, z.err
-// 			}
-// 		}
-// 		if z.Name != "" {
-// 			z.err = z.writeString(z.Name)
-// 			if z.err != nil {
-// 				return 0, z.err
-// 			}
-// 		}
-// 		if z.Comment != "" {
-// 			z.err = z.writeString(z.Comment)
-// 			if z.err != nil {
-// 				return 0, z.err
-// 			}
-// 		}
-// 		if z.compressor == nil {
-// 			z.compressor, _ = flate.NewWriter(z.w, z.level)
-// 		}
-// 	}
-// 	z.size += uint32(len(p))
-// 	z.digest = crc32.Update(z.digest, crc32.IEEETable, p)
-// 	n, z.err = z.compressor.Write(p)
-// 	return n, z.err
-// }
-
-// // Flush flushes any pending compressed data to the underlying writer.
-// //
-// // It is useful mainly in compressed network protocols, to ensure that
-// // a remote reader has enough data to reconstruct a packet. Flush does
-// // not return until the data has been written. If the underlying
-// // writer returns an error, Flush returns that error.
-// //
-// // In the terminology of the zlib library, Flush is equivalent to Z_SYNC_FLUSH.
-// func (z *Writer) Flush() error {
-// 	if z.err != nil {
-// 		return z.err
-// 	}
-// 	if z.closed {
-// 		return nil
-// 	}
-// 	if !z.wroteHeader {
-// 		z.Write(nil)
-// 		if z.err != nil {
-// 			return z.err
-// 		}
-// 	}
-// 	z.err = z.compressor.Flush()
-// 	return z.err
-// }
-
-// // Close closes the Writer by flushing any unwritten data to the underlying
-// // io.Writer and writing the GZIP footer.
-// // It does not close the underlying io.Writer.
-// func (z *Writer) Close() error {
-// 	if z.err != nil {
-// 		return z.err
-// 	}
-// 	if z.closed {
-// 		return nil
-// 	}
-// 	z.closed = true
-// 	if !z.wroteHeader {
-// 		z.Write(nil)
-// 		if z.err != nil {
-// 			return z.err
-// 		}
-// 	}
-// 	z.err = z.compressor.Close()
-// 	if z.err != nil {
-// 		return z.err
-// 	}
-// 	le.PutUint32(z.buf[:4], z.digest)
-// 	le.PutUint32(z.buf[4:8], z.size)
-// 	_, z.err = z.w.Write(z.buf[:8])
-// 	return z.err
-// }
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2010 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::gunzip::{GZIP_DEFLATE, GZIP_ID1, GZIP_ID2};
+use super::Header;
+use crate::compress::flate;
+use crate::encoding::binary::{ByteOrder, LITTLE_ENDIAN};
+use crate::errors;
+use crate::hash::crc32;
+use crate::time;
+
+// These constants are copied from the flate package, so that code that imports
+// "compress/gzip" does not also have to import "compress/flate".
+pub const NO_COMPRESSION: isize = flate::NO_COMPRESSION;
+pub const BEST_SPEED: isize = flate::BEST_SPEED;
+pub const BEST_COMPRESSION: isize = flate::BEST_COMPRESSION;
+pub const DEFAULT_COMPRESSION: isize = flate::DEFAULT_COMPRESSION;
+pub const HUFFMAN_ONLY: isize = flate::HUFFMAN_ONLY;
+
+/// A Writer takes data written to it and writes the compressed
+/// form of that data in gzip format to an underlying writer (output).
+pub struct Writer<Output: std::io::Write> {
+    pub header: Header, // written at first call to write, flush, or close
+    level: isize,
+    wrote_header: bool,
+    compressor: flate::Writer<Output>,
+    digest: u32, // CRC-32, IEEE polynomial (section 8)
+    size: u32,   // Uncompressed size (section 2.3.1)
+    closed: bool,
+    err: Option<std::io::Error>,
+}
+
+pub(super) fn default_header() -> Header {
+    Header {
+        comment: None,
+        extra: None,
+        mod_time: time::Time::default(),
+        name: None,
+        os: 255, // unknown
+    }
+}
+
+impl<Output: std::io::Write> Writer<Output> {
+    /// new returns a new Writer.
+    /// Writes to the returned writer are compressed and written to w.
+    ///
+    /// It is the caller's responsibility to call close on the Writer when done.
+    /// Writes may be buffered and not flushed until close.
+    ///
+    /// Callers that wish to set the fields in Writer.header must do so before
+    /// the first call to write, flush, or close.
+    pub fn new(w: Output) -> Self {
+        Self::new_level(w, DEFAULT_COMPRESSION).unwrap()
+    }
+
+    /// new_level is like new but specifies the compression level instead
+    /// of assuming DEFAULT_COMPRESSION.
+    ///
+    /// The compression level can be DEFAULT_COMPRESSION, NO_COMPRESSION, HUFFMAN_ONLY
+    /// or any integer value between BEST_SPEED and BEST_COMPRESSION inclusive.
+    /// The error returned will be nil if the level is valid.
+    pub fn new_level(w: Output, level: isize) -> std::io::Result<Self> {
+        if !(HUFFMAN_ONLY..=BEST_COMPRESSION).contains(&level) {
+            return Err(errors::new_stdio_other_error(format!(
+                "gzip: invalid compression level: {}",
+                level
+            )));
+        }
+        Ok(Self {
+            header: default_header(),
+            level,
+            wrote_header: false,
+            compressor: flate::Writer::new(w, level)?,
+            digest: 0,
+            size: 0,
+            closed: false,
+            err: None,
+        })
+    }
+
+    /// reset discards the Writer's state and makes it equivalent to the
+    /// result of its original state from new or new_level, but
+    /// writing to w instead. This permits reusing a Writer rather than
+    /// allocating a new one.
+    pub fn reset(&mut self, w: Output) {
+        self.compressor.reset(w);
+        self.header = default_header();
+        self.wrote_header = false;
+        self.digest = 0;
+        self.size = 0;
+        self.closed = false;
+        self.err = None;
+    }
+
+    /// get_ref returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Output {
+        self.compressor.get_ref()
+    }
+
+    /// get_mut returns a mutable reference to the underlying writer.
+    /// Writing to it directly corrupts the compressed stream.
+    pub fn get_mut(&mut self) -> &mut Output {
+        self.compressor.get_mut()
+    }
+
+    /// into_inner returns the underlying writer. close must be called before
+    /// to write the rest of the compressed data and the GZIP footer.
+    pub fn into_inner(self) -> Output {
+        self.compressor.into_inner()
+    }
+
+    /// write_header writes the GZIP header lazily, according to section 2.3.1.
+    fn write_header(&mut self) -> std::io::Result<()> {
+        if self.wrote_header {
+            return Ok(());
+        }
+        self.wrote_header = true;
+        let res = header_bytes(&self.header, self.level)
+            .and_then(|hdr| self.compressor.output().write_all(&hdr));
+        if let Err(err) = res {
+            self.err = Some(errors::copy_stdio_error(&err));
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// close closes the Writer by flushing any unwritten data to the underlying
+    /// writer and writing the GZIP footer.
+    /// It does not close the underlying writer.
+    pub fn close(&mut self) -> std::io::Result<()> {
+        if let Some(err) = &self.err {
+            return Err(errors::copy_stdio_error(err));
+        }
+        if self.closed {
+            return Ok(());
+        }
+        self.closed = true;
+        self.write_header()?;
+        if let Err(err) = self.compressor.close() {
+            self.err = Some(errors::copy_stdio_error(&err));
+            return Err(err);
+        }
+        let mut buf = [0; 8];
+        LITTLE_ENDIAN.put_uint32(&mut buf[..4], self.digest);
+        LITTLE_ENDIAN.put_uint32(&mut buf[4..8], self.size);
+        if let Err(err) = self.compressor.output().write_all(&buf) {
+            self.err = Some(errors::copy_stdio_error(&err));
+            return Err(err);
+        }
+        Ok(())
+    }
+}
+
+impl<Output: std::io::Write> std::io::Write for Writer<Output> {
+    /// write writes a compressed form of p to the underlying writer. The
+    /// compressed bytes are not necessarily flushed until the Writer is closed.
+    fn write(&mut self, p: &[u8]) -> std::io::Result<usize> {
+        if let Some(err) = &self.err {
+            return Err(errors::copy_stdio_error(err));
+        }
+        self.write_header()?;
+        self.size = self.size.wrapping_add(p.len() as u32);
+        self.digest = crc32::update(self.digest, &crc32::IEEE_TABLE, p);
+        match self.compressor.write(p) {
+            Ok(n) => Ok(n),
+            Err(err) => {
+                self.err = Some(errors::copy_stdio_error(&err));
+                Err(err)
+            }
+        }
+    }
+
+    /// flush flushes any pending compressed data to the underlying writer.
+    ///
+    /// It is useful mainly in compressed network protocols, to ensure that
+    /// a remote reader has enough data to reconstruct a packet. Flush does
+    /// not return until the data has been written. If the underlying
+    /// writer returns an error, Flush returns that error.
+    ///
+    /// In the terminology of the zlib library, Flush is equivalent to Z_SYNC_FLUSH.
+    fn flush(&mut self) -> std::io::Result<()> {
+        if let Some(err) = &self.err {
+            return Err(errors::copy_stdio_error(err));
+        }
+        if self.closed {
+            return Ok(());
+        }
+        self.write_header()?;
+        if let Err(err) = self.compressor.flush() {
+            self.err = Some(errors::copy_stdio_error(&err));
+            return Err(err);
+        }
+        Ok(())
+    }
+}
+
+/// header_bytes returns the GZIP header according to section 2.3.1.
+pub(super) fn header_bytes(header: &Header, level: isize) -> std::io::Result<Vec<u8>> {
+    let mut hdr = vec![GZIP_ID1, GZIP_ID2, GZIP_DEFLATE, 0, 0, 0, 0, 0, 0, 0];
+    let name = header.name.as_deref().unwrap_or_default();
+    let comment = header.comment.as_deref().unwrap_or_default();
+    if header.extra.is_some() {
+        hdr[3] |= 0x04;
+    }
+    if !name.is_empty() {
+        hdr[3] |= 0x08;
+    }
+    if !comment.is_empty() {
+        hdr[3] |= 0x10;
+    }
+    if header.mod_time.unix() > 0 {
+        // Section 2.3.1, the zero value for MTIME means that the
+        // modified time is not set.
+        LITTLE_ENDIAN.put_uint32(&mut hdr[4..8], header.mod_time.unix() as u32);
+    }
+    if level == BEST_COMPRESSION {
+        hdr[8] = 2;
+    } else if level == BEST_SPEED {
+        hdr[8] = 4;
+    }
+    hdr[9] = header.os;
+    if let Some(extra) = &header.extra {
+        append_bytes(&mut hdr, extra)?;
+    }
+    if !name.is_empty() {
+        append_string(&mut hdr, name)?;
+    }
+    if !comment.is_empty() {
+        append_string(&mut hdr, comment)?;
+    }
+    Ok(hdr)
+}
+
+/// append_bytes appends a length-prefixed byte slice to dst.
+fn append_bytes(dst: &mut Vec<u8>, b: &[u8]) -> std::io::Result<()> {
+    if b.len() > 0xffff {
+        return Err(errors::new_stdio_other_error(
+            "gzip.Write: Extra data is too large".to_string(),
+        ));
+    }
+    let mut buf = [0; 2];
+    LITTLE_ENDIAN.put_uint16(&mut buf, b.len() as u16);
+    dst.extend_from_slice(&buf);
+    dst.extend_from_slice(b);
+    Ok(())
+}
+
+/// append_string appends a UTF-8 string s in GZIP's format to dst.
+/// GZIP (RFC 1952) specifies that strings are NUL-terminated ISO 8859-1 (Latin-1).
+pub(super) fn append_string(dst: &mut Vec<u8>, s: &str) -> std::io::Result<()> {
+    // GZIP stores Latin-1 strings; error if non-Latin-1; convert if non-ASCII.
+    for v in s.chars() {
+        if v == '\0' || v > '\u{ff}' {
+            return Err(errors::new_stdio_other_error(
+                "gzip.Write: non-Latin-1 header string".to_string(),
+            ));
+        }
+    }
+    dst.extend(s.chars().map(|v| v as u8));
+    // GZIP strings are NUL-terminated.
+    dst.push(0);
+    Ok(())
+}
@@ -1,275 +1,298 @@
-// // Copyright 2010 The Go Authors. All rights reserved.
-// // Use of this source code is governed by a BSD-style
-// // license that can be found in the LICENSE file.
-
-// package gzip
-
-// import (
-// 	"bufio"
-// 	"bytes"
-// 	"io"
-// 	"reflect"
-// 	"testing"
-// 	"time"
-// )
-
-// // TestEmpty tests that an empty payload still forms a valid GZIP stream.
-// func TestEmpty(t *testing.T) {
-// 	buf := new(bytes.Buffer)
-
-// 	if err := NewWriter(buf).Close(); err != nil {
-// 		t.Fatalf("Writer.Close: %v", err)
-// 	}
-
-// 	r, err := NewReader(buf)
-// 	if err != nil {
-// 		t.Fatalf("NewReader: %v", err)
-// 	}
-// 	if want := (Header{OS: 255}); !reflect.DeepEqual(r.Header, want) {
-// 		t.Errorf("Header mismatch:\ngot  %#v\nwant %#v", r.Header, want)
-// 	}
-// 	b, err := io.ReadAll(r)
-// 	if err != nil {
-// 		t.Fatalf("ReadAll: %v", err)
-// 	}
-// 	if len(b) != 0 {
-// 		t.Fatalf("got %d bytes, want 0", len(b))
-// 	}
-// 	if err := r.Close(); err != nil {
-// 		t.Fatalf("Reader.Close: %v", err)
-// 	}
-// }
-
-// // TestRoundTrip tests that gzipping and then gunzipping is the identity
-// // function.
-// func TestRoundTrip(t *testing.T) {
-// 	buf := new(bytes.Buffer)
-
-// 	w := NewWriter(buf)
-// 	w.Comment = "comment"
-// 	w.Extra = []byte("extra")
-// 	w.ModTime = time.Unix(1e8, 0)
-// 	w.Name = "name"
-// 	if _, err := w.Write([]byte("payload")); err != nil {
-// 		t.Fatalf("Write: %v", err)
-// 	}
-// 	if err := w.Close(); err != nil {
-// 		t.Fatalf("Writer.Close: %v", err)
-// 	}
-
-// 	r, err := NewReader(buf)
-// 	if err != nil {
-// 		t.Fatalf("NewReader: %v", err)
-// 	}
-// 	b, err := io.ReadAll(r)
-// 	if err != nil {
-// 		t.Fatalf("ReadAll: %v", err)
-// 	}
-// 	if string(b) != "payload" {
-// 		t.Fatalf("payload is %q, want %q", string(b), "payload")
-// 	}
-// 	if r.Comment != "comment" {
-// 		t.Fatalf("comment is %q, want %q", r.Comment, "comment")
-// 	}
-// 	if string(r.Extra) != "extra" {
-// 		t.Fatalf("extra is %q, want %q", r.Extra, "extra")
-// 	}
-// 	if r.ModTime.Unix() != 1e8 {
-// 		t.Fatalf("mtime is %d, want %d", r.ModTime.Unix(), uint32(1e8))
-// 	}
-// 	if r.Name != "name" {
-// 		t.Fatalf("name is %q, want %q", r.Name, "name")
-// 	}
-// 	if err := r.Close(); err != nil {
-// 		t.Fatalf("Reader.Close: %v", err)
-// 	}
-// }
-
-// // TestLatin1 tests the internal functions for converting to and from Latin-1.
-// func TestLatin1(t *testing.T) {
-// 	latin1 := []byte{0xc4, 'u', 0xdf, 'e', 'r', 'u', 'n', 'g', 0}
-// 	utf8 := "Äußerung"
-// 	z := Reader{r: bufio.NewReader(bytes.NewReader(latin1))}
-// 	s, err := z.readString()
-// 	if err != nil {
-// 		t.Fatalf("readString: %v", err)
-// 	}
-// 	if s != utf8 {
-// 		t.Fatalf("read latin-1: got %q, want %q", s, utf8)
-// 	}
-
-// 	buf := bytes.NewBuffer(make([]byte, 0, len(latin1)))
-// 	c := Writer{w: buf}
-// 	if err = c.writeString(utf8); err != nil {
-// 		t.Fatalf("writeString: %v", err)
-// 	}
-// 	s = buf.String()
-// 	if s != string(latin1) {
-// 		t.Fatalf("write utf-8: got %q, want %q", s, string(latin1))
-// 	}
-// }
-
-// // TestLatin1RoundTrip tests that metadata that is representable in Latin-1
-// // survives a round trip.
-// func TestLatin1RoundTrip(t *testing.T) {
-// 	testCases := []struct {
-// 		name string
-// 		ok   bool
-// 	}{
-// 		{"", true},
-// 		{"ASCII is OK", true},
-// 		{"unless it contains a NUL\x00", false},
-// 		{"no matter where \x00 occurs", false},
-// 		{"\x00\x00\x00", false},
-// 		{"Látin-1 also passes (U+00E1)", true},
-// 		{"but LĀtin Extended-A (U+0100) does not", false},
-// 		{"neither does 日本語", false},
-// 		{"invalid UTF-8 also \xffails", false},
-// 		{"\x00 as does Látin-1 with NUL", false},
-// 	}
-// 	for _, tc := range testCases {
-// 		buf := new(bytes.Buffer)
-
-// 		w := NewWriter(buf)
-// 		w.Name = tc.name
-// 		err := w.Close()
-// 		if (err == nil) != tc.ok {
-// 			t.Errorf("Writer.Close: name = %q, err = %v", tc.name, err)
-// 			continue
-// 		}
-// 		if !tc.ok {
-// 			continue
-// 		}
-
-// 		r, err := NewReader(buf)
-// 		if err != nil {
-// 			t.Errorf("NewReader: %v", err)
-// 			continue
-// 		}
-// 		_, err = io.ReadAll(r)
-// 		if err != nil {
-// 			t.Errorf("ReadAll: %v", err)
-// 			continue
-// 		}
-// 		if r.Name != tc.name {
-// 			t.Errorf("name is %q, want %q", r.Name, tc.name)
-// 			continue
-// 		}
-// 		if err := r.Close(); err != nil {
-// 			t.Errorf("Reader.Close: %v", err)
-// 			continue
-// 		}
-// 	}
-// }
-
-// func TestWriterFlush(t *testing.T) {
-// 	buf := new(bytes.Buffer)
-
-// 	w := NewWriter(buf)
-// 	w.Comment = "comment"
-// 	w.Extra = []byte("extra")
-// 	w.ModTime = time.Unix(1e8, 0)
-// 	w.Name = "name"
-
-// 	n0 := buf.Len()
-// 	if n0 != 0 {
-// 		t.Fatalf("buffer size = %d before writes; want 0", n0)
-// 	}
-
-// 	if err := w.Flush(); err != nil {
-// 		t.Fatal(err)
-// 	}
-
-// 	n1 := buf.Len()
-// 	if n1 == 0 {
-// 		t.Fatal("no data after first flush")
-// 	}
-
-// 	w.Write([]byte("x"))
-
-// 	n2 := buf.Len()
-// 	if n1 != n2 {
-// 		t.Fatalf("after writing a single byte, size changed from %d to %d; want no change", n1, n2)
-// 	}
-
-// 	if err := w.Flush(); err != nil {
-// 		t.Fatal(err)
-// 	}
-
-// 	n3 := buf.Len()
-// 	if n2 == n3 {
-// 		t.Fatal("Flush didn't flush any data")
-// 	}
-// }
-
-// // Multiple gzip files concatenated form a valid gzip file.
-// func TestConcat(t *testing.T) {
-// 	var buf bytes.Buffer
-// 	w := NewWriter(&buf)
-// 	w.Write([]byte("hello "))
-// 	w.Close()
-// 	w = NewWriter(&buf)
-// 	w.Write([]byte("world\n"))
-// 	w.Close()
-
-// 	r, err := NewReader(&buf)
-// 	if err != nil {
-// 		t.Fatal(err)
-// 	}
-// 	data, err := io.ReadAll(r)
-// 	if string(data) != "hello world\n" || err != nil {
-// 		t.Fatalf("ReadAll = %q, %v, want %q, nil", data, err, "hello world")
-// 	}
-// }
-
-// func TestWriterReset(t *testing.T) {
-// 	buf := new(bytes.Buffer)
-// 	buf2 := new(bytes.Buffer)
-// 	z := NewWriter(buf)
-// 	msg := []byte("hello world")
-// 	z.Write(msg)
-// 	z.Close()
-// 	z.Reset(buf2)
-// 	z.Write(msg)
-// 	z.Close()
-// 	if buf.String() != buf2.String() {
-// 		t.Errorf("buf2 %q != original buf of %q", buf2.String(), buf.String())
-// 	}
-// }
-
-// type limitedWriter struct {
-// 	N int
-// }
-
-// func (l *limitedWriter) Write(p []byte) (n int, err error) {
-// 	if n := l.N; n < len(p) {
-// 		l.N = 0
-// 		return n, io.ErrShortWrite
-// 	}
-// 	l.N -= len(p)
-// 	return len(p), nil
-// }
-
-// // Write should never return more bytes than the input slice.
-// func TestLimitedWrite(t *testing.T) {
-// 	msg := []byte("a")
-
-// 	for lim := 2; lim < 20; lim++ {
-// 		z := NewWriter(&limitedWriter{lim})
-// 		if n, _ := z.Write(msg); n > len(msg) {
-// 			t.Errorf("Write() = %d, want %d or less", n, len(msg))
-// 		}
-
-// 		z.Reset(&limitedWriter{lim})
-// 		z.Header = Header{
-// 			Comment: "comment",
-// 			Extra:   []byte("extra"),
-// 			ModTime: time.Now(),
-// 			Name:    "name",
-// 			OS:      1,
-// 		}
-// 		if n, _ := z.Write(msg); n > len(msg) {
-// 			t.Errorf("Write() = %d, want %d or less", n, len(msg))
-// 		}
-// 	}
-// }
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2010 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::gzip::append_string;
+use super::{Header, Reader, Writer};
+use crate::compress::zlib;
+use crate::time;
+use std::io::{Read, Write};
+
+/// test_empty tests that an empty payload still forms a valid GZIP stream.
+#[test]
+fn test_empty() {
+    let mut buf = Vec::new();
+
+    Writer::new(&mut buf).close().unwrap();
+
+    let mut input = buf.as_slice();
+    let mut r = Reader::new(&mut input).unwrap();
+    let header = r.header.as_ref().unwrap();
+    assert_eq!(header.os, 255, "Header mismatch: got {:?}", header);
+    assert!(header.name.is_none(), "Header mismatch: got {:?}", header);
+    assert!(
+        header.comment.is_none(),
+        "Header mismatch: got {:?}",
+        header
+    );
+    assert!(header.extra.is_none(), "Header mismatch: got {:?}", header);
+    assert_eq!(header.mod_time.unix(), time::Time::default().unix());
+    let mut b = Vec::new();
+    r.read_to_end(&mut b).unwrap();
+    assert_eq!(b.len(), 0, "got {} bytes, want 0", b.len());
+    r.close().unwrap();
+}
+
+/// test_round_trip tests that gzipping and then gunzipping is the identity
+/// function.
+#[test]
+fn test_round_trip() {
+    let mut buf = Vec::new();
+
+    let mut w = Writer::new(&mut buf);
+    w.header.comment = Some("comment".to_string());
+    w.header.extra = Some(b"extra".to_vec());
+    w.header.mod_time = time::unix(100000000, 0);
+    w.header.name = Some("name".to_string());
+    w.write_all(b"payload").unwrap();
+    w.close().unwrap();
+
+    let mut input = buf.as_slice();
+    let mut r = Reader::new(&mut input).unwrap();
+    let header = r.header.as_ref().unwrap();
+    assert_eq!(header.comment.as_deref(), Some("comment"));
+    assert_eq!(header.extra.as_deref(), Some(b"extra".as_slice()));
+    assert_eq!(header.mod_time.unix(), 100000000);
+    assert_eq!(header.name.as_deref(), Some("name"));
+    let mut b = Vec::new();
+    r.read_to_end(&mut b).unwrap();
+    assert_eq!(b, b"payload");
+    r.close().unwrap();
+}
+
+/// test_latin1 tests the internal function for converting to Latin-1.
+#[test]
+fn test_latin1() {
+    let latin1 = &[0xc4, b'u', 0xdf, b'e', b'r', b'u', b'n', b'g', 0];
+    let utf8 = "Äußerung";
+
+    let mut buf = Vec::new();
+    append_string(&mut buf, utf8).unwrap();
+    assert_eq!(buf, latin1, "write utf-8: got {:?}, want {:?}", buf, latin1);
+}
+
+/// test_latin1_round_trip tests that metadata that is representable in Latin-1
+/// survives a round trip.
+#[test]
+fn test_latin1_round_trip() {
+    let test_cases = [
+        ("", true),
+        ("ASCII is OK", true),
+        ("unless it contains a NUL\x00", false),
+        ("no matter where \x00 occurs", false),
+        ("\x00\x00\x00", false),
+        ("Látin-1 also passes (U+00E1)", true),
+        ("but LĀtin Extended-A (U+0100) does not", false),
+        ("neither does 日本語", false),
+        ("\x00 as does Látin-1 with NUL", false),
+    ];
+    for (name, ok) in test_cases {
+        let mut buf = Vec::new();
+
+        let mut w = Writer::new(&mut buf);
+        w.header.name = Some(name.to_string());
+        let res = w.close();
+        assert_eq!(
+            res.is_ok(),
+            ok,
+            "Writer.close: name = {:?}, err = {:?}",
+            name,
+            res
+        );
+        if !ok {
+            continue;
+        }
+
+        let mut input = buf.as_slice();
+        let mut r = Reader::new(&mut input).unwrap();
+        let got = r.header.as_ref().unwrap().name.clone().unwrap_or_default();
+        assert_eq!(got, name, "name is {:?}, want {:?}", got, name);
+        let mut b = Vec::new();
+        r.read_to_end(&mut b).unwrap();
+        r.close().unwrap();
+    }
+}
+
+#[test]
+fn test_writer_flush() {
+    let mut buf = Vec::new();
+
+    let mut w = Writer::new(&mut buf);
+    w.header.comment = Some("comment".to_string());
+    w.header.extra = Some(b"extra".to_vec());
+    w.header.mod_time = time::unix(100000000, 0);
+    w.header.name = Some("name".to_string());
+
+    let n0 = w.get_ref().len();
+    assert_eq!(n0, 0, "buffer size = {} before writes; want 0", n0);
+
+    w.flush().unwrap();
+
+    let n1 = w.get_ref().len();
+    assert!(n1 != 0, "no data after first flush");
+
+    w.write_all(b"x").unwrap();
+
+    let n2 = w.get_ref().len();
+    assert_eq!(
+        n1, n2,
+        "after writing a single byte, size changed from {} to {}; want no change",
+        n1, n2
+    );
+
+    w.flush().unwrap();
+
+    let n3 = w.get_ref().len();
+    assert!(n2 != n3, "Flush didn't flush any data");
+}
+
+/// Multiple gzip files concatenated form a valid gzip file.
+#[test]
+fn test_concat() {
+    let mut buf = Vec::new();
+    let mut w = Writer::new(&mut buf);
+    w.write_all(b"hello ").unwrap();
+    w.close().unwrap();
+    let mut w = Writer::new(&mut buf);
+    w.write_all(b"world\n").unwrap();
+    w.close().unwrap();
+
+    let mut input = buf.as_slice();
+    let mut r = Reader::new(&mut input).unwrap();
+    let mut data = String::new();
+    r.read_to_string(&mut data).unwrap();
+    assert_eq!(data, "hello world\n");
+}
+
+#[test]
+fn test_writer_reset() {
+    let mut buf = Vec::new();
+    let mut buf2 = Vec::new();
+    let msg = b"hello world";
+    {
+        let mut z = Writer::new(&mut buf);
+        z.write_all(msg).unwrap();
+        z.close().unwrap();
+        z.reset(&mut buf2);
+        z.write_all(msg).unwrap();
+        z.close().unwrap();
+    }
+    assert_eq!(buf, buf2, "buf2 {:?} != original buf of {:?}", buf2, buf);
+}
+
+struct LimitedWriter {
+    n: usize,
+}
+
+impl std::io::Write for LimitedWriter {
+    fn write(&mut self, p: &[u8]) -> std::io::Result<usize> {
+        if self.n < p.len() {
+            let n = self.n;
+            self.n = 0;
+            return Ok(n);
+        }
+        self.n -= p.len();
+        Ok(p.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// write should never return more bytes than the input slice.
+#[test]
+fn test_limited_write() {
+    let msg = b"a";
+
+    for lim in 2..20 {
+        let mut lw = LimitedWriter { n: lim };
+        let mut lw2 = LimitedWriter { n: lim };
+        let mut z = Writer::new(&mut lw);
+        if let Ok(n) = z.write(msg) {
+            assert!(
+                n <= msg.len(),
+                "write() = {}, want {} or less",
+                n,
+                msg.len()
+            );
+        }
+
+        z.reset(&mut lw2);
+        z.header = Header {
+            comment: Some("comment".to_string()),
+            extra: Some(b"extra".to_vec()),
+            mod_time: time::now(),
+            name: Some("name".to_string()),
+            os: 1,
+        };
+        if let Ok(n) = z.write(msg) {
+            assert!(
+                n <= msg.len(),
+                "write() = {}, want {} or less",
+                n,
+                msg.len()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_writer_levels() {
+    let msg = b"hello hello hello hello world world world world";
+    for level in [
+        super::NO_COMPRESSION,
+        super::BEST_SPEED,
+        5,
+        super::BEST_COMPRESSION,
+        super::DEFAULT_COMPRESSION,
+        super::HUFFMAN_ONLY,
+    ] {
+        let mut buf = Vec::new();
+        let mut w = Writer::new_level(&mut buf, level).unwrap();
+        w.write_all(msg).unwrap();
+        w.close().unwrap();
+
+        let mut input = buf.as_slice();
+        let mut r = Reader::new(&mut input).unwrap();
+        let mut got = Vec::new();
+        r.read_to_end(&mut got).unwrap();
+        assert_eq!(got, msg, "level {}", level);
+    }
+
+    let mut buf = Vec::new();
+    assert!(Writer::new_level(&mut buf, 10).is_err());
+}
+
+/// test_owned_adapters tests that readers and writers owning their streams
+/// compose like the std adapters.
+#[test]
+fn test_owned_adapters() {
+    let data = b"hello, world\n".repeat(100);
+    let mut w = Writer::new(zlib::Writer::new(Vec::new()));
+    w.write_all(&data).unwrap();
+    w.close().unwrap();
+    let mut zw = w.into_inner();
+    zw.close().unwrap();
+    let compressed = zw.into_inner();
+
+    let zr = zlib::Reader::new(compressed.as_slice()).unwrap();
+    let mut r: Box<dyn Read> = Box::new(Reader::new(std::io::BufReader::new(zr)).unwrap());
+    let mut output = Vec::new();
+    r.read_to_end(&mut output).unwrap();
+    assert_eq!(output, data);
+
+    // Without multistream, the reader stops right after the gzip stream.
+    let mut w = Writer::new(Vec::new());
+    w.write_all(&data).unwrap();
+    w.close().unwrap();
+    let mut compressed = w.into_inner();
+    compressed.extend_from_slice(b"trailer");
+    let mut r = Reader::new(compressed.as_slice()).unwrap();
+    r.multistream(false);
+    let mut output = Vec::new();
+    r.read_to_end(&mut output).unwrap();
+    assert_eq!(output, data);
+    assert_eq!(r.into_inner(), b"trailer");
+}
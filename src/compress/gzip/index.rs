@@ -0,0 +1,62 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::gunzip::{get_err_checksum, ReadState};
+use crate::compress::flate;
+use crate::encoding::binary::{ByteOrder, LITTLE_ENDIAN};
+use crate::hash::crc32;
+
+/// build_index decompresses the first member of the gzip file read from r,
+/// verifies its checksum and size, and returns the index of its DEFLATE
+/// stream. The checkpoints are at least span uncompressed bytes apart.
+///
+/// flate::Index::open on the same file returns a reader of the
+/// uncompressed data starting at any offset. The reader stops at the end
+/// of the member and doesn't verify the checksum.
+pub fn build_index<Input: std::io::BufRead>(r: Input, span: u64) -> std::io::Result<flate::Index> {
+    let mut cr = CountingReader { r, n: 0 };
+    if ReadState::new().read_header(&mut cr)?.is_none() {
+        return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+    }
+
+    let mut f = flate::Reader::new(cr.r);
+    let mut digest = 0;
+    let index = flate::Index::build_from(&mut f, cr.n, span, |b| {
+        digest = crc32::update(digest, &crc32::IEEE_TABLE, b);
+    })?;
+
+    let mut buf = [0; 8];
+    f.input_reader().read_exact(&mut buf)?;
+    if LITTLE_ENDIAN.uint32(&buf[..4]) != digest
+        || LITTLE_ENDIAN.uint32(&buf[4..8]) != index.size as u32
+    {
+        return Err(get_err_checksum());
+    }
+    Ok(index)
+}
+
+/// CountingReader counts the bytes read from r.
+struct CountingReader<R: std::io::BufRead> {
+    r: R,
+    n: u64,
+}
+
+impl<R: std::io::BufRead> std::io::Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.r.read(buf)?;
+        self.n += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: std::io::BufRead> std::io::BufRead for CountingReader<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.r.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.n += amt as u64;
+        self.r.consume(amt)
+    }
+}
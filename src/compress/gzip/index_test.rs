@@ -0,0 +1,52 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::{build_index, Writer, ERR_CHECKSUM_MSG};
+use crate::compress::flate;
+use std::io::{Read, Write};
+
+fn gzip(data: &[u8], name: &str) -> Vec<u8> {
+    let mut compressed = Vec::new();
+    let mut w = Writer::new(&mut compressed);
+    w.header.name = Some(name.to_string());
+    w.write_all(data).unwrap();
+    w.close().unwrap();
+    compressed
+}
+
+#[test]
+fn test_build_index() {
+    let data = flate::index_test::test_data(500_000);
+    let compressed = gzip(&data, "data.txt");
+    let index = build_index(compressed.as_slice(), 64 * 1024).unwrap();
+    assert_eq!(index.offset, 10 + "data.txt".len() as u64 + 1);
+    assert_eq!(index.size, data.len() as u64);
+    assert!(index.checkpoints.len() > 2);
+
+    for offset in [0, 1, 99_999, 250_000, data.len() - 10, data.len()] {
+        let mut r = index
+            .open(std::io::Cursor::new(&compressed), offset as u64)
+            .unwrap();
+        let mut output = Vec::new();
+        r.read_to_end(&mut output).unwrap();
+        assert!(
+            output == data[offset..],
+            "data mismatch at offset {}",
+            offset
+        );
+    }
+}
+
+#[test]
+fn test_build_index_checksum() {
+    let data = flate::index_test::test_data(10_000);
+    let mut compressed = gzip(&data, "");
+    let n = compressed.len();
+    compressed[n - 8] ^= 1;
+    let err = build_index(compressed.as_slice(), 1000).unwrap_err();
+    assert_eq!(err.to_string(), ERR_CHECKSUM_MSG);
+
+    let err = build_index(&compressed[..0], 1000).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+}
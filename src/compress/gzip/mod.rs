@@ -6,9 +6,27 @@
 //! Package gzip implements reading and writing of gzip format compressed files,
 //! as specified in RFC 1952.
 
+mod context;
 mod gunzip;
+mod gzip;
+mod index;
+mod parallel;
 
-pub use gunzip::{Header, Reader, ERR_CHECKSUM_MSG, ERR_INVALID_HEADER};
+pub use context::{compress, compress_bound, decompress, Context};
+pub use gunzip::{Error, Header, Reader, ERR_CHECKSUM_MSG, ERR_INVALID_HEADER};
+pub use gzip::{
+    Writer, BEST_COMPRESSION, BEST_SPEED, DEFAULT_COMPRESSION, HUFFMAN_ONLY, NO_COMPRESSION,
+};
+pub use index::build_index;
+pub use parallel::{ParallelWriter, DEFAULT_BLOCK_SIZE};
 
+#[cfg(test)]
+mod context_test;
 #[cfg(test)]
 mod gunzip_test;
+#[cfg(test)]
+mod gzip_test;
+#[cfg(test)]
+mod index_test;
+#[cfg(test)]
+mod parallel_test;
@@ -0,0 +1,471 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! Parallel gzip compression.
+//!
+//! The input is split into blocks which are compressed independently on
+//! a pool of worker threads. Each block is primed with the last 32 KiB of
+//! the data preceding it as a preset dictionary, and all blocks except
+//! the last one end with a sync flush, so the compressed blocks can be
+//! concatenated into a single deflate stream. The result is a regular
+//! single-member gzip file.
+
+use super::gzip::{default_header, header_bytes};
+use super::{Header, BEST_COMPRESSION, DEFAULT_COMPRESSION, HUFFMAN_ONLY};
+use crate::compress::flate;
+use crate::encoding::binary::{ByteOrder, LITTLE_ENDIAN};
+use crate::errors;
+use crate::hash::crc32;
+use std::collections::VecDeque;
+use std::io::Write;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+/// DEFAULT_BLOCK_SIZE is the default size of the uncompressed blocks
+/// that are compressed in parallel.
+pub const DEFAULT_BLOCK_SIZE: usize = 1 << 20;
+
+/// The size of the deflate window and so the maximum useful dictionary size.
+const MAX_DICT_SIZE: usize = 1 << 15;
+
+/// A ParallelWriter is like Writer, but compresses the data on
+/// multiple threads.
+///
+/// The data written to a ParallelWriter is split into blocks of block_size bytes.
+/// Up to `blocks` blocks are compressed concurrently. Because every block
+/// is primed with the data preceding it, the compression ratio is close to
+/// what Writer achieves. The output is a single gzip member which can be
+/// read by any gzip reader, including Reader.
+///
+/// Data is written to the underlying writer only when complete blocks are
+/// compressed, or when flush or close is called.
+pub struct ParallelWriter<Output: std::io::Write> {
+    pub header: Header, // written at first call to write, flush, or close
+    w: Output,
+    level: isize,
+    block_size: usize,
+    blocks: usize,
+    pool: Option<Pool>,
+    buf: Vec<u8>,  // uncompressed data of the current block
+    dict: Vec<u8>, // up to the last 32 KiB of the data before the current block
+    pending: VecDeque<mpsc::Receiver<std::io::Result<CompressedBlock>>>,
+    digest: u32, // CRC-32, IEEE polynomial (section 8)
+    size: u32,   // Uncompressed size (section 2.3.1)
+    wrote_header: bool,
+    closed: bool,
+    err: Option<std::io::Error>,
+}
+
+impl<Output: std::io::Write> ParallelWriter<Output> {
+    /// new returns a new ParallelWriter using DEFAULT_COMPRESSION,
+    /// DEFAULT_BLOCK_SIZE and as many concurrent blocks as there are CPUs.
+    /// Writes to the returned writer are compressed and written to w.
+    ///
+    /// It is the caller's responsibility to call close on the ParallelWriter when done.
+    ///
+    /// Callers that wish to set the fields in ParallelWriter.header must do so before
+    /// the first call to write, flush, or close.
+    pub fn new(w: Output) -> Self {
+        Self::new_level(w, DEFAULT_COMPRESSION).unwrap()
+    }
+
+    /// new_level is like new but specifies the compression level instead
+    /// of assuming DEFAULT_COMPRESSION.
+    ///
+    /// The compression level can be DEFAULT_COMPRESSION, NO_COMPRESSION, HUFFMAN_ONLY
+    /// or any integer value between BEST_SPEED and BEST_COMPRESSION inclusive.
+    pub fn new_level(w: Output, level: isize) -> std::io::Result<Self> {
+        if !(HUFFMAN_ONLY..=BEST_COMPRESSION).contains(&level) {
+            return Err(errors::new_stdio_other_error(format!(
+                "gzip: invalid compression level: {}",
+                level
+            )));
+        }
+        let blocks = thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        Ok(Self {
+            header: default_header(),
+            w,
+            level,
+            block_size: DEFAULT_BLOCK_SIZE,
+            blocks,
+            pool: None,
+            buf: Vec::new(),
+            dict: Vec::new(),
+            pending: VecDeque::new(),
+            digest: 0,
+            size: 0,
+            wrote_header: false,
+            closed: false,
+            err: None,
+        })
+    }
+
+    /// set_concurrency sets the size of the blocks the input is split into
+    /// and the number of blocks that are compressed at the same time,
+    /// which is also the number of worker threads.
+    ///
+    /// It must be called before the first call to write, flush, or close.
+    pub fn set_concurrency(&mut self, block_size: usize, blocks: usize) -> std::io::Result<()> {
+        if block_size == 0 {
+            return Err(errors::new_stdio_other_error(
+                "gzip: block size must be positive".to_string(),
+            ));
+        }
+        if blocks == 0 {
+            return Err(errors::new_stdio_other_error(
+                "gzip: number of blocks must be positive".to_string(),
+            ));
+        }
+        if self.wrote_header {
+            return Err(errors::new_stdio_other_error(
+                "gzip: set_concurrency called after writing".to_string(),
+            ));
+        }
+        self.block_size = block_size;
+        self.blocks = blocks;
+        Ok(())
+    }
+
+    /// get_ref returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Output {
+        &self.w
+    }
+
+    /// get_mut returns a mutable reference to the underlying writer.
+    /// Writing to it directly corrupts the compressed stream.
+    pub fn get_mut(&mut self) -> &mut Output {
+        &mut self.w
+    }
+
+    /// into_inner returns the underlying writer. close must be called before
+    /// to write the rest of the compressed data and the GZIP footer.
+    pub fn into_inner(self) -> Output {
+        self.w
+    }
+
+    /// close closes the ParallelWriter by compressing and writing all the remaining
+    /// data to the underlying writer and writing the GZIP footer.
+    /// It does not close the underlying writer.
+    pub fn close(&mut self) -> std::io::Result<()> {
+        if let Some(err) = &self.err {
+            return Err(errors::copy_stdio_error(err));
+        }
+        if self.closed {
+            return Ok(());
+        }
+        self.closed = true;
+        let res = self.finish();
+        self.pool = None;
+        if let Err(err) = &res {
+            self.err = Some(errors::copy_stdio_error(err));
+        }
+        res
+    }
+
+    fn finish(&mut self) -> std::io::Result<()> {
+        self.write_header()?;
+        self.submit_block(true)?;
+        while !self.pending.is_empty() {
+            self.write_oldest_block()?;
+        }
+        let mut buf = [0; 8];
+        LITTLE_ENDIAN.put_uint32(&mut buf[..4], self.digest);
+        LITTLE_ENDIAN.put_uint32(&mut buf[4..8], self.size);
+        self.w.write_all(&buf)
+    }
+
+    /// write_header writes the GZIP header and starts the worker threads.
+    fn write_header(&mut self) -> std::io::Result<()> {
+        if self.wrote_header {
+            return Ok(());
+        }
+        self.wrote_header = true;
+        self.pool = Some(Pool::new(self.blocks));
+        self.buf.reserve(self.block_size);
+        let hdr = header_bytes(&self.header, self.level)?;
+        self.w.write_all(&hdr)
+    }
+
+    /// submit_block sends the current block to the worker threads.
+    /// If the maximum number of blocks is in flight, it waits for the oldest
+    /// one to complete and writes it out.
+    fn submit_block(&mut self, last: bool) -> std::io::Result<()> {
+        let data = std::mem::replace(&mut self.buf, Vec::with_capacity(self.block_size));
+        let dict = self.dict.clone();
+        if data.len() >= MAX_DICT_SIZE {
+            self.dict.clear();
+            self.dict
+                .extend_from_slice(&data[data.len() - MAX_DICT_SIZE..]);
+        } else {
+            self.dict.extend_from_slice(&data);
+            if self.dict.len() > MAX_DICT_SIZE {
+                self.dict.drain(..self.dict.len() - MAX_DICT_SIZE);
+            }
+        }
+
+        let (result, receiver) = mpsc::channel();
+        let job = Job {
+            data,
+            dict,
+            level: self.level,
+            last,
+            result,
+        };
+        self.pool.as_ref().unwrap().submit(job);
+        self.pending.push_back(receiver);
+
+        while self.pending.len() > self.blocks {
+            self.write_oldest_block()?;
+        }
+        Ok(())
+    }
+
+    /// write_oldest_block waits for the oldest block in flight to get
+    /// compressed and writes it to the underlying writer.
+    fn write_oldest_block(&mut self) -> std::io::Result<()> {
+        let receiver = self.pending.pop_front().unwrap();
+        let block = match receiver.recv() {
+            Ok(res) => res?,
+            Err(_) => {
+                return Err(errors::new_stdio_other_error(
+                    "gzip: compression thread terminated unexpectedly".to_string(),
+                ))
+            }
+        };
+        self.digest = crc32_combine(self.digest, block.digest, block.size as u64);
+        self.size = self.size.wrapping_add(block.size as u32);
+        self.w.write_all(&block.out)
+    }
+}
+
+impl<Output: std::io::Write> std::io::Write for ParallelWriter<Output> {
+    /// write writes a compressed form of p to the underlying writer. The
+    /// compressed bytes are not necessarily written until the ParallelWriter
+    /// is closed.
+    fn write(&mut self, p: &[u8]) -> std::io::Result<usize> {
+        if let Some(err) = &self.err {
+            return Err(errors::copy_stdio_error(err));
+        }
+        if self.closed {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::BrokenPipe,
+                "gzip: closed writer",
+            ));
+        }
+        let mut res = self.write_header();
+        let mut data = p;
+        while res.is_ok() && !data.is_empty() {
+            let n = (self.block_size - self.buf.len()).min(data.len());
+            self.buf.extend_from_slice(&data[..n]);
+            data = &data[n..];
+            if self.buf.len() == self.block_size {
+                res = self.submit_block(false);
+            }
+        }
+        if let Err(err) = res {
+            self.err = Some(errors::copy_stdio_error(&err));
+            return Err(err);
+        }
+        Ok(p.len())
+    }
+
+    /// flush compresses all the buffered data and writes it to the underlying
+    /// writer, which is flushed as well.
+    ///
+    /// Like Writer.flush, it is equivalent to Z_SYNC_FLUSH in the terminology
+    /// of the zlib library.
+    fn flush(&mut self) -> std::io::Result<()> {
+        if let Some(err) = &self.err {
+            return Err(errors::copy_stdio_error(err));
+        }
+        if self.closed {
+            return Ok(());
+        }
+        let mut res = self.write_header();
+        // A flush with nothing buffered only waits for the pending blocks;
+        // it doesn't emit another empty sync-flush block.
+        if res.is_ok() && !self.buf.is_empty() {
+            res = self.submit_block(false);
+        }
+        while res.is_ok() && !self.pending.is_empty() {
+            res = self.write_oldest_block();
+        }
+        if let Err(err) = res {
+            self.err = Some(errors::copy_stdio_error(&err));
+            return Err(err);
+        }
+        self.w.flush()
+    }
+}
+
+struct CompressedBlock {
+    out: Vec<u8>,
+    digest: u32,
+    size: usize,
+}
+
+struct Job {
+    data: Vec<u8>,
+    dict: Vec<u8>,
+    level: isize,
+    last: bool,
+    result: mpsc::Sender<std::io::Result<CompressedBlock>>,
+}
+
+impl Job {
+    fn run(self) {
+        let res = compress_block(&self.data, &self.dict, self.level, self.last).map(|out| {
+            CompressedBlock {
+                out,
+                digest: crc32::checksum_ieee(&self.data),
+                size: self.data.len(),
+            }
+        });
+        // the receiver is gone only if the writer was dropped
+        _ = self.result.send(res);
+    }
+}
+
+/// compress_block compresses data as a part of a bigger deflate stream.
+/// All blocks but the last one are terminated with a sync flush,
+/// so that the output stays byte-aligned.
+fn compress_block(data: &[u8], dict: &[u8], level: isize, last: bool) -> std::io::Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(data.len() / 2 + 64);
+    let mut fw = flate::Writer::new_dict(&mut out, level, dict)?;
+    fw.write_all(data)?;
+    if last {
+        fw.close()?;
+    } else {
+        fw.flush()?;
+    }
+    drop(fw);
+    Ok(out)
+}
+
+/// Pool is a set of worker threads compressing blocks.
+/// The threads exit when the Pool is dropped.
+struct Pool {
+    jobs: Option<mpsc::Sender<Job>>,
+    workers: Vec<thread::JoinHandle<()>>,
+}
+
+impl Pool {
+    fn new(size: usize) -> Self {
+        let (jobs, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..size)
+            .map(|_| {
+                let receiver = Arc::clone(&receiver);
+                thread::spawn(move || loop {
+                    let job = receiver.lock().unwrap().recv();
+                    match job {
+                        Ok(job) => job.run(),
+                        Err(_) => break,
+                    }
+                })
+            })
+            .collect();
+        Self {
+            jobs: Some(jobs),
+            workers,
+        }
+    }
+
+    fn submit(&self, job: Job) {
+        self.jobs.as_ref().unwrap().send(job).unwrap();
+    }
+}
+
+impl Drop for Pool {
+    fn drop(&mut self) {
+        // closing the channel makes the workers exit
+        self.jobs = None;
+        for worker in self.workers.drain(..) {
+            _ = worker.join();
+        }
+    }
+}
+
+const GF2_DIM: usize = 32; // dimension of GF(2) vectors (length of CRC)
+
+fn gf2_matrix_times(mat: &[u32; GF2_DIM], vec: u32) -> u32 {
+    let mut vec = vec;
+    let mut sum = 0;
+    let mut i = 0;
+    while vec != 0 {
+        if vec & 1 != 0 {
+            sum ^= mat[i];
+        }
+        vec >>= 1;
+        i += 1;
+    }
+    sum
+}
+
+fn gf2_matrix_square(square: &mut [u32; GF2_DIM], mat: &[u32; GF2_DIM]) {
+    for n in 0..GF2_DIM {
+        square[n] = gf2_matrix_times(mat, mat[n]);
+    }
+}
+
+/// crc32_combine returns the IEEE CRC-32 of the concatenation of two blocks
+/// of data given the checksums of the blocks and the length of the second block.
+///
+/// This is the algorithm used by zlib's crc32_combine.
+pub(super) fn crc32_combine(crc1: u32, crc2: u32, len2: u64) -> u32 {
+    // degenerate case (also disallow negative lengths)
+    if len2 == 0 {
+        return crc1;
+    }
+
+    let mut even = [0; GF2_DIM]; // even-power-of-two zeros operator
+    let mut odd = [0; GF2_DIM]; // odd-power-of-two zeros operator
+
+    // put operator for one zero bit in odd
+    odd[0] = crc32::PredefinedPolynomials::IEEE as u32; // CRC-32 polynomial
+    let mut row = 1;
+    for item in odd.iter_mut().skip(1) {
+        *item = row;
+        row <<= 1;
+    }
+
+    // put operator for two zero bits in even
+    gf2_matrix_square(&mut even, &odd);
+
+    // put operator for four zero bits in odd
+    gf2_matrix_square(&mut odd, &even);
+
+    // apply len2 zeros to crc1 (first square will put the operator for one
+    // zero byte, eight zero bits, in even)
+    let mut crc1 = crc1;
+    let mut len2 = len2;
+    loop {
+        // apply zeros operator for this bit of len2
+        gf2_matrix_square(&mut even, &odd);
+        if len2 & 1 != 0 {
+            crc1 = gf2_matrix_times(&even, crc1);
+        }
+        len2 >>= 1;
+
+        // if no more bits set, then done
+        if len2 == 0 {
+            break;
+        }
+
+        // another iteration of the loop with odd and even swapped
+        gf2_matrix_square(&mut odd, &even);
+        if len2 & 1 != 0 {
+            crc1 = gf2_matrix_times(&odd, crc1);
+        }
+        len2 >>= 1;
+
+        if len2 == 0 {
+            break;
+        }
+    }
+
+    crc1 ^ crc2
+}
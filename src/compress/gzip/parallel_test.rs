@@ -0,0 +1,244 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::parallel::crc32_combine;
+use super::{ParallelWriter, Reader, Writer};
+use crate::hash::crc32;
+use crate::time;
+use std::io::{Read, Write};
+
+/// test_data returns n bytes of compressible pseudo-random text.
+fn test_data(n: usize) -> Vec<u8> {
+    const WORDS: &[&[u8]] = &[
+        b"gopher ",
+        b"rust ",
+        b"deflate ",
+        b"gzip ",
+        b"window ",
+        b"block ",
+        b"thread ",
+        b"\n",
+    ];
+    let mut data = Vec::with_capacity(n + 16);
+    let mut seed: u32 = 1;
+    while data.len() < n {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        data.extend_from_slice(WORDS[(seed >> 16) as usize % WORDS.len()]);
+    }
+    data.truncate(n);
+    data
+}
+
+/// gunzip decompresses a single gzip member and fails the test
+/// if b contains anything after it.
+fn gunzip(b: &[u8]) -> Vec<u8> {
+    let mut input = b;
+    let mut r = Reader::new(&mut input).unwrap();
+    r.multistream(false);
+    let mut got = Vec::new();
+    r.read_to_end(&mut got).unwrap();
+    r.close().unwrap();
+    assert!(input.is_empty(), "{} trailing bytes", input.len());
+    got
+}
+
+#[test]
+fn test_parallel_round_trip() {
+    let tests = [
+        (0, 1024, 4),
+        (1, 1024, 4),
+        (1000, 1024, 4),
+        (1024, 1024, 4),
+        (100000, 1024, 1),
+        (100000, 1024, 3),
+        (100000, 40000, 8),
+        (300000, 65536, 2),
+    ];
+    for (size, block_size, blocks) in tests {
+        let data = test_data(size);
+        let mut buf = Vec::new();
+        let mut w = ParallelWriter::new(&mut buf);
+        w.set_concurrency(block_size, blocks).unwrap();
+        // write in chunks not aligned with the blocks
+        for chunk in data.chunks(777) {
+            w.write_all(chunk).unwrap();
+        }
+        w.close().unwrap();
+
+        let got = gunzip(&buf);
+        assert!(
+            got == data,
+            "size {}, block_size {}, blocks {}: data mismatch",
+            size,
+            block_size,
+            blocks
+        );
+    }
+}
+
+#[test]
+fn test_parallel_compression_ratio() {
+    // Because every block is primed with the preceding data, splitting the
+    // input into blocks should cost very little.
+    let data = test_data(200000);
+
+    let mut serial = Vec::new();
+    let mut w = Writer::new(&mut serial);
+    w.write_all(&data).unwrap();
+    w.close().unwrap();
+
+    let mut parallel = Vec::new();
+    let mut w = ParallelWriter::new(&mut parallel);
+    w.set_concurrency(50000, 4).unwrap();
+    w.write_all(&data).unwrap();
+    w.close().unwrap();
+
+    assert_eq!(gunzip(&parallel), data);
+    assert!(
+        parallel.len() < serial.len() + serial.len() / 20,
+        "parallel output is {} bytes, serial output is {} bytes",
+        parallel.len(),
+        serial.len()
+    );
+}
+
+#[test]
+fn test_parallel_levels() {
+    let data = test_data(50000);
+    for level in [
+        super::NO_COMPRESSION,
+        super::BEST_SPEED,
+        5,
+        super::BEST_COMPRESSION,
+        super::DEFAULT_COMPRESSION,
+        super::HUFFMAN_ONLY,
+    ] {
+        let mut buf = Vec::new();
+        let mut w = ParallelWriter::new_level(&mut buf, level).unwrap();
+        w.set_concurrency(8192, 3).unwrap();
+        w.write_all(&data).unwrap();
+        w.close().unwrap();
+        assert!(gunzip(&buf) == data, "level {}: data mismatch", level);
+    }
+
+    let mut buf = Vec::new();
+    assert!(ParallelWriter::new_level(&mut buf, 10).is_err());
+}
+
+#[test]
+fn test_parallel_header() {
+    let mut buf = Vec::new();
+    let mut w = ParallelWriter::new(&mut buf);
+    w.header.comment = Some("comment".to_string());
+    w.header.extra = Some(b"extra".to_vec());
+    w.header.mod_time = time::unix(100000000, 0);
+    w.header.name = Some("name".to_string());
+    w.write_all(b"payload").unwrap();
+    w.close().unwrap();
+
+    let mut serial = Vec::new();
+    let mut w = Writer::new(&mut serial);
+    w.header.comment = Some("comment".to_string());
+    w.header.extra = Some(b"extra".to_vec());
+    w.header.mod_time = time::unix(100000000, 0);
+    w.header.name = Some("name".to_string());
+    w.write_all(b"payload").unwrap();
+    w.close().unwrap();
+
+    // A single block is compressed exactly like Writer does it.
+    assert_eq!(buf, serial);
+
+    let mut input = buf.as_slice();
+    let r = Reader::new(&mut input).unwrap();
+    let header = r.header.as_ref().unwrap();
+    assert_eq!(header.comment.as_deref(), Some("comment"));
+    assert_eq!(header.extra.as_deref(), Some(b"extra".as_slice()));
+    assert_eq!(header.mod_time.unix(), 100000000);
+    assert_eq!(header.name.as_deref(), Some("name"));
+}
+
+#[test]
+fn test_parallel_flush() {
+    let data = test_data(10000);
+    let mut buf = Vec::new();
+    let mut w = ParallelWriter::new(&mut buf);
+    w.set_concurrency(4096, 2).unwrap();
+    w.write_all(&data[..5000]).unwrap();
+    w.flush().unwrap();
+    assert!(w.set_concurrency(1024, 2).is_err());
+    w.write_all(&data[5000..]).unwrap();
+    w.flush().unwrap();
+    w.close().unwrap();
+    assert!(w.write(b"x").is_err());
+
+    assert_eq!(gunzip(&buf), data);
+}
+
+#[test]
+fn test_parallel_flush_is_readable() {
+    // After flush, everything written so far must be decodable.
+    let data = test_data(5000);
+    let mut buf = Vec::new();
+    {
+        let mut w = ParallelWriter::new(&mut buf);
+        w.set_concurrency(1024, 2).unwrap();
+        w.write_all(&data).unwrap();
+        w.flush().unwrap();
+    }
+
+    let mut input = buf.as_slice();
+    let mut r = Reader::new(&mut input).unwrap();
+    let mut got = vec![0; data.len()];
+    r.read_exact(&mut got).unwrap();
+    assert_eq!(got, data);
+}
+
+#[test]
+fn test_parallel_double_flush() {
+    // A second flush with nothing written in between must not
+    // change the output.
+    let data = test_data(5000);
+    let compress = |flushes: usize| {
+        let mut buf = Vec::new();
+        let mut w = ParallelWriter::new(&mut buf);
+        w.set_concurrency(1024, 2).unwrap();
+        w.write_all(&data).unwrap();
+        for _ in 0..flushes {
+            w.flush().unwrap();
+        }
+        w.close().unwrap();
+        buf
+    };
+    let once = compress(1);
+    assert_eq!(compress(2), once);
+    assert_eq!(gunzip(&once), data);
+}
+
+#[test]
+fn test_set_concurrency_invalid() {
+    let mut buf = Vec::new();
+    let mut w = ParallelWriter::new(&mut buf);
+    assert!(w.set_concurrency(0, 1).is_err());
+    assert!(w.set_concurrency(1, 0).is_err());
+    assert!(w.set_concurrency(1, 1).is_ok());
+}
+
+#[test]
+fn test_crc32_combine() {
+    let data = test_data(10000);
+    let want = crc32::checksum_ieee(&data);
+    for split in [0, 1, 2, 3, 100, 4096, 9999, 10000] {
+        let (a, b) = data.split_at(split);
+        let got = crc32_combine(
+            crc32::checksum_ieee(a),
+            crc32::checksum_ieee(b),
+            b.len() as u64,
+        );
+        assert_eq!(
+            got, want,
+            "split at {}: got {:08x}, want {:08x}",
+            split, got, want
+        );
+    }
+}
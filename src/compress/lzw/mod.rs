@@ -0,0 +1,28 @@
+// Copyright 2023 The rust-ggstd authors.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! Package lzw implements the Lempel-Ziv-Welch compressed data format,
+//! described in T. A. Welch, “A Technique for High-Performance Data
+//! Compression”, Computer, 17(6) (June 1984), pp 8-19.
+//!
+//! In particular, it implements LZW as used by the GIF and PDF file
+//! formats, which means variable-width codes up to 12 bits and the first
+//! two non-literal codes are a clear code and an EOF code.
+//!
+//! The TIFF file format and PDF LZWDecode streams by default use a similar
+//! but incompatible version of the LZW algorithm, which increases the code
+//! width one code early. It is supported by the early_change methods
+//! of Reader and Writer.
+
+mod reader;
+mod writer;
+
+pub use reader::{Error, Order, Reader};
+pub use writer::Writer;
+
+#[cfg(test)]
+mod reader_test;
+#[cfg(test)]
+mod writer_test;
@@ -0,0 +1,359 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use crate::compat;
+use crate::compress::copy_error;
+use crate::errors;
+
+/// Order specifies the bit ordering in an LZW data stream.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Order {
+    /// LSB means Least Significant Bits first, as used in the GIF file format.
+    LSB,
+    /// MSB means Most Significant Bits first, as used in the TIFF and PDF
+    /// file formats.
+    MSB,
+}
+
+pub(super) const MAX_WIDTH: u32 = 12;
+const DECODER_INVALID_CODE: u16 = 0xffff;
+const FLUSH_BUFFER: usize = 1 << MAX_WIDTH;
+
+pub(super) const ERR_CLOSED: &str = "lzw: reader/writer is closed";
+
+/// Error describes invalid LZW data. Reader returns it wrapped
+/// in a std::io::Error of the ErrorKind::InvalidData kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// InvalidCode is returned when a code is greater than the next code
+    /// to be defined.
+    InvalidCode,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidCode => write!(f, "lzw: invalid code"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for std::io::Error {
+    fn from(e: Error) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, e)
+    }
+}
+
+/// new_lit_width_error reports that lit_width is not in the range [2, 8].
+pub(super) fn new_lit_width_error(lit_width: usize) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        format!("lzw: litWidth {} out of range", lit_width),
+    )
+}
+
+/// Reader is a std::io::Read which can be used to read compressed data in the
+/// LZW format.
+pub struct Reader<Input: std::io::BufRead> {
+    r: Input,
+    bits: u32,
+    n_bits: u32,
+    width: u32,
+    order: Order,
+    /// lit_width is the width in bits of literal codes.
+    lit_width: usize,
+    err: Option<std::io::Error>,
+    /// eof is set when the EOF code has been read.
+    eof: bool,
+    /// early_change is set for the TIFF and PDF variant of LZW.
+    early_change: bool,
+
+    // The first 1<<lit_width codes are literal codes.
+    // The next two codes mean clear and EOF.
+    // Other valid codes are in the range [lo, hi] where lo := clear + 2,
+    // with the upper bound incrementing on each code seen.
+    //
+    // overflow is the code at which hi overflows the code width. It always
+    // equals 1 << width. With early change, the width grows one code early,
+    // when hi reaches overflow - 1.
+    //
+    // last is the most recently seen code, or DECODER_INVALID_CODE.
+    //
+    // An invariant is that hi + early_change < overflow.
+    clear: u16,
+    eof_code: u16,
+    hi: u16,
+    overflow: u16,
+    last: u16,
+
+    // Each code c in [lo, hi] expands to two or more bytes. For c != hi:
+    //   suffix[c] is the last of these bytes.
+    //   prefix[c] is the code for all but the last byte.
+    //   This code can either be a literal code or another code in [lo, c).
+    // The c == hi case is a special case.
+    suffix: [u8; 1 << MAX_WIDTH],
+    prefix: [u16; 1 << MAX_WIDTH],
+
+    /// output is the temporary output buffer.
+    /// Literal codes are accumulated from the start of the buffer.
+    /// Non-literal codes decode to a sequence of suffixes that are first
+    /// written right-to-left from the end of the buffer before being copied
+    /// to the start of the buffer.
+    /// It is flushed when it contains >= 1<<MAX_WIDTH bytes,
+    /// so that there is always room to decode an entire code.
+    output: [u8; 2 * (1 << MAX_WIDTH)],
+    /// write index into output
+    o: usize,
+    /// output[to_read_start..to_read_end] are the bytes to return from read.
+    to_read_start: usize,
+    to_read_end: usize,
+}
+
+impl<Input: std::io::BufRead> Reader<Input> {
+    /// new creates a new Reader.
+    /// Reads from the returned Reader read and decompress data from r.
+    /// It is the caller's responsibility to call close on the Reader when
+    /// finished reading.
+    /// The number of bits to use for literal codes, lit_width, must be in the
+    /// range [2,8] and is typically 8. It must equal the lit_width
+    /// used during compression.
+    pub fn new(r: Input, order: Order, lit_width: usize) -> Self {
+        let mut z = Self {
+            r,
+            bits: 0,
+            n_bits: 0,
+            width: 0,
+            order,
+            lit_width: 0,
+            err: None,
+            eof: false,
+            early_change: false,
+            clear: 0,
+            eof_code: 0,
+            hi: 0,
+            overflow: 0,
+            last: 0,
+            suffix: [0; 1 << MAX_WIDTH],
+            prefix: [0; 1 << MAX_WIDTH],
+            output: [0; 2 * (1 << MAX_WIDTH)],
+            o: 0,
+            to_read_start: 0,
+            to_read_end: 0,
+        };
+        z.init(order, lit_width);
+        z
+    }
+
+    fn init(&mut self, order: Order, lit_width: usize) {
+        self.bits = 0;
+        self.n_bits = 0;
+        self.err = None;
+        self.eof = false;
+        self.o = 0;
+        self.to_read_start = 0;
+        self.to_read_end = 0;
+        self.order = order;
+        if !(2..=8).contains(&lit_width) {
+            self.err = Some(new_lit_width_error(lit_width));
+            return;
+        }
+        self.lit_width = lit_width;
+        self.width = 1 + lit_width as u32;
+        self.clear = 1 << lit_width;
+        self.eof_code = self.clear + 1;
+        self.hi = self.clear + 1;
+        self.overflow = 1 << self.width;
+        self.last = DECODER_INVALID_CODE;
+    }
+
+    /// early_change controls whether the reader decodes the variant of LZW
+    /// used by the TIFF and PDF file formats.
+    ///
+    /// If enabled, the code width is increased one code earlier than in the
+    /// GIF variant, as done by the original TIFF encoders. TIFF files and
+    /// PDF LZWDecode streams use this variant with the MSB order and
+    /// a lit_width of 8, unless the PDF stream has an EarlyChange value of 0.
+    ///
+    /// early_change must be called before the first read.
+    pub fn early_change(&mut self, ok: bool) {
+        self.early_change = ok;
+    }
+
+    /// reset clears the Reader's state and allows it to be reused again
+    /// as a new Reader. The early change setting is kept.
+    pub fn reset(&mut self, src: Input, order: Order, lit_width: usize) {
+        self.r = src;
+        self.init(order, lit_width);
+    }
+
+    /// close makes any future reads return an error.
+    pub fn close(&mut self) -> std::io::Result<()> {
+        // in case any reads come along
+        self.err = Some(errors::new_stdio_other_error(ERR_CLOSED.to_string()));
+        Ok(())
+    }
+
+    /// input_reader returns the underlying reader.
+    pub fn input_reader(&mut self) -> &mut Input {
+        &mut self.r
+    }
+
+    fn read_byte(&mut self) -> std::io::Result<u8> {
+        let buf = self.r.fill_buf()?;
+        if buf.is_empty() {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+        }
+        let c = buf[0];
+        self.r.consume(1);
+        Ok(c)
+    }
+
+    /// read_lsb returns the next code for "Least Significant Bits first" data.
+    fn read_lsb(&mut self) -> std::io::Result<u16> {
+        while self.n_bits < self.width {
+            let x = self.read_byte()?;
+            self.bits |= (x as u32) << self.n_bits;
+            self.n_bits += 8;
+        }
+        let code = (self.bits & ((1 << self.width) - 1)) as u16;
+        self.bits >>= self.width;
+        self.n_bits -= self.width;
+        Ok(code)
+    }
+
+    /// read_msb returns the next code for "Most Significant Bits first" data.
+    fn read_msb(&mut self) -> std::io::Result<u16> {
+        while self.n_bits < self.width {
+            let x = self.read_byte()?;
+            self.bits |= (x as u32) << (24 - self.n_bits);
+            self.n_bits += 8;
+        }
+        let code = (self.bits >> (32 - self.width)) as u16;
+        self.bits <<= self.width;
+        self.n_bits -= self.width;
+        Ok(code)
+    }
+
+    /// decode decompresses bytes from r and leaves them in output[to_read_start..to_read_end].
+    fn decode(&mut self) {
+        // Loop over the code stream, converting codes into decompressed bytes.
+        loop {
+            let code = match self.order {
+                Order::LSB => self.read_lsb(),
+                Order::MSB => self.read_msb(),
+            };
+            let code = match code {
+                Ok(code) => code,
+                Err(err) => {
+                    self.err = Some(err);
+                    break;
+                }
+            };
+            if code < self.clear {
+                // We have a literal code.
+                self.output[self.o] = code as u8;
+                self.o += 1;
+                if self.last != DECODER_INVALID_CODE {
+                    // Save what the hi code expands to.
+                    self.suffix[self.hi as usize] = code as u8;
+                    self.prefix[self.hi as usize] = self.last;
+                }
+            } else if code == self.clear {
+                self.width = 1 + self.lit_width as u32;
+                self.hi = self.eof_code;
+                self.overflow = 1 << self.width;
+                self.last = DECODER_INVALID_CODE;
+                continue;
+            } else if code == self.eof_code {
+                self.eof = true;
+                break;
+            } else if code <= self.hi {
+                let mut c = code;
+                let mut i = self.output.len() - 1;
+                if code == self.hi && self.last != DECODER_INVALID_CODE {
+                    // code == hi is a special case which expands to the last expansion
+                    // followed by the head of the last expansion. To find the head, we walk
+                    // the prefix chain until we find a literal code.
+                    c = self.last;
+                    while c >= self.clear {
+                        c = self.prefix[c as usize];
+                    }
+                    self.output[i] = c as u8;
+                    i -= 1;
+                    c = self.last;
+                }
+                // Copy the suffix chain into output and then write that to w.
+                while c >= self.clear {
+                    self.output[i] = self.suffix[c as usize];
+                    i -= 1;
+                    c = self.prefix[c as usize];
+                }
+                self.output[i] = c as u8;
+                let n = self.output.len() - i;
+                self.output.copy_within(i.., self.o);
+                self.o += n;
+                if self.last != DECODER_INVALID_CODE {
+                    // Save what the hi code expands to.
+                    self.suffix[self.hi as usize] = c as u8;
+                    self.prefix[self.hi as usize] = self.last;
+                }
+            } else {
+                self.err = Some(Error::InvalidCode.into());
+                break;
+            }
+            self.last = code;
+            self.hi += 1;
+            if self.hi + self.early_change as u16 >= self.overflow {
+                if self.width == MAX_WIDTH {
+                    self.last = DECODER_INVALID_CODE;
+                    // Undo the self.hi += 1 a few lines above, so that (1) we maintain
+                    // the invariant that self.hi + early_change < self.overflow, and
+                    // (2) self.hi does not eventually overflow a u16.
+                    self.hi -= 1;
+                } else {
+                    self.width += 1;
+                    self.overflow = 1 << self.width;
+                }
+            }
+            if self.o >= FLUSH_BUFFER {
+                break;
+            }
+        }
+        // Flush pending output.
+        self.to_read_start = 0;
+        self.to_read_end = self.o;
+        self.o = 0;
+    }
+}
+
+impl<Input: std::io::BufRead> std::io::Read for Reader<Input> {
+    /// read reads uncompressed bytes from its underlying reader.
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = compat::copy(buf, std::io::BufRead::fill_buf(self)?);
+        self.to_read_start += n;
+        Ok(n)
+    }
+}
+
+impl<Input: std::io::BufRead> std::io::BufRead for Reader<Input> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        while self.to_read_start == self.to_read_end {
+            if let Some(err) = &self.err {
+                return Err(copy_error(err));
+            }
+            if self.eof {
+                break;
+            }
+            self.decode();
+        }
+        Ok(&self.output[self.to_read_start..self.to_read_end])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.to_read_start = (self.to_read_start + amt).min(self.to_read_end);
+    }
+}
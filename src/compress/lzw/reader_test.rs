@@ -0,0 +1,177 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::{Error, Order, Reader, Writer};
+use crate::math::rand;
+use std::io::{Read, Write};
+
+struct LzwTest {
+    desc: &'static str,
+    raw: &'static [u8],
+    compressed: &'static [u8],
+    err: Option<std::io::ErrorKind>,
+}
+
+const LZW_TESTS: &[LzwTest] = &[
+    LzwTest {
+        desc: "empty;LSB;8",
+        raw: b"",
+        compressed: b"\x01\x01",
+        err: None,
+    },
+    LzwTest {
+        desc: "empty;MSB;8",
+        raw: b"",
+        compressed: b"\x80\x80",
+        err: None,
+    },
+    LzwTest {
+        desc: "tobe;LSB;7",
+        raw: b"TOBEORNOTTOBEORTOBEORNOT",
+        compressed: b"\x54\x4f\x42\x45\x4f\x52\x4e\x4f\x54\x82\x84\x86\x8b\x85\x87\x89\x81",
+        err: None,
+    },
+    LzwTest {
+        desc: "tobe;LSB;8",
+        raw: b"TOBEORNOTTOBEORTOBEORNOT",
+        compressed:
+            b"\x54\x9e\x08\x29\xf2\x44\x8a\x93\x27\x54\x04\x12\x34\xb8\xb0\xe0\xc1\x84\x01\x01",
+        err: None,
+    },
+    LzwTest {
+        desc: "tobe;MSB;7",
+        raw: b"TOBEORNOTTOBEORTOBEORNOT",
+        compressed: b"\x54\x4f\x42\x45\x4f\x52\x4e\x4f\x54\x82\x84\x86\x8b\x85\x87\x89\x81",
+        err: None,
+    },
+    LzwTest {
+        desc: "tobe-truncated;LSB;8",
+        raw: b"TOBEORNOTTOBEORTOBEORNOT",
+        compressed: b"\x54\x9e\x08\x29\xf2\x44\x8a\x93\x27\x54\x04",
+        err: Some(std::io::ErrorKind::UnexpectedEof),
+    },
+    // This example comes from https://en.wikipedia.org/wiki/Graphics_Interchange_Format.
+    LzwTest {
+        desc: "gif;LSB;8",
+        raw: b"\x28\xff\xff\xff\x28\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff",
+        compressed: b"\x00\x51\xfc\x1b\x28\x70\xa0\xc1\x83\x01\x01",
+        err: None,
+    },
+    // This example comes from http://compgroups.net/comp.lang.ruby/Decompressing-LZW-compression-from-PDF-file
+    LzwTest {
+        desc: "pdf;MSB;8",
+        raw: b"-----A---B",
+        compressed: b"\x80\x0b\x60\x50\x22\x0c\x0c\x85\x01",
+        err: None,
+    },
+];
+
+fn parse_desc(desc: &str) -> (Order, usize) {
+    let d: Vec<&str> = desc.split(';').collect();
+    let order = match d[1] {
+        "LSB" => Order::LSB,
+        "MSB" => Order::MSB,
+        _ => panic!("{}: bad order {:?}", desc, d[1]),
+    };
+    (order, d[2].parse().unwrap())
+}
+
+#[test]
+fn test_reader() {
+    for tt in LZW_TESTS {
+        let (order, lit_width) = parse_desc(tt.desc);
+        let mut rc = Reader::new(tt.compressed, order, lit_width);
+        let mut b = Vec::new();
+        let res = rc.read_to_end(&mut b);
+        rc.close().unwrap();
+        match (tt.err, res) {
+            (None, Ok(_)) => {}
+            (Some(want), Err(err)) => {
+                assert_eq!(want, err.kind(), "{}: wrong error: {}", tt.desc, err);
+                continue;
+            }
+            (want, got) => panic!("{}: got {:?}, want err {:?}", tt.desc, got, want),
+        }
+        assert_eq!(tt.raw, b.as_slice(), "{}", tt.desc);
+    }
+}
+
+#[test]
+fn test_reader_early_change() {
+    // The example from section 7.4.4.2 of the PDF 1.7 specification.
+    let compressed = b"\x80\x0b\x60\x50\x22\x0c\x0c\x85\x01";
+    let mut r = Reader::new(&compressed[..], Order::MSB, 8);
+    r.early_change(true);
+    let mut b = Vec::new();
+    r.read_to_end(&mut b).unwrap();
+    assert_eq!(b, b"-----A---B");
+}
+
+#[test]
+fn test_reader_reset() {
+    for tt in LZW_TESTS {
+        let (order, lit_width) = parse_desc(tt.desc);
+        let mut rc = Reader::new(tt.compressed, order, lit_width);
+        let mut b1 = Vec::new();
+        let res1 = rc.read_to_end(&mut b1);
+        rc.reset(tt.compressed, order, lit_width);
+        let mut b2 = Vec::new();
+        let res2 = rc.read_to_end(&mut b2);
+        assert_eq!(res1.is_ok(), res2.is_ok(), "{}", tt.desc);
+        assert_eq!(b1, b2, "{}: bytes read were not the same", tt.desc);
+    }
+}
+
+#[test]
+fn test_reader_after_close() {
+    let mut rc = Reader::new(LZW_TESTS[3].compressed, Order::LSB, 8);
+    rc.close().unwrap();
+    let err = rc.read(&mut [0; 8]).err().unwrap();
+    assert_eq!(err.to_string(), "lzw: reader/writer is closed");
+}
+
+#[test]
+fn test_reader_invalid_lit_width() {
+    for lit_width in [0, 1, 9] {
+        let mut rc = Reader::new(&b"\x00\x00"[..], Order::LSB, lit_width);
+        let err = rc.read(&mut [0; 8]).err().unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
+
+#[test]
+fn test_invalid_code() {
+    // With a literal width of 2 the first non-literal code is 6, so the code
+    // 7 (after a literal) refers to a code that has not been defined yet.
+    let mut rc = Reader::new(&b"\x3b\x00"[..], Order::LSB, 2);
+    let err = rc.read_to_end(&mut Vec::new()).err().unwrap();
+    assert_eq!(err.to_string(), "lzw: invalid code");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    let inner = err.get_ref().and_then(|e| e.downcast_ref::<Error>());
+    assert_eq!(inner, Some(&Error::InvalidCode));
+}
+
+/// test_hi_code_does_not_overflow tests that decoding a long stream, which
+/// fills up the code table several times without an explicit clear code from
+/// the encoder at the right moment, round-trips correctly.
+#[test]
+fn test_hi_code_does_not_overflow() {
+    let mut r = rand::Rand::new(rand::new_source(1));
+    let mut input = vec![0u8; 1 << 17];
+    for b in input.iter_mut() {
+        *b = (r.int63() % 4) as u8;
+    }
+    for order in [Order::LSB, Order::MSB] {
+        let mut compressed = Vec::new();
+        let mut w = Writer::new(&mut compressed, order, 8);
+        w.write_all(&input).unwrap();
+        w.close().unwrap();
+
+        let mut rc = Reader::new(compressed.as_slice(), order, 8);
+        let mut got = Vec::new();
+        rc.read_to_end(&mut got).unwrap();
+        assert!(got == input, "{:?}: decompressed data differs", order);
+    }
+}
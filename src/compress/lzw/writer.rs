@@ -0,0 +1,326 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::reader::{new_lit_width_error, Order, ERR_CLOSED};
+use crate::errors;
+
+// A code is a 12 bit value, stored as a u32 when encoding to avoid
+// type conversions when shifting bits.
+const MAX_CODE: u32 = (1 << 12) - 1;
+const INVALID_CODE: u32 = u32::MAX;
+// There are 1<<12 possible codes, which is an upper bound on the number of
+// valid hash table entries at any given point in time. TABLE_SIZE is 4x that.
+const TABLE_SIZE: usize = 4 * (1 << 12);
+const TABLE_MASK: u32 = TABLE_SIZE as u32 - 1;
+// A hash table entry is a u32. Zero is an invalid entry since the
+// lower 12 bits of a valid entry must be a non-literal code.
+const INVALID_ENTRY: u32 = 0;
+
+/// OUTPUT_BUFFER_SIZE is the number of encoded bytes buffered before they are
+/// written to the underlying writer.
+const OUTPUT_BUFFER_SIZE: usize = 4096;
+
+/// Writer is an LZW compressor. It writes the compressed form of the data
+/// to an underlying writer.
+pub struct Writer<Output: std::io::Write> {
+    /// w is the writer that compressed bytes are written to.
+    w: Output,
+    /// buf holds the compressed bytes that are not yet written to w.
+    buf: Vec<u8>,
+    order: Order,
+    /// early_change is set for the TIFF and PDF variant of LZW.
+    early_change: bool,
+    n_bits: u32,
+    width: u32,
+    bits: u32,
+    lit_width: u32,
+    /// hi is the code implied by the next code emission.
+    /// overflow is the code at which hi overflows the code width.
+    hi: u32,
+    overflow: u32,
+    /// saved_code is the accumulated code at the end of the most recent write
+    /// call. It is equal to INVALID_CODE if there was no such call.
+    saved_code: u32,
+    /// err is the first error encountered during writing. Closing the writer
+    /// will make any future write calls return ERR_CLOSED.
+    err: Option<std::io::Error>,
+    /// closed is set when close was called.
+    closed: bool,
+    /// table is the hash table from 20-bit keys to 12-bit values. Each table
+    /// entry contains key<<12|val and collisions resolve by linear probing.
+    /// The keys consist of a 12-bit code prefix and an 8-bit byte suffix.
+    /// The values are a 12-bit code.
+    table: Vec<u32>,
+}
+
+impl<Output: std::io::Write> Writer<Output> {
+    /// new creates a new Writer.
+    /// Writes to the returned Writer are compressed and written to w.
+    /// It is the caller's responsibility to call close on the Writer when
+    /// finished writing.
+    /// The number of bits to use for literal codes, lit_width, must be in the
+    /// range [2,8] and is typically 8. Input bytes must be less than 1<<lit_width.
+    pub fn new(w: Output, order: Order, lit_width: usize) -> Self {
+        let mut z = Self {
+            w,
+            buf: Vec::with_capacity(OUTPUT_BUFFER_SIZE),
+            order,
+            early_change: false,
+            n_bits: 0,
+            width: 0,
+            bits: 0,
+            lit_width: 0,
+            hi: 0,
+            overflow: 0,
+            saved_code: INVALID_CODE,
+            err: None,
+            closed: false,
+            table: vec![INVALID_ENTRY; TABLE_SIZE],
+        };
+        z.init(order, lit_width);
+        z
+    }
+
+    fn init(&mut self, order: Order, lit_width: usize) {
+        self.buf.clear();
+        self.n_bits = 0;
+        self.bits = 0;
+        self.err = None;
+        self.closed = false;
+        self.table.fill(INVALID_ENTRY);
+        self.order = order;
+        if !(2..=8).contains(&lit_width) {
+            self.err = Some(new_lit_width_error(lit_width));
+            return;
+        }
+        let lw = lit_width as u32;
+        self.width = 1 + lw;
+        self.lit_width = lw;
+        self.hi = (1 << lw) + 1;
+        self.overflow = 1 << (lw + 1);
+        self.saved_code = INVALID_CODE;
+    }
+
+    /// early_change controls whether the writer produces the variant of LZW
+    /// used by the TIFF and PDF file formats, where the code width is
+    /// increased one code earlier than in the GIF variant.
+    /// See Reader::early_change.
+    ///
+    /// early_change must be called before the first write.
+    pub fn early_change(&mut self, ok: bool) {
+        self.early_change = ok;
+    }
+
+    /// reset clears the Writer's state and allows it to be reused again
+    /// as a new Writer. The early change setting is kept.
+    pub fn reset(&mut self, dst: Output, order: Order, lit_width: usize) {
+        self.w = dst;
+        self.init(order, lit_width);
+    }
+
+    /// get_ref returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Output {
+        &self.w
+    }
+
+    /// get_mut returns a mutable reference to the underlying writer.
+    /// Writing to it directly corrupts the compressed stream.
+    pub fn get_mut(&mut self) -> &mut Output {
+        &mut self.w
+    }
+
+    /// into_inner returns the underlying writer. close must be called before
+    /// to write the rest of the compressed data.
+    pub fn into_inner(self) -> Output {
+        self.w
+    }
+
+    fn write_byte(&mut self, c: u8) -> std::io::Result<()> {
+        self.buf.push(c);
+        if self.buf.len() >= OUTPUT_BUFFER_SIZE {
+            self.w.write_all(&self.buf)?;
+            self.buf.clear();
+        }
+        Ok(())
+    }
+
+    /// write_code writes the code c using the Writer's bit order.
+    fn write_code(&mut self, c: u32) -> std::io::Result<()> {
+        match self.order {
+            Order::LSB => self.write_lsb(c),
+            Order::MSB => self.write_msb(c),
+        }
+    }
+
+    /// write_lsb writes the code c for "Least Significant Bits first" data.
+    fn write_lsb(&mut self, c: u32) -> std::io::Result<()> {
+        self.bits |= c << self.n_bits;
+        self.n_bits += self.width;
+        while self.n_bits >= 8 {
+            self.write_byte(self.bits as u8)?;
+            self.bits >>= 8;
+            self.n_bits -= 8;
+        }
+        Ok(())
+    }
+
+    /// write_msb writes the code c for "Most Significant Bits first" data.
+    fn write_msb(&mut self, c: u32) -> std::io::Result<()> {
+        self.bits |= c << (32 - self.width - self.n_bits);
+        self.n_bits += self.width;
+        while self.n_bits >= 8 {
+            self.write_byte((self.bits >> 24) as u8)?;
+            self.bits <<= 8;
+            self.n_bits -= 8;
+        }
+        Ok(())
+    }
+
+    /// inc_hi increments self.hi and checks for both overflow and running out of
+    /// unused codes. In the latter case, inc_hi sends a clear code, resets the
+    /// writer state and returns true.
+    fn inc_hi(&mut self) -> std::io::Result<bool> {
+        self.hi += 1;
+        if self.hi == MAX_CODE {
+            let clear = 1 << self.lit_width;
+            self.write_code(clear)?;
+            self.width = self.lit_width + 1;
+            self.hi = clear + 1;
+            self.overflow = clear << 1;
+            self.table.fill(INVALID_ENTRY);
+            return Ok(true);
+        }
+        if self.hi + self.early_change as u32 == self.overflow {
+            self.width += 1;
+            self.overflow <<= 1;
+        }
+        Ok(false)
+    }
+
+    fn write_codes(&mut self, p: &[u8]) -> std::io::Result<()> {
+        let mut p = p;
+        let mut code = self.saved_code;
+        if code == INVALID_CODE {
+            // This is the first write; send a clear code.
+            // https://www.w3.org/Graphics/GIF/spec-gif89a.txt Appendix F
+            // "Variable-Length-Code LZW Compression" says that "Encoders should
+            // output a Clear code as the first code of each image data stream".
+            //
+            // LZW compression isn't only used by GIF, but it's cheap to follow
+            // that directive unconditionally.
+            let clear = 1 << self.lit_width;
+            self.write_code(clear)?;
+            // After the starting clear code, the next code sent (for non-empty
+            // input) is always a literal code.
+            code = p[0] as u32;
+            p = &p[1..];
+        }
+        'outer: for &x in p {
+            let literal = x as u32;
+            let key = code << 8 | literal;
+            // If there is a hash table hit for this key then we continue the loop
+            // and do not emit a code yet.
+            let mut hash = (key >> 12 ^ key) & TABLE_MASK;
+            let mut h = hash;
+            let mut t = self.table[hash as usize];
+            while t != INVALID_ENTRY {
+                if key == t >> 12 {
+                    code = t & MAX_CODE;
+                    continue 'outer;
+                }
+                h = (h + 1) & TABLE_MASK;
+                t = self.table[h as usize];
+            }
+            // Otherwise, write the current code, and literal becomes the start of
+            // the next emitted code.
+            self.write_code(code)?;
+            code = literal;
+            // Increment self.hi, the next implied code. If we run out of codes, reset
+            // the writer state (including clearing the hash table) and continue.
+            if self.inc_hi()? {
+                continue;
+            }
+            // Otherwise, insert key -> self.hi into the map that self.table represents.
+            loop {
+                if self.table[hash as usize] == INVALID_ENTRY {
+                    self.table[hash as usize] = (key << 12) | self.hi;
+                    break;
+                }
+                hash = (hash + 1) & TABLE_MASK;
+            }
+        }
+        self.saved_code = code;
+        Ok(())
+    }
+
+    /// close closes the Writer, flushing any pending output. It does not close
+    /// the underlying writer.
+    pub fn close(&mut self) -> std::io::Result<()> {
+        if self.closed {
+            return Ok(());
+        }
+        if let Some(err) = &self.err {
+            return Err(errors::copy_stdio_error(err));
+        }
+        // Make any future calls to write return ERR_CLOSED.
+        self.closed = true;
+        // Write the saved_code if valid.
+        if self.saved_code != INVALID_CODE {
+            self.write_code(self.saved_code)?;
+            self.inc_hi()?;
+        } else {
+            // Write the starting clear code, as self.write did not.
+            let clear = 1 << self.lit_width;
+            self.write_code(clear)?;
+        }
+        // Write the eof code.
+        let eof = (1 << self.lit_width) + 1;
+        self.write_code(eof)?;
+        // Write the final bits.
+        if self.n_bits > 0 {
+            if self.order == Order::MSB {
+                self.bits >>= 24;
+            }
+            self.write_byte(self.bits as u8)?;
+        }
+        self.w.write_all(&self.buf)?;
+        self.buf.clear();
+        self.w.flush()
+    }
+}
+
+impl<Output: std::io::Write> std::io::Write for Writer<Output> {
+    /// write writes a compressed representation of p to the underlying writer.
+    fn write(&mut self, p: &[u8]) -> std::io::Result<usize> {
+        if self.closed {
+            return Err(errors::new_stdio_other_error(ERR_CLOSED.to_string()));
+        }
+        if let Some(err) = &self.err {
+            return Err(errors::copy_stdio_error(err));
+        }
+        if p.is_empty() {
+            return Ok(0);
+        }
+        let max_lit = ((1 << self.lit_width) - 1) as u8;
+        if max_lit != 0xff && p.iter().any(|&x| x > max_lit) {
+            let err = std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "lzw: input byte too large for the litWidth",
+            );
+            self.err = Some(errors::copy_stdio_error(&err));
+            return Err(err);
+        }
+        if let Err(err) = self.write_codes(p) {
+            self.err = Some(errors::copy_stdio_error(&err));
+            return Err(err);
+        }
+        Ok(p.len())
+    }
+
+    /// flush does nothing: the final bits can only be written by close.
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,188 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::{Order, Reader, Writer};
+use crate::os as ggos;
+use std::io::{Read, Write};
+
+const FILENAMES: &[&str] = &[
+    "src/compress/testdata/e.txt",
+    "src/compress/testdata/gettysburg.txt",
+    "src/compress/testdata/pi.txt",
+];
+
+/// test_file tests that compressing and then decompressing the given file with
+/// the given options yields equivalent bytes to the original file.
+fn test_file(fn_: &str, order: Order, lit_width: usize, early_change: bool) {
+    let mut golden = ggos::read_file(fn_).unwrap();
+    if lit_width < 8 {
+        // Mask the input so that every byte is below 1<<lit_width.
+        for b in golden.iter_mut() {
+            *b &= (1 << lit_width) - 1;
+        }
+    }
+
+    // Compress the input in chunks of varying sizes, as a pipeline would.
+    let mut compressed = Vec::new();
+    let mut lzww = Writer::new(&mut compressed, order, lit_width);
+    lzww.early_change(early_change);
+    for chunk in golden.chunks(4096 - 7) {
+        assert_eq!(chunk.len(), lzww.write(chunk).unwrap());
+    }
+    lzww.close().unwrap();
+
+    let mut lzwr = Reader::new(compressed.as_slice(), order, lit_width);
+    lzwr.early_change(early_change);
+    let mut b1 = Vec::new();
+    lzwr.read_to_end(&mut b1).unwrap();
+    lzwr.close().unwrap();
+    assert_eq!(
+        golden.len(),
+        b1.len(),
+        "{} (order={:?} litWidth={} early_change={}): length mismatch",
+        fn_,
+        order,
+        lit_width,
+        early_change
+    );
+    assert!(
+        golden == b1,
+        "{} (order={:?} litWidth={} early_change={}): mismatch",
+        fn_,
+        order,
+        lit_width,
+        early_change
+    );
+}
+
+#[test]
+fn test_writer() {
+    for filename in FILENAMES {
+        for order in [Order::LSB, Order::MSB] {
+            // The test data "2.71828 etcetera" is ASCII text requiring at least 6 bits.
+            for lit_width in 6..=8 {
+                test_file(filename, order, lit_width, false);
+                test_file(filename, order, lit_width, true);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_writer_early_change() {
+    // The example from section 7.4.4.2 of the PDF 1.7 specification.
+    let mut compressed = Vec::new();
+    let mut w = Writer::new(&mut compressed, Order::MSB, 8);
+    w.early_change(true);
+    w.write_all(b"-----A---B").unwrap();
+    w.close().unwrap();
+    assert_eq!(compressed, b"\x80\x0b\x60\x50\x22\x0c\x0c\x85\x01");
+
+    // A longer stream, where the code width grows several times, must
+    // round-trip through an early change Reader.
+    let golden = ggos::read_file("src/compress/testdata/e.txt").unwrap();
+    let mut w = Writer::new(Vec::new(), Order::MSB, 8);
+    w.early_change(true);
+    w.write_all(&golden).unwrap();
+    w.close().unwrap();
+    let compressed = w.into_inner();
+    let mut r = Reader::new(compressed.as_slice(), Order::MSB, 8);
+    r.early_change(true);
+    let mut b = Vec::new();
+    r.read_to_end(&mut b).unwrap();
+    assert!(b == golden, "early change round trip mismatch");
+}
+
+#[test]
+fn test_writer_reset_keeps_early_change() {
+    let golden = ggos::read_file("src/compress/testdata/e.txt").unwrap();
+    let mut w = Writer::new(Vec::new(), Order::MSB, 8);
+    w.early_change(true);
+    w.write_all(&golden).unwrap();
+    w.close().unwrap();
+    let want = std::mem::take(w.get_mut());
+
+    w.reset(Vec::new(), Order::MSB, 8);
+    w.write_all(&golden).unwrap();
+    w.close().unwrap();
+    assert!(*w.get_ref() == want, "reset disabled early change");
+
+    let mut r = Reader::new(want.as_slice(), Order::MSB, 8);
+    r.early_change(true);
+    let mut b1 = Vec::new();
+    r.read_to_end(&mut b1).unwrap();
+    r.reset(want.as_slice(), Order::MSB, 8);
+    let mut b2 = Vec::new();
+    r.read_to_end(&mut b2).unwrap();
+    assert!(b1 == golden && b2 == golden, "reset disabled early change");
+}
+
+#[test]
+fn test_writer_reset() {
+    for order in [Order::LSB, Order::MSB] {
+        for lit_width in 6..=8 {
+            let data = ggos::read_file("src/compress/testdata/e.txt").unwrap();
+            let mask = ((1 << lit_width) - 1) as u8;
+            let data: Vec<u8> = data.iter().map(|b| b & mask).collect();
+
+            let mut buf = Vec::new();
+            let mut w = Writer::new(&mut buf, order, lit_width);
+            w.write_all(&data).unwrap();
+            w.close().unwrap();
+
+            let mut buf2 = Vec::new();
+            w.reset(&mut buf2, order, lit_width);
+            w.write_all(&data).unwrap();
+            w.close().unwrap();
+
+            assert!(
+                buf == buf2,
+                "order={:?} litWidth={}: mismatch",
+                order,
+                lit_width
+            );
+        }
+    }
+}
+
+#[test]
+fn test_writer_return_values() {
+    let mut out = Vec::new();
+    let mut w = Writer::new(&mut out, Order::LSB, 8);
+    let s = b"asdf";
+    assert_eq!(s.len(), w.write(s).unwrap());
+    w.close().unwrap();
+    let err = w.write(s).err().unwrap();
+    assert_eq!(err.to_string(), "lzw: reader/writer is closed");
+}
+
+#[test]
+fn test_small_lit_width() {
+    let mut out = Vec::new();
+    let mut w = Writer::new(&mut out, Order::LSB, 2);
+    w.write_all(&[0x03])
+        .expect("write a byte < 1<<2: got an error, want none");
+    assert!(
+        w.write(&[0x04]).is_err(),
+        "write a byte >= 1<<2: got no error, want one"
+    );
+}
+
+#[test]
+fn test_starts_with_clear_code() {
+    // An empty stream is encoded as a clear code followed by an EOF code.
+    let mut out = Vec::new();
+    let mut w = Writer::new(&mut out, Order::LSB, 8);
+    w.close().unwrap();
+    assert_eq!(out, [0x00, 0x03, 0x02]);
+
+    // A non-empty stream also starts with a clear code.
+    let mut out = Vec::new();
+    let mut w = Writer::new(&mut out, Order::LSB, 2);
+    w.write_all(&[0x01]).unwrap();
+    w.close().unwrap();
+    // Codes: clear(4), 1, eof(5), packed LSB first in 3-bit codes.
+    assert_eq!(out, [0x4c, 0x01]);
+}
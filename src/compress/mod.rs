@@ -1,6 +1,17 @@
 // Copyright 2023 The rust-ggstd authors.
 // SPDX-License-Identifier: BSD-3-Clause
 
+//! The compress packages report invalid compressed data as std::io::Error
+//! values of the ErrorKind::InvalidData kind. Each package wraps its own
+//! Error type in them, which can be recovered with get_ref and downcast_ref,
+//! so that corrupt input can be told apart from failures of the
+//! underlying reader or writer.
+
+pub mod bzip2;
+mod error;
 pub mod flate;
 pub mod gzip;
+pub mod lzw;
 pub mod zlib;
+
+pub(crate) use error::{copy_error, copy_option_error};
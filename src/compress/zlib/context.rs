@@ -0,0 +1,104 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::reader::{read_dictionary_config, Error};
+use super::writer::header;
+use super::{BEST_COMPRESSION, DEFAULT_COMPRESSION, HUFFMAN_ONLY};
+use crate::compress::flate;
+use crate::errors;
+use crate::hash::adler32;
+
+/// compress_bound returns the maximum size of the ZLIB stream produced
+/// by compressing n bytes at any level.
+pub fn compress_bound(n: usize) -> usize {
+    // 2 bytes of header and 4 bytes of checksum.
+    flate::compress_bound(n) + 6
+}
+
+/// compress returns the ZLIB-compressed form of src at the given level.
+pub fn compress(src: &[u8], level: isize) -> std::io::Result<Vec<u8>> {
+    Context::new(level)?.compress(src)
+}
+
+/// decompress returns the data decompressed from the ZLIB stream src
+/// and verifies its checksum. Streams which need a preset dictionary
+/// are rejected.
+pub fn decompress(src: &[u8]) -> std::io::Result<Vec<u8>> {
+    Context::new(DEFAULT_COMPRESSION)?.decompress(src)
+}
+
+/// Context compresses and decompresses whole slices in memory,
+/// reusing the compressor and decompressor state between calls.
+pub struct Context {
+    level: isize,
+    flate: flate::Context,
+}
+
+impl Context {
+    /// new returns a Context compressing at the given level.
+    pub fn new(level: isize) -> std::io::Result<Self> {
+        if !(HUFFMAN_ONLY..=BEST_COMPRESSION).contains(&level) {
+            return Err(errors::new_stdio_other_error(format!(
+                "zlib: invalid compression level: {}",
+                level
+            )));
+        }
+        Ok(Self {
+            level,
+            flate: flate::Context::new(level)?,
+        })
+    }
+
+    /// compress returns the compressed form of src.
+    pub fn compress(&mut self, src: &[u8]) -> std::io::Result<Vec<u8>> {
+        let mut dst = Vec::with_capacity(compress_bound(src.len()));
+        self.compress_to(src, &mut dst)?;
+        Ok(dst)
+    }
+
+    /// compress_to appends the compressed form of src to dst and returns
+    /// the number of bytes appended.
+    pub fn compress_to(&mut self, src: &[u8], dst: &mut Vec<u8>) -> std::io::Result<usize> {
+        let start = dst.len();
+        dst.reserve(compress_bound(src.len()));
+        dst.extend_from_slice(&header(self.level, &[]));
+        self.flate.compress_to(src, dst)?;
+        // ZLIB (RFC 1950) is big-endian, unlike GZIP (RFC 1952).
+        dst.extend_from_slice(&adler32::checksum(src).to_be_bytes());
+        Ok(dst.len() - start)
+    }
+
+    /// decompress returns the data decompressed from the ZLIB stream src.
+    pub fn decompress(&mut self, src: &[u8]) -> std::io::Result<Vec<u8>> {
+        let mut dst = Vec::new();
+        self.decompress_to(src, &mut dst)?;
+        Ok(dst)
+    }
+
+    /// decompress_to appends the data decompressed from the ZLIB stream src
+    /// to dst and returns the number of bytes appended.
+    /// Any data following the end of the stream is ignored.
+    pub fn decompress_to(&mut self, src: &[u8], dst: &mut Vec<u8>) -> std::io::Result<usize> {
+        let start = dst.len();
+        self.decompress_stream(src, dst, start)?;
+        Ok(dst.len() - start)
+    }
+
+    fn decompress_stream(
+        &mut self,
+        mut src: &[u8],
+        dst: &mut Vec<u8>,
+        start: usize,
+    ) -> std::io::Result<()> {
+        read_dictionary_config(&mut src, &[])?;
+        let rest = self.flate.decompress_stream(src, dst)?;
+        if rest.len() < 4 {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+        }
+        if u32::from_be_bytes(rest[..4].try_into().unwrap()) != adler32::checksum(&dst[start..]) {
+            return Err(Error::ErrChecksum.into());
+        }
+        Ok(())
+    }
+}
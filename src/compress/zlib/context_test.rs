@@ -0,0 +1,62 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::{compress, compress_bound, decompress, Context, Error, Writer};
+use super::{BEST_COMPRESSION, DEFAULT_COMPRESSION, HUFFMAN_ONLY};
+use crate::compress::flate;
+use crate::os as ggos;
+
+#[test]
+fn test_compress() {
+    let data = ggos::read_file("src/compress/testdata/e.txt").unwrap();
+    let mut ctx = Context::new(DEFAULT_COMPRESSION).unwrap();
+    for level in HUFFMAN_ONLY..=BEST_COMPRESSION {
+        let mut want = Vec::new();
+        let mut w = Writer::new_level(&mut want, level);
+        w.write(&data).unwrap();
+        w.close().unwrap();
+
+        let got = compress(&data, level).unwrap();
+        assert!(got == want, "level {}: output differs from Writer", level);
+        assert!(got.len() <= compress_bound(data.len()));
+        assert!(ctx.decompress(&got).unwrap() == data, "level {}", level);
+    }
+    assert!(compress(&data, 10).is_err());
+}
+
+#[test]
+fn test_compress_bound() {
+    let data = flate::context_test::random_data(100_000);
+    let compressed = compress(&data, BEST_COMPRESSION).unwrap();
+    assert!(compressed.len() <= compress_bound(data.len()));
+    assert_eq!(compressed.capacity(), compress_bound(data.len()));
+}
+
+#[test]
+fn test_decompress_errors() {
+    let data = flate::index_test::test_data(10_000);
+    let compressed = compress(&data, DEFAULT_COMPRESSION).unwrap();
+
+    let mut bad = compressed.clone();
+    bad[0] ^= 1;
+    let err = decompress(&bad).unwrap_err();
+    assert_eq!(err.to_string(), Error::ErrHeader.to_string());
+
+    let mut bad = compressed.clone();
+    let n = bad.len();
+    bad[n - 1] ^= 1;
+    let err = decompress(&bad).unwrap_err();
+    assert_eq!(err.to_string(), Error::ErrChecksum.to_string());
+
+    let err = decompress(&compressed[..compressed.len() - 2]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+
+    // A stream compressed with a dictionary.
+    let mut with_dict = Vec::new();
+    let mut w = Writer::new_level_dict(&mut with_dict, DEFAULT_COMPRESSION, b"dict").unwrap();
+    w.write(&data).unwrap();
+    w.close().unwrap();
+    let err = decompress(&with_dict).unwrap_err();
+    assert_eq!(err.to_string(), Error::ErrDictionary.to_string());
+}
@@ -36,14 +36,18 @@
 //!     r.read_to_string(&mut output).unwrap();
 //!     println!("{}", output);
 
+mod context;
 mod reader;
 mod writer;
 
+pub use context::{compress, compress_bound, decompress, Context};
 pub use reader::{Error, Reader};
 pub use writer::{
     Writer, BEST_COMPRESSION, BEST_SPEED, DEFAULT_COMPRESSION, HUFFMAN_ONLY, NO_COMPRESSION,
 };
 
+#[cfg(test)]
+mod context_test;
 #[cfg(test)]
 mod reader_test;
 #[cfg(test)]
@@ -3,15 +3,17 @@
 // Use of this source code is governed by a BSD-style
 // license that can be found in the LICENSE file.
 
-use crate::compress::flate;
-use crate::errors;
+use crate::compat;
+use crate::compress::{copy_error, flate};
 use crate::hash::{self, adler32, Hash32};
 use std::io::Write;
 
 const ZLIB_DEFLATE: u8 = 8;
 const ZLIB_MAX_WINDOW: u8 = 7;
 
-#[derive(Debug)]
+/// Error describes invalid ZLIB data. Reader returns it wrapped
+/// in a std::io::Error of the ErrorKind::InvalidData kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Error {
     /// ErrChecksum is returned when reading ZLIB data that has an invalid checksum.
     ErrChecksum,
@@ -19,30 +21,6 @@ pub enum Error {
     ErrDictionary,
     /// ErrHeader is returned when reading ZLIB data that has an invalid header.
     ErrHeader,
-    StdIo(std::io::Error),
-}
-
-impl PartialEq for Error {
-    fn eq(&self, other: &Self) -> bool {
-        match self {
-            Error::ErrChecksum => matches!(other, Error::ErrChecksum),
-            Error::ErrDictionary => matches!(other, Error::ErrDictionary),
-            Error::ErrHeader => matches!(other, Error::ErrHeader),
-            Error::StdIo(e1) => match other {
-                Error::StdIo(e2) => e1.kind() == e2.kind(),
-                _ => false,
-            },
-        }
-    }
-}
-
-impl Error {
-    pub fn to_stdio_error(&self) -> std::io::Error {
-        match self {
-            Error::StdIo(e) => errors::copy_stdio_error(e),
-            _ => std::io::Error::new(std::io::ErrorKind::Other, self.to_string()),
-        }
-    }
 }
 
 impl std::fmt::Display for Error {
@@ -51,20 +29,15 @@ impl std::fmt::Display for Error {
             Error::ErrHeader => write!(f, "zlib: invalid header"),
             Error::ErrDictionary => write!(f, "zlib: invalid dictionary"),
             Error::ErrChecksum => write!(f, "zlib: invalid checksum"),
-            Error::StdIo(err) => write!(f, "{}", err),
         }
     }
 }
 
-impl From<std::io::Error> for Error {
-    fn from(error: std::io::Error) -> Self {
-        Error::StdIo(error)
-    }
-}
+impl std::error::Error for Error {}
 
-impl From<std::io::ErrorKind> for Error {
-    fn from(kind: std::io::ErrorKind) -> Self {
-        Error::StdIo(std::io::Error::from(kind))
+impl From<Error> for std::io::Error {
+    fn from(e: Error) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, e)
     }
 }
 
@@ -72,13 +45,16 @@ impl From<std::io::ErrorKind> for Error {
 // If r does not implement io.ByteReader, the decompressor may read more
 // data than necessary from r.
 // It is the caller's responsibility to call close on the Reader when done.
-pub struct Reader<'a, Input: std::io::BufRead> {
-    decompressor: flate::Reader<&'a mut Input>,
+pub struct Reader<Input: std::io::BufRead> {
+    decompressor: flate::Reader<Input>,
     digest: adler32::Digest,
+    // eof is set once the checksum has been verified.
+    eof: bool,
+    err: Option<std::io::Error>,
 }
 
-impl<'a, Input: std::io::BufRead> Reader<'a, Input> {
-    pub fn new(r: &'a mut Input) -> Result<Self, Error> {
+impl<Input: std::io::BufRead> Reader<Input> {
+    pub fn new(r: Input) -> std::io::Result<Self> {
         Self::new_dict(r, &[])
     }
 
@@ -88,8 +64,8 @@ impl<'a, Input: std::io::BufRead> Reader<'a, Input> {
     //
     // ggstd not implemented:
     // The Reader returned by new_reader_dict also implements Resetter.
-    pub fn new_dict(r: &'a mut Input, dict: &'a [u8]) -> Result<Self, Error> {
-        let have_dict = read_dictionary_config(r, dict)?;
+    pub fn new_dict(mut r: Input, dict: &[u8]) -> std::io::Result<Self> {
+        let have_dict = read_dictionary_config(&mut r, dict)?;
         Ok(Self {
             decompressor: if have_dict {
                 flate::Reader::new_dict(r, dict)
@@ -97,37 +73,21 @@ impl<'a, Input: std::io::BufRead> Reader<'a, Input> {
                 flate::Reader::new(r)
             },
             digest: hash::adler32::new(),
+            eof: false,
+            err: None,
         })
     }
 
-    pub fn read(&mut self, p: &mut [u8]) -> Result<usize, Error> {
-        let (n, err) = crate::io::Reader::read(&mut self.decompressor, p);
-        self.digest.write_all(&p[0..n])?;
-
-        if n > 0 {
-            return Ok(n);
-        }
-
-        if let Some(err) = err {
-            return Err(Error::StdIo(err));
-        }
-
-        // n == 0 or an error happened and it is EOF
-
-        // Finished file; check checksum.
+    /// read_checksum reads the checksum at the end of the stream
+    /// and compares it with the digest of the decompressed data.
+    fn read_checksum(&mut self) -> std::io::Result<()> {
         let mut scratch = [0; 4];
-
         std::io::Read::read_exact(self.decompressor.input_reader(), &mut scratch)?;
-
         // ZLIB (RFC 1950) is big-endian, unlike GZIP (RFC 1952).
-        let checksum = (scratch[0] as u32) << 24
-            | (scratch[1] as u32) << 16
-            | (scratch[2] as u32) << 8
-            | (scratch[3] as u32);
-        if checksum != self.digest.sum32() {
-            return Err(Error::ErrChecksum);
+        if u32::from_be_bytes(scratch) != self.digest.sum32() {
+            return Err(Error::ErrChecksum.into());
         }
-        Ok(n)
+        Ok(())
     }
 
     /// Calling close does not close the wrapped io.Reader originally passed to new_reader.
@@ -140,48 +100,88 @@ impl<'a, Input: std::io::BufRead> Reader<'a, Input> {
     /// reset discards any buffered data and resets the Reader as if it was
     /// newly initialized with the given reader.
     /// This permits reusing a Reader instead of allocating a new one.
-    pub fn reset(&mut self, r: &'a mut Input, dict: &'a [u8]) -> Result<(), Error> {
-        let have_dict = read_dictionary_config(r, dict)?;
-        self.decompressor = if have_dict {
-            flate::Reader::new_dict(r, dict)
-        } else {
-            flate::Reader::new(r)
-        };
+    pub fn reset(&mut self, mut r: Input, dict: &[u8]) -> std::io::Result<()> {
+        let have_dict = read_dictionary_config(&mut r, dict)?;
+        self.decompressor
+            .reset(r, if have_dict { dict } else { &[] });
         self.digest = hash::adler32::new();
+        self.eof = false;
+        self.err = None;
         Ok(())
     }
+
+    /// get_ref returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &Input {
+        self.decompressor.get_ref()
+    }
+
+    /// get_mut returns a mutable reference to the underlying reader.
+    /// Reading from it directly corrupts the decompressed stream.
+    pub fn get_mut(&mut self) -> &mut Input {
+        self.decompressor.get_mut()
+    }
+
+    /// into_inner returns the underlying reader. Once the Reader has been
+    /// read to the end, the underlying reader is positioned right after the
+    /// ZLIB checksum.
+    pub fn into_inner(self) -> Input {
+        self.decompressor.into_inner()
+    }
 }
 
-impl<Input: std::io::BufRead> std::io::Read for Reader<'_, Input> {
+impl<Input: std::io::BufRead> std::io::Read for Reader<Input> {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        let res = Reader::read(self, buf);
-        match res {
-            Ok(n) => Ok(n),
-            Err(err) => Err(err.to_stdio_error()),
+        let n = compat::copy(buf, std::io::BufRead::fill_buf(self)?);
+        std::io::BufRead::consume(self, n);
+        Ok(n)
+    }
+}
+
+impl<Input: std::io::BufRead> std::io::BufRead for Reader<Input> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        if let Some(err) = &self.err {
+            return Err(copy_error(err));
         }
+        if self.eof {
+            return Ok(&[]);
+        }
+        if self.decompressor.fill_buf()?.is_empty() {
+            // Finished file; check checksum.
+            self.eof = true;
+            if let Err(err) = self.read_checksum() {
+                self.err = Some(copy_error(&err));
+                return Err(err);
+            }
+        }
+        Ok(self.decompressor.buffer())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = amt.min(self.decompressor.buffer().len());
+        self.digest
+            .write_all(&self.decompressor.buffer()[..amt])
+            .unwrap();
+        self.decompressor.consume(amt);
     }
 }
 
 /// Read the header, validate it and return true if the dictionary is used.
-fn read_dictionary_config<Input: std::io::BufRead>(
+pub(super) fn read_dictionary_config<Input: std::io::BufRead>(
     r: &mut Input,
     dict: &[u8],
-) -> Result<bool, Error> {
+) -> std::io::Result<bool> {
     let mut scratch = [0; 4];
     r.read_exact(&mut scratch[0..2])?;
     let h = ((scratch[0] as usize) << 8) | (scratch[1] as usize);
     if (scratch[0] & 0x0f != ZLIB_DEFLATE) || (scratch[0] >> 4 > ZLIB_MAX_WINDOW) || (h % 31 != 0) {
-        return Err(Error::ErrHeader);
+        return Err(Error::ErrHeader.into());
     }
     let have_dict = scratch[1] & 0x20 != 0;
     if have_dict {
         r.read_exact(&mut scratch[0..4])?;
-        let checksum = (scratch[0] as u32) << 24
-            | (scratch[1] as u32) << 16
-            | (scratch[2] as u32) << 8
-            | (scratch[3] as u32);
+        let checksum = u32::from_be_bytes(scratch);
         if checksum != hash::adler32::checksum(dict) {
-            return Err(Error::ErrDictionary);
+            return Err(Error::ErrDictionary.into());
         }
     }
     Ok(have_dict)
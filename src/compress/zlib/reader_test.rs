@@ -12,7 +12,7 @@ struct ZlibTest {
     raw: &'static str,
     compressed: &'static [u8],
     dict: Option<&'static [u8]>,
-    expected_err: Option<zlib::Error>,
+    expected_err: Option<std::io::Error>,
 }
 
 // Compare-to-golden test data was generated by the ZLIB example program at
@@ -25,14 +25,14 @@ fn get_tests() -> [ZlibTest; 14] {
             raw: "",
             compressed: &[],
             dict: None,
-            expected_err: Some(zlib::Error::from(std::io::ErrorKind::UnexpectedEof)),
+            expected_err: Some(std::io::ErrorKind::UnexpectedEof.into()),
         },
         ZlibTest {
             _desc: "truncated dict",
             raw: "",
             compressed: &[0x78, 0xbb],
             dict: Some(&[0x00]),
-            expected_err: Some(zlib::Error::from(std::io::ErrorKind::UnexpectedEof)),
+            expected_err: Some(std::io::ErrorKind::UnexpectedEof.into()),
         },
         ZlibTest {
             _desc: "truncated checksum",
@@ -42,7 +42,7 @@ fn get_tests() -> [ZlibTest; 14] {
                 0xcf, 0x2f, 0xca, 0x49, 0x01, 0x04, 0x00, 0x00, 0xff, 0xff,
             ],
             dict: Some(&[0x00]),
-            expected_err: Some(zlib::Error::from(std::io::ErrorKind::UnexpectedEof)),
+            expected_err: Some(std::io::ErrorKind::UnexpectedEof.into()),
         },
         ZlibTest {
             _desc: "empty",
@@ -66,32 +66,28 @@ fn get_tests() -> [ZlibTest; 14] {
             raw: "",
             compressed: &[0x88, 0x98, 0x03, 0x00, 0x00, 0x00, 0x00, 0x01],
             dict: None,
-            expected_err: Some(zlib::Error::ErrHeader),
+            expected_err: Some(zlib::Error::ErrHeader.into()),
         },
         ZlibTest {
             _desc: "bad header (FCHECK)",
             raw: "",
             compressed: &[0x78, 0x9f, 0x03, 0x00, 0x00, 0x00, 0x00, 0x01],
             dict: None,
-            expected_err: Some(zlib::Error::ErrHeader),
+            expected_err: Some(zlib::Error::ErrHeader.into()),
         },
         ZlibTest {
             _desc: "bad checksum",
             raw: "",
             compressed: &[0x78, 0x9c, 0x03, 0x00, 0x00, 0x00, 0x00, 0xff],
             dict: None,
-            // expected_err: Some(zlib::Error::ErrChecksum),
-            expected_err: Some(zlib::Error::StdIo(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "zlib: invalid checksum",
-            ))),
+            expected_err: Some(zlib::Error::ErrChecksum.into()),
         },
         ZlibTest {
             _desc: "not enough data",
             raw: "",
             compressed: &[0x78, 0x9c, 0x03, 0x00, 0x00, 0x00],
             dict: None,
-            expected_err: Some(zlib::Error::from(std::io::ErrorKind::UnexpectedEof)),
+            expected_err: Some(std::io::ErrorKind::UnexpectedEof.into()),
         },
         ZlibTest {
             _desc: "excess data is silently ignored",
@@ -122,7 +118,7 @@ fn get_tests() -> [ZlibTest; 14] {
                 0x00, 0x24, 0x12, 0x04, 0x74,
             ],
             dict: Some(&[0x48, 0x65, 0x6c, 0x6c]),
-            expected_err: Some(zlib::Error::ErrDictionary),
+            expected_err: Some(zlib::Error::ErrDictionary.into()),
         },
         ZlibTest {
             _desc: "truncated zlib stream amid raw-block",
@@ -131,41 +127,35 @@ fn get_tests() -> [ZlibTest; 14] {
                 0x78, 0x9c, 0x00, 0x0c, 0x00, 0xf3, 0xff, 0x68, 0x65, 0x6c, 0x6c, 0x6f,
             ],
             dict: None,
-            expected_err: Some(zlib::Error::from(std::io::ErrorKind::UnexpectedEof)),
+            expected_err: Some(std::io::ErrorKind::UnexpectedEof.into()),
         },
         ZlibTest {
             _desc: "truncated zlib stream amid fixed-block",
             raw: "He",
             compressed: &[0x78, 0x9c, 0xf2, 0x48, 0xcd],
             dict: None,
-            expected_err: Some(zlib::Error::from(std::io::ErrorKind::UnexpectedEof)),
+            expected_err: Some(std::io::ErrorKind::UnexpectedEof.into()),
         },
     ]
 }
 
 #[test]
 fn test_decompressor() {
-    fn compare_errors_zlib(got: &zlib::Error, want: &Option<zlib::Error>) {
-        if want.is_none() {
-            panic!("unexpected error {:?}", got);
-        }
+    fn compare_errors(got: &std::io::Error, want: &Option<std::io::Error>) {
+        let want = match want {
+            Some(want) => want,
+            None => panic!("unexpected error {:?}", got),
+        };
         assert_eq!(
-            got,
-            want.as_ref().unwrap(),
+            got.kind(),
+            want.kind(),
             "expecting {:?}, got {:?}",
-            want.as_ref().unwrap(),
+            want,
             got
         );
-    }
-    fn compare_errors(got: &std::io::Error, want: &Option<zlib::Error>) {
-        if want.is_none() {
-            panic!("unexpected error {:?}", got);
-        }
-        if let zlib::Error::StdIo(want) = want.as_ref().unwrap() {
-            assert_eq!(got.kind(), want.kind());
-            return;
-        }
-        panic!("expecting {:?}, got {:?}", want.as_ref().unwrap(), got);
+        let want = want.get_ref().and_then(|e| e.downcast_ref::<zlib::Error>());
+        let got = got.get_ref().and_then(|e| e.downcast_ref::<zlib::Error>());
+        assert_eq!(got, want);
     }
     for tt in get_tests() {
         let mut input = bytes::Reader::new(tt.compressed);
@@ -177,7 +167,7 @@ fn test_decompressor() {
         let mut zr = match zr {
             Ok(zr) => zr,
             Err(err) => {
-                compare_errors_zlib(&err, &tt.expected_err);
+                compare_errors(&err, &tt.expected_err);
                 continue;
             }
         };
@@ -198,3 +188,19 @@ fn test_decompressor() {
         assert!(res.is_ok());
     }
 }
+
+#[test]
+fn test_reader_into_inner() {
+    let mut w = zlib::Writer::new(Vec::new());
+    w.write(b"hello, world").unwrap();
+    w.close().unwrap();
+    let mut compressed = w.into_inner();
+    compressed.extend_from_slice(b"trailer");
+
+    let mut r = zlib::Reader::new(compressed.as_slice()).unwrap();
+    let mut output = String::new();
+    r.read_to_string(&mut output).unwrap();
+    assert_eq!(output, "hello, world");
+    assert_eq!(*r.get_ref(), b"trailer");
+    assert_eq!(r.into_inner(), b"trailer");
+}
@@ -19,22 +19,22 @@ pub const HUFFMAN_ONLY: isize = flate::HUFFMAN_ONLY;
 
 /// A Writer takes data written to it and writes the compressed
 /// form of that data to an underlying writer (output).
-pub struct Writer<'a, Output: std::io::Write> {
+pub struct Writer<Output: std::io::Write> {
     level: isize,
-    dict: &'a [u8],
-    compressor: flate::Writer<'a, Output>,
+    dict: Vec<u8>,
+    compressor: flate::Writer<Output>,
     digest: Box<dyn hash::Hash32>,
     scratch: [u8; 4],
     wrote_header: bool,
 }
 
-impl<'a, Output: std::io::Write> Writer<'a, Output> {
+impl<Output: std::io::Write> Writer<Output> {
     /// new creates a new Writer.
     /// Writes to the returned Writer are compressed and written to w.
     ///
     /// It is the caller's responsibility to call close on the Writer when done.
     /// Writes may be buffered and not flushed until close.
-    pub fn new(w: &'a mut Output) -> Self {
+    pub fn new(w: Output) -> Self {
         Writer::new_level_dict(w, DEFAULT_COMPRESSION, &[]).unwrap()
     }
 
@@ -44,20 +44,15 @@ impl<'a, Output: std::io::Write> Writer<'a, Output> {
     /// The compression level can be DEFAULT_COMPRESSION, NO_COMPRESSION, HUFFMAN_ONLY
     /// or any integer value between BEST_SPEED and BEST_COMPRESSION inclusive.
     /// The error returned will be nil if the level is valid.
-    pub fn new_level(w: &'a mut Output, level: isize) -> Self {
+    pub fn new_level(w: Output, level: isize) -> Self {
         Writer::new_level_dict(w, level, &[]).unwrap()
     }
 
     /// new_level_dict is like new_level but specifies a dictionary to
     /// compress with.
     ///
-    /// The dictionary may be nil. If not, its contents should not be modified until
-    /// the Writer is closed.
-    pub fn new_level_dict(
-        w: &'a mut Output,
-        level: isize,
-        dict: &'a [u8],
-    ) -> std::io::Result<Self> {
+    /// The dictionary may be empty.
+    pub fn new_level_dict(w: Output, level: isize, dict: &[u8]) -> std::io::Result<Self> {
         if !(HUFFMAN_ONLY..=BEST_COMPRESSION).contains(&level) {
             return Err(errors::new_stdio_other_error(format!(
                 "zlib: invalid compression level: {}",
@@ -66,7 +61,7 @@ impl<'a, Output: std::io::Write> Writer<'a, Output> {
         }
         Ok(Writer {
             level,
-            dict,
+            dict: dict.to_vec(),
             compressor: flate::Writer::new_dict(w, level, dict).unwrap(),
             digest: Box::new(adler32::new()),
             scratch: [0; 4],
@@ -77,7 +72,7 @@ impl<'a, Output: std::io::Write> Writer<'a, Output> {
     /// reset clears the state of the Writer z such that it is equivalent to its
     /// initial state from new_level or new_level_dict, but instead writing
     /// to w.
-    pub fn reset(&mut self, w: &'a mut Output) {
+    pub fn reset(&mut self, w: Output) {
         // self.w = w;
         self.compressor.reset(w);
         self.digest.reset();
@@ -91,32 +86,12 @@ impl<'a, Output: std::io::Write> Writer<'a, Output> {
             return Ok(());
         }
         self.wrote_header = true;
-        // ZLIB has a two-u8 header (as documented in RFC 1950).
-        // The first four bits is the CINFO (compression info), which is 7 for the default deflate window size.
-        // The next four bits is the CM (compression method), which is 8 for deflate.
-        self.scratch[0] = 0x78;
-        // The next two bits is the FLEVEL (compression level). The four values are:
-        // 0=fastest, 1=fast, 2=default, 3=best.
-        // The next bit, FDICT, is set if a dictionary is given.
-        // The final five FCHECK bits form a mod-31 checksum.
-        self.scratch[1] = match self.level {
-            -2 | 0 | 1 => 0 << 6,
-            2..=5 => 1 << 6,
-            6 | -1 => 2 << 6,
-            7..=9 => 3 << 6,
-            _ => {
-                panic!("unreachable");
-            }
-        };
-        if !self.dict.is_empty() {
-            self.scratch[1] |= 1 << 5;
-        }
-        self.scratch[1] +=
-            (31 - (((self.scratch[0] as u16) << 8) + (self.scratch[1] as u16)) % 31) as u8;
-        self.compressor.output().write_all(&self.scratch[0..2])?;
+        self.compressor
+            .output()
+            .write_all(&header(self.level, &self.dict))?;
         if !self.dict.is_empty() {
             // The next four bytes are the Adler-32 checksum of the dictionary.
-            binary::BIG_ENDIAN.put_uint32(&mut self.scratch[..], adler32::checksum(self.dict));
+            binary::BIG_ENDIAN.put_uint32(&mut self.scratch[..], adler32::checksum(&self.dict));
             self.compressor.output().write_all(&self.scratch[0..4])?;
         }
         Ok(())
@@ -150,12 +125,29 @@ impl<'a, Output: std::io::Write> Writer<'a, Output> {
     }
 
     /// flush flushes the Writer to its underlying ggio::Writer.
-    pub fn flush(&'a mut self) -> std::io::Result<()> {
+    pub fn flush(&mut self) -> std::io::Result<()> {
         self.write_header()?;
         self.compressor.flush()?;
         Ok(())
     }
 
+    /// get_ref returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Output {
+        self.compressor.get_ref()
+    }
+
+    /// get_mut returns a mutable reference to the underlying writer.
+    /// Writing to it directly corrupts the compressed stream.
+    pub fn get_mut(&mut self) -> &mut Output {
+        self.compressor.get_mut()
+    }
+
+    /// into_inner returns the underlying writer. close must be called before
+    /// to write the rest of the compressed data and the checksum.
+    pub fn into_inner(self) -> Output {
+        self.compressor.into_inner()
+    }
+
     /// close closes the Writer, flushing any unwritten data to the underlying
     /// writer, but does not close the underlying writer.
     pub fn close(&mut self) -> std::io::Result<()> {
@@ -168,3 +160,40 @@ impl<'a, Output: std::io::Write> Writer<'a, Output> {
         Ok(())
     }
 }
+
+impl<Output: std::io::Write> std::io::Write for Writer<Output> {
+    fn write(&mut self, p: &[u8]) -> std::io::Result<usize> {
+        Writer::write(self, p)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Writer::flush(self)
+    }
+}
+
+/// header returns the two-byte ZLIB header for the level and dictionary.
+pub(super) fn header(level: isize, dict: &[u8]) -> [u8; 2] {
+    let mut hdr = [0; 2];
+    // ZLIB has a two-u8 header (as documented in RFC 1950).
+    // The first four bits is the CINFO (compression info), which is 7 for the default deflate window size.
+    // The next four bits is the CM (compression method), which is 8 for deflate.
+    hdr[0] = 0x78;
+    // The next two bits is the FLEVEL (compression level). The four values are:
+    // 0=fastest, 1=fast, 2=default, 3=best.
+    // The next bit, FDICT, is set if a dictionary is given.
+    // The final five FCHECK bits form a mod-31 checksum.
+    hdr[1] = match level {
+        -2 | 0 | 1 => 0 << 6,
+        2..=5 => 1 << 6,
+        6 | -1 => 2 << 6,
+        7..=9 => 3 << 6,
+        _ => {
+            panic!("unreachable");
+        }
+    };
+    if !dict.is_empty() {
+        hdr[1] |= 1 << 5;
+    }
+    hdr[1] += (31 - (((hdr[0] as u16) << 8) + (hdr[1] as u16)) % 31) as u8;
+    hdr
+}
@@ -3,9 +3,12 @@
 // Use of this source code is governed by a BSD-style
 // license that can be found in the LICENSE file.
 
+use super::block;
+use super::consts::{SBOX0, SBOX1};
 use crate::crypto::aes;
 use crate::crypto::cipher::Block;
 use crate::encoding::hex;
+use crate::math::rand;
 
 // // See const.go for overview of math here.
 
@@ -82,6 +85,39 @@ use crate::encoding::hex;
 // 	}
 // }
 
+/// Check that the bitsliced S-box circuits agree with the S-box tables
+/// from FIPS 197, and that the tables are inverses of each other.
+#[test]
+fn test_sboxes() {
+    for i in 0..256 {
+        assert_eq!(SBOX0[SBOX1[i] as usize], i as u8, "sbox0[sbox1[{:#x}]]", i);
+        assert_eq!(SBOX1[SBOX0[i] as usize], i as u8, "sbox1[sbox0[{:#x}]]", i);
+    }
+    // The bitsliced state holds 64 bytes, so the 256 inputs
+    // are checked in four batches.
+    for batch in 0..4 {
+        let mut blocks = [[0u8; 16]; 4];
+        for (i, b) in blocks.iter_mut().flatten().enumerate() {
+            *b = (batch * 64 + i) as u8;
+        }
+        let input = blocks;
+
+        let mut q = block::load(&blocks);
+        block::sbox(&mut q);
+        block::store(q, &mut blocks);
+        for (x, y) in input.iter().flatten().zip(blocks.iter().flatten()) {
+            assert_eq!(*y, SBOX0[*x as usize], "sbox({:#x})", x);
+        }
+
+        let mut q = block::load(&input);
+        block::inv_sbox(&mut q);
+        block::store(q, &mut blocks);
+        for (x, y) in input.iter().flatten().zip(blocks.iter().flatten()) {
+            assert_eq!(*y, SBOX1[*x as usize], "inv_sbox({:#x})", x);
+        }
+    }
+}
+
 // // Test that encryption tables are correct.
 // // (Can adapt this code to generate them too.)
 // func TestTe(t *testing.T) {
@@ -123,121 +159,78 @@ use crate::encoding::hex;
 // // Test vectors are from FIPS 197:
 // //	https://csrc.nist.gov/publications/fips/fips197/fips-197.pdf
 
-// // Appendix A of FIPS 197: Key expansion examples
-// type KeyTest struct {
-// 	key: &[u8]
-// 	enc []uint32
-// 	dec []uint32 // decryption expansion; not in FIPS 197, computed from C implementation.
-// }
+/// Appendix A of FIPS 197: Key expansion examples
+struct KeyTest {
+    key: &'static [u8],
+    enc: &'static [u32],
+}
 
-// var keyTests = []KeyTest{
-// 	{
-// 		// A.1.  Expansion of a 128-bit Cipher Key
-// 		[]byte{0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c},
-// 		[]uint32{
-// 			0x2b7e1516, 0x28aed2a6, 0xabf71588, 0x09cf4f3c,
-// 			0xa0fafe17, 0x88542cb1, 0x23a33939, 0x2a6c7605,
-// 			0xf2c295f2, 0x7a96b943, 0x5935807a, 0x7359f67f,
-// 			0x3d80477d, 0x4716fe3e, 0x1e237e44, 0x6d7a883b,
-// 			0xef44a541, 0xa8525b7f, 0xb671253b, 0xdb0bad00,
-// 			0xd4d1c6f8, 0x7c839d87, 0xcaf2b8bc, 0x11f915bc,
-// 			0x6d88a37a, 0x110b3efd, 0xdbf98641, 0xca0093fd,
-// 			0x4e54f70e, 0x5f5fc9f3, 0x84a64fb2, 0x4ea6dc4f,
-// 			0xead27321, 0xb58dbad2, 0x312bf560, 0x7f8d292f,
-// 			0xac7766f3, 0x19fadc21, 0x28d12941, 0x575c006e,
-// 			0xd014f9a8, 0xc9ee2589, 0xe13f0cc8, 0xb6630ca6,
-// 		},
-// 		[]uint32{
-// 			0xd014f9a8, 0xc9ee2589, 0xe13f0cc8, 0xb6630ca6,
-// 			0xc7b5a63, 0x1319eafe, 0xb0398890, 0x664cfbb4,
-// 			0xdf7d925a, 0x1f62b09d, 0xa320626e, 0xd6757324,
-// 			0x12c07647, 0xc01f22c7, 0xbc42d2f3, 0x7555114a,
-// 			0x6efcd876, 0xd2df5480, 0x7c5df034, 0xc917c3b9,
-// 			0x6ea30afc, 0xbc238cf6, 0xae82a4b4, 0xb54a338d,
-// 			0x90884413, 0xd280860a, 0x12a12842, 0x1bc89739,
-// 			0x7c1f13f7, 0x4208c219, 0xc021ae48, 0x969bf7b,
-// 			0xcc7505eb, 0x3e17d1ee, 0x82296c51, 0xc9481133,
-// 			0x2b3708a7, 0xf262d405, 0xbc3ebdbf, 0x4b617d62,
-// 			0x2b7e1516, 0x28aed2a6, 0xabf71588, 0x9cf4f3c,
-// 		},
-// 	},
-// 	{
-// 		// A.2.  Expansion of a 192-bit Cipher Key
-// 		[]byte{
-// 			0x8e, 0x73, 0xb0, 0xf7, 0xda, 0x0e, 0x64, 0x52, 0xc8, 0x10, 0xf3, 0x2b, 0x80, 0x90, 0x79, 0xe5,
-// 			0x62, 0xf8, 0xea, 0xd2, 0x52, 0x2c, 0x6b, 0x7b,
-// 		},
-// 		[]uint32{
-// 			0x8e73b0f7, 0xda0e6452, 0xc810f32b, 0x809079e5,
-// 			0x62f8ead2, 0x522c6b7b, 0xfe0c91f7, 0x2402f5a5,
-// 			0xec12068e, 0x6c827f6b, 0x0e7a95b9, 0x5c56fec2,
-// 			0x4db7b4bd, 0x69b54118, 0x85a74796, 0xe92538fd,
-// 			0xe75fad44, 0xbb095386, 0x485af057, 0x21efb14f,
-// 			0xa448f6d9, 0x4d6dce24, 0xaa326360, 0x113b30e6,
-// 			0xa25e7ed5, 0x83b1cf9a, 0x27f93943, 0x6a94f767,
-// 			0xc0a69407, 0xd19da4e1, 0xec1786eb, 0x6fa64971,
-// 			0x485f7032, 0x22cb8755, 0xe26d1352, 0x33f0b7b3,
-// 			0x40beeb28, 0x2f18a259, 0x6747d26b, 0x458c553e,
-// 			0xa7e1466c, 0x9411f1df, 0x821f750a, 0xad07d753,
-// 			0xca400538, 0x8fcc5006, 0x282d166a, 0xbc3ce7b5,
-// 			0xe98ba06f, 0x448c773c, 0x8ecc7204, 0x01002202,
-// 		},
-// 		nil,
-// 	},
-// 	{
-// 		// A.3.  Expansion of a 256-bit Cipher Key
-// 		[]byte{
-// 			0x60, 0x3d, 0xeb, 0x10, 0x15, 0xca, 0x71, 0xbe, 0x2b, 0x73, 0xae, 0xf0, 0x85, 0x7d, 0x77, 0x81,
-// 			0x1f, 0x35, 0x2c, 0x07, 0x3b, 0x61, 0x08, 0xd7, 0x2d, 0x98, 0x10, 0xa3, 0x09, 0x14, 0xdf, 0xf4,
-// 		},
-// 		[]uint32{
-// 			0x603deb10, 0x15ca71be, 0x2b73aef0, 0x857d7781,
-// 			0x1f352c07, 0x3b6108d7, 0x2d9810a3, 0x0914dff4,
-// 			0x9ba35411, 0x8e6925af, 0xa51a8b5f, 0x2067fcde,
-// 			0xa8b09c1a, 0x93d194cd, 0xbe49846e, 0xb75d5b9a,
-// 			0xd59aecb8, 0x5bf3c917, 0xfee94248, 0xde8ebe96,
-// 			0xb5a9328a, 0x2678a647, 0x98312229, 0x2f6c79b3,
-// 			0x812c81ad, 0xdadf48ba, 0x24360af2, 0xfab8b464,
-// 			0x98c5bfc9, 0xbebd198e, 0x268c3ba7, 0x09e04214,
-// 			0x68007bac, 0xb2df3316, 0x96e939e4, 0x6c518d80,
-// 			0xc814e204, 0x76a9fb8a, 0x5025c02d, 0x59c58239,
-// 			0xde136967, 0x6ccc5a71, 0xfa256395, 0x9674ee15,
-// 			0x5886ca5d, 0x2e2f31d7, 0x7e0af1fa, 0x27cf73c3,
-// 			0x749c47ab, 0x18501dda, 0xe2757e4f, 0x7401905a,
-// 			0xcafaaae3, 0xe4d59b34, 0x9adf6ace, 0xbd10190d,
-// 			0xfe4890d1, 0xe6188d0b, 0x046df344, 0x706c631e,
-// 		},
-// 		nil,
-// 	},
-// }
+const KEY_TESTS: &[KeyTest] = &[
+    KeyTest {
+        // A.1.  Expansion of a 128-bit Cipher Key
+        key: &[
+            0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf,
+            0x4f, 0x3c,
+        ],
+        enc: &[
+            0x2b7e1516, 0x28aed2a6, 0xabf71588, 0x09cf4f3c, 0xa0fafe17, 0x88542cb1, 0x23a33939,
+            0x2a6c7605, 0xf2c295f2, 0x7a96b943, 0x5935807a, 0x7359f67f, 0x3d80477d, 0x4716fe3e,
+            0x1e237e44, 0x6d7a883b, 0xef44a541, 0xa8525b7f, 0xb671253b, 0xdb0bad00, 0xd4d1c6f8,
+            0x7c839d87, 0xcaf2b8bc, 0x11f915bc, 0x6d88a37a, 0x110b3efd, 0xdbf98641, 0xca0093fd,
+            0x4e54f70e, 0x5f5fc9f3, 0x84a64fb2, 0x4ea6dc4f, 0xead27321, 0xb58dbad2, 0x312bf560,
+            0x7f8d292f, 0xac7766f3, 0x19fadc21, 0x28d12941, 0x575c006e, 0xd014f9a8, 0xc9ee2589,
+            0xe13f0cc8, 0xb6630ca6,
+        ],
+    },
+    KeyTest {
+        // A.2.  Expansion of a 192-bit Cipher Key
+        key: &[
+            0x8e, 0x73, 0xb0, 0xf7, 0xda, 0x0e, 0x64, 0x52, 0xc8, 0x10, 0xf3, 0x2b, 0x80, 0x90,
+            0x79, 0xe5, 0x62, 0xf8, 0xea, 0xd2, 0x52, 0x2c, 0x6b, 0x7b,
+        ],
+        enc: &[
+            0x8e73b0f7, 0xda0e6452, 0xc810f32b, 0x809079e5, 0x62f8ead2, 0x522c6b7b, 0xfe0c91f7,
+            0x2402f5a5, 0xec12068e, 0x6c827f6b, 0x0e7a95b9, 0x5c56fec2, 0x4db7b4bd, 0x69b54118,
+            0x85a74796, 0xe92538fd, 0xe75fad44, 0xbb095386, 0x485af057, 0x21efb14f, 0xa448f6d9,
+            0x4d6dce24, 0xaa326360, 0x113b30e6, 0xa25e7ed5, 0x83b1cf9a, 0x27f93943, 0x6a94f767,
+            0xc0a69407, 0xd19da4e1, 0xec1786eb, 0x6fa64971, 0x485f7032, 0x22cb8755, 0xe26d1352,
+            0x33f0b7b3, 0x40beeb28, 0x2f18a259, 0x6747d26b, 0x458c553e, 0xa7e1466c, 0x9411f1df,
+            0x821f750a, 0xad07d753, 0xca400538, 0x8fcc5006, 0x282d166a, 0xbc3ce7b5, 0xe98ba06f,
+            0x448c773c, 0x8ecc7204, 0x01002202,
+        ],
+    },
+    KeyTest {
+        // A.3.  Expansion of a 256-bit Cipher Key
+        key: &[
+            0x60, 0x3d, 0xeb, 0x10, 0x15, 0xca, 0x71, 0xbe, 0x2b, 0x73, 0xae, 0xf0, 0x85, 0x7d,
+            0x77, 0x81, 0x1f, 0x35, 0x2c, 0x07, 0x3b, 0x61, 0x08, 0xd7, 0x2d, 0x98, 0x10, 0xa3,
+            0x09, 0x14, 0xdf, 0xf4,
+        ],
+        enc: &[
+            0x603deb10, 0x15ca71be, 0x2b73aef0, 0x857d7781, 0x1f352c07, 0x3b6108d7, 0x2d9810a3,
+            0x0914dff4, 0x9ba35411, 0x8e6925af, 0xa51a8b5f, 0x2067fcde, 0xa8b09c1a, 0x93d194cd,
+            0xbe49846e, 0xb75d5b9a, 0xd59aecb8, 0x5bf3c917, 0xfee94248, 0xde8ebe96, 0xb5a9328a,
+            0x2678a647, 0x98312229, 0x2f6c79b3, 0x812c81ad, 0xdadf48ba, 0x24360af2, 0xfab8b464,
+            0x98c5bfc9, 0xbebd198e, 0x268c3ba7, 0x09e04214, 0x68007bac, 0xb2df3316, 0x96e939e4,
+            0x6c518d80, 0xc814e204, 0x76a9fb8a, 0x5025c02d, 0x59c58239, 0xde136967, 0x6ccc5a71,
+            0xfa256395, 0x9674ee15, 0x5886ca5d, 0x2e2f31d7, 0x7e0af1fa, 0x27cf73c3, 0x749c47ab,
+            0x18501dda, 0xe2757e4f, 0x7401905a, 0xcafaaae3, 0xe4d59b34, 0x9adf6ace, 0xbd10190d,
+            0xfe4890d1, 0xe6188d0b, 0x046df344, 0x706c631e,
+        ],
+    },
+];
 
-// // Test key expansion against FIPS 197 examples.
-// func TestExpandKey(t *testing.T) {
-// L:
-// 	for i, tt := range keyTests {
-// 		enc := make([]uint32, len(tt.enc))
-// 		var dec []uint32
-// 		if tt.dec != nil {
-// 			dec = make([]uint32, len(tt.dec))
-// 		}
-// 		// This test could only test Go version of expandKey because asm
-// 		// version might use different memory layout for expanded keys
-// 		// This is OK because we don't expose expanded keys to the outside
-// 		expandKeyGo(tt.key, enc, dec)
-// 		for j, v := range enc {
-// 			if v != tt.enc[j] {
-// 				t.Errorf("key %d: enc[%d] = %#x, want %#x", i, j, v, tt.enc[j])
-// 				continue L
-// 			}
-// 		}
-// 		for j, v := range dec {
-// 			if v != tt.dec[j] {
-// 				t.Errorf("key %d: dec[%d] = %#x, want %#x", i, j, v, tt.dec[j])
-// 				continue L
-// 			}
-// 		}
-// 	}
-// }
+/// Test key expansion against FIPS 197 examples.
+#[test]
+fn test_expand_key() {
+    for (i, tt) in KEY_TESTS.iter().enumerate() {
+        let mut enc = vec![0; tt.enc.len()];
+        block::expand_key(tt.key, &mut enc);
+        for (j, v) in enc.iter().enumerate() {
+            assert_eq!(*v, tt.enc[j], "key {}: enc[{}]", i, j);
+        }
+    }
+}
 
 struct CryptTest {
     key: &'static [u8],
@@ -346,6 +339,53 @@ fn test_cipher_decrypt() {
     }
 }
 
+/// Test the bitsliced software implementation against FIPS 197 examples,
+/// whether or not the CPU supports AES-NI.
+#[test]
+fn test_cipher_generic() {
+    for tt in ENCRYPT_TESTS {
+        let c = super::cipher::new_cipher_generic(tt.key);
+        let mut out = vec![0; tt.input.len()];
+        c.encrypt(&mut out, tt.input);
+        assert_eq!(tt.out, &out);
+
+        let mut plain = vec![0; tt.input.len()];
+        c.decrypt(&mut plain, tt.out);
+        assert_eq!(tt.input, &plain);
+    }
+}
+
+/// test_cross_check verifies that the cipher selected for this CPU
+/// agrees with the bitsliced implementation on random keys and blocks.
+#[test]
+fn test_cross_check() {
+    let mut r = rand::Rand::new(rand::new_source(1));
+    for key_len in [16, 24, 32] {
+        for _ in 0..50 {
+            let mut key = vec![0; key_len];
+            for b in key.iter_mut() {
+                *b = r.int63() as u8;
+            }
+            let mut input = [0; aes::BLOCK_SIZE];
+            for b in input.iter_mut() {
+                *b = r.int63() as u8;
+            }
+            let c = aes::Cipher::new(&key).unwrap();
+            let g = super::cipher::new_cipher_generic(&key);
+
+            let mut want = [0; aes::BLOCK_SIZE];
+            let mut got = [0; aes::BLOCK_SIZE];
+            g.encrypt(&mut want, &input);
+            c.encrypt(&mut got, &input);
+            assert_eq!(got, want, "encrypt mismatch for key {:?}", key);
+
+            g.decrypt(&mut want, &input);
+            c.decrypt(&mut got, &input);
+            assert_eq!(got, want, "decrypt mismatch for key {:?}", key);
+        }
+    }
+}
+
 // // Test short input/output.
 // // Assembly used to not notice.
 // // See issue 7928.
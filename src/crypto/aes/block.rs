@@ -3,377 +3,431 @@
 // Use of this source code is governed by a BSD-style
 // license that can be found in the LICENSE file.
 
-use super::consts::{POWX, SBOX0, SBOX1, TD0, TD1, TD2, TD3, TE0, TE1, TE2, TE3};
+use super::consts::POWX;
 use crate::encoding::binary::{self, ByteOrder};
 
-// This Go implementation is derived in part from the reference
-// ANSI C implementation, which carries the following notice:
+// This is a constant-time software implementation of AES. It does not use
+// lookup tables indexed by secret data; instead the cipher state is kept in
+// bitsliced form, and the S-box is computed with boolean operations.
 //
-//	rijndael-alg-fst.c
-//
-//	@version 3.0 (December 2000)
-//
-//	Optimised ANSI C code for the Rijndael cipher (now AES)
-//
-//	@author Vincent Rijmen <vincent.rijmen@esat.kuleuven.ac.be>
-//	@author Antoon Bosselaers <antoon.bosselaers@esat.kuleuven.ac.be>
-//	@author Paulo Barreto <paulo.barreto@terra.com.br>
-//
-//	This code is hereby placed in the public domain.
-//
-//	THIS SOFTWARE IS PROVIDED BY THE AUTHORS ''AS IS'' AND ANY EXPRESS
-//	OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
-//	WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
-//	ARE DISCLAIMED.  IN NO EVENT SHALL THE AUTHORS OR CONTRIBUTORS BE
-//	LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
-//	CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
-//	SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR
-//	BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
-//	WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE
-//	OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE,
-//	EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+// The bitsliced representation is the one used by Thomas Pornin's aes_ct64
+// implementation in BearSSL: up to four blocks are processed in parallel, and
+// q[i] holds bit i of each of the 64 state bytes. Within each q[i], the 16
+// bits of a row are grouped by column, and the 4 bits of a column belong to
+// the 4 blocks.
 //
 // See FIPS 197 for specification, and see Daemen and Rijmen's Rijndael submission
 // for implementation details.
 //	https://csrc.nist.gov/csrc/media/publications/fips/197/final/documents/fips-197.pdf
 //	https://csrc.nist.gov/archive/aes/rijndael/Rijndael-ammended.pdf
 
-/// encrypt one block from src into dst, using the expanded key xk.
-pub fn encrypt_block_go(xk: &[u32], dst: &mut [u8], src: &[u8]) {
-    assert!(src.len() >= 16); // bounds check
-
-    let mut s0 = binary::BIG_ENDIAN.uint32(&src[0..4]);
-    let mut s1 = binary::BIG_ENDIAN.uint32(&src[4..8]);
-    let mut s2 = binary::BIG_ENDIAN.uint32(&src[8..12]);
-    let mut s3 = binary::BIG_ENDIAN.uint32(&src[12..16]);
-
-    // First round just XORs input with key.
-    s0 ^= xk[0];
-    s1 ^= xk[1];
-    s2 ^= xk[2];
-    s3 ^= xk[3];
-
-    // Middle rounds shuffle using tables.
-    // Number of rounds is set by length of expanded key.
-    let nr = xk.len() / 4 - 2; // - 2: one above, one more below
-    let mut k = 4;
-    let mut t0: u32;
-    let mut t1: u32;
-    let mut t2: u32;
-    let mut t3: u32;
-    let mut r = 0;
-    loop {
-        t0 = xk[k]
-            ^ TE0[((s0 >> 24) as u8) as usize]
-            ^ TE1[((s1 >> 16) as u8) as usize]
-            ^ TE2[((s2 >> 8) as u8) as usize]
-            ^ TE3[((s3) as u8) as usize];
-        t1 = xk[k + 1]
-            ^ TE0[((s1 >> 24) as u8) as usize]
-            ^ TE1[((s2 >> 16) as u8) as usize]
-            ^ TE2[((s3 >> 8) as u8) as usize]
-            ^ TE3[((s0) as u8) as usize];
-        t2 = xk[k + 2]
-            ^ TE0[((s2 >> 24) as u8) as usize]
-            ^ TE1[((s3 >> 16) as u8) as usize]
-            ^ TE2[((s0 >> 8) as u8) as usize]
-            ^ TE3[((s1) as u8) as usize];
-        t3 = xk[k + 3]
-            ^ TE0[((s3 >> 24) as u8) as usize]
-            ^ TE1[((s0 >> 16) as u8) as usize]
-            ^ TE2[((s1 >> 8) as u8) as usize]
-            ^ TE3[((s2) as u8) as usize];
-        k += 4;
-        (s0, s1, s2, s3) = (t0, t1, t2, t3);
-        r += 1;
-        if r == nr {
-            break;
-        }
+/// BitslicedKeys holds the round keys in bitsliced form,
+/// eight words per round key.
+pub type BitslicedKeys = [u64; 8 * 15];
+
+/// sbox applies the AES S-box to each byte of the bitsliced state q.
+///
+/// This is a straightforward translation of the circuit described by
+/// Boyar and Peralta in "A new combinational logic minimization technique
+/// with applications to cryptology" (<https://eprint.iacr.org/2009/191.pdf>).
+/// Note that variables x* (input) and s* (output) are numbered in
+/// "reverse" order (x0 is the high bit, x7 is the low bit).
+pub fn sbox(q: &mut [u64; 8]) {
+    let x0 = q[7];
+    let x1 = q[6];
+    let x2 = q[5];
+    let x3 = q[4];
+    let x4 = q[3];
+    let x5 = q[2];
+    let x6 = q[1];
+    let x7 = q[0];
+
+    // Top linear transformation.
+    let y14 = x3 ^ x5;
+    let y13 = x0 ^ x6;
+    let y9 = x0 ^ x3;
+    let y8 = x0 ^ x5;
+    let t0 = x1 ^ x2;
+    let y1 = t0 ^ x7;
+    let y4 = y1 ^ x3;
+    let y12 = y13 ^ y14;
+    let y2 = y1 ^ x0;
+    let y5 = y1 ^ x6;
+    let y3 = y5 ^ y8;
+    let t1 = x4 ^ y12;
+    let y15 = t1 ^ x5;
+    let y20 = t1 ^ x1;
+    let y6 = y15 ^ x7;
+    let y10 = y15 ^ t0;
+    let y11 = y20 ^ y9;
+    let y7 = x7 ^ y11;
+    let y17 = y10 ^ y11;
+    let y19 = y10 ^ y8;
+    let y16 = t0 ^ y11;
+    let y21 = y13 ^ y16;
+    let y18 = x0 ^ y16;
+
+    // Non-linear section.
+    let t2 = y12 & y15;
+    let t3 = y3 & y6;
+    let t4 = t3 ^ t2;
+    let t5 = y4 & x7;
+    let t6 = t5 ^ t2;
+    let t7 = y13 & y16;
+    let t8 = y5 & y1;
+    let t9 = t8 ^ t7;
+    let t10 = y2 & y7;
+    let t11 = t10 ^ t7;
+    let t12 = y9 & y11;
+    let t13 = y14 & y17;
+    let t14 = t13 ^ t12;
+    let t15 = y8 & y10;
+    let t16 = t15 ^ t12;
+    let t17 = t4 ^ t14;
+    let t18 = t6 ^ t16;
+    let t19 = t9 ^ t14;
+    let t20 = t11 ^ t16;
+    let t21 = t17 ^ y20;
+    let t22 = t18 ^ y19;
+    let t23 = t19 ^ y21;
+    let t24 = t20 ^ y18;
+
+    let t25 = t21 ^ t22;
+    let t26 = t21 & t23;
+    let t27 = t24 ^ t26;
+    let t28 = t25 & t27;
+    let t29 = t28 ^ t22;
+    let t30 = t23 ^ t24;
+    let t31 = t22 ^ t26;
+    let t32 = t31 & t30;
+    let t33 = t32 ^ t24;
+    let t34 = t23 ^ t33;
+    let t35 = t27 ^ t33;
+    let t36 = t24 & t35;
+    let t37 = t36 ^ t34;
+    let t38 = t27 ^ t36;
+    let t39 = t29 & t38;
+    let t40 = t25 ^ t39;
+
+    let t41 = t40 ^ t37;
+    let t42 = t29 ^ t33;
+    let t43 = t29 ^ t40;
+    let t44 = t33 ^ t37;
+    let t45 = t42 ^ t41;
+    let z0 = t44 & y15;
+    let z1 = t37 & y6;
+    let z2 = t33 & x7;
+    let z3 = t43 & y16;
+    let z4 = t40 & y1;
+    let z5 = t29 & y7;
+    let z6 = t42 & y11;
+    let z7 = t45 & y17;
+    let z8 = t41 & y10;
+    let z9 = t44 & y12;
+    let z10 = t37 & y3;
+    let z11 = t33 & y4;
+    let z12 = t43 & y13;
+    let z13 = t40 & y5;
+    let z14 = t29 & y2;
+    let z15 = t42 & y9;
+    let z16 = t45 & y14;
+    let z17 = t41 & y8;
+
+    // Bottom linear transformation.
+    let t46 = z15 ^ z16;
+    let t47 = z10 ^ z11;
+    let t48 = z5 ^ z13;
+    let t49 = z9 ^ z10;
+    let t50 = z2 ^ z12;
+    let t51 = z2 ^ z5;
+    let t52 = z7 ^ z8;
+    let t53 = z0 ^ z3;
+    let t54 = z6 ^ z7;
+    let t55 = z16 ^ z17;
+    let t56 = z12 ^ t48;
+    let t57 = t50 ^ t53;
+    let t58 = z4 ^ t46;
+    let t59 = z3 ^ t54;
+    let t60 = t46 ^ t57;
+    let t61 = z14 ^ t57;
+    let t62 = t52 ^ t58;
+    let t63 = t49 ^ t58;
+    let t64 = z4 ^ t59;
+    let t65 = t61 ^ t62;
+    let t66 = z1 ^ t63;
+    let s0 = t59 ^ t63;
+    let s6 = t56 ^ !t62;
+    let s7 = t48 ^ !t60;
+    let t67 = t64 ^ t65;
+    let s3 = t53 ^ t66;
+    let s4 = t51 ^ t66;
+    let s5 = t47 ^ t65;
+    let s1 = t64 ^ !s3;
+    let s2 = t55 ^ !t67;
+
+    q[7] = s0;
+    q[6] = s1;
+    q[5] = s2;
+    q[4] = s3;
+    q[3] = s4;
+    q[2] = s5;
+    q[1] = s6;
+    q[0] = s7;
+}
+
+/// inv_affine applies the inverse of the affine transformation
+/// of the S-box to each byte of the bitsliced state q.
+fn inv_affine(q: &mut [u64; 8]) {
+    let q0 = !q[0];
+    let q1 = !q[1];
+    let q2 = q[2];
+    let q3 = q[3];
+    let q4 = q[4];
+    let q5 = !q[5];
+    let q6 = !q[6];
+    let q7 = q[7];
+    q[7] = q1 ^ q4 ^ q6;
+    q[6] = q0 ^ q3 ^ q5;
+    q[5] = q7 ^ q2 ^ q4;
+    q[4] = q6 ^ q1 ^ q3;
+    q[3] = q5 ^ q0 ^ q2;
+    q[2] = q4 ^ q7 ^ q1;
+    q[1] = q3 ^ q6 ^ q0;
+    q[0] = q2 ^ q5 ^ q7;
+}
+
+/// inv_sbox applies the inverse AES S-box to each byte of the bitsliced state q.
+///
+/// The S-box is the inversion in GF(2⁸) followed by an affine transformation A,
+/// so its inverse is A⁻¹ followed by the inversion, which is itself
+/// computed as A⁻¹ applied after the S-box.
+pub fn inv_sbox(q: &mut [u64; 8]) {
+    inv_affine(q);
+    sbox(q);
+    inv_affine(q);
+}
+
+/// ortho converts between the bitsliced representation and the interleaved
+/// one produced by interleave_in. It is its own inverse.
+fn ortho(q: &mut [u64; 8]) {
+    fn swap(q: &mut [u64; 8], i: usize, j: usize, cl: u64, ch: u64, s: u32) {
+        let a = q[i];
+        let b = q[j];
+        q[i] = (a & cl) | ((b & cl) << s);
+        q[j] = ((a & ch) >> s) | (b & ch);
     }
+    const CL2: u64 = 0x5555555555555555;
+    const CH2: u64 = 0xAAAAAAAAAAAAAAAA;
+    const CL4: u64 = 0x3333333333333333;
+    const CH4: u64 = 0xCCCCCCCCCCCCCCCC;
+    const CL8: u64 = 0x0F0F0F0F0F0F0F0F;
+    const CH8: u64 = 0xF0F0F0F0F0F0F0F0;
+
+    swap(q, 0, 1, CL2, CH2, 1);
+    swap(q, 2, 3, CL2, CH2, 1);
+    swap(q, 4, 5, CL2, CH2, 1);
+    swap(q, 6, 7, CL2, CH2, 1);
+
+    swap(q, 0, 2, CL4, CH4, 2);
+    swap(q, 1, 3, CL4, CH4, 2);
+    swap(q, 4, 6, CL4, CH4, 2);
+    swap(q, 5, 7, CL4, CH4, 2);
+
+    swap(q, 0, 4, CL8, CH8, 4);
+    swap(q, 1, 5, CL8, CH8, 4);
+    swap(q, 2, 6, CL8, CH8, 4);
+    swap(q, 3, 7, CL8, CH8, 4);
+}
 
-    // Last round uses s-box directly and XORs to produce output.
-    s0 = (((SBOX0[(t0 >> 24) as usize]) as u32) << 24)
-        | ((SBOX0[((t1 >> 16) & 0xff) as usize] as u32) << 16)
-        | ((SBOX0[((t2 >> 8) & 0xff) as usize] as u32) << 8)
-        | (SBOX0[(t3 & 0xff) as usize] as u32);
-    s1 = (((SBOX0[(t1 >> 24) as usize]) as u32) << 24)
-        | ((SBOX0[((t2 >> 16) & 0xff) as usize] as u32) << 16)
-        | ((SBOX0[((t3 >> 8) & 0xff) as usize] as u32) << 8)
-        | (SBOX0[(t0 & 0xff) as usize] as u32);
-    s2 = (((SBOX0[(t2 >> 24) as usize]) as u32) << 24)
-        | ((SBOX0[((t3 >> 16) & 0xff) as usize] as u32) << 16)
-        | ((SBOX0[((t0 >> 8) & 0xff) as usize] as u32) << 8)
-        | (SBOX0[(t1 & 0xff) as usize] as u32);
-    s3 = (((SBOX0[(t3 >> 24) as usize]) as u32) << 24)
-        | ((SBOX0[((t0 >> 16) & 0xff) as usize] as u32) << 16)
-        | ((SBOX0[((t1 >> 8) & 0xff) as usize] as u32) << 8)
-        | (SBOX0[(t2 & 0xff) as usize] as u32);
-
-    s0 ^= xk[k];
-    s1 ^= xk[k + 1];
-    s2 ^= xk[k + 2];
-    s3 ^= xk[k + 3];
-
-    assert!(dst.len() >= 16); // bounds check
-    binary::BIG_ENDIAN.put_uint32(&mut dst[0..4], s0);
-    binary::BIG_ENDIAN.put_uint32(&mut dst[4..8], s1);
-    binary::BIG_ENDIAN.put_uint32(&mut dst[8..12], s2);
-    binary::BIG_ENDIAN.put_uint32(&mut dst[12..16], s3);
+/// interleave_in spreads the four little-endian words of a block over two
+/// 64-bit values, as expected by ortho.
+fn interleave_in(w: &[u32; 4]) -> (u64, u64) {
+    let mut x = w.map(|w| w as u64);
+    for x in x.iter_mut() {
+        *x |= *x << 16;
+        *x &= 0x0000FFFF0000FFFF;
+        *x |= *x << 8;
+        *x &= 0x00FF00FF00FF00FF;
+    }
+    (x[0] | (x[2] << 8), x[1] | (x[3] << 8))
 }
 
-/// encrypt one block in the buffer
-pub fn encrypt_block_inplace_go(xk: &[u32], buffer: &mut [u8]) {
-    assert!(buffer.len() >= 16); // bounds check
-
-    let mut s0 = binary::BIG_ENDIAN.uint32(&buffer[0..4]);
-    let mut s1 = binary::BIG_ENDIAN.uint32(&buffer[4..8]);
-    let mut s2 = binary::BIG_ENDIAN.uint32(&buffer[8..12]);
-    let mut s3 = binary::BIG_ENDIAN.uint32(&buffer[12..16]);
-
-    // First round just XORs input with key.
-    s0 ^= xk[0];
-    s1 ^= xk[1];
-    s2 ^= xk[2];
-    s3 ^= xk[3];
-
-    // Middle rounds shuffle using tables.
-    // Number of rounds is set by length of expanded key.
-    let nr = xk.len() / 4 - 2; // - 2: one above, one more below
-    let mut k = 4;
-    let mut t0: u32;
-    let mut t1: u32;
-    let mut t2: u32;
-    let mut t3: u32;
-    let mut r = 0;
-    loop {
-        t0 = xk[k]
-            ^ TE0[((s0 >> 24) as u8) as usize]
-            ^ TE1[((s1 >> 16) as u8) as usize]
-            ^ TE2[((s2 >> 8) as u8) as usize]
-            ^ TE3[((s3) as u8) as usize];
-        t1 = xk[k + 1]
-            ^ TE0[((s1 >> 24) as u8) as usize]
-            ^ TE1[((s2 >> 16) as u8) as usize]
-            ^ TE2[((s3 >> 8) as u8) as usize]
-            ^ TE3[((s0) as u8) as usize];
-        t2 = xk[k + 2]
-            ^ TE0[((s2 >> 24) as u8) as usize]
-            ^ TE1[((s3 >> 16) as u8) as usize]
-            ^ TE2[((s0 >> 8) as u8) as usize]
-            ^ TE3[((s1) as u8) as usize];
-        t3 = xk[k + 3]
-            ^ TE0[((s3 >> 24) as u8) as usize]
-            ^ TE1[((s0 >> 16) as u8) as usize]
-            ^ TE2[((s1 >> 8) as u8) as usize]
-            ^ TE3[((s2) as u8) as usize];
-        k += 4;
-        (s0, s1, s2, s3) = (t0, t1, t2, t3);
-        r += 1;
-        if r == nr {
-            break;
-        }
+/// interleave_out is the inverse of interleave_in.
+fn interleave_out(q0: u64, q1: u64) -> [u32; 4] {
+    let mut x = [
+        q0 & 0x00FF00FF00FF00FF,
+        q1 & 0x00FF00FF00FF00FF,
+        (q0 >> 8) & 0x00FF00FF00FF00FF,
+        (q1 >> 8) & 0x00FF00FF00FF00FF,
+    ];
+    for x in x.iter_mut() {
+        *x |= *x >> 8;
+        *x &= 0x0000FFFF0000FFFF;
     }
+    x.map(|x| (x as u32) | ((x >> 16) as u32))
+}
 
-    // Last round uses s-box directly and XORs to produce output.
-    s0 = (((SBOX0[(t0 >> 24) as usize]) as u32) << 24)
-        | ((SBOX0[((t1 >> 16) & 0xff) as usize] as u32) << 16)
-        | ((SBOX0[((t2 >> 8) & 0xff) as usize] as u32) << 8)
-        | (SBOX0[(t3 & 0xff) as usize] as u32);
-    s1 = (((SBOX0[(t1 >> 24) as usize]) as u32) << 24)
-        | ((SBOX0[((t2 >> 16) & 0xff) as usize] as u32) << 16)
-        | ((SBOX0[((t3 >> 8) & 0xff) as usize] as u32) << 8)
-        | (SBOX0[(t0 & 0xff) as usize] as u32);
-    s2 = (((SBOX0[(t2 >> 24) as usize]) as u32) << 24)
-        | ((SBOX0[((t3 >> 16) & 0xff) as usize] as u32) << 16)
-        | ((SBOX0[((t0 >> 8) & 0xff) as usize] as u32) << 8)
-        | (SBOX0[(t1 & 0xff) as usize] as u32);
-    s3 = (((SBOX0[(t3 >> 24) as usize]) as u32) << 24)
-        | ((SBOX0[((t0 >> 16) & 0xff) as usize] as u32) << 16)
-        | ((SBOX0[((t1 >> 8) & 0xff) as usize] as u32) << 8)
-        | (SBOX0[(t2 & 0xff) as usize] as u32);
-
-    s0 ^= xk[k];
-    s1 ^= xk[k + 1];
-    s2 ^= xk[k + 2];
-    s3 ^= xk[k + 3];
-
-    assert!(buffer.len() >= 16); // bounds check
-    binary::BIG_ENDIAN.put_uint32(&mut buffer[0..4], s0);
-    binary::BIG_ENDIAN.put_uint32(&mut buffer[4..8], s1);
-    binary::BIG_ENDIAN.put_uint32(&mut buffer[8..12], s2);
-    binary::BIG_ENDIAN.put_uint32(&mut buffer[12..16], s3);
+/// load converts up to four blocks into the bitsliced representation.
+pub fn load(blocks: &[[u8; 16]]) -> [u64; 8] {
+    let mut q = [0; 8];
+    for (i, b) in blocks.iter().enumerate() {
+        let mut w = [0; 4];
+        for (j, w) in w.iter_mut().enumerate() {
+            *w = binary::LITTLE_ENDIAN.uint32(&b[4 * j..]);
+        }
+        (q[i], q[i + 4]) = interleave_in(&w);
+    }
+    ortho(&mut q);
+    q
 }
 
-/// decrypt one block from src into dst, using the expanded key xk.
-pub fn decrypt_block_go(xk: &[u32], dst: &mut [u8], src: &[u8]) {
-    assert!(src.len() >= 16); // bounds check
-    let mut s0 = binary::BIG_ENDIAN.uint32(&src[0..4]);
-    let mut s1 = binary::BIG_ENDIAN.uint32(&src[4..8]);
-    let mut s2 = binary::BIG_ENDIAN.uint32(&src[8..12]);
-    let mut s3 = binary::BIG_ENDIAN.uint32(&src[12..16]);
-
-    // First round just XORs input with key.
-    s0 ^= xk[0];
-    s1 ^= xk[1];
-    s2 ^= xk[2];
-    s3 ^= xk[3];
-
-    // Middle rounds shuffle using tables.
-    // Number of rounds is set by length of expanded key.
-    let nr = xk.len() / 4 - 2; // - 2: one above, one more below
-    let mut k = 4;
-    let mut t0: u32;
-    let mut t1: u32;
-    let mut t2: u32;
-    let mut t3: u32;
-    let mut r = 0;
-    loop {
-        t0 = xk[k]
-            ^ TD0[((s0 >> 24) as u8) as usize]
-            ^ TD1[((s3 >> 16) as u8) as usize]
-            ^ TD2[((s2 >> 8) as u8) as usize]
-            ^ TD3[((s1) as u8) as usize];
-        t1 = xk[k + 1]
-            ^ TD0[((s1 >> 24) as u8) as usize]
-            ^ TD1[((s0 >> 16) as u8) as usize]
-            ^ TD2[((s3 >> 8) as u8) as usize]
-            ^ TD3[((s2) as u8) as usize];
-        t2 = xk[k + 2]
-            ^ TD0[((s2 >> 24) as u8) as usize]
-            ^ TD1[((s1 >> 16) as u8) as usize]
-            ^ TD2[((s0 >> 8) as u8) as usize]
-            ^ TD3[((s3) as u8) as usize];
-        t3 = xk[k + 3]
-            ^ TD0[((s3 >> 24) as u8) as usize]
-            ^ TD1[((s2 >> 16) as u8) as usize]
-            ^ TD2[((s1 >> 8) as u8) as usize]
-            ^ TD3[((s0) as u8) as usize];
-        k += 4;
-        (s0, s1, s2, s3) = (t0, t1, t2, t3);
-        r += 1;
-        if r == nr {
-            break;
+/// store converts the bitsliced state q back into up to four blocks.
+pub fn store(mut q: [u64; 8], blocks: &mut [[u8; 16]]) {
+    ortho(&mut q);
+    for (i, b) in blocks.iter_mut().enumerate() {
+        let w = interleave_out(q[i], q[i + 4]);
+        for (j, w) in w.iter().enumerate() {
+            binary::LITTLE_ENDIAN.put_uint32(&mut b[4 * j..], *w);
         }
     }
+}
 
-    // Last round uses s-box directly and XORs to produce output.
-    s0 = (((SBOX1[(t0 >> 24) as usize]) as u32) << 24)
-        | ((SBOX1[((t3 >> 16) & 0xff) as usize] as u32) << 16)
-        | ((SBOX1[((t2 >> 8) & 0xff) as usize] as u32) << 8)
-        | (SBOX1[(t1 & 0xff) as usize] as u32);
-    s1 = (((SBOX1[(t1 >> 24) as usize]) as u32) << 24)
-        | ((SBOX1[((t0 >> 16) & 0xff) as usize] as u32) << 16)
-        | ((SBOX1[((t3 >> 8) & 0xff) as usize] as u32) << 8)
-        | (SBOX1[(t2 & 0xff) as usize] as u32);
-    s2 = (((SBOX1[(t2 >> 24) as usize]) as u32) << 24)
-        | ((SBOX1[((t1 >> 16) & 0xff) as usize] as u32) << 16)
-        | ((SBOX1[((t0 >> 8) & 0xff) as usize] as u32) << 8)
-        | (SBOX1[(t3 & 0xff) as usize] as u32);
-    s3 = (((SBOX1[(t3 >> 24) as usize]) as u32) << 24)
-        | ((SBOX1[((t2 >> 16) & 0xff) as usize] as u32) << 16)
-        | ((SBOX1[((t1 >> 8) & 0xff) as usize] as u32) << 8)
-        | (SBOX1[(t0 & 0xff) as usize] as u32);
-
-    s0 ^= xk[k];
-    s1 ^= xk[k + 1];
-    s2 ^= xk[k + 2];
-    s3 ^= xk[k + 3];
-
-    assert!(dst.len() >= 16); // bounds check
-    binary::BIG_ENDIAN.put_uint32(&mut dst[0..4], s0);
-    binary::BIG_ENDIAN.put_uint32(&mut dst[4..8], s1);
-    binary::BIG_ENDIAN.put_uint32(&mut dst[8..12], s2);
-    binary::BIG_ENDIAN.put_uint32(&mut dst[12..16], s3);
+fn add_round_key(q: &mut [u64; 8], sk: &[u64]) {
+    for (q, k) in q.iter_mut().zip(sk) {
+        *q ^= k;
+    }
 }
 
-/// decrypt one block in the buffer, using the expanded key xk.
-pub fn decrypt_block_inplace_go(xk: &[u32], buffer: &mut [u8]) {
-    assert!(buffer.len() >= 16); // bounds check
-    let mut s0 = binary::BIG_ENDIAN.uint32(&buffer[0..4]);
-    let mut s1 = binary::BIG_ENDIAN.uint32(&buffer[4..8]);
-    let mut s2 = binary::BIG_ENDIAN.uint32(&buffer[8..12]);
-    let mut s3 = binary::BIG_ENDIAN.uint32(&buffer[12..16]);
-
-    // First round just XORs input with key.
-    s0 ^= xk[0];
-    s1 ^= xk[1];
-    s2 ^= xk[2];
-    s3 ^= xk[3];
-
-    // Middle rounds shuffle using tables.
-    // Number of rounds is set by length of expanded key.
-    let nr = xk.len() / 4 - 2; // - 2: one above, one more below
-    let mut k = 4;
-    let mut t0: u32;
-    let mut t1: u32;
-    let mut t2: u32;
-    let mut t3: u32;
-    let mut r = 0;
-    loop {
-        t0 = xk[k]
-            ^ TD0[((s0 >> 24) as u8) as usize]
-            ^ TD1[((s3 >> 16) as u8) as usize]
-            ^ TD2[((s2 >> 8) as u8) as usize]
-            ^ TD3[((s1) as u8) as usize];
-        t1 = xk[k + 1]
-            ^ TD0[((s1 >> 24) as u8) as usize]
-            ^ TD1[((s0 >> 16) as u8) as usize]
-            ^ TD2[((s3 >> 8) as u8) as usize]
-            ^ TD3[((s2) as u8) as usize];
-        t2 = xk[k + 2]
-            ^ TD0[((s2 >> 24) as u8) as usize]
-            ^ TD1[((s1 >> 16) as u8) as usize]
-            ^ TD2[((s0 >> 8) as u8) as usize]
-            ^ TD3[((s3) as u8) as usize];
-        t3 = xk[k + 3]
-            ^ TD0[((s3 >> 24) as u8) as usize]
-            ^ TD1[((s2 >> 16) as u8) as usize]
-            ^ TD2[((s1 >> 8) as u8) as usize]
-            ^ TD3[((s0) as u8) as usize];
-        k += 4;
-        (s0, s1, s2, s3) = (t0, t1, t2, t3);
-        r += 1;
-        if r == nr {
-            break;
-        }
+fn shift_rows(q: &mut [u64; 8]) {
+    for x in q.iter_mut() {
+        *x = (*x & 0x000000000000FFFF)
+            | ((*x & 0x00000000FFF00000) >> 4)
+            | ((*x & 0x00000000000F0000) << 12)
+            | ((*x & 0x0000FF0000000000) >> 8)
+            | ((*x & 0x000000FF00000000) << 8)
+            | ((*x & 0xF000000000000000) >> 12)
+            | ((*x & 0x0FFF000000000000) << 4);
+    }
+}
+
+fn inv_shift_rows(q: &mut [u64; 8]) {
+    for x in q.iter_mut() {
+        *x = (*x & 0x000000000000FFFF)
+            | ((*x & 0x000000000FFF0000) << 4)
+            | ((*x & 0x00000000F0000000) >> 12)
+            | ((*x & 0x000000FF00000000) << 8)
+            | ((*x & 0x0000FF0000000000) >> 8)
+            | ((*x & 0x000F000000000000) << 12)
+            | ((*x & 0xFFF0000000000000) >> 4);
+    }
+}
+
+fn rotr32(x: u64) -> u64 {
+    x.rotate_right(32)
+}
+
+/// mix_columns multiplies each column of the state by
+/// 03·x³ + 01·x² + 01·x + 02. See FIPS-197, section 5.1.3.
+fn mix_columns(q: &mut [u64; 8]) {
+    // r[i] holds the next row of q[i], rotr32 selects the row after that.
+    let [q0, q1, q2, q3, q4, q5, q6, q7] = *q;
+    let [r0, r1, r2, r3, r4, r5, r6, r7] = q.map(|x| x.rotate_right(16));
+
+    q[0] = q7 ^ r7 ^ r0 ^ rotr32(q0 ^ r0);
+    q[1] = q0 ^ r0 ^ q7 ^ r7 ^ r1 ^ rotr32(q1 ^ r1);
+    q[2] = q1 ^ r1 ^ r2 ^ rotr32(q2 ^ r2);
+    q[3] = q2 ^ r2 ^ q7 ^ r7 ^ r3 ^ rotr32(q3 ^ r3);
+    q[4] = q3 ^ r3 ^ q7 ^ r7 ^ r4 ^ rotr32(q4 ^ r4);
+    q[5] = q4 ^ r4 ^ r5 ^ rotr32(q5 ^ r5);
+    q[6] = q5 ^ r5 ^ r6 ^ rotr32(q6 ^ r6);
+    q[7] = q6 ^ r6 ^ r7 ^ rotr32(q7 ^ r7);
+}
+
+/// inv_mix_columns multiplies each column of the state by
+/// 0b·x³ + 0d·x² + 09·x + 0e. See FIPS-197, section 5.3.3.
+///
+/// That polynomial is (03·x³ + 01·x² + 01·x + 02)·(04·x² + 05),
+/// so the multiplication by 04·x² + 05 is done first,
+/// followed by mix_columns.
+fn inv_mix_columns(q: &mut [u64; 8]) {
+    // y holds each byte xored with the byte two rows below it.
+    let y = q.map(rotr32);
+    let mut y = [
+        q[0] ^ y[0],
+        q[1] ^ y[1],
+        q[2] ^ y[2],
+        q[3] ^ y[3],
+        q[4] ^ y[4],
+        q[5] ^ y[5],
+        q[6] ^ y[6],
+        q[7] ^ y[7],
+    ];
+    xtime(&mut y);
+    xtime(&mut y);
+    for (q, y) in q.iter_mut().zip(y) {
+        *q ^= y;
+    }
+    mix_columns(q);
+}
+
+/// xtime multiplies each byte of the bitsliced state q by x (that is, by 02).
+fn xtime(q: &mut [u64; 8]) {
+    let [q0, q1, q2, q3, q4, q5, q6, q7] = *q;
+    *q = [q7, q0 ^ q7, q1, q2 ^ q7, q3 ^ q7, q4, q5, q6];
+}
+
+/// encrypt_bitsliced encrypts the bitsliced state q in place.
+fn encrypt_bitsliced(rounds: usize, sk: &[u64], q: &mut [u64; 8]) {
+    add_round_key(q, &sk[..8]);
+    for u in 1..rounds {
+        sbox(q);
+        shift_rows(q);
+        mix_columns(q);
+        add_round_key(q, &sk[u * 8..]);
     }
+    sbox(q);
+    shift_rows(q);
+    add_round_key(q, &sk[rounds * 8..]);
+}
 
-    // Last round uses s-box directly and XORs to produce output.
-    s0 = (((SBOX1[(t0 >> 24) as usize]) as u32) << 24)
-        | ((SBOX1[((t3 >> 16) & 0xff) as usize] as u32) << 16)
-        | ((SBOX1[((t2 >> 8) & 0xff) as usize] as u32) << 8)
-        | (SBOX1[(t1 & 0xff) as usize] as u32);
-    s1 = (((SBOX1[(t1 >> 24) as usize]) as u32) << 24)
-        | ((SBOX1[((t0 >> 16) & 0xff) as usize] as u32) << 16)
-        | ((SBOX1[((t3 >> 8) & 0xff) as usize] as u32) << 8)
-        | (SBOX1[(t2 & 0xff) as usize] as u32);
-    s2 = (((SBOX1[(t2 >> 24) as usize]) as u32) << 24)
-        | ((SBOX1[((t1 >> 16) & 0xff) as usize] as u32) << 16)
-        | ((SBOX1[((t0 >> 8) & 0xff) as usize] as u32) << 8)
-        | (SBOX1[(t3 & 0xff) as usize] as u32);
-    s3 = (((SBOX1[(t3 >> 24) as usize]) as u32) << 24)
-        | ((SBOX1[((t2 >> 16) & 0xff) as usize] as u32) << 16)
-        | ((SBOX1[((t1 >> 8) & 0xff) as usize] as u32) << 8)
-        | (SBOX1[(t0 & 0xff) as usize] as u32);
-
-    s0 ^= xk[k];
-    s1 ^= xk[k + 1];
-    s2 ^= xk[k + 2];
-    s3 ^= xk[k + 3];
-
-    assert!(buffer.len() >= 16); // bounds check
-    binary::BIG_ENDIAN.put_uint32(&mut buffer[0..4], s0);
-    binary::BIG_ENDIAN.put_uint32(&mut buffer[4..8], s1);
-    binary::BIG_ENDIAN.put_uint32(&mut buffer[8..12], s2);
-    binary::BIG_ENDIAN.put_uint32(&mut buffer[12..16], s3);
+/// decrypt_bitsliced decrypts the bitsliced state q in place.
+fn decrypt_bitsliced(rounds: usize, sk: &[u64], q: &mut [u64; 8]) {
+    add_round_key(q, &sk[rounds * 8..]);
+    for u in (1..rounds).rev() {
+        inv_shift_rows(q);
+        inv_sbox(q);
+        add_round_key(q, &sk[u * 8..]);
+        inv_mix_columns(q);
+    }
+    inv_shift_rows(q);
+    inv_sbox(q);
+    add_round_key(q, &sk[..8]);
 }
 
-/// Apply sbox0 to each byte in w.
+/// encrypt_blocks encrypts up to four blocks in place,
+/// using the bitsliced round keys sk.
+pub fn encrypt_blocks(rounds: usize, sk: &BitslicedKeys, blocks: &mut [[u8; 16]]) {
+    let mut q = load(blocks);
+    encrypt_bitsliced(rounds, sk, &mut q);
+    store(q, blocks);
+}
+
+/// decrypt_blocks decrypts up to four blocks in place,
+/// using the bitsliced round keys sk.
+pub fn decrypt_blocks(rounds: usize, sk: &BitslicedKeys, blocks: &mut [[u8; 16]]) {
+    let mut q = load(blocks);
+    decrypt_bitsliced(rounds, sk, &mut q);
+    store(q, blocks);
+}
+
+/// Apply the S-box to each byte in w.
 fn subw(w: u32) -> u32 {
-    ((SBOX0[(w >> 24) as usize]) as u32) << 24
-        | (SBOX0[((w >> 16) & 0xff) as usize] as u32) << 16
-        | (SBOX0[((w >> 8) & 0xff) as usize] as u32) << 8
-        | ((SBOX0[(w & 0xff) as usize]) as u32)
+    let mut q = [0; 8];
+    q[0] = w as u64;
+    ortho(&mut q);
+    sbox(&mut q);
+    ortho(&mut q);
+    q[0] as u32
 }
 
 /// Rotate
@@ -383,9 +437,8 @@ fn rotw(w: u32) -> u32 {
 
 /// Key expansion algorithm. See FIPS-197, Figure 11.
 /// Their rcon[i] is our powx[i-1] << 24.
-pub fn expand_key_go(key: &[u8], enc: &mut [u32], dec: &mut [u32]) {
-    // Encryption key setup.
-    // 	var i int
+/// The words of enc are the round keys in big-endian order.
+pub fn expand_key(key: &[u8], enc: &mut [u32]) {
     let nk = key.len() / 4;
     for i in 0..nk {
         enc[i] = binary::BIG_ENDIAN.uint32(&key[4 * i..]);
@@ -399,25 +452,22 @@ pub fn expand_key_go(key: &[u8], enc: &mut [u32], dec: &mut [u32]) {
         }
         enc[i] = enc[i - nk] ^ t;
     }
+}
 
-    // Derive decryption key from encryption key.
-    // Reverse the 4-word round key sets from enc to produce dec.
-    // All sets but the first and last get the MixColumn transform applied.
-    // 	if dec == nil {
-    // 		return
-    // 	}
-    let n = enc.len();
-    for i in (0..n).step_by(4) {
-        let ei = n - i - 4;
-        for j in 0..4 {
-            let mut x = enc[ei + j];
-            if i > 0 && i + 4 < n {
-                x = TD0[SBOX0[(x >> 24) as usize] as usize]
-                    ^ TD1[SBOX0[((x >> 16) & 0xff) as usize] as usize]
-                    ^ TD2[SBOX0[((x >> 8) & 0xff) as usize] as usize]
-                    ^ TD3[SBOX0[(x & 0xff) as usize] as usize];
-            }
-            dec[i + j] = x;
-        }
+/// bitslice_keys converts the round keys produced by expand_key into
+/// the bitsliced form used by encrypt_blocks and decrypt_blocks.
+/// Each round key is replicated for the four blocks processed in parallel.
+pub fn bitslice_keys(enc: &[u32], sk: &mut BitslicedKeys) {
+    for (rk, sk) in enc.chunks_exact(4).zip(sk.chunks_exact_mut(8)) {
+        let w = [
+            rk[0].swap_bytes(),
+            rk[1].swap_bytes(),
+            rk[2].swap_bytes(),
+            rk[3].swap_bytes(),
+        ];
+        let (q0, q4) = interleave_in(&w);
+        let mut q = [q0, q0, q0, q0, q4, q4, q4, q4];
+        ortho(&mut q);
+        sk.copy_from_slice(&q);
     }
 }
@@ -4,6 +4,8 @@
 // license that can be found in the LICENSE file.
 
 use super::block;
+#[cfg(target_arch = "x86_64")]
+use super::cipher_asm;
 use crate::crypto::cipher::Block;
 
 /// The AES block size in bytes.
@@ -11,8 +13,16 @@ pub const BLOCK_SIZE: usize = 16;
 
 /// Cipher is an instance of AES encryption using a particular key.
 pub struct Cipher {
-    enc: Vec<u32>,
-    dec: Vec<u32>,
+    rounds: usize,
+    keys: Keys,
+}
+
+/// Keys holds the round keys in the form needed by the implementation
+/// selected for the CPU.
+enum Keys {
+    Generic(Box<block::BitslicedKeys>),
+    #[cfg(target_arch = "x86_64")]
+    Asm(Box<cipher_asm::RoundKeys>),
 }
 
 #[derive(Debug)]
@@ -24,16 +34,37 @@ impl std::fmt::Display for KeySizeError {
     }
 }
 
-/// new_cipher_generic creates and returns a new cipher::Block
-/// implemented in pure Go.
-fn new_cipher_generic(key: &[u8]) -> Cipher {
-    let n = key.len() + 28;
-    let mut c = Cipher {
-        enc: vec![0; n],
-        dec: vec![0; n],
-    };
-    super::block::expand_key_go(key, &mut c.enc, &mut c.dec);
-    c
+/// new_cipher_generic creates and returns a new Cipher
+/// using the bitsliced software implementation.
+pub(super) fn new_cipher_generic(key: &[u8]) -> Cipher {
+    let rounds = key.len() / 4 + 6;
+    let mut enc = [0; 4 * 15];
+    block::expand_key(key, &mut enc[..4 * (rounds + 1)]);
+    let mut sk = Box::new([0; 8 * 15]);
+    block::bitslice_keys(&enc[..4 * (rounds + 1)], &mut sk);
+    Cipher {
+        rounds,
+        keys: Keys::Generic(sk),
+    }
+}
+
+/// new_cipher creates and returns a new Cipher, using the AES-NI
+/// instructions if the CPU supports them.
+fn new_cipher(key: &[u8]) -> Cipher {
+    #[cfg(target_arch = "x86_64")]
+    if cipher_asm::supports_aes() {
+        let rounds = key.len() / 4 + 6;
+        let mut enc = [0; 4 * 15];
+        block::expand_key(key, &mut enc[..4 * (rounds + 1)]);
+        return Cipher {
+            rounds,
+            // Safety: the CPU support was checked above.
+            keys: Keys::Asm(Box::new(unsafe {
+                cipher_asm::RoundKeys::new(rounds, &enc)
+            })),
+        };
+    }
+    new_cipher_generic(key)
 }
 
 impl Cipher {
@@ -50,7 +81,35 @@ impl Cipher {
         // 	if boring.Enabled {
         // 		return boring.NewAESCipher(key)
         // 	}
-        Ok(new_cipher_generic(key))
+        Ok(new_cipher(key))
+    }
+
+    fn encrypt_block(&self, dst: &mut [u8], src: &[u8]) {
+        match &self.keys {
+            Keys::Generic(sk) => {
+                let mut b = [[0; BLOCK_SIZE]];
+                b[0].copy_from_slice(&src[..BLOCK_SIZE]);
+                block::encrypt_blocks(self.rounds, sk, &mut b);
+                dst[..BLOCK_SIZE].copy_from_slice(&b[0]);
+            }
+            #[cfg(target_arch = "x86_64")]
+            // Safety: Keys::Asm is only created when the CPU supports AES-NI.
+            Keys::Asm(k) => unsafe { cipher_asm::encrypt_block(self.rounds, k, dst, src) },
+        }
+    }
+
+    fn decrypt_block(&self, dst: &mut [u8], src: &[u8]) {
+        match &self.keys {
+            Keys::Generic(sk) => {
+                let mut b = [[0; BLOCK_SIZE]];
+                b[0].copy_from_slice(&src[..BLOCK_SIZE]);
+                block::decrypt_blocks(self.rounds, sk, &mut b);
+                dst[..BLOCK_SIZE].copy_from_slice(&b[0]);
+            }
+            #[cfg(target_arch = "x86_64")]
+            // Safety: Keys::Asm is only created when the CPU supports AES-NI.
+            Keys::Asm(k) => unsafe { cipher_asm::decrypt_block(self.rounds, k, dst, src) },
+        }
     }
 }
 
@@ -70,7 +129,7 @@ impl Block for Cipher {
         // 	if alias.InexactOverlap(dst[..block_size], src[..block_size]) {
         // 		panic!("crypto/aes: invalid buffer overlap");
         // 	}
-        block::encrypt_block_go(&self.enc, dst, src);
+        self.encrypt_block(dst, src);
     }
 
     fn decrypt(&self, dst: &mut [u8], src: &[u8]) {
@@ -84,20 +143,24 @@ impl Block for Cipher {
         // if alias.InexactOverlap(dst[..block_size], src[..block_size]) {
         //     panic!("crypto/aes: invalid buffer overlap");
         // }
-        block::decrypt_block_go(&self.dec, dst, src);
+        self.decrypt_block(dst, src);
     }
 
     fn encrypt_inplace(&self, buffer: &mut [u8]) {
         if buffer.len() < BLOCK_SIZE {
             panic!("crypto/aes: input not full block");
         }
-        block::encrypt_block_inplace_go(&self.enc, buffer);
+        let mut b = [0; BLOCK_SIZE];
+        b.copy_from_slice(&buffer[..BLOCK_SIZE]);
+        self.encrypt_block(buffer, &b);
     }
 
     fn decrypt_inplace(&self, buffer: &mut [u8]) {
         if buffer.len() < BLOCK_SIZE {
             panic!("crypto/aes: input not full block");
         }
-        block::decrypt_block_inplace_go(&self.dec, buffer);
+        let mut b = [0; BLOCK_SIZE];
+        b.copy_from_slice(&buffer[..BLOCK_SIZE]);
+        self.decrypt_block(buffer, &b);
     }
 }
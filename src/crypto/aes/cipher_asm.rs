@@ -0,0 +1,90 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2012 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// AES implementation using the AES-NI instructions of x86_64 processors.
+// The instructions run in constant time.
+
+use super::BLOCK_SIZE;
+use std::arch::x86_64::*;
+
+/// supports_aes reports whether the CPU supports the AES-NI instructions.
+pub fn supports_aes() -> bool {
+    is_x86_feature_detected!("aes") && is_x86_feature_detected!("sse2")
+}
+
+/// RoundKeys holds the encryption and decryption round keys
+/// in the form used by the AES-NI instructions.
+pub struct RoundKeys {
+    enc: [__m128i; 15],
+    dec: [__m128i; 15],
+}
+
+impl RoundKeys {
+    /// new builds the round keys from the key expansion enc,
+    /// given as big-endian words.
+    ///
+    /// # Safety
+    ///
+    /// The CPU must support the AES-NI instructions, see supports_aes.
+    #[target_feature(enable = "aes,sse2")]
+    pub unsafe fn new(rounds: usize, enc: &[u32]) -> Self {
+        let mut keys = RoundKeys {
+            enc: [_mm_setzero_si128(); 15],
+            dec: [_mm_setzero_si128(); 15],
+        };
+        for (k, w) in keys.enc.iter_mut().zip(enc.chunks_exact(4)) {
+            let mut b = [0u8; BLOCK_SIZE];
+            for (b, w) in b.chunks_exact_mut(4).zip(w) {
+                b.copy_from_slice(&w.to_be_bytes());
+            }
+            *k = _mm_loadu_si128(b.as_ptr() as *const __m128i);
+        }
+        // The equivalent inverse cipher uses the round keys in reverse order,
+        // with InvMixColumns applied to all but the first and last.
+        // See FIPS-197, section 5.3.5.
+        keys.dec[0] = keys.enc[rounds];
+        for i in 1..rounds {
+            keys.dec[i] = _mm_aesimc_si128(keys.enc[rounds - i]);
+        }
+        keys.dec[rounds] = keys.enc[0];
+        keys
+    }
+}
+
+/// encrypt_block encrypts one block from src into dst.
+///
+/// # Safety
+///
+/// The CPU must support the AES-NI instructions, see supports_aes.
+#[target_feature(enable = "aes,sse2")]
+pub unsafe fn encrypt_block(rounds: usize, keys: &RoundKeys, dst: &mut [u8], src: &[u8]) {
+    let src = &src[..BLOCK_SIZE];
+    let dst = &mut dst[..BLOCK_SIZE];
+    let mut b = _mm_loadu_si128(src.as_ptr() as *const __m128i);
+    b = _mm_xor_si128(b, keys.enc[0]);
+    for k in &keys.enc[1..rounds] {
+        b = _mm_aesenc_si128(b, *k);
+    }
+    b = _mm_aesenclast_si128(b, keys.enc[rounds]);
+    _mm_storeu_si128(dst.as_mut_ptr() as *mut __m128i, b);
+}
+
+/// decrypt_block decrypts one block from src into dst.
+///
+/// # Safety
+///
+/// The CPU must support the AES-NI instructions, see supports_aes.
+#[target_feature(enable = "aes,sse2")]
+pub unsafe fn decrypt_block(rounds: usize, keys: &RoundKeys, dst: &mut [u8], src: &[u8]) {
+    let src = &src[..BLOCK_SIZE];
+    let dst = &mut dst[..BLOCK_SIZE];
+    let mut b = _mm_loadu_si128(src.as_ptr() as *const __m128i);
+    b = _mm_xor_si128(b, keys.dec[0]);
+    for k in &keys.dec[1..rounds] {
+        b = _mm_aesdec_si128(b, *k);
+    }
+    b = _mm_aesdeclast_si128(b, keys.dec[rounds]);
+    _mm_storeu_si128(dst.as_mut_ptr() as *mut __m128i, b);
+}
@@ -6,15 +6,16 @@
 //! Package aes implements AES encryption (formerly Rijndael), as defined in
 //! U.S. Federal Information Processing Standards Publication 197.
 //!
-//! The AES operations in this package are not implemented using constant-time algorithms.
-// An exception is when running on systems with enabled hardware support for AES
-// that makes these operations constant-time. Examples include amd64 systems using AES-NI
-// extensions and s390x systems using Message-Security-Assist extensions.
-// On such systems, when the result of NewCipher is passed to cipher.NewGCM,
-// the GHASH operation used by GCM is also constant-time.
+//! The AES operations in this package are implemented using constant-time algorithms.
+//! On x86_64 systems with the AES-NI extensions the hardware instructions are used,
+//! otherwise a bitsliced software implementation is used.
+//! On x86_64 systems with the PCLMULQDQ extension, when the Cipher is passed to
+//! crate::crypto::cipher::GCM, the GHASH operation used by GCM is also constant-time.
 
 mod block;
 mod cipher;
+#[cfg(target_arch = "x86_64")]
+mod cipher_asm;
 mod consts;
 
 pub use cipher::{Cipher, BLOCK_SIZE};
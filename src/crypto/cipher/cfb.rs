@@ -1,83 +1,131 @@
-// // Copyright 2010 The Go Authors. All rights reserved.
-// // Use of this source code is governed by a BSD-style
-// // license that can be found in the LICENSE file.
-
-// // CFB (Cipher Feedback) Mode.
-
-// package cipher
-
-// import (
-// 	"crypto/internal/alias"
-// 	"crypto/subtle"
-// )
-
-// type cfb struct {
-// 	b       Block
-// 	next    []byte
-// 	out     []byte
-// 	outUsed int
-
-// 	decrypt bool
-// }
-
-// func (x *cfb) xor_key_stream(dst, src []byte) {
-// 	if dst.len() < src.len() {
-// 		panic("crypto/cipher: output smaller than input")
-// 	}
-// 	if alias.InexactOverlap(dst[..src.len()], src) {
-// 		panic("crypto/cipher: invalid buffer overlap")
-// 	}
-// 	for src.len() > 0 {
-// 		if x.outUsed == len(x.out) {
-// 			x.b.encrypt(x.out, x.next)
-// 			x.outUsed = 0
-// 		}
-
-// 		if x.decrypt {
-// 			// We can precompute a larger segment of the
-// 			// keystream on decryption. This will allow
-// 			// larger batches for xor, and we should be
-// 			// able to match CTR/OFB performance.
-// 			copy(x.next[x.outUsed:], src)
-// 		}
-// 		n := subtle.XORBytes(dst, src, x.out[x.outUsed:])
-// 		if !x.decrypt {
-// 			copy(x.next[x.outUsed:], dst)
-// 		}
-// 		dst = dst[n:]
-// 		src = src[n:]
-// 		x.outUsed += n
-// 	}
-// }
-
-// // NewCFBEncrypter returns a Stream which encrypts with cipher feedback mode,
-// // using the given Block. The iv must be the same length as the Block's block
-// // size.
-// func NewCFBEncrypter(block Block, iv []byte) Stream {
-// 	return newCFB(block, iv, false)
-// }
-
-// // NewCFBDecrypter returns a Stream which decrypts with cipher feedback mode,
-// // using the given Block. The iv must be the same length as the Block's block
-// // size.
-// func NewCFBDecrypter(block Block, iv []byte) Stream {
-// 	return newCFB(block, iv, true)
-// }
-
-// func newCFB(block Block, iv []byte, decrypt bool) Stream {
-// 	blockSize := block.block_size()
-// 	if len(iv) != blockSize {
-// 		// stack trace will indicate whether it was de or encryption
-// 		panic("cipher.newCFB: IV length must equal block size")
-// 	}
-// 	x := &cfb{
-// 		b:       block,
-// 		out:     make([]byte, blockSize),
-// 		next:    make([]byte, blockSize),
-// 		outUsed: blockSize,
-// 		decrypt: decrypt,
-// 	}
-// 	copy(x.next, iv)
-
-// 	return x
-// }
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2010 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! CFB (Cipher Feedback) Mode.
+
+use crate::compat;
+use crate::crypto::cipher::{Block, Stream};
+use crate::crypto::subtle;
+
+struct CFBStream<'a, B: Block> {
+    b: &'a B,
+    next: Vec<u8>,
+    out: Vec<u8>,
+    out_used: usize,
+
+    decrypt: bool,
+}
+
+impl<'a, B: Block> CFBStream<'a, B> {
+    fn new(block: &'a B, iv: &[u8], decrypt: bool) -> Self {
+        let block_size = block.block_size();
+        if iv.len() != block_size {
+            // stack trace will indicate whether it was de or encryption
+            panic!("cipher.newCFB: IV length must equal block size");
+        }
+        Self {
+            b: block,
+            out: vec![0; block_size],
+            next: iv.to_vec(),
+            out_used: block_size,
+            decrypt,
+        }
+    }
+
+    fn xor_key_stream(&mut self, dst: &mut [u8], src: &[u8]) {
+        if dst.len() < src.len() {
+            panic!("crypto/cipher: output smaller than input");
+        }
+        let mut dst = dst;
+        let mut src = src;
+        while !src.is_empty() {
+            if self.out_used == self.out.len() {
+                self.b.encrypt(&mut self.out, &self.next);
+                self.out_used = 0;
+            }
+
+            if self.decrypt {
+                // We can precompute a larger segment of the
+                // keystream on decryption. This will allow
+                // larger batches for xor, and we should be
+                // able to match CTR/OFB performance.
+                compat::copy(&mut self.next[self.out_used..], src);
+            }
+            let n = subtle::xor_bytes(dst, src, &self.out[self.out_used..]);
+            if !self.decrypt {
+                compat::copy(&mut self.next[self.out_used..], &dst[..n]);
+            }
+            dst = &mut dst[n..];
+            src = &src[n..];
+            self.out_used += n;
+        }
+    }
+
+    fn xor_key_stream_inplace(&mut self, data: &mut [u8]) {
+        let mut data = data;
+        while !data.is_empty() {
+            if self.out_used == self.out.len() {
+                self.b.encrypt(&mut self.out, &self.next);
+                self.out_used = 0;
+            }
+
+            if self.decrypt {
+                compat::copy(&mut self.next[self.out_used..], data);
+            }
+            let n = subtle::xor_bytes_inplace(data, &self.out[self.out_used..]);
+            if !self.decrypt {
+                compat::copy(&mut self.next[self.out_used..], &data[..n]);
+            }
+            data = &mut data[n..];
+            self.out_used += n;
+        }
+    }
+}
+
+/// CFBEncrypter is a Stream which encrypts with cipher feedback mode,
+/// using the given Block.
+pub struct CFBEncrypter<'a, B: Block>(CFBStream<'a, B>);
+
+impl<'a, B: Block> CFBEncrypter<'a, B> {
+    /// new returns a Stream which encrypts with cipher feedback mode,
+    /// using the given Block. The iv must be the same length as the Block's block
+    /// size.
+    pub fn new(block: &'a B, iv: &[u8]) -> Self {
+        Self(CFBStream::new(block, iv, false))
+    }
+}
+
+impl<'a, B: Block> Stream for CFBEncrypter<'a, B> {
+    fn xor_key_stream(&mut self, dst: &mut [u8], src: &[u8]) {
+        self.0.xor_key_stream(dst, src);
+    }
+
+    fn xor_key_stream_inplace(&mut self, data: &mut [u8]) {
+        self.0.xor_key_stream_inplace(data);
+    }
+}
+
+/// CFBDecrypter is a Stream which decrypts with cipher feedback mode,
+/// using the given Block.
+pub struct CFBDecrypter<'a, B: Block>(CFBStream<'a, B>);
+
+impl<'a, B: Block> CFBDecrypter<'a, B> {
+    /// new returns a Stream which decrypts with cipher feedback mode,
+    /// using the given Block. The iv must be the same length as the Block's block
+    /// size.
+    pub fn new(block: &'a B, iv: &[u8]) -> Self {
+        Self(CFBStream::new(block, iv, true))
+    }
+}
+
+impl<'a, B: Block> Stream for CFBDecrypter<'a, B> {
+    fn xor_key_stream(&mut self, dst: &mut [u8], src: &[u8]) {
+        self.0.xor_key_stream(dst, src);
+    }
+
+    fn xor_key_stream_inplace(&mut self, data: &mut [u8]) {
+        self.0.xor_key_stream_inplace(data);
+    }
+}
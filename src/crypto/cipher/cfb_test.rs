@@ -1,113 +1,151 @@
-// // Copyright 2010 The Go Authors. All rights reserved.
-// // Use of this source code is governed by a BSD-style
-// // license that can be found in the LICENSE file.
-
-// package cipher_test
-
-// import (
-// 	"bytes"
-// 	"crypto/aes"
-// 	"crypto/cipher"
-// 	"crypto/rand"
-// 	"encoding/hex"
-// 	"testing"
-// )
-
-// // cfbTests contains the test vectors from
-// // https://csrc.nist.gov/publications/nistpubs/800-38a/sp800-38a.pdf, section
-// // F.3.13.
-// var cfbTests = []struct {
-// 	key, iv, plaintext, ciphertext string
-// }{
-// 	{
-// 		"2b7e151628aed2a6abf7158809cf4f3c",
-// 		"000102030405060708090a0b0c0d0e0f",
-// 		"6bc1bee22e409f96e93d7e117393172a",
-// 		"3b3fd92eb72dad20333449f8e83cfb4a",
-// 	},
-// 	{
-// 		"2b7e151628aed2a6abf7158809cf4f3c",
-// 		"3B3FD92EB72DAD20333449F8E83CFB4A",
-// 		"ae2d8a571e03ac9c9eb76fac45af8e51",
-// 		"c8a64537a0b3a93fcde3cdad9f1ce58b",
-// 	},
-// 	{
-// 		"2b7e151628aed2a6abf7158809cf4f3c",
-// 		"C8A64537A0B3A93FCDE3CDAD9F1CE58B",
-// 		"30c81c46a35ce411e5fbc1191a0a52ef",
-// 		"26751f67a3cbb140b1808cf187a4f4df",
-// 	},
-// 	{
-// 		"2b7e151628aed2a6abf7158809cf4f3c",
-// 		"26751F67A3CBB140B1808CF187A4F4DF",
-// 		"f69f2445df4f9b17ad2b417be66c3710",
-// 		"c04b05357c5d1c0eeac4c66f9ff7f2e6",
-// 	},
-// }
-
-// func TestCFBVectors(t *testing.T) {
-// 	for i, test := range cfbTests {
-// 		key, err := hex.DecodeString(test.key)
-// 		if err != nil {
-// 			t.Fatal(err)
-// 		}
-// 		iv, err := hex.DecodeString(test.iv)
-// 		if err != nil {
-// 			t.Fatal(err)
-// 		}
-// 		plaintext, err := hex.DecodeString(test.plaintext)
-// 		if err != nil {
-// 			t.Fatal(err)
-// 		}
-// 		expected, err := hex.DecodeString(test.ciphertext)
-// 		if err != nil {
-// 			t.Fatal(err)
-// 		}
-
-// 		block, err := aes.NewCipher(key)
-// 		if err != nil {
-// 			t.Fatal(err)
-// 		}
-
-// 		ciphertext := make([]byte, len(plaintext))
-// 		cfb := cipher.NewCFBEncrypter(block, iv)
-// 		cfb.xor_key_stream(ciphertext, plaintext)
-
-// 		if !bytes.Equal(ciphertext, expected) {
-// 			t.Errorf("#%d: wrong output: got %x, expected %x", i, ciphertext, expected)
-// 		}
-
-// 		cfbdec := cipher.NewCFBDecrypter(block, iv)
-// 		plaintextCopy := make([]byte, len(ciphertext))
-// 		cfbdec.xor_key_stream(plaintextCopy, ciphertext)
-
-// 		if !bytes.Equal(plaintextCopy, plaintext) {
-// 			t.Errorf("#%d: wrong plaintext: got %x, expected %x", i, plaintextCopy, plaintext)
-// 		}
-// 	}
-// }
-
-// func TestCFBInverse(t *testing.T) {
-// 	block, err := aes.NewCipher(commonKey128)
-// 	if err != nil {
-// 		t.Error(err)
-// 		return
-// 	}
-
-// 	plaintext := []byte("this is the plaintext. this is the plaintext.")
-// 	iv := make([]byte, block.block_size())
-// 	rand.Reader.Read(iv)
-// 	cfb := cipher.NewCFBEncrypter(block, iv)
-// 	ciphertext := make([]byte, len(plaintext))
-// 	copy(ciphertext, plaintext)
-// 	cfb.xor_key_stream(ciphertext, ciphertext)
-
-// 	cfbdec := cipher.NewCFBDecrypter(block, iv)
-// 	plaintextCopy := make([]byte, len(plaintext))
-// 	copy(plaintextCopy, ciphertext)
-// 	cfbdec.xor_key_stream(plaintextCopy, plaintextCopy)
-
-// 	if !bytes.Equal(plaintextCopy, plaintext) {
-// 		t.Errorf("got: %x, want: %x", plaintextCopy, plaintext)
-// 	}
-// }
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2010 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::common_test::COMMON_KEY128;
+use crate::crypto::aes;
+use crate::crypto::cipher::{self, Block, Stream};
+use crate::crypto::rand;
+use crate::encoding::hex;
+
+struct CfbTest {
+    key: &'static str,
+    iv: &'static str,
+    plaintext: &'static str,
+    ciphertext: &'static str,
+}
+
+impl CfbTest {
+    const fn new(
+        key: &'static str,
+        iv: &'static str,
+        plaintext: &'static str,
+        ciphertext: &'static str,
+    ) -> Self {
+        Self {
+            key,
+            iv,
+            plaintext,
+            ciphertext,
+        }
+    }
+}
+
+/// CFB_TESTS contains the test vectors from
+/// <https://csrc.nist.gov/publications/nistpubs/800-38a/sp800-38a.pdf>, section
+/// F.3.13.
+const CFB_TESTS: &[CfbTest] = &[
+    CfbTest::new(
+        "2b7e151628aed2a6abf7158809cf4f3c",
+        "000102030405060708090a0b0c0d0e0f",
+        "6bc1bee22e409f96e93d7e117393172a",
+        "3b3fd92eb72dad20333449f8e83cfb4a",
+    ),
+    CfbTest::new(
+        "2b7e151628aed2a6abf7158809cf4f3c",
+        "3B3FD92EB72DAD20333449F8E83CFB4A",
+        "ae2d8a571e03ac9c9eb76fac45af8e51",
+        "c8a64537a0b3a93fcde3cdad9f1ce58b",
+    ),
+    CfbTest::new(
+        "2b7e151628aed2a6abf7158809cf4f3c",
+        "C8A64537A0B3A93FCDE3CDAD9F1CE58B",
+        "30c81c46a35ce411e5fbc1191a0a52ef",
+        "26751f67a3cbb140b1808cf187a4f4df",
+    ),
+    CfbTest::new(
+        "2b7e151628aed2a6abf7158809cf4f3c",
+        "26751F67A3CBB140B1808CF187A4F4DF",
+        "f69f2445df4f9b17ad2b417be66c3710",
+        "c04b05357c5d1c0eeac4c66f9ff7f2e6",
+    ),
+];
+
+#[test]
+fn test_cfb_vectors() {
+    for (i, test) in CFB_TESTS.iter().enumerate() {
+        let (key, err) = hex::decode_string(test.key);
+        assert!(err.is_none());
+        let (iv, err) = hex::decode_string(test.iv);
+        assert!(err.is_none());
+        let (plaintext, err) = hex::decode_string(test.plaintext);
+        assert!(err.is_none());
+        let (expected, err) = hex::decode_string(test.ciphertext);
+        assert!(err.is_none());
+
+        let block = aes::Cipher::new(&key).unwrap();
+
+        let mut ciphertext = vec![0; plaintext.len()];
+        let mut cfb = cipher::CFBEncrypter::new(&block, &iv);
+        cfb.xor_key_stream(&mut ciphertext, &plaintext);
+        assert_eq!(
+            ciphertext, expected,
+            "#{}: wrong output: got {:x?}, expected {:x?}",
+            i, ciphertext, expected
+        );
+
+        let mut cfbdec = cipher::CFBDecrypter::new(&block, &iv);
+        let mut plaintext_copy = vec![0; ciphertext.len()];
+        cfbdec.xor_key_stream(&mut plaintext_copy, &ciphertext);
+        assert_eq!(
+            plaintext_copy, plaintext,
+            "#{}: wrong plaintext: got {:x?}, expected {:x?}",
+            i, plaintext_copy, plaintext
+        );
+    }
+}
+
+#[test]
+fn test_cfb_inverse() {
+    let block = aes::Cipher::new(COMMON_KEY128).unwrap();
+
+    let plaintext = b"this is the plaintext. this is the plaintext.";
+    let mut iv = vec![0; block.block_size()];
+    rand::read(&mut iv).unwrap();
+    let mut cfb = cipher::CFBEncrypter::new(&block, &iv);
+    let mut ciphertext = plaintext.to_vec();
+    cfb.xor_key_stream_inplace(&mut ciphertext);
+
+    let mut cfbdec = cipher::CFBDecrypter::new(&block, &iv);
+    let mut plaintext_copy = ciphertext.clone();
+    cfbdec.xor_key_stream_inplace(&mut plaintext_copy);
+
+    assert_eq!(
+        plaintext_copy, plaintext,
+        "got: {:x?}, want: {:x?}",
+        plaintext_copy, plaintext
+    );
+}
+
+#[test]
+fn test_cfb_split_writes() {
+    // Feeding the data in uneven pieces must give the same result
+    // as a single call.
+    let block = aes::Cipher::new(COMMON_KEY128).unwrap();
+    let iv = [7; aes::BLOCK_SIZE];
+    let plaintext: Vec<u8> = (0..100).collect();
+
+    let mut want = vec![0; plaintext.len()];
+    cipher::CFBEncrypter::new(&block, &iv).xor_key_stream(&mut want, &plaintext);
+
+    let mut cfb = cipher::CFBEncrypter::new(&block, &iv);
+    let mut got = vec![0; plaintext.len()];
+    let mut start = 0;
+    for size in [1, 5, 16, 17, 30, 31] {
+        cfb.xor_key_stream(
+            &mut got[start..start + size],
+            &plaintext[start..start + size],
+        );
+        start += size;
+    }
+    assert_eq!(got, want);
+
+    let mut cfbdec = cipher::CFBDecrypter::new(&block, &iv);
+    let mut start = 0;
+    for size in [3, 13, 29, 55] {
+        cfbdec.xor_key_stream_inplace(&mut got[start..start + size]);
+        start += size;
+    }
+    assert_eq!(got, plaintext);
+}
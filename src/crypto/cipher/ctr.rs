@@ -31,7 +31,7 @@ pub struct CTR<'a, B: Block> {
     out_used: usize,
 }
 
-const STREAM_BUFFER_SIZE: usize = 512;
+pub(super) const STREAM_BUFFER_SIZE: usize = 512;
 
 // // ctrAble is an interface implemented by ciphers that have a specific optimized
 // // implementation of CTR, like crypto/aes. NewCTR will check for this interface
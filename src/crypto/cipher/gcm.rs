@@ -1,50 +1,54 @@
-// // Copyright 2013 The Go Authors. All rights reserved.
-// // Use of this source code is governed by a BSD-style
-// // license that can be found in the LICENSE file.
-
-// package cipher
-
-// import (
-// 	"crypto/internal/alias"
-// 	"crypto/subtle"
-// 	"encoding/binary"
-// 	"errors"
-// )
-
-// // AEAD is a cipher mode providing authenticated encryption with associated
-// // data. For a description of the methodology, see
-// // https://en.wikipedia.org/wiki/Authenticated_encryption.
-// type AEAD interface {
-// 	// NonceSize returns the size of the nonce that must be passed to Seal
-// 	// and Open.
-// 	NonceSize() int
-
-// 	// Overhead returns the maximum difference between the lengths of a
-// 	// plaintext and its ciphertext.
-// 	Overhead() int
-
-// 	// Seal encrypts and authenticates plaintext, authenticates the
-// 	// additional data and appends the result to dst, returning the updated
-// 	// slice. The nonce must be NonceSize() bytes long and unique for all
-// 	// time, for a given key.
-// 	//
-// 	// To reuse plaintext's storage for the encrypted output, use plaintext[:0]
-// 	// as dst. Otherwise, the remaining capacity of dst must not overlap plaintext.
-// 	Seal(dst, nonce, plaintext, additionalData []byte) []byte
-
-// 	// Open decrypts and authenticates ciphertext, authenticates the
-// 	// additional data and, if successful, appends the resulting plaintext
-// 	// to dst, returning the updated slice. The nonce must be NonceSize()
-// 	// bytes long and both it and the additional data must match the
-// 	// value passed to Seal.
-// 	//
-// 	// To reuse ciphertext's storage for the decrypted output, use ciphertext[:0]
-// 	// as dst. Otherwise, the remaining capacity of dst must not overlap plaintext.
-// 	//
-// 	// Even if the function fails, the contents of dst, up to its capacity,
-// 	// may be overwritten.
-// 	Open(dst, nonce, ciphertext, additionalData []byte) ([]byte, error)
-// }
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2013 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! Galois Counter Mode (GCM).
+//!
+//! GCM is an authenticated encryption mode built on a 128-bit block cipher
+//! running in counter mode, with the GHASH universal hash used for
+//! authentication.
+//!
+//! See <https://csrc.nist.gov/groups/ST/toolkit/BCM/documents/proposedmodes/gcm/gcm-revised-spec.pdf>
+//! and NIST Special Publication 800-38D.
+
+use super::gcm_arch;
+use crate::crypto::cipher::Block;
+use crate::crypto::subtle;
+use crate::encoding::binary::{ByteOrder, BIG_ENDIAN};
+use crate::errors;
+
+/// AEAD is a cipher mode providing authenticated encryption with associated
+/// data. For a description of the methodology, see
+/// <https://en.wikipedia.org/wiki/Authenticated_encryption>.
+pub trait AEAD {
+    /// nonce_size returns the size of the nonce that must be passed to seal
+    /// and open.
+    fn nonce_size(&self) -> usize;
+
+    /// overhead returns the maximum difference between the lengths of a
+    /// plaintext and its ciphertext.
+    fn overhead(&self) -> usize;
+
+    /// seal encrypts and authenticates plaintext, authenticates the
+    /// additional data and appends the result to dst. The nonce must be
+    /// nonce_size() bytes long and unique for all time, for a given key.
+    fn seal(&self, dst: &mut Vec<u8>, nonce: &[u8], plaintext: &[u8], additional_data: &[u8]);
+
+    /// open decrypts and authenticates ciphertext, authenticates the
+    /// additional data and, if successful, appends the resulting plaintext
+    /// to dst. The nonce must be nonce_size() bytes long and both it and the
+    /// additional data must match the value passed to seal.
+    ///
+    /// If the function fails, dst is left unchanged.
+    fn open(
+        &self,
+        dst: &mut Vec<u8>,
+        nonce: &[u8],
+        ciphertext: &[u8],
+        additional_data: &[u8],
+    ) -> Result<(), errors::ErrorStaticString>;
+}
 
 // // gcmAble is an interface implemented by ciphers that have a specific optimized
 // // implementation of GCM, like crypto/aes. NewGCM will check for this interface
@@ -53,375 +57,410 @@
 // 	NewGCM(nonceSize, tagSize int) (AEAD, error)
 // }
 
-// // gcmFieldElement represents a value in GF(2¹²⁸). In order to reflect the GCM
-// // standard and make binary.BigEndian suitable for marshaling these values, the
-// // bits are stored in big endian order. For example:
-// //
-// //	the coefficient of x⁰ can be obtained by v.low >> 63.
-// //	the coefficient of x⁶³ can be obtained by v.low & 1.
-// //	the coefficient of x⁶⁴ can be obtained by v.high >> 63.
-// //	the coefficient of x¹²⁷ can be obtained by v.high & 1.
-// type gcmFieldElement struct {
-// 	low, high uint64
-// }
-
-// // gcm represents a Galois Counter Mode with a specific key. See
-// // https://csrc.nist.gov/groups/ST/toolkit/BCM/documents/proposedmodes/gcm/gcm-revised-spec.pdf
-// type gcm struct {
-// 	cipher    Block
-// 	nonceSize int
-// 	tagSize   int
-// 	// productTable contains the first sixteen powers of the key, H.
-// 	// However, they are in bit reversed order. See NewGCMWithNonceSize.
-// 	productTable [16]gcmFieldElement
-// }
-
-// // NewGCM returns the given 128-bit, block cipher wrapped in Galois Counter Mode
-// // with the standard nonce length.
-// //
-// // In general, the GHASH operation performed by this implementation of GCM is not constant-time.
-// // An exception is when the underlying Block was created by aes.NewCipher
-// // on systems with hardware support for AES. See the crypto/aes package documentation for details.
-// func NewGCM(cipher Block) (AEAD, error) {
-// 	return newGCMWithNonceAndTagSize(cipher, gcmStandardNonceSize, gcmTagSize)
-// }
-
-// // NewGCMWithNonceSize returns the given 128-bit, block cipher wrapped in Galois
-// // Counter Mode, which accepts nonces of the given length. The length must not
-// // be zero.
-// //
-// // Only use this function if you require compatibility with an existing
-// // cryptosystem that uses non-standard nonce lengths. All other users should use
-// // NewGCM, which is faster and more resistant to misuse.
-// func NewGCMWithNonceSize(cipher Block, size int) (AEAD, error) {
-// 	return newGCMWithNonceAndTagSize(cipher, size, gcmTagSize)
-// }
-
-// // NewGCMWithTagSize returns the given 128-bit, block cipher wrapped in Galois
-// // Counter Mode, which generates tags with the given length.
-// //
-// // Tag sizes between 12 and 16 bytes are allowed.
-// //
-// // Only use this function if you require compatibility with an existing
-// // cryptosystem that uses non-standard tag lengths. All other users should use
-// // NewGCM, which is more resistant to misuse.
-// func NewGCMWithTagSize(cipher Block, tagSize int) (AEAD, error) {
-// 	return newGCMWithNonceAndTagSize(cipher, gcmStandardNonceSize, tagSize)
-// }
-
-// func newGCMWithNonceAndTagSize(cipher Block, nonceSize, tagSize int) (AEAD, error) {
-// 	if tagSize < gcmMinimumTagSize || tagSize > gcmblock_size {
-// 		return nil, errors.New("cipher: incorrect tag size given to GCM")
-// 	}
-
-// 	if nonceSize <= 0 {
-// 		return nil, errors.New("cipher: the nonce can't have zero length, or the security of the key will be immediately compromised")
-// 	}
-
-// 	if cipher, ok := cipher.(gcmAble); ok {
-// 		return cipher.NewGCM(nonceSize, tagSize)
-// 	}
-
-// 	if cipher.block_size() != gcmblock_size {
-// 		return nil, errors.New("cipher: NewGCM requires 128-bit block cipher")
-// 	}
-
-// 	var key [gcmblock_size]byte
-// 	cipher.encrypt(key[:], key[:])
-
-// 	g := &gcm{cipher: cipher, nonceSize: nonceSize, tagSize: tagSize}
-
-// 	// We precompute 16 multiples of |key|. However, when we do lookups
-// 	// into this table we'll be using bits from a field element and
-// 	// therefore the bits will be in the reverse order. So normally one
-// 	// would expect, say, 4*key to be in index 4 of the table but due to
-// 	// this bit ordering it will actually be in index 0010 (base 2) = 2.
-// 	x := gcmFieldElement{
-// 		binary.BigEndian.Uint64(key[:8]),
-// 		binary.BigEndian.Uint64(key[8:]),
-// 	}
-// 	g.productTable[reverseBits(1)] = x
-
-// 	for i := 2; i < 16; i += 2 {
-// 		g.productTable[reverseBits(i)] = gcmDouble(&g.productTable[reverseBits(i/2)])
-// 		g.productTable[reverseBits(i+1)] = gcmAdd(&g.productTable[reverseBits(i)], &x)
-// 	}
-
-// 	return g, nil
-// }
-
-// const (
-// 	gcmblock_size         = 16
-// 	gcmTagSize           = 16
-// 	gcmMinimumTagSize    = 12 // NIST SP 800-38D recommends tags with 12 or more bytes.
-// 	gcmStandardNonceSize = 12
-// )
-
-// func (g *gcm) NonceSize() int {
-// 	return g.nonceSize
-// }
-
-// func (g *gcm) Overhead() int {
-// 	return g.tagSize
-// }
-
-// func (g *gcm) Seal(dst, nonce, plaintext, data []byte) []byte {
-// 	if len(nonce) != g.nonceSize {
-// 		panic("crypto/cipher: incorrect nonce length given to GCM")
-// 	}
-// 	if uint64(len(plaintext)) > ((1<<32)-2)*uint64(g.cipher.block_size()) {
-// 		panic("crypto/cipher: message too large for GCM")
-// 	}
-
-// 	ret, out := sliceForAppend(dst, len(plaintext)+g.tagSize)
-// 	if alias.InexactOverlap(out, plaintext) {
-// 		panic("crypto/cipher: invalid buffer overlap")
-// 	}
-
-// 	var counter, tagMask [gcmblock_size]byte
-// 	g.deriveCounter(&counter, nonce)
-
-// 	g.cipher.encrypt(tagMask[:], counter[:])
-// 	gcmInc32(&counter)
-
-// 	g.counterCrypt(out, plaintext, &counter)
-
-// 	var tag [gcmTagSize]byte
-// 	g.auth(tag[:], out[:len(plaintext)], data, &tagMask)
-// 	copy(out[len(plaintext):], tag[:])
-
-// 	return ret
-// }
-
-// var errOpen = errors.New("cipher: message authentication failed")
-
-// func (g *gcm) Open(dst, nonce, ciphertext, data []byte) ([]byte, error) {
-// 	if len(nonce) != g.nonceSize {
-// 		panic("crypto/cipher: incorrect nonce length given to GCM")
-// 	}
-// 	// Sanity check to prevent the authentication from always succeeding if an implementation
-// 	// leaves tagSize uninitialized, for example.
-// 	if g.tagSize < gcmMinimumTagSize {
-// 		panic("crypto/cipher: incorrect GCM tag size")
-// 	}
-
-// 	if len(ciphertext) < g.tagSize {
-// 		return nil, errOpen
-// 	}
-// 	if uint64(len(ciphertext)) > ((1<<32)-2)*uint64(g.cipher.block_size())+uint64(g.tagSize) {
-// 		return nil, errOpen
-// 	}
-
-// 	tag := ciphertext[len(ciphertext)-g.tagSize:]
-// 	ciphertext = ciphertext[:len(ciphertext)-g.tagSize]
-
-// 	var counter, tagMask [gcmblock_size]byte
-// 	g.deriveCounter(&counter, nonce)
-
-// 	g.cipher.encrypt(tagMask[:], counter[:])
-// 	gcmInc32(&counter)
-
-// 	var expectedTag [gcmTagSize]byte
-// 	g.auth(expectedTag[:], ciphertext, data, &tagMask)
-
-// 	ret, out := sliceForAppend(dst, len(ciphertext))
-// 	if alias.InexactOverlap(out, ciphertext) {
-// 		panic("crypto/cipher: invalid buffer overlap")
-// 	}
-
-// 	if subtle.ConstantTimeCompare(expectedTag[:g.tagSize], tag) != 1 {
-// 		// The AESNI code decrypts and authenticates concurrently, and
-// 		// so overwrites dst in the event of a tag mismatch. That
-// 		// behavior is mimicked here in order to be consistent across
-// 		// platforms.
-// 		for i := range out {
-// 			out[i] = 0
-// 		}
-// 		return nil, errOpen
-// 	}
-
-// 	g.counterCrypt(out, ciphertext, &counter)
-
-// 	return ret, nil
-// }
-
-// // reverseBits reverses the order of the bits of 4-bit number in i.
-// func reverseBits(i int) int {
-// 	i = ((i << 2) & 0xc) | ((i >> 2) & 0x3)
-// 	i = ((i << 1) & 0xa) | ((i >> 1) & 0x5)
-// 	return i
-// }
-
-// // gcmAdd adds two elements of GF(2¹²⁸) and returns the sum.
-// func gcmAdd(x, y *gcmFieldElement) gcmFieldElement {
-// 	// Addition in a characteristic 2 field is just XOR.
-// 	return gcmFieldElement{x.low ^ y.low, x.high ^ y.high}
-// }
-
-// // gcmDouble returns the result of doubling an element of GF(2¹²⁸).
-// func gcmDouble(x *gcmFieldElement) (double gcmFieldElement) {
-// 	msbSet := x.high&1 == 1
-
-// 	// Because of the bit-ordering, doubling is actually a right shift.
-// 	double.high = x.high >> 1
-// 	double.high |= x.low << 63
-// 	double.low = x.low >> 1
-
-// 	// If the most-significant bit was set before shifting then it,
-// 	// conceptually, becomes a term of x^128. This is greater than the
-// 	// irreducible polynomial so the result has to be reduced. The
-// 	// irreducible polynomial is 1+x+x^2+x^7+x^128. We can subtract that to
-// 	// eliminate the term at x^128 which also means subtracting the other
-// 	// four terms. In characteristic 2 fields, subtraction == addition ==
-// 	// XOR.
-// 	if msbSet {
-// 		double.low ^= 0xe100000000000000
-// 	}
-
-// 	return
-// }
-
-// var gcmReductionTable = []uint16{
-// 	0x0000, 0x1c20, 0x3840, 0x2460, 0x7080, 0x6ca0, 0x48c0, 0x54e0,
-// 	0xe100, 0xfd20, 0xd940, 0xc560, 0x9180, 0x8da0, 0xa9c0, 0xb5e0,
-// }
-
-// // mul sets y to y*H, where H is the GCM key, fixed during NewGCMWithNonceSize.
-// func (g *gcm) mul(y *gcmFieldElement) {
-// 	var z gcmFieldElement
-
-// 	for i := 0; i < 2; i++ {
-// 		word := y.high
-// 		if i == 1 {
-// 			word = y.low
-// 		}
-
-// 		// Multiplication works by multiplying z by 16 and adding in
-// 		// one of the precomputed multiples of H.
-// 		for j := 0; j < 64; j += 4 {
-// 			msw := z.high & 0xf
-// 			z.high >>= 4
-// 			z.high |= z.low << 60
-// 			z.low >>= 4
-// 			z.low ^= uint64(gcmReductionTable[msw]) << 48
-
-// 			// the values in |table| are ordered for
-// 			// little-endian bit positions. See the comment
-// 			// in NewGCMWithNonceSize.
-// 			t := &g.productTable[word&0xf]
-
-// 			z.low ^= t.low
-// 			z.high ^= t.high
-// 			word >>= 4
-// 		}
-// 	}
-
-// 	*y = z
-// }
-
-// // updateBlocks extends y with more polynomial terms from blocks, based on
-// // Horner's rule. There must be a multiple of gcmblock_size bytes in blocks.
-// func (g *gcm) updateBlocks(y *gcmFieldElement, blocks []byte) {
-// 	for len(blocks) > 0 {
-// 		y.low ^= binary.BigEndian.Uint64(blocks)
-// 		y.high ^= binary.BigEndian.Uint64(blocks[8:])
-// 		g.mul(y)
-// 		blocks = blocks[gcmblock_size:]
-// 	}
-// }
-
-// // update extends y with more polynomial terms from data. If data is not a
-// // multiple of gcmblock_size bytes long then the remainder is zero padded.
-// func (g *gcm) update(y *gcmFieldElement, data []byte) {
-// 	fullBlocks := (len(data) >> 4) << 4
-// 	g.updateBlocks(y, data[:fullBlocks])
-
-// 	if len(data) != fullBlocks {
-// 		var partialBlock [gcmblock_size]byte
-// 		copy(partialBlock[:], data[fullBlocks:])
-// 		g.updateBlocks(y, partialBlock[:])
-// 	}
-// }
-
-// // gcmInc32 treats the final four bytes of counterBlock as a big-endian value
-// // and increments it.
-// func gcmInc32(counterBlock *[16]byte) {
-// 	ctr := counterBlock[len(counterBlock)-4:]
-// 	binary.BigEndian.PutUint32(ctr, binary.BigEndian.Uint32(ctr)+1)
-// }
-
-// // sliceForAppend takes a slice and a requested number of bytes. It returns a
-// // slice with the contents of the given slice followed by that many bytes and a
-// // second slice that aliases into it and contains only the extra bytes. If the
-// // original slice has sufficient capacity then no allocation is performed.
-// func sliceForAppend(in []byte, n int) (head, tail []byte) {
-// 	if total := len(in) + n; cap(in) >= total {
-// 		head = in[:total]
-// 	} else {
-// 		head = make([]byte, total)
-// 		copy(head, in)
-// 	}
-// 	tail = head[len(in):]
-// 	return
-// }
-
-// // counterCrypt crypts in to out using g.cipher in counter mode.
-// func (g *gcm) counterCrypt(out, in []byte, counter *[gcmblock_size]byte) {
-// 	var mask [gcmblock_size]byte
-
-// 	for len(in) >= gcmblock_size {
-// 		g.cipher.encrypt(mask[:], counter[:])
-// 		gcmInc32(counter)
-
-// 		subtle.XORBytes(out, in, mask[:])
-// 		out = out[gcmblock_size:]
-// 		in = in[gcmblock_size:]
-// 	}
-
-// 	if len(in) > 0 {
-// 		g.cipher.encrypt(mask[:], counter[:])
-// 		gcmInc32(counter)
-// 		subtle.XORBytes(out, in, mask[:])
-// 	}
-// }
-
-// // deriveCounter computes the initial GCM counter state from the given nonce.
-// // See NIST SP 800-38D, section 7.1. This assumes that counter is filled with
-// // zeros on entry.
-// func (g *gcm) deriveCounter(counter *[gcmblock_size]byte, nonce []byte) {
-// 	// GCM has two modes of operation with respect to the initial counter
-// 	// state: a "fast path" for 96-bit (12-byte) nonces, and a "slow path"
-// 	// for nonces of other lengths. For a 96-bit nonce, the nonce, along
-// 	// with a four-byte big-endian counter starting at one, is used
-// 	// directly as the starting counter. For other nonce sizes, the counter
-// 	// is computed by passing it through the GHASH function.
-// 	if len(nonce) == gcmStandardNonceSize {
-// 		copy(counter[:], nonce)
-// 		counter[gcmblock_size-1] = 1
-// 	} else {
-// 		var y gcmFieldElement
-// 		g.update(&y, nonce)
-// 		y.high ^= uint64(len(nonce)) * 8
-// 		g.mul(&y)
-// 		binary.BigEndian.PutUint64(counter[:8], y.low)
-// 		binary.BigEndian.PutUint64(counter[8:], y.high)
-// 	}
-// }
-
-// // auth calculates GHASH(ciphertext, additionalData), masks the result with
-// // tagMask and writes the result to out.
-// func (g *gcm) auth(out, ciphertext, additionalData []byte, tagMask *[gcmTagSize]byte) {
-// 	var y gcmFieldElement
-// 	g.update(&y, additionalData)
-// 	g.update(&y, ciphertext)
-
-// 	y.low ^= uint64(len(additionalData)) * 8
-// 	y.high ^= uint64(len(ciphertext)) * 8
-
-// 	g.mul(&y)
-
-// 	binary.BigEndian.PutUint64(out, y.low)
-// 	binary.BigEndian.PutUint64(out[8:], y.high)
-
-// 	subtle.XORBytes(out, out, tagMask[:])
-// }
+/// GCMFieldElement represents a value in GF(2¹²⁸). In order to reflect the GCM
+/// standard and make binary::BIG_ENDIAN suitable for marshaling these values, the
+/// bits are stored in big endian order. For example:
+///
+///   the coefficient of x⁰ can be obtained by v.low >> 63.
+///   the coefficient of x⁶³ can be obtained by v.low & 1.
+///   the coefficient of x⁶⁴ can be obtained by v.high >> 63.
+///   the coefficient of x¹²⁷ can be obtained by v.high & 1.
+#[derive(Clone, Copy, Default)]
+pub(super) struct GCMFieldElement {
+    pub(super) low: u64,
+    pub(super) high: u64,
+}
+
+/// GCM represents a Galois Counter Mode with a specific key. See
+/// <https://csrc.nist.gov/groups/ST/toolkit/BCM/documents/proposedmodes/gcm/gcm-revised-spec.pdf>
+pub struct GCM<'a, B: Block> {
+    cipher: &'a B,
+    nonce_size: usize,
+    tag_size: usize,
+    /// product_table contains the first sixteen powers of the key, H.
+    /// However, they are in bit reversed order. See new_with_nonce_and_tag_size.
+    product_table: [GCMFieldElement; 16],
+    /// h is the key, H, used by the carry-less multiplication.
+    pub(super) h: GCMFieldElement,
+    /// use_clmul reports whether GHASH uses the carry-less multiplication
+    /// instruction instead of product_table.
+    use_clmul: bool,
+}
+
+pub(super) const GCM_BLOCK_SIZE: usize = 16;
+const GCM_TAG_SIZE: usize = 16;
+const GCM_MINIMUM_TAG_SIZE: usize = 12; // NIST SP 800-38D recommends tags with 12 or more bytes.
+const GCM_STANDARD_NONCE_SIZE: usize = 12;
+
+static ERR_OPEN: errors::ErrorStaticString =
+    errors::new_static("cipher: message authentication failed");
+
+impl<'a, B: Block> GCM<'a, B> {
+    /// new returns the given 128-bit, block cipher wrapped in Galois Counter Mode
+    /// with the standard nonce length.
+    ///
+    /// In general, the GHASH operation performed by this implementation of GCM is not constant-time.
+    /// An exception is when running on x86_64 systems with the PCLMULQDQ extension,
+    /// where the carry-less multiplication instruction is used instead of lookup tables.
+    pub fn new(cipher: &'a B) -> Result<Self, errors::ErrorStaticString> {
+        Self::new_with_nonce_and_tag_size(cipher, GCM_STANDARD_NONCE_SIZE, GCM_TAG_SIZE)
+    }
+
+    /// new_with_nonce_size returns the given 128-bit, block cipher wrapped in Galois
+    /// Counter Mode, which accepts nonces of the given length. The length must not
+    /// be zero.
+    ///
+    /// Only use this function if you require compatibility with an existing
+    /// cryptosystem that uses non-standard nonce lengths. All other users should use
+    /// new, which is faster and more resistant to misuse.
+    pub fn new_with_nonce_size(
+        cipher: &'a B,
+        size: usize,
+    ) -> Result<Self, errors::ErrorStaticString> {
+        Self::new_with_nonce_and_tag_size(cipher, size, GCM_TAG_SIZE)
+    }
+
+    /// new_with_tag_size returns the given 128-bit, block cipher wrapped in Galois
+    /// Counter Mode, which generates tags with the given length.
+    ///
+    /// Tag sizes between 12 and 16 bytes are allowed.
+    ///
+    /// Only use this function if you require compatibility with an existing
+    /// cryptosystem that uses non-standard tag lengths. All other users should use
+    /// new, which is more resistant to misuse.
+    pub fn new_with_tag_size(
+        cipher: &'a B,
+        tag_size: usize,
+    ) -> Result<Self, errors::ErrorStaticString> {
+        Self::new_with_nonce_and_tag_size(cipher, GCM_STANDARD_NONCE_SIZE, tag_size)
+    }
+
+    fn new_with_nonce_and_tag_size(
+        cipher: &'a B,
+        nonce_size: usize,
+        tag_size: usize,
+    ) -> Result<Self, errors::ErrorStaticString> {
+        if !(GCM_MINIMUM_TAG_SIZE..=GCM_BLOCK_SIZE).contains(&tag_size) {
+            return Err(errors::new_static(
+                "cipher: incorrect tag size given to GCM",
+            ));
+        }
+
+        if nonce_size == 0 {
+            return Err(errors::new_static("cipher: the nonce can't have zero length, or the security of the key will be immediately compromised"));
+        }
+
+        // 	if cipher, ok := cipher.(gcmAble); ok {
+        // 		return cipher.NewGCM(nonceSize, tagSize)
+        // 	}
+
+        if cipher.block_size() != GCM_BLOCK_SIZE {
+            return Err(errors::new_static(
+                "cipher: NewGCM requires 128-bit block cipher",
+            ));
+        }
+
+        let mut key = [0; GCM_BLOCK_SIZE];
+        cipher.encrypt_inplace(&mut key);
+
+        let mut g = GCM {
+            cipher,
+            nonce_size,
+            tag_size,
+            product_table: [GCMFieldElement::default(); 16],
+            h: GCMFieldElement::default(),
+            use_clmul: gcm_arch::arch_available_ghash(),
+        };
+
+        // We precompute 16 multiples of |key|. However, when we do lookups
+        // into this table we'll be using bits from a field element and
+        // therefore the bits will be in the reverse order. So normally one
+        // would expect, say, 4*key to be in index 4 of the table but due to
+        // this bit ordering it will actually be in index 0010 (base 2) = 2.
+        let x = GCMFieldElement {
+            low: BIG_ENDIAN.uint64(&key[..8]),
+            high: BIG_ENDIAN.uint64(&key[8..]),
+        };
+        g.h = x;
+        g.product_table[reverse_bits(1)] = x;
+
+        for i in (2..16).step_by(2) {
+            g.product_table[reverse_bits(i)] = gcm_double(&g.product_table[reverse_bits(i / 2)]);
+            g.product_table[reverse_bits(i + 1)] = gcm_add(&g.product_table[reverse_bits(i)], &x);
+        }
+
+        Ok(g)
+    }
+
+    /// mul sets y to y*H, where H is the GCM key, fixed during new_with_nonce_and_tag_size.
+    fn mul(&self, y: &mut GCMFieldElement) {
+        if self.use_clmul {
+            // Safety: use_clmul is only set when arch_available_ghash is true.
+            unsafe { gcm_arch::arch_ghash_mul(y, &self.h) };
+        } else {
+            self.mul_generic(y);
+        }
+    }
+
+    /// mul_generic sets y to y*H using product_table.
+    pub(super) fn mul_generic(&self, y: &mut GCMFieldElement) {
+        let mut z = GCMFieldElement::default();
+
+        for i in 0..2 {
+            let mut word = if i == 1 { y.low } else { y.high };
+
+            // Multiplication works by multiplying z by 16 and adding in
+            // one of the precomputed multiples of H.
+            for _ in (0..64).step_by(4) {
+                let msw = z.high & 0xf;
+                z.high >>= 4;
+                z.high |= z.low << 60;
+                z.low >>= 4;
+                z.low ^= (GCM_REDUCTION_TABLE[msw as usize] as u64) << 48;
+
+                // the values in |table| are ordered for
+                // little-endian bit positions. See the comment
+                // in new_with_nonce_and_tag_size.
+                let t = &self.product_table[(word & 0xf) as usize];
+
+                z.low ^= t.low;
+                z.high ^= t.high;
+                word >>= 4;
+            }
+        }
+
+        *y = z;
+    }
+
+    /// update_blocks extends y with more polynomial terms from blocks, based on
+    /// Horner's rule. There must be a multiple of GCM_BLOCK_SIZE bytes in blocks.
+    fn update_blocks(&self, y: &mut GCMFieldElement, blocks: &[u8]) {
+        if self.use_clmul {
+            // Safety: use_clmul is only set when arch_available_ghash is true.
+            unsafe { gcm_arch::arch_ghash_update(y, &self.h, blocks) };
+            return;
+        }
+        for block in blocks.chunks_exact(GCM_BLOCK_SIZE) {
+            y.low ^= BIG_ENDIAN.uint64(block);
+            y.high ^= BIG_ENDIAN.uint64(&block[8..]);
+            self.mul_generic(y);
+        }
+    }
+
+    /// update extends y with more polynomial terms from data. If data is not a
+    /// multiple of GCM_BLOCK_SIZE bytes long then the remainder is zero padded.
+    fn update(&self, y: &mut GCMFieldElement, data: &[u8]) {
+        let full_blocks = (data.len() >> 4) << 4;
+        self.update_blocks(y, &data[..full_blocks]);
+
+        if data.len() != full_blocks {
+            let mut partial_block = [0; GCM_BLOCK_SIZE];
+            partial_block[..data.len() - full_blocks].copy_from_slice(&data[full_blocks..]);
+            self.update_blocks(y, &partial_block);
+        }
+    }
+
+    /// counter_crypt crypts input to out using self.cipher in counter mode.
+    fn counter_crypt(&self, out: &mut [u8], input: &[u8], counter: &mut [u8; GCM_BLOCK_SIZE]) {
+        let mut mask = [0; GCM_BLOCK_SIZE];
+
+        for (out, input) in out
+            .chunks_mut(GCM_BLOCK_SIZE)
+            .zip(input.chunks(GCM_BLOCK_SIZE))
+        {
+            self.cipher.encrypt(&mut mask, counter);
+            gcm_inc32(counter);
+
+            subtle::xor_bytes(out, input, &mask);
+        }
+    }
+
+    /// derive_counter computes the initial GCM counter state from the given nonce.
+    /// See NIST SP 800-38D, section 7.1. This assumes that counter is filled with
+    /// zeros on entry.
+    fn derive_counter(&self, counter: &mut [u8; GCM_BLOCK_SIZE], nonce: &[u8]) {
+        // GCM has two modes of operation with respect to the initial counter
+        // state: a "fast path" for 96-bit (12-byte) nonces, and a "slow path"
+        // for nonces of other lengths. For a 96-bit nonce, the nonce, along
+        // with a four-byte big-endian counter starting at one, is used
+        // directly as the starting counter. For other nonce sizes, the counter
+        // is computed by passing it through the GHASH function.
+        if nonce.len() == GCM_STANDARD_NONCE_SIZE {
+            counter[..GCM_STANDARD_NONCE_SIZE].copy_from_slice(nonce);
+            counter[GCM_BLOCK_SIZE - 1] = 1;
+        } else {
+            let mut y = GCMFieldElement::default();
+            self.update(&mut y, nonce);
+            y.high ^= (nonce.len() as u64) * 8;
+            self.mul(&mut y);
+            BIG_ENDIAN.put_uint64(&mut counter[..8], y.low);
+            BIG_ENDIAN.put_uint64(&mut counter[8..], y.high);
+        }
+    }
+
+    /// auth calculates GHASH(ciphertext, additional_data), masks the result with
+    /// tag_mask and writes the result to out.
+    fn auth(
+        &self,
+        out: &mut [u8; GCM_TAG_SIZE],
+        ciphertext: &[u8],
+        additional_data: &[u8],
+        tag_mask: &[u8; GCM_TAG_SIZE],
+    ) {
+        let mut y = GCMFieldElement::default();
+        self.update(&mut y, additional_data);
+        self.update(&mut y, ciphertext);
+
+        y.low ^= (additional_data.len() as u64) * 8;
+        y.high ^= (ciphertext.len() as u64) * 8;
+
+        self.mul(&mut y);
+
+        BIG_ENDIAN.put_uint64(&mut out[..8], y.low);
+        BIG_ENDIAN.put_uint64(&mut out[8..], y.high);
+
+        subtle::xor_bytes_inplace(out, tag_mask);
+    }
+}
+
+impl<'a, B: Block> AEAD for GCM<'a, B> {
+    fn nonce_size(&self) -> usize {
+        self.nonce_size
+    }
+
+    fn overhead(&self) -> usize {
+        self.tag_size
+    }
+
+    fn seal(&self, dst: &mut Vec<u8>, nonce: &[u8], plaintext: &[u8], additional_data: &[u8]) {
+        if nonce.len() != self.nonce_size {
+            panic!("crypto/cipher: incorrect nonce length given to GCM");
+        }
+        if plaintext.len() as u64 > ((1 << 32) - 2) * (self.cipher.block_size() as u64) {
+            panic!("crypto/cipher: message too large for GCM");
+        }
+
+        let start = dst.len();
+        dst.resize(start + plaintext.len() + self.tag_size, 0);
+        let out = &mut dst[start..];
+
+        let mut counter = [0; GCM_BLOCK_SIZE];
+        let mut tag_mask = [0; GCM_BLOCK_SIZE];
+        self.derive_counter(&mut counter, nonce);
+
+        self.cipher.encrypt(&mut tag_mask, &counter);
+        gcm_inc32(&mut counter);
+
+        self.counter_crypt(&mut out[..plaintext.len()], plaintext, &mut counter);
+
+        let mut tag = [0; GCM_TAG_SIZE];
+        self.auth(
+            &mut tag,
+            &out[..plaintext.len()],
+            additional_data,
+            &tag_mask,
+        );
+        out[plaintext.len()..].copy_from_slice(&tag[..self.tag_size]);
+    }
+
+    fn open(
+        &self,
+        dst: &mut Vec<u8>,
+        nonce: &[u8],
+        ciphertext: &[u8],
+        additional_data: &[u8],
+    ) -> Result<(), errors::ErrorStaticString> {
+        if nonce.len() != self.nonce_size {
+            panic!("crypto/cipher: incorrect nonce length given to GCM");
+        }
+        // Sanity check to prevent the authentication from always succeeding if an implementation
+        // leaves tag_size uninitialized, for example.
+        if self.tag_size < GCM_MINIMUM_TAG_SIZE {
+            panic!("crypto/cipher: incorrect GCM tag size");
+        }
+
+        if ciphertext.len() < self.tag_size {
+            return Err(ERR_OPEN);
+        }
+        if ciphertext.len() as u64
+            > ((1 << 32) - 2) * (self.cipher.block_size() as u64) + (self.tag_size as u64)
+        {
+            return Err(ERR_OPEN);
+        }
+
+        let (ciphertext, tag) = ciphertext.split_at(ciphertext.len() - self.tag_size);
+
+        let mut counter = [0; GCM_BLOCK_SIZE];
+        let mut tag_mask = [0; GCM_BLOCK_SIZE];
+        self.derive_counter(&mut counter, nonce);
+
+        self.cipher.encrypt(&mut tag_mask, &counter);
+        gcm_inc32(&mut counter);
+
+        let mut expected_tag = [0; GCM_TAG_SIZE];
+        self.auth(&mut expected_tag, ciphertext, additional_data, &tag_mask);
+
+        if subtle::constant_time_compare(&expected_tag[..self.tag_size], tag) != 1 {
+            // rust ggstd: unlike Go, which overwrites dst up to its capacity,
+            // nothing is appended to dst when authentication fails.
+            return Err(ERR_OPEN);
+        }
+
+        let start = dst.len();
+        dst.resize(start + ciphertext.len(), 0);
+        self.counter_crypt(&mut dst[start..], ciphertext, &mut counter);
+
+        Ok(())
+    }
+}
+
+/// reverse_bits reverses the order of the bits of 4-bit number in i.
+fn reverse_bits(i: usize) -> usize {
+    let i = ((i << 2) & 0xc) | ((i >> 2) & 0x3);
+    ((i << 1) & 0xa) | ((i >> 1) & 0x5)
+}
+
+/// gcm_add adds two elements of GF(2¹²⁸) and returns the sum.
+fn gcm_add(x: &GCMFieldElement, y: &GCMFieldElement) -> GCMFieldElement {
+    // Addition in a characteristic 2 field is just XOR.
+    GCMFieldElement {
+        low: x.low ^ y.low,
+        high: x.high ^ y.high,
+    }
+}
+
+/// gcm_double returns the result of doubling an element of GF(2¹²⁸).
+fn gcm_double(x: &GCMFieldElement) -> GCMFieldElement {
+    let msb_set = x.high & 1 == 1;
+
+    // Because of the bit-ordering, doubling is actually a right shift.
+    let mut double = GCMFieldElement {
+        high: (x.high >> 1) | (x.low << 63),
+        low: x.low >> 1,
+    };
+
+    // If the most-significant bit was set before shifting then it,
+    // conceptually, becomes a term of x^128. This is greater than the
+    // irreducible polynomial so the result has to be reduced. The
+    // irreducible polynomial is 1+x+x^2+x^7+x^128. We can subtract that to
+    // eliminate the term at x^128 which also means subtracting the other
+    // four terms. In characteristic 2 fields, subtraction == addition ==
+    // XOR.
+    if msb_set {
+        double.low ^= 0xe100000000000000;
+    }
+
+    double
+}
+
+const GCM_REDUCTION_TABLE: [u16; 16] = [
+    0x0000, 0x1c20, 0x3840, 0x2460, 0x7080, 0x6ca0, 0x48c0, 0x54e0, 0xe100, 0xfd20, 0xd940, 0xc560,
+    0x9180, 0x8da0, 0xa9c0, 0xb5e0,
+];
+
+/// gcm_inc32 treats the final four bytes of counter_block as a big-endian value
+/// and increments it.
+fn gcm_inc32(counter_block: &mut [u8; GCM_BLOCK_SIZE]) {
+    let ctr = &mut counter_block[GCM_BLOCK_SIZE - 4..];
+    BIG_ENDIAN.put_uint32(ctr, BIG_ENDIAN.uint32(ctr).wrapping_add(1));
+}
@@ -0,0 +1,86 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2015 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// GHASH using the carry-less multiplication instruction of x86_64 processors.
+// Unlike the table-driven multiplication in gcm.rs, it doesn't look up
+// memory with secret-dependent indexes, so it runs in constant time.
+
+use super::gcm::{GCMFieldElement, GCM_BLOCK_SIZE};
+use crate::encoding::binary::{ByteOrder, BIG_ENDIAN};
+use std::arch::x86_64::*;
+
+pub fn arch_available_ghash() -> bool {
+    is_x86_feature_detected!("pclmulqdq") && is_x86_feature_detected!("sse2")
+}
+
+/// arch_ghash_update extends y with more polynomial terms from blocks,
+/// using the key h. There must be a multiple of GCM_BLOCK_SIZE bytes in blocks.
+///
+/// # Safety
+///
+/// The CPU must support the PCLMULQDQ and SSE2 instructions, see arch_available_ghash.
+#[target_feature(enable = "pclmulqdq,sse2")]
+pub unsafe fn arch_ghash_update(y: &mut GCMFieldElement, h: &GCMFieldElement, blocks: &[u8]) {
+    for block in blocks.chunks_exact(GCM_BLOCK_SIZE) {
+        y.low ^= BIG_ENDIAN.uint64(block);
+        y.high ^= BIG_ENDIAN.uint64(&block[8..]);
+        *y = gcm_mul_clmul(y, h);
+    }
+}
+
+/// arch_ghash_mul sets y to y*h.
+///
+/// # Safety
+///
+/// The CPU must support the PCLMULQDQ and SSE2 instructions, see arch_available_ghash.
+#[target_feature(enable = "pclmulqdq,sse2")]
+pub(crate) unsafe fn arch_ghash_mul(y: &mut GCMFieldElement, h: &GCMFieldElement) {
+    *y = gcm_mul_clmul(y, h);
+}
+
+/// gcm_mul_clmul returns x*y in GF(2¹²⁸).
+///
+/// The field elements use the bit-reflected GCM representation: read as a
+/// big-endian 128-bit integer, the coefficient of xⁱ is bit 127-i. The
+/// carry-less product of two reflected values is the reflected 255-bit
+/// product, so it is shifted left by one bit and then reduced modulo
+/// x¹²⁸ + x⁷ + x² + x + 1.
+#[target_feature(enable = "pclmulqdq,sse2")]
+unsafe fn gcm_mul_clmul(x: &GCMFieldElement, y: &GCMFieldElement) -> GCMFieldElement {
+    let a = _mm_set_epi64x(x.low as i64, x.high as i64);
+    let b = _mm_set_epi64x(y.low as i64, y.high as i64);
+
+    let lo = _mm_clmulepi64_si128(a, b, 0x00);
+    let hi = _mm_clmulepi64_si128(a, b, 0x11);
+    let mid = _mm_xor_si128(
+        _mm_clmulepi64_si128(a, b, 0x01),
+        _mm_clmulepi64_si128(a, b, 0x10),
+    );
+    let lo = to_u128(_mm_xor_si128(lo, _mm_slli_si128(mid, 8)));
+    let hi = to_u128(_mm_xor_si128(hi, _mm_srli_si128(mid, 8)));
+
+    // After the shift, h holds the coefficients of x⁰ to x¹²⁷
+    // and l those of x¹²⁸ to x²⁵⁵.
+    let h = (hi << 1) | (lo >> 127);
+    let l = lo << 1;
+
+    // Each term xⁱ⁺¹²⁸ of l is replaced by xⁱ⁺⁷ + xⁱ⁺² + xⁱ⁺¹ + xⁱ.
+    // The terms of degree 128 and above that this produces are
+    // collected in f and reduced once more.
+    let f = (l << 127) ^ (l << 126) ^ (l << 121);
+    let r = h ^ l ^ (l >> 1) ^ (l >> 2) ^ (l >> 7) ^ f ^ (f >> 1) ^ (f >> 2) ^ (f >> 7);
+
+    GCMFieldElement {
+        low: (r >> 64) as u64,
+        high: r as u64,
+    }
+}
+
+#[target_feature(enable = "sse2")]
+unsafe fn to_u128(v: __m128i) -> u128 {
+    let mut w = [0u64; 2];
+    _mm_storeu_si128(w.as_mut_ptr() as *mut __m128i, v);
+    ((w[1] as u128) << 64) | (w[0] as u128)
+}
@@ -0,0 +1,24 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2015 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::gcm::GCMFieldElement;
+
+pub fn arch_available_ghash() -> bool {
+    false
+}
+
+/// # Safety
+///
+/// Never available on this architecture, see arch_available_ghash.
+pub unsafe fn arch_ghash_update(_y: &mut GCMFieldElement, _h: &GCMFieldElement, _blocks: &[u8]) {
+    panic!("not implemented")
+}
+
+/// # Safety
+///
+/// Never available on this architecture, see arch_available_ghash.
+pub(crate) unsafe fn arch_ghash_mul(_y: &mut GCMFieldElement, _h: &GCMFieldElement) {
+    panic!("not implemented")
+}
@@ -1,53 +1,71 @@
-// // Copyright 2010 The Go Authors. All rights reserved.
-// // Use of this source code is governed by a BSD-style
-// // license that can be found in the LICENSE file.
-
-// package cipher
-
-// import "io"
-
-// // The Stream* objects are so simple that all their members are public. Users
-// // can create them themselves.
-
-// // StreamReader wraps a Stream into an io.Reader. It calls xor_key_stream
-// // to process each slice of data which passes through.
-// type StreamReader struct {
-// 	S Stream
-// 	R io.Reader
-// }
-
-// func (r StreamReader) Read(dst []byte) (n int, err error) {
-// 	n, err = r.R.Read(dst)
-// 	r.S.xor_key_stream(dst[..n], dst[..n])
-// 	return
-// }
-
-// // StreamWriter wraps a Stream into an io.Writer. It calls xor_key_stream
-// // to process each slice of data which passes through. If any Write call
-// // returns short then the StreamWriter is out of sync and must be discarded.
-// // A StreamWriter has no internal buffering; Close does not need
-// // to be called to flush write data.
-// type StreamWriter struct {
-// 	S   Stream
-// 	W   io.Writer
-// 	Err error // unused
-// }
-
-// func (w StreamWriter) Write(src []byte) (n int, err error) {
-// 	c := make([]byte, src.len())
-// 	w.S.xor_key_stream(c, src)
-// 	n, err = w.W.Write(c)
-// 	if n != src.len() && err == nil { // should never happen
-// 		err = io.ErrShortWrite
-// 	}
-// 	return
-// }
-
-// // Close closes the underlying Writer and returns its Close return value, if the Writer
-// // is also an io.Closer. Otherwise it returns nil.
-// func (w StreamWriter) Close() error {
-// 	if c, ok := w.W.(io.Closer); ok {
-// 		return c.Close()
-// 	}
-// 	return nil
-// }
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2010 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use crate::crypto::cipher::Stream;
+
+// The Stream* objects are so simple that all their members are public. Users
+// can create them themselves.
+
+/// StreamReader wraps a Stream into an std::io::Read. It calls xor_key_stream
+/// to process each slice of data which passes through.
+pub struct StreamReader<'a, R: std::io::Read> {
+    pub s: Box<dyn Stream + 'a>,
+    pub r: R,
+}
+
+impl<'a, R: std::io::Read> StreamReader<'a, R> {
+    /// new creates a StreamReader that decrypts or encrypts the data read from r
+    /// using the stream s.
+    pub fn new(s: Box<dyn Stream + 'a>, r: R) -> Self {
+        Self { s, r }
+    }
+}
+
+impl<R: std::io::Read> std::io::Read for StreamReader<'_, R> {
+    fn read(&mut self, dst: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.r.read(dst)?;
+        self.s.xor_key_stream_inplace(&mut dst[..n]);
+        Ok(n)
+    }
+}
+
+/// StreamWriter wraps a Stream into an std::io::Write. It calls xor_key_stream
+/// to process each slice of data which passes through. If any write call
+/// returns an error then the StreamWriter is out of sync and must be discarded.
+/// A StreamWriter has no internal buffering; flush does not need
+/// to be called to flush write data.
+pub struct StreamWriter<'a, W: std::io::Write> {
+    pub s: Box<dyn Stream + 'a>,
+    pub w: W,
+    buf: Vec<u8>,
+}
+
+impl<'a, W: std::io::Write> StreamWriter<'a, W> {
+    /// new creates a StreamWriter that decrypts or encrypts the data
+    /// using the stream s before writing it to w.
+    pub fn new(s: Box<dyn Stream + 'a>, w: W) -> Self {
+        Self {
+            s,
+            w,
+            buf: Vec::new(),
+        }
+    }
+}
+
+impl<W: std::io::Write> std::io::Write for StreamWriter<'_, W> {
+    fn write(&mut self, src: &[u8]) -> std::io::Result<usize> {
+        self.buf.resize(src.len(), 0);
+        self.s.xor_key_stream(&mut self.buf, src);
+        // rust ggstd: the whole buffer must be written, otherwise
+        // the key stream would be out of sync with the output.
+        self.w.write_all(&self.buf)?;
+        Ok(src.len())
+    }
+
+    /// flush flushes the underlying writer.
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.w.flush()
+    }
+}
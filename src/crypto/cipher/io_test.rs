@@ -0,0 +1,74 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use super::common_test::{COMMON_INPUT, COMMON_KEY128};
+use crate::compress::gzip;
+use crate::crypto::aes;
+use crate::crypto::cipher::{self, Stream};
+use std::io::{Read, Write};
+
+const COUNTER: &[u8] = &[
+    0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe, 0xff,
+];
+
+#[test]
+fn test_stream_writer() {
+    let block = aes::Cipher::new(COMMON_KEY128).unwrap();
+    let mut want = vec![0; COMMON_INPUT.len()];
+    cipher::CTR::new(&block, COUNTER).xor_key_stream(&mut want, COMMON_INPUT);
+
+    let mut w = cipher::StreamWriter::new(Box::new(cipher::CTR::new(&block, COUNTER)), Vec::new());
+    // writing in uneven pieces must not affect the result
+    for chunk in COMMON_INPUT.chunks(7) {
+        assert_eq!(w.write(chunk).unwrap(), chunk.len());
+    }
+    w.flush().unwrap();
+    assert_eq!(w.w, want);
+}
+
+#[test]
+fn test_stream_reader() {
+    let block = aes::Cipher::new(COMMON_KEY128).unwrap();
+    let mut encrypted = vec![0; COMMON_INPUT.len()];
+    cipher::CTR::new(&block, COUNTER).xor_key_stream(&mut encrypted, COMMON_INPUT);
+
+    let mut r = cipher::StreamReader::new(
+        Box::new(cipher::CTR::new(&block, COUNTER)),
+        encrypted.as_slice(),
+    );
+    let mut decrypted = Vec::new();
+    let mut buf = [0; 5];
+    loop {
+        let n = r.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        decrypted.extend_from_slice(&buf[..n]);
+    }
+    assert_eq!(decrypted, COMMON_INPUT);
+}
+
+#[test]
+fn test_stream_reader_gzip() {
+    // "hello world\n" compressed with gzip
+    let gz: &[u8] = &[
+        0x1f, 0x8b, 0x08, 0x08, 0xc8, 0x58, 0x13, 0x4a, 0x00, 0x03, 0x68, 0x65, 0x6c, 0x6c, 0x6f,
+        0x2e, 0x74, 0x78, 0x74, 0x00, 0xcb, 0x48, 0xcd, 0xc9, 0xc9, 0x57, 0x28, 0xcf, 0x2f, 0xca,
+        0x49, 0xe1, 0x02, 0x00, 0x2d, 0x3b, 0x08, 0xaf, 0x0c, 0x00, 0x00, 0x00,
+    ];
+    let block = aes::Cipher::new(COMMON_KEY128).unwrap();
+
+    let mut w = cipher::StreamWriter::new(Box::new(cipher::CTR::new(&block, COUNTER)), Vec::new());
+    w.write_all(gz).unwrap();
+    let encrypted = w.w;
+
+    let r = cipher::StreamReader::new(
+        Box::new(cipher::CTR::new(&block, COUNTER)),
+        encrypted.as_slice(),
+    );
+    let mut br = std::io::BufReader::new(r);
+    let mut z = gzip::Reader::new(&mut br).unwrap();
+    let mut got = String::new();
+    z.read_to_string(&mut got).unwrap();
+    assert_eq!(got, "hello world\n");
+}
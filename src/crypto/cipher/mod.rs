@@ -9,16 +9,35 @@
 //! and NIST Special Publication 800-38A.
 
 mod cbc;
+mod cfb;
 mod cipher;
 mod ctr;
+mod gcm;
+#[cfg(target_arch = "x86_64")]
+mod gcm_amd64;
+#[cfg(not(target_arch = "x86_64"))]
+mod gcm_otherarch;
+mod io;
+mod ofb;
+
+#[cfg(target_arch = "x86_64")]
+use gcm_amd64 as gcm_arch;
+#[cfg(not(target_arch = "x86_64"))]
+use gcm_otherarch as gcm_arch;
 
 pub use cbc::{CBCDecrypter, CBCEncrypter};
+pub use cfb::{CFBDecrypter, CFBEncrypter};
 pub use cipher::{Block, BlockMode, Stream};
 pub use ctr::CTR;
+pub use gcm::{AEAD, GCM};
+pub use io::{StreamReader, StreamWriter};
+pub use ofb::OFB;
 
 #[cfg(test)]
 mod cbc_aes_test;
 #[cfg(test)]
+mod cfb_test;
+#[cfg(test)]
 mod cipher_test;
 #[cfg(test)]
 mod common_test;
@@ -26,3 +45,9 @@ mod common_test;
 mod ctr_aes_test;
 #[cfg(test)]
 mod ctr_test;
+#[cfg(test)]
+mod gcm_test;
+#[cfg(test)]
+mod io_test;
+#[cfg(test)]
+mod ofb_test;
@@ -1,77 +1,88 @@
-// // Copyright 2011 The Go Authors. All rights reserved.
-// // Use of this source code is governed by a BSD-style
-// // license that can be found in the LICENSE file.
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
 
-// // OFB (Output Feedback) Mode.
+//! OFB (Output Feedback) Mode.
 
-// package cipher
+use super::ctr::STREAM_BUFFER_SIZE;
+use crate::compat;
+use crate::crypto::cipher::{Block, Stream};
+use crate::crypto::subtle;
 
-// import (
-// 	"crypto/internal/alias"
-// 	"crypto/subtle"
-// )
+/// OFB is a Stream that encrypts or decrypts using the block cipher
+/// in output feedback mode.
+pub struct OFB<'a, B: Block> {
+    b: &'a B,
+    cipher: Vec<u8>,
+    out: Vec<u8>,
+    out_size: usize,
+    out_used: usize,
+}
 
-// type ofb struct {
-// 	b       Block
-// 	cipher  []byte
-// 	out     []byte
-// 	outUsed int
-// }
+impl<'a, B: Block> OFB<'a, B> {
+    /// new returns a Stream that encrypts or decrypts using the block cipher b
+    /// in output feedback mode. The initialization vector iv's length must be equal
+    /// to b's block size.
+    pub fn new(b: &'a B, iv: &[u8]) -> Self {
+        let block_size = b.block_size();
+        if iv.len() != block_size {
+            panic!("cipher.NewOFB: IV length must equal block size");
+        }
+        let buf_size = STREAM_BUFFER_SIZE.max(block_size);
+        Self {
+            b,
+            cipher: iv.to_vec(),
+            out: vec![0; buf_size],
+            out_size: 0,
+            out_used: 0,
+        }
+    }
 
-// // NewOFB returns a Stream that encrypts or decrypts using the block cipher b
-// // in output feedback mode. The initialization vector iv's length must be equal
-// // to b's block size.
-// func NewOFB(b Block, iv []byte) Stream {
-// 	blockSize := b.block_size()
-// 	if len(iv) != blockSize {
-// 		panic("cipher.NewOFB: IV length must equal block size")
-// 	}
-// 	bufSize := streamBufferSize
-// 	if bufSize < blockSize {
-// 		bufSize = blockSize
-// 	}
-// 	x := &ofb{
-// 		b:       b,
-// 		cipher:  make([]byte, blockSize),
-// 		out:     make([]byte, 0, bufSize),
-// 		outUsed: 0,
-// 	}
+    fn refill(&mut self) {
+        let bs = self.b.block_size();
+        let mut remain = self.out_size - self.out_used;
+        if remain > self.out_used {
+            return;
+        }
+        compat::copy_within(&mut self.out, self.out_used..self.out_size, 0);
+        while remain < self.out.len() - bs {
+            self.b.encrypt_inplace(&mut self.cipher);
+            compat::copy(&mut self.out[remain..], &self.cipher);
+            remain += bs;
+        }
+        self.out_size = remain;
+        self.out_used = 0;
+    }
+}
 
-// 	copy(x.cipher, iv)
-// 	return x
-// }
+impl<'a, B: Block> Stream for OFB<'a, B> {
+    fn xor_key_stream(&mut self, dst: &mut [u8], src: &[u8]) {
+        if dst.len() < src.len() {
+            panic!("crypto/cipher: output smaller than input");
+        }
+        let mut dst = dst;
+        let mut src = src;
+        while !src.is_empty() {
+            if self.out_used + self.b.block_size() >= self.out_size {
+                self.refill();
+            }
+            let n = subtle::xor_bytes(dst, src, &self.out[self.out_used..self.out_size]);
+            dst = &mut dst[n..];
+            src = &src[n..];
+            self.out_used += n;
+        }
+    }
 
-// func (x *ofb) refill() {
-// 	bs := x.b.block_size()
-// 	remain := len(x.out) - x.outUsed
-// 	if remain > x.outUsed {
-// 		return
-// 	}
-// 	copy(x.out, x.out[x.outUsed:])
-// 	x.out = x.out[:cap(x.out)]
-// 	for remain < len(x.out)-bs {
-// 		x.b.encrypt(x.cipher, x.cipher)
-// 		copy(x.out[remain:], x.cipher)
-// 		remain += bs
-// 	}
-// 	x.out = x.out[:remain]
-// 	x.outUsed = 0
-// }
-
-// func (x *ofb) xor_key_stream(dst, src []byte) {
-// 	if dst.len() < src.len() {
-// 		panic("crypto/cipher: output smaller than input")
-// 	}
-// 	if alias.InexactOverlap(dst[..src.len()], src) {
-// 		panic("crypto/cipher: invalid buffer overlap")
-// 	}
-// 	for src.len() > 0 {
-// 		if x.outUsed >= len(x.out)-x.b.block_size() {
-// 			x.refill()
-// 		}
-// 		n := subtle.XORBytes(dst, src, x.out[x.outUsed:])
-// 		dst = dst[n:]
-// 		src = src[n:]
-// 		x.outUsed += n
-// 	}
-// }
+    fn xor_key_stream_inplace(&mut self, data: &mut [u8]) {
+        let mut data = data;
+        while !data.is_empty() {
+            if self.out_used + self.b.block_size() >= self.out_size {
+                self.refill();
+            }
+            let n = subtle::xor_bytes_inplace(data, &self.out[self.out_used..self.out_size]);
+            data = &mut data[n..];
+            self.out_used += n;
+        }
+    }
+}
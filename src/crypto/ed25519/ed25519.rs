@@ -0,0 +1,300 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2016 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use crate::crypto::internal::edwards25519::{Point, Scalar};
+use crate::crypto::sha512;
+use crate::crypto::subtle;
+use crate::errors;
+use crate::hash::Hash;
+
+/// PUBLIC_KEY_SIZE is the size, in bytes, of public keys as used in this package.
+pub const PUBLIC_KEY_SIZE: usize = 32;
+/// PRIVATE_KEY_SIZE is the size, in bytes, of private keys as used in this package.
+pub const PRIVATE_KEY_SIZE: usize = 64;
+/// SIGNATURE_SIZE is the size, in bytes, of signatures generated and verified by this package.
+pub const SIGNATURE_SIZE: usize = 64;
+/// SEED_SIZE is the size, in bytes, of private key seeds. These are the private key representations used by RFC 8032.
+pub const SEED_SIZE: usize = 32;
+
+/// PublicKey is the type of Ed25519 public keys.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublicKey(pub [u8; PUBLIC_KEY_SIZE]);
+
+impl PublicKey {
+    /// equal reports whether self and x have the same value.
+    pub fn equal(&self, x: &PublicKey) -> bool {
+        subtle::constant_time_compare(&self.0, &x.0) == 1
+    }
+}
+
+/// PrivateKey is the type of Ed25519 private keys.
+///
+/// It is the 32-byte seed followed by the 32-byte public key.
+#[derive(Clone)]
+pub struct PrivateKey(pub [u8; PRIVATE_KEY_SIZE]);
+
+impl PrivateKey {
+    /// public returns the PublicKey corresponding to self.
+    pub fn public(&self) -> PublicKey {
+        let mut public_key = [0; PUBLIC_KEY_SIZE];
+        public_key.copy_from_slice(&self.0[SEED_SIZE..]);
+        PublicKey(public_key)
+    }
+
+    /// equal reports whether self and x have the same value.
+    pub fn equal(&self, x: &PrivateKey) -> bool {
+        subtle::constant_time_compare(&self.0, &x.0) == 1
+    }
+
+    /// seed returns the private key seed corresponding to self. It is provided for
+    /// interoperability with RFC 8032. RFC 8032's private keys correspond to seeds
+    /// in this package.
+    pub fn seed(&self) -> [u8; SEED_SIZE] {
+        let mut seed = [0; SEED_SIZE];
+        seed.copy_from_slice(&self.0[..SEED_SIZE]);
+        seed
+    }
+
+    /// sign signs the given message with self.
+    ///
+    /// If opts.prehashed is true, the message is expected to be a SHA-512 hash
+    /// and the Ed25519ph variant is used. Otherwise, if opts.context is not
+    /// empty, the Ed25519ctx variant is used, and if both are unset, the
+    /// message is signed with plain Ed25519, as by the sign function.
+    ///
+    /// An error is returned if the message hash or the context have a bad length.
+    pub fn sign(
+        &self,
+        message: &[u8],
+        opts: &Options,
+    ) -> Result<[u8; SIGNATURE_SIZE], errors::ErrorString> {
+        let context = opts.context;
+        if opts.prehashed {
+            // Ed25519ph
+            if message.len() != sha512::SIZE {
+                return Err(errors::new_str(&format!(
+                    "ed25519: bad Ed25519ph message hash length: {}",
+                    message.len()
+                )));
+            }
+            if context.len() > 255 {
+                return Err(errors::new_str(&format!(
+                    "ed25519: bad Ed25519ph context length: {}",
+                    context.len()
+                )));
+            }
+            Ok(sign_internal(self, message, DOM_PREFIX_PH, context))
+        } else if !context.is_empty() {
+            // Ed25519ctx
+            if context.len() > 255 {
+                return Err(errors::new_str(&format!(
+                    "ed25519: bad Ed25519ctx context length: {}",
+                    context.len()
+                )));
+            }
+            Ok(sign_internal(self, message, DOM_PREFIX_CTX, context))
+        } else {
+            // Ed25519
+            Ok(sign(self, message))
+        }
+    }
+}
+
+/// Options can be used with PrivateKey::sign or verify_with_options
+/// to select Ed25519 variants.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Options<'a> {
+    /// prehashed selects Ed25519ph, in which case the message must be
+    /// a SHA-512 hash of the data. If false, the message is not hashed.
+    pub prehashed: bool,
+
+    /// context, if not empty, selects Ed25519ctx or provides the context
+    /// string for Ed25519ph. It can be at most 255 bytes in length.
+    pub context: &'a [u8],
+}
+
+/// generate_key generates a public/private key pair using entropy from rand.
+/// Most callers should pass crate::crypto::rand::Reader.
+///
+/// The output of this function is deterministic, and equivalent to reading
+/// SEED_SIZE bytes from rand, and passing them to new_key_from_seed.
+pub fn generate_key(rand: &mut dyn std::io::Read) -> std::io::Result<(PublicKey, PrivateKey)> {
+    let mut seed = [0; SEED_SIZE];
+    rand.read_exact(&mut seed)?;
+
+    let private_key = new_key_from_seed(&seed);
+    Ok((private_key.public(), private_key))
+}
+
+/// new_key_from_seed calculates a private key from a seed. It will panic if
+/// seed.len() is not SEED_SIZE. This function is provided for interoperability
+/// with RFC 8032. RFC 8032's private keys correspond to seeds in this
+/// package.
+pub fn new_key_from_seed(seed: &[u8]) -> PrivateKey {
+    if seed.len() != SEED_SIZE {
+        panic!("ed25519: bad seed length: {}", seed.len());
+    }
+
+    let h = sha512::sum512(seed);
+    let s = Scalar::set_bytes_with_clamping(&h[..32]).unwrap();
+    let a = Point::scalar_base_mult(&s);
+
+    let mut private_key = [0; PRIVATE_KEY_SIZE];
+    private_key[..SEED_SIZE].copy_from_slice(seed);
+    private_key[SEED_SIZE..].copy_from_slice(&a.bytes());
+    PrivateKey(private_key)
+}
+
+/// sign signs the message with private_key and returns a signature.
+pub fn sign(private_key: &PrivateKey, message: &[u8]) -> [u8; SIGNATURE_SIZE] {
+    sign_internal(private_key, message, DOM_PREFIX_PURE, b"")
+}
+
+// Domain separation prefixes used to disambiguate Ed25519/Ed25519ph/Ed25519ctx.
+// See RFC 8032, Section 2 and Section 5.1.
+const DOM_PREFIX_PURE: &[u8] = b"";
+const DOM_PREFIX_PH: &[u8] = b"SigEd25519 no Ed25519 collisions\x01";
+const DOM_PREFIX_CTX: &[u8] = b"SigEd25519 no Ed25519 collisions\x00";
+
+/// write_dom writes the dom2(phflag, context) prefix to h, unless dom_prefix
+/// selects plain Ed25519.
+fn write_dom(h: &mut sha512::Digest, dom_prefix: &[u8], context: &[u8]) {
+    if dom_prefix != DOM_PREFIX_PURE {
+        h.write(dom_prefix);
+        h.write(&[context.len() as u8]);
+        h.write(context);
+    }
+}
+
+fn sign_internal(
+    private_key: &PrivateKey,
+    message: &[u8],
+    dom_prefix: &[u8],
+    context: &[u8],
+) -> [u8; SIGNATURE_SIZE] {
+    let (seed, public_key) = private_key.0.split_at(SEED_SIZE);
+
+    let h = sha512::sum512(seed);
+    let s = Scalar::set_bytes_with_clamping(&h[..32]).unwrap();
+    let prefix = &h[32..];
+
+    let mut mh = sha512::Digest::new();
+    write_dom(&mut mh, dom_prefix, context);
+    mh.write(prefix);
+    mh.write(message);
+    let message_digest = mh.sum(&[]);
+    let r = Scalar::set_uniform_bytes(&message_digest).unwrap();
+
+    let big_r = Point::scalar_base_mult(&r).bytes();
+
+    let mut kh = sha512::Digest::new();
+    write_dom(&mut kh, dom_prefix, context);
+    kh.write(&big_r);
+    kh.write(public_key);
+    kh.write(message);
+    let hram_digest = kh.sum(&[]);
+    let k = Scalar::set_uniform_bytes(&hram_digest).unwrap();
+
+    let big_s = k.multiply_add(&s, &r);
+
+    let mut signature = [0; SIGNATURE_SIZE];
+    signature[..32].copy_from_slice(&big_r);
+    signature[32..].copy_from_slice(&big_s.bytes());
+    signature
+}
+
+/// verify reports whether sig is a valid signature of message by public_key.
+///
+/// The inputs are not considered confidential, and may leak through timing side
+/// channels.
+pub fn verify(public_key: &PublicKey, message: &[u8], sig: &[u8]) -> bool {
+    verify_internal(public_key, message, sig, DOM_PREFIX_PURE, b"")
+}
+
+/// verify_with_options reports whether sig is a valid signature of message by
+/// public_key. A valid signature is indicated by returning Ok(()).
+///
+/// If opts.prehashed is true, the message is expected to be a SHA-512 hash
+/// and the Ed25519ph variant is used. Otherwise, if opts.context is not empty,
+/// the Ed25519ctx variant is used, and if both are unset, plain Ed25519 is used.
+///
+/// The inputs are not considered confidential, and may leak through timing side
+/// channels.
+pub fn verify_with_options(
+    public_key: &PublicKey,
+    message: &[u8],
+    sig: &[u8],
+    opts: &Options,
+) -> Result<(), errors::ErrorString> {
+    let context = opts.context;
+    let ok = if opts.prehashed {
+        // Ed25519ph
+        if message.len() != sha512::SIZE {
+            return Err(errors::new_str(&format!(
+                "ed25519: bad Ed25519ph message hash length: {}",
+                message.len()
+            )));
+        }
+        if context.len() > 255 {
+            return Err(errors::new_str(&format!(
+                "ed25519: bad Ed25519ph context length: {}",
+                context.len()
+            )));
+        }
+        verify_internal(public_key, message, sig, DOM_PREFIX_PH, context)
+    } else if !context.is_empty() {
+        // Ed25519ctx
+        if context.len() > 255 {
+            return Err(errors::new_str(&format!(
+                "ed25519: bad Ed25519ctx context length: {}",
+                context.len()
+            )));
+        }
+        verify_internal(public_key, message, sig, DOM_PREFIX_CTX, context)
+    } else {
+        // Ed25519
+        verify(public_key, message, sig)
+    };
+    if !ok {
+        return Err(errors::new_str("ed25519: invalid signature"));
+    }
+    Ok(())
+}
+
+fn verify_internal(
+    public_key: &PublicKey,
+    message: &[u8],
+    sig: &[u8],
+    dom_prefix: &[u8],
+    context: &[u8],
+) -> bool {
+    if sig.len() != SIGNATURE_SIZE || sig[63] & 224 != 0 {
+        return false;
+    }
+
+    let a = match Point::set_bytes(&public_key.0) {
+        Ok(a) => a,
+        Err(_) => return false,
+    };
+
+    let mut kh = sha512::Digest::new();
+    write_dom(&mut kh, dom_prefix, context);
+    kh.write(&sig[..32]);
+    kh.write(&public_key.0);
+    kh.write(message);
+    let hram_digest = kh.sum(&[]);
+    let k = Scalar::set_uniform_bytes(&hram_digest).unwrap();
+
+    let s = match Scalar::set_canonical_bytes(&sig[32..]) {
+        Ok(s) => s,
+        Err(_) => return false,
+    };
+
+    // [S]B = R + [k]A --> [k](-A) + [S]B = R
+    let minus_a = a.negate();
+    let r = Point::var_time_double_scalar_base_mult(&k, &minus_a, &s);
+
+    sig[..32] == r.bytes()
+}
@@ -0,0 +1,320 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2016 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::*;
+use crate::compress::gzip;
+use crate::crypto::rand;
+use crate::crypto::sha512;
+use crate::encoding::hex;
+use std::io::BufRead;
+
+fn decode(s: &str) -> Vec<u8> {
+    let (b, err) = hex::decode_string(s);
+    assert!(err.is_none(), "bad hex {}", s);
+    b
+}
+
+struct TestVector {
+    seed: &'static str,
+    public_key: &'static str,
+    message: &'static str,
+    signature: &'static str,
+}
+
+// Test vectors from RFC 8032, Section 7.1.
+const RFC8032_TESTS: &[TestVector] = &[
+    TestVector {
+        seed: "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+        public_key: "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+        message: "",
+        signature: "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+    },
+    TestVector {
+        seed: "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+        public_key: "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+        message: "72",
+        signature: "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+    },
+    TestVector {
+        seed: "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+        public_key: "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+        message: "af82",
+        signature: "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+    },
+    // SHA(abc)
+    TestVector {
+        seed: "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42",
+        public_key: "ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf",
+        message: "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+        signature: "dc2a4459e7369633a52b1bf277839a00201009a3efbf3ecb69bea2186c26b58909351fc9ac90b3ecfdfbc7c66431e0303dca179c138ac17ad9bef1177331a704",
+    },
+];
+
+#[test]
+fn test_rfc8032_vectors() {
+    for tv in RFC8032_TESTS {
+        let private_key = new_key_from_seed(&decode(tv.seed));
+        let public_key = private_key.public();
+        assert_eq!(hex::encode_to_string(&public_key.0), tv.public_key);
+
+        let message = decode(tv.message);
+        let sig = sign(&private_key, &message);
+        assert_eq!(hex::encode_to_string(&sig), tv.signature);
+        assert!(verify(&public_key, &message, &sig));
+
+        let sig2 = private_key.sign(&message, &Options::default()).unwrap();
+        assert_eq!(sig, sig2);
+        assert!(verify_with_options(&public_key, &message, &sig, &Options::default()).is_ok());
+    }
+}
+
+#[test]
+fn test_sign_verify() {
+    let (public_key, private_key) = generate_key(&mut rand::Reader::new()).unwrap();
+
+    let message = b"test message";
+    let sig = sign(&private_key, message);
+    assert!(
+        verify(&public_key, message, &sig),
+        "valid signature rejected"
+    );
+
+    let wrong_message = b"wrong message";
+    assert!(
+        !verify(&public_key, wrong_message, &sig),
+        "signature of different message accepted"
+    );
+
+    let (other_public_key, _) = generate_key(&mut rand::Reader::new()).unwrap();
+    assert!(
+        !verify(&other_public_key, message, &sig),
+        "signature accepted with a different key"
+    );
+
+    assert!(!verify(&public_key, message, &sig[..63]));
+    let mut bad_sig = sig;
+    bad_sig[10] ^= 1;
+    assert!(!verify(&public_key, message, &bad_sig));
+}
+
+#[test]
+fn test_sign_verify_ph() {
+    // RFC 8032, Section 7.3, TEST abc.
+    let private_key = new_key_from_seed(&decode(
+        "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42",
+    ));
+    let public_key = private_key.public();
+    let message = b"abc";
+    let want = "98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae4131f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406";
+
+    let ph = sha512::sum512(message);
+    let opts = Options {
+        prehashed: true,
+        ..Default::default()
+    };
+    let sig = private_key.sign(&ph, &opts).unwrap();
+    assert_eq!(hex::encode_to_string(&sig), want);
+    assert!(verify_with_options(&public_key, &ph, &sig, &opts).is_ok());
+
+    let wrong_ph = sha512::sum512(b"wrong message");
+    assert!(verify_with_options(&public_key, &wrong_ph, &sig, &opts).is_err());
+
+    // The same signature is not valid as plain Ed25519 or with a context.
+    assert!(!verify(&public_key, &ph, &sig));
+    let opts_ctx = Options {
+        prehashed: true,
+        context: b"123",
+    };
+    assert!(verify_with_options(&public_key, &ph, &sig, &opts_ctx).is_err());
+
+    // The message must be a SHA-512 hash.
+    let err = private_key.sign(message, &opts).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "ed25519: bad Ed25519ph message hash length: 3"
+    );
+    assert!(verify_with_options(&public_key, message, &sig, &opts).is_err());
+}
+
+#[test]
+fn test_sign_verify_ctx() {
+    // RFC 8032, Section 7.2, foo.
+    let private_key = new_key_from_seed(&decode(
+        "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+    ));
+    let public_key = private_key.public();
+    assert_eq!(
+        hex::encode_to_string(&public_key.0),
+        "dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292"
+    );
+    let message = decode("f726936d19c800494e3fdaff20b276a8");
+    let want = "55a4cc2f70a54e04288c5f4cd1e45a7bb520b36292911876cada7323198dd87a8b36950b95130022907a7fb7c4e9b2d5f6cca685a587b4b21f4b888e4e7edb0d";
+
+    let opts = Options {
+        context: b"foo",
+        ..Default::default()
+    };
+    let sig = private_key.sign(&message, &opts).unwrap();
+    assert_eq!(hex::encode_to_string(&sig), want);
+    assert!(verify_with_options(&public_key, &message, &sig, &opts).is_ok());
+
+    let wrong_opts = Options {
+        context: b"bar",
+        ..Default::default()
+    };
+    let err = verify_with_options(&public_key, &message, &sig, &wrong_opts).unwrap_err();
+    assert_eq!(err.to_string(), "ed25519: invalid signature");
+    assert!(!verify(&public_key, &message, &sig));
+
+    let long_context = [0; 256];
+    let long_opts = Options {
+        context: &long_context,
+        ..Default::default()
+    };
+    let err = private_key.sign(&message, &long_opts).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "ed25519: bad Ed25519ctx context length: 256"
+    );
+    assert!(verify_with_options(&public_key, &message, &sig, &long_opts).is_err());
+}
+
+#[test]
+fn test_golden() {
+    // sign.input.gz is a selection of test cases from
+    // https://ed25519.cr.yp.to/python/sign.input
+    let f = std::fs::File::open("src/crypto/ed25519/testdata/sign.input.gz").unwrap();
+    let zr = gzip::Reader::new(std::io::BufReader::new(f)).unwrap();
+    let mut line_no = 0;
+    for line in std::io::BufReader::new(zr).lines() {
+        let line = line.unwrap();
+        line_no += 1;
+
+        let parts: Vec<&str> = line.split(':').collect();
+        assert_eq!(parts.len(), 5, "bad number of parts on line {}", line_no);
+
+        let priv_bytes = decode(parts[0]);
+        let pub_key = decode(parts[1]);
+        let msg = decode(parts[2]);
+        let sig = decode(parts[3]);
+        // The signatures in the test vectors also include the message
+        // at the end, but we just want R and S.
+        let sig = &sig[..SIGNATURE_SIZE];
+
+        assert_eq!(
+            pub_key.len(),
+            PUBLIC_KEY_SIZE,
+            "bad public key length on line {}",
+            line_no
+        );
+
+        let mut private_key = PrivateKey([0; PRIVATE_KEY_SIZE]);
+        private_key.0[..32].copy_from_slice(&priv_bytes[..32]);
+        private_key.0[32..].copy_from_slice(&pub_key);
+
+        let sig2 = sign(&private_key, &msg);
+        assert_eq!(sig, sig2, "different signature result on line {}", line_no);
+
+        let public_key = PublicKey(pub_key.try_into().unwrap());
+        assert!(
+            verify(&public_key, &msg, &sig2),
+            "signature failed to verify on line {}",
+            line_no
+        );
+
+        let private_key2 = new_key_from_seed(&private_key.0[..32]);
+        assert!(
+            private_key.equal(&private_key2),
+            "recreating key pair gave different private key on line {}",
+            line_no
+        );
+        assert!(
+            public_key.equal(&private_key2.public()),
+            "recreating key pair gave different public key on line {}",
+            line_no
+        );
+        assert_eq!(
+            private_key.0[..32],
+            private_key2.seed(),
+            "recreating key pair gave different seed on line {}",
+            line_no
+        );
+    }
+    assert_eq!(line_no, 1024);
+}
+
+#[test]
+fn test_malleability() {
+    // Adding l to S yields an encoding of the same scalar, which RFC 8032
+    // requires verifiers to reject. See Section 5.1.7.
+    const L: [u8; 32] = [
+        0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde,
+        0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x10,
+    ];
+    let tv = &RFC8032_TESTS[0];
+    let public_key = new_key_from_seed(&decode(tv.seed)).public();
+    let sig = decode(tv.signature);
+    assert!(verify(&public_key, b"", &sig));
+
+    let mut malleated = sig.clone();
+    let mut carry = 0;
+    for i in 0..32 {
+        let sum = malleated[32 + i] as u16 + L[i] as u16 + carry;
+        malleated[32 + i] = sum as u8;
+        carry = sum >> 8;
+    }
+    assert_eq!(carry, 0);
+    assert!(malleated[63] & 224 == 0);
+    assert!(
+        !verify(&public_key, b"", &malleated),
+        "non-canonical signature accepted"
+    );
+}
+
+#[test]
+fn test_invalid_public_key() {
+    let tv = &RFC8032_TESTS[0];
+    let sig = decode(tv.signature);
+    // An encoding of y = 2, which is not on the curve.
+    let mut bad_key = [0; PUBLIC_KEY_SIZE];
+    bad_key[0] = 2;
+    assert!(!verify(&PublicKey(bad_key), b"", &sig));
+}
+
+#[test]
+fn test_generate_key_deterministic() {
+    let seed = decode(RFC8032_TESTS[1].seed);
+    let (public_key, private_key) = generate_key(&mut seed.as_slice()).unwrap();
+    assert_eq!(
+        hex::encode_to_string(&public_key.0),
+        RFC8032_TESTS[1].public_key
+    );
+    assert_eq!(private_key.seed().as_slice(), seed.as_slice());
+    assert!(private_key.equal(&new_key_from_seed(&seed)));
+
+    // A short read is an error.
+    assert!(generate_key(&mut &seed[..31]).is_err());
+}
+
+#[test]
+fn test_equal() {
+    let (public_key, private_key) = generate_key(&mut rand::Reader::new()).unwrap();
+
+    assert!(public_key.equal(&public_key));
+    assert!(public_key.equal(&private_key.public()));
+    assert!(private_key.equal(&private_key.clone()));
+    assert!(private_key.equal(&new_key_from_seed(&private_key.seed())));
+
+    let (other_public_key, other_private_key) = generate_key(&mut rand::Reader::new()).unwrap();
+    assert!(!public_key.equal(&other_public_key));
+    assert!(!private_key.equal(&other_private_key));
+}
+
+#[test]
+#[should_panic(expected = "ed25519: bad seed length: 31")]
+fn test_new_key_from_seed_bad_length() {
+    new_key_from_seed(&[0; 31]);
+}
@@ -0,0 +1,31 @@
+// Copyright 2023 The rust-ggstd authors.
+// Copyright 2016 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! Package ed25519 implements the Ed25519 signature algorithm. See
+//! <https://ed25519.cr.yp.to/>.
+//!
+//! These functions are also compatible with the "Ed25519" function defined in
+//! RFC 8032. However, unlike RFC 8032's formulation, this package's private key
+//! representation includes a public key suffix to make multiple signing
+//! operations with the same key more efficient. This package refers to the RFC
+//! 8032 private key as the "seed".
+//!
+//! ```
+//! use ggstd::crypto::{ed25519, rand};
+//!
+//! let (public_key, private_key) = ed25519::generate_key(&mut rand::Reader::new()).unwrap();
+//! let sig = ed25519::sign(&private_key, b"release manifest");
+//! assert!(ed25519::verify(&public_key, b"release manifest", &sig));
+//! ```
+
+mod ed25519;
+
+pub use ed25519::{
+    generate_key, new_key_from_seed, sign, verify, verify_with_options, Options, PrivateKey,
+    PublicKey, PRIVATE_KEY_SIZE, PUBLIC_KEY_SIZE, SEED_SIZE, SIGNATURE_SIZE,
+};
+
+#[cfg(test)]
+mod ed25519_test;
@@ -7,6 +7,7 @@ use super::HMAC;
 use crate::crypto::md5;
 use crate::crypto::sha1;
 use crate::crypto::sha256;
+use crate::crypto::sha512;
 use crate::encoding::hex;
 use crate::hash::Hash;
 use std::io::Write;
@@ -355,162 +356,156 @@ const HMAC_TESTS: &[HmacTest] = &[
         sha256::SIZE,
         sha256::BLOCK_SIZE,
     ),
-    // HmacTest::new(
-    // 		sha512.New384,
-    // 		&[
-    // 			0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
-    // 			0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
-    // 			0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
-    // 			0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
-    // 			0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
-    // 			0x28, 0x29, 0x2a, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f,
-    // 			0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37,
-    // 			0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x3f,
-    // 			0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47,
-    // 			0x48, 0x49, 0x4a, 0x4b, 0x4c, 0x4d, 0x4e, 0x4f,
-    // 			0x50, 0x51, 0x52, 0x53, 0x54, 0x55, 0x56, 0x57,
-    // 			0x58, 0x59, 0x5a, 0x5b, 0x5c, 0x5d, 0x5e, 0x5f,
-    // 			0x60, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67,
-    // 			0x68, 0x69, 0x6a, 0x6b, 0x6c, 0x6d, 0x6e, 0x6f,
-    // 			0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77,
-    // 			0x78, 0x79, 0x7a, 0x7b, 0x7c, 0x7d, 0x7e, 0x7f,
-    // 		],
-    // 		b"Sample message for keylen=blocklen",
-    // 		"63c5daa5e651847ca897c95814ab830bededc7d25e83eef9195cd45857a37f448947858f5af50cc2b1b730ddf29671a9",
-    // 		sha512.Size384,
-    // 		sha512::BLOCK_SIZE,
-    // 	),
-    // HmacTest::new(
-    // 		sha512.New384,
-    // 		&[
-    // 			0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
-    // 			0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
-    // 			0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
-    // 			0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
-    // 			0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
-    // 			0x28, 0x29, 0x2a, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f,
-    // 		],
-    // 		b"Sample message for keylen<blocklen",
-    // 		"6eb242bdbb582ca17bebfa481b1e23211464d2b7f8c20b9ff2201637b93646af5ae9ac316e98db45d9cae773675eeed0",
-    // 		sha512.Size384,
-    // 		sha512::BLOCK_SIZE,
-    // 	),
-    // HmacTest::new(
-    // 		sha512.New384,
-    // 		&[
-    // 			0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
-    // 			0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
-    // 			0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
-    // 			0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
-    // 			0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
-    // 			0x28, 0x29, 0x2a, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f,
-    // 			0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37,
-    // 			0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x3f,
-    // 			0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47,
-    // 			0x48, 0x49, 0x4a, 0x4b, 0x4c, 0x4d, 0x4e, 0x4f,
-    // 			0x50, 0x51, 0x52, 0x53, 0x54, 0x55, 0x56, 0x57,
-    // 			0x58, 0x59, 0x5a, 0x5b, 0x5c, 0x5d, 0x5e, 0x5f,
-    // 			0x60, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67,
-    // 			0x68, 0x69, 0x6a, 0x6b, 0x6c, 0x6d, 0x6e, 0x6f,
-    // 			0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77,
-    // 			0x78, 0x79, 0x7a, 0x7b, 0x7c, 0x7d, 0x7e, 0x7f,
-    // 			0x80, 0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87,
-    // 			0x88, 0x89, 0x8a, 0x8b, 0x8c, 0x8d, 0x8e, 0x8f,
-    // 			0x90, 0x91, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97,
-    // 			0x98, 0x99, 0x9a, 0x9b, 0x9c, 0x9d, 0x9e, 0x9f,
-    // 			0xa0, 0xa1, 0xa2, 0xa3, 0xa4, 0xa5, 0xa6, 0xa7,
-    // 			0xa8, 0xa9, 0xaa, 0xab, 0xac, 0xad, 0xae, 0xaf,
-    // 			0xb0, 0xb1, 0xb2, 0xb3, 0xb4, 0xb5, 0xb6, 0xb7,
-    // 			0xb8, 0xb9, 0xba, 0xbb, 0xbc, 0xbd, 0xbe, 0xbf,
-    // 			0xc0, 0xc1, 0xc2, 0xc3, 0xc4, 0xc5, 0xc6, 0xc7,
-    // 		],
-    // 		b"Sample message for keylen=blocklen",
-    // 		"5b664436df69b0ca22551231a3f0a3d5b4f97991713cfa84bff4d0792eff96c27dccbbb6f79b65d548b40e8564cef594",
-    // 		sha512.Size384,
-    // 		sha512::BLOCK_SIZE,
-    // 	),
-    // HmacTest::new(
-    // 		sha512.New,
-    // 		&[
-    // 			0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
-    // 			0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
-    // 			0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
-    // 			0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
-    // 			0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
-    // 			0x28, 0x29, 0x2a, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f,
-    // 			0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37,
-    // 			0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x3f,
-    // 			0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47,
-    // 			0x48, 0x49, 0x4a, 0x4b, 0x4c, 0x4d, 0x4e, 0x4f,
-    // 			0x50, 0x51, 0x52, 0x53, 0x54, 0x55, 0x56, 0x57,
-    // 			0x58, 0x59, 0x5a, 0x5b, 0x5c, 0x5d, 0x5e, 0x5f,
-    // 			0x60, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67,
-    // 			0x68, 0x69, 0x6a, 0x6b, 0x6c, 0x6d, 0x6e, 0x6f,
-    // 			0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77,
-    // 			0x78, 0x79, 0x7a, 0x7b, 0x7c, 0x7d, 0x7e, 0x7f,
-    // 		],
-    // 		b"Sample message for keylen=blocklen",
-    // 		"fc25e240658ca785b7a811a8d3f7b4ca" +
-    // 			"48cfa26a8a366bf2cd1f836b05fcb024bd36853081811d6c" +
-    // 			"ea4216ebad79da1cfcb95ea4586b8a0ce356596a55fb1347",
-    // 		sha512::SIZE,
-    // 		sha512::BLOCK_SIZE,
-    // 	),
-    // HmacTest::new(
-    // 		sha512.New,
-    // 		&[
-    // 			0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
-    // 			0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
-    // 			0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
-    // 			0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
-    // 			0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
-    // 			0x28, 0x29, 0x2a, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f,
-    // 			0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37,
-    // 			0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x3f,
-    // 		],
-    // 		b"Sample message for keylen<blocklen",
-    // 		"fd44c18bda0bb0a6ce0e82b031bf2818" +
-    // 			"f6539bd56ec00bdc10a8a2d730b3634de2545d639b0f2cf7" +
-    // 			"10d0692c72a1896f1f211c2b922d1a96c392e07e7ea9fedc",
-    // 		sha512::SIZE,
-    // 		sha512::BLOCK_SIZE,
-    // 	),
-    // HmacTest::new(
-    // 		sha512.New,
-    // 		&[
-    // 			0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
-    // 			0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
-    // 			0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
-    // 			0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
-    // 			0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
-    // 			0x28, 0x29, 0x2a, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f,
-    // 			0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37,
-    // 			0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x3f,
-    // 			0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47,
-    // 			0x48, 0x49, 0x4a, 0x4b, 0x4c, 0x4d, 0x4e, 0x4f,
-    // 			0x50, 0x51, 0x52, 0x53, 0x54, 0x55, 0x56, 0x57,
-    // 			0x58, 0x59, 0x5a, 0x5b, 0x5c, 0x5d, 0x5e, 0x5f,
-    // 			0x60, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67,
-    // 			0x68, 0x69, 0x6a, 0x6b, 0x6c, 0x6d, 0x6e, 0x6f,
-    // 			0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77,
-    // 			0x78, 0x79, 0x7a, 0x7b, 0x7c, 0x7d, 0x7e, 0x7f,
-    // 			0x80, 0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87,
-    // 			0x88, 0x89, 0x8a, 0x8b, 0x8c, 0x8d, 0x8e, 0x8f,
-    // 			0x90, 0x91, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97,
-    // 			0x98, 0x99, 0x9a, 0x9b, 0x9c, 0x9d, 0x9e, 0x9f,
-    // 			0xa0, 0xa1, 0xa2, 0xa3, 0xa4, 0xa5, 0xa6, 0xa7,
-    // 			0xa8, 0xa9, 0xaa, 0xab, 0xac, 0xad, 0xae, 0xaf,
-    // 			0xb0, 0xb1, 0xb2, 0xb3, 0xb4, 0xb5, 0xb6, 0xb7,
-    // 			0xb8, 0xb9, 0xba, 0xbb, 0xbc, 0xbd, 0xbe, 0xbf,
-    // 			0xc0, 0xc1, 0xc2, 0xc3, 0xc4, 0xc5, 0xc6, 0xc7,
-    // 		],
-    // 		b"Sample message for keylen=blocklen",
-    // 		"d93ec8d2de1ad2a9957cb9b83f14e76a" +
-    // 			"d6b5e0cce285079a127d3b14bccb7aa7286d4ac0d4ce6421" +
-    // 			"5f2bc9e6870b33d97438be4aaa20cda5c5a912b48b8e27f3",
-    // 		sha512::SIZE,
-    // 		sha512::BLOCK_SIZE,
-    // 	),
+    HmacTest::new(
+        "sha384",
+        &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+            0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
+            0x28, 0x29, 0x2a, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f,
+            0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37,
+            0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x3f,
+            0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47,
+            0x48, 0x49, 0x4a, 0x4b, 0x4c, 0x4d, 0x4e, 0x4f,
+            0x50, 0x51, 0x52, 0x53, 0x54, 0x55, 0x56, 0x57,
+            0x58, 0x59, 0x5a, 0x5b, 0x5c, 0x5d, 0x5e, 0x5f,
+            0x60, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67,
+            0x68, 0x69, 0x6a, 0x6b, 0x6c, 0x6d, 0x6e, 0x6f,
+            0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77,
+            0x78, 0x79, 0x7a, 0x7b, 0x7c, 0x7d, 0x7e, 0x7f,
+        ],
+        b"Sample message for keylen=blocklen",
+        "63c5daa5e651847ca897c95814ab830bededc7d25e83eef9195cd45857a37f448947858f5af50cc2b1b730ddf29671a9",
+        sha512::SIZE384,
+        sha512::BLOCK_SIZE,
+    ),
+    HmacTest::new(
+        "sha384",
+        &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+            0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
+            0x28, 0x29, 0x2a, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f,
+        ],
+        b"Sample message for keylen<blocklen",
+        "6eb242bdbb582ca17bebfa481b1e23211464d2b7f8c20b9ff2201637b93646af5ae9ac316e98db45d9cae773675eeed0",
+        sha512::SIZE384,
+        sha512::BLOCK_SIZE,
+    ),
+    HmacTest::new(
+        "sha384",
+        &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+            0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
+            0x28, 0x29, 0x2a, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f,
+            0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37,
+            0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x3f,
+            0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47,
+            0x48, 0x49, 0x4a, 0x4b, 0x4c, 0x4d, 0x4e, 0x4f,
+            0x50, 0x51, 0x52, 0x53, 0x54, 0x55, 0x56, 0x57,
+            0x58, 0x59, 0x5a, 0x5b, 0x5c, 0x5d, 0x5e, 0x5f,
+            0x60, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67,
+            0x68, 0x69, 0x6a, 0x6b, 0x6c, 0x6d, 0x6e, 0x6f,
+            0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77,
+            0x78, 0x79, 0x7a, 0x7b, 0x7c, 0x7d, 0x7e, 0x7f,
+            0x80, 0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87,
+            0x88, 0x89, 0x8a, 0x8b, 0x8c, 0x8d, 0x8e, 0x8f,
+            0x90, 0x91, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97,
+            0x98, 0x99, 0x9a, 0x9b, 0x9c, 0x9d, 0x9e, 0x9f,
+            0xa0, 0xa1, 0xa2, 0xa3, 0xa4, 0xa5, 0xa6, 0xa7,
+            0xa8, 0xa9, 0xaa, 0xab, 0xac, 0xad, 0xae, 0xaf,
+            0xb0, 0xb1, 0xb2, 0xb3, 0xb4, 0xb5, 0xb6, 0xb7,
+            0xb8, 0xb9, 0xba, 0xbb, 0xbc, 0xbd, 0xbe, 0xbf,
+            0xc0, 0xc1, 0xc2, 0xc3, 0xc4, 0xc5, 0xc6, 0xc7,
+        ],
+        b"Sample message for keylen=blocklen",
+        "5b664436df69b0ca22551231a3f0a3d5b4f97991713cfa84bff4d0792eff96c27dccbbb6f79b65d548b40e8564cef594",
+        sha512::SIZE384,
+        sha512::BLOCK_SIZE,
+    ),
+    HmacTest::new(
+        "sha512",
+        &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+            0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
+            0x28, 0x29, 0x2a, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f,
+            0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37,
+            0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x3f,
+            0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47,
+            0x48, 0x49, 0x4a, 0x4b, 0x4c, 0x4d, 0x4e, 0x4f,
+            0x50, 0x51, 0x52, 0x53, 0x54, 0x55, 0x56, 0x57,
+            0x58, 0x59, 0x5a, 0x5b, 0x5c, 0x5d, 0x5e, 0x5f,
+            0x60, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67,
+            0x68, 0x69, 0x6a, 0x6b, 0x6c, 0x6d, 0x6e, 0x6f,
+            0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77,
+            0x78, 0x79, 0x7a, 0x7b, 0x7c, 0x7d, 0x7e, 0x7f,
+        ],
+        b"Sample message for keylen=blocklen",
+        "fc25e240658ca785b7a811a8d3f7b4ca48cfa26a8a366bf2cd1f836b05fcb024bd36853081811d6cea4216ebad79da1cfcb95ea4586b8a0ce356596a55fb1347",
+        sha512::SIZE,
+        sha512::BLOCK_SIZE,
+    ),
+    HmacTest::new(
+        "sha512",
+        &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+            0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
+            0x28, 0x29, 0x2a, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f,
+            0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37,
+            0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x3f,
+        ],
+        b"Sample message for keylen<blocklen",
+        "fd44c18bda0bb0a6ce0e82b031bf2818f6539bd56ec00bdc10a8a2d730b3634de2545d639b0f2cf710d0692c72a1896f1f211c2b922d1a96c392e07e7ea9fedc",
+        sha512::SIZE,
+        sha512::BLOCK_SIZE,
+    ),
+    HmacTest::new(
+        "sha512",
+        &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+            0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
+            0x28, 0x29, 0x2a, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f,
+            0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37,
+            0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x3f,
+            0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47,
+            0x48, 0x49, 0x4a, 0x4b, 0x4c, 0x4d, 0x4e, 0x4f,
+            0x50, 0x51, 0x52, 0x53, 0x54, 0x55, 0x56, 0x57,
+            0x58, 0x59, 0x5a, 0x5b, 0x5c, 0x5d, 0x5e, 0x5f,
+            0x60, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67,
+            0x68, 0x69, 0x6a, 0x6b, 0x6c, 0x6d, 0x6e, 0x6f,
+            0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77,
+            0x78, 0x79, 0x7a, 0x7b, 0x7c, 0x7d, 0x7e, 0x7f,
+            0x80, 0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87,
+            0x88, 0x89, 0x8a, 0x8b, 0x8c, 0x8d, 0x8e, 0x8f,
+            0x90, 0x91, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97,
+            0x98, 0x99, 0x9a, 0x9b, 0x9c, 0x9d, 0x9e, 0x9f,
+            0xa0, 0xa1, 0xa2, 0xa3, 0xa4, 0xa5, 0xa6, 0xa7,
+            0xa8, 0xa9, 0xaa, 0xab, 0xac, 0xad, 0xae, 0xaf,
+            0xb0, 0xb1, 0xb2, 0xb3, 0xb4, 0xb5, 0xb6, 0xb7,
+            0xb8, 0xb9, 0xba, 0xbb, 0xbc, 0xbd, 0xbe, 0xbf,
+            0xc0, 0xc1, 0xc2, 0xc3, 0xc4, 0xc5, 0xc6, 0xc7,
+        ],
+        b"Sample message for keylen=blocklen",
+        "d93ec8d2de1ad2a9957cb9b83f14e76ad6b5e0cce285079a127d3b14bccb7aa7286d4ac0d4ce64215f2bc9e6870b33d97438be4aaa20cda5c5a912b48b8e27f3",
+        sha512::SIZE,
+        sha512::BLOCK_SIZE,
+    ),
     // HMAC without key is dumb but should probably not fail.
     HmacTest::new(
         "sha1",
@@ -528,14 +523,14 @@ const HMAC_TESTS: &[HmacTest] = &[
         sha256::SIZE,
         sha256::BLOCK_SIZE,
     ),
-    // HmacTest::new(
-    // 		sha512.New,
-    // 		&[],
-    // 		b"message",
-    // 		"08fce52f6395d59c2a3fb8abb281d74ad6f112b9a9c787bcea290d94dadbc82b2ca3e5e12bf2277c7fedbb0154d5493e41bb7459f63c8e39554ea3651b812492",
-    // 		sha512::SIZE,
-    // 		sha512::BLOCK_SIZE,
-    // 	),
+    HmacTest::new(
+        "sha512",
+        &[],
+        b"message",
+        "08fce52f6395d59c2a3fb8abb281d74ad6f112b9a9c787bcea290d94dadbc82b2ca3e5e12bf2277c7fedbb0154d5493e41bb7459f63c8e39554ea3651b812492",
+        sha512::SIZE,
+        sha512::BLOCK_SIZE,
+    ),
 ];
 
 #[test]
@@ -545,6 +540,8 @@ fn test_hmac() {
             "sha1" => Box::new(HMAC::new(sha1::Digest::new, tt.key)),
             "sha256" => Box::new(HMAC::new(sha256::Digest::new, tt.key)),
             "sha224" => Box::new(HMAC::new(sha256::Digest::new224, tt.key)),
+            "sha384" => Box::new(HMAC::new(sha512::Digest::new384, tt.key)),
+            "sha512" => Box::new(HMAC::new(sha512::Digest::new, tt.key)),
             "md5" => Box::new(HMAC::new(md5::Digest::new, tt.key)),
             _ => panic!("unsupported hash {}", tt.hash),
         };
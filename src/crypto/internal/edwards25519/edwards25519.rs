@@ -0,0 +1,424 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright (c) 2017 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::field::Element;
+use crate::errors;
+
+// Point types.
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct ProjP1xP1 {
+    pub(super) x: Element,
+    pub(super) y: Element,
+    pub(super) z: Element,
+    pub(super) t: Element,
+}
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct ProjP2 {
+    pub(super) x: Element,
+    pub(super) y: Element,
+    pub(super) z: Element,
+}
+
+/// Point represents a point on the edwards25519 curve.
+///
+/// This type works similarly to math/big.Int, except that the operations
+/// return a new value instead of setting a receiver.
+///
+/// Point doesn't implement PartialEq, as equivalent points can be
+/// represented by different values. Use equal instead.
+#[derive(Clone, Copy)]
+pub struct Point {
+    // The point is internally represented in extended coordinates (X, Y, Z, T)
+    // where x = X/Z, y = Y/Z, and xy = T/Z per https://eprint.iacr.org/2008/522.
+    pub(super) x: Element,
+    pub(super) y: Element,
+    pub(super) z: Element,
+    pub(super) t: Element,
+}
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct ProjCached {
+    pub(super) y_plus_x: Element,
+    pub(super) y_minus_x: Element,
+    pub(super) z: Element,
+    pub(super) t2d: Element,
+}
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct AffineCached {
+    pub(super) y_plus_x: Element,
+    pub(super) y_minus_x: Element,
+    pub(super) t2d: Element,
+}
+
+// Constructors.
+
+impl ProjP2 {
+    pub(super) fn zero() -> ProjP2 {
+        ProjP2 {
+            x: Element::zero(),
+            y: Element::one(),
+            z: Element::one(),
+        }
+    }
+}
+
+/// new_identity_point returns a new Point set to the identity.
+pub fn new_identity_point() -> Point {
+    Point {
+        x: Element::zero(),
+        y: Element::one(),
+        z: Element::one(),
+        t: Element::zero(),
+    }
+}
+
+/// GENERATOR_BYTES is the encoding of the canonical curve basepoint.
+/// See test_generator for the correspondence of this encoding with the
+/// values in RFC 8032.
+const GENERATOR_BYTES: [u8; 32] = [
+    0x58, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+    0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+];
+
+/// new_generator_point returns a new Point set to the canonical generator.
+pub fn new_generator_point() -> Point {
+    Point::set_bytes(&GENERATOR_BYTES).unwrap()
+}
+
+impl ProjCached {
+    pub(super) fn zero() -> ProjCached {
+        ProjCached {
+            y_plus_x: Element::one(),
+            y_minus_x: Element::one(),
+            z: Element::one(),
+            t2d: Element::zero(),
+        }
+    }
+}
+
+impl AffineCached {
+    pub(super) fn zero() -> AffineCached {
+        AffineCached {
+            y_plus_x: Element::one(),
+            y_minus_x: Element::one(),
+            t2d: Element::zero(),
+        }
+    }
+}
+
+// Encoding.
+
+impl Point {
+    /// bytes returns the canonical 32-byte encoding of v, according to RFC 8032,
+    /// Section 5.1.2.
+    pub fn bytes(&self) -> [u8; 32] {
+        let z_inv = self.z.invert(); // z_inv = 1 / Z
+        let x = self.x.multiply(&z_inv); // x = X / Z
+        let y = self.y.multiply(&z_inv); // y = Y / Z
+
+        let mut out = y.bytes();
+        out[31] |= (x.is_negative() << 7) as u8;
+        out
+    }
+
+    /// set_bytes returns the point x, where x is a 32-byte encoding of a point.
+    /// If x does not represent a valid point on the curve, set_bytes returns
+    /// an error.
+    ///
+    /// Note that set_bytes accepts all non-canonical encodings of valid points.
+    /// That is, it follows decoding rules that match most implementations in
+    /// the ecosystem rather than RFC 8032.
+    pub fn set_bytes(x: &[u8]) -> Result<Point, errors::ErrorStaticString> {
+        // Specifically, the non-canonical encodings that are accepted are
+        //   1) the ones where the field element is not reduced (see the
+        //      field::Element::set_bytes docs) and
+        //   2) the ones where the x-coordinate is zero and the sign bit is set.
+        //
+        // Read more at https://hdevalence.ca/blog/2020-10-04-its-25519am,
+        // specifically the "Canonical A, R" section.
+
+        let y = Element::set_bytes(x)
+            .map_err(|_| errors::new_static("edwards25519: invalid point encoding length"))?;
+
+        // -x² + y² = 1 + dx²y²
+        // x² + dx²y² = x²(dy² + 1) = y² - 1
+        // x² = (y² - 1) / (dy² + 1)
+
+        // u = y² - 1
+        let y2 = y.square();
+        let u = y2.subtract(&Element::one());
+
+        // v = dy² + 1
+        let vv = y2.multiply(&D).add(&Element::one());
+
+        // x = +√(u/v)
+        let (xx, was_square) = Element::sqrt_ratio(&u, &vv);
+        if was_square == 0 {
+            return Err(errors::new_static("edwards25519: invalid point encoding"));
+        }
+
+        // Select the negative square root if the sign bit is set.
+        let xx_neg = xx.negate();
+        let xx = Element::select(&xx_neg, &xx, (x[31] >> 7) as isize);
+
+        Ok(Point {
+            x: xx,
+            y,
+            z: Element::one(),
+            t: xx.multiply(&y), // xy = T / Z
+        })
+    }
+}
+
+// Conversions.
+
+impl ProjP2 {
+    pub(super) fn from_p1xp1(p: &ProjP1xP1) -> ProjP2 {
+        ProjP2 {
+            x: p.x.multiply(&p.t),
+            y: p.y.multiply(&p.z),
+            z: p.z.multiply(&p.t),
+        }
+    }
+
+    pub(super) fn from_p3(p: &Point) -> ProjP2 {
+        ProjP2 {
+            x: p.x,
+            y: p.y,
+            z: p.z,
+        }
+    }
+}
+
+impl Point {
+    pub(super) fn from_p1xp1(p: &ProjP1xP1) -> Point {
+        Point {
+            x: p.x.multiply(&p.t),
+            y: p.y.multiply(&p.z),
+            z: p.z.multiply(&p.t),
+            t: p.x.multiply(&p.y),
+        }
+    }
+
+    pub(super) fn from_p2(p: &ProjP2) -> Point {
+        Point {
+            x: p.x.multiply(&p.z),
+            y: p.y.multiply(&p.z),
+            z: p.z.square(),
+            t: p.x.multiply(&p.y),
+        }
+    }
+}
+
+/// D is a constant in the curve equation.
+const D: Element = Element {
+    l0: 929955233495203,
+    l1: 466365720129213,
+    l2: 1662059464998953,
+    l3: 2033849074728123,
+    l4: 1442794654840575,
+};
+
+/// D2 is 2 * D.
+const D2: Element = Element {
+    l0: 1859910466990425,
+    l1: 932731440258426,
+    l2: 1072319116312658,
+    l3: 1815898335770999,
+    l4: 633789495995903,
+};
+
+impl ProjCached {
+    pub(super) fn from_p3(p: &Point) -> ProjCached {
+        ProjCached {
+            y_plus_x: p.y.add(&p.x),
+            y_minus_x: p.y.subtract(&p.x),
+            z: p.z,
+            t2d: p.t.multiply(&D2),
+        }
+    }
+}
+
+impl AffineCached {
+    pub(super) fn from_p3(p: &Point) -> AffineCached {
+        let inv_z = p.z.invert();
+        AffineCached {
+            y_plus_x: p.y.add(&p.x).multiply(&inv_z),
+            y_minus_x: p.y.subtract(&p.x).multiply(&inv_z),
+            t2d: p.t.multiply(&D2).multiply(&inv_z),
+        }
+    }
+}
+
+// (Re)addition and subtraction.
+
+impl Point {
+    /// add returns p + q.
+    pub fn add(&self, q: &Point) -> Point {
+        let q_cached = ProjCached::from_p3(q);
+        Point::from_p1xp1(&ProjP1xP1::add(self, &q_cached))
+    }
+
+    /// subtract returns p - q.
+    pub fn subtract(&self, q: &Point) -> Point {
+        let q_cached = ProjCached::from_p3(q);
+        Point::from_p1xp1(&ProjP1xP1::sub(self, &q_cached))
+    }
+}
+
+impl ProjP1xP1 {
+    pub(super) fn add(p: &Point, q: &ProjCached) -> ProjP1xP1 {
+        let y_plus_x = p.y.add(&p.x);
+        let y_minus_x = p.y.subtract(&p.x);
+
+        let pp = y_plus_x.multiply(&q.y_plus_x);
+        let mm = y_minus_x.multiply(&q.y_minus_x);
+        let tt2d = p.t.multiply(&q.t2d);
+        let zz2 = p.z.multiply(&q.z);
+        let zz2 = zz2.add(&zz2);
+
+        ProjP1xP1 {
+            x: pp.subtract(&mm),
+            y: pp.add(&mm),
+            z: zz2.add(&tt2d),
+            t: zz2.subtract(&tt2d),
+        }
+    }
+
+    pub(super) fn sub(p: &Point, q: &ProjCached) -> ProjP1xP1 {
+        let y_plus_x = p.y.add(&p.x);
+        let y_minus_x = p.y.subtract(&p.x);
+
+        let pp = y_plus_x.multiply(&q.y_minus_x); // flipped sign
+        let mm = y_minus_x.multiply(&q.y_plus_x); // flipped sign
+        let tt2d = p.t.multiply(&q.t2d);
+        let zz2 = p.z.multiply(&q.z);
+        let zz2 = zz2.add(&zz2);
+
+        ProjP1xP1 {
+            x: pp.subtract(&mm),
+            y: pp.add(&mm),
+            z: zz2.subtract(&tt2d), // flipped sign
+            t: zz2.add(&tt2d),      // flipped sign
+        }
+    }
+
+    pub(super) fn add_affine(p: &Point, q: &AffineCached) -> ProjP1xP1 {
+        let y_plus_x = p.y.add(&p.x);
+        let y_minus_x = p.y.subtract(&p.x);
+
+        let pp = y_plus_x.multiply(&q.y_plus_x);
+        let mm = y_minus_x.multiply(&q.y_minus_x);
+        let tt2d = p.t.multiply(&q.t2d);
+        let z2 = p.z.add(&p.z);
+
+        ProjP1xP1 {
+            x: pp.subtract(&mm),
+            y: pp.add(&mm),
+            z: z2.add(&tt2d),
+            t: z2.subtract(&tt2d),
+        }
+    }
+
+    pub(super) fn sub_affine(p: &Point, q: &AffineCached) -> ProjP1xP1 {
+        let y_plus_x = p.y.add(&p.x);
+        let y_minus_x = p.y.subtract(&p.x);
+
+        let pp = y_plus_x.multiply(&q.y_minus_x); // flipped sign
+        let mm = y_minus_x.multiply(&q.y_plus_x); // flipped sign
+        let tt2d = p.t.multiply(&q.t2d);
+        let z2 = p.z.add(&p.z);
+
+        ProjP1xP1 {
+            x: pp.subtract(&mm),
+            y: pp.add(&mm),
+            z: z2.subtract(&tt2d), // flipped sign
+            t: z2.add(&tt2d),      // flipped sign
+        }
+    }
+
+    // Doubling.
+
+    pub(super) fn double(p: &ProjP2) -> ProjP1xP1 {
+        let xx = p.x.square();
+        let yy = p.y.square();
+        let zz2 = p.z.square();
+        let zz2 = zz2.add(&zz2);
+        let x_plus_y_sq = p.x.add(&p.y).square();
+
+        let y = yy.add(&xx);
+        let z = yy.subtract(&xx);
+        ProjP1xP1 {
+            x: x_plus_y_sq.subtract(&y),
+            y,
+            z,
+            t: zz2.subtract(&z),
+        }
+    }
+}
+
+// Negation.
+
+impl Point {
+    /// negate returns -p.
+    pub fn negate(&self) -> Point {
+        Point {
+            x: self.x.negate(),
+            y: self.y,
+            z: self.z,
+            t: self.t.negate(),
+        }
+    }
+
+    /// equal returns 1 if v is equivalent to u, and 0 otherwise.
+    pub fn equal(&self, u: &Point) -> isize {
+        let t1 = self.x.multiply(&u.z);
+        let t2 = u.x.multiply(&self.z);
+        let t3 = self.y.multiply(&u.z);
+        let t4 = u.y.multiply(&self.z);
+
+        t1.equal(&t2) & t3.equal(&t4)
+    }
+}
+
+// Constant-time operations
+
+impl ProjCached {
+    /// select returns a if cond == 1 and b if cond == 0.
+    pub(super) fn select(a: &ProjCached, b: &ProjCached, cond: isize) -> ProjCached {
+        ProjCached {
+            y_plus_x: Element::select(&a.y_plus_x, &b.y_plus_x, cond),
+            y_minus_x: Element::select(&a.y_minus_x, &b.y_minus_x, cond),
+            z: Element::select(&a.z, &b.z, cond),
+            t2d: Element::select(&a.t2d, &b.t2d, cond),
+        }
+    }
+
+    /// cond_neg negates v if cond == 1 and leaves it unchanged if cond == 0.
+    pub(super) fn cond_neg(&mut self, cond: isize) {
+        self.y_plus_x.swap(&mut self.y_minus_x, cond);
+        self.t2d = Element::select(&self.t2d.negate(), &self.t2d, cond);
+    }
+}
+
+impl AffineCached {
+    /// select returns a if cond == 1 and b if cond == 0.
+    pub(super) fn select(a: &AffineCached, b: &AffineCached, cond: isize) -> AffineCached {
+        AffineCached {
+            y_plus_x: Element::select(&a.y_plus_x, &b.y_plus_x, cond),
+            y_minus_x: Element::select(&a.y_minus_x, &b.y_minus_x, cond),
+            t2d: Element::select(&a.t2d, &b.t2d, cond),
+        }
+    }
+
+    /// cond_neg negates v if cond == 1 and leaves it unchanged if cond == 0.
+    pub(super) fn cond_neg(&mut self, cond: isize) {
+        self.y_plus_x.swap(&mut self.y_minus_x, cond);
+        self.t2d = Element::select(&self.t2d.negate(), &self.t2d, cond);
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright (c) 2017 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::scalar_test::generate_scalar;
+use super::{new_generator_point, new_identity_point, Point};
+use crate::encoding::hex;
+use crate::math::rand;
+
+#[test]
+fn test_generator() {
+    // These are the coordinates of B from RFC 8032, Section 5.1,
+    // encoded as little-endian integers.
+    let x = "1ad5258f602d56c9b2a7259560c72c695cdcd6fd31e2a4c0fe536ecdd3366921";
+    let y = "5866666666666666666666666666666666666666666666666666666666666666";
+
+    let b = new_generator_point();
+    let z_inv = b.z.invert();
+    assert_eq!(hex::encode_to_string(&b.x.multiply(&z_inv).bytes()), x);
+    assert_eq!(hex::encode_to_string(&b.y.multiply(&z_inv).bytes()), y);
+    assert_eq!(hex::encode_to_string(&b.bytes()), y);
+}
+
+#[test]
+fn test_identity() {
+    let mut want = [0; 32];
+    want[0] = 1;
+    assert_eq!(new_identity_point().bytes(), want);
+
+    let b = new_generator_point();
+    assert_eq!(b.add(&new_identity_point()).equal(&b), 1);
+    assert_eq!(b.subtract(&b).equal(&new_identity_point()), 1);
+    assert_eq!(b.equal(&new_identity_point()), 0);
+}
+
+#[test]
+fn test_add_sub_neg_on_base_point() {
+    let b = new_generator_point();
+    let check_lhs = b.add(&b);
+    let check_rhs = Point::scalar_base_mult(&{
+        let mut two = [0; 32];
+        two[0] = 2;
+        super::Scalar::set_canonical_bytes(&two).unwrap()
+    });
+    assert_eq!(check_lhs.equal(&check_rhs), 1);
+
+    let b_neg = b.negate();
+    assert_eq!(b.subtract(&b).equal(&b.add(&b_neg)), 1);
+    assert_eq!(b.add(&b_neg).equal(&new_identity_point()), 1);
+}
+
+#[test]
+fn test_encoding_round_trip() {
+    let mut r = rand::Rand::new(rand::new_source(1));
+    for _ in 0..50 {
+        let p = Point::scalar_base_mult(&generate_scalar(&mut r));
+        let enc = p.bytes();
+        let q = Point::set_bytes(&enc).unwrap();
+        assert_eq!(p.equal(&q), 1);
+        assert_eq!(q.bytes(), enc);
+    }
+}
+
+#[test]
+fn test_invalid_encodings() {
+    // An encoding of y = 2, which is not on the curve.
+    let invalid = "0200000000000000000000000000000000000000000000000000000000000000";
+    let (b, _) = hex::decode_string(invalid);
+    assert!(Point::set_bytes(&b).is_err());
+    assert!(Point::set_bytes(&b[..31]).is_err());
+    assert!(Point::set_bytes(&[0; 33]).is_err());
+}
+
+#[test]
+fn test_non_canonical_points() {
+    // y = p + 1 is a non-canonical encoding of y = 1, the identity,
+    // which is accepted and re-encoded canonically.
+    let (b, _) =
+        hex::decode_string("eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f");
+    let p = Point::set_bytes(&b).unwrap();
+    assert_eq!(p.equal(&new_identity_point()), 1);
+    let mut want = [0; 32];
+    want[0] = 1;
+    assert_eq!(p.bytes(), want);
+}
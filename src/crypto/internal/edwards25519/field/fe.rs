@@ -0,0 +1,423 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright (c) 2017 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::fe_generic::{carry_propagate, fe_mul, fe_square};
+use crate::crypto::subtle;
+use crate::encoding::binary::{ByteOrder, LITTLE_ENDIAN};
+use crate::errors;
+
+/// Element represents an element of the field GF(2^255-19). Note that this
+/// is not a cryptographically secure group, and should only be used to interact
+/// with edwards25519.Point coordinates.
+///
+/// This type works similarly to math/big.Int, except that the operations
+/// return a new value instead of setting a receiver.
+///
+/// The zero value is a valid zero element.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Element {
+    // An element t represents the integer
+    //     t.l0 + t.l1*2^51 + t.l2*2^102 + t.l3*2^153 + t.l4*2^204
+    //
+    // Between operations, all limbs are expected to be lower than 2^52.
+    pub(crate) l0: u64,
+    pub(crate) l1: u64,
+    pub(crate) l2: u64,
+    pub(crate) l3: u64,
+    pub(crate) l4: u64,
+}
+
+pub(super) const MASK_LOW_51_BITS: u64 = (1 << 51) - 1;
+
+const FE_ZERO: Element = Element {
+    l0: 0,
+    l1: 0,
+    l2: 0,
+    l3: 0,
+    l4: 0,
+};
+
+const FE_ONE: Element = Element {
+    l0: 1,
+    l1: 0,
+    l2: 0,
+    l3: 0,
+    l4: 0,
+};
+
+/// SQRT_M1 is 2^((p-1)/4), which squared is equal to -1 by Euler's Criterion.
+const SQRT_M1: Element = Element {
+    l0: 1718705420411056,
+    l1: 234908883556509,
+    l2: 2233514472574048,
+    l3: 2117202627021982,
+    l4: 765476049583133,
+};
+
+impl Element {
+    /// zero returns the element 0.
+    pub const fn zero() -> Element {
+        FE_ZERO
+    }
+
+    /// one returns the element 1.
+    pub const fn one() -> Element {
+        FE_ONE
+    }
+
+    /// reduce reduces v modulo 2^255 - 19 and returns it.
+    fn reduce(&self) -> Element {
+        let mut v = *self;
+        carry_propagate(&mut v);
+
+        // After the light reduction we now have a field element representation
+        // v < 2^255 + 2^13 * 19, but need v < 2^255 - 19.
+
+        // If v >= 2^255 - 19, then v + 19 >= 2^255, which would overflow 2^255 - 1,
+        // generating a carry. That is, c will be 0 if v < 2^255 - 19, and 1 otherwise.
+        let mut c = (v.l0 + 19) >> 51;
+        c = (v.l1 + c) >> 51;
+        c = (v.l2 + c) >> 51;
+        c = (v.l3 + c) >> 51;
+        c = (v.l4 + c) >> 51;
+
+        // If v < 2^255 - 19 and c = 0, this will be a no-op. Otherwise, it's
+        // effectively applying the reduction identity to the carry.
+        v.l0 += 19 * c;
+
+        v.l1 += v.l0 >> 51;
+        v.l0 &= MASK_LOW_51_BITS;
+        v.l2 += v.l1 >> 51;
+        v.l1 &= MASK_LOW_51_BITS;
+        v.l3 += v.l2 >> 51;
+        v.l2 &= MASK_LOW_51_BITS;
+        v.l4 += v.l3 >> 51;
+        v.l3 &= MASK_LOW_51_BITS;
+        // no additional carry
+        v.l4 &= MASK_LOW_51_BITS;
+
+        v
+    }
+
+    /// add returns v + b.
+    pub fn add(&self, b: &Element) -> Element {
+        let mut v = Element {
+            l0: self.l0 + b.l0,
+            l1: self.l1 + b.l1,
+            l2: self.l2 + b.l2,
+            l3: self.l3 + b.l3,
+            l4: self.l4 + b.l4,
+        };
+        carry_propagate(&mut v);
+        v
+    }
+
+    /// subtract returns v - b.
+    pub fn subtract(&self, b: &Element) -> Element {
+        // We first add 2 * p, to guarantee the subtraction won't underflow, and
+        // then subtract b (which can be up to 2^255 + 2^13 * 19).
+        let mut v = Element {
+            l0: (self.l0 + 0xFFFFFFFFFFFDA) - b.l0,
+            l1: (self.l1 + 0xFFFFFFFFFFFFE) - b.l1,
+            l2: (self.l2 + 0xFFFFFFFFFFFFE) - b.l2,
+            l3: (self.l3 + 0xFFFFFFFFFFFFE) - b.l3,
+            l4: (self.l4 + 0xFFFFFFFFFFFFE) - b.l4,
+        };
+        carry_propagate(&mut v);
+        v
+    }
+
+    /// negate returns -v.
+    pub fn negate(&self) -> Element {
+        FE_ZERO.subtract(self)
+    }
+
+    /// invert returns 1/v mod p.
+    /// If v == 0, invert returns 0.
+    pub fn invert(&self) -> Element {
+        // Inversion is implemented as exponentiation with exponent p − 2. It uses the
+        // same sequence of 255 squarings and 11 multiplications as [Curve25519].
+        let z = self;
+
+        let z2 = z.square(); // 2
+        let mut t = z2.square(); // 4
+        t = t.square(); // 8
+        let z9 = t.multiply(z); // 9
+        let z11 = z9.multiply(&z2); // 11
+        t = z11.square(); // 22
+        let z2_5_0 = t.multiply(&z9); // 31 = 2^5 - 2^0
+
+        t = z2_5_0.square(); // 2^6 - 2^1
+        for _ in 0..4 {
+            t = t.square(); // 2^10 - 2^5
+        }
+        let z2_10_0 = t.multiply(&z2_5_0); // 2^10 - 2^0
+
+        t = z2_10_0.square(); // 2^11 - 2^1
+        for _ in 0..9 {
+            t = t.square(); // 2^20 - 2^10
+        }
+        let z2_20_0 = t.multiply(&z2_10_0); // 2^20 - 2^0
+
+        t = z2_20_0.square(); // 2^21 - 2^1
+        for _ in 0..19 {
+            t = t.square(); // 2^40 - 2^20
+        }
+        t = t.multiply(&z2_20_0); // 2^40 - 2^0
+
+        t = t.square(); // 2^41 - 2^1
+        for _ in 0..9 {
+            t = t.square(); // 2^50 - 2^10
+        }
+        let z2_50_0 = t.multiply(&z2_10_0); // 2^50 - 2^0
+
+        t = z2_50_0.square(); // 2^51 - 2^1
+        for _ in 0..49 {
+            t = t.square(); // 2^100 - 2^50
+        }
+        let z2_100_0 = t.multiply(&z2_50_0); // 2^100 - 2^0
+
+        t = z2_100_0.square(); // 2^101 - 2^1
+        for _ in 0..99 {
+            t = t.square(); // 2^200 - 2^100
+        }
+        t = t.multiply(&z2_100_0); // 2^200 - 2^0
+
+        t = t.square(); // 2^201 - 2^1
+        for _ in 0..49 {
+            t = t.square(); // 2^250 - 2^50
+        }
+        t = t.multiply(&z2_50_0); // 2^250 - 2^0
+
+        t = t.square(); // 2^251 - 2^1
+        t = t.square(); // 2^252 - 2^2
+        t = t.square(); // 2^253 - 2^3
+        t = t.square(); // 2^254 - 2^4
+        t = t.square(); // 2^255 - 2^5
+
+        t.multiply(&z11) // 2^255 - 21
+    }
+
+    /// set_bytes returns the element x, where x is a 32-byte little-endian
+    /// encoding. If x is not of the right length, set_bytes returns an error.
+    ///
+    /// Consistent with RFC 7748, the most significant bit (the high bit of the
+    /// last byte) is ignored, and non-canonical values (2^255-19 through 2^255-1)
+    /// are accepted. Note that this is laxer than specified by RFC 8032, but
+    /// consistent with most Ed25519 implementations.
+    pub fn set_bytes(x: &[u8]) -> Result<Element, errors::ErrorStaticString> {
+        if x.len() != 32 {
+            return Err(errors::new_static(
+                "edwards25519: invalid field element input size",
+            ));
+        }
+
+        Ok(Element {
+            // Bits 0:51 (bytes 0:8, bits 0:64, shift 0, mask 51).
+            l0: LITTLE_ENDIAN.uint64(&x[0..8]) & MASK_LOW_51_BITS,
+            // Bits 51:102 (bytes 6:14, bits 48:112, shift 3, mask 51).
+            l1: (LITTLE_ENDIAN.uint64(&x[6..14]) >> 3) & MASK_LOW_51_BITS,
+            // Bits 102:153 (bytes 12:20, bits 96:160, shift 6, mask 51).
+            l2: (LITTLE_ENDIAN.uint64(&x[12..20]) >> 6) & MASK_LOW_51_BITS,
+            // Bits 153:204 (bytes 19:27, bits 152:216, shift 1, mask 51).
+            l3: (LITTLE_ENDIAN.uint64(&x[19..27]) >> 1) & MASK_LOW_51_BITS,
+            // Bits 204:255 (bytes 24:32, bits 192:256, shift 12, mask 51).
+            // Note: not bytes 25:33, shift 4, to avoid overread.
+            l4: (LITTLE_ENDIAN.uint64(&x[24..32]) >> 12) & MASK_LOW_51_BITS,
+        })
+    }
+
+    /// bytes returns the canonical 32-byte little-endian encoding of v.
+    pub fn bytes(&self) -> [u8; 32] {
+        let t = self.reduce();
+        let mut out = [0; 32];
+        let mut buf = [0; 8];
+        for (i, l) in [t.l0, t.l1, t.l2, t.l3, t.l4].into_iter().enumerate() {
+            let bits_offset = i * 51;
+            LITTLE_ENDIAN.put_uint64(&mut buf, l << (bits_offset % 8));
+            for (i, bb) in buf.iter().enumerate() {
+                let off = bits_offset / 8 + i;
+                if off >= out.len() {
+                    break;
+                }
+                out[off] |= bb;
+            }
+        }
+        out
+    }
+
+    /// equal returns 1 if v and u are equal, and 0 otherwise.
+    pub fn equal(&self, u: &Element) -> isize {
+        subtle::constant_time_compare(&u.bytes(), &self.bytes())
+    }
+
+    /// select returns a if cond == 1, and b if cond == 0.
+    pub fn select(a: &Element, b: &Element, cond: isize) -> Element {
+        let m = mask_64_bits(cond);
+        Element {
+            l0: (m & a.l0) | (!m & b.l0),
+            l1: (m & a.l1) | (!m & b.l1),
+            l2: (m & a.l2) | (!m & b.l2),
+            l3: (m & a.l3) | (!m & b.l3),
+            l4: (m & a.l4) | (!m & b.l4),
+        }
+    }
+
+    /// swap swaps v and u if cond == 1 or leaves them unchanged if cond == 0.
+    pub fn swap(&mut self, u: &mut Element, cond: isize) {
+        let m = mask_64_bits(cond);
+        let mut t = m & (self.l0 ^ u.l0);
+        self.l0 ^= t;
+        u.l0 ^= t;
+        t = m & (self.l1 ^ u.l1);
+        self.l1 ^= t;
+        u.l1 ^= t;
+        t = m & (self.l2 ^ u.l2);
+        self.l2 ^= t;
+        u.l2 ^= t;
+        t = m & (self.l3 ^ u.l3);
+        self.l3 ^= t;
+        u.l3 ^= t;
+        t = m & (self.l4 ^ u.l4);
+        self.l4 ^= t;
+        u.l4 ^= t;
+    }
+
+    /// is_negative returns 1 if v is negative, and 0 otherwise.
+    pub fn is_negative(&self) -> isize {
+        (self.bytes()[0] & 1) as isize
+    }
+
+    /// absolute returns |v|.
+    pub fn absolute(&self) -> Element {
+        Element::select(&self.negate(), self, self.is_negative())
+    }
+
+    /// multiply returns v * y.
+    pub fn multiply(&self, y: &Element) -> Element {
+        fe_mul(self, y)
+    }
+
+    /// square returns v * v.
+    pub fn square(&self) -> Element {
+        fe_square(self)
+    }
+
+    /// mult32 returns v * y.
+    pub fn mult32(&self, y: u32) -> Element {
+        let (x0lo, x0hi) = mul51(self.l0, y);
+        let (x1lo, x1hi) = mul51(self.l1, y);
+        let (x2lo, x2hi) = mul51(self.l2, y);
+        let (x3lo, x3hi) = mul51(self.l3, y);
+        let (x4lo, x4hi) = mul51(self.l4, y);
+        // The hi portions are going to be only 32 bits, plus any previous excess,
+        // so we can skip the carry propagation.
+        Element {
+            l0: x0lo + 19 * x4hi, // carried over per the reduction identity
+            l1: x1lo + x0hi,
+            l2: x2lo + x1hi,
+            l3: x3lo + x2hi,
+            l4: x4lo + x3hi,
+        }
+    }
+
+    /// pow22523 returns x^((p-5)/8). (p-5)/8 is 2^252-3.
+    pub fn pow22523(&self) -> Element {
+        let x = self;
+        let mut t0 = x.square(); // x^2
+        let mut t1 = t0.square(); // x^4
+        t1 = t1.square(); // x^8
+        t1 = x.multiply(&t1); // x^9
+        t0 = t0.multiply(&t1); // x^11
+        t0 = t0.square(); // x^22
+        t0 = t1.multiply(&t0); // x^31
+        t1 = t0.square(); // x^62
+        for _ in 1..5 {
+            // x^992
+            t1 = t1.square();
+        }
+        t0 = t1.multiply(&t0); // x^1023 -> 1023 = 2^10 - 1
+        t1 = t0.square(); // 2^11 - 2
+        for _ in 1..10 {
+            // 2^20 - 2^10
+            t1 = t1.square();
+        }
+        t1 = t1.multiply(&t0); // 2^20 - 1
+        let mut t2 = t1.square(); // 2^21 - 2
+        for _ in 1..20 {
+            // 2^40 - 2^20
+            t2 = t2.square();
+        }
+        t1 = t2.multiply(&t1); // 2^40 - 1
+        t1 = t1.square(); // 2^41 - 2
+        for _ in 1..10 {
+            // 2^50 - 2^10
+            t1 = t1.square();
+        }
+        t0 = t1.multiply(&t0); // 2^50 - 1
+        t1 = t0.square(); // 2^51 - 2
+        for _ in 1..50 {
+            // 2^100 - 2^50
+            t1 = t1.square();
+        }
+        t1 = t1.multiply(&t0); // 2^100 - 1
+        t2 = t1.square(); // 2^101 - 2
+        for _ in 1..100 {
+            // 2^200 - 2^100
+            t2 = t2.square();
+        }
+        t1 = t2.multiply(&t1); // 2^200 - 1
+        t1 = t1.square(); // 2^201 - 2
+        for _ in 1..50 {
+            // 2^250 - 2^50
+            t1 = t1.square();
+        }
+        t0 = t1.multiply(&t0); // 2^250 - 1
+        t0 = t0.square(); // 2^251 - 2
+        t0 = t0.square(); // 2^252 - 4
+        t0.multiply(x) // 2^252 - 3 -> x^(2^252-3)
+    }
+
+    /// sqrt_ratio returns the non-negative square root of the ratio of u and v.
+    ///
+    /// If u/v is square, sqrt_ratio returns r and 1. If u/v is not square, sqrt_ratio
+    /// returns r according to Section 4.3 of draft-irtf-cfrg-ristretto255-decaf448-00,
+    /// and 0.
+    pub fn sqrt_ratio(u: &Element, v: &Element) -> (Element, isize) {
+        // r = (u * v3) * (u * v7)^((p-5)/8)
+        let v2 = v.square();
+        let uv3 = u.multiply(&v2.multiply(v));
+        let uv7 = uv3.multiply(&v2.square());
+        let rr = uv3.multiply(&uv7.pow22523());
+        let check = v.multiply(&rr.square()); // check = v * r^2
+
+        let u_neg = u.negate();
+        let correct_sign_sqrt = check.equal(u);
+        let flipped_sign_sqrt = check.equal(&u_neg);
+        let flipped_sign_sqrt_i = check.equal(&u_neg.multiply(&SQRT_M1));
+
+        let r_prime = rr.multiply(&SQRT_M1); // r_prime = SQRT_M1 * r
+
+        // r = CT_SELECT(r_prime IF flipped_sign_sqrt | flipped_sign_sqrt_i ELSE r)
+        let rr = Element::select(&r_prime, &rr, flipped_sign_sqrt | flipped_sign_sqrt_i);
+
+        // Choose the nonnegative square root.
+        (rr.absolute(), correct_sign_sqrt | flipped_sign_sqrt)
+    }
+}
+
+/// mask_64_bits returns 0xffffffff if cond is 1, and 0 otherwise.
+fn mask_64_bits(cond: isize) -> u64 {
+    !((cond as u64).wrapping_sub(1))
+}
+
+/// mul51 returns lo + hi * 2⁵¹ = a * b.
+fn mul51(a: u64, b: u32) -> (u64, u64) {
+    let m = (a as u128) * (b as u128);
+    let (mh, ml) = ((m >> 64) as u64, m as u64);
+    let lo = ml & MASK_LOW_51_BITS;
+    let hi = (mh << 13) | (ml >> 51);
+    (lo, hi)
+}
@@ -0,0 +1,320 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright (c) 2017 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::Element;
+use crate::encoding::hex;
+use crate::math::rand;
+
+/// generate_field_element returns a random reduced field element.
+fn generate_field_element<S: rand::Source>(r: &mut rand::Rand<S>) -> Element {
+    let mut b = [0; 32];
+    for x in b.iter_mut() {
+        *x = r.int63() as u8;
+    }
+    Element::set_bytes(&b).unwrap()
+}
+
+/// generate_weird_field_element returns a random element with limbs
+/// close to 2^52, as allowed by the Element invariant.
+fn generate_weird_field_element<S: rand::Source>(r: &mut rand::Rand<S>) -> Element {
+    const WEIRD_LIMBS: [u64; 9] = [
+        0,
+        1,
+        19 - 1,
+        19,
+        0x2aaaaaaaaaaaa,
+        0x5555555555555,
+        (1 << 51) - 20,
+        (1 << 51) - 19,
+        (1 << 51) - 1,
+    ];
+    let mut limb = || WEIRD_LIMBS[r.intn(9) as usize] + (r.intn(2) as u64) * (1 << 51);
+    Element {
+        l0: limb(),
+        l1: limb(),
+        l2: limb(),
+        l3: limb(),
+        l4: limb(),
+    }
+}
+
+fn decode_hex(s: &str) -> Element {
+    let (b, err) = hex::decode_string(s);
+    assert!(err.is_none());
+    Element::set_bytes(&b).unwrap()
+}
+
+#[test]
+fn test_multiply_distributes_over_add() {
+    let mut r = rand::Rand::new(rand::new_source(1));
+    for i in 0..1000 {
+        let (x, y, z) = if i % 2 == 0 {
+            (
+                generate_field_element(&mut r),
+                generate_field_element(&mut r),
+                generate_field_element(&mut r),
+            )
+        } else {
+            (
+                generate_weird_field_element(&mut r),
+                generate_weird_field_element(&mut r),
+                generate_weird_field_element(&mut r),
+            )
+        };
+
+        // Compute t1 = (x+y)*z
+        let t1 = x.add(&y).multiply(&z);
+
+        // Compute t2 = x*z + y*z
+        let t2 = x.multiply(&z).add(&y.multiply(&z));
+
+        assert_eq!(t1.equal(&t2), 1);
+    }
+}
+
+#[test]
+fn test_square_is_multiply() {
+    let mut r = rand::Rand::new(rand::new_source(1));
+    for _ in 0..1000 {
+        let x = generate_weird_field_element(&mut r);
+        assert_eq!(x.square().equal(&x.multiply(&x)), 1);
+    }
+}
+
+#[test]
+fn test_set_bytes_round_trip() {
+    let mut r = rand::Rand::new(rand::new_source(1));
+    for _ in 0..1000 {
+        let mut b = [0; 32];
+        for x in b.iter_mut() {
+            *x = r.int63() as u8;
+        }
+        // Values with the high bit cleared are canonical
+        // with overwhelming probability.
+        b[31] &= 127;
+        let fe = Element::set_bytes(&b).unwrap();
+        assert_eq!(fe.bytes(), b);
+    }
+
+    // Check some fixed vectors from dalek
+    struct FeRTTest {
+        fe: Element,
+        b: [u8; 32],
+    }
+    let tests = [
+        FeRTTest {
+            fe: Element {
+                l0: 358744748052810,
+                l1: 1691584618240980,
+                l2: 977650209285361,
+                l3: 1429865912637724,
+                l4: 560044844278676,
+            },
+            b: [
+                74, 209, 69, 197, 70, 70, 161, 222, 56, 226, 229, 19, 112, 60, 25, 92, 187, 74,
+                222, 56, 50, 153, 51, 233, 40, 74, 57, 6, 160, 185, 213, 31,
+            ],
+        },
+        FeRTTest {
+            fe: Element {
+                l0: 84926274344903,
+                l1: 473620666599931,
+                l2: 365590438845504,
+                l3: 1028470286882429,
+                l4: 2146499180330972,
+            },
+            b: [
+                199, 23, 106, 112, 61, 77, 216, 79, 186, 60, 11, 118, 13, 16, 103, 15, 42, 32, 83,
+                250, 44, 57, 204, 198, 78, 199, 253, 119, 146, 172, 3, 122,
+            ],
+        },
+    ];
+
+    for tt in &tests {
+        let b = tt.fe.bytes();
+        assert_eq!(b, tt.b);
+        let fe = Element::set_bytes(&tt.b).unwrap();
+        assert_eq!(fe.equal(&tt.fe), 1);
+    }
+}
+
+#[test]
+fn test_bytes_big_equivalence() {
+    // Products and inverses computed with arbitrary precision integers.
+    let tests = [
+        (
+            "38b4e652e44da7f2370d9e260e27136550a4a3a6d07f5c0c332f8b1212841f69",
+            "2b902f8911e81818f8c99d5d5d9831957504d90e945de2e8f54ee781e63a7b1b",
+            "1dd55beefc5da817fc4ba68639834f2f3e133c3058b7aaa8988665d89aebe60f",
+            "80b59f83ce3d2630be7bcd6a8e60032a7fcfd54e42ee64fb45885a7295ba5b23",
+        ),
+        (
+            "d85099095aa300165a67036f9b540d6b8f0be21124179c3dd9f7381767b78846",
+            "264aad6cb6dd210faf94acd3cf92c190237cb11f5d108cf2593026399c59b850",
+            "99d8eee4ca6c1795a4537b9b9d45feeed00d5a33e66778aeab9b4bffabfc5b71",
+            "e1be68650ba79e6d4de2c3f88bb5775400b16ceeff1ed166b57f7b0f57096f17",
+        ),
+    ];
+    for (x, y, xy, x_inv) in tests {
+        let x = decode_hex(x);
+        let y = decode_hex(y);
+        assert_eq!(x.multiply(&y).equal(&decode_hex(xy)), 1);
+        assert_eq!(x.invert().equal(&decode_hex(x_inv)), 1);
+    }
+}
+
+#[test]
+fn test_equal() {
+    let x = Element::one();
+    let y = Element::zero().add(&Element::one()).add(&Element::one());
+    assert_eq!(x.equal(&x), 1);
+    assert_eq!(x.equal(&y), 0);
+}
+
+#[test]
+fn test_invert() {
+    let x = Element {
+        l0: 1,
+        l1: 1,
+        l2: 1,
+        l3: 1,
+        l4: 1,
+    };
+    let one = Element::one();
+    let xinv = x.invert();
+    assert_eq!(x.multiply(&xinv).equal(&one), 1);
+
+    let mut r = rand::Rand::new(rand::new_source(1));
+    for _ in 0..100 {
+        let x = generate_field_element(&mut r);
+        let xinv = x.invert();
+        assert_eq!(x.multiply(&xinv).equal(&one), 1);
+    }
+
+    let zero = Element::zero();
+    assert_eq!(
+        zero.invert().equal(&zero),
+        1,
+        "inverting zero did not return zero"
+    );
+}
+
+#[test]
+fn test_select_swap() {
+    let a = Element {
+        l0: 358744748052810,
+        l1: 1691584618240980,
+        l2: 977650209285361,
+        l3: 1429865912637724,
+        l4: 560044844278676,
+    };
+    let b = Element {
+        l0: 84926274344903,
+        l1: 473620666599931,
+        l2: 365590438845504,
+        l3: 1028470286882429,
+        l4: 2146499180330972,
+    };
+
+    let c = Element::select(&a, &b, 1);
+    let d = Element::select(&a, &b, 0);
+    assert!(c.equal(&a) == 1 && d.equal(&b) == 1, "select failed");
+
+    let (mut c, mut d) = (c, d);
+    c.swap(&mut d, 0);
+    assert!(c.equal(&a) == 1 && d.equal(&b) == 1, "swap failed");
+
+    c.swap(&mut d, 1);
+    assert!(c.equal(&b) == 1 && d.equal(&a) == 1, "swap failed");
+}
+
+#[test]
+fn test_mult32() {
+    let mut r = rand::Rand::new(rand::new_source(1));
+    for _ in 0..1000 {
+        let x = generate_weird_field_element(&mut r);
+        let y = r.uint32();
+        let t1 = x.mult32(y);
+        let ty = Element {
+            l0: y as u64,
+            ..Element::zero()
+        };
+        let t2 = x.multiply(&ty);
+        assert_eq!(t1.equal(&t2), 1);
+    }
+}
+
+#[test]
+fn test_sqrt_ratio() {
+    // From draft-irtf-cfrg-ristretto255-decaf448-00, Appendix A.4.
+    struct Test {
+        u: &'static str,
+        v: &'static str,
+        was_square: isize,
+        r: &'static str,
+    }
+    let tests = [
+        // If u is 0, the function is defined to return (0, TRUE), even if v
+        // is zero. Note that where used in this package, the denominator v
+        // is never zero.
+        Test {
+            u: "0000000000000000000000000000000000000000000000000000000000000000",
+            v: "0000000000000000000000000000000000000000000000000000000000000000",
+            was_square: 1,
+            r: "0000000000000000000000000000000000000000000000000000000000000000",
+        },
+        // 0/1 == 0²
+        Test {
+            u: "0000000000000000000000000000000000000000000000000000000000000000",
+            v: "0100000000000000000000000000000000000000000000000000000000000000",
+            was_square: 1,
+            r: "0000000000000000000000000000000000000000000000000000000000000000",
+        },
+        // If u is non-zero and v is zero, defined to return (0, FALSE).
+        Test {
+            u: "0100000000000000000000000000000000000000000000000000000000000000",
+            v: "0000000000000000000000000000000000000000000000000000000000000000",
+            was_square: 0,
+            r: "0000000000000000000000000000000000000000000000000000000000000000",
+        },
+        // 2/1 is not square in this field.
+        Test {
+            u: "0200000000000000000000000000000000000000000000000000000000000000",
+            v: "0100000000000000000000000000000000000000000000000000000000000000",
+            was_square: 0,
+            r: "3c5ff1b5d8e4113b871bd052f9e7bcd0582804c266ffb2d4f4203eb07fdb7c54",
+        },
+        // 4/1 == 2²
+        Test {
+            u: "0400000000000000000000000000000000000000000000000000000000000000",
+            v: "0100000000000000000000000000000000000000000000000000000000000000",
+            was_square: 1,
+            r: "0200000000000000000000000000000000000000000000000000000000000000",
+        },
+        // 1/4 == (2⁻¹)² == (2ᵖ⁻²)² per Euler's theorem
+        Test {
+            u: "0100000000000000000000000000000000000000000000000000000000000000",
+            v: "0400000000000000000000000000000000000000000000000000000000000000",
+            was_square: 1,
+            r: "f6ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff3f",
+        },
+    ];
+
+    for (i, tt) in tests.iter().enumerate() {
+        let u = decode_hex(tt.u);
+        let v = decode_hex(tt.v);
+        let want = decode_hex(tt.r);
+        let (got, was_square) = Element::sqrt_ratio(&u, &v);
+        assert_eq!(
+            got.equal(&want),
+            1,
+            "#{}: got r = {:?}, want {}",
+            i,
+            got,
+            tt.r
+        );
+        assert_eq!(was_square, tt.was_square, "#{}: wrong was_square", i);
+    }
+}
@@ -0,0 +1,14 @@
+// Copyright 2023 The rust-ggstd authors.
+// Copyright (c) 2017 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! Package field implements fast arithmetic modulo 2^255-19.
+
+mod fe;
+mod fe_generic;
+
+pub use fe::Element;
+
+#[cfg(test)]
+mod fe_test;
@@ -0,0 +1,32 @@
+// Copyright 2023 The rust-ggstd authors.
+// Copyright (c) 2017 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! Package edwards25519 implements group logic for the twisted Edwards curve
+//!
+//! ```text
+//! -x^2 + y^2 = 1 + -(121665/121666)*x^2*y^2
+//! ```
+//!
+//! This is better known as the Edwards curve equivalent to Curve25519, and is
+//! the curve used by the Ed25519 signature scheme.
+//!
+//! Most users don't need this package, and should instead use crate::crypto::ed25519
+//! for signatures.
+
+mod edwards25519;
+pub mod field;
+mod scalar;
+mod scalarmult;
+mod tables;
+
+pub use edwards25519::{new_generator_point, new_identity_point, Point};
+pub use scalar::{new_scalar, Scalar};
+
+#[cfg(test)]
+mod edwards25519_test;
+#[cfg(test)]
+mod scalar_test;
+#[cfg(test)]
+mod scalarmult_test;
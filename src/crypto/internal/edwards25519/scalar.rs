@@ -0,0 +1,398 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright (c) 2016 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use crate::crypto::subtle;
+use crate::encoding::binary::{ByteOrder, LITTLE_ENDIAN};
+use crate::errors;
+
+/// A Scalar is an integer modulo
+///
+/// ```text
+/// l = 2^252 + 27742317777372353535851937790883648493
+/// ```
+///
+/// which is the prime order of the edwards25519 group.
+///
+/// This type works similarly to math/big.Int, except that the operations
+/// return a new value instead of setting a receiver.
+///
+/// The zero value is a valid zero element.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Scalar {
+    /// s is the scalar as five 52-bit little-endian limbs, fully reduced modulo l.
+    s: [u64; 5],
+}
+
+const MASK_52_BITS: u64 = (1 << 52) - 1;
+
+/// L is the order l in 52-bit limbs.
+const L: [u64; 5] = [
+    0x0002631a5cf5d3ed,
+    0x000dea2f79cd6581,
+    0x000000000014def9,
+    0x0000000000000000,
+    0x0000100000000000,
+];
+
+/// LFACTOR is -l⁻¹ mod 2^52, used by the Montgomery reduction.
+const LFACTOR: u64 = 0x51da312547e1b;
+
+/// R is 2^260 mod l, the Montgomery constant.
+const R: [u64; 5] = [
+    0x000f48bd6721e6ed,
+    0x0003bab5ac67e45a,
+    0x000fffffeb35e51b,
+    0x000fffffffffffff,
+    0x00000fffffffffff,
+];
+
+/// RR is R² mod l.
+const RR: [u64; 5] = [
+    0x0009d265e952d13b,
+    0x000d63c715bea69f,
+    0x0005be65cb687604,
+    0x0003dceec73d217f,
+    0x000009411b7c309a,
+];
+
+/// new_scalar returns a new zero Scalar.
+pub fn new_scalar() -> Scalar {
+    Scalar::default()
+}
+
+impl Scalar {
+    /// multiply_add returns x * y + z mod l, where x is the receiver.
+    /// It is equivalent to using multiply and then add.
+    pub fn multiply_add(&self, y: &Scalar, z: &Scalar) -> Scalar {
+        self.multiply(y).add(z)
+    }
+
+    /// add returns x + y mod l.
+    pub fn add(&self, y: &Scalar) -> Scalar {
+        // The limbs of x and y are below 2^52, so the sum
+        // fits without carries out of the top limb.
+        let mut sum = [0; 5];
+        let mut carry = 0;
+        for (i, s) in sum.iter_mut().enumerate() {
+            carry = self.s[i] + y.s[i] + (carry >> 52);
+            *s = carry & MASK_52_BITS;
+        }
+        Scalar { s: sub(&sum, &L) }
+    }
+
+    /// subtract returns x - y mod l.
+    pub fn subtract(&self, y: &Scalar) -> Scalar {
+        Scalar {
+            s: sub(&self.s, &y.s),
+        }
+    }
+
+    /// negate returns -x mod l.
+    pub fn negate(&self) -> Scalar {
+        Scalar::default().subtract(self)
+    }
+
+    /// multiply returns x * y mod l.
+    pub fn multiply(&self, y: &Scalar) -> Scalar {
+        // The Montgomery product of x and y is x * y / R, so a second
+        // Montgomery multiplication by R² brings it back to x * y.
+        let xy = montgomery_reduce(&mul_internal(&self.s, &y.s));
+        Scalar {
+            s: montgomery_reduce(&mul_internal(&xy, &RR)),
+        }
+    }
+
+    /// set_uniform_bytes returns x mod l, where x is a 64-byte little-endian integer.
+    /// If x is not of the right length, set_uniform_bytes returns an error.
+    ///
+    /// set_uniform_bytes can be used to get a uniformly distributed value given
+    /// 64 uniformly distributed random bytes.
+    pub fn set_uniform_bytes(x: &[u8]) -> Result<Scalar, errors::ErrorStaticString> {
+        if x.len() != 64 {
+            return Err(errors::new_static(
+                "edwards25519: invalid SetUniformBytes input length",
+            ));
+        }
+
+        let mut words = [0; 8];
+        for (i, w) in words.iter_mut().enumerate() {
+            *w = LITTLE_ENDIAN.uint64(&x[i * 8..]);
+        }
+
+        // Split x into the low 260 bits and the high 252 bits,
+        // so that x = hi * 2^260 + lo.
+        let lo = [
+            words[0] & MASK_52_BITS,
+            ((words[0] >> 52) | (words[1] << 12)) & MASK_52_BITS,
+            ((words[1] >> 40) | (words[2] << 24)) & MASK_52_BITS,
+            ((words[2] >> 28) | (words[3] << 36)) & MASK_52_BITS,
+            ((words[3] >> 16) | (words[4] << 48)) & MASK_52_BITS,
+        ];
+        let hi = [
+            (words[4] >> 4) & MASK_52_BITS,
+            ((words[4] >> 56) | (words[5] << 8)) & MASK_52_BITS,
+            ((words[5] >> 44) | (words[6] << 20)) & MASK_52_BITS,
+            ((words[6] >> 32) | (words[7] << 32)) & MASK_52_BITS,
+            words[7] >> 20,
+        ];
+
+        // lo * R / R = lo mod l, and hi * R² / R = hi * 2^260 mod l.
+        let lo = montgomery_reduce(&mul_internal(&lo, &R));
+        let hi = montgomery_reduce(&mul_internal(&hi, &RR));
+        Ok(Scalar { s: lo }.add(&Scalar { s: hi }))
+    }
+
+    /// set_canonical_bytes returns x, where x is a 32-byte little-endian encoding of
+    /// a scalar. If x is not a canonical encoding of a scalar, set_canonical_bytes
+    /// returns an error.
+    pub fn set_canonical_bytes(x: &[u8]) -> Result<Scalar, errors::ErrorStaticString> {
+        if x.len() != 32 {
+            return Err(errors::new_static("invalid scalar length"));
+        }
+        if !is_reduced(x) {
+            return Err(errors::new_static("invalid scalar encoding"));
+        }
+
+        let mut words = [0; 4];
+        for (i, w) in words.iter_mut().enumerate() {
+            *w = LITTLE_ENDIAN.uint64(&x[i * 8..]);
+        }
+        Ok(Scalar {
+            s: [
+                words[0] & MASK_52_BITS,
+                ((words[0] >> 52) | (words[1] << 12)) & MASK_52_BITS,
+                ((words[1] >> 40) | (words[2] << 24)) & MASK_52_BITS,
+                ((words[2] >> 28) | (words[3] << 36)) & MASK_52_BITS,
+                words[3] >> 16,
+            ],
+        })
+    }
+
+    /// set_bytes_with_clamping applies the buffer pruning described in RFC 8032,
+    /// Section 5.1.5 (also known as clamping) and returns the result. The input
+    /// must be 32 bytes, and it is not modified. If x is not of the right length,
+    /// set_bytes_with_clamping returns an error.
+    ///
+    /// Note that since Scalar values are always reduced modulo the prime order of
+    /// the curve, the resulting value will not preserve any of the cofactor-clearing
+    /// properties that clamping is meant to provide. It will however work as
+    /// expected as long as it is applied to points on the prime order subgroup, like
+    /// in Ed25519. In fact, it is lost to history why RFC 8032 adopted the
+    /// irrelevant RFC 7748 clamping, but it is now required for compatibility.
+    pub fn set_bytes_with_clamping(x: &[u8]) -> Result<Scalar, errors::ErrorStaticString> {
+        // The description above omits the purpose of the high bits of the clamping
+        // for brevity, but those are also lost to reductions, and are also
+        // irrelevant to edwards25519 as they protect against a specific
+        // implementation bug that was once observed in a generic Montgomery ladder.
+        if x.len() != 32 {
+            return Err(errors::new_static(
+                "edwards25519: invalid SetBytesWithClamping input length",
+            ));
+        }
+
+        // We need to use the wide reduction from set_uniform_bytes, since clamping
+        // sets the 2^254 bit, making the value higher than the order.
+        let mut wide_bytes = [0; 64];
+        wide_bytes[..32].copy_from_slice(x);
+        wide_bytes[0] &= 248;
+        wide_bytes[31] &= 63;
+        wide_bytes[31] |= 64;
+        Scalar::set_uniform_bytes(&wide_bytes)
+    }
+
+    /// bytes returns the canonical 32-byte little-endian encoding of s.
+    pub fn bytes(&self) -> [u8; 32] {
+        let mut out = [0; 32];
+        let mut acc: u128 = 0;
+        let mut acc_bits = 0;
+        let mut i = 0;
+        for limb in self.s {
+            acc |= (limb as u128) << acc_bits;
+            acc_bits += 52;
+            while acc_bits >= 8 && i < out.len() {
+                out[i] = acc as u8;
+                acc >>= 8;
+                acc_bits -= 8;
+                i += 1;
+            }
+        }
+        if i < out.len() {
+            out[i] = acc as u8;
+        }
+        out
+    }
+
+    /// equal returns 1 if s and t are equal, and 0 otherwise.
+    pub fn equal(&self, t: &Scalar) -> isize {
+        subtle::constant_time_compare(&self.bytes(), &t.bytes())
+    }
+
+    /// non_adjacent_form computes a width-w non-adjacent form for this scalar.
+    ///
+    /// w must be between 2 and 8, or non_adjacent_form will panic.
+    pub(super) fn non_adjacent_form(&self, w: usize) -> [i8; 256] {
+        // This implementation is adapted from the one
+        // in curve25519-dalek and is documented there:
+        // https://github.com/dalek-cryptography/curve25519-dalek/blob/f630041af28e9a405255f98a8a93adca18e4315b/src/scalar.rs#L800-L871
+        let b = self.bytes();
+        if b[31] > 127 {
+            panic!("scalar has high bit set illegally");
+        }
+        if !(2..=8).contains(&w) {
+            panic!("w must be between 2 and 8");
+        }
+
+        let mut digits = [0u64; 5];
+        for (i, d) in digits.iter_mut().take(4).enumerate() {
+            *d = LITTLE_ENDIAN.uint64(&b[i * 8..]);
+        }
+
+        let width = 1u64 << w;
+        let window_mask = width - 1;
+
+        let mut naf = [0i8; 256];
+        let mut pos = 0;
+        let mut carry = 0;
+        while pos < 256 {
+            let index_u64 = pos / 64;
+            let index_bit = pos % 64;
+            let bit_buf = if index_bit < 64 - w {
+                // This window's bits are contained in a single u64
+                digits[index_u64] >> index_bit
+            } else {
+                // Combine the current 64 bits with bits from the next 64
+                (digits[index_u64] >> index_bit) | (digits[1 + index_u64] << (64 - index_bit))
+            };
+
+            // Add carry into the current window
+            let window = carry + (bit_buf & window_mask);
+
+            if window & 1 == 0 {
+                // If the window value is even, preserve the carry and continue.
+                // Why is the carry preserved?
+                // If carry == 0 and window & 1 == 0,
+                //    then the next carry should be 0
+                // If carry == 1 and window & 1 == 0,
+                //    then bit_buf & 1 == 1 so the next carry should be 1
+                pos += 1;
+                continue;
+            }
+
+            if window < width / 2 {
+                carry = 0;
+                naf[pos] = window as i8;
+            } else {
+                carry = 1;
+                naf[pos] = (window as i8).wrapping_sub(width as i8);
+            }
+
+            pos += w;
+        }
+        naf
+    }
+
+    /// signed_radix16 returns the digits of the scalar in radix 16,
+    /// each in the range [-8, 8].
+    pub(super) fn signed_radix16(&self) -> [i8; 64] {
+        let b = self.bytes();
+        if b[31] > 127 {
+            panic!("scalar has high bit set illegally");
+        }
+
+        let mut digits = [0i8; 64];
+
+        // Compute unsigned radix-16 digits:
+        for i in 0..32 {
+            digits[2 * i] = (b[i] & 15) as i8;
+            digits[2 * i + 1] = ((b[i] >> 4) & 15) as i8;
+        }
+
+        // Recenter coefficients:
+        for i in 0..63 {
+            let carry = (digits[i] + 8) >> 4;
+            digits[i] -= carry << 4;
+            digits[i + 1] += carry;
+        }
+
+        digits
+    }
+}
+
+/// is_reduced returns whether the given scalar in 32-byte little endian encoded
+/// form is reduced modulo l.
+fn is_reduced(s: &[u8]) -> bool {
+    const SCALAR_MINUS_ONE_BYTES: [u8; 32] = [
+        236, 211, 245, 92, 26, 99, 18, 88, 214, 156, 247, 162, 222, 249, 222, 20, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 16,
+    ];
+    for i in (0..32).rev() {
+        if s[i] > SCALAR_MINUS_ONE_BYTES[i] {
+            return false;
+        } else if s[i] < SCALAR_MINUS_ONE_BYTES[i] {
+            return true;
+        }
+    }
+    true
+}
+
+/// sub returns a - b, adding l back if the difference is negative.
+/// a and b must be in the range [0, 2^260) with a - b in (-l, l).
+fn sub(a: &[u64; 5], b: &[u64; 5]) -> [u64; 5] {
+    let mut difference = [0; 5];
+    let mut borrow: u64 = 0;
+    for i in 0..5 {
+        borrow = a[i].wrapping_sub(b[i] + (borrow >> 63));
+        difference[i] = borrow & MASK_52_BITS;
+    }
+
+    // Conditionally add l if the difference is negative.
+    let underflow_mask = ((borrow >> 63) ^ 1).wrapping_sub(1);
+    let mut carry: u64 = 0;
+    for i in 0..5 {
+        carry = (carry >> 52) + difference[i] + (L[i] & underflow_mask);
+        difference[i] = carry & MASK_52_BITS;
+    }
+    difference
+}
+
+/// mul_internal returns the 9 column sums of the product of a and b.
+fn mul_internal(a: &[u64; 5], b: &[u64; 5]) -> [u128; 9] {
+    let mut z = [0u128; 9];
+    for i in 0..5 {
+        for j in 0..5 {
+            z[i + j] += (a[i] as u128) * (b[j] as u128);
+        }
+    }
+    z
+}
+
+/// montgomery_reduce returns limbs / R mod l, where limbs is the
+/// output of mul_internal for inputs below l.
+fn montgomery_reduce(limbs: &[u128; 9]) -> [u64; 5] {
+    let mut t = *limbs;
+
+    // Add multiples of l to make the low limbs divisible by 2^52, one at a
+    // time, so that the value becomes divisible by R.
+    for i in 0..5 {
+        let n = (t[i] as u64).wrapping_mul(LFACTOR) & MASK_52_BITS;
+        for j in 0..5 {
+            t[i + j] += (n as u128) * (L[j] as u128);
+        }
+        t[i + 1] += t[i] >> 52;
+    }
+
+    // Dividing by R drops the five low limbs.
+    let mut r = [0; 5];
+    let mut carry = 0;
+    for i in 0..4 {
+        let sum = t[5 + i] + carry;
+        r[i] = (sum as u64) & MASK_52_BITS;
+        carry = sum >> 52;
+    }
+    r[4] = carry as u64;
+
+    // The result may be greater than l, so attempt to subtract l.
+    sub(&r, &L)
+}
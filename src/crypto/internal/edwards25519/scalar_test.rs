@@ -0,0 +1,188 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright (c) 2016 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::scalar::Scalar;
+use crate::encoding::hex;
+use crate::math::rand;
+
+/// scalar_from_hex decodes a canonical little-endian scalar encoding.
+fn scalar_from_hex(s: &str) -> Scalar {
+    let (b, err) = hex::decode_string(s);
+    assert!(err.is_none());
+    Scalar::set_canonical_bytes(&b).unwrap()
+}
+
+/// small_scalar returns the scalar x, for -128 <= x < 128.
+fn small_scalar(x: i8) -> Scalar {
+    let mut b = [0; 32];
+    b[0] = x.unsigned_abs();
+    let s = Scalar::set_canonical_bytes(&b).unwrap();
+    if x < 0 {
+        s.negate()
+    } else {
+        s
+    }
+}
+
+/// generate_scalar returns a random uniformly distributed scalar.
+pub(super) fn generate_scalar<S: rand::Source>(r: &mut rand::Rand<S>) -> Scalar {
+    let mut b = [0; 64];
+    for x in b.iter_mut() {
+        *x = r.int63() as u8;
+    }
+    Scalar::set_uniform_bytes(&b).unwrap()
+}
+
+const SCALAR_MINUS_ONE: &str = "ecd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010";
+
+#[test]
+fn test_scalar_set_canonical_bytes() {
+    let s = scalar_from_hex(SCALAR_MINUS_ONE);
+    assert_eq!(hex::encode_to_string(&s.bytes()), SCALAR_MINUS_ONE);
+    assert_eq!(s.equal(&small_scalar(-1)), 1);
+
+    // l itself and anything with the high bits set is not canonical.
+    for input in [
+        "edd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010",
+        "0000000000000000000000000000000000000000000000000000000000000080",
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+    ] {
+        let (b, _) = hex::decode_string(input);
+        assert!(
+            Scalar::set_canonical_bytes(&b).is_err(),
+            "accepted {}",
+            input
+        );
+    }
+    assert!(Scalar::set_canonical_bytes(&[0; 31]).is_err());
+}
+
+#[test]
+fn test_scalar_set_uniform_bytes() {
+    let s = Scalar::set_uniform_bytes(&[0xff; 64]).unwrap();
+    assert_eq!(
+        hex::encode_to_string(&s.bytes()),
+        "000f9c44e31106a447938568a71b0ed065bef517d273ecce3d9a307c1b419903"
+    );
+    assert!(Scalar::set_uniform_bytes(&[0; 32]).is_err());
+
+    // l + 1 reduces to 1.
+    let (mut b, _) = hex::decode_string(SCALAR_MINUS_ONE);
+    b[0] += 2;
+    b.resize(64, 0);
+    let s = Scalar::set_uniform_bytes(&b).unwrap();
+    assert_eq!(s.equal(&small_scalar(1)), 1);
+}
+
+#[test]
+fn test_scalar_set_bytes_with_clamping() {
+    let (b, _) =
+        hex::decode_string("633d368491364dc9cd4c1bf891b1d59460face1644813240a313e61f2c88216e");
+    let s = Scalar::set_bytes_with_clamping(&b).unwrap();
+    assert_eq!(
+        hex::encode_to_string(&s.bytes()),
+        "d2457356f3e3deb8c79f4d265ad69b1760face1644813240a313e61f2c88210e"
+    );
+
+    let s = Scalar::set_bytes_with_clamping(&[0; 32]).unwrap();
+    // 2^254 mod l
+    let mut want = [0; 32];
+    want[31] = 0x40;
+    let s2 = Scalar::set_uniform_bytes(&[want, [0; 32]].concat()).unwrap();
+    assert_eq!(s.equal(&s2), 1);
+    assert!(Scalar::set_bytes_with_clamping(&[0; 64]).is_err());
+}
+
+#[test]
+fn test_scalar_arithmetic() {
+    let a = scalar_from_hex("c7711cc7711cc701519e60495a07d250c7ce034aad2076b31100000000000000");
+    let b = scalar_from_hex("711cc7711cc7711c877357229687d1ffdcba34286daa0d5ae4716a7e20010000");
+    let c = scalar_from_hex("55555555555555550312b728a56c686f17a52e2e3041d55e6207510000000000");
+
+    assert_eq!(
+        hex::encode_to_string(&a.multiply_add(&b, &c).bytes()),
+        "c89dca3db06892dc03eeb0d0a260621d6182b94cf794ae2d6987178acef5800e"
+    );
+    assert_eq!(
+        hex::encode_to_string(&a.subtract(&b).bytes()),
+        "43294bb26fb8673da0c700caa279df65ea13cf21407668592d8e9581dffeff0f"
+    );
+    assert_eq!(
+        hex::encode_to_string(&a.negate().bytes()),
+        "2662d995a8464b5685fe965984f20cc43831fcb552df894ceeffffffffffff0f"
+    );
+}
+
+#[test]
+fn test_scalar_properties() {
+    let mut r = rand::Rand::new(rand::new_source(1));
+    let zero = Scalar::default();
+    let one = small_scalar(1);
+    for _ in 0..200 {
+        let x = generate_scalar(&mut r);
+        let y = generate_scalar(&mut r);
+        let z = generate_scalar(&mut r);
+
+        // Encoding round trip.
+        let x2 = Scalar::set_canonical_bytes(&x.bytes()).unwrap();
+        assert_eq!(x.equal(&x2), 1);
+
+        assert_eq!(x.add(&y).equal(&y.add(&x)), 1);
+        assert_eq!(x.multiply(&y).equal(&y.multiply(&x)), 1);
+        assert_eq!(x.add(&x.negate()).equal(&zero), 1);
+        assert_eq!(x.subtract(&y).equal(&x.add(&y.negate())), 1);
+        assert_eq!(x.multiply(&one).equal(&x), 1);
+        assert_eq!(x.multiply_add(&y, &z).equal(&x.multiply(&y).add(&z)), 1);
+
+        // x * (y + z) = x*y + x*z
+        let t1 = x.multiply(&y.add(&z));
+        let t2 = x.multiply(&y).add(&x.multiply(&z));
+        assert_eq!(t1.equal(&t2), 1);
+    }
+}
+
+#[test]
+fn test_scalar_non_adjacent_form() {
+    let mut r = rand::Rand::new(rand::new_source(1));
+    for _ in 0..50 {
+        let x = generate_scalar(&mut r);
+        for w in [5, 8] {
+            let naf = x.non_adjacent_form(w);
+            let mut acc = Scalar::default();
+            let mut last_nonzero = None;
+            for (i, &d) in naf.iter().enumerate().rev() {
+                acc = acc.add(&acc).add(&small_scalar(d));
+                if d != 0 {
+                    assert!(d % 2 != 0, "even digit {} at {}", d, i);
+                    assert!((d as i32).abs() < (1 << (w - 1)));
+                    // Nonzero digits are at least w apart.
+                    if let Some(j) = last_nonzero {
+                        assert!(j - i >= w);
+                    }
+                    last_nonzero = Some(i);
+                }
+            }
+            assert_eq!(acc.equal(&x), 1, "w = {}", w);
+        }
+    }
+}
+
+#[test]
+fn test_scalar_signed_radix16() {
+    let mut r = rand::Rand::new(rand::new_source(1));
+    for _ in 0..50 {
+        let x = generate_scalar(&mut r);
+        let digits = x.signed_radix16();
+        let mut acc = Scalar::default();
+        for &d in digits.iter().rev() {
+            assert!((-8..=8).contains(&d));
+            for _ in 0..4 {
+                acc = acc.add(&acc);
+            }
+            acc = acc.add(&small_scalar(d));
+        }
+        assert_eq!(acc.equal(&x), 1);
+    }
+}
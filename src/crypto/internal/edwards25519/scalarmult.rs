@@ -0,0 +1,183 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright (c) 2019 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::edwards25519::{new_generator_point, new_identity_point, Point, ProjP1xP1, ProjP2};
+use super::scalar::Scalar;
+use super::tables::{AffineLookupTable, NafLookupTable5, NafLookupTable8, ProjLookupTable};
+use std::sync::OnceLock;
+
+/// basepoint_table is a set of 32 AffineLookupTables, where table i is generated
+/// from 256i * basepoint. It is precomputed the first time it's used.
+fn basepoint_table() -> &'static [AffineLookupTable; 32] {
+    static TABLE: OnceLock<Box<[AffineLookupTable; 32]>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut p = new_generator_point();
+        Box::new(std::array::from_fn(|_| {
+            let table = AffineLookupTable::from_p3(&p);
+            for _ in 0..8 {
+                p = p.add(&p);
+            }
+            table
+        }))
+    })
+}
+
+/// multiply_by_16 returns 16 * v, computed with four doublings.
+fn multiply_by_16(v: &Point) -> ProjP1xP1 {
+    let mut tmp2 = ProjP2::from_p3(v); // tmp2 =    v in P2 coords
+    let mut tmp1 = ProjP1xP1::double(&tmp2); // tmp1 =  2*v in P1xP1 coords
+    tmp2 = ProjP2::from_p1xp1(&tmp1); // tmp2 =  2*v in P2 coords
+    tmp1 = ProjP1xP1::double(&tmp2); // tmp1 =  4*v in P1xP1 coords
+    tmp2 = ProjP2::from_p1xp1(&tmp1); // tmp2 =  4*v in P2 coords
+    tmp1 = ProjP1xP1::double(&tmp2); // tmp1 =  8*v in P1xP1 coords
+    tmp2 = ProjP2::from_p1xp1(&tmp1); // tmp2 =  8*v in P2 coords
+    ProjP1xP1::double(&tmp2) // 16*v in P1xP1 coords
+}
+
+impl Point {
+    /// scalar_base_mult returns x * B, where B is the canonical generator.
+    ///
+    /// The scalar multiplication is done in constant time.
+    pub fn scalar_base_mult(x: &Scalar) -> Point {
+        let basepoint_table = basepoint_table();
+
+        // Write x = sum(x_i * 16^i) so  x*B = sum( B*x_i*16^i )
+        // as described in the Ed25519 paper
+        //
+        // Group even and odd coefficients
+        // x*B     = x_0*16^0*B + x_2*16^2*B + ... + x_62*16^62*B
+        //         + x_1*16^1*B + x_3*16^3*B + ... + x_63*16^63*B
+        // x*B     = x_0*16^0*B + x_2*16^2*B + ... + x_62*16^62*B
+        //    + 16*( x_1*16^0*B + x_3*16^2*B + ... + x_63*16^62*B)
+        //
+        // We use a lookup table for each i to get x_i*16^(2*i)*B
+        // and do four doublings to multiply by 16.
+        let digits = x.signed_radix16();
+
+        // Accumulate the odd components first
+        let mut v = new_identity_point();
+        for i in (1..64).step_by(2) {
+            let multiple = basepoint_table[i / 2].select_into(digits[i]);
+            v = Point::from_p1xp1(&ProjP1xP1::add_affine(&v, &multiple));
+        }
+
+        // Multiply by 16
+        v = Point::from_p1xp1(&multiply_by_16(&v)); // now v = 16*(odd components)
+
+        // Accumulate the even components
+        for i in (0..64).step_by(2) {
+            let multiple = basepoint_table[i / 2].select_into(digits[i]);
+            v = Point::from_p1xp1(&ProjP1xP1::add_affine(&v, &multiple));
+        }
+
+        v
+    }
+
+    /// scalar_mult returns x * q.
+    ///
+    /// The scalar multiplication is done in constant time.
+    pub fn scalar_mult(x: &Scalar, q: &Point) -> Point {
+        let table = ProjLookupTable::from_p3(q);
+
+        // Write x = sum(x_i * 16^i)
+        // so  x*Q = sum( Q*x_i*16^i )
+        //         = Q*x_0 + 16*(Q*x_1 + 16*( ... + Q*x_63) ... )
+        //           <------compute inside out---------
+        //
+        // We use the lookup table to get the x_i*Q values
+        // and do four doublings to compute 16*Q
+        let digits = x.signed_radix16();
+
+        // Unwrap first loop iteration to save computing 16*identity
+        let multiple = table.select_into(digits[63]);
+        let mut tmp1 = ProjP1xP1::add(&new_identity_point(), &multiple); // tmp1 = x_63*Q in P1xP1 coords
+        for i in (0..63).rev() {
+            let v = Point::from_p1xp1(&multiply_by_16(&Point::from_p1xp1(&tmp1))); // v = 16*(prev) in P3 coords
+            let multiple = table.select_into(digits[i]);
+            tmp1 = ProjP1xP1::add(&v, &multiple); // tmp1 = x_i*Q + 16*(prev) in P1xP1 coords
+        }
+        Point::from_p1xp1(&tmp1)
+    }
+
+    /// var_time_double_scalar_base_mult returns a * A + b * B, where B is the
+    /// canonical generator.
+    ///
+    /// Execution time depends on the inputs.
+    #[allow(non_snake_case)]
+    pub fn var_time_double_scalar_base_mult(a: &Scalar, A: &Point, b: &Scalar) -> Point {
+        // Similarly to the single variable-base approach, we compute
+        // digits and use them with a lookup table.  However, because
+        // we are allowed to do variable-time operations, we don't
+        // need constant-time lookups or constant-time digit
+        // computations.
+        //
+        // So we use a non-adjacent form of some width w instead of
+        // radix 16.  This is like a binary representation (one digit
+        // for each binary place) but we allow the digits to grow in
+        // magnitude up to 2^{w-1} so that the nonzero digits are as
+        // sparse as possible.  Intuitively, this "condenses" the
+        // "mass" of the scalar onto sparse coefficients (meaning
+        // fewer additions).
+
+        let basepoint_naf_table = basepoint_naf_table();
+        let a_table = NafLookupTable5::from_p3(A);
+        // Because the basepoint is fixed, we can use a wider NAF
+        // corresponding to a bigger table.
+        let a_naf = a.non_adjacent_form(5);
+        let b_naf = b.non_adjacent_form(8);
+
+        // Find the first nonzero coefficient.
+        let mut i = 255;
+        while i > 0 && a_naf[i] == 0 && b_naf[i] == 0 {
+            i -= 1;
+        }
+
+        let mut tmp2 = ProjP2::zero();
+
+        // Move from high to low bits, doubling the accumulator
+        // at each iteration and checking whether there is a nonzero
+        // coefficient to look up a multiple of.
+        loop {
+            let mut tmp1 = ProjP1xP1::double(&tmp2);
+
+            // Only update v if we have a nonzero coeff to add in.
+            if a_naf[i] > 0 {
+                let v = Point::from_p1xp1(&tmp1);
+                let mult_a = a_table.select_into(a_naf[i]);
+                tmp1 = ProjP1xP1::add(&v, &mult_a);
+            } else if a_naf[i] < 0 {
+                let v = Point::from_p1xp1(&tmp1);
+                let mult_a = a_table.select_into(-a_naf[i]);
+                tmp1 = ProjP1xP1::sub(&v, &mult_a);
+            }
+
+            if b_naf[i] > 0 {
+                let v = Point::from_p1xp1(&tmp1);
+                let mult_b = basepoint_naf_table.select_into(b_naf[i]);
+                tmp1 = ProjP1xP1::add_affine(&v, &mult_b);
+            } else if b_naf[i] < 0 {
+                let v = Point::from_p1xp1(&tmp1);
+                let mult_b = basepoint_naf_table.select_into(-b_naf[i]);
+                tmp1 = ProjP1xP1::sub_affine(&v, &mult_b);
+            }
+
+            tmp2 = ProjP2::from_p1xp1(&tmp1);
+
+            if i == 0 {
+                break;
+            }
+            i -= 1;
+        }
+
+        Point::from_p2(&tmp2)
+    }
+}
+
+/// basepoint_naf_table is the NafLookupTable8 for the basepoint.
+/// It is precomputed the first time it's used.
+fn basepoint_naf_table() -> &'static NafLookupTable8 {
+    static TABLE: OnceLock<Box<NafLookupTable8>> = OnceLock::new();
+    TABLE.get_or_init(|| Box::new(NafLookupTable8::from_p3(&new_generator_point())))
+}
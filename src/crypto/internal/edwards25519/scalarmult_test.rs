@@ -0,0 +1,78 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright (c) 2019 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::scalar_test::generate_scalar;
+use super::{new_generator_point, new_identity_point, Point, Scalar};
+use crate::math::rand;
+
+#[test]
+fn test_scalar_mult_small_scalars() {
+    let z = Scalar::default();
+    let b = new_generator_point();
+    assert_eq!(Point::scalar_mult(&z, &b).equal(&new_identity_point()), 1);
+    assert_eq!(Point::scalar_base_mult(&z).equal(&new_identity_point()), 1);
+
+    let mut one = [0; 32];
+    one[0] = 1;
+    let one = Scalar::set_canonical_bytes(&one).unwrap();
+    assert_eq!(Point::scalar_mult(&one, &b).equal(&b), 1);
+    assert_eq!(Point::scalar_base_mult(&one).equal(&b), 1);
+
+    let minus_one = one.negate();
+    assert_eq!(Point::scalar_mult(&minus_one, &b).equal(&b.negate()), 1);
+    assert_eq!(Point::scalar_base_mult(&minus_one).equal(&b.negate()), 1);
+}
+
+#[test]
+fn test_scalar_mult_matches_base_mult() {
+    let mut r = rand::Rand::new(rand::new_source(1));
+    let b = new_generator_point();
+    for _ in 0..20 {
+        let x = generate_scalar(&mut r);
+        let p = Point::scalar_mult(&x, &b);
+        let q = Point::scalar_base_mult(&x);
+        assert_eq!(p.equal(&q), 1);
+    }
+}
+
+#[test]
+fn test_scalar_mult_distributes_over_add() {
+    let mut r = rand::Rand::new(rand::new_source(1));
+    let b = new_generator_point();
+    for _ in 0..20 {
+        let x = generate_scalar(&mut r);
+        let y = generate_scalar(&mut r);
+        let z = x.add(&y);
+        let p = Point::scalar_base_mult(&x);
+        let q = Point::scalar_base_mult(&y);
+        assert_eq!(Point::scalar_base_mult(&z).equal(&p.add(&q)), 1);
+
+        // x * (y * B) = (x * y) * B
+        let lhs = Point::scalar_mult(&x, &q);
+        let rhs = Point::scalar_mult(&x.multiply(&y), &b);
+        assert_eq!(lhs.equal(&rhs), 1);
+    }
+}
+
+#[test]
+fn test_var_time_double_base_mult_matches_base_mult() {
+    let mut r = rand::Rand::new(rand::new_source(1));
+    let b = new_generator_point();
+    for _ in 0..20 {
+        let x = generate_scalar(&mut r);
+        let y = generate_scalar(&mut r);
+        let a = Point::scalar_base_mult(&generate_scalar(&mut r));
+
+        let check = Point::scalar_mult(&x, &a).add(&Point::scalar_base_mult(&y));
+        let got = Point::var_time_double_scalar_base_mult(&x, &a, &y);
+        assert_eq!(got.equal(&check), 1);
+
+        let got = Point::var_time_double_scalar_base_mult(&x, &b, &Scalar::default());
+        assert_eq!(got.equal(&Point::scalar_base_mult(&x)), 1);
+    }
+    let z = Scalar::default();
+    let got = Point::var_time_double_scalar_base_mult(&z, &b, &z);
+    assert_eq!(got.equal(&new_identity_point()), 1);
+}
@@ -0,0 +1,143 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright (c) 2019 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::edwards25519::{AffineCached, Point, ProjCached, ProjP1xP1};
+use crate::crypto::subtle;
+
+/// A dynamic lookup table for variable-base, constant-time scalar muls.
+pub(super) struct ProjLookupTable {
+    points: [ProjCached; 8],
+}
+
+/// A precomputed lookup table for fixed-base, constant-time scalar muls.
+pub(super) struct AffineLookupTable {
+    points: [AffineCached; 8],
+}
+
+/// A dynamic lookup table for variable-base, variable-time scalar muls.
+pub(super) struct NafLookupTable5 {
+    points: [ProjCached; 8],
+}
+
+/// A precomputed lookup table for fixed-base, variable-time scalar muls.
+pub(super) struct NafLookupTable8 {
+    points: [AffineCached; 64],
+}
+
+// Constructors.
+
+impl ProjLookupTable {
+    /// Builds a lookup table at runtime. Fast.
+    pub(super) fn from_p3(q: &Point) -> ProjLookupTable {
+        // Goal: v.points[i] = (i+1)*Q, i.e., Q, 2Q, ..., 8Q
+        // This allows lookup of -8Q, ..., -Q, 0, Q, ..., 8Q
+        let mut v = ProjLookupTable {
+            points: [ProjCached::default(); 8],
+        };
+        v.points[0] = ProjCached::from_p3(q);
+        for i in 0..7 {
+            // Compute (i+1)*Q + Q = (i+2)*Q
+            let tmp = Point::from_p1xp1(&ProjP1xP1::add(q, &v.points[i]));
+            v.points[i + 1] = ProjCached::from_p3(&tmp);
+        }
+        v
+    }
+
+    /// select_into returns x*Q, where -8 <= x <= 8, in constant time.
+    pub(super) fn select_into(&self, x: i8) -> ProjCached {
+        // Compute xabs = |x|
+        let xmask = x >> 7;
+        let xabs = ((x + xmask) ^ xmask) as u8;
+
+        let mut dest = ProjCached::zero();
+        for j in 1..=8 {
+            // Set dest = j*Q if |x| = j
+            let cond = subtle::constant_time_byte_eq(xabs, j as u8);
+            dest = ProjCached::select(&self.points[j - 1], &dest, cond);
+        }
+        // Now dest = |x|*Q, conditionally negate to get x*Q
+        dest.cond_neg((xmask & 1) as isize);
+        dest
+    }
+}
+
+impl AffineLookupTable {
+    /// This is not optimised for speed; fixed-base tables should be precomputed.
+    pub(super) fn from_p3(q: &Point) -> AffineLookupTable {
+        // Goal: v.points[i] = (i+1)*Q, i.e., Q, 2Q, ..., 8Q
+        // This allows lookup of -8Q, ..., -Q, 0, Q, ..., 8Q
+        let mut v = AffineLookupTable {
+            points: [AffineCached::default(); 8],
+        };
+        v.points[0] = AffineCached::from_p3(q);
+        for i in 0..7 {
+            // Compute (i+1)*Q + Q = (i+2)*Q
+            let tmp = Point::from_p1xp1(&ProjP1xP1::add_affine(q, &v.points[i]));
+            v.points[i + 1] = AffineCached::from_p3(&tmp);
+        }
+        v
+    }
+
+    /// select_into returns x*Q, where -8 <= x <= 8, in constant time.
+    pub(super) fn select_into(&self, x: i8) -> AffineCached {
+        // Compute xabs = |x|
+        let xmask = x >> 7;
+        let xabs = ((x + xmask) ^ xmask) as u8;
+
+        let mut dest = AffineCached::zero();
+        for j in 1..=8 {
+            // Set dest = j*Q if |x| = j
+            let cond = subtle::constant_time_byte_eq(xabs, j as u8);
+            dest = AffineCached::select(&self.points[j - 1], &dest, cond);
+        }
+        // Now dest = |x|*Q, conditionally negate to get x*Q
+        dest.cond_neg((xmask & 1) as isize);
+        dest
+    }
+}
+
+impl NafLookupTable5 {
+    /// Builds a lookup table at runtime. Fast.
+    pub(super) fn from_p3(q: &Point) -> NafLookupTable5 {
+        // Goal: v.points[i] = (2*i+1)*Q, i.e., Q, 3Q, 5Q, ..., 15Q
+        // This allows lookup of -15Q, ..., -3Q, -Q, 0, Q, 3Q, ..., 15Q
+        let mut v = NafLookupTable5 {
+            points: [ProjCached::default(); 8],
+        };
+        v.points[0] = ProjCached::from_p3(q);
+        let q2 = q.add(q);
+        for i in 0..7 {
+            let tmp = Point::from_p1xp1(&ProjP1xP1::add(&q2, &v.points[i]));
+            v.points[i + 1] = ProjCached::from_p3(&tmp);
+        }
+        v
+    }
+
+    /// Given odd x with 0 < x < 2^4, return x*Q (in variable time).
+    pub(super) fn select_into(&self, x: i8) -> ProjCached {
+        self.points[(x / 2) as usize]
+    }
+}
+
+impl NafLookupTable8 {
+    /// This is not optimised for speed; fixed-base tables should be precomputed.
+    pub(super) fn from_p3(q: &Point) -> NafLookupTable8 {
+        let mut v = NafLookupTable8 {
+            points: [AffineCached::default(); 64],
+        };
+        v.points[0] = AffineCached::from_p3(q);
+        let q2 = q.add(q);
+        for i in 0..63 {
+            let tmp = Point::from_p1xp1(&ProjP1xP1::add_affine(&q2, &v.points[i]));
+            v.points[i + 1] = AffineCached::from_p3(&tmp);
+        }
+        v
+    }
+
+    /// Given odd x with 0 < x < 2^7, return x*Q (in variable time).
+    pub(super) fn select_into(&self, x: i8) -> AffineCached {
+        self.points[(x / 2) as usize]
+    }
+}
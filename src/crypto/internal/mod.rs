@@ -0,0 +1,4 @@
+// Copyright 2023 The rust-ggstd authors.
+// SPDX-License-Identifier: BSD-3-Clause
+
+pub mod edwards25519;
@@ -4,7 +4,8 @@
 // license that can be found in the LICENSE file.
 
 use crate::compat;
-use crate::encoding::binary::{self, ByteOrder};
+use crate::encoding::binary::{self, AppendByteOrder, ByteOrder};
+use crate::errors;
 use crate::hash;
 use std::io::Write;
 
@@ -43,9 +44,8 @@ impl Default for Digest {
 
 impl Digest {
     /// new returns a new Digest for computing the MD5 checksum.
-    // The Hash also
-    // implements encoding.BinaryMarshaler and encoding.BinaryUnmarshaler to
-    // marshal and unmarshal the internal state of the hash.
+    /// The Digest also implements marshal_binary and unmarshal_binary to
+    /// marshal and unmarshal the internal state of the hash.
     pub fn new() -> Self {
         Self {
             s: INIT_STATE,
@@ -82,49 +82,54 @@ impl Digest {
     }
 }
 
-// const (
-// 	magic         = "md5\x01"
-// 	marshaledSize = len(magic) + 4*4 + block_size + 8
-// )
-
-// fn (d *digest) MarshalBinary() ([]byte, error) {
-// 	b := make([]byte, 0, marshaledSize)
-// 	b = append(b, magic...)
-// 	b = binary.BigEndian.AppendUint32(b, d.s[0])
-// 	b = binary.BigEndian.AppendUint32(b, d.s[1])
-// 	b = binary.BigEndian.AppendUint32(b, d.s[2])
-// 	b = binary.BigEndian.AppendUint32(b, d.s[3])
-// 	b = append(b, d.x[..d.nx]...)
-// 	b = b[..len(b)+len(d.x)-d.nx] // already zero
-// 	b = binary.BigEndian.AppendUint64(b, d.len)
-// 	return b, nil
-// }
+const MAGIC: &[u8] = b"md5\x01";
+const MARSHALED_SIZE: usize = MAGIC.len() + 4 * 4 + BLOCK_SIZE + 8;
 
-// fn (d *digest) UnmarshalBinary(b []byte) error {
-// 	if len(b) < len(magic) || string(b[..len(magic)]) != magic {
-// 		return errors.New("crypto/md5: invalid hash state identifier")
-// 	}
-// 	if len(b) != marshaledSize {
-// 		return errors.New("crypto/md5: invalid hash state size")
-// 	}
-// 	b = b[len(magic)..]
-// 	b, d.s[0] = consumeUint32(b)
-// 	b, d.s[1] = consumeUint32(b)
-// 	b, d.s[2] = consumeUint32(b)
-// 	b, d.s[3] = consumeUint32(b)
-// 	b = b[copy(d.x[..], b)..]
-// 	b, d.len = consumeUint64(b)
-// 	d.nx = isize(d.len % block_size)
-// 	return nil
-// }
+impl Digest {
+    /// marshal_binary returns the internal state of the digest in the same
+    /// format as Go's encoding.BinaryMarshaler implementation,
+    /// so the state can be restored later with unmarshal_binary.
+    pub fn marshal_binary(&self) -> Vec<u8> {
+        let mut b = Vec::with_capacity(MARSHALED_SIZE);
+        b.extend_from_slice(MAGIC);
+        for s in self.s {
+            binary::BIG_ENDIAN.append_uint32(&mut b, s);
+        }
+        b.extend_from_slice(&self.x[..self.nx]);
+        b.resize(b.len() + BLOCK_SIZE - self.nx, 0);
+        binary::BIG_ENDIAN.append_uint64(&mut b, self.len);
+        b
+    }
 
-// fn consumeUint64(b []byte) ([]byte, u64) {
-// 	return b[8..], binary.BigEndian.Uint64(b[0:8])
-// }
+    /// unmarshal_binary restores the internal state of the digest
+    /// from the output of marshal_binary.
+    pub fn unmarshal_binary(&mut self, b: &[u8]) -> Result<(), errors::ErrorStaticString> {
+        if b.len() < MAGIC.len() || &b[..MAGIC.len()] != MAGIC {
+            return Err(errors::new_static(
+                "crypto/md5: invalid hash state identifier",
+            ));
+        }
+        if b.len() != MARSHALED_SIZE {
+            return Err(errors::new_static("crypto/md5: invalid hash state size"));
+        }
+        let mut b = &b[MAGIC.len()..];
+        for s in self.s.iter_mut() {
+            (b, *s) = consume_uint32(b);
+        }
+        b = &b[compat::copy(&mut self.x, b)..];
+        (_, self.len) = consume_uint64(b);
+        self.nx = (self.len % BLOCK_SIZE as u64) as usize;
+        Ok(())
+    }
+}
 
-// fn consumeUint32(b []byte) ([]byte, u32) {
-// 	return b[4..], binary.BigEndian.Uint32(b[0:4])
-// }
+fn consume_uint64(b: &[u8]) -> (&[u8], u64) {
+    (&b[8..], binary::BIG_ENDIAN.uint64(&b[0..8]))
+}
+
+fn consume_uint32(b: &[u8]) -> (&[u8], u32) {
+    (&b[4..], binary::BIG_ENDIAN.uint32(&b[0..4]))
+}
 
 impl hash::Hash for Digest {
     fn reset(&mut self) {
@@ -3,9 +3,12 @@
 
 pub mod aes;
 pub mod cipher;
+pub mod ed25519;
 pub mod hmac;
+pub mod internal;
 pub mod md5;
 pub mod rand;
 pub mod sha1;
 pub mod sha256;
+pub mod sha512;
 pub mod subtle;
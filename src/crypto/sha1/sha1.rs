@@ -4,7 +4,8 @@
 // license that can be found in the LICENSE file.
 
 use crate::compat;
-use crate::encoding::binary::{ByteOrder, BIG_ENDIAN};
+use crate::encoding::binary::{AppendByteOrder, ByteOrder, BIG_ENDIAN};
+use crate::errors;
 use crate::hash::{self, Hash};
 use crate::math::bits::rotate_left32;
 
@@ -34,10 +35,8 @@ pub struct Digest {
     len: u64,
 }
 
-// const (
-// 	magic         = "sha\x01"
-// 	marshaledSize = len(magic) + 5*4 + CHUNK + 8
-// )
+const MAGIC: &[u8] = b"sha\x01";
+const MARSHALED_SIZE: usize = MAGIC.len() + 5 * 4 + CHUNK + 8;
 
 impl Default for Digest {
     fn default() -> Self {
@@ -47,9 +46,8 @@ impl Default for Digest {
 
 impl Digest {
     /// new returns a new Digest computing the SHA1 checksum.
-    // The Hash also
-    // implements encoding.BinaryMarshaler and encoding.BinaryUnmarshaler to
-    // marshal and unmarshal the internal state of the hash.
+    /// The Digest also implements marshal_binary and unmarshal_binary to
+    /// marshal and unmarshal the internal state of the hash.
     pub fn new() -> Self {
         // 	if boringEnabled {
         // 		return boringNewSHA1()
@@ -185,51 +183,49 @@ impl Digest {
     // 	return digest
     // }
 
-    // fn MarshalBinary(&mut self) (&[u8], error) {
-    // 	b := make(&[u8], 0, marshaledSize)
-    // 	b = append(b, magic...)
-    // 	b = binary.BigEndian.AppendUint32(b, self.h[0])
-    // 	b = binary.BigEndian.AppendUint32(b, self.h[1])
-    // 	b = binary.BigEndian.AppendUint32(b, self.h[2])
-    // 	b = binary.BigEndian.AppendUint32(b, self.h[3])
-    // 	b = binary.BigEndian.AppendUint32(b, self.h[4])
-    // 	b = append(b, self.x[:self.nx]...)
-    // 	b = b[:len(b)+len(self.x)-self.nx] // already zero
-    // 	b = binary.BigEndian.AppendUint64(b, self.len)
-    // 	return b, nil
-    // }
+    /// marshal_binary returns the internal state of the digest in the same
+    /// format as Go's encoding.BinaryMarshaler implementation,
+    /// so the state can be restored later with unmarshal_binary.
+    pub fn marshal_binary(&self) -> Vec<u8> {
+        let mut b = Vec::with_capacity(MARSHALED_SIZE);
+        b.extend_from_slice(MAGIC);
+        for h in self.h {
+            BIG_ENDIAN.append_uint32(&mut b, h);
+        }
+        b.extend_from_slice(&self.x[..self.nx]);
+        b.resize(b.len() + CHUNK - self.nx, 0);
+        BIG_ENDIAN.append_uint64(&mut b, self.len);
+        b
+    }
 
-    // fn UnmarshalBinary(&mut self, b &[u8]) error {
-    // 	if len(b) < len(magic) || string(b[:len(magic)]) != magic {
-    // 		return errors.New("crypto/sha1: invalid hash state identifier")
-    // 	}
-    // 	if len(b) != marshaledSize {
-    // 		return errors.New("crypto/sha1: invalid hash state size")
-    // 	}
-    // 	b = b[len(magic)..]
-    // 	b, self.h[0] = consumeUint32(b)
-    // 	b, self.h[1] = consumeUint32(b)
-    // 	b, self.h[2] = consumeUint32(b)
-    // 	b, self.h[3] = consumeUint32(b)
-    // 	b, self.h[4] = consumeUint32(b)
-    // 	b = b[compat::copy(self.&x[..], b)..]
-    // 	b, self.len = consumeUint64(b)
-    // 	self.nx = isize(self.len % CHUNK)
-    // 	return nil
-    // }
+    /// unmarshal_binary restores the internal state of the digest
+    /// from the output of marshal_binary.
+    pub fn unmarshal_binary(&mut self, b: &[u8]) -> Result<(), errors::ErrorStaticString> {
+        if b.len() < MAGIC.len() || &b[..MAGIC.len()] != MAGIC {
+            return Err(errors::new_static(
+                "crypto/sha1: invalid hash state identifier",
+            ));
+        }
+        if b.len() != MARSHALED_SIZE {
+            return Err(errors::new_static("crypto/sha1: invalid hash state size"));
+        }
+        let mut b = &b[MAGIC.len()..];
+        for h in self.h.iter_mut() {
+            (b, *h) = consume_uint32(b);
+        }
+        b = &b[compat::copy(&mut self.x, b)..];
+        (_, self.len) = consume_uint64(b);
+        self.nx = (self.len % CHUNK as u64) as usize;
+        Ok(())
+    }
+}
 
-    // fn consumeUint64(b &[u8]) (&[u8], u64) {
-    // 	_ = b[7]
-    // 	x := u64(b[7]) | u64(b[6])<<8 | u64(b[5])<<16 | u64(b[4])<<24 |
-    // 		u64(b[3])<<32 | u64(b[2])<<40 | u64(b[1])<<48 | u64(b[0])<<56
-    // 	return b[8..], x
-    // }
+fn consume_uint64(b: &[u8]) -> (&[u8], u64) {
+    (&b[8..], BIG_ENDIAN.uint64(&b[0..8]))
+}
 
-    // fn consumeUint32(b &[u8]) (&[u8], u32) {
-    // 	_ = b[3]
-    // 	x := u32(b[3]) | u32(b[2])<<8 | u32(b[1])<<16 | u32(b[0])<<24
-    // 	return b[4..], x
-    // }
+fn consume_uint32(b: &[u8]) -> (&[u8], u32) {
+    (&b[4..], BIG_ENDIAN.uint32(&b[0..4]))
 }
 
 impl hash::Hash for Digest {
@@ -1,35 +1,90 @@
-// // Copyright 2016 The Go Authors. All rights reserved.
-// // Use of this source code is governed by a BSD-style
-// // license that can be found in the LICENSE file.
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2016 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
 
-// //go:build s390x
+use super::sha256::CHUNK;
+use super::sha256block::block_generic;
+use crate::encoding::hex;
+use crate::hash::Hash;
+use crate::math::rand;
+use std::io::Write;
 
-// package sha256
+type BlockFunc = fn(&mut [u32; 8], &[u8]);
 
-// import (
-// 	"fmt"
-// 	"io"
-// 	"testing"
-// )
+/// implementations returns the block functions usable on this CPU,
+/// starting with the generic one.
+fn implementations() -> Vec<(&'static str, BlockFunc)> {
+    let mut res: Vec<(&'static str, BlockFunc)> =
+        vec![("generic", block_generic), ("block", super::block)];
+    #[cfg(target_arch = "x86_64")]
+    {
+        use super::sha256block_amd64::{block_avx2, block_sha_ni, use_avx2, use_sha};
+        if use_sha() {
+            // Safety: the CPU features were detected.
+            res.push(("sha-ni", |h, p| unsafe { block_sha_ni(h, p) }));
+        }
+        if use_avx2() {
+            // Safety: the CPU features were detected.
+            res.push(("avx2", |h, p| unsafe { block_avx2(h, p) }));
+        }
+    }
+    res
+}
 
-// // Tests the fallback code path in case the optimized asm
-// // implementation cannot be used.
-// // See also TestBlockGeneric.
-// func TestGenericPath(t *testing.T) {
-// 	if useAsm == false {
-// 		t.Skipf("assembly implementation unavailable")
-// 	}
-// 	useAsm = false
-// 	defer func() { useAsm = true }()
-// 	c := New()
-// 	in := "ΑΒΓΔΕϜΖΗΘΙΚΛΜΝΞΟΠϺϘΡΣΤΥΦΧΨΩ"
-// 	gold := "e93d84ec2b22383123be9f713697fb25" +
-// 		"338c86e2f7d8d1ddc2d89d332dd9d76c"
-// 	if _, err := io.WriteString(c, in); err != nil {
-// 		t.Fatalf("could not write to c: %v", err)
-// 	}
-// 	out := fmt.Sprintf("%x", c.Sum(nil))
-// 	if out != gold {
-// 		t.Fatalf("mismatch: got %s, wanted %s", out, gold)
-// 	}
-// }
+// Tests the fallback code path in case the optimized asm
+// implementation cannot be used.
+// See also test_block_cross_check.
+#[test]
+fn test_generic_path() {
+    let input = "ΑΒΓΔΕϜΖΗΘΙΚΛΜΝΞΟΠϺϘΡΣΤΥΦΧΨΩ";
+    let gold = "e93d84ec2b22383123be9f713697fb25338c86e2f7d8d1ddc2d89d332dd9d76c";
+
+    let mut c = super::Digest::new();
+    c.write_all(input.as_bytes()).unwrap();
+    assert_eq!(hex::encode_to_string(&c.sum(&[])), gold);
+
+    // The input fits into a single padded block, which every
+    // implementation must hash to the same value.
+    let mut padded = [0; CHUNK];
+    padded[..input.len()].copy_from_slice(input.as_bytes());
+    padded[input.len()] = 0x80;
+    padded[CHUNK - 8..].copy_from_slice(&((input.len() as u64) << 3).to_be_bytes());
+    for (name, block) in implementations() {
+        // The SHA-256 initial hash value.
+        let mut h = [
+            0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+            0x5be0cd19,
+        ];
+        block(&mut h, &padded);
+        let out: Vec<u8> = h.iter().flat_map(|x| x.to_be_bytes()).collect();
+        assert_eq!(hex::encode_to_string(&out), gold, "{} mismatch", name);
+    }
+}
+
+// test_block_cross_check verifies that all the block implementations
+// available on this CPU agree with block_generic on random input.
+#[test]
+fn test_block_cross_check() {
+    let mut r = rand::Rand::new(rand::new_source(1));
+    for blocks in [0, 1, 2, 3, 4, 5, 8, 17] {
+        // A trailing partial block must be ignored.
+        for extra in [0, 1, 63] {
+            let mut p = vec![0; blocks * CHUNK + extra];
+            for b in p.iter_mut() {
+                *b = r.int63() as u8;
+            }
+            let mut h0 = [0; 8];
+            for x in h0.iter_mut() {
+                *x = r.uint32();
+            }
+            let mut want = h0;
+            block_generic(&mut want, &p);
+            for (name, block) in implementations() {
+                let mut h = h0;
+                block(&mut h, &p);
+                assert_eq!(h, want, "{}: mismatch for {} blocks", name, blocks);
+            }
+        }
+    }
+}
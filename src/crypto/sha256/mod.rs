@@ -8,8 +8,19 @@
 
 mod sha256;
 mod sha256block;
+#[cfg(target_arch = "x86_64")]
+mod sha256block_amd64;
+#[cfg(not(target_arch = "x86_64"))]
+mod sha256block_generic;
+
+#[cfg(target_arch = "x86_64")]
+use sha256block_amd64::block;
+#[cfg(not(target_arch = "x86_64"))]
+use sha256block_generic::block;
 
 pub use sha256::{sum224, sum256, Digest, BLOCK_SIZE, SIZE, SIZE224};
 
+#[cfg(test)]
+mod fallback_test;
 #[cfg(test)]
 mod sha256_test;
@@ -3,9 +3,10 @@
 // Use of this source code is governed by a BSD-style
 // license that can be found in the LICENSE file.
 
-use super::sha256block::block_generic;
+use super::block;
 use crate::compat;
-use crate::encoding::binary::{ByteOrder, BIG_ENDIAN};
+use crate::encoding::binary::{AppendByteOrder, ByteOrder, BIG_ENDIAN};
+use crate::errors;
 use crate::hash::{self, Hash};
 use std::io::Write;
 
@@ -60,10 +61,8 @@ impl Default for Digest {
 
 impl Digest {
     /// new returns a new Digest for computing the SHA256 checksum.
-    // The Hash
-    // also implements encoding.BinaryMarshaler and
-    // encoding.BinaryUnmarshaler to marshal and unmarshal the internal
-    // state of the hash.
+    /// The Digest also implements marshal_binary and unmarshal_binary
+    /// to marshal and unmarshal the internal state of the hash.
     pub fn new() -> Self {
         // if boring.Enabled {
         // 	return boring.NewSHA256()
@@ -98,14 +97,14 @@ impl Digest {
             let n = compat::copy(&mut self.x[self.nx..], p);
             self.nx += n;
             if self.nx == CHUNK {
-                block_generic(&mut self.h, &self.x[..]);
+                block(&mut self.h, &self.x[..]);
                 self.nx = 0
             }
             p = &p[n..];
         }
         if p.len() >= CHUNK {
             let n = p.len() & !(CHUNK - 1);
-            block_generic(&mut self.h, &p[..n]);
+            block(&mut self.h, &p[..n]);
             p = &p[n..];
         }
         if !p.is_empty() {
@@ -114,67 +113,62 @@ impl Digest {
     }
 }
 
-// const (
-// 	magic224      = "sha\x02"
-// 	magic256      = "sha\x03"
-// 	marshaledSize = len(magic256) + 8*4 + chunk + 8
-// )
-
-// fn (d *Digest) MarshalBinary() ([]u8, error) {
-// 	b := make([]u8, 0, marshaledSize)
-// 	if d.is224 {
-// 		b = append(b, magic224...)
-// 	} else {
-// 		b = append(b, magic256...)
-// 	}
-// 	b = binary::BigEndian.AppendUint32(b, d.h[0])
-// 	b = binary::BigEndian.AppendUint32(b, d.h[1])
-// 	b = binary::BigEndian.AppendUint32(b, d.h[2])
-// 	b = binary::BigEndian.AppendUint32(b, d.h[3])
-// 	b = binary::BigEndian.AppendUint32(b, d.h[4])
-// 	b = binary::BigEndian.AppendUint32(b, d.h[5])
-// 	b = binary::BigEndian.AppendUint32(b, d.h[6])
-// 	b = binary::BigEndian.AppendUint32(b, d.h[7])
-// 	b = append(b, d.x[..d.nx]...)
-// 	b = b[..b.len()+len(d.x)-d.nx] // already zero
-// 	b = binary::BigEndian.AppendUint64(b, d.len)
-// 	return b, nil
-// }
+const MAGIC224: &[u8] = b"sha\x02";
+const MAGIC256: &[u8] = b"sha\x03";
+const MARSHALED_SIZE: usize = MAGIC256.len() + 8 * 4 + CHUNK + 8;
 
-// fn (d *Digest) UnmarshalBinary(b []u8) error {
-// 	if b.len() < len(magic224) || (d.is224 && string(b[..len(magic224)]) != magic224) || (!d.is224 && string(b[..len(magic256)]) != magic256) {
-// 		return errors.New("crypto/sha256: invalid hash state identifier")
-// 	}
-// 	if b.len() != marshaledSize {
-// 		return errors.New("crypto/sha256: invalid hash state size")
-// 	}
-// 	b = b[len(magic224):]
-// 	b, d.h[0] = consumeUint32(b)
-// 	b, d.h[1] = consumeUint32(b)
-// 	b, d.h[2] = consumeUint32(b)
-// 	b, d.h[3] = consumeUint32(b)
-// 	b, d.h[4] = consumeUint32(b)
-// 	b, d.h[5] = consumeUint32(b)
-// 	b, d.h[6] = consumeUint32(b)
-// 	b, d.h[7] = consumeUint32(b)
-// 	b = b[copy(d.x[..], b):]
-// 	b, d.len = consumeUint64(b)
-// 	d.nx = isize(d.len % chunk)
-// 	return nil
-// }
+impl Digest {
+    /// marshal_binary returns the internal state of the digest in the same
+    /// format as Go's encoding.BinaryMarshaler implementation,
+    /// so the state can be restored later with unmarshal_binary.
+    pub fn marshal_binary(&self) -> Vec<u8> {
+        let mut b = Vec::with_capacity(MARSHALED_SIZE);
+        if self.is224 {
+            b.extend_from_slice(MAGIC224);
+        } else {
+            b.extend_from_slice(MAGIC256);
+        }
+        for h in self.h {
+            BIG_ENDIAN.append_uint32(&mut b, h);
+        }
+        b.extend_from_slice(&self.x[..self.nx]);
+        b.resize(b.len() + CHUNK - self.nx, 0);
+        BIG_ENDIAN.append_uint64(&mut b, self.len);
+        b
+    }
 
-// fn consumeUint64(b []u8) ([]u8, u64) {
-// 	_ = b[7]
-// 	x := u64(b[7]) | u64(b[6])<<8 | u64(b[5])<<16 | u64(b[4])<<24 |
-// 		u64(b[3])<<32 | u64(b[2])<<40 | u64(b[1])<<48 | u64(b[0])<<56
-// 	return b[8:], x
-// }
+    /// unmarshal_binary restores the internal state of the digest
+    /// from the output of marshal_binary.
+    pub fn unmarshal_binary(&mut self, b: &[u8]) -> Result<(), errors::ErrorStaticString> {
+        if b.len() < MAGIC224.len()
+            || (self.is224 && &b[..MAGIC224.len()] != MAGIC224)
+            || (!self.is224 && &b[..MAGIC256.len()] != MAGIC256)
+        {
+            return Err(errors::new_static(
+                "crypto/sha256: invalid hash state identifier",
+            ));
+        }
+        if b.len() != MARSHALED_SIZE {
+            return Err(errors::new_static("crypto/sha256: invalid hash state size"));
+        }
+        let mut b = &b[MAGIC224.len()..];
+        for h in self.h.iter_mut() {
+            (b, *h) = consume_uint32(b);
+        }
+        b = &b[compat::copy(&mut self.x, b)..];
+        (_, self.len) = consume_uint64(b);
+        self.nx = (self.len % CHUNK as u64) as usize;
+        Ok(())
+    }
+}
+
+fn consume_uint64(b: &[u8]) -> (&[u8], u64) {
+    (&b[8..], BIG_ENDIAN.uint64(&b[0..8]))
+}
 
-// fn consumeUint32(b []u8) ([u32; ]u8, ) {
-// 	_ = b[3]
-// 	xu32;  := (b[3])u32;  | (b[2])<<8u3,2;  | (b[1])<<16u32;  | (b[0])<<24
-// 	return b[4:], x
-// },
+fn consume_uint32(b: &[u8]) -> (&[u8], u32) {
+    (&b[4..], BIG_ENDIAN.uint32(&b[0..4]))
+}
 
 impl hash::Hash for Digest {
     fn reset(&mut self) {
@@ -1,9 +1,223 @@
-// // Copyright 2017 The Go Authors. All rights reserved.
-// // Use of this source code is governed by a BSD-style
-// // license that can be found in the LICENSE file.
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2017 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
 
-// package sha256
+// x86_64 implementations of the SHA256 block step.
+// The implementation is selected at run time based on the CPU features,
+// falling back to block_generic when no extension is available.
 
-// import "internal/cpu"
+use super::sha256::CHUNK;
+use super::sha256block::{block_generic, K};
+use std::arch::x86_64::*;
 
-// var useAVX2 = cpu.X86.HasAVX2 && cpu.X86.HasBMI2
+/// use_sha reports whether the SHA extensions can be used.
+pub(super) fn use_sha() -> bool {
+    is_x86_feature_detected!("sha")
+        && is_x86_feature_detected!("sse2")
+        && is_x86_feature_detected!("ssse3")
+        && is_x86_feature_detected!("sse4.1")
+}
+
+/// use_avx2 reports whether the AVX2 message schedule can be used.
+pub(super) fn use_avx2() -> bool {
+    is_x86_feature_detected!("avx2") && is_x86_feature_detected!("bmi2")
+}
+
+/// block processes as many whole blocks of p as possible,
+/// using the fastest implementation supported by the CPU.
+pub fn block(h: &mut [u32; 8], p: &[u8]) {
+    if use_sha() {
+        // Safety: the required CPU features were detected above.
+        unsafe { block_sha_ni(h, p) }
+    } else if use_avx2() {
+        // Safety: the required CPU features were detected above.
+        unsafe { block_avx2(h, p) }
+    } else {
+        block_generic(h, p)
+    }
+}
+
+/// block_sha_ni is the block step using the SHA-NI instructions.
+///
+/// # Safety
+///
+/// The CPU must support the sha, sse2, ssse3 and sse4.1 features.
+#[target_feature(enable = "sha,sse2,ssse3,sse4.1")]
+pub(super) unsafe fn block_sha_ni(h: &mut [u32; 8], p: &[u8]) {
+    let flip = _mm_set_epi64x(0x0c0d0e0f08090a0b, 0x0405060700010203);
+
+    // The instructions operate on the state in ABEF and CDGH order.
+    let tmp = _mm_shuffle_epi32(_mm_loadu_si128(h.as_ptr() as *const __m128i), 0xb1); // CDAB
+    let mut state1 = _mm_shuffle_epi32(_mm_loadu_si128(h[4..].as_ptr() as *const __m128i), 0x1b); // EFGH
+    let mut state0 = _mm_alignr_epi8(tmp, state1, 8); // ABEF
+    state1 = _mm_blend_epi16(state1, tmp, 0xf0); // CDGH
+
+    let mut p = p;
+    while p.len() >= CHUNK {
+        let abef_save = state0;
+        let cdgh_save = state1;
+
+        let mut m = [_mm_setzero_si128(); 4];
+        for (i, m) in m.iter_mut().enumerate() {
+            let v = _mm_loadu_si128(p[i * 16..].as_ptr() as *const __m128i);
+            *m = _mm_shuffle_epi8(v, flip);
+        }
+
+        // Each iteration does four rounds and extends the message schedule
+        // by four words, three groups ahead of where it is consumed.
+        for g in 0..16 {
+            let k = _mm_loadu_si128(K[g * 4..].as_ptr() as *const __m128i);
+            let mut msg = _mm_add_epi32(m[g % 4], k);
+            state1 = _mm_sha256rnds2_epu32(state1, state0, msg);
+            if (3..15).contains(&g) {
+                let tmp = _mm_alignr_epi8(m[g % 4], m[(g + 3) % 4], 4);
+                m[(g + 1) % 4] = _mm_add_epi32(m[(g + 1) % 4], tmp);
+                m[(g + 1) % 4] = _mm_sha256msg2_epu32(m[(g + 1) % 4], m[g % 4]);
+            }
+            msg = _mm_shuffle_epi32(msg, 0x0e);
+            state0 = _mm_sha256rnds2_epu32(state0, state1, msg);
+            if (1..13).contains(&g) {
+                m[(g + 3) % 4] = _mm_sha256msg1_epu32(m[(g + 3) % 4], m[g % 4]);
+            }
+        }
+
+        state0 = _mm_add_epi32(state0, abef_save);
+        state1 = _mm_add_epi32(state1, cdgh_save);
+        p = &p[CHUNK..];
+    }
+
+    let tmp = _mm_shuffle_epi32(state0, 0x1b); // FEBA
+    state1 = _mm_shuffle_epi32(state1, 0xb1); // DCHG
+    state0 = _mm_blend_epi16(tmp, state1, 0xf0); // DCBA
+    state1 = _mm_alignr_epi8(state1, tmp, 8); // HGFE
+    _mm_storeu_si128(h.as_mut_ptr() as *mut __m128i, state0);
+    _mm_storeu_si128(h[4..].as_mut_ptr() as *mut __m128i, state1);
+}
+
+/// block_avx2 is the block step using AVX2 to compute the message schedule
+/// of two blocks at a time, one in each 128-bit lane.
+/// The rounds themselves are done with scalar instructions.
+///
+/// # Safety
+///
+/// The CPU must support the avx2 and bmi2 features.
+#[target_feature(enable = "avx2,bmi2")]
+pub(super) unsafe fn block_avx2(h: &mut [u32; 8], p: &[u8]) {
+    let mut wk = [[0u32; 64]; 2];
+    let mut p = p;
+    while p.len() >= CHUNK {
+        // With an odd number of blocks the last one is scheduled twice
+        // and the second result is ignored.
+        let n = if p.len() >= 2 * CHUNK { 2 } else { 1 };
+        schedule_avx2(&p[..CHUNK], &p[(n - 1) * CHUNK..n * CHUNK], &mut wk);
+        for wk in wk.iter().take(n) {
+            rounds(h, wk);
+        }
+        p = &p[n * CHUNK..];
+    }
+}
+
+/// schedule_avx2 stores the message schedule words of blocks p0 and p1,
+/// with the round constants already added, into wk.
+#[target_feature(enable = "avx2")]
+unsafe fn schedule_avx2(p0: &[u8], p1: &[u8], wk: &mut [[u32; 64]; 2]) {
+    let flip = _mm256_set_epi64x(
+        0x0c0d0e0f08090a0b,
+        0x0405060700010203,
+        0x0c0d0e0f08090a0b,
+        0x0405060700010203,
+    );
+    let mut x = [_mm256_setzero_si256(); 4];
+    for (i, x) in x.iter_mut().enumerate() {
+        let lo = _mm_loadu_si128(p0[i * 16..].as_ptr() as *const __m128i);
+        let hi = _mm_loadu_si128(p1[i * 16..].as_ptr() as *const __m128i);
+        *x = _mm256_shuffle_epi8(
+            _mm256_inserti128_si256(_mm256_castsi128_si256(lo), hi, 1),
+            flip,
+        );
+        store_wk(wk, i * 4, *x);
+    }
+    for t in (16..64).step_by(4) {
+        let w = schedule4(x[0], x[1], x[2], x[3]);
+        x = [x[1], x[2], x[3], w];
+        store_wk(wk, t, w);
+    }
+}
+
+/// store_wk adds the round constants t..t+4 to both lanes of w
+/// and stores them into wk.
+#[target_feature(enable = "avx2")]
+unsafe fn store_wk(wk: &mut [[u32; 64]; 2], t: usize, w: __m256i) {
+    let k = _mm256_broadcastsi128_si256(_mm_loadu_si128(K[t..].as_ptr() as *const __m128i));
+    let w = _mm256_add_epi32(w, k);
+    _mm_storeu_si128(
+        wk[0][t..].as_mut_ptr() as *mut __m128i,
+        _mm256_castsi256_si128(w),
+    );
+    _mm_storeu_si128(
+        wk[1][t..].as_mut_ptr() as *mut __m128i,
+        _mm256_extracti128_si256(w, 1),
+    );
+}
+
+/// schedule4 computes the next four message schedule words in each lane,
+/// given the previous sixteen in x0..x3.
+#[target_feature(enable = "avx2")]
+unsafe fn schedule4(x0: __m256i, x1: __m256i, x2: __m256i, x3: __m256i) -> __m256i {
+    let w15 = _mm256_alignr_epi8(x1, x0, 4);
+    let w7 = _mm256_alignr_epi8(x3, x2, 4);
+    let s0 = _mm256_xor_si256(
+        _mm256_xor_si256(
+            _mm256_or_si256(_mm256_srli_epi32(w15, 7), _mm256_slli_epi32(w15, 25)),
+            _mm256_or_si256(_mm256_srli_epi32(w15, 18), _mm256_slli_epi32(w15, 14)),
+        ),
+        _mm256_srli_epi32(w15, 3),
+    );
+    let w = _mm256_add_epi32(_mm256_add_epi32(x0, w7), s0);
+
+    // The sigma1 term of the last two words depends on the first two,
+    // so it is added in two steps.
+    let low = _mm256_set_epi32(0, 0, -1, -1, 0, 0, -1, -1);
+    let s1 = _mm256_and_si256(sigma1(_mm256_shuffle_epi32(x3, 0xee)), low);
+    let w = _mm256_add_epi32(w, s1);
+    let s1 = _mm256_andnot_si256(low, sigma1(_mm256_shuffle_epi32(w, 0x44)));
+    _mm256_add_epi32(w, s1)
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn sigma1(x: __m256i) -> __m256i {
+    _mm256_xor_si256(
+        _mm256_xor_si256(
+            _mm256_or_si256(_mm256_srli_epi32(x, 17), _mm256_slli_epi32(x, 15)),
+            _mm256_or_si256(_mm256_srli_epi32(x, 19), _mm256_slli_epi32(x, 13)),
+        ),
+        _mm256_srli_epi32(x, 10),
+    )
+}
+
+/// rounds does the 64 rounds of one block
+/// using the message schedule words with the round constants added.
+#[inline]
+fn rounds(dig: &mut [u32; 8], wk: &[u32; 64]) {
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *dig;
+    for wk in wk {
+        let t1 = h
+            .wrapping_add(e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25))
+            .wrapping_add((e & f) ^ (!e & g))
+            .wrapping_add(*wk);
+        let t2 = (a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22))
+            .wrapping_add((a & b) ^ (a & c) ^ (b & c));
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+    for (x, v) in dig.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *x = x.wrapping_add(v);
+    }
+}
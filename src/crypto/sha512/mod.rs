@@ -0,0 +1,20 @@
+// Copyright 2023 The rust-ggstd authors.
+// Copyright 2009 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! Package sha512 implements the SHA-384, SHA-512, SHA-512/224, and SHA-512/256
+//! hash algorithms as defined in FIPS 180-4.
+//!
+//! All the Digest constructors in this package also implement
+//! crate::hash::Hash, so they can be used with crate::crypto::hmac.
+
+mod sha512;
+mod sha512block;
+
+pub use sha512::{
+    sum384, sum512, sum512_224, sum512_256, Digest, BLOCK_SIZE, SIZE, SIZE224, SIZE256, SIZE384,
+};
+
+#[cfg(test)]
+mod sha512_test;
@@ -0,0 +1,304 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2009 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::sha512block::block_generic;
+use crate::compat;
+use crate::encoding::binary::{ByteOrder, BIG_ENDIAN};
+use crate::hash::{self, Hash};
+use std::io::Write;
+
+// fn init() {
+// 	crypto.RegisterHash(crypto.SHA384, New384)
+// 	crypto.RegisterHash(crypto.SHA512, New)
+// 	crypto.RegisterHash(crypto.SHA512_224, New512_224)
+// 	crypto.RegisterHash(crypto.SHA512_256, New512_256)
+// }
+
+/// SIZE is the size, in bytes, of a SHA-512 checksum.
+pub const SIZE: usize = 64;
+
+/// SIZE224 is the size, in bytes, of a SHA-512/224 checksum.
+pub const SIZE224: usize = 28;
+
+/// SIZE256 is the size, in bytes, of a SHA-512/256 checksum.
+pub const SIZE256: usize = 32;
+
+/// SIZE384 is the size, in bytes, of a SHA-384 checksum.
+pub const SIZE384: usize = 48;
+
+/// BLOCK_SIZE is the block size, in bytes, of the SHA-512/224,
+/// SHA-512/256, SHA-384 and SHA-512 hash functions.
+pub const BLOCK_SIZE: usize = 128;
+
+pub const CHUNK: usize = 128;
+const INIT0: u64 = 0x6a09e667f3bcc908;
+const INIT1: u64 = 0xbb67ae8584caa73b;
+const INIT2: u64 = 0x3c6ef372fe94f82b;
+const INIT3: u64 = 0xa54ff53a5f1d36f1;
+const INIT4: u64 = 0x510e527fade682d1;
+const INIT5: u64 = 0x9b05688c2b3e6c1f;
+const INIT6: u64 = 0x1f83d9abfb41bd6b;
+const INIT7: u64 = 0x5be0cd19137e2179;
+const INIT0_224: u64 = 0x8c3d37c819544da2;
+const INIT1_224: u64 = 0x73e1996689dcd4d6;
+const INIT2_224: u64 = 0x1dfab7ae32ff9c82;
+const INIT3_224: u64 = 0x679dd514582f9fcf;
+const INIT4_224: u64 = 0x0f6d2b697bd44da8;
+const INIT5_224: u64 = 0x77e36f7304c48942;
+const INIT6_224: u64 = 0x3f9d85a86a1d36c8;
+const INIT7_224: u64 = 0x1112e6ad91d692a1;
+const INIT0_256: u64 = 0x22312194fc2bf72c;
+const INIT1_256: u64 = 0x9f555fa3c84c64c2;
+const INIT2_256: u64 = 0x2393b86b6f53b151;
+const INIT3_256: u64 = 0x963877195940eabd;
+const INIT4_256: u64 = 0x96283ee2a88effe3;
+const INIT5_256: u64 = 0xbe5e1e2553863992;
+const INIT6_256: u64 = 0x2b0199fc2c85b8aa;
+const INIT7_256: u64 = 0x0eb72ddc81c52ca2;
+const INIT0_384: u64 = 0xcbbb9d5dc1059ed8;
+const INIT1_384: u64 = 0x629a292a367cd507;
+const INIT2_384: u64 = 0x9159015a3070dd17;
+const INIT3_384: u64 = 0x152fecd8f70e5939;
+const INIT4_384: u64 = 0x67332667ffc00b31;
+const INIT5_384: u64 = 0x8eb44a8768581511;
+const INIT6_384: u64 = 0xdb0c2e0d64f98fa7;
+const INIT7_384: u64 = 0x47b5481dbefa4fa4;
+
+/// Function identifies which of the SHA-512 family hashes a Digest computes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Function {
+    Sha384,
+    Sha512,
+    Sha512_224,
+    Sha512_256,
+}
+
+/// Digest represents the partial evaluation of a SHA-512 family checksum.
+/// Digest implements crate::hash::Hash trait.
+#[derive(Copy, Clone)]
+pub struct Digest {
+    h: [u64; 8],
+    x: [u8; CHUNK],
+    nx: usize,
+    len: u64,
+    function: Function,
+}
+
+impl Default for Digest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest {
+    fn with_function(function: Function) -> Self {
+        let mut d = Self {
+            h: [0; 8],
+            x: [0; CHUNK],
+            nx: 0,
+            len: 0,
+            function,
+        };
+        d.reset();
+        d
+    }
+
+    /// new returns a new Digest computing the SHA-512 checksum.
+    pub fn new() -> Self {
+        // 	if boring.Enabled {
+        // 		return boring.NewSHA512()
+        // 	}
+        Self::with_function(Function::Sha512)
+    }
+
+    /// new512_224 returns a new Digest computing the SHA-512/224 checksum.
+    pub fn new512_224() -> Self {
+        Self::with_function(Function::Sha512_224)
+    }
+
+    /// new512_256 returns a new Digest computing the SHA-512/256 checksum.
+    pub fn new512_256() -> Self {
+        Self::with_function(Function::Sha512_256)
+    }
+
+    /// new384 returns a new Digest computing the SHA-384 checksum.
+    pub fn new384() -> Self {
+        // 	if boring.Enabled {
+        // 		return boring.NewSHA384()
+        // 	}
+        Self::with_function(Function::Sha384)
+    }
+
+    pub fn write(&mut self, p: &[u8]) {
+        // 	if d.function != crypto.SHA512_224 && d.function != crypto.SHA512_256 {
+        // 		boringUnreachable()
+        // 	}
+        let mut p = p;
+        self.len += p.len() as u64;
+        if self.nx > 0 {
+            let n = compat::copy(&mut self.x[self.nx..], p);
+            self.nx += n;
+            if self.nx == CHUNK {
+                block_generic(&mut self.h, &self.x[..]);
+                self.nx = 0
+            }
+            p = &p[n..];
+        }
+        if p.len() >= CHUNK {
+            let n = p.len() & !(CHUNK - 1);
+            block_generic(&mut self.h, &p[..n]);
+            p = &p[n..];
+        }
+        if !p.is_empty() {
+            self.nx = compat::copy(&mut self.x, p);
+        }
+    }
+}
+
+// const (
+// 	magic384      = "sha\x04"
+// 	magic512_224  = "sha\x05"
+// 	magic512_256  = "sha\x06"
+// 	magic512      = "sha\x07"
+// 	marshaledSize = len(magic512) + 8*8 + chunk + 8
+// )
+
+impl hash::Hash for Digest {
+    fn reset(&mut self) {
+        self.h = match self.function {
+            Function::Sha384 => [
+                INIT0_384, INIT1_384, INIT2_384, INIT3_384, INIT4_384, INIT5_384, INIT6_384,
+                INIT7_384,
+            ],
+            Function::Sha512_224 => [
+                INIT0_224, INIT1_224, INIT2_224, INIT3_224, INIT4_224, INIT5_224, INIT6_224,
+                INIT7_224,
+            ],
+            Function::Sha512_256 => [
+                INIT0_256, INIT1_256, INIT2_256, INIT3_256, INIT4_256, INIT5_256, INIT6_256,
+                INIT7_256,
+            ],
+            Function::Sha512 => [INIT0, INIT1, INIT2, INIT3, INIT4, INIT5, INIT6, INIT7],
+        };
+        self.nx = 0;
+        self.len = 0;
+    }
+
+    fn size(&self) -> usize {
+        match self.function {
+            Function::Sha512_224 => SIZE224,
+            Function::Sha512_256 => SIZE256,
+            Function::Sha384 => SIZE384,
+            Function::Sha512 => SIZE,
+        }
+    }
+
+    fn block_size(&self) -> usize {
+        BLOCK_SIZE
+    }
+
+    fn sum(&self, b: &[u8]) -> Vec<u8> {
+        // Make a copy of d so that caller can keep writing and summing.
+        let mut d0 = *self;
+        let hash = d0.checksum();
+        let mut res = b.to_vec();
+        res.extend_from_slice(&hash[..d0.size()]);
+        res
+    }
+}
+
+impl std::io::Write for Digest {
+    fn write(&mut self, p: &[u8]) -> std::io::Result<usize> {
+        Digest::write(self, p);
+        Ok(p.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Digest {
+    /// checksum returns checksum of the data.
+    /// It is the same as `hash::Hash::sum(&[])`, but returns array, not vector.
+    /// For the truncated variants only the first size() bytes are meaningful.
+    fn checksum(&mut self) -> [u8; SIZE] {
+        // Padding. Add a 1 bit and 0 bits until 112 bytes mod 128.
+        let len = self.len;
+        let mut tmp = [0; 128 + 16]; // padding + length buffer
+        tmp[0] = 0x80_u8;
+        let t: u64 = if len % 128 < 112 {
+            112 - len % 128
+        } else {
+            128 + 112 - len % 128
+        };
+
+        // Length in bits.
+        let len = len << 3;
+        let padlen = &mut tmp[0..(t + 16) as usize];
+        // Upper 64 bits are always zero, because len variable has type u64,
+        // and tmp is already zeroed at that index, because it's a local variable.
+        BIG_ENDIAN.put_uint64(&mut padlen[(t + 8) as usize..], len);
+        self.write_all(padlen).expect("write error is not expected");
+
+        if self.nx != 0 {
+            panic!("self.nx != 0");
+        }
+
+        let mut digest = [0; SIZE];
+        BIG_ENDIAN.put_uint64(&mut digest[0..], self.h[0]);
+        BIG_ENDIAN.put_uint64(&mut digest[8..], self.h[1]);
+        BIG_ENDIAN.put_uint64(&mut digest[16..], self.h[2]);
+        BIG_ENDIAN.put_uint64(&mut digest[24..], self.h[3]);
+        BIG_ENDIAN.put_uint64(&mut digest[32..], self.h[4]);
+        BIG_ENDIAN.put_uint64(&mut digest[40..], self.h[5]);
+        if self.function != Function::Sha384 {
+            BIG_ENDIAN.put_uint64(&mut digest[48..], self.h[6]);
+            BIG_ENDIAN.put_uint64(&mut digest[56..], self.h[7]);
+        }
+
+        digest
+    }
+}
+
+/// sum512 returns the SHA512 checksum of the data.
+pub fn sum512(data: &[u8]) -> [u8; SIZE] {
+    // if boring.Enabled {
+    // 	return boring.SHA512(data)
+    // }
+    let mut d = Digest::new();
+    d.write_all(data).expect("write error is not expected");
+    d.checksum()
+}
+
+/// sum384 returns the SHA384 checksum of the data.
+pub fn sum384(data: &[u8]) -> [u8; SIZE384] {
+    // if boring.Enabled {
+    // 	return boring.SHA384(data)
+    // }
+    let mut d = Digest::new384();
+    d.write_all(data).expect("write error is not expected");
+    let mut res = [0; SIZE384];
+    res.copy_from_slice(&d.checksum()[..SIZE384]);
+    res
+}
+
+/// sum512_224 returns the Sum512/224 checksum of the data.
+pub fn sum512_224(data: &[u8]) -> [u8; SIZE224] {
+    let mut d = Digest::new512_224();
+    d.write_all(data).expect("write error is not expected");
+    let mut res = [0; SIZE224];
+    res.copy_from_slice(&d.checksum()[..SIZE224]);
+    res
+}
+
+/// sum512_256 returns the Sum512/256 checksum of the data.
+pub fn sum512_256(data: &[u8]) -> [u8; SIZE256] {
+    let mut d = Digest::new512_256();
+    d.write_all(data).expect("write error is not expected");
+    let mut res = [0; SIZE256];
+    res.copy_from_slice(&d.checksum()[..SIZE256]);
+    res
+}
@@ -16,7 +16,7 @@ pub fn xor_bytes(dst: &mut [u8], x: &[u8], y: &[u8]) -> usize {
         panic!("subtle.XORBytes: dst too short")
     }
     // 	xorBytes(&dst[0], &x[0], &y[0], n) // arch-specific
-    super::xor_generic::xor_loop(dst, x, y);
+    super::xor_generic::xor_loop(&mut dst[..n], x, y);
     n
 }
 
@@ -27,6 +27,6 @@ pub fn xor_bytes_inplace(dst: &mut [u8], y: &[u8]) -> usize {
     if n == 0 {
         return 0;
     }
-    super::xor_generic::xor_loop_inplace(dst, y);
+    super::xor_generic::xor_loop_inplace(&mut dst[..n], y);
     n
 }
@@ -23,14 +23,14 @@ pub trait ByteOrder {
     // 	String() string
 }
 
-// // AppendByteOrder specifies how to append 16-, 32-, or 64-bit unsigned integers
-// // into a u8 slice.
-// type AppendByteOrder interface {
-// 	AppendUint16([]u8, u16) []u8
-// 	AppendUint32([]u8, u32) []u8
-// 	AppendUint64([]u8, u64) []u8
-// 	String() string
-// }
+/// AppendByteOrder specifies how to append 16-, 32-, or 64-bit unsigned integers
+/// into a u8 vector.
+pub trait AppendByteOrder {
+    fn append_uint16(&self, b: &mut Vec<u8>, v: u16);
+    fn append_uint32(&self, b: &mut Vec<u8>, v: u32);
+    fn append_uint64(&self, b: &mut Vec<u8>, v: u64);
+    // 	String() string
+}
 
 /// LittleEndian is the little-endian implementation of ByteOrder and AppendByteOrder.
 pub struct LittleEndian {}
@@ -80,40 +80,39 @@ impl ByteOrder for LittleEndian {
 
 // type littleEndian struct{}
 
-// fn (littleEndian) PutUint16(b: &[u8], v: u16) {
-// 	_ = b[1] // early bounds check to guarantee safety of writes below
-// 	b[0] = u8(v)
-// 	b[1] = u8(v >> 8)
-// }
+impl LittleEndian {
+    /// put_uint16 stores v into b[..2].
+    ///
+    /// It is not part of ByteOrder, so that adding it doesn't break
+    /// other implementations of the trait.
+    pub fn put_uint16(&self, b: &mut [u8], v: u16) {
+        b[0] = (v) as u8;
+        b[1] = (v >> 8) as u8;
+    }
+}
 
-// fn (littleEndian) AppendUint16(b: &[u8], v: u16) []u8 {
-// 	return append(b,
-// 		u8(v),
-// 		u8(v>>8),
-// 	)
-// }
+impl AppendByteOrder for LittleEndian {
+    fn append_uint16(&self, b: &mut Vec<u8>, v: u16) {
+        b.extend_from_slice(&[(v) as u8, (v >> 8) as u8]);
+    }
 
-// fn (littleEndian) AppendUint32(b: &[u8], v: u32) []u8 {
-// 	return append(b,
-// 		u8(v),
-// 		u8(v>>8),
-// 		u8(v>>16),
-// 		u8(v>>24),
-// 	)
-// }
+    fn append_uint32(&self, b: &mut Vec<u8>, v: u32) {
+        b.extend_from_slice(&[(v) as u8, (v >> 8) as u8, (v >> 16) as u8, (v >> 24) as u8]);
+    }
 
-// fn (littleEndian) AppendUint64(b: &[u8], v: u64) []u8 {
-// 	return append(b,
-// 		u8(v),
-// 		u8(v>>8),
-// 		u8(v>>16),
-// 		u8(v>>24),
-// 		u8(v>>32),
-// 		u8(v>>40),
-// 		u8(v>>48),
-// 		u8(v>>56),
-// 	)
-// }
+    fn append_uint64(&self, b: &mut Vec<u8>, v: u64) {
+        b.extend_from_slice(&[
+            (v) as u8,
+            (v >> 8) as u8,
+            (v >> 16) as u8,
+            (v >> 24) as u8,
+            (v >> 32) as u8,
+            (v >> 40) as u8,
+            (v >> 48) as u8,
+            (v >> 56) as u8,
+        ]);
+    }
+}
 
 // fn (littleEndian) String() string { return "LittleEndian" }
 
@@ -133,12 +132,6 @@ impl ByteOrder for BigEndian {
         (b[3] as u32) | (b[2] as u32) << 8 | (b[1] as u32) << 16 | (b[0] as u32) << 24
     }
 
-    // fn PutUint16(b: &[u8], v: u16) {
-    // 	_ = b[1] // early bounds check to guarantee safety of writes below
-    // 	b[0] = u8(v >> 8)
-    // 	b[1] = u8(v)
-    // }
-
     fn put_uint32(&self, b: &mut [u8], v: u32) {
         b[0] = (v >> 24) as u8;
         b[1] = (v >> 16) as u8;
@@ -169,35 +162,35 @@ impl ByteOrder for BigEndian {
     }
 }
 
+impl AppendByteOrder for BigEndian {
+    fn append_uint16(&self, b: &mut Vec<u8>, v: u16) {
+        b.extend_from_slice(&[(v >> 8) as u8, (v) as u8]);
+    }
+
+    fn append_uint32(&self, b: &mut Vec<u8>, v: u32) {
+        b.extend_from_slice(&[(v >> 24) as u8, (v >> 16) as u8, (v >> 8) as u8, (v) as u8]);
+    }
+
+    fn append_uint64(&self, b: &mut Vec<u8>, v: u64) {
+        b.extend_from_slice(&[
+            (v >> 56) as u8,
+            (v >> 48) as u8,
+            (v >> 40) as u8,
+            (v >> 32) as u8,
+            (v >> 24) as u8,
+            (v >> 16) as u8,
+            (v >> 8) as u8,
+            (v) as u8,
+        ]);
+    }
+}
+
 impl BigEndian {
-    // pub fn AppendUint16(b: &[u8], v: u16) []u8 {
-    // 	return append(b,
-    // 		u8(v>>8),
-    // 		u8(v),
-    // 	)
-    // }
-
-    // pub fn AppendUint32(b: &[u8], v: u32) []u8 {
-    // 	return append(b,
-    // 		u8(v>>24),
-    // 		u8(v>>16),
-    // 		u8(v>>8),
-    // 		u8(v),
-    // 	)
-    // }
-
-    // pub fn AppendUint64(b: &[u8], v: u64) []u8 {
-    // 	return append(b,
-    // 		u8(v>>56),
-    // 		u8(v>>48),
-    // 		u8(v>>40),
-    // 		u8(v>>32),
-    // 		u8(v>>24),
-    // 		u8(v>>16),
-    // 		u8(v>>8),
-    // 		u8(v),
-    // 	)
-    // }
+    /// put_uint16 stores v into b[..2].
+    pub fn put_uint16(&self, b: &mut [u8], v: u16) {
+        b[0] = (v >> 8) as u8;
+        b[1] = (v) as u8;
+    }
 
     // pub fn String() string { return "BigEndian" }
 
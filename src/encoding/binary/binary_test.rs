@@ -4,7 +4,7 @@
 // license that can be found in the LICENSE file.
 
 use super::binary;
-use super::binary::ByteOrder;
+use super::binary::{AppendByteOrder, ByteOrder};
 
 // import (
 // 	"bytes"
@@ -444,16 +444,16 @@ use super::binary::ByteOrder;
 // 	return false
 // }
 
+trait ByteOrderAndAppend: ByteOrder + AppendByteOrder {}
+
+impl<T: ByteOrder + AppendByteOrder> ByteOrderAndAppend for T {}
+
 #[test]
 fn test_byte_order() {
-    // 	type byteOrder interface {
-    // 		ByteOrder
-    // 		AppendByteOrder
-    // 	}
     let mut buf = [0_u8; 8];
-    let orders: &[&dyn ByteOrder] = &[&binary::LITTLE_ENDIAN, &binary::BIG_ENDIAN];
+    let orders: &[&dyn ByteOrderAndAppend] = &[&binary::LITTLE_ENDIAN, &binary::BIG_ENDIAN];
     for order in orders {
-        // let offset = 3;
+        let offset = 3;
         let test_values: &[u64] = &[
             0x0000000000000000,
             0x0123456789abcdef,
@@ -470,43 +470,122 @@ fn test_byte_order() {
             // 			if got := order.Uint16(buf[..2]); got != want16 {
             // 				t.Errorf("PutUint16: Uint16 = {}, want {}", got, want16)
             // 			}
-            // 			buf = order.AppendUint16(buf[..offset], want16)
-            // 			if got := order.Uint16(buf[offset:]); got != want16 {
-            // 				t.Errorf("AppendUint16: Uint16 = {}, want {}", got, want16)
-            // 			}
-            // 			if len(buf) != offset+2 {
-            // 				t.Errorf("AppendUint16: len(buf) = %d, want %d", len(buf), offset+2)
-            // 			}
+            let want16 = value as u16;
+            let mut abuf = vec![0; offset];
+            order.append_uint16(&mut abuf, want16);
+            let got = order.uint16(&abuf[offset..]);
+            assert_eq!(
+                want16, got,
+                "append_uint16: uint16 = {}, want {}",
+                got, want16
+            );
+            assert_eq!(
+                abuf.len(),
+                offset + 2,
+                "append_uint16: len(buf) = {}, want {}",
+                abuf.len(),
+                offset + 2
+            );
 
             let want32 = value as u32;
             order.put_uint32(&mut buf[..4], want32);
             let got = order.uint32(&buf[..4]);
             assert_eq!(want32, got, "pub_uint32: uint32 = {}, want {}", got, want32);
 
-            // 			buf = order.AppendUint32(buf[..offset], want32)
-            // 			if got := order.uint32(buf[offset:]); got != want32 {
-            // 				t.Errorf("AppendUint32: Uint32 = {}, want {}", got, want32)
-            // 			}
-            // 			if len(buf) != offset+4 {
-            // 				t.Errorf("AppendUint32: len(buf) = %d, want %d", len(buf), offset+4)
-            // 			}
+            let mut abuf = vec![0; offset];
+            order.append_uint32(&mut abuf, want32);
+            let got = order.uint32(&abuf[offset..]);
+            assert_eq!(
+                want32, got,
+                "append_uint32: uint32 = {}, want {}",
+                got, want32
+            );
+            assert_eq!(
+                abuf.len(),
+                offset + 4,
+                "append_uint32: len(buf) = {}, want {}",
+                abuf.len(),
+                offset + 4
+            );
 
             let want64 = value;
             order.put_uint64(&mut buf[..8], want64);
             let got = order.uint64(&buf[..8]);
             assert_eq!(want64, got, "put_uint64: uint64 = {}, want {}", got, want64);
 
-            // 			buf = order.AppendUint64(buf[..offset], want64)
-            // 			if got := order.Uint64(buf[offset:]); got != want64 {
-            // 				t.Errorf("AppendUint64: Uint64 = {}, want {}", got, want64)
-            // 			}
-            // 			if len(buf) != offset+8 {
-            // 				t.Errorf("AppendUint64: len(buf) = %d, want %d", len(buf), offset+8)
-            // 			}
+            let mut abuf = vec![0; offset];
+            order.append_uint64(&mut abuf, want64);
+            let got = order.uint64(&abuf[offset..]);
+            assert_eq!(
+                want64, got,
+                "append_uint64: uint64 = {}, want {}",
+                got, want64
+            );
+            assert_eq!(
+                abuf.len(),
+                offset + 8,
+                "append_uint64: len(buf) = {}, want {}",
+                abuf.len(),
+                offset + 8
+            );
         }
     }
 }
 
+#[test]
+fn test_put_uint16() {
+    let test_values: &[u16] = &[0x0000, 0x0102, 0xcdef, 0xffff];
+    let mut buf = [0_u8; 2];
+    for value in test_values {
+        let want16 = *value;
+        binary::LITTLE_ENDIAN.put_uint16(&mut buf, want16);
+        assert_eq!(buf, [want16 as u8, (want16 >> 8) as u8]);
+        let got = binary::LITTLE_ENDIAN.uint16(&buf);
+        assert_eq!(want16, got, "put_uint16: uint16 = {}, want {}", got, want16);
+
+        binary::BIG_ENDIAN.put_uint16(&mut buf, want16);
+        assert_eq!(buf, [(want16 >> 8) as u8, want16 as u8]);
+        let got = binary::BIG_ENDIAN.uint16(&buf);
+        assert_eq!(want16, got, "put_uint16: uint16 = {}, want {}", got, want16);
+    }
+}
+
+/// OldOrder implements ByteOrder with only the methods the trait always had.
+struct OldOrder<T: ByteOrder>(T);
+
+impl<T: ByteOrder> ByteOrder for OldOrder<T> {
+    fn uint16(&self, b: &[u8]) -> u16 {
+        self.0.uint16(b)
+    }
+    fn uint32(&self, b: &[u8]) -> u32 {
+        self.0.uint32(b)
+    }
+    fn uint64(&self, b: &[u8]) -> u64 {
+        self.0.uint64(b)
+    }
+    fn put_uint32(&self, b: &mut [u8], v: u32) {
+        self.0.put_uint32(b, v)
+    }
+    fn put_uint64(&self, b: &mut [u8], v: u64) {
+        self.0.put_uint64(b, v)
+    }
+}
+
+#[test]
+fn test_old_byte_order() {
+    let orders: &[&dyn ByteOrder] = &[
+        &OldOrder(binary::LITTLE_ENDIAN),
+        &OldOrder(binary::BIG_ENDIAN),
+    ];
+    let mut buf = [0_u8; 8];
+    for order in orders {
+        order.put_uint32(&mut buf[..4], 0x01020304);
+        assert_eq!(order.uint32(&buf[..4]), 0x01020304);
+        order.put_uint64(&mut buf, 0x0102030405060708);
+        assert_eq!(order.uint64(&buf), 0x0102030405060708);
+    }
+}
+
 // fn TestEarlyBoundsChecks(t *testing.T) {
 // 	if testUint64SmallSliceLengthPanics() != true {
 // 		t.Errorf("binary.LittleEndian.Uint64 expected to panic for small slices, but didn't")
@@ -23,7 +23,7 @@
 
 mod binary;
 
-pub use binary::{BigEndian, ByteOrder, LittleEndian, BIG_ENDIAN, LITTLE_ENDIAN};
+pub use binary::{AppendByteOrder, BigEndian, ByteOrder, LittleEndian, BIG_ENDIAN, LITTLE_ENDIAN};
 
 #[cfg(test)]
 mod binary_test;
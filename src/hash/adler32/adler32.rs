@@ -3,6 +3,8 @@
 // Use of this source code is governed by a BSD-style
 // license that can be found in the LICENSE file.
 
+use crate::encoding::binary::{AppendByteOrder, ByteOrder, BIG_ENDIAN};
+use crate::errors;
 use crate::hash;
 use crate::hash::Hash;
 use crate::hash::Hash32;
@@ -25,7 +27,9 @@ pub struct Digest {
 }
 
 /// new returns a new Digest computing the Adler-32 checksum. Its
-/// Sum method will lay the value out in big-endian byte order.
+/// Sum method will lay the value out in big-endian byte order. The
+/// returned Digest also implements marshal_binary and unmarshal_binary
+/// to marshal and unmarshal the internal state of the hash.
 pub fn new() -> Digest {
     Digest::new()
 }
@@ -109,6 +113,36 @@ impl Digest {
     }
 }
 
+const MAGIC: &[u8] = b"adl\x01";
+const MARSHALED_SIZE: usize = MAGIC.len() + 4;
+
+impl Digest {
+    /// marshal_binary returns the internal state of the digest in the same
+    /// format as Go's encoding.BinaryMarshaler implementation,
+    /// so the state can be restored later with unmarshal_binary.
+    pub fn marshal_binary(&self) -> Vec<u8> {
+        let mut b = Vec::with_capacity(MARSHALED_SIZE);
+        b.extend_from_slice(MAGIC);
+        BIG_ENDIAN.append_uint32(&mut b, self.hash);
+        b
+    }
+
+    /// unmarshal_binary restores the internal state of the digest
+    /// from the output of marshal_binary.
+    pub fn unmarshal_binary(&mut self, b: &[u8]) -> Result<(), errors::ErrorStaticString> {
+        if b.len() < MAGIC.len() || &b[..MAGIC.len()] != MAGIC {
+            return Err(errors::new_static(
+                "hash/adler32: invalid hash state identifier",
+            ));
+        }
+        if b.len() != MARSHALED_SIZE {
+            return Err(errors::new_static("hash/adler32: invalid hash state size"));
+        }
+        self.hash = BIG_ENDIAN.uint32(&b[MAGIC.len()..]);
+        Ok(())
+    }
+}
+
 impl Default for Digest {
     fn default() -> Self {
         Self::new()
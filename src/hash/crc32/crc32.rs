@@ -3,8 +3,12 @@
 // Use of this source code is governed by a BSD-style
 // license that can be found in the LICENSE file.
 
+use super::crc32_arch;
 use super::crc32_generic;
+use crate::encoding::binary::{AppendByteOrder, ByteOrder, BIG_ENDIAN};
+use crate::errors;
 use crate::hash;
+use std::sync::OnceLock;
 
 // The size of a CRC-32 checksum in bytes.
 pub const SIZE: usize = 4;
@@ -65,32 +69,53 @@ pub type Table = [u32; 256];
 // //    // if archInitCastagnoli() was previously called.
 // //    archUpdateCastagnoli(crc uint32, p [u8]) uint32
 
-// // castagnoliTable points to a lazily initialized Table for the Castagnoli
-// // polynomial. MakeTable will always return this value when asked to make a
-// // Castagnoli table so we can compare against it to find when the caller is
-// // using this polynomial.
-// var castagnoliTable *Table
-// var castagnoliTable8 *slicing8Table
-// var updateCastagnoli func(crc uint32, p [u8]) uint32
-// var castagnoliOnce sync.Once
-// var haveCastagnoli atomic.Bool
-
-// func castagnoliInit() {
-// 	castagnoliTable = simple_make_table(Castagnoli)
-
-// 	if archAvailableCastagnoli() {
-// 		archInitCastagnoli()
-// 		updateCastagnoli = archUpdateCastagnoli
-// 	} else {
-// 		// Initialize the slicing-by-8 table.
-// 		castagnoliTable8 = slicingMakeTable(Castagnoli)
-// 		updateCastagnoli = func(crc uint32, p [u8]) uint32 {
-// 			return slicingUpdate(crc, castagnoliTable8, p)
-// 		}
-// 	}
+/// castagnoli_table returns the Table for the Castagnoli polynomial.
+/// make_table returns a copy of this value when asked to make a
+/// Castagnoli table, so update can compare against it to find when
+/// the caller is using this polynomial.
+fn castagnoli_table() -> &'static Table {
+    static CASTAGNOLI_TABLE: OnceLock<Table> = OnceLock::new();
+    CASTAGNOLI_TABLE
+        .get_or_init(|| crc32_generic::simple_make_table(PredefinedPolynomials::Castagnoli as u32))
+}
 
-// 	haveCastagnoli.Store(true)
-// }
+/// is_table reports whether tab has the same contents as the predefined table
+/// want for the polynomial poly.
+///
+/// It compares the whole table, which is needed for the copies returned by
+/// make_table; callers check std::ptr::eq against the static tables first.
+fn is_table(tab: &Table, want: &Table, poly: u32) -> bool {
+    // The entry at 128 is the polynomial itself, which makes for a cheap
+    // first check before comparing the whole table.
+    tab[128] == poly && tab == want
+}
+
+/// TableKind tells which implementation update uses for a table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TableKind {
+    Ieee,
+    Castagnoli,
+    Other,
+}
+
+/// table_kind finds which of the predefined tables tab is, if any.
+fn table_kind(tab: &Table) -> TableKind {
+    if std::ptr::eq(tab, &IEEE_TABLE) {
+        TableKind::Ieee
+    } else if std::ptr::eq(tab, castagnoli_table()) {
+        TableKind::Castagnoli
+    } else if is_table(tab, &IEEE_TABLE, PredefinedPolynomials::IEEE as u32) {
+        TableKind::Ieee
+    } else if is_table(
+        tab,
+        castagnoli_table(),
+        PredefinedPolynomials::Castagnoli as u32,
+    ) {
+        TableKind::Castagnoli
+    } else {
+        TableKind::Other
+    }
+}
 
 /// IEEE_TABLE is the table for the IEEE polynomial.
 /// The table was precalculated using crc32_generic::simple_make_table(crc32::PredefinedPolynomials::IEEE as u32) function.
@@ -150,40 +175,43 @@ pub static IEEE_TABLE: Table = [
 /// MakeTable returns a Table constructed from the specified polynomial.
 // The contents of this Table must not be modified.
 pub fn make_table(poly: u32) -> Table {
-    // 	switch poly {
-    // 	case IEEE:
-    // 		ieeeOnce.Do(ieeeInit)
-    // 		return IEEE_TABLE
-    // 	case Castagnoli:
-    // 		castagnoliOnce.Do(castagnoliInit)
-    // 		return castagnoliTable
-    // 	default:
-    super::crc32_generic::simple_make_table(poly)
-    // 	}
+    if poly == PredefinedPolynomials::IEEE as u32 {
+        IEEE_TABLE
+    } else if poly == PredefinedPolynomials::Castagnoli as u32 {
+        *castagnoli_table()
+    } else {
+        crc32_generic::simple_make_table(poly)
+    }
 }
 
 // digest represents the partial evaluation of a checksum.
 pub struct Digest<'a> {
     crc: u32,
     tab: &'a Table,
+    // kind is found once here so that writes don't compare the table again.
+    kind: TableKind,
 }
 
 // New creates a new hash::Hash32 computing the CRC-32 checksum using the
 // polynomial represented by the Table. Its Sum method will lay the
-// value out in big-endian byte order. The returned Hash32 also
-// implements encoding.BinaryMarshaler and encoding.BinaryUnmarshaler to
+// value out in big-endian byte order. The returned Digest also
+// implements marshal_binary and unmarshal_binary to
 // marshal and unmarshal the internal state of the hash.
 pub fn new(tab: &Table) -> Digest {
     // if tab == IEEE_TABLE {
     // 	ieeeOnce.Do(ieeeInit)
     // }
-    Digest { crc: 0, tab }
+    Digest {
+        crc: 0,
+        tab,
+        kind: table_kind(tab),
+    }
 }
 
 // new_ieee creates a new hash::Hash32 computing the CRC-32 checksum using
 // the IEEE polynomial. Its Sum method will lay the value out in
-// big-endian byte order. The returned Hash32 also implements
-// encoding.BinaryMarshaler and encoding.BinaryUnmarshaler to marshal
+// big-endian byte order. The returned Digest also implements
+// marshal_binary and unmarshal_binary to marshal
 // and unmarshal the internal state of the hash.
 pub fn new_ieee() -> Digest<'static> {
     new(&IEEE_TABLE)
@@ -217,61 +245,51 @@ impl hash::Hash32 for Digest<'_> {
     }
 }
 
-// const (
-// 	magic         = "crc\x01"
-// 	marshaledSize = len(magic) + 4 + 4
-// )
-
-// func (d *digest) MarshalBinary() ([u8], error) {
-// 	b := make([u8], 0, marshaledSize)
-// 	b = append(b, magic...)
-// 	b = appendUint32(b, tableSum(d.tab))
-// 	b = appendUint32(b, d.crc)
-// 	return b, nil
-// }
-
-// func (d *digest) UnmarshalBinary(b [u8]) error {
-// 	if b.len() < len(magic) || string(b[..len(magic)]) != magic {
-// 		return errors.New("hash/crc32: invalid hash state identifier")
-// 	}
-// 	if b.len() != marshaledSize {
-// 		return errors.New("hash/crc32: invalid hash state size")
-// 	}
-// 	if tableSum(d.tab) != readUint32(b[4:]) {
-// 		return errors.New("hash/crc32: tables do not match")
-// 	}
-// 	d.crc = readUint32(b[8:])
-// 	return nil
-// }
-
-// func appendUint32(b [u8], x uint32) [u8] {
-// 	a := [4]byte{
-// 		byte(x >> 24),
-// 		byte(x >> 16),
-// 		byte(x >> 8),
-// 		byte(x),
-// 	}
-// 	return append(b, a[..]...)
-// }
+const MAGIC: &[u8] = b"crc\x01";
+const MARSHALED_SIZE: usize = MAGIC.len() + 4 + 4;
+
+impl Digest<'_> {
+    /// marshal_binary returns the internal state of the digest in the same
+    /// format as Go's encoding.BinaryMarshaler implementation,
+    /// so the state can be restored later with unmarshal_binary.
+    pub fn marshal_binary(&self) -> Vec<u8> {
+        let mut b = Vec::with_capacity(MARSHALED_SIZE);
+        b.extend_from_slice(MAGIC);
+        BIG_ENDIAN.append_uint32(&mut b, table_sum(self.tab));
+        BIG_ENDIAN.append_uint32(&mut b, self.crc);
+        b
+    }
 
-// func readUint32(b [u8]) uint32 {
-// 	_ = b[3]
-// 	return uint32(b[3]) | uint32(b[2])<<8 | uint32(b[1])<<16 | uint32(b[0])<<24
-// }
+    /// unmarshal_binary restores the internal state of the digest
+    /// from the output of marshal_binary.
+    /// The state must have been produced by a digest using the same table.
+    pub fn unmarshal_binary(&mut self, b: &[u8]) -> Result<(), errors::ErrorStaticString> {
+        if b.len() < MAGIC.len() || &b[..MAGIC.len()] != MAGIC {
+            return Err(errors::new_static(
+                "hash/crc32: invalid hash state identifier",
+            ));
+        }
+        if b.len() != MARSHALED_SIZE {
+            return Err(errors::new_static("hash/crc32: invalid hash state size"));
+        }
+        if table_sum(self.tab) != BIG_ENDIAN.uint32(&b[4..]) {
+            return Err(errors::new_static("hash/crc32: tables do not match"));
+        }
+        self.crc = BIG_ENDIAN.uint32(&b[8..]);
+        Ok(())
+    }
+}
 
-fn update_internal(crc: u32, tab: &Table, p: &[u8]) -> u32 {
-    // 	switch {
-    // 	case haveCastagnoli.Load() && tab == castagnoliTable:
-    // 		return updateCastagnoli(crc, p)
-    // 	case tab == IEEE_TABLE:
-    // 		if checkInitIEEE {
-    // 			ieeeOnce.Do(ieeeInit)
-    // 		}
-    // 		return updateIEEE(crc, p)
-    // 	default:
-    // 		return simple_update(crc, tab, p)
-    // 	}
-    crc32_generic::simple_update(crc, tab, p)
+fn update_internal(crc: u32, tab: &Table, kind: TableKind, p: &[u8]) -> u32 {
+    match kind {
+        TableKind::Ieee if crc32_arch::arch_available_ieee() => {
+            crc32_arch::arch_update_ieee(crc, p)
+        }
+        TableKind::Castagnoli if crc32_arch::arch_available_castagnoli() => {
+            crc32_arch::arch_update_castagnoli(crc, p)
+        }
+        _ => crc32_generic::simple_update(crc, tab, p),
+    }
 }
 
 // update returns the result of adding the bytes in p to the crc.
@@ -280,12 +298,12 @@ pub fn update(crc: u32, tab: &Table, p: &[u8]) -> u32 {
     // // call to MakeTable. We have to make sure it gets initialized in that case.
     // return update(crc, tab, p, true);
 
-    update_internal(crc, tab, p)
+    update_internal(crc, tab, table_kind(tab), p)
 }
 
 impl std::io::Write for Digest<'_> {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        self.crc = update(self.crc, self.tab, buf);
+        self.crc = update_internal(self.crc, self.tab, self.kind, buf);
         Ok(buf.len())
     }
 
@@ -308,14 +326,11 @@ pub fn checksum_ieee(data: &[u8]) -> u32 {
     checksum(data, &IEEE_TABLE)
 }
 
-// // tableSum returns the IEEE checksum of table t.
-// func tableSum(t *Table) uint32 {
-// 	var a [1024]byte
-// 	b := a[..0]
-// 	if t != nil {
-// 		for _, x := range t {
-// 			b = appendUint32(b, x)
-// 		}
-// 	}
-// 	return checksum_ieee(b)
-// }
+/// table_sum returns the IEEE checksum of table t.
+fn table_sum(t: &Table) -> u32 {
+    let mut b = Vec::with_capacity(1024);
+    for x in t {
+        BIG_ENDIAN.append_uint32(&mut b, *x);
+    }
+    checksum_ieee(&b)
+}
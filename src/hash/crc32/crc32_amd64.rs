@@ -0,0 +1,278 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// AMD64-specific hardware-assisted CRC32 algorithms. See crc32.rs for a
+// description of the interface that each architecture-specific file
+// implements.
+
+use super::crc32::IEEE_TABLE;
+use super::crc32_generic::simple_update;
+use std::arch::x86_64::*;
+use std::sync::OnceLock;
+
+// This file contains the code to call the SSE 4.2 version of the Castagnoli
+// and IEEE CRC.
+
+const CASTAGNOLI_K1: usize = 168;
+const CASTAGNOLI_K2: usize = 1344;
+
+type Sse42Table = [[u32; 256]; 4];
+
+/// castagnoli_sse42 updates the (non-inverted) crc with the given buffer.
+#[target_feature(enable = "sse4.2")]
+unsafe fn castagnoli_sse42(crc: u32, p: &[u8]) -> u32 {
+    let mut crc = crc as u64;
+    let mut chunks = p.chunks_exact(8);
+    for c in &mut chunks {
+        crc = _mm_crc32_u64(crc, u64::from_le_bytes(c.try_into().unwrap()));
+    }
+    let mut crc = crc as u32;
+    for b in chunks.remainder() {
+        crc = _mm_crc32_u8(crc, *b);
+    }
+    crc
+}
+
+/// castagnoli_sse42_triple updates three (non-inverted) crcs with
+/// the first k bytes of a, b and c, interleaving the three streams.
+#[target_feature(enable = "sse4.2")]
+unsafe fn castagnoli_sse42_triple(
+    crc_a: u32,
+    crc_b: u32,
+    crc_c: u32,
+    a: &[u8],
+    b: &[u8],
+    c: &[u8],
+    k: usize,
+) -> (u32, u32, u32) {
+    let (mut crc_a, mut crc_b, mut crc_c) = (crc_a as u64, crc_b as u64, crc_c as u64);
+    for i in (0..k).step_by(8) {
+        crc_a = _mm_crc32_u64(crc_a, u64::from_le_bytes(a[i..i + 8].try_into().unwrap()));
+        crc_b = _mm_crc32_u64(crc_b, u64::from_le_bytes(b[i..i + 8].try_into().unwrap()));
+        crc_c = _mm_crc32_u64(crc_c, u64::from_le_bytes(c[i..i + 8].try_into().unwrap()));
+    }
+    (crc_a as u32, crc_b as u32, crc_c as u32)
+}
+
+/// cast_sse42_tables returns the tables used to shift a crc
+/// past K1 and K2 zero bytes.
+fn cast_sse42_tables() -> &'static (Sse42Table, Sse42Table) {
+    static TABLES: OnceLock<Box<(Sse42Table, Sse42Table)>> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let mut tables = Box::new(([[0; 256]; 4], [[0; 256]; 4]));
+        // See description in arch_update_castagnoli.
+        //    t[0][i] = CRC(i000, O)
+        //    t[1][i] = CRC(0i00, O)
+        //    t[2][i] = CRC(00i0, O)
+        //    t[3][i] = CRC(000i, O)
+        // where O is a sequence of K zeros.
+        let tmp = [0; CASTAGNOLI_K2];
+        for b in 0..4 {
+            for i in 0..256 {
+                let val = (i as u32) << (b * 8);
+                // Safety: the tables are only used when SSE 4.2 is available.
+                unsafe {
+                    tables.0[b][i] = castagnoli_sse42(val, &tmp[..CASTAGNOLI_K1]);
+                    tables.1[b][i] = castagnoli_sse42(val, &tmp);
+                }
+            }
+        }
+        tables
+    })
+}
+
+/// castagnoli_shift computes the CRC32-C of K1 or K2 zeroes (depending on the
+/// table given) with the given initial crc value. This corresponds to
+/// CRC(crc, O) in the description in arch_update_castagnoli.
+fn castagnoli_shift(table: &Sse42Table, crc: u32) -> u32 {
+    table[3][(crc >> 24) as usize]
+        ^ table[2][((crc >> 16) & 0xFF) as usize]
+        ^ table[1][((crc >> 8) & 0xFF) as usize]
+        ^ table[0][(crc & 0xFF) as usize]
+}
+
+pub fn arch_available_castagnoli() -> bool {
+    is_x86_feature_detected!("sse4.2")
+}
+
+pub fn arch_update_castagnoli(crc: u32, p: &[u8]) -> u32 {
+    assert!(
+        arch_available_castagnoli(),
+        "not available: arch_update_castagnoli requires SSE 4.2"
+    );
+    // Safety: the CPU feature was checked above.
+    unsafe { update_castagnoli_sse42(crc, p) }
+}
+
+#[target_feature(enable = "sse4.2")]
+unsafe fn update_castagnoli_sse42(crc: u32, p: &[u8]) -> u32 {
+    let mut crc = !crc;
+    let mut p = p;
+
+    // If a buffer is long enough to use the optimization, process the first few
+    // bytes to align the buffer to an 8 byte boundary (if necessary).
+    if p.len() >= CASTAGNOLI_K1 * 3 {
+        let mut delta = p.as_ptr() as usize & 7;
+        if delta != 0 {
+            delta = 8 - delta;
+            crc = castagnoli_sse42(crc, &p[..delta]);
+            p = &p[delta..];
+        }
+    }
+
+    // Process 3*K2 at a time.
+    while p.len() >= CASTAGNOLI_K2 * 3 {
+        // Compute CRCs for three blocks.
+        let (crc_a, crc_b, crc_c) = castagnoli_sse42_triple(
+            crc,
+            0,
+            0,
+            p,
+            &p[CASTAGNOLI_K2..],
+            &p[CASTAGNOLI_K2 * 2..],
+            CASTAGNOLI_K2,
+        );
+
+        // A == P[..K], B == P[K..2K], C == P[2K..3K]
+        // The CRC of A is computed from the initial crc, while the CRCs of
+        // B and C are computed from 0. Since CRC is linear,
+        //    CRC(I, AB) = CRC(CRC(I, A), O) xor CRC(0, B)
+        //    CRC(I, ABC) = CRC(CRC(I, AB), O) xor CRC(0, C)
+        // where O is a sequence of K zeros.
+        let (_, k2) = cast_sse42_tables();
+        let crc_ab = castagnoli_shift(k2, crc_a) ^ crc_b;
+        crc = castagnoli_shift(k2, crc_ab) ^ crc_c;
+        p = &p[CASTAGNOLI_K2 * 3..];
+    }
+
+    // Process 3*K1 at a time.
+    while p.len() >= CASTAGNOLI_K1 * 3 {
+        // Compute CRCs for three blocks.
+        let (crc_a, crc_b, crc_c) = castagnoli_sse42_triple(
+            crc,
+            0,
+            0,
+            p,
+            &p[CASTAGNOLI_K1..],
+            &p[CASTAGNOLI_K1 * 2..],
+            CASTAGNOLI_K1,
+        );
+
+        // Combine the three CRCs as above.
+        let (k1, _) = cast_sse42_tables();
+        let crc_ab = castagnoli_shift(k1, crc_a) ^ crc_b;
+        crc = castagnoli_shift(k1, crc_ab) ^ crc_c;
+        p = &p[CASTAGNOLI_K1 * 3..];
+    }
+
+    // Use the simple implementation for what's left.
+    crc = castagnoli_sse42(crc, p);
+    !crc
+}
+
+pub fn arch_available_ieee() -> bool {
+    is_x86_feature_detected!("pclmulqdq")
+        && is_x86_feature_detected!("sse2")
+        && is_x86_feature_detected!("sse4.1")
+}
+
+pub fn arch_update_ieee(crc: u32, p: &[u8]) -> u32 {
+    assert!(
+        arch_available_ieee(),
+        "not available: arch_update_ieee requires PCLMULQDQ and SSE 4.1"
+    );
+    let mut crc = crc;
+    let mut p = p;
+    if p.len() >= 64 {
+        let left = p.len() & 15;
+        let do_len = p.len() - left;
+        // Safety: the CPU features were checked above.
+        crc = !unsafe { ieee_clmul(!crc, &p[..do_len]) };
+        p = &p[do_len..];
+    }
+    if p.is_empty() {
+        return crc;
+    }
+    simple_update(crc, &IEEE_TABLE, p)
+}
+
+// Constants for the PCLMULQDQ folding of the IEEE polynomial,
+// see "Fast CRC Computation for Generic Polynomials Using PCLMULQDQ
+// Instruction" by Intel.
+const R2R1: [u64; 2] = [0x154442bd4, 0x1c6e41596];
+const R4R3: [u64; 2] = [0x1751997d0, 0x0ccaa009e];
+const RUPOLY: [u64; 2] = [0x1db710641, 0x1f7011641];
+const R5: u64 = 0x163cd6124;
+
+/// ieee_clmul updates the inverted crc with p using the PCLMULQDQ folding
+/// method. The length of p must be a multiple of 16 and at least 64.
+#[target_feature(enable = "pclmulqdq,sse2,sse4.1")]
+unsafe fn ieee_clmul(crc: u32, p: &[u8]) -> u32 {
+    debug_assert!(p.len() >= 64 && p.len() & 15 == 0);
+    let mut x1 = _mm_xor_si128(load(p, 0), _mm_cvtsi32_si128(crc as i32));
+    let mut x2 = load(p, 16);
+    let mut x3 = load(p, 32);
+    let mut x4 = load(p, 48);
+    let mut i = 64;
+
+    // Fold 64 bytes at a time.
+    let k = _mm_set_epi64x(R2R1[1] as i64, R2R1[0] as i64);
+    while p.len() - i >= 64 {
+        x1 = _mm_xor_si128(fold(x1, k), load(p, i));
+        x2 = _mm_xor_si128(fold(x2, k), load(p, i + 16));
+        x3 = _mm_xor_si128(fold(x3, k), load(p, i + 32));
+        x4 = _mm_xor_si128(fold(x4, k), load(p, i + 48));
+        i += 64;
+    }
+
+    // Fold result into a single register (x1).
+    let k = _mm_set_epi64x(R4R3[1] as i64, R4R3[0] as i64);
+    x1 = _mm_xor_si128(fold(x1, k), x2);
+    x1 = _mm_xor_si128(fold(x1, k), x3);
+    x1 = _mm_xor_si128(fold(x1, k), x4);
+
+    // Encode 16 bytes at a time.
+    while p.len() - i >= 16 {
+        x1 = _mm_xor_si128(fold(x1, k), load(p, i));
+        i += 16;
+    }
+
+    // Fold final result into 32 bits and return it.
+    let x0 = _mm_clmulepi64_si128(k, x1, 0x01);
+    x1 = _mm_xor_si128(_mm_srli_si128(x1, 8), x0);
+
+    // Creates 32 bit mask. Note that we don't care about upper half.
+    let mask = _mm_srli_epi64(_mm_set1_epi32(-1), 32);
+
+    let x2 = _mm_srli_si128(x1, 4);
+    x1 = _mm_and_si128(x1, mask);
+    x1 = _mm_clmulepi64_si128(x1, _mm_set_epi64x(0, R5 as i64), 0x00);
+    x1 = _mm_xor_si128(x1, x2);
+
+    let rupoly = _mm_set_epi64x(RUPOLY[1] as i64, RUPOLY[0] as i64);
+    let x2 = x1;
+    x1 = _mm_and_si128(x1, mask);
+    x1 = _mm_clmulepi64_si128(x1, rupoly, 0x10);
+    x1 = _mm_and_si128(x1, mask);
+    x1 = _mm_clmulepi64_si128(x1, rupoly, 0x00);
+    x1 = _mm_xor_si128(x1, x2);
+
+    _mm_extract_epi32(x1, 1) as u32
+}
+
+#[target_feature(enable = "sse2")]
+unsafe fn load(p: &[u8], i: usize) -> __m128i {
+    _mm_loadu_si128(p[i..i + 16].as_ptr() as *const __m128i)
+}
+
+/// fold multiplies the two halves of x by the two halves of k
+/// and combines the products.
+#[target_feature(enable = "pclmulqdq,sse2")]
+unsafe fn fold(x: __m128i, k: __m128i) -> __m128i {
+    _mm_xor_si128(
+        _mm_clmulepi64_si128(x, k, 0x00),
+        _mm_clmulepi64_si128(x, k, 0x11),
+    )
+}
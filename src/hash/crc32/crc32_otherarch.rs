@@ -0,0 +1,20 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+pub fn arch_available_ieee() -> bool {
+    false
+}
+
+pub fn arch_update_ieee(_crc: u32, _p: &[u8]) -> u32 {
+    panic!("not implemented")
+}
+
+pub fn arch_available_castagnoli() -> bool {
+    false
+}
+
+pub fn arch_update_castagnoli(_crc: u32, _p: &[u8]) -> u32 {
+    panic!("not implemented")
+}
@@ -4,8 +4,10 @@
 // license that can be found in the LICENSE file.
 
 use super::crc32;
+use super::crc32_arch;
 use super::crc32_generic;
 use crate::hash::Hash32;
+use crate::math::rand;
 use std::io::Write;
 
 // // First test, so that it can be the one to initialize castagnoliTable.
@@ -20,21 +22,27 @@ use std::io::Write;
 
 struct Test {
     ieee: u32,
-    // castagnoli: u32,
+    castagnoli: u32,
     input: &'static str,
-    // half_state_ieee: &'static str, // IEEE marshaled hash state after first half of in written, used by TestGoldenMarshal
-    // half_state_castagnoli: &'static str, // Castagnoli marshaled hash state after first half of in written, used by TestGoldenMarshal
+    half_state_ieee: &'static [u8], // IEEE marshaled hash state after first half of in written, used by test_golden_marshal
+    half_state_castagnoli: &'static [u8], // Castagnoli marshaled hash state after first half of in written, used by test_golden_marshal
 }
 
 impl Test {
     const fn new(
         ieee: u32,
-        _castagnoli: u32,
+        castagnoli: u32,
         input: &'static str,
-        // _half_state_ieee: &[u8],
-        // _half_state_castagnoli: &[u8],
+        half_state_ieee: &'static [u8],
+        half_state_castagnoli: &'static [u8],
     ) -> Self {
-        Test { ieee, input }
+        Test {
+            ieee,
+            castagnoli,
+            input,
+            half_state_ieee,
+            half_state_castagnoli,
+        }
     }
 }
 
@@ -42,158 +50,158 @@ const GOLDEN: &[Test] = &[
     Test::new(
         0x0, 0x0,
         "",
-        // "crc\x01ʇ\x91M\x00\x00\x00\x00",
-        // "crc\x01wB\x84\x81\x00\x00\x00\x00",
+        b"crc\x01\xca\x87\x91M\x00\x00\x00\x00",
+        b"crc\x01wB\x84\x81\x00\x00\x00\x00",
     ),
     Test::new(0xe8b7be43, 0xc1d04330,
         "a",
-    // "crc\x01ʇ\x91M\x00\x00\x00\x00",
-    // "crc\x01wB\x84\x81\x00\x00\x00\x00",
+        b"crc\x01\xca\x87\x91M\x00\x00\x00\x00",
+        b"crc\x01wB\x84\x81\x00\x00\x00\x00",
     ),
     Test::new(0x9e83486d, 0xe2a22936,
         "ab",
-    // "crc\x01ʇ\x91M跾C",
-    // "crc\x01wB\x84\x81\xc1\xd0C0",
+        b"crc\x01\xca\x87\x91M\xe8\xb7\xbeC",
+        b"crc\x01wB\x84\x81\xc1\xd0C0",
     ),
     Test::new(0x352441c2, 0x364b3fb7,
         "abc",
-    // "crc\x01ʇ\x91M跾C",
-    // "crc\x01wB\x84\x81\xc1\xd0C0",
+        b"crc\x01\xca\x87\x91M\xe8\xb7\xbeC",
+        b"crc\x01wB\x84\x81\xc1\xd0C0",
     ),
     Test::new(0xed82cd11, 0x92c80a31,
         "abcd",
-    // "crc\x01ʇ\x91M\x9e\x83Hm",
-    // "crc\x01wB\x84\x81\xe2\xa2)6",
+        b"crc\x01\xca\x87\x91M\x9e\x83Hm",
+        b"crc\x01wB\x84\x81\xe2\xa2)6",
     ),
     Test::new(0x8587d865, 0xc450d697,
         "abcde",
-    // "crc\x01ʇ\x91M\x9e\x83Hm",
-    // "crc\x01wB\x84\x81\xe2\xa2)6",
+        b"crc\x01\xca\x87\x91M\x9e\x83Hm",
+        b"crc\x01wB\x84\x81\xe2\xa2)6",
     ),
     Test::new(0x4b8e39ef, 0x53bceff1,
         "abcdef",
-    // "crc\x01ʇ\x91M5$A\xc2",
-    // "crc\x01wB\x84\x816K?\xb7",
+        b"crc\x01\xca\x87\x91M5$A\xc2",
+        b"crc\x01wB\x84\x816K?\xb7",
     ),
     Test::new(0x312a6aa6, 0xe627f441,
         "abcdefg",
-    // "crc\x01ʇ\x91M5$A\xc2",
-    // "crc\x01wB\x84\x816K?\xb7",
+        b"crc\x01\xca\x87\x91M5$A\xc2",
+        b"crc\x01wB\x84\x816K?\xb7",
     ),
     Test::new(0xaeef2a50, 0xa9421b7,
         "abcdefgh",
-    // "crc\x01ʇ\x91M\xed\x82\xcd\x11",
-    // "crc\x01wB\x84\x81\x92\xc8\n1",
+        b"crc\x01\xca\x87\x91M\xed\x82\xcd\x11",
+        b"crc\x01wB\x84\x81\x92\xc8\x0a1",
     ),
     Test::new(0x8da988af, 0x2ddc99fc,
         "abcdefghi",
-    // "crc\x01ʇ\x91M\xed\x82\xcd\x11",
-    // "crc\x01wB\x84\x81\x92\xc8\n1",
+        b"crc\x01\xca\x87\x91M\xed\x82\xcd\x11",
+        b"crc\x01wB\x84\x81\x92\xc8\x0a1",
     ),
     Test::new(0x3981703a, 0xe6599437,
         "abcdefghij",
-    // "crc\x01ʇ\x91M\x85\x87\xd8e",
-    // "crc\x01wB\x84\x81\xc4P֗",
+        b"crc\x01\xca\x87\x91M\x85\x87\xd8e",
+        b"crc\x01wB\x84\x81\xc4P\xd6\x97",
     ),
     Test::new(0x6b9cdfe7, 0xb2cc01fe,
         "Discard medicine more than two years old.",
-    // "crc\x01ʇ\x91M\xfd\xe5\xc2J",
-    // "crc\x01wB\x84\x81S\"(\xe0",
+        b"crc\x01\xca\x87\x91M\xfd\xe5\xc2J",
+        b"crc\x01wB\x84\x81S\"(\xe0",
     ),
     Test::new(0xc90ef73f, 0xe28207f,
         "He who has a shady past knows that nice guys finish last.",
-    // "crc\x01ʇ\x91M\x01ǋ+",
-    // "crc\x01wB\x84\x81'\xdaR\x15",
+        b"crc\x01\xca\x87\x91M\x01\xc7\x8b+",
+        b"crc\x01wB\x84\x81'\xdaR\x15",
     ),
     Test::new(0xb902341f, 0xbe93f964,
         "I wouldn't marry him with a ten foot pole.",
-    // "crc\x01ʇ\x91M\x9d\x13\xce\x10",
-    // "crc\x01wB\x84\x81\xc3\xed\xabG",
+        b"crc\x01\xca\x87\x91M\x9d\x13\xce\x10",
+        b"crc\x01wB\x84\x81\xc3\xed\xabG",
     ),
     Test::new(0x42080e8, 0x9e3be0c3,
         "Free! Free!/A trip/to Mars/for 900/empty jars/Burma Shave",
-    // "crc\x01ʇ\x91M-\xed\xf7\x94",
-    // "crc\x01wB\x84\x81\xce\xceb\x81",
+        b"crc\x01\xca\x87\x91M-\xed\xf7\x94",
+        b"crc\x01wB\x84\x81\xce\xceb\x81",
     ),
     Test::new(0x154c6d11, 0xf505ef04,
         "The days of the digital watch are numbered.  -Tom Stoppard",
-    // "crc\x01ʇ\x91MOa\xa5\r",
-    // "crc\x01wB\x84\x81\xd3s\x9dP",
+        b"crc\x01\xca\x87\x91MOa\xa5\x0d",
+        b"crc\x01wB\x84\x81\xd3s\x9dP",
     ),
     Test::new(0x4c418325, 0x85d3dc82,
         "Nepal premier won't resign.",
-    // "crc\x01ʇ\x91M\xa8S9\x85",
-    // "crc\x01wB\x84\x81{\x90\x8a\x14",
+        b"crc\x01\xca\x87\x91M\xa8S9\x85",
+        b"crc\x01wB\x84\x81{\x90\x8a\x14",
     ),
     Test::new(0x33955150, 0xc5142380,
         "For every action there is an equal and opposite government program.",
-    // "crc\x01ʇ\x91Ma\xe9>\x86",
-    // "crc\x01wB\x84\x81\xaa@\xc4\x1c",
+        b"crc\x01\xca\x87\x91Ma\xe9>\x86",
+        b"crc\x01wB\x84\x81\xaa@\xc4\x1c",
     ),
     Test::new(0x26216a4b, 0x75eb77dd,
         "His money is twice tainted: 'taint yours and 'taint mine.",
-    // "crc\x01ʇ\x91M\\\x1an\x88",
-    // "crc\x01wB\x84\x81W\a8Z",
+        b"crc\x01\xca\x87\x91M\\\x1an\x88",
+        b"crc\x01wB\x84\x81W\x078Z",
     ),
     Test::new(0x1abbe45e, 0x91ebe9f7,
         "There is no reason for any individual to have a computer in their home. -Ken Olsen, 1977",
-    // "crc\x01ʇ\x91M\xb7\xf5\xf2\xca",
-    // "crc\x01wB\x84\x81\xc4o\x9d\x85",
+        b"crc\x01\xca\x87\x91M\xb7\xf5\xf2\xca",
+        b"crc\x01wB\x84\x81\xc4o\x9d\x85",
     ),
     Test::new(0xc89a94f7, 0xf0b1168e,
         "It's a tiny change to the code and not completely disgusting. - Bob Manchek",
-    // "crc\x01ʇ\x91M\x84g1\xe8",
-    // "crc\x01wB\x84\x81#\x98\f\xab",
+        b"crc\x01\xca\x87\x91M\x84g1\xe8",
+        b"crc\x01wB\x84\x81#\x98\x0c\xab",
     ),
     Test::new(0xab3abe14, 0x572b74e2,
         "size:  a.out:  bad magic",
-    // "crc\x01ʇ\x91M\x8a\x0f\xad\b",
-    // "crc\x01wB\x84\x81\x80\xc9n\xd8",
+        b"crc\x01\xca\x87\x91M\x8a\x0f\xad\x08",
+        b"crc\x01wB\x84\x81\x80\xc9n\xd8",
     ),
     Test::new(0xbab102b6, 0x8a58a6d5,
         "The major problem is with sendmail.  -Mark Horton",
-    // "crc\x01ʇ\x91M\a\xf0\xb3\x15",
-    // "crc\x01wB\x84\x81liS\xcc",
+        b"crc\x01\xca\x87\x91M\x07\xf0\xb3\x15",
+        b"crc\x01wB\x84\x81liS\xcc",
     ),
     Test::new(0x999149d7, 0x9c426c50,
         "Give me a rock, paper and scissors and I will move the world.  CCFestoon",
-    // "crc\x01ʇ\x91M\x0fa\xbc.",
-    // "crc\x01wB\x84\x81\xdb͏C",
+        b"crc\x01\xca\x87\x91M\x0fa\xbc.",
+        b"crc\x01wB\x84\x81\xdb\xcd\x8fC",
     ),
     Test::new(0x6d52a33c, 0x735400a4,
         "If the enemy is within range, then so are you.",
-    // "crc\x01ʇ\x91My\x1b\x99\xf8",
-    // "crc\x01wB\x84\x81\xaaB\x037",
+        b"crc\x01\xca\x87\x91My\x1b\x99\xf8",
+        b"crc\x01wB\x84\x81\xaaB\x037",
     ),
     Test::new(0x90631e8d, 0xbec49c95,
         "It's well we cannot hear the screams/That we create in others' dreams.",
-    // "crc\x01ʇ\x91M\bqfY",
-    // "crc\x01wB\x84\x81\x16y\xa1\xd2",
+        b"crc\x01\xca\x87\x91M\x08qfY",
+        b"crc\x01wB\x84\x81\x16y\xa1\xd2",
     ),
     Test::new(0x78309130, 0xa95a2079,
         "You remind me of a TV show, but that's all right: I watch it anyway.",
-    // "crc\x01ʇ\x91M\xbdO,\xc2",
-    // "crc\x01wB\x84\x81f&\xc5\xe4",
+        b"crc\x01\xca\x87\x91M\xbdO,\xc2",
+        b"crc\x01wB\x84\x81f&\xc5\xe4",
     ),
     Test::new(0x7d0a377f, 0xde2e65c5,
         "C is as portable as Stonehedge!!",
-    // "crc\x01ʇ\x91M\xf7\xd6\x00\xd5",
-    // "crc\x01wB\x84\x81de\\\xf8",
+        b"crc\x01\xca\x87\x91M\xf7\xd6\x00\xd5",
+        b"crc\x01wB\x84\x81de\\\xf8",
     ),
     Test::new(0x8c79fd79, 0x297a88ed,
         "Even if I could be Shakespeare, I think I should still choose to be Faraday. - A. Huxley",
-    // "crc\x01ʇ\x91Ml+\xb8\xa7",
-    // "crc\x01wB\x84\x81\xbf\xd6S\xdd",
+        b"crc\x01\xca\x87\x91Ml+\xb8\xa7",
+        b"crc\x01wB\x84\x81\xbf\xd6S\xdd",
     ),
     Test::new(0xa20b7167, 0x66ed1d8b,
         "The fugacity of a constituent in a mixture of gases at a given temperature is proportional to its mole fraction.  Lewis-Randall Rule",
-    // "crc\x01ʇ\x91M<lR[",
-    // "crc\x01wB\x84\x81{\xaco\xb1",
+        b"crc\x01\xca\x87\x91M<lR[",
+        b"crc\x01wB\x84\x81{\xaco\xb1",
     ),
     Test::new(0x8e0bb443, 0xdcded527,
         "How can you write a big system without C++?  -Paul Glick",
-    // "crc\x01ʇ\x91M\x0e\x88\x89\xed",
-    // "crc\x01wB\x84\x813\xd7C\u007f",
+        b"crc\x01\xca\x87\x91M\x0e\x88\x89\xed",
+        b"crc\x01wB\x84\x813\xd7C\x7f",
     ),
 ];
 
@@ -209,37 +217,57 @@ where
     }
 }
 
-// // testGoldenCastagnoli verifies that the given function returns
-// // correct IEEE checksums.
-// fn testGoldenCastagnoli(t *testing.T, crcFunc func(b [u8]) u32) {
-// 	for g in GOLDEN {
-// 		if crc := crcFunc([u8](g.in)); crc != g.castagnoli {
-// 			t.Errorf("Castagnoli(%s) = 0x%x want 0x%x", g.in, crc, g.castagnoli)
-// 		}
-// 	}
-// }
+// test_golden_castagnoli verifies that the given function returns
+// correct Castagnoli checksums.
+fn test_golden_castagnoli<F>(crc_func: F)
+where
+    F: Fn(&[u8]) -> u32,
+{
+    for g in GOLDEN {
+        let crc = crc_func(g.input.as_bytes());
+        assert_eq!(
+            g.castagnoli, crc,
+            "Castagnoli({}) = {:#x} want {:#x}",
+            g.input, crc, g.castagnoli
+        );
+    }
+}
 
-// // testCrossCheck generates random buffers of various lengths and verifies that
-// // the two "update" functions return the same result.
-// fn testCrossCheck(t *testing.T, crcFunc1, crcFunc2 func(crc u32, b [u8]) u32) {
-// 	// The AMD64 implementation has some cutoffs at lengths 168*3=504 and
-// 	// 1344*3=4032. We should make sure lengths around these values are in the
-// 	// list.
-// 	lengths := []int{0, 1, 2, 3, 4, 5, 10, 16, 50, 63, 64, 65, 100,
-// 		127, 128, 129, 255, 256, 257, 300, 312, 384, 416, 448, 480,
-// 		500, 501, 502, 503, 504, 505, 512, 513, 1000, 1024, 2000,
-// 		4030, 4031, 4032, 4033, 4036, 4040, 4048, 4096, 5000, 10000}
-// 	for _, length := range lengths {
-// 		p := make([u8], length)
-// 		_, _ = rand.Read(p)
-// 		crcInit := u32(rand.Int63())
-// 		crc1 := crcFunc1(crcInit, p)
-// 		crc2 := crcFunc2(crcInit, p)
-// 		if crc1 != crc2 {
-// 			t.Errorf("mismatch: 0x%x vs 0x%x (buffer length {})", crc1, crc2, length)
-// 		}
-// 	}
-// }
+// test_cross_check generates random buffers of various lengths and verifies that
+// the two "update" functions return the same result.
+fn test_cross_check<F1, F2>(crc_func1: F1, crc_func2: F2)
+where
+    F1: Fn(u32, &[u8]) -> u32,
+    F2: Fn(u32, &[u8]) -> u32,
+{
+    // The AMD64 implementation has some cutoffs at lengths 168*3=504 and
+    // 1344*3=4032. We should make sure lengths around these values are in the
+    // list.
+    let lengths = [
+        0, 1, 2, 3, 4, 5, 10, 16, 50, 63, 64, 65, 100, 127, 128, 129, 255, 256, 257, 300, 312, 384,
+        416, 448, 480, 500, 501, 502, 503, 504, 505, 512, 513, 1000, 1024, 2000, 4030, 4031, 4032,
+        4033, 4036, 4040, 4048, 4096, 5000, 10000,
+    ];
+    let mut r = rand::Rand::new(rand::new_source(1));
+    for length in lengths {
+        // Also check buffers which do not start at an 8 byte boundary.
+        let mut buf = vec![0; length + 7];
+        for b in buf.iter_mut() {
+            *b = r.int63() as u8;
+        }
+        for offset in 0..8 {
+            let p = &buf[offset..offset + length];
+            let crc_init = r.int63() as u32;
+            let crc1 = crc_func1(crc_init, p);
+            let crc2 = crc_func2(crc_init, p);
+            assert_eq!(
+                crc1, crc2,
+                "mismatch: {:#x} vs {:#x} (buffer length {}, offset {})",
+                crc1, crc2, length, offset
+            );
+        }
+    }
+}
 
 // test_simple tests the simple generic algorithm.
 #[test]
@@ -248,91 +276,68 @@ fn test_simple() {
     let f = |b: &[u8]| crc32_generic::simple_update(0, &tab, b);
     test_golden_ieee(f);
 
-    // 	tab = simpleMakeTable(Castagnoli)
-    // 	testGoldenCastagnoli(t, func(b [u8]) u32 {
-    // 		return simpleUpdate(0, tab, b)
-    // 	})
+    let tab = crc32_generic::simple_make_table(crc32::PredefinedPolynomials::Castagnoli as u32);
+    test_golden_castagnoli(|b: &[u8]| crc32_generic::simple_update(0, &tab, b));
 }
 
-// fn TestGoldenMarshal() {
-// 	t.Run("IEEE", func() {
-// 		for g in GOLDEN {
-// 			h := New(IEEETable)
-// 			h2 := New(IEEETable)
-
-// 			io.writeString(h, g.in[..len(g.in)/2])
-
-// 			state, err := h.(encoding.BinaryMarshaler).MarshalBinary()
-// 			if err != nil {
-// 				t.Errorf("could not marshal: {}", err)
-// 				continue
-// 			}
-
-// 			if string(state) != g.half_state_ieee {
-// 				t.Errorf("IEEE(%q) state = %q, want %q", g.in, state, g.half_state_ieee)
-// 				continue
-// 			}
-
-// 			if err := h2.(encoding.BinaryUnmarshaler).UnmarshalBinary(state); err != nil {
-// 				t.Errorf("could not unmarshal: {}", err)
-// 				continue
-// 			}
-
-// 			io.writeString(h, g.in[len(g.in)/2:])
-// 			io.writeString(h2, g.in[len(g.in)/2:])
-
-// 			if h.sum32() != h2.sum32() {
-// 				t.Errorf("IEEE(%s) = 0x%x != marshaled 0x%x", g.in, h.sum32(), h2.sum32())
-// 			}
-// 		}
-// 	})
-// 	t.Run("Castagnoli", func() {
-// 		table := MakeTable(Castagnoli)
-// 		for g in GOLDEN {
-// 			h := New(table)
-// 			h2 := New(table)
-
-// 			io.writeString(h, g.in[..len(g.in)/2])
-
-// 			state, err := h.(encoding.BinaryMarshaler).MarshalBinary()
-// 			if err != nil {
-// 				t.Errorf("could not marshal: {}", err)
-// 				continue
-// 			}
-
-// 			if string(state) != g.half_state_castagnoli {
-// 				t.Errorf("Castagnoli(%q) state = %q, want %q", g.in, state, g.half_state_castagnoli)
-// 				continue
-// 			}
-
-// 			if err := h2.(encoding.BinaryUnmarshaler).UnmarshalBinary(state); err != nil {
-// 				t.Errorf("could not unmarshal: {}", err)
-// 				continue
-// 			}
-
-// 			io.writeString(h, g.in[len(g.in)/2:])
-// 			io.writeString(h2, g.in[len(g.in)/2:])
+fn check_golden_marshal(name: &str, table: &crc32::Table, half_state: fn(&Test) -> &[u8]) {
+    for g in GOLDEN {
+        let mut h = crc32::new(table);
+        let mut h2 = crc32::new(table);
+        let data = g.input.as_bytes();
+
+        h.write_all(&data[..data.len() / 2]).unwrap();
+
+        let state = h.marshal_binary();
+        assert_eq!(
+            state,
+            half_state(g),
+            "{}({}) state = {:?}, want {:?}",
+            name,
+            g.input,
+            state,
+            half_state(g)
+        );
+
+        if let Err(err) = h2.unmarshal_binary(&state) {
+            panic!("could not unmarshal: {}", err);
+        }
+
+        h.write_all(&data[data.len() / 2..]).unwrap();
+        h2.write_all(&data[data.len() / 2..]).unwrap();
+
+        assert_eq!(
+            h.sum32(),
+            h2.sum32(),
+            "{}({}) = {:#x} != marshaled {:#x}",
+            name,
+            g.input,
+            h.sum32(),
+            h2.sum32()
+        );
+    }
+}
 
-// 			if h.sum32() != h2.sum32() {
-// 				t.Errorf("Castagnoli(%s) = 0x%x != marshaled 0x%x", g.in, h.sum32(), h2.sum32())
-// 			}
-// 		}
-// 	})
-// }
+#[test]
+fn test_golden_marshal() {
+    check_golden_marshal("IEEE", &crc32::IEEE_TABLE, |g| g.half_state_ieee);
+    let table = crc32::make_table(crc32::PredefinedPolynomials::Castagnoli as u32);
+    check_golden_marshal("Castagnoli", &table, |g| g.half_state_castagnoli);
+}
 
-// fn TestMarshalTableMismatch() {
-// 	h1 := New(IEEETable)
-// 	h2 := New(MakeTable(Castagnoli))
+#[test]
+fn test_marshal_table_mismatch() {
+    let h1 = crc32::new(&crc32::IEEE_TABLE);
+    let table = crc32::make_table(crc32::PredefinedPolynomials::Castagnoli as u32);
+    let mut h2 = crc32::new(&table);
 
-// 	state1, err := h1.(encoding.BinaryMarshaler).MarshalBinary()
-// 	if err != nil {
-// 		t.Errorf("could not marshal: {}", err)
-// 	}
+    let state1 = h1.marshal_binary();
 
-// 	if err := h2.(encoding.BinaryUnmarshaler).UnmarshalBinary(state1); err == nil {
-// 		t.Errorf("no error when one was expected")
-// 	}
-// }
+    assert!(
+        h2.unmarshal_binary(&state1).is_err(),
+        "no error when one was expected"
+    );
+}
 
 // // TestSimple tests the slicing-by-8 algorithm.
 // fn TestSlicing() {
@@ -360,27 +365,50 @@ fn test_simple() {
 // 	}
 // }
 
-// fn TestArchIEEE() {
-// 	if !archAvailableIEEE() {
-// 		t.Skip("Arch-specific IEEE not available.")
-// 	}
-// 	archInitIEEE()
-// 	slicingTable := slicingMakeTable(IEEE)
-// 	testCrossCheck(t, archUpdateIEEE, func(crc u32, b [u8]) u32 {
-// 		return slicingUpdate(crc, slicingTable, b)
-// 	})
-// }
+#[test]
+fn test_arch_ieee() {
+    if !crc32_arch::arch_available_ieee() {
+        eprintln!("Arch-specific IEEE not available.");
+        return;
+    }
+    let simple_table = crc32_generic::simple_make_table(crc32::PredefinedPolynomials::IEEE as u32);
+    test_cross_check(crc32_arch::arch_update_ieee, |crc, b| {
+        crc32_generic::simple_update(crc, &simple_table, b)
+    });
+}
 
-// fn TestArchCastagnoli() {
-// 	if !archAvailableCastagnoli() {
-// 		t.Skip("Arch-specific Castagnoli not available.")
-// 	}
-// 	archInitCastagnoli()
-// 	slicingTable := slicingMakeTable(Castagnoli)
-// 	testCrossCheck(t, archUpdateCastagnoli, func(crc u32, b [u8]) u32 {
-// 		return slicingUpdate(crc, slicingTable, b)
-// 	})
-// }
+#[test]
+fn test_arch_castagnoli() {
+    if !crc32_arch::arch_available_castagnoli() {
+        eprintln!("Arch-specific Castagnoli not available.");
+        return;
+    }
+    let simple_table =
+        crc32_generic::simple_make_table(crc32::PredefinedPolynomials::Castagnoli as u32);
+    test_cross_check(crc32_arch::arch_update_castagnoli, |crc, b| {
+        crc32_generic::simple_update(crc, &simple_table, b)
+    });
+}
+
+// test_update_cross_check verifies that update, which selects an
+// implementation based on the table, agrees with the generic code.
+#[test]
+fn test_update_cross_check() {
+    for poly in [
+        crc32::PredefinedPolynomials::IEEE as u32,
+        crc32::PredefinedPolynomials::Castagnoli as u32,
+        crc32::PredefinedPolynomials::Koopman as u32,
+        0xD5828281,
+    ] {
+        let tab = crc32::make_table(poly);
+        let simple_table = crc32_generic::simple_make_table(poly);
+        assert!(tab == simple_table, "make_table({:#x}) mismatch", poly);
+        test_cross_check(
+            |crc, b| crc32::update(crc, &tab, b),
+            |crc, b| crc32_generic::simple_update(crc, &simple_table, b),
+        );
+    }
+}
 
 #[test]
 fn test_golden() {
@@ -401,31 +429,28 @@ fn test_golden() {
         })
     }
 
-    // 	castagnoliTab := MakeTable(Castagnoli)
-    // 	if castagnoliTab == nil {
-    // 		t.Errorf("nil Castagnoli Table")
-    // 	}
-
-    // 	testGoldenCastagnoli(t, func(b [u8]) u32 {
-    // 		castagnoli := New(castagnoliTab)
-    // 		castagnoli.write_all(b)
-    // 		return castagnoli.sum32()
-    // 	})
-
-    // 	// Some implementations have special code to deal with misaligned
-    // 	// data; test that as well.
-    // 	for delta := 1; delta <= 7; delta++ {
-    // 		testGoldenCastagnoli(t, func(b [u8]) u32 {
-    // 			castagnoli := New(castagnoliTab)
-    // 			d := delta
-    // 			if d >= b.len() {
-    // 				d = b.len()
-    // 			}
-    // 			castagnoli.write_all(b[..d])
-    // 			castagnoli.write_all(b[d:])
-    // 			return castagnoli.sum32()
-    // 		})
-    // 	}
+    let castagnoli_tab = crc32::make_table(crc32::PredefinedPolynomials::Castagnoli as u32);
+
+    test_golden_castagnoli(|b: &[u8]| -> u32 {
+        let mut castagnoli = crc32::new(&castagnoli_tab);
+        castagnoli.write_all(b).unwrap();
+        castagnoli.sum32()
+    });
+
+    // Some implementations have special code to deal with misaligned
+    // data; test that as well.
+    for delta in 1..=7 {
+        test_golden_castagnoli(|b: &[u8]| -> u32 {
+            let mut castagnoli = crc32::new(&castagnoli_tab);
+            let mut d = delta;
+            if d >= b.len() {
+                d = b.len()
+            }
+            castagnoli.write_all(&b[..d]).unwrap();
+            castagnoli.write_all(&b[d..]).unwrap();
+            castagnoli.sum32()
+        })
+    }
 }
 
 // fn BenchmarkCRC32(b *testing.B) {
@@ -13,7 +13,16 @@
 //! for information.
 
 mod crc32;
+#[cfg(target_arch = "x86_64")]
+mod crc32_amd64;
 mod crc32_generic;
+#[cfg(not(target_arch = "x86_64"))]
+mod crc32_otherarch;
+
+#[cfg(target_arch = "x86_64")]
+use crc32_amd64 as crc32_arch;
+#[cfg(not(target_arch = "x86_64"))]
+use crc32_otherarch as crc32_arch;
 
 pub use crc32::{
     checksum, checksum_ieee, make_table, new, new_ieee, update, Digest, PredefinedPolynomials,
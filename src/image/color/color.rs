@@ -3,6 +3,8 @@
 // Use of this source code is governed by a BSD-style
 // license that can be found in the LICENSE file.
 
+use super::ycbcr::{NYCbCrA, YCbCr};
+
 /// Color can convert itself to alpha-premultiplied 16-bits per channel RGBA.
 /// The conversion may be lossy.
 pub trait ColorTrait {
@@ -38,6 +40,10 @@ pub enum Color {
     Alpha(Alpha),
     /// Alpha16 represents a 16-bit alpha color.
     Alpha16(Alpha16),
+    /// YCbCr represents a fully opaque 24-bit Y'CbCr color.
+    YCbCr(YCbCr),
+    /// NYCbCrA represents a non-alpha-premultiplied Y'CbCr-with-alpha color.
+    NYCbCrA(NYCbCrA),
 }
 
 impl Color {
@@ -95,6 +101,8 @@ impl ColorTrait for Color {
             Color::NRGBA64(c) => c.rgba(),
             Color::Alpha(c) => c.rgba(),
             Color::Alpha16(c) => c.rgba(),
+            Color::YCbCr(c) => c.rgba(),
+            Color::NYCbCrA(c) => c.rgba(),
         }
     }
 }
@@ -457,6 +465,8 @@ pub enum Model {
     Alpha16Model,
     GrayModel,
     Gray16Model,
+    YCbCrModel,
+    NYCbCrAModel,
     Paletted(Palette),
 }
 
@@ -473,6 +483,8 @@ impl Model {
             Model::Gray16Model => Color::Gray16(Gray16::new_from(c)),
             Model::AlphaModel => Color::Alpha(Alpha::new_from(c)),
             Model::Alpha16Model => Color::Alpha16(Alpha16::new_from(c)),
+            Model::YCbCrModel => Color::YCbCr(YCbCr::new_from(c)),
+            Model::NYCbCrAModel => Color::NYCbCrA(NYCbCrA::new_from(c)),
             Model::Paletted(pal) => pal.convert(c),
         }
     }
@@ -486,6 +498,8 @@ impl Model {
             Model::Alpha16Model => 16,
             Model::GrayModel => 8,
             Model::Gray16Model => 16,
+            Model::YCbCrModel => 8,
+            Model::NYCbCrAModel => 8,
             Model::Paletted(pal) => match pal.colors.len() {
                 0..=2 => 1,
                 3..=4 => 2,
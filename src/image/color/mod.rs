@@ -2,11 +2,16 @@
 // SPDX-License-Identifier: BSD-3-Clause
 
 mod color;
+pub mod palette;
+mod ycbcr;
 
 pub use color::{
     Alpha, Alpha16, Color, ColorTrait, Gray, Gray16, Model, Palette, BLACK, NRGBA, NRGBA64, OPAQUE,
     OPAQUE_BLACK, RGBA, RGBA64, TRANSPARENT, WHITE,
 };
+pub use ycbcr::{cmyk_to_rgb, rgb_to_cmyk, rgb_to_ycbcr, ycbcr_to_rgb, NYCbCrA, YCbCr};
 
 #[cfg(test)]
 mod color_test;
+#[cfg(test)]
+mod ycbcr_test;
@@ -0,0 +1,10 @@
+// Copyright 2023 The rust-ggstd authors.
+// Copyright 2013 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! Package palette provides standard color palettes.
+
+mod palette;
+
+pub use palette::{PLAN9, WEB_SAFE};
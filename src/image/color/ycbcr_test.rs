@@ -1,119 +1,174 @@
-// // Copyright 2011 The Go Authors. All rights reserved.
-// // Use of this source code is governed by a BSD-style
-// // license that can be found in the LICENSE file.
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
 
-// package color
+use super::{
+    cmyk_to_rgb, rgb_to_cmyk, rgb_to_ycbcr, ycbcr_to_rgb, Alpha, ColorTrait, Gray, NYCbCrA, YCbCr,
+};
 
-// import (
-// 	"fmt"
-// 	"testing"
-// )
+fn delta(x: u8, y: u8) -> u8 {
+    x.abs_diff(y)
+}
 
-// func delta(x, y uint8) uint8 {
-// 	if x >= y {
-// 		return x - y
-// 	}
-// 	return y - x
-// }
+fn eq(c0: &dyn ColorTrait, c1: &dyn ColorTrait) -> Result<(), String> {
+    let (r0, g0, b0, a0) = c0.rgba();
+    let (r1, g1, b1, a1) = c1.rgba();
+    if r0 != r1 || g0 != g1 || b0 != b1 || a0 != a1 {
+        return Err(format!(
+            "got  0x{:04x} 0x{:04x} 0x{:04x} 0x{:04x}\nwant 0x{:04x} 0x{:04x} 0x{:04x} 0x{:04x}",
+            r0, g0, b0, a0, r1, g1, b1, a1
+        ));
+    }
+    Ok(())
+}
 
-// func eq(c0, c1 Color) error {
-// 	r0, g0, b0, a0 := c0.rgba()
-// 	r1, g1, b1, a1 := c1.rgba()
-// 	if r0 != r1 || g0 != g1 || b0 != b1 || a0 != a1 {
-// 		return fmt.Errorf("got  0x%04x 0x%04x 0x%04x 0x%04x\nwant 0x%04x 0x%04x 0x%04x 0x%04x",
-// 			r0, g0, b0, a0, r1, g1, b1, a1)
-// 	}
-// 	return nil
-// }
+/// test_ycbcr_roundtrip tests that a subset of RGB space can be converted to YCbCr
+/// and back to within 2/256 tolerance.
+#[test]
+fn test_ycbcr_roundtrip() {
+    for r in (0..256).step_by(7) {
+        for g in (0..256).step_by(5) {
+            for b in (0..256).step_by(3) {
+                let (r0, g0, b0) = (r as u8, g as u8, b as u8);
+                let (y, cb, cr) = rgb_to_ycbcr(r0, g0, b0);
+                let (r1, g1, b1) = ycbcr_to_rgb(y, cb, cr);
+                assert!(
+                    delta(r0, r1) <= 2 && delta(g0, g1) <= 2 && delta(b0, b1) <= 2,
+                    "\nr0, g0, b0 = {}, {}, {}\ny,  cb, cr = {}, {}, {}\nr1, g1, b1 = {}, {}, {}",
+                    r0,
+                    g0,
+                    b0,
+                    y,
+                    cb,
+                    cr,
+                    r1,
+                    g1,
+                    b1
+                );
+            }
+        }
+    }
+}
 
-// // TestYCbCrRoundtrip tests that a subset of RGB space can be converted to YCbCr
-// // and back to within 2/256 tolerance.
-// func TestYCbCrRoundtrip(t *testing.T) {
-// 	for r := 0; r < 256; r += 7 {
-// 		for g := 0; g < 256; g += 5 {
-// 			for b := 0; b < 256; b += 3 {
-// 				r0, g0, b0 := uint8(r), uint8(g), uint8(b)
-// 				y, cb, cr := RGBToYCbCr(r0, g0, b0)
-// 				r1, g1, b1 := YCbCrToRGB(y, cb, cr)
-// 				if delta(r0, r1) > 2 || delta(g0, g1) > 2 || delta(b0, b1) > 2 {
-// 					t.Fatalf("\nr0, g0, b0 = %d, %d, %d\ny,  cb, cr = %d, %d, %d\nr1, g1, b1 = %d, %d, %d",
-// 						r0, g0, b0, y, cb, cr, r1, g1, b1)
-// 				}
-// 			}
-// 		}
-// 	}
-// }
+/// test_ycbcr_to_rgb_consistency tests that calling the rgba method (16 bit color)
+/// then truncating to 8 bits is equivalent to calling the ycbcr_to_rgb function (8
+/// bit color).
+#[test]
+fn test_ycbcr_to_rgb_consistency() {
+    for y in (0..256).step_by(7) {
+        for cb in (0..256).step_by(5) {
+            for cr in (0..256).step_by(3) {
+                let x = YCbCr::new(y as u8, cb as u8, cr as u8);
+                let (r0, g0, b0, _) = x.rgba();
+                let (r1, g1, b1) = ((r0 >> 8) as u8, (g0 >> 8) as u8, (b0 >> 8) as u8);
+                let (r2, g2, b2) = ycbcr_to_rgb(x.y, x.cb, x.cr);
+                assert!(
+                    r1 == r2 && g1 == g2 && b1 == b2,
+                    "y, cb, cr = {}, {}, {}\nr1, g1, b1 = {}, {}, {}\nr2, g2, b2 = {}, {}, {}",
+                    y,
+                    cb,
+                    cr,
+                    r1,
+                    g1,
+                    b1,
+                    r2,
+                    g2,
+                    b2
+                );
+            }
+        }
+    }
+}
 
-// // TestYCbCrToRGBConsistency tests that calling the RGBA method (16 bit color)
-// // then truncating to 8 bits is equivalent to calling the YCbCrToRGB function (8
-// // bit color).
-// func TestYCbCrToRGBConsistency(t *testing.T) {
-// 	for y := 0; y < 256; y += 7 {
-// 		for cb := 0; cb < 256; cb += 5 {
-// 			for cr := 0; cr < 256; cr += 3 {
-// 				x := YCbCr{uint8(y), uint8(cb), uint8(cr)}
-// 				r0, g0, b0, _ := x.rgba()
-// 				r1, g1, b1 := uint8(r0>>8), uint8(g0>>8), uint8(b0>>8)
-// 				r2, g2, b2 := YCbCrToRGB(x.y, x.Cb, x.Cr)
-// 				if r1 != r2 || g1 != g2 || b1 != b2 {
-// 					t.Fatalf("y, cb, cr = %d, %d, %d\nr1, g1, b1 = %d, %d, %d\nr2, g2, b2 = %d, %d, %d",
-// 						y, cb, cr, r1, g1, b1, r2, g2, b2)
-// 				}
-// 			}
-// 		}
-// 	}
-// }
+/// test_ycbcr_gray tests that YCbCr colors are a superset of Gray colors.
+#[test]
+fn test_ycbcr_gray() {
+    for i in 0..256 {
+        let c0 = YCbCr::new(i as u8, 0x80, 0x80);
+        let c1 = Gray::new(i as u8);
+        if let Err(err) = eq(&c0, &c1) {
+            panic!("i=0x{:02x}:\n{}", i, err);
+        }
+    }
+}
 
-// // TestYCbCrGray tests that YCbCr colors are a superset of Gray colors.
-// func TestYCbCrGray(t *testing.T) {
-// 	for i := 0; i < 256; i++ {
-// 		c0 := YCbCr{uint8(i), 0x80, 0x80}
-// 		c1 := Gray{uint8(i)}
-// 		if err := eq(c0, c1); err != nil {
-// 			t.Errorf("i=0x%02x:\n%v", i, err)
-// 		}
-// 	}
-// }
+/// test_nycbcra_alpha tests that NYCbCrA colors are a superset of Alpha colors.
+#[test]
+fn test_nycbcra_alpha() {
+    for i in 0..256 {
+        let c0 = NYCbCrA::new(0xff, 0x80, 0x80, i as u8);
+        let c1 = Alpha::new(i as u8);
+        if let Err(err) = eq(&c0, &c1) {
+            panic!("i=0x{:02x}:\n{}", i, err);
+        }
+    }
+}
 
-// // TestNYCbCrAAlpha tests that NYCbCrA colors are a superset of Alpha colors.
-// func TestNYCbCrAAlpha(t *testing.T) {
-// 	for i := 0; i < 256; i++ {
-// 		c0 := NYCbCrA{YCbCr{0xff, 0x80, 0x80}, uint8(i)}
-// 		c1 := Alpha{uint8(i)}
-// 		if err := eq(c0, c1); err != nil {
-// 			t.Errorf("i=0x%02x:\n%v", i, err)
-// 		}
-// 	}
-// }
+/// test_nycbcra_ycbcr tests that NYCbCrA colors are a superset of YCbCr colors.
+#[test]
+fn test_nycbcra_ycbcr() {
+    for i in 0..256 {
+        let c0 = NYCbCrA::new(i as u8, 0x40, 0xc0, 0xff);
+        let c1 = YCbCr::new(i as u8, 0x40, 0xc0);
+        if let Err(err) = eq(&c0, &c1) {
+            panic!("i=0x{:02x}:\n{}", i, err);
+        }
+    }
+}
 
-// // TestNYCbCrAYCbCr tests that NYCbCrA colors are a superset of YCbCr colors.
-// func TestNYCbCrAYCbCr(t *testing.T) {
-// 	for i := 0; i < 256; i++ {
-// 		c0 := NYCbCrA{YCbCr{uint8(i), 0x40, 0xc0}, 0xff}
-// 		c1 := YCbCr{uint8(i), 0x40, 0xc0}
-// 		if err := eq(c0, c1); err != nil {
-// 			t.Errorf("i=0x%02x:\n%v", i, err)
-// 		}
-// 	}
-// }
+#[test]
+fn test_ycbcr_models() {
+    use super::{Color, Model};
 
-// // TestCMYKRoundtrip tests that a subset of RGB space can be converted to CMYK
-// // and back to within 1/256 tolerance.
-// func TestCMYKRoundtrip(t *testing.T) {
-// 	for r := 0; r < 256; r += 7 {
-// 		for g := 0; g < 256; g += 5 {
-// 			for b := 0; b < 256; b += 3 {
-// 				r0, g0, b0 := uint8(r), uint8(g), uint8(b)
-// 				c, m, y, k := RGBToCMYK(r0, g0, b0)
-// 				r1, g1, b1 := CMYKToRGB(c, m, y, k)
-// 				if delta(r0, r1) > 1 || delta(g0, g1) > 1 || delta(b0, b1) > 1 {
-// 					t.Fatalf("\nr0, g0, b0 = %d, %d, %d\nc, m, y, k = %d, %d, %d, %d\nr1, g1, b1 = %d, %d, %d",
-// 						r0, g0, b0, c, m, y, k, r1, g1, b1)
-// 				}
-// 			}
-// 		}
-// 	}
-// }
+    let c = Color::new_rgba(0x12, 0x34, 0x56, 0xff);
+    let (y, cb, cr) = rgb_to_ycbcr(0x12, 0x34, 0x56);
+    assert_eq!(
+        Model::YCbCrModel.convert(&c),
+        Color::YCbCr(YCbCr::new(y, cb, cr))
+    );
+    assert_eq!(
+        Model::NYCbCrAModel.convert(&c),
+        Color::NYCbCrA(NYCbCrA::new(y, cb, cr, 0xff))
+    );
+
+    // Conversion from a color of the same model is the identity.
+    let c = Color::YCbCr(YCbCr::new(1, 2, 3));
+    assert_eq!(Model::YCbCrModel.convert(&c), c);
+    assert_eq!(
+        Model::NYCbCrAModel.convert(&c),
+        Color::NYCbCrA(NYCbCrA::new(1, 2, 3, 0xff))
+    );
+    let c = Color::NYCbCrA(NYCbCrA::new(1, 2, 3, 4));
+    assert_eq!(Model::NYCbCrAModel.convert(&c), c);
+
+    // NYCbCrA is not alpha-premultiplied.
+    let c = Color::new_nrgba(0xff, 0xff, 0xff, 0x80);
+    assert_eq!(
+        Model::NYCbCrAModel.convert(&c),
+        Color::NYCbCrA(NYCbCrA::new(0xff, 0x80, 0x80, 0x80))
+    );
+}
+
+/// test_cmyk_roundtrip tests that a subset of RGB space can be converted to CMYK
+/// and back to within 1/256 tolerance.
+#[test]
+fn test_cmyk_roundtrip() {
+    for r in (0..256).step_by(7) {
+        for g in (0..256).step_by(5) {
+            for b in (0..256).step_by(3) {
+                let (r0, g0, b0) = (r as u8, g as u8, b as u8);
+                let (c, m, y, k) = rgb_to_cmyk(r0, g0, b0);
+                let (r1, g1, b1) = cmyk_to_rgb(c, m, y, k);
+                assert!(
+                    delta(r0, r1) <= 1 && delta(g0, g1) <= 1 && delta(b0, b1) <= 1,
+                    "\nr0, g0, b0 = {}, {}, {}\nc, m, y, k = {}, {}, {}, {}\nr1, g1, b1 = {}, {}, {}",
+                    r0, g0, b0, c, m, y, k, r1, g1, b1
+                );
+            }
+        }
+    }
+}
 
 // // TestCMYKToRGBConsistency tests that calling the RGBA method (16 bit color)
 // // then truncating to 8 bits is equivalent to calling the CMYKToRGB function (8
@@ -1,135 +1,275 @@
-// // Copyright 2011 The Go Authors. All rights reserved.
-// // Use of this source code is governed by a BSD-style
-// // license that can be found in the LICENSE file.
-
-// package image_test
-
-// import (
-// 	"bufio"
-// 	"fmt"
-// 	"image"
-// 	"image/color"
-// 	"os"
-// 	"testing"
-
-// 	_ "image/gif"
-// 	_ "image/jpeg"
-// 	_ "image/png"
-// )
-
-// type imageTest struct {
-// 	goldenFilename string
-// 	filename       string
-// 	tolerance      int
-// }
-
-// var imageTests = []imageTest{
-// 	{"testdata/video-001.png", "testdata/video-001.png", 0},
-// 	// GIF images are restricted to a 256-color palette and the conversion
-// 	// to GIF loses significant image quality.
-// 	{"testdata/video-001.png", "testdata/video-001.gif", 64 << 8},
-// 	{"testdata/video-001.png", "testdata/video-001.interlaced.gif", 64 << 8},
-// 	{"testdata/video-001.png", "testdata/video-001.5bpp.gif", 128 << 8},
-// 	// JPEG is a lossy format and hence needs a non-zero tolerance.
-// 	{"testdata/video-001.png", "testdata/video-001.jpeg", 8 << 8},
-// 	{"testdata/video-001.png", "testdata/video-001.progressive.jpeg", 8 << 8},
-// 	{"testdata/video-001.221212.png", "testdata/video-001.221212.jpeg", 8 << 8},
-// 	{"testdata/video-001.cmyk.png", "testdata/video-001.cmyk.jpeg", 8 << 8},
-// 	{"testdata/video-001.rgb.png", "testdata/video-001.rgb.jpeg", 8 << 8},
-// 	{"testdata/video-001.progressive.truncated.png", "testdata/video-001.progressive.truncated.jpeg", 8 << 8},
-// 	// Grayscale images.
-// 	{"testdata/video-005.gray.png", "testdata/video-005.gray.jpeg", 8 << 8},
-// 	{"testdata/video-005.gray.png", "testdata/video-005.gray.png", 0},
-// }
-
-// func decode(filename string) (image.Image, string, error) {
-// 	f, err := os.Open(filename)
-// 	if err != nil {
-// 		return nil, "", err
-// 	}
-// 	defer f.Close()
-// 	return image.Decode(bufio.NewReader(f))
-// }
-
-// func decodeConfig(filename string) (image.Config, string, error) {
-// 	f, err := os.Open(filename)
-// 	if err != nil {
-// 		return image.Config{}, "", err
-// 	}
-// 	defer f.Close()
-// 	return image.decode_config(bufio.NewReader(f))
-// }
-
-// func delta(u0, u1 uint32) int {
-// 	d := int(u0) - int(u1)
-// 	if d < 0 {
-// 		return -d
-// 	}
-// 	return d
-// }
-
-// func withinTolerance(c0, c1 color.Color, tolerance int) bool {
-// 	r0, g0, b0, a0 := c0.rgba()
-// 	r1, g1, b1, a1 := c1.rgba()
-// 	r := delta(r0, r1)
-// 	g := delta(g0, g1)
-// 	b := delta(b0, b1)
-// 	a := delta(a0, a1)
-// 	return r <= tolerance && g <= tolerance && b <= tolerance && a <= tolerance
-// }
-
-// func TestDecode(t *testing.T) {
-// 	rgba := func(c color.Color) string {
-// 		r, g, b, a := c.rgba()
-// 		return fmt.Sprintf("rgba = 0x%04x, 0x%04x, 0x%04x, 0x%04x for %T%v", r, g, b, a, c, c)
-// 	}
-
-// 	golden := make(map[string]image.Image)
-// loop:
-// 	for _, it := range imageTests {
-// 		g := golden[it.goldenFilename]
-// 		if g == nil {
-// 			var err error
-// 			g, _, err = decode(it.goldenFilename)
-// 			if err != nil {
-// 				t.Errorf("%s: %v", it.goldenFilename, err)
-// 				continue loop
-// 			}
-// 			golden[it.goldenFilename] = g
-// 		}
-// 		m, imageFormat, err := decode(it.filename)
-// 		if err != nil {
-// 			t.Errorf("%s: %v", it.filename, err)
-// 			continue loop
-// 		}
-// 		b := g.bounds()
-// 		if !b.Eq(m.bounds()) {
-// 			t.Errorf("%s: got bounds %v want %v", it.filename, m.bounds(), b)
-// 			continue loop
-// 		}
-// 		for y := b.min.y; y < b.max.y; y++ {
-// 			for x := b.min.x; x < b.max.x; x++ {
-// 				if !withinTolerance(g.At(x, y), m.At(x, y), it.tolerance) {
-// 					t.Errorf("%s: at (%d, %d):\ngot  %v\nwant %v",
-// 						it.filename, x, y, rgba(m.At(x, y)), rgba(g.At(x, y)))
-// 					continue loop
-// 				}
-// 			}
-// 		}
-// 		if imageFormat == "gif" {
-// 			// Each frame of a GIF can have a frame-local palette override the
-// 			// GIF-global palette. Thus, image.Decode can yield a different ColorModel
-// 			// than image.decode_config.
-// 			continue
-// 		}
-// 		c, _, err := decodeConfig(it.filename)
-// 		if err != nil {
-// 			t.Errorf("%s: %v", it.filename, err)
-// 			continue loop
-// 		}
-// 		if m.ColorModel() != c.ColorModel {
-// 			t.Errorf("%s: color models differ", it.filename)
-// 			continue loop
-// 		}
-// 	}
-// }
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::color::{self, ColorTrait};
+use super::{Config, Image, Img};
+
+struct ImageTest {
+    golden_filename: &'static str,
+    filename: &'static str,
+    tolerance: usize,
+}
+
+impl ImageTest {
+    const fn new(golden_filename: &'static str, filename: &'static str, tolerance: usize) -> Self {
+        Self {
+            golden_filename,
+            filename,
+            tolerance,
+        }
+    }
+}
+
+const IMAGE_TESTS: &[ImageTest] = &[
+    ImageTest::new(
+        "src/image/testdata/video-001.png",
+        "src/image/testdata/video-001.png",
+        0,
+    ),
+    // GIF images are restricted to a 256-color palette and the conversion
+    // to GIF loses significant image quality.
+    ImageTest::new(
+        "src/image/testdata/video-001.png",
+        "src/image/testdata/video-001.gif",
+        64 << 8,
+    ),
+    ImageTest::new(
+        "src/image/testdata/video-001.png",
+        "src/image/testdata/video-001.interlaced.gif",
+        64 << 8,
+    ),
+    ImageTest::new(
+        "src/image/testdata/video-001.png",
+        "src/image/testdata/video-001.5bpp.gif",
+        128 << 8,
+    ),
+    // JPEG is a lossy format and hence needs a non-zero tolerance.
+    ImageTest::new(
+        "src/image/testdata/video-001.png",
+        "src/image/testdata/video-001.jpeg",
+        8 << 8,
+    ),
+    ImageTest::new(
+        "src/image/testdata/video-001.png",
+        "src/image/testdata/video-001.progressive.jpeg",
+        8 << 8,
+    ),
+    ImageTest::new(
+        "src/image/testdata/video-001.221212.png",
+        "src/image/testdata/video-001.221212.jpeg",
+        8 << 8,
+    ),
+    ImageTest::new(
+        "src/image/testdata/video-001.cmyk.png",
+        "src/image/testdata/video-001.cmyk.jpeg",
+        8 << 8,
+    ),
+    ImageTest::new(
+        "src/image/testdata/video-001.rgb.png",
+        "src/image/testdata/video-001.rgb.jpeg",
+        8 << 8,
+    ),
+    ImageTest::new(
+        "src/image/testdata/video-001.progressive.truncated.png",
+        "src/image/testdata/video-001.progressive.truncated.jpeg",
+        8 << 8,
+    ),
+    // Grayscale images.
+    ImageTest::new(
+        "src/image/testdata/video-005.gray.png",
+        "src/image/testdata/video-005.gray.jpeg",
+        8 << 8,
+    ),
+    ImageTest::new(
+        "src/image/testdata/video-005.gray.png",
+        "src/image/testdata/video-005.gray.png",
+        0,
+    ),
+];
+
+fn decode(filename: &str) -> std::io::Result<(Box<Img>, &'static str)> {
+    let f = std::fs::File::open(filename)?;
+    super::decode(&mut std::io::BufReader::new(f))
+}
+
+fn decode_config(filename: &str) -> std::io::Result<(Config, &'static str)> {
+    let f = std::fs::File::open(filename)?;
+    super::decode_config(&mut std::io::BufReader::new(f))
+}
+
+fn delta(u0: u32, u1: u32) -> usize {
+    (u0 as isize - u1 as isize).unsigned_abs()
+}
+
+fn within_tolerance(c0: &color::Color, c1: &color::Color, tolerance: usize) -> bool {
+    let (r0, g0, b0, a0) = c0.rgba();
+    let (r1, g1, b1, a1) = c1.rgba();
+    let r = delta(r0, r1);
+    let g = delta(g0, g1);
+    let b = delta(b0, b1);
+    let a = delta(a0, a1);
+    r <= tolerance && g <= tolerance && b <= tolerance && a <= tolerance
+}
+
+#[test]
+fn test_decode() {
+    let rgba = |c: &color::Color| -> String {
+        let (r, g, b, a) = c.rgba();
+        format!(
+            "rgba = 0x{:04x}, 0x{:04x}, 0x{:04x}, 0x{:04x} for {:?}",
+            r, g, b, a, c
+        )
+    };
+
+    let mut golden = std::collections::HashMap::new();
+    for it in IMAGE_TESTS {
+        let g = golden
+            .entry(it.golden_filename)
+            .or_insert_with(|| decode(it.golden_filename).unwrap().0);
+        let (m, image_format) = decode(it.filename).unwrap();
+        let b = g.bounds();
+        assert!(
+            b.eq(m.bounds()),
+            "{}: got bounds {:?} want {:?}",
+            it.filename,
+            m.bounds(),
+            b
+        );
+        for y in b.min.y..b.max.y {
+            for x in b.min.x..b.max.x {
+                assert!(
+                    within_tolerance(&g.at(x, y), &m.at(x, y), it.tolerance),
+                    "{}: at ({}, {}):\ngot  {}\nwant {}",
+                    it.filename,
+                    x,
+                    y,
+                    rgba(&m.at(x, y)),
+                    rgba(&g.at(x, y))
+                );
+            }
+        }
+        if image_format == "gif" {
+            // Each frame of a GIF can have a frame-local palette override the
+            // GIF-global palette. Thus, image.Decode can yield a different ColorModel
+            // than image.decode_config.
+            continue;
+        }
+        let (c, _) = decode_config(it.filename).unwrap();
+        assert!(
+            m.color_model() == c.color_model,
+            "{}: color models differ",
+            it.filename
+        );
+    }
+}
+
+#[test]
+fn test_decode_format_name() {
+    let (m, name) = decode("src/image/testdata/video-001.png").unwrap();
+    assert_eq!(name, "png");
+    let (c, name) = decode_config("src/image/testdata/video-001.png").unwrap();
+    assert_eq!(name, "png");
+    assert_eq!(c.width, m.bounds().dx());
+    assert_eq!(c.height, m.bounds().dy());
+}
+
+#[test]
+fn test_decode_unknown_format() {
+    for data in [b"".as_slice(), b"\x89PN", b"not an image"] {
+        let err = super::decode(&mut &data[..]).err().unwrap();
+        assert_eq!(err.to_string(), super::ERR_FORMAT_MSG);
+        let err = super::decode_config(&mut &data[..]).err().unwrap();
+        assert_eq!(err.to_string(), super::ERR_FORMAT_MSG);
+    }
+
+    // When the reader already buffers the longest magic, the sniffed bytes
+    // are not consumed, so the caller can retry.
+    let data = b"not an image";
+    let mut r = std::io::BufReader::new(&data[..]);
+    assert!(super::decode(&mut r).is_err());
+    assert!(super::decode_config(&mut r).is_err());
+    let mut rest = Vec::new();
+    std::io::Read::read_to_end(&mut r, &mut rest).unwrap();
+    assert_eq!(rest, data);
+}
+
+/// ShortReader returns at most n bytes per read call, like a socket or a pipe.
+struct ShortReader<'a> {
+    data: &'a [u8],
+    n: usize,
+}
+
+impl std::io::Read for ShortReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.n.min(buf.len()).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+#[test]
+fn test_decode_short_reads() {
+    let data = crate::os::read_file("src/image/testdata/video-001.png").unwrap();
+    let (want, _) = super::decode(&mut data.as_slice()).unwrap();
+    for n in [1, 3, 4, 7] {
+        let mut r = std::io::BufReader::new(ShortReader { data: &data, n });
+        let (m, name) = super::decode(&mut r).unwrap();
+        assert_eq!(name, "png", "n = {}", n);
+        assert_eq!(m.bounds(), want.bounds());
+        assert_eq!(m.pix(), want.pix());
+
+        let mut r = std::io::BufReader::new(ShortReader { data: &data, n });
+        let (c, name) = super::decode_config(&mut r).unwrap();
+        assert_eq!(name, "png", "n = {}", n);
+        assert_eq!(c.width, want.bounds().dx());
+    }
+}
+
+#[test]
+fn test_register_format_wildcard() {
+    // The decoder reports the bytes it sees, to check that the sniffed
+    // bytes are not lost.
+    fn decode_test_format(r: &mut dyn std::io::BufRead) -> std::io::Result<Box<Img>> {
+        let mut data = Vec::new();
+        r.read_to_end(&mut data)?;
+        let mut m = super::Gray::new(&super::rect(0, 0, data.len() as isize, 1));
+        for (i, v) in data.iter().enumerate() {
+            m.set(i as isize, 0, &color::Color::Gray(color::Gray { y: *v }));
+        }
+        Ok(Box::new(Img::Gray(m)))
+    }
+    fn decode_test_format_config(r: &mut dyn std::io::BufRead) -> std::io::Result<Config> {
+        let mut data = Vec::new();
+        r.read_to_end(&mut data)?;
+        Ok(Config {
+            color_model: color::Model::GrayModel,
+            width: data.len(),
+            height: 1,
+        })
+    }
+    super::register_format(
+        "test?format",
+        b"TE?T",
+        decode_test_format,
+        decode_test_format_config,
+    );
+
+    for data in [b"TEST".as_slice(), b"TEXT", b"TE\x00T and more data"] {
+        let (m, name) = super::decode(&mut &data[..]).unwrap();
+        assert_eq!(name, "test?format");
+        assert_eq!(m.pix(), data);
+        let (c, name) = super::decode_config(&mut &data[..]).unwrap();
+        assert_eq!(name, "test?format");
+        assert_eq!(c.width, data.len());
+    }
+    for data in [b"TE".as_slice(), b"TAST", b"te?t"] {
+        assert!(super::decode(&mut &data[..]).is_err());
+    }
+
+    // PNG is still registered.
+    let (_, name) = decode("src/image/testdata/video-001.png").unwrap();
+    assert_eq!(name, "png");
+}
@@ -4,6 +4,7 @@
 // license that can be found in the LICENSE file.
 
 use crate::compat;
+use crate::image::color::{self, ColorTrait};
 use crate::image::{self, Image, Img, Point, Rectangle};
 
 // import (
@@ -31,15 +32,15 @@ use crate::image::{self, Image, Img, Point, Rectangle};
 // 	SetRGBA64(x, y int, c color.RGBA64)
 // }
 
-// // Quantizer produces a palette for an image.
-// type Quantizer interface {
-// 	// Quantize appends up to cap(p) - len(p) colors to p and returns the
-// 	// updated palette suitable for converting m to a paletted image.
-// 	Quantize(p color.Palette, m image.Image) color.Palette
-// }
+/// Quantizer produces a palette for an image.
+pub trait Quantizer {
+    /// quantize appends up to num_colors - p.colors.len() colors to p and
+    /// returns the updated palette suitable for converting m to a paletted image.
+    fn quantize(&self, p: color::Palette, num_colors: usize, m: &Img) -> color::Palette;
+}
 
 /// Op is a Porter-Duff compositing operator.
-#[derive(PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Op {
     /// Over specifies ``(src in mask) over dst''.
     Over,
@@ -47,32 +48,37 @@ pub enum Op {
     Src,
 }
 
-// // draw implements the Drawer interface by calling the draw function with this
-// // Op.
-// fn (op: Op) draw(dst: &mut Img, r: &Rectangle, src: &Img, sp: &Point) {
-// 	draw_mask(dst, r, src, sp, nil, image::Point{}, op)
-// }
-
-// // Drawer contains the draw method.
-// type Drawer interface {
-// 	// draw aligns r.min in dst with sp in src and then replaces the
-// 	// rectangle r in dst with the result of drawing src on dst.
-// 	draw(dst: &mut Img, r: &Rectangle, src: &Img, sp: &Point)
-// }
-
-// // FloydSteinberg is a Drawer that is the Src Op with Floyd-Steinberg error
-// // diffusion.
-// var FloydSteinberg Drawer = floydSteinberg{}
+impl Drawer for Op {
+    /// draw implements the Drawer trait by calling the draw function with this
+    /// Op.
+    fn draw(&self, dst: &mut Img, r: &Rectangle, src: &Img, sp: &Point) {
+        draw_mask(dst, r, src, sp, None, &Point::new(0, 0), *self)
+    }
+}
 
-// type floydSteinberg struct{}
+/// Drawer contains the draw method.
+pub trait Drawer {
+    /// draw aligns r.min in dst with sp in src and then replaces the
+    /// rectangle r in dst with the result of drawing src on dst.
+    fn draw(&self, dst: &mut Img, r: &Rectangle, src: &Img, sp: &Point);
+}
 
-// fn (floydSteinberg) draw(dst: &mut Img, r: &Rectangle, src: &Img, sp: &Point) {
-// 	clip(dst, &r, src, &sp, nil, nil)
-// 	if r.empty() {
-// 		return
-// 	}
-// 	drawPaletted(dst, r, src, sp, true)
-// }
+/// FloydSteinberg is a Drawer that is the Src Op with Floyd-Steinberg error
+/// diffusion.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FloydSteinberg;
+
+impl Drawer for FloydSteinberg {
+    fn draw(&self, dst: &mut Img, r: &Rectangle, src: &Img, sp: &Point) {
+        let mut r = *r;
+        let mut sp = *sp;
+        clip(dst, &mut r, src, &mut sp, None, &mut Point::new(0, 0));
+        if r.empty() {
+            return;
+        }
+        draw_paletted(dst, &r, src, &sp, true);
+    }
+}
 
 /// clip clips r against each image's bounds (after translating into the
 /// destination image's coordinate space) and shifts the points sp and mp by
@@ -260,11 +266,17 @@ pub fn draw_mask(
         // 					copy(dst0.pix[i0:i1], firstRow)
         // 				}
         // 				return
-        // 			} else if !processBackward(dst, r, src, sp) {
-        // 				drawPaletted(dst0, r, src, sp, false)
-        // 				return
         // 			}
         // 		}
+        Img::Paletted(_) => {
+            if op == Op::Src && mask.is_none() {
+                // dst and src cannot overlap, so there is no need to process
+                // backward.
+                draw_paletted(dst, &r, src, &sp, false);
+            } else {
+                unimplemented!();
+            }
+        }
         Img::NRGBA(dst) => {
             if op == Op::Src && mask.is_none() {
                 if let Img::NRGBA(src) = src {
@@ -994,157 +1006,116 @@ fn draw_nrgba_src(dst: &mut image::RGBA, r: &Rectangle, src: &image::NRGBA, sp:
 // 	}
 // }
 
-// // clamp clamps i to the interval [0, 0xffff].
-// fn clamp(i int32) int32 {
-// 	if i < 0 {
-// 		return 0
-// 	}
-// 	if i > 0xffff {
-// 		return 0xffff
-// 	}
-// 	return i
-// }
-
-// // sqDiff returns the squared-difference of x and y, shifted by 2 so that
-// // adding four of those won't overflow a uint32.
-// //
-// // x and y are both assumed to be in the range [0, 0xffff].
-// fn sqDiff(x, y int32) uint32 {
-// 	// This is an optimized code relying on the overflow/wrap around
-// 	// properties of unsigned integers operations guaranteed by the language
-// 	// spec. See sqDiff from the image/color package for more details.
-// 	d := uint32(x - y)
-// 	return (d * d) >> 2
-// }
-
-// fn drawPaletted(dst: &mut Img, r: &Rectangle, src: &Img, sp: &Point, floydSteinberg bool) {
-// 	// TODO(nigeltao): handle the case where the dst and src overlap.
-// 	// Does it even make sense to try and do Floyd-Steinberg whilst
-// 	// walking the image backward (right-to-left bottom-to-top)?
-
-// 	// If dst is an *image.Paletted, we have a fast path for dst.Set and
-// 	// dst.At. The dst.Set equivalent is a batch version of the algorithm
-// 	// used by color.Palette's Index method in image/color/color.go, plus
-// 	// optional Floyd-Steinberg error diffusion.
-// 	palette, pix, stride := [][4]int32(nil), [u8](nil), 0
-// 	if p, ok := dst.(*image.Paletted); ok {
-// 		palette = make([][4]int32, len(p.Palette))
-// 		for i, col := range p.Palette {
-// 			r, g, b, a := col.RGBA()
-// 			palette[i][0] = int32(r)
-// 			palette[i][1] = int32(g)
-// 			palette[i][2] = int32(b)
-// 			palette[i][3] = int32(a)
-// 		}
-// 		pix, stride = p.pix[p.pix_offset(r.min.x, r.min.y)..], p.stride;
-// 	}
-
-// 	// quantErrorCurr and quantErrorNext are the Floyd-Steinberg quantization
-// 	// errors that have been propagated to the pixels in the current and next
-// 	// rows. The +2 simplifies calculation near the edges.
-// 	var quantErrorCurr, quantErrorNext [][4]int32
-// 	if floydSteinberg {
-// 		quantErrorCurr = make([][4]int32, r.dx()+2)
-// 		quantErrorNext = make([][4]int32, r.dx()+2)
-// 	}
-// 	pxRGBA := fn(x, y int) (r, g, b, a uint32) { return src.At(x, y).RGBA() }
-// 	// Fast paths for special cases to avoid excessive use of the color.Color
-// 	// interface which escapes to the heap but need to be discovered for
-// 	// each pixel on r. See also https://golang.org/issues/15759.
-// 	switch src0 := src.(type) {
-// 	case *image.RGBA:
-// 		pxRGBA = fn(x, y int) (r, g, b, a uint32) { return src0.RGBAAt(x, y).RGBA() }
-// 	case *image.NRGBA:
-// 		pxRGBA = fn(x, y int) (r, g, b, a uint32) { return src0.NRGBAAt(x, y).RGBA() }
-// 	case *image.YCbCr:
-// 		pxRGBA = fn(x, y int) (r, g, b, a uint32) { return src0.YCbCrAt(x, y).RGBA() }
-// 	}
-
-// 	// Loop over each source pixel.
-// 	out := color.RGBA64{A: 0xffff}
-// 	for y := 0; y != r.dy(); y++ {
-// 		for x := 0; x != r.dx(); x++ {
-// 			// er, eg and eb are the pixel's R,G,B values plus the
-// 			// optional Floyd-Steinberg error.
-// 			sr, sg, sb, sa := pxRGBA(sp.x+x, sp.y+y)
-// 			er, eg, eb, ea := int32(sr), int32(sg), int32(sb), int32(sa)
-// 			if floydSteinberg {
-// 				er = clamp(er + quantErrorCurr[x+1][0]/16)
-// 				eg = clamp(eg + quantErrorCurr[x+1][1]/16)
-// 				eb = clamp(eb + quantErrorCurr[x+1][2]/16)
-// 				ea = clamp(ea + quantErrorCurr[x+1][3]/16)
-// 			}
+/// sq_diff returns the squared-difference of x and y, shifted by 2 so that
+/// adding four of those won't overflow a u32.
+///
+/// x and y are both assumed to be in the range [0, 0xffff].
+pub(super) fn sq_diff(x: i32, y: i32) -> u32 {
+    // This is an optimized code relying on the overflow/wrap around
+    // properties of unsigned integers operations. See sq_diff from the
+    // image::color module for more details.
+    let d = x.wrapping_sub(y) as u32;
+    d.wrapping_mul(d) >> 2
+}
 
-// 			if palette != nil {
-// 				// Find the closest palette color in Euclidean R,G,B,A space:
-// 				// the one that minimizes sum-squared-difference.
-// 				// TODO(nigeltao): consider smarter algorithms.
-// 				bestIndex, bestSum := 0, uint32(1<<32-1)
-// 				for index, p := range palette {
-// 					sum := sqDiff(er, p[0]) + sqDiff(eg, p[1]) + sqDiff(eb, p[2]) + sqDiff(ea, p[3])
-// 					if sum < bestSum {
-// 						bestIndex, bestSum = index, sum
-// 						if sum == 0 {
-// 							break
-// 						}
-// 					}
-// 				}
-// 				pix[y*stride+x] = byte(bestIndex)
+fn draw_paletted(dst: &mut Img, r: &Rectangle, src: &Img, sp: &Point, floyd_steinberg: bool) {
+    // If dst is an Img::Paletted, we have a fast path for dst.set and
+    // dst.at. The dst.set equivalent is a batch version of the algorithm
+    // used by color::Palette's index method in image/color/color.rs, plus
+    // optional Floyd-Steinberg error diffusion.
+    let (mut palette, mut pix0, mut stride) = (Vec::new(), 0, 0);
+    if let Img::Paletted(p) = dst {
+        palette = p
+            .palette
+            .colors
+            .iter()
+            .map(|col| {
+                let (r, g, b, a) = col.rgba();
+                [r as i32, g as i32, b as i32, a as i32]
+            })
+            .collect();
+        (pix0, stride) = (p.pix_offset(r.min.x, r.min.y), p.stride);
+    }
+    let paletted = matches!(dst, Img::Paletted(_));
+
+    // quant_error_curr and quant_error_next are the Floyd-Steinberg quantization
+    // errors that have been propagated to the pixels in the current and next
+    // rows. The +2 simplifies calculation near the edges.
+    let (mut quant_error_curr, mut quant_error_next) = if floyd_steinberg {
+        (vec![[0i32; 4]; r.dx() + 2], vec![[0i32; 4]; r.dx() + 2])
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    // Loop over each source pixel.
+    for y in 0..r.dy() {
+        for x in 0..r.dx() {
+            // er, eg and eb are the pixel's R,G,B values plus the
+            // optional Floyd-Steinberg error.
+            let (sr, sg, sb, sa) = src.at(sp.x + x as isize, sp.y + y as isize).rgba();
+            let (mut er, mut eg, mut eb, mut ea) = (sr as i32, sg as i32, sb as i32, sa as i32);
+            if floyd_steinberg {
+                er = (er + quant_error_curr[x + 1][0] / 16).clamp(0, 0xffff);
+                eg = (eg + quant_error_curr[x + 1][1] / 16).clamp(0, 0xffff);
+                eb = (eb + quant_error_curr[x + 1][2] / 16).clamp(0, 0xffff);
+                ea = (ea + quant_error_curr[x + 1][3] / 16).clamp(0, 0xffff);
+            }
 
-// 				if !floydSteinberg {
-// 					continue
-// 				}
-// 				er -= palette[bestIndex][0]
-// 				eg -= palette[bestIndex][1]
-// 				eb -= palette[bestIndex][2]
-// 				ea -= palette[bestIndex][3]
+            if paletted {
+                // Find the closest palette color in Euclidean R,G,B,A space:
+                // the one that minimizes sum-squared-difference.
+                let (mut best_index, mut best_sum) = (0, u32::MAX);
+                for (index, p) in palette.iter().enumerate() {
+                    let sum = sq_diff(er, p[0])
+                        + sq_diff(eg, p[1])
+                        + sq_diff(eb, p[2])
+                        + sq_diff(ea, p[3]);
+                    if sum < best_sum {
+                        (best_index, best_sum) = (index, sum);
+                        if sum == 0 {
+                            break;
+                        }
+                    }
+                }
+                if let Img::Paletted(p) = dst {
+                    p.pix[pix0 + y * stride + x] = best_index as u8;
+                }
 
-// 			} else {
-// 				out.R = uint16(er)
-// 				out.G = uint16(eg)
-// 				out.B = uint16(eb)
-// 				out.A = uint16(ea)
-// 				// The third argument is &out instead of out (and out is
-// 				// declared outside of the inner loop) to avoid the implicit
-// 				// conversion to color.Color here allocating memory in the
-// 				// inner loop if sizeof(color.RGBA64) > sizeof(uintptr).
-// 				dst.Set(r.min.x+x, r.min.y+y, &out)
-
-// 				if !floydSteinberg {
-// 					continue
-// 				}
-// 				sr, sg, sb, sa = dst.At(r.min.x+x, r.min.y+y).RGBA()
-// 				er -= int32(sr)
-// 				eg -= int32(sg)
-// 				eb -= int32(sb)
-// 				ea -= int32(sa)
-// 			}
+                if !floyd_steinberg {
+                    continue;
+                }
+                er -= palette[best_index][0];
+                eg -= palette[best_index][1];
+                eb -= palette[best_index][2];
+                ea -= palette[best_index][3];
+            } else {
+                let out = color::Color::new_rgba64(er as u16, eg as u16, eb as u16, ea as u16);
+                let (dx, dy) = (r.min.x + x as isize, r.min.y + y as isize);
+                dst.set(dx, dy, &out);
+
+                if !floyd_steinberg {
+                    continue;
+                }
+                let (sr, sg, sb, sa) = dst.at(dx, dy).rgba();
+                er -= sr as i32;
+                eg -= sg as i32;
+                eb -= sb as i32;
+                ea -= sa as i32;
+            }
 
-// 			// Propagate the Floyd-Steinberg quantization error.
-// 			quantErrorNext[x+0][0] += er * 3
-// 			quantErrorNext[x+0][1] += eg * 3
-// 			quantErrorNext[x+0][2] += eb * 3
-// 			quantErrorNext[x+0][3] += ea * 3
-// 			quantErrorNext[x+1][0] += er * 5
-// 			quantErrorNext[x+1][1] += eg * 5
-// 			quantErrorNext[x+1][2] += eb * 5
-// 			quantErrorNext[x+1][3] += ea * 5
-// 			quantErrorNext[x+2][0] += er * 1
-// 			quantErrorNext[x+2][1] += eg * 1
-// 			quantErrorNext[x+2][2] += eb * 1
-// 			quantErrorNext[x+2][3] += ea * 1
-// 			quantErrorCurr[x+2][0] += er * 7
-// 			quantErrorCurr[x+2][1] += eg * 7
-// 			quantErrorCurr[x+2][2] += eb * 7
-// 			quantErrorCurr[x+2][3] += ea * 7
-// 		}
+            // Propagate the Floyd-Steinberg quantization error.
+            for (i, e) in [er, eg, eb, ea].into_iter().enumerate() {
+                quant_error_next[x][i] += e * 3;
+                quant_error_next[x + 1][i] += e * 5;
+                quant_error_next[x + 2][i] += e;
+                quant_error_curr[x + 2][i] += e * 7;
+            }
+        }
 
-// 		// Recycle the quantization error buffers.
-// 		if floydSteinberg {
-// 			quantErrorCurr, quantErrorNext = quantErrorNext, quantErrorCurr
-// 			for i := range quantErrorNext {
-// 				quantErrorNext[i] = [4]int32{}
-// 			}
-// 		}
-// 	}
-// }
+        // Recycle the quantization error buffers.
+        if floyd_steinberg {
+            std::mem::swap(&mut quant_error_curr, &mut quant_error_next);
+            quant_error_next.fill([0; 4]);
+        }
+    }
+}
@@ -1,3 +1,13 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2010 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::draw::sq_diff;
+use super::{draw, Drawer, FloydSteinberg, Op};
+use crate::image::color::{self, Color};
+use crate::image::{png, rect, Gray16, Image, Img, Paletted, Point};
+
 // // Copyright 2010 The Go Authors. All rights reserved.
 // // Use of this source code is governed by a BSD-style
 // // license that can be found in the LICENSE file.
@@ -686,125 +696,117 @@
 // 	}
 // }
 
-// // TestFloydSteinbergCheckerboard tests that the result of Floyd-Steinberg
-// // error diffusion of a uniform 50% gray source image with a black-and-white
-// // palette is a checkerboard pattern.
-// func TestFloydSteinbergCheckerboard(t *testing.T) {
-// 	b := image.Rect(0, 0, 640, 480)
-// 	// We can't represent 50% exactly, but 0x7fff / 0xffff is close enough.
-// 	src := &image.Uniform{color.Gray16{0x7fff}}
-// 	dst := image.NewPaletted(b, color.Palette{color.Black, color.White})
-// 	FloydSteinberg.Draw(dst, b, src, image.Point{})
-// 	nErr := 0
-// 	for y := b.Min.Y; y < b.Max.Y; y++ {
-// 		for x := b.Min.X; x < b.Max.X; x++ {
-// 			got := dst.Pix[dst.PixOffset(x, y)]
-// 			want := uint8(x+y) % 2
-// 			if got != want {
-// 				t.Errorf("at (%d, %d): got %d, want %d", x, y, got, want)
-// 				if nErr++; nErr == 10 {
-// 					t.Fatal("there may be more errors")
-// 				}
-// 			}
-// 		}
-// 	}
-// }
-
-// // embeddedPaletted is an Image that behaves like an *image.Paletted but whose
-// // type is not *image.Paletted.
-// type embeddedPaletted struct {
-// 	*image.Paletted
-// }
-
-// // TestPaletted tests that the drawPaletted function behaves the same
-// // regardless of whether dst is an *image.Paletted.
-// func TestPaletted(t *testing.T) {
-// 	f, err := os.Open("../testdata/video-001.png")
-// 	if err != nil {
-// 		t.Fatalf("open: %v", err)
-// 	}
-// 	defer f.Close()
-// 	video001, err := png.Decode(f)
-// 	if err != nil {
-// 		t.Fatalf("decode: %v", err)
-// 	}
-// 	b := video001.Bounds()
-
-// 	cgaPalette := color.Palette{
-// 		color.RGBA{0x00, 0x00, 0x00, 0xff},
-// 		color.RGBA{0x55, 0xff, 0xff, 0xff},
-// 		color.RGBA{0xff, 0x55, 0xff, 0xff},
-// 		color.RGBA{0xff, 0xff, 0xff, 0xff},
-// 	}
-// 	drawers := map[string]Drawer{
-// 		"src":             Src,
-// 		"floyd-steinberg": FloydSteinberg,
-// 	}
-// 	sources := map[string]image.Image{
-// 		"uniform":  &image.Uniform{color.RGBA{0xff, 0x7f, 0xff, 0xff}},
-// 		"video001": video001,
-// 	}
-
-// 	for dName, d := range drawers {
-// 	loop:
-// 		for sName, src := range sources {
-// 			dst0 := image.NewPaletted(b, cgaPalette)
-// 			dst1 := image.NewPaletted(b, cgaPalette)
-// 			d.Draw(dst0, b, src, image.Point{})
-// 			d.Draw(embeddedPaletted{dst1}, b, src, image.Point{})
-// 			for y := b.Min.Y; y < b.Max.Y; y++ {
-// 				for x := b.Min.X; x < b.Max.X; x++ {
-// 					if !eq(dst0.At(x, y), dst1.At(x, y)) {
-// 						t.Errorf("%s / %s: at (%d, %d), %v versus %v",
-// 							dName, sName, x, y, dst0.At(x, y), dst1.At(x, y))
-// 						continue loop
-// 					}
-// 				}
-// 			}
-// 		}
-// 	}
-// }
-
-// func TestSqDiff(t *testing.T) {
-// 	// This test is similar to the one from the image/color package, but
-// 	// sqDiff in this package accepts int32 instead of uint32, so test it
-// 	// for appropriate input.
-
-// 	// canonical sqDiff implementation
-// 	orig := func(x, y int32) uint32 {
-// 		var d uint32
-// 		if x > y {
-// 			d = uint32(x - y)
-// 		} else {
-// 			d = uint32(y - x)
-// 		}
-// 		return (d * d) >> 2
-// 	}
-// 	testCases := []int32{
-// 		0,
-// 		1,
-// 		2,
-// 		0x0fffd,
-// 		0x0fffe,
-// 		0x0ffff,
-// 		0x10000,
-// 		0x10001,
-// 		0x10002,
-// 		0x7ffffffd,
-// 		0x7ffffffe,
-// 		0x7fffffff,
-// 		-0x7ffffffd,
-// 		-0x7ffffffe,
-// 		-0x80000000,
-// 	}
-// 	for _, x := range testCases {
-// 		for _, y := range testCases {
-// 			if got, want := sqDiff(x, y), orig(x, y); got != want {
-// 				t.Fatalf("sqDiff(%#x, %#x): got %d, want %d", x, y, got, want)
-// 			}
-// 		}
-// 	}
-// 	if err := quick.CheckEqual(orig, sqDiff, &quick.Config{MaxCountScale: 10}); err != nil {
-// 		t.Fatal(err)
-// 	}
-// }
+/// test_floyd_steinberg_checkerboard tests that the result of Floyd-Steinberg
+/// error diffusion of a uniform 50% gray source image with a black-and-white
+/// palette is a checkerboard pattern.
+#[test]
+fn test_floyd_steinberg_checkerboard() {
+    let b = rect(0, 0, 640, 480);
+    // We can't represent 50% exactly, but 0x7fff / 0xffff is close enough.
+    let mut src = Gray16::new(&b);
+    for p in src.pix.chunks_exact_mut(2) {
+        p.copy_from_slice(&[0x7f, 0xff]);
+    }
+    let src = Img::Gray16(src);
+    let mut dst = Img::Paletted(Paletted::new(
+        &b,
+        color::Palette {
+            colors: vec![Color::new_gray(0), Color::new_gray(0xff)],
+        },
+    ));
+    FloydSteinberg.draw(&mut dst, &b, &src, &Point::new(0, 0));
+    let Img::Paletted(dst) = dst else {
+        unreachable!()
+    };
+    for y in b.min.y..b.max.y {
+        for x in b.min.x..b.max.x {
+            let got = dst.pix[dst.pix_offset(x, y)];
+            let want = ((x + y) % 2) as u8;
+            assert_eq!(got, want, "at ({}, {})", x, y);
+        }
+    }
+}
+
+/// test_paletted tests that drawing onto a Paletted image picks, for every
+/// pixel, a color from the destination palette, and that the Src op picks
+/// the closest one.
+#[test]
+fn test_paletted() {
+    let f = std::fs::File::open("src/image/testdata/video-001.png").unwrap();
+    let video001 = png::decode(&mut std::io::BufReader::new(f)).unwrap();
+    let b = *video001.bounds();
+
+    let cga_palette = color::Palette {
+        colors: vec![
+            Color::new_rgba(0x00, 0x00, 0x00, 0xff),
+            Color::new_rgba(0x55, 0xff, 0xff, 0xff),
+            Color::new_rgba(0xff, 0x55, 0xff, 0xff),
+            Color::new_rgba(0xff, 0xff, 0xff, 0xff),
+        ],
+    };
+
+    let mut dst0 = Img::Paletted(Paletted::new(&b, cga_palette.clone()));
+    draw(&mut dst0, &b, &video001, &Point::new(0, 0), Op::Src);
+    for y in b.min.y..b.max.y {
+        for x in b.min.x..b.max.x {
+            let want = cga_palette.convert(&video001.at(x, y));
+            assert_eq!(dst0.at(x, y), want, "src: at ({}, {})", x, y);
+        }
+    }
+
+    let mut dst1 = Img::Paletted(Paletted::new(&b, cga_palette.clone()));
+    FloydSteinberg.draw(&mut dst1, &b, &video001, &Point::new(0, 0));
+    assert!(
+        dst0.pix() != dst1.pix(),
+        "floyd-steinberg: no error diffusion happened"
+    );
+    for y in b.min.y..b.max.y {
+        for x in b.min.x..b.max.x {
+            assert!(
+                cga_palette.colors.contains(&dst1.at(x, y)),
+                "floyd-steinberg: at ({}, {}) color is not in the palette",
+                x,
+                y
+            );
+        }
+    }
+}
+
+#[test]
+fn test_sq_diff() {
+    // This test is similar to the one from the image::color module, but
+    // sq_diff in this module accepts i32 instead of u32, so test it
+    // for appropriate input.
+
+    // canonical sq_diff implementation
+    let orig = |x: i32, y: i32| -> u32 {
+        let d = if x > y {
+            x.wrapping_sub(y) as u32
+        } else {
+            y.wrapping_sub(x) as u32
+        };
+        d.wrapping_mul(d) >> 2
+    };
+    let test_cases: &[i32] = &[
+        0,
+        1,
+        2,
+        0x0fffd,
+        0x0fffe,
+        0x0ffff,
+        0x10000,
+        0x10001,
+        0x10002,
+        0x7ffffffd,
+        0x7ffffffe,
+        0x7fffffff,
+        -0x7ffffffd,
+        -0x7ffffffe,
+        -0x80000000,
+    ];
+    for &x in test_cases {
+        for &y in test_cases {
+            assert_eq!(sq_diff(x, y), orig(x, y), "sq_diff({:#x}, {:#x})", x, y);
+        }
+    }
+}
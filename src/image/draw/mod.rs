@@ -10,4 +10,7 @@
 
 mod draw;
 
-pub use draw::{draw, Op};
+pub use draw::{draw, draw_mask, Drawer, FloydSteinberg, Op, Quantizer};
+
+#[cfg(test)]
+mod draw_test;
@@ -1,109 +1,153 @@
-// // Copyright 2010 The Go Authors. All rights reserved.
-// // Use of this source code is governed by a BSD-style
-// // license that can be found in the LICENSE file.
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2010 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
 
-// package image
+use super::{gif, jpeg, png, Config, Img};
+use crate::errors;
+use std::io::{BufRead, Read};
+use std::sync::{Mutex, OnceLock};
 
-// import (
-// 	"bufio"
-// 	"errors"
-// 	"io"
-// 	"sync"
-// 	"sync/atomic"
-// )
+/// ERR_FORMAT_MSG is an error message that will be returned as std::io::Error
+/// when decoding encountered an unknown format.
+pub const ERR_FORMAT_MSG: &str = "image: unknown format";
 
-// // ErrFormat indicates that decoding encountered an unknown format.
-// var ErrFormat = errors.New("image: unknown format")
+/// DecodeFn is a function that decodes an image in a particular format.
+pub type DecodeFn = fn(&mut dyn BufRead) -> std::io::Result<Box<Img>>;
 
-// // A format holds an image format's name, magic header and how to decode it.
-// type format struct {
-// 	name, magic  string
-// 	decode       func(io.Reader) (Image, error)
-// 	decodeConfig func(io.Reader) (Config, error)
-// }
+/// DecodeConfigFn is a function that decodes just the configuration
+/// of an image in a particular format.
+pub type DecodeConfigFn = fn(&mut dyn BufRead) -> std::io::Result<Config>;
 
-// // Formats is the list of registered formats.
-// var (
-// 	formatsMu     sync.Mutex
-// 	atomicFormats atomic.Value
-// )
+/// A Format holds an image format's name, magic header and how to decode it.
+#[derive(Clone, Copy)]
+struct Format {
+    name: &'static str,
+    magic: &'static [u8],
+    decode: DecodeFn,
+    decode_config: DecodeConfigFn,
+}
 
-// // RegisterFormat registers an image format for use by Decode.
-// // Name is the name of the format, like "jpeg" or "png".
-// // Magic is the magic prefix that identifies the format's encoding. The magic
-// // string can contain "?" wildcards that each match any one byte.
-// // Decode is the function that decodes the encoded image.
-// // decode_config is the function that decodes just its configuration.
-// func RegisterFormat(name, magic string, decode func(io.Reader) (Image, error), decodeConfig func(io.Reader) (Config, error)) {
-// 	formatsMu.Lock()
-// 	formats, _ := atomicFormats.Load().([]format)
-// 	atomicFormats.Store(append(formats, format{name, magic, decode, decodeConfig}))
-// 	formatsMu.Unlock()
-// }
+/// formats returns the list of registered formats.
+/// The formats supported by this library are registered by default.
+fn formats() -> &'static Mutex<Vec<Format>> {
+    static FORMATS: OnceLock<Mutex<Vec<Format>>> = OnceLock::new();
+    FORMATS.get_or_init(|| {
+        Mutex::new(vec![
+            Format {
+                name: "jpeg",
+                magic: b"\xff\xd8",
+                decode: |mut r| jpeg::decode(&mut r),
+                decode_config: |mut r| jpeg::decode_config(&mut r),
+            },
+            Format {
+                name: "png",
+                magic: b"\x89PNG\r\n\x1a\n",
+                decode: |mut r| png::decode(&mut r),
+                decode_config: |mut r| png::decode_config(&mut r),
+            },
+            Format {
+                name: "gif",
+                magic: b"GIF8?a",
+                decode: |mut r| gif::decode(&mut r),
+                decode_config: |mut r| gif::decode_config(&mut r),
+            },
+        ])
+    })
+}
 
-// // A reader is an io.Reader that can also peek ahead.
-// type reader interface {
-// 	io.Reader
-// 	Peek(int) ([]byte, error)
-// }
+/// register_format registers an image format for use by decode.
+/// Name is the name of the format, like "jpeg" or "png".
+/// Magic is the magic prefix that identifies the format's encoding. The magic
+/// string can contain "?" wildcards that each match any one byte.
+/// Decode is the function that decodes the encoded image.
+/// decode_config is the function that decodes just its configuration.
+pub fn register_format(
+    name: &'static str,
+    magic: &'static [u8],
+    decode: DecodeFn,
+    decode_config: DecodeConfigFn,
+) {
+    formats().lock().unwrap().push(Format {
+        name,
+        magic,
+        decode,
+        decode_config,
+    });
+}
 
-// // asReader converts an io.Reader to a reader.
-// func asReader(r io.Reader) reader {
-// 	if rr, ok := r.(reader); ok {
-// 		return rr
-// 	}
-// 	return bufio.NewReader(r)
-// }
+/// match_magic reports whether magic matches b. Magic may contain "?" wildcards.
+fn match_magic(magic: &[u8], b: &[u8]) -> bool {
+    if magic.len() != b.len() {
+        return false;
+    }
+    for (i, c) in b.iter().enumerate() {
+        if magic[i] != *c && magic[i] != b'?' {
+            return false;
+        }
+    }
+    true
+}
 
-// // match reports whether magic matches b. Magic may contain "?" wildcards.
-// func match(magic string, b []byte) bool {
-// 	if len(magic) != len(b) {
-// 		return false
-// 	}
-// 	for i, c := range b {
-// 		if magic[i] != c && magic[i] != '?' {
-// 			return false
-// 		}
-// 	}
-// 	return true
-// }
+/// find_format returns the first registered format whose magic matches
+/// the start of head.
+fn find_format(formats: &[Format], head: &[u8]) -> Option<Format> {
+    formats
+        .iter()
+        .find(|f| head.len() >= f.magic.len() && match_magic(f.magic, &head[..f.magic.len()]))
+        .copied()
+}
 
-// // sniff determines the format of r's data.
-// func sniff(r reader) format {
-// 	formats, _ := atomicFormats.Load().([]format)
-// 	for _, f := range formats {
-// 		b, err := r.Peek(len(f.magic))
-// 		if err == nil && match(f.magic, b) {
-// 			return f
-// 		}
-// 	}
-// 	return format{}
-// }
+/// sniff determines the format of r's data.
+///
+/// If r already buffers enough bytes for the longest registered magic, they
+/// are peeked at without being consumed, and the returned prefix is empty.
+/// Otherwise, like Go's bufio.Reader.Peek, sniff reads ahead until the
+/// longest magic is available or r is at EOF. The bytes read that way are
+/// returned and must be fed to the decoder before the rest of r.
+fn sniff<Input: BufRead>(r: &mut Input) -> std::io::Result<(Option<Format>, Vec<u8>)> {
+    let formats = formats().lock().unwrap().clone();
+    let max_len = formats.iter().map(|f| f.magic.len()).max().unwrap_or(0);
+    let buf = r.fill_buf()?;
+    if buf.len() >= max_len {
+        return Ok((find_format(&formats, buf), Vec::new()));
+    }
+    let mut head = Vec::with_capacity(max_len);
+    r.by_ref().take(max_len as u64).read_to_end(&mut head)?;
+    Ok((find_format(&formats, &head), head))
+}
 
-// // Decode decodes an image that has been encoded in a registered format.
-// // The string returned is the format name used during format registration.
-// // Format registration is typically done by an init function in the codec-
-// // specific package.
-// func Decode(r io.Reader) (Image, string, error) {
-// 	rr := asReader(r)
-// 	f := sniff(rr)
-// 	if f.decode == nil {
-// 		return nil, "", ErrFormat
-// 	}
-// 	m, err := f.decode(rr)
-// 	return m, f.name, err
-// }
+/// decode decodes an image that has been encoded in a registered format.
+/// The string returned is the format name used during format registration.
+/// JPEG, PNG and GIF are registered by default, other formats are registered
+/// with register_format.
+pub fn decode<Input: BufRead>(r: &mut Input) -> std::io::Result<(Box<Img>, &'static str)> {
+    let (f, head) = sniff(r)?;
+    let f = match f {
+        Some(f) => f,
+        None => return Err(errors::new_stdio_other_error(ERR_FORMAT_MSG.to_string())),
+    };
+    let m = if head.is_empty() {
+        (f.decode)(r)?
+    } else {
+        (f.decode)(&mut head.as_slice().chain(r))?
+    };
+    Ok((m, f.name))
+}
 
-// // decode_config decodes the color model and dimensions of an image that has
-// // been encoded in a registered format. The string returned is the format name
-// // used during format registration. Format registration is typically done by
-// // an init function in the codec-specific package.
-// func decode_config(r io.Reader) (Config, string, error) {
-// 	rr := asReader(r)
-// 	f := sniff(rr)
-// 	if f.decodeConfig == nil {
-// 		return Config{}, "", ErrFormat
-// 	}
-// 	c, err := f.decodeConfig(rr)
-// 	return c, f.name, err
-// }
+/// decode_config decodes the color model and dimensions of an image that has
+/// been encoded in a registered format. The string returned is the format name
+/// used during format registration.
+pub fn decode_config<Input: BufRead>(r: &mut Input) -> std::io::Result<(Config, &'static str)> {
+    let (f, head) = sniff(r)?;
+    let f = match f {
+        Some(f) => f,
+        None => return Err(errors::new_stdio_other_error(ERR_FORMAT_MSG.to_string())),
+    };
+    let c = if head.is_empty() {
+        (f.decode_config)(r)?
+    } else {
+        (f.decode_config)(&mut head.as_slice().chain(r))?
+    };
+    Ok((c, f.name))
+}
@@ -0,0 +1,21 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! Package gif implements a GIF image decoder and encoder.
+//!
+//! The GIF specification is at <https://www.w3.org/Graphics/GIF/spec-gif89a.txt>.
+
+mod reader;
+mod writer;
+
+pub use reader::{
+    decode, decode_all, decode_config, DISPOSAL_BACKGROUND, DISPOSAL_NONE, DISPOSAL_PREVIOUS, GIF,
+};
+pub use writer::{encode, encode_all, Options};
+
+#[cfg(test)]
+mod reader_test;
+#[cfg(test)]
+mod writer_test;
@@ -0,0 +1,626 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use crate::compress::lzw;
+use crate::errors;
+use crate::image::color::{self, Model};
+use crate::image::{rect, Config, Image, Img, Paletted};
+use std::io::{BufRead, Read};
+
+/// new_error returns an InvalidData error with the given message.
+pub(super) fn new_error(msg: String) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
+}
+
+const ERR_NOT_ENOUGH: &str = "gif: not enough image data";
+const ERR_TOO_MUCH: &str = "gif: too much image data";
+const ERR_BAD_PIXEL: &str = "gif: invalid pixel value";
+
+// Masks etc.
+// Fields.
+pub(super) const F_COLOR_TABLE: u8 = 1 << 7;
+const F_INTERLACE: u8 = 1 << 6;
+const F_COLOR_TABLE_BITS_MASK: u8 = 7;
+
+// Graphic control flags.
+const GC_TRANSPARENT_COLOR_SET: u8 = 1 << 0;
+const GC_DISPOSAL_METHOD_MASK: u8 = 7 << 2;
+
+/// Disposal Methods.
+pub const DISPOSAL_NONE: u8 = 0x01;
+pub const DISPOSAL_BACKGROUND: u8 = 0x02;
+pub const DISPOSAL_PREVIOUS: u8 = 0x03;
+
+// Section indicators.
+pub(super) const S_EXTENSION: u8 = 0x21;
+pub(super) const S_IMAGE_DESCRIPTOR: u8 = 0x2C;
+pub(super) const S_TRAILER: u8 = 0x3B;
+
+// Extensions.
+const E_TEXT: u8 = 0x01; // Plain Text
+pub(super) const E_GRAPHIC_CONTROL: u8 = 0xF9; // Graphic Control
+const E_COMMENT: u8 = 0xFE; // Comment
+const E_APPLICATION: u8 = 0xFF; // Application
+
+fn read_byte<Input: BufRead + ?Sized>(r: &mut Input) -> std::io::Result<u8> {
+    let buf = r.fill_buf()?;
+    if buf.is_empty() {
+        return Err(errors::new_unexpected_eof());
+    }
+    let c = buf[0];
+    r.consume(1);
+    Ok(c)
+}
+
+/// Decoder is the type used to decode a GIF file.
+struct Decoder<'a, Input: BufRead> {
+    r: &'a mut Input,
+
+    // From header.
+    width: usize,
+    height: usize,
+    loop_count: isize,
+    delay_time: usize,
+    background_index: u8,
+    disposal_method: u8,
+
+    // From image descriptor.
+    image_fields: u8,
+
+    // From graphics control.
+    transparent_index: u8,
+    has_transparent_index: bool,
+
+    // Computed.
+    global_color_table: color::Palette,
+
+    // Used when decoding.
+    delay: Vec<usize>,
+    disposal: Vec<u8>,
+    image: Vec<Paletted>,
+    tmp: [u8; 1024], // must be at least 768 so we can read color table
+}
+
+/// BlockReader parses the block structure of GIF image data, which comprises
+/// (n, (n bytes)) blocks, with 1 <= n <= 255. It is the reader given to the
+/// LZW decoder, which is thus immune to the blocking. After the LZW decoder
+/// completes, there will be a 0-byte block remaining (0, ()), which is
+/// consumed when checking that the BlockReader is exhausted.
+struct BlockReader<'a, Input: BufRead> {
+    r: &'a mut Input,
+    /// buf[i..j] contains the buffered bytes
+    buf: [u8; 256],
+    i: usize,
+    j: usize,
+    /// eof is set when the block terminator was read.
+    eof: bool,
+    err: Option<std::io::Error>,
+}
+
+impl<'a, Input: BufRead> BlockReader<'a, Input> {
+    fn new(r: &'a mut Input) -> Self {
+        Self {
+            r,
+            buf: [0; 256],
+            i: 0,
+            j: 0,
+            eof: false,
+            err: None,
+        }
+    }
+
+    fn fill(&mut self) {
+        if self.eof || self.err.is_some() {
+            return;
+        }
+        self.j = match read_byte(self.r) {
+            Ok(j) => j as usize,
+            Err(err) => {
+                self.err = Some(err);
+                return;
+            }
+        };
+        if self.j == 0 {
+            self.eof = true;
+            return;
+        }
+        self.i = 0;
+        if let Err(err) = self.r.read_exact(&mut self.buf[..self.j]) {
+            self.err = Some(err);
+            self.j = 0;
+        }
+    }
+
+    /// close primarily detects whether or not a block terminator was encountered
+    /// after reading a sequence of data sub-blocks. It allows at most one trailing
+    /// sub-block worth of data. I.e., if some number of bytes exist in one sub-block
+    /// following the end of LZW data, the very next sub-block must be the block
+    /// terminator. If the very end of LZW data happened to fill one sub-block, at
+    /// most one more sub-block of length 1 may exist before the block-terminator.
+    /// These accommodations allow us to support GIFs created by less strict encoders.
+    /// See <https://golang.org/issue/16146>.
+    fn close(&mut self) -> std::io::Result<()> {
+        if self.eof {
+            // A clean block-sequence terminator was encountered while reading.
+            return Ok(());
+        }
+        if let Some(err) = &self.err {
+            // Some other error was encountered while reading.
+            return Err(errors::copy_stdio_error(err));
+        }
+
+        if self.i == self.j {
+            // We reached the end of a sub block reading LZW data. We'll allow at
+            // most one more sub block of data with a length of 1 byte.
+            self.fill();
+            if self.eof {
+                return Ok(());
+            } else if let Some(err) = &self.err {
+                return Err(errors::copy_stdio_error(err));
+            } else if self.j > 1 {
+                return Err(new_error(ERR_TOO_MUCH.to_string()));
+            }
+        }
+
+        // Part of a sub-block remains buffered. We expect that the next attempt to
+        // buffer a sub-block will reach the block terminator.
+        self.fill();
+        if self.eof {
+            return Ok(());
+        } else if let Some(err) = &self.err {
+            return Err(errors::copy_stdio_error(err));
+        }
+        Err(new_error(ERR_TOO_MUCH.to_string()))
+    }
+}
+
+impl<Input: BufRead> Read for BlockReader<'_, Input> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = {
+            let available = self.fill_buf()?;
+            let n = buf.len().min(available.len());
+            buf[..n].copy_from_slice(&available[..n]);
+            n
+        };
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<Input: BufRead> BufRead for BlockReader<'_, Input> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        if self.i == self.j {
+            self.fill();
+            if let Some(err) = &self.err {
+                return Err(errors::copy_stdio_error(err));
+            }
+            if self.eof {
+                return Ok(&[]);
+            }
+        }
+        Ok(&self.buf[self.i..self.j])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.i += amt;
+    }
+}
+
+impl<'a, Input: BufRead> Decoder<'a, Input> {
+    fn new(r: &'a mut Input) -> Self {
+        Self {
+            r,
+            width: 0,
+            height: 0,
+            loop_count: -1,
+            delay_time: 0,
+            background_index: 0,
+            disposal_method: 0,
+            image_fields: 0,
+            transparent_index: 0,
+            has_transparent_index: false,
+            global_color_table: color::Palette::new(0),
+            delay: Vec::new(),
+            disposal: Vec::new(),
+            image: Vec::new(),
+            tmp: [0; 1024],
+        }
+    }
+
+    /// decode reads a GIF image from r and stores the result in self.
+    fn decode(&mut self, config_only: bool, keep_all_frames: bool) -> std::io::Result<()> {
+        self.read_header_and_screen_descriptor()?;
+        if config_only {
+            return Ok(());
+        }
+
+        loop {
+            let c = read_byte(self.r)
+                .map_err(|err| new_error(format!("gif: reading frames: {}", err)))?;
+            match c {
+                S_EXTENSION => self.read_extension()?,
+                S_IMAGE_DESCRIPTOR => {
+                    self.read_image_descriptor(keep_all_frames)?;
+                    if !keep_all_frames && self.image.len() == 1 {
+                        return Ok(());
+                    }
+                }
+                S_TRAILER => {
+                    if self.image.is_empty() {
+                        return Err(new_error("gif: missing image data".to_string()));
+                    }
+                    return Ok(());
+                }
+                _ => return Err(new_error(format!("gif: unknown block type: 0x{:02x}", c))),
+            }
+        }
+    }
+
+    fn read_header_and_screen_descriptor(&mut self) -> std::io::Result<()> {
+        self.r
+            .read_exact(&mut self.tmp[..13])
+            .map_err(|err| new_error(format!("gif: reading header: {}", err)))?;
+        let vers = &self.tmp[..6];
+        if vers != b"GIF87a" && vers != b"GIF89a" {
+            return Err(new_error(format!(
+                "gif: can't recognize format {:?}",
+                String::from_utf8_lossy(vers)
+            )));
+        }
+        self.width = self.tmp[6] as usize | (self.tmp[7] as usize) << 8;
+        self.height = self.tmp[8] as usize | (self.tmp[9] as usize) << 8;
+        let fields = self.tmp[10];
+        if fields & F_COLOR_TABLE != 0 {
+            self.background_index = self.tmp[11];
+            // read_color_table overwrites the contents of self.tmp, but that's OK.
+            self.global_color_table = self.read_color_table(fields)?;
+        }
+        // self.tmp[12] is the Pixel Aspect Ratio, which is ignored.
+        Ok(())
+    }
+
+    fn read_color_table(&mut self, fields: u8) -> std::io::Result<color::Palette> {
+        let n = 1 << (1 + (fields & F_COLOR_TABLE_BITS_MASK));
+        self.r
+            .read_exact(&mut self.tmp[..3 * n])
+            .map_err(|err| new_error(format!("gif: reading color table: {}", err)))?;
+        Ok(color::Palette {
+            colors: self.tmp[..3 * n]
+                .chunks_exact(3)
+                .map(|c| color::Color::new_rgba(c[0], c[1], c[2], 0xff))
+                .collect(),
+        })
+    }
+
+    fn read_extension(&mut self) -> std::io::Result<()> {
+        let extension_error =
+            |err: std::io::Error| new_error(format!("gif: reading extension: {}", err));
+        let extension = read_byte(self.r).map_err(extension_error)?;
+        let size = match extension {
+            E_TEXT => 13,
+            E_GRAPHIC_CONTROL => return self.read_graphic_control(),
+            // nothing to do but read the data.
+            E_COMMENT => 0,
+            // The spec requires size be 11, but Adobe sometimes uses 10.
+            E_APPLICATION => read_byte(self.r).map_err(extension_error)? as usize,
+            _ => {
+                return Err(new_error(format!(
+                    "gif: unknown extension 0x{:02x}",
+                    extension
+                )))
+            }
+        };
+        if size > 0 {
+            self.r
+                .read_exact(&mut self.tmp[..size])
+                .map_err(extension_error)?;
+        }
+
+        // Application Extension with "NETSCAPE2.0" as string and 1 in data means
+        // this extension defines a loop count.
+        if extension == E_APPLICATION && &self.tmp[..size] == b"NETSCAPE2.0" {
+            let n = self.read_block().map_err(extension_error)?;
+            if n == 0 {
+                return Ok(());
+            }
+            if n == 3 && self.tmp[0] == 1 {
+                self.loop_count = (self.tmp[1] as usize | (self.tmp[2] as usize) << 8) as isize;
+            }
+        }
+        loop {
+            let n = self.read_block().map_err(extension_error)?;
+            if n == 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    fn read_graphic_control(&mut self) -> std::io::Result<()> {
+        self.r
+            .read_exact(&mut self.tmp[..6])
+            .map_err(|err| new_error(format!("gif: can't read graphic control: {}", err)))?;
+        if self.tmp[0] != 4 {
+            return Err(new_error(format!(
+                "gif: invalid graphic control extension block size: {}",
+                self.tmp[0]
+            )));
+        }
+        let flags = self.tmp[1];
+        self.disposal_method = (flags & GC_DISPOSAL_METHOD_MASK) >> 2;
+        self.delay_time = self.tmp[2] as usize | (self.tmp[3] as usize) << 8;
+        if flags & GC_TRANSPARENT_COLOR_SET != 0 {
+            self.transparent_index = self.tmp[4];
+            self.has_transparent_index = true;
+        }
+        if self.tmp[5] != 0 {
+            return Err(new_error(format!(
+                "gif: invalid graphic control extension block terminator: {}",
+                self.tmp[5]
+            )));
+        }
+        Ok(())
+    }
+
+    fn read_image_descriptor(&mut self, keep_all_frames: bool) -> std::io::Result<()> {
+        let mut m = self.new_image_from_descriptor()?;
+        let use_local_color_table = self.image_fields & F_COLOR_TABLE != 0;
+        if use_local_color_table {
+            m.palette = self.read_color_table(self.image_fields)?;
+        } else {
+            if self.global_color_table.colors.is_empty() {
+                return Err(new_error("gif: no color table".to_string()));
+            }
+            m.palette = self.global_color_table.clone();
+        }
+        if self.has_transparent_index {
+            let ti = self.transparent_index as usize;
+            if ti >= m.palette.colors.len() {
+                // The transparent_index is out of range, which is an error
+                // according to the spec, but Firefox and Google Chrome
+                // seem OK with this, so we enlarge the palette with
+                // transparent colors. See golang.org/issue/15059.
+                m.palette
+                    .colors
+                    .resize(ti + 1, color::Color::new_rgba(0, 0, 0, 0));
+            }
+            m.palette.colors[ti] = color::Color::new_rgba(0, 0, 0, 0);
+        }
+        let lit_width = read_byte(self.r)
+            .map_err(|err| new_error(format!("gif: reading image data: {}", err)))?;
+        if !(2..=8).contains(&lit_width) {
+            return Err(new_error(format!(
+                "gif: pixel size in decode out of range: {}",
+                lit_width
+            )));
+        }
+        let mut br = BlockReader::new(self.r);
+        {
+            let mut lzwr = lzw::Reader::new(&mut br, lzw::Order::LSB, lit_width as usize);
+            if let Err(err) = lzwr.read_exact(&mut m.pix) {
+                if err.kind() == std::io::ErrorKind::UnexpectedEof {
+                    return Err(new_error(ERR_NOT_ENOUGH.to_string()));
+                }
+                return Err(new_error(format!("gif: reading image data: {}", err)));
+            }
+            // In theory, both lzwr and br should be exhausted. Reading from them
+            // should yield Ok(0).
+            //
+            // The spec (Appendix F - Compression), says that "An End of
+            // Information code... must be the last code output by the encoder
+            // for an image". In practice, though, giflib (a widely used C
+            // library) does not enforce this, so we also accept lzwr returning
+            // an UnexpectedEof error (meaning that the encoded stream hit EOF
+            // before the LZW decoder saw an explicit end code), provided that
+            // the read_exact call above successfully read m.pix.len() bytes.
+            // See https://golang.org/issue/9856 for an example GIF.
+            match lzwr.read(&mut self.tmp[256..257]) {
+                Ok(0) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => {}
+                Ok(_) => return Err(new_error(ERR_TOO_MUCH.to_string())),
+                Err(err) => return Err(new_error(format!("gif: reading image data: {}", err))),
+            }
+            lzwr.close()?;
+        }
+
+        // In practice, some GIFs have an extra byte in the data sub-block
+        // stream, which we ignore. See https://golang.org/issue/16146.
+        if let Err(err) = br.close() {
+            if err.to_string() == ERR_TOO_MUCH {
+                return Err(err);
+            }
+            return Err(new_error(format!("gif: reading image data: {}", err)));
+        }
+
+        // Check that the color indexes are inside the palette.
+        let n = m.palette.colors.len();
+        if n < 256 && m.pix.iter().any(|&pixel| pixel as usize >= n) {
+            return Err(new_error(ERR_BAD_PIXEL.to_string()));
+        }
+
+        // Undo the interlacing if necessary.
+        if self.image_fields & F_INTERLACE != 0 {
+            uninterlace(&mut m);
+        }
+
+        if keep_all_frames || self.image.is_empty() {
+            self.image.push(m);
+            self.delay.push(self.delay_time);
+            self.disposal.push(self.disposal_method);
+        }
+        // The GIF89a spec, Section 23 (Graphic Control Extension) says:
+        // "The scope of this extension is the first graphic rendering block
+        // to follow." We therefore reset the GCE fields to zero.
+        self.delay_time = 0;
+        self.has_transparent_index = false;
+        Ok(())
+    }
+
+    fn new_image_from_descriptor(&mut self) -> std::io::Result<Paletted> {
+        self.r
+            .read_exact(&mut self.tmp[..9])
+            .map_err(|err| new_error(format!("gif: can't read image descriptor: {}", err)))?;
+        let left = self.tmp[0] as usize | (self.tmp[1] as usize) << 8;
+        let top = self.tmp[2] as usize | (self.tmp[3] as usize) << 8;
+        let width = self.tmp[4] as usize | (self.tmp[5] as usize) << 8;
+        let height = self.tmp[6] as usize | (self.tmp[7] as usize) << 8;
+        self.image_fields = self.tmp[8];
+
+        // The GIF89a spec, Section 20 (Image Descriptor) says: "Each image must
+        // fit within the boundaries of the Logical Screen, as defined in the
+        // Logical Screen Descriptor."
+        //
+        // This is conceptually similar to testing
+        //	frame_bounds.inside(&image_bounds)
+        // but r.inside(s) is true whenever r is an empty rectangle, even if
+        // r.min.x > s.max.x. Here, we want something stricter.
+        //
+        // Note that, by construction, left >= 0 && top >= 0, so we only have to
+        // explicitly compare the frame bounds max (left+width, top+height) against
+        // the image bounds max (self.width, self.height).
+        if left + width > self.width || top + height > self.height {
+            return Err(new_error(
+                "gif: frame bounds larger than image bounds".to_string(),
+            ));
+        }
+        Ok(Paletted::new(
+            &rect(
+                left as isize,
+                top as isize,
+                (left + width) as isize,
+                (top + height) as isize,
+            ),
+            color::Palette::new(0),
+        ))
+    }
+
+    /// read_block reads a data sub-block into self.tmp and returns its size.
+    fn read_block(&mut self) -> std::io::Result<usize> {
+        let n = read_byte(self.r)? as usize;
+        if n == 0 {
+            return Ok(0);
+        }
+        self.r.read_exact(&mut self.tmp[..n])?;
+        Ok(n)
+    }
+}
+
+/// InterlaceScan defines the ordering for a pass of the interlace algorithm.
+struct InterlaceScan {
+    skip: usize,
+    start: usize,
+}
+
+/// INTERLACING represents the set of scans in an interlaced GIF image.
+const INTERLACING: [InterlaceScan; 4] = [
+    InterlaceScan { skip: 8, start: 0 }, // Group 1 : Every 8th. row, starting with row 0.
+    InterlaceScan { skip: 8, start: 4 }, // Group 2 : Every 8th. row, starting with row 4.
+    InterlaceScan { skip: 4, start: 2 }, // Group 3 : Every 4th. row, starting with row 2.
+    InterlaceScan { skip: 2, start: 1 }, // Group 4 : Every 2nd. row, starting with row 1.
+];
+
+/// uninterlace rearranges the pixels in m to account for interlaced input.
+fn uninterlace(m: &mut Paletted) {
+    let dx = m.bounds().dx();
+    let dy = m.bounds().dy();
+    let mut n_pix = vec![0; dx * dy];
+    let mut offset = 0; // steps through the input by sequential scan lines.
+    for pass in INTERLACING.iter() {
+        let mut n_offset = pass.start * dx; // steps through the output as defined by pass.
+        for _ in (pass.start..dy).step_by(pass.skip) {
+            n_pix[n_offset..n_offset + dx].copy_from_slice(&m.pix[offset..offset + dx]);
+            offset += dx;
+            n_offset += dx * pass.skip;
+        }
+    }
+    m.pix = n_pix;
+}
+
+/// decode reads a GIF image from r and returns the first embedded
+/// image as an Img::Paletted.
+pub fn decode<Input: BufRead>(r: &mut Input) -> std::io::Result<Box<Img>> {
+    let mut d = Decoder::new(r);
+    d.decode(false, false)?;
+    Ok(Box::new(Img::Paletted(d.image.swap_remove(0))))
+}
+
+/// GIF represents the possibly multiple images stored in a GIF file.
+#[derive(Debug)]
+pub struct GIF {
+    /// The successive images.
+    pub image: Vec<Paletted>,
+    /// The successive delay times, one per frame, in 100ths of a second.
+    pub delay: Vec<usize>,
+    /// loop_count controls the number of times an animation will be
+    /// restarted during display.
+    /// A loop_count of 0 means to loop forever.
+    /// A loop_count of -1 means to show each frame only once.
+    /// Otherwise, the animation is looped loop_count+1 times.
+    pub loop_count: isize,
+    /// disposal is the successive disposal methods, one per frame. An empty
+    /// disposal is valid to pass to encode_all, and implies that each frame's
+    /// disposal method is 0 (no disposal specified).
+    pub disposal: Vec<u8>,
+    /// config is the global color table (palette), width and height. An
+    /// empty Model::Paletted palette means that each frame has its own
+    /// color table and there is no global color table. Each frame's bounds must
+    /// be within the rectangle defined by the two points (0, 0) and
+    /// (config.width, config.height).
+    ///
+    /// A zero width and height together with an empty palette is valid to pass
+    /// to encode_all, and implies that the overall GIF's width and height equals
+    /// the first frame's bounds' Rectangle.max point.
+    pub config: Config,
+    /// background_index is the background index in the global color table, for
+    /// use with the DISPOSAL_BACKGROUND disposal method.
+    pub background_index: u8,
+}
+
+impl Default for GIF {
+    fn default() -> Self {
+        Self {
+            image: Vec::new(),
+            delay: Vec::new(),
+            loop_count: 0,
+            disposal: Vec::new(),
+            config: Config {
+                color_model: Model::Paletted(color::Palette::new(0)),
+                width: 0,
+                height: 0,
+            },
+            background_index: 0,
+        }
+    }
+}
+
+/// decode_all reads a GIF image from r and returns the sequential frames
+/// and timing information.
+pub fn decode_all<Input: BufRead>(r: &mut Input) -> std::io::Result<GIF> {
+    let mut d = Decoder::new(r);
+    d.decode(false, true)?;
+    Ok(GIF {
+        image: d.image,
+        delay: d.delay,
+        loop_count: d.loop_count,
+        disposal: d.disposal,
+        config: Config {
+            color_model: Model::Paletted(d.global_color_table),
+            width: d.width,
+            height: d.height,
+        },
+        background_index: d.background_index,
+    })
+}
+
+/// decode_config returns the global color model and dimensions of a GIF image
+/// without decoding the entire image.
+pub fn decode_config<Input: BufRead>(r: &mut Input) -> std::io::Result<Config> {
+    let mut d = Decoder::new(r);
+    d.decode(true, false)?;
+    Ok(Config {
+        color_model: Model::Paletted(d.global_color_table),
+        width: d.width,
+        height: d.height,
+    })
+}
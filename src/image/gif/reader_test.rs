@@ -0,0 +1,336 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2013 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::{decode, decode_all, decode_config};
+use crate::compress::lzw;
+use crate::image::color::{self, Color, Model};
+use crate::image::{rect, Image, Img};
+use std::io::Write;
+
+// HEADER, PALETTE and TRAILER are parts of a valid 2x1 GIF image.
+const HEADER: &[u8] = b"GIF89a\
+    \x02\x00\x01\x00\
+    \x80\x00\x00"; // width=2, height=1, header fields=(a color table of 2 pixels), background index, aspect
+const PALETTE: &[u8] = b"\x10\x20\x30\x40\x50\x60"; // the color table, also known as a palette
+const TRAILER: &[u8] = b"\x3b";
+
+/// lzw_encode returns an LZW encoding (with 2-bit literals) of input.
+fn lzw_encode(input: &[u8]) -> Vec<u8> {
+    let mut b = Vec::new();
+    let mut w = lzw::Writer::new(&mut b, lzw::Order::LSB, 2);
+    w.write_all(input).unwrap();
+    w.close().unwrap();
+    b
+}
+
+/// write_2x1_image writes an image descriptor with bounds 2x1, a flags byte
+/// and 2-bit LZW literals, followed by the LZW encoding of pix in a single
+/// data sub-block and the block terminator.
+fn write_2x1_image(b: &mut Vec<u8>, pix: &[u8]) {
+    b.extend_from_slice(b"\x2c\x00\x00\x00\x00\x02\x00\x01\x00\x00\x02");
+    let enc = lzw_encode(pix);
+    assert!(
+        enc.len() <= 0xff,
+        "compressed length {} is too large",
+        enc.len()
+    );
+    b.push(enc.len() as u8);
+    b.extend_from_slice(&enc);
+    b.push(0x00);
+}
+
+#[test]
+fn test_decode() {
+    // EXTRA contains superfluous bytes to inject into the GIF, either at the end
+    // of an existing data sub-block (past the LZW End of Information code) or in
+    // a separate data sub-block. The 0x02 values are arbitrary.
+    const EXTRA: &[u8] = b"\x02\x02\x02\x02";
+
+    const ERR_NOT_ENOUGH: Option<&str> = Some("gif: not enough image data");
+    const ERR_TOO_MUCH: Option<&str> = Some("gif: too much image data");
+
+    // (n_pix, extra_existing, extra_separate, want_err)
+    //
+    // n_pix is the number of pixels in the image data.
+    // If non-zero, extra_existing bytes are written inside the data sub-block
+    // containing the LZW end code.
+    // If non-zero, an extra block of extra_separate bytes is written.
+    let test_cases: &[(usize, usize, usize, Option<&str>)] = &[
+        (0, 0, 0, ERR_NOT_ENOUGH),
+        (1, 0, 0, ERR_NOT_ENOUGH),
+        (2, 0, 0, None),
+        // An extra data sub-block after the compressed section with 1 byte which we
+        // silently skip.
+        (2, 0, 1, None),
+        // An extra data sub-block after the compressed section with 2 bytes. In
+        // this case we complain that there is too much data.
+        (2, 0, 2, ERR_TOO_MUCH),
+        // Too much pixel data.
+        (3, 0, 0, ERR_TOO_MUCH),
+        // An extra byte after LZW data, but inside the same data sub-block.
+        (2, 1, 0, None),
+        // Two extra bytes after LZW data, but inside the same data sub-block.
+        (2, 2, 0, None),
+        // Extra data exists in the final sub-block with LZW data, AND there is
+        // a bogus sub-block following.
+        (2, 1, 1, ERR_TOO_MUCH),
+    ];
+    for &(n_pix, extra_existing, extra_separate, want_err) in test_cases {
+        let mut b = Vec::new();
+        b.extend_from_slice(HEADER);
+        b.extend_from_slice(PALETTE);
+        // Write an image with bounds 2x1 but n_pix pixels. If n_pix != 2
+        // then this should result in an invalid GIF image. First, write a
+        // magic 0x2c (image descriptor) byte, bounds=(0,0)-(2,1), a flags
+        // byte, and 2-bit LZW literals.
+        b.extend_from_slice(b"\x2c\x00\x00\x00\x00\x02\x00\x01\x00\x00\x02");
+        if n_pix > 0 {
+            let enc = lzw_encode(&vec![0; n_pix]);
+            assert!(enc.len() + extra_existing <= 0xff);
+            // Write the size of the data sub-block containing the LZW data.
+            b.push((enc.len() + extra_existing) as u8);
+            // Write the LZW data.
+            b.extend_from_slice(&enc);
+            // Write extra bytes inside the same data sub-block where LZW data
+            // ended. Each arbitrarily 0x02.
+            b.extend_from_slice(&EXTRA[..extra_existing]);
+        }
+        if extra_separate > 0 {
+            // Data sub-block size. This indicates how many extra bytes follow.
+            b.push(extra_separate as u8);
+            b.extend_from_slice(&EXTRA[..extra_separate]);
+        }
+        b.push(0x00); // An empty block signifies the end of the image data.
+        b.extend_from_slice(TRAILER);
+
+        let desc = format!(
+            "n_pix={}, extra_existing={}, extra_separate={}",
+            n_pix, extra_existing, extra_separate
+        );
+        let got = decode(&mut b.as_slice());
+        if let Some(want_err) = want_err {
+            let err = got
+                .err()
+                .unwrap_or_else(|| panic!("{}: want an error", desc));
+            assert_eq!(err.to_string(), want_err, "{}", desc);
+            continue;
+        }
+        let got = got.unwrap_or_else(|err| panic!("{}: {}", desc, err));
+        let Img::Paletted(got) = *got else {
+            panic!("{}: want a Paletted image", desc)
+        };
+        assert_eq!(got.pix, [0, 0], "{}", desc);
+        assert_eq!(got.stride, 2, "{}", desc);
+        assert!(*got.bounds() == rect(0, 0, 2, 1), "{}", desc);
+        assert_eq!(
+            got.palette.colors,
+            [
+                Color::new_rgba(0x10, 0x20, 0x30, 0xff),
+                Color::new_rgba(0x40, 0x50, 0x60, 0xff),
+            ],
+            "{}",
+            desc
+        );
+    }
+}
+
+#[test]
+fn test_transparent_index() {
+    let mut b = Vec::new();
+    b.extend_from_slice(HEADER);
+    b.extend_from_slice(PALETTE);
+    for transparent_index in 0..3 {
+        if transparent_index < 2 {
+            // Write the graphic control for the transparent index.
+            b.extend_from_slice(b"\x21\xf9\x04\x01\x00\x00");
+            b.push(transparent_index);
+            b.push(0);
+        }
+        // Write an image with bounds 2x1, as per test_decode.
+        write_2x1_image(&mut b, &[0x00, 0x00]);
+    }
+    b.extend_from_slice(TRAILER);
+
+    let g = decode_all(&mut b.as_slice()).unwrap();
+    let c0 = Color::new_rgba(PALETTE[0], PALETTE[1], PALETTE[2], 0xff);
+    let c1 = Color::new_rgba(PALETTE[3], PALETTE[4], PALETTE[5], 0xff);
+    let cz = Color::new_rgba(0, 0, 0, 0);
+    let wants = [[cz, c1], [c0, cz], [c0, c1]];
+    assert_eq!(g.image.len(), wants.len());
+    for (i, want) in wants.iter().enumerate() {
+        assert_eq!(g.image[i].palette.colors, want, "palette #{}", i);
+    }
+}
+
+/// TEST_GIF is a simple GIF that we can modify to test different scenarios.
+const TEST_GIF: &[u8] = &[
+    b'G', b'I', b'F', b'8', b'9', b'a', //
+    1, 0, 1, 0, // w=1, h=1 (6)
+    128, 0, 0, // header fields, bg, aspect (10)
+    0, 0, 0, 1, 1, 1, // color table and graphics control (13)
+    0x21, 0xf9, 0x04, 0x00, 0x00, 0x00, 0xff, 0x00, // (19)
+    // frame 1 (0,0 - 1,1)
+    0x2c, //
+    0x00, 0x00, 0x00, 0x00, //
+    0x01, 0x00, 0x01, 0x00, // (32)
+    0x00, //
+    0x02, 0x02, 0x4c, 0x01, 0x00, // lzw pixels
+    // trailer
+    0x3b,
+];
+
+fn try_decode_all(b: &[u8], want: &str) {
+    let got = match decode_all(&mut &b[..]) {
+        Ok(_) => String::new(),
+        Err(err) => err.to_string(),
+    };
+    assert_eq!(got, want);
+}
+
+#[test]
+fn test_bounds() {
+    // Make a local copy of TEST_GIF.
+    let mut gif = TEST_GIF.to_vec();
+    try_decode_all(&gif, "");
+
+    // Make the bounds too big, just by one.
+    gif[32] = 2;
+    try_decode_all(&gif, "gif: frame bounds larger than image bounds");
+
+    // Make the bounds too small; does not trigger bounds
+    // check, but now there's too much data.
+    gif[32] = 0;
+    try_decode_all(&gif, "gif: too much image data");
+    gif[32] = 1;
+
+    // Make the bounds really big, expect an error.
+    for i in 0..4 {
+        gif[32 + i] = 0xff;
+    }
+    try_decode_all(&gif, "gif: frame bounds larger than image bounds");
+}
+
+#[test]
+fn test_no_palette() {
+    let mut b = Vec::new();
+
+    // Manufacture a GIF with no palette, so any pixel at all
+    // will be invalid.
+    b.extend_from_slice(&HEADER[..10]);
+    b.extend_from_slice(b"\x00\x00\x00");
+
+    // Write an image with bounds 2x1, as per test_decode.
+    write_2x1_image(&mut b, &[0x00, 0x03]);
+    b.extend_from_slice(TRAILER);
+
+    try_decode_all(&b, "gif: no color table");
+}
+
+#[test]
+fn test_pixel_outside_palette_range() {
+    for pval in 0..4 {
+        let mut b = Vec::new();
+
+        // Manufacture a GIF with a 2 color palette.
+        b.extend_from_slice(HEADER);
+        b.extend_from_slice(PALETTE);
+
+        // Write an image with bounds 2x1, as per test_decode.
+        write_2x1_image(&mut b, &[pval, pval]);
+        b.extend_from_slice(TRAILER);
+
+        // No error expected, unless the pixels are beyond the 2 color palette.
+        let want = if pval >= 2 {
+            "gif: invalid pixel value"
+        } else {
+            ""
+        };
+        try_decode_all(&b, want);
+    }
+}
+
+#[test]
+fn test_transparent_pixel_outside_palette_range() {
+    let mut b = Vec::new();
+
+    // Manufacture a GIF with a 2 color palette.
+    b.extend_from_slice(HEADER);
+    b.extend_from_slice(PALETTE);
+
+    // Set 0x02 as a transparent color index in the Graphic Control Extension.
+    b.extend_from_slice(b"\x21\xf9\x04\x01\x00\x00\x02\x00");
+
+    // Write an image with bounds 2x1, as per test_decode.
+    write_2x1_image(&mut b, &[0x02, 0x02]);
+    b.extend_from_slice(TRAILER);
+
+    try_decode_all(&b, "");
+}
+
+#[test]
+fn test_unexpected_eof() {
+    for i in 0..TEST_GIF.len() - 1 {
+        let err = decode_all(&mut &TEST_GIF[..i]).err();
+        assert!(err.is_some(), "#{}: want an error", i);
+    }
+}
+
+#[test]
+fn test_decode_config() {
+    let f = std::fs::File::open("src/image/testdata/video-001.gif").unwrap();
+    let c = decode_config(&mut std::io::BufReader::new(f)).unwrap();
+    assert_eq!((c.width, c.height), (150, 103));
+    let Model::Paletted(p) = c.color_model else {
+        panic!("want a paletted color model, got {:?}", c.color_model)
+    };
+    assert!(!p.colors.is_empty());
+}
+
+#[test]
+fn test_decode_interlaced() {
+    let read = |filename: &str| {
+        let f = std::fs::File::open(filename).unwrap();
+        decode(&mut std::io::BufReader::new(f)).unwrap()
+    };
+    let m0 = read("src/image/testdata/video-001.gif");
+    let m1 = read("src/image/testdata/video-001.interlaced.gif");
+    let b = m0.bounds();
+    assert!(b == m1.bounds());
+    for y in b.min.y..b.max.y {
+        for x in b.min.x..b.max.x {
+            assert!(m0.at(x, y) == m1.at(x, y), "at ({}, {})", x, y);
+        }
+    }
+}
+
+#[test]
+fn test_loop_count() {
+    let mut b = Vec::new();
+    b.extend_from_slice(HEADER);
+    b.extend_from_slice(PALETTE);
+    // A NETSCAPE2.0 application extension with a loop count of 3.
+    b.extend_from_slice(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x03\x00\x00");
+    write_2x1_image(&mut b, &[0x00, 0x01]);
+    write_2x1_image(&mut b, &[0x01, 0x00]);
+    b.extend_from_slice(TRAILER);
+    let g = decode_all(&mut b.as_slice()).unwrap();
+    assert_eq!(g.loop_count, 3);
+    assert_eq!(g.image.len(), 2);
+    assert_eq!(g.delay, [0, 0]);
+
+    // Without the extension, the frames are shown only once.
+    let mut b = Vec::new();
+    b.extend_from_slice(HEADER);
+    b.extend_from_slice(PALETTE);
+    write_2x1_image(&mut b, &[0x00, 0x01]);
+    b.extend_from_slice(TRAILER);
+    let g = decode_all(&mut b.as_slice()).unwrap();
+    assert_eq!(g.loop_count, -1);
+    let Model::Paletted(p) = g.config.color_model else {
+        unreachable!()
+    };
+    assert_eq!(p.colors.len(), 2);
+    assert_eq!(p.colors[1], color::Color::new_rgba(0x40, 0x50, 0x60, 0xff));
+}
@@ -0,0 +1,410 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2013 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::reader::{
+    E_GRAPHIC_CONTROL, F_COLOR_TABLE, GIF, S_EXTENSION, S_IMAGE_DESCRIPTOR, S_TRAILER,
+};
+use crate::bufio;
+use crate::compress::lzw;
+use crate::image::color::{self, palette, ColorTrait, Model};
+use crate::image::draw::{Drawer, FloydSteinberg, Quantizer};
+use crate::image::{rect, Config, Image, Img, Paletted};
+use std::io::Write;
+
+// Graphic control extension fields.
+const GC_BLOCK_SIZE: u8 = 0x04;
+
+/// new_input_error reports that the image to encode is invalid.
+fn new_input_error(msg: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, msg)
+}
+
+const LOG2_LOOKUP: [usize; 8] = [2, 4, 8, 16, 32, 64, 128, 256];
+
+fn log2(x: usize) -> usize {
+    for (i, &v) in LOG2_LOOKUP.iter().enumerate() {
+        if x <= v {
+            return i;
+        }
+    }
+    LOG2_LOOKUP.len()
+}
+
+/// Encoder encodes an image to the GIF format.
+struct Encoder<'a, 'g> {
+    w: bufio::Writer<'a>,
+    /// g is a reference to the data that is being encoded.
+    g: &'g GIF,
+    /// width and height are the overall GIF's dimensions.
+    width: usize,
+    height: usize,
+    /// global_ct is the size in bytes of the global color table.
+    global_ct: usize,
+    global_color_table: [u8; 3 * 256],
+    local_color_table: [u8; 3 * 256],
+}
+
+/// BlockWriter writes the block structure of GIF image data, which
+/// comprises (n, (n bytes)) blocks, with 1 <= n <= 255. It is the
+/// writer given to the LZW encoder, which is thus immune to the
+/// blocking.
+struct BlockWriter<'a, 'b> {
+    w: &'b mut bufio::Writer<'a>,
+    /// buf[0] is the length of the buffered sub-block, buf[1..] its contents.
+    buf: [u8; 256],
+}
+
+impl<'a, 'b> BlockWriter<'a, 'b> {
+    fn new(w: &'b mut bufio::Writer<'a>) -> Self {
+        Self { w, buf: [0; 256] }
+    }
+
+    fn write_byte(&mut self, c: u8) -> std::io::Result<()> {
+        // Append c to buffered sub-block.
+        self.buf[0] += 1;
+        self.buf[self.buf[0] as usize] = c;
+        if self.buf[0] < 255 {
+            return Ok(());
+        }
+
+        // Flush block
+        self.w.write_all(&self.buf)?;
+        self.buf[0] = 0;
+        Ok(())
+    }
+
+    fn close(&mut self) -> std::io::Result<()> {
+        // Write the block terminator (0x00), either by itself, or along with a
+        // pending sub-block.
+        if self.buf[0] == 0 {
+            self.w.write_byte(0)
+        } else {
+            let n = self.buf[0] as usize;
+            self.w.write_all(&self.buf[..n + 1])?;
+            self.w.write_byte(0)
+        }
+    }
+}
+
+impl Write for BlockWriter<'_, '_> {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        for &c in data {
+            self.write_byte(c)?;
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// encode_color_table writes the colors of p to dst, padded with black up to
+/// 2^(1+size) entries, and returns the number of bytes written.
+pub(super) fn encode_color_table(
+    dst: &mut [u8],
+    p: &color::Palette,
+    size: usize,
+) -> std::io::Result<usize> {
+    if size >= LOG2_LOOKUP.len() {
+        return Err(new_input_error(
+            "gif: cannot encode color table with more than 256 entries",
+        ));
+    }
+    for (i, c) in p.colors.iter().enumerate() {
+        let (r, g, b) = match c {
+            // It is most likely that c is already a color::RGBA.
+            color::Color::RGBA(c) => (c.r, c.g, c.b),
+            _ => {
+                let (r, g, b, _) = c.rgba();
+                ((r >> 8) as u8, (g >> 8) as u8, (b >> 8) as u8)
+            }
+        };
+        dst[3 * i] = r;
+        dst[3 * i + 1] = g;
+        dst[3 * i + 2] = b;
+    }
+    let n = LOG2_LOOKUP[size];
+    if n > p.colors.len() {
+        // Pad with black.
+        dst[3 * p.colors.len()..3 * n].fill(0);
+    }
+    Ok(3 * n)
+}
+
+/// color_tables_match reports whether the encoded local color table matches
+/// the global one, ignoring the entry at transparent_index.
+pub(super) fn color_tables_match(
+    global: &[u8],
+    local: &[u8],
+    local_len: usize,
+    transparent_index: Option<usize>,
+) -> bool {
+    let local_size = 3 * local_len;
+    if let Some(ti) = transparent_index {
+        let tr_off = 3 * ti;
+        return global[..tr_off] == local[..tr_off]
+            && global[tr_off + 3..local_size] == local[tr_off + 3..local_size];
+    }
+    global[..local_size] == local[..local_size]
+}
+
+impl Encoder<'_, '_> {
+    fn write_header(&mut self) -> std::io::Result<()> {
+        self.w.write_all(b"GIF89a")?;
+
+        // Logical screen width and height.
+        self.w.write_all(&(self.width as u16).to_le_bytes())?;
+        self.w.write_all(&(self.height as u16).to_le_bytes())?;
+
+        match &self.g.config.color_model {
+            Model::Paletted(p) if !p.colors.is_empty() => {
+                let padded_size = log2(p.colors.len()); // Size of Global Color Table: 2^(1+n).
+                self.w.write_all(&[
+                    F_COLOR_TABLE | padded_size as u8,
+                    self.g.background_index,
+                    0x00, // Pixel Aspect Ratio.
+                ])?;
+                self.global_ct = encode_color_table(&mut self.global_color_table, p, padded_size)?;
+                self.w
+                    .write_all(&self.global_color_table[..self.global_ct])?;
+            }
+            _ => {
+                // All frames have a local color table, so a global color table
+                // is not needed.
+                self.w.write_all(&[
+                    0x00, 0x00, // Background Color Index.
+                    0x00, // Pixel Aspect Ratio.
+                ])?;
+            }
+        }
+
+        // Add animation info if necessary.
+        if self.g.image.len() > 1 && self.g.loop_count >= 0 {
+            self.w.write_all(&[
+                0x21, // Extension Introducer.
+                0xff, // Application Label.
+                0x0b, // Block Size.
+            ])?;
+            self.w.write_all(b"NETSCAPE2.0")?; // Application Identifier.
+            let lc = (self.g.loop_count as u16).to_le_bytes();
+            self.w.write_all(&[
+                0x03, // Block Size.
+                0x01, // Sub-block Index.
+                lc[0], lc[1], 0x00, // Block Terminator.
+            ])?;
+        }
+        Ok(())
+    }
+
+    fn write_image_block(
+        &mut self,
+        pm: &Paletted,
+        delay: usize,
+        disposal: u8,
+    ) -> std::io::Result<()> {
+        if pm.palette.colors.is_empty() {
+            return Err(new_input_error(
+                "gif: cannot encode image block with empty palette",
+            ));
+        }
+
+        let b = *pm.bounds();
+        if b.min.x < 0 || b.max.x >= 1 << 16 || b.min.y < 0 || b.max.y >= 1 << 16 {
+            return Err(new_input_error("gif: image block is too large to encode"));
+        }
+        if !b.inside(&rect(0, 0, self.width as isize, self.height as isize)) {
+            return Err(new_input_error("gif: image block is out of bounds"));
+        }
+
+        let transparent_index = pm.palette.colors.iter().position(|c| c.rgba().3 == 0);
+
+        if delay > 0 || disposal != 0 || transparent_index.is_some() {
+            let d = (delay as u16).to_le_bytes();
+            self.w.write_all(&[
+                S_EXTENSION,       // Extension Introducer.
+                E_GRAPHIC_CONTROL, // Graphic Control Label.
+                GC_BLOCK_SIZE,     // Block Size.
+                u8::from(transparent_index.is_some()) | disposal << 2,
+                d[0], // Delay Time (1/100ths of a second)
+                d[1],
+                transparent_index.unwrap_or(0) as u8, // Transparent color index.
+                0x00,                                 // Block Terminator.
+            ])?;
+        }
+        self.w.write_byte(S_IMAGE_DESCRIPTOR)?;
+        for v in [b.min.x as usize, b.min.y as usize, b.dx(), b.dy()] {
+            self.w.write_all(&(v as u16).to_le_bytes())?;
+        }
+
+        // To determine whether or not this frame's palette is the same as the
+        // global palette, we iterate through the colors.
+        let padded_size = log2(pm.palette.colors.len()); // Size of Local Color Table: 2^(1+n).
+        let ct = encode_color_table(&mut self.local_color_table, &pm.palette, padded_size)?;
+        if ct != self.global_ct
+            || !color_tables_match(
+                &self.global_color_table,
+                &self.local_color_table,
+                pm.palette.colors.len(),
+                transparent_index,
+            )
+        {
+            // Use a local color table.
+            self.w.write_byte(F_COLOR_TABLE | padded_size as u8)?;
+            self.w.write_all(&self.local_color_table[..ct])?;
+        } else {
+            // Use the global color table.
+            self.w.write_byte(0)?;
+        }
+
+        let lit_width = (padded_size + 1).max(2);
+        self.w.write_byte(lit_width as u8)?; // LZW Minimum Code Size.
+
+        let mut bw = BlockWriter::new(&mut self.w);
+        {
+            let mut lzww = lzw::Writer::new(&mut bw, lzw::Order::LSB, lit_width);
+            let dx = b.dx();
+            if dx == pm.stride {
+                lzww.write_all(&pm.pix[..dx * b.dy()])?;
+            } else {
+                for row in pm.pix.chunks(pm.stride).take(b.dy()) {
+                    lzww.write_all(&row[..dx])?;
+                }
+            }
+            lzww.close()?; // flush to bw
+        }
+        bw.close() // flush to self.w
+    }
+}
+
+/// Options are the encoding parameters.
+#[derive(Default)]
+pub struct Options {
+    /// num_colors is the maximum number of colors used in the image.
+    /// It ranges from 1 to 256. Values outside that range mean 256.
+    pub num_colors: usize,
+
+    /// quantizer is used to produce a palette with size num_colors.
+    /// palette::PLAN9 is used in place of a None quantizer.
+    pub quantizer: Option<Box<dyn Quantizer>>,
+
+    /// drawer is used to convert the source image to the desired palette.
+    /// draw::FloydSteinberg is used in place of a None drawer.
+    pub drawer: Option<Box<dyn Drawer>>,
+}
+
+/// encode_all writes the images in g to w in GIF format with the
+/// given loop count and delay between frames.
+pub fn encode_all(w: &mut dyn Write, g: &GIF) -> std::io::Result<()> {
+    if g.image.is_empty() {
+        return Err(new_input_error("gif: must provide at least one image"));
+    }
+
+    if g.image.len() != g.delay.len() {
+        return Err(new_input_error("gif: mismatched image and delay lengths"));
+    }
+
+    if !g.disposal.is_empty() && g.image.len() != g.disposal.len() {
+        return Err(new_input_error(
+            "gif: mismatched image and disposal lengths",
+        ));
+    }
+    let (mut width, mut height) = (g.config.width, g.config.height);
+    match &g.config.color_model {
+        Model::Paletted(p) => {
+            if p.colors.is_empty() && width == 0 && height == 0 {
+                let p = g.image[0].bounds().max;
+                (width, height) = (p.x as usize, p.y as usize);
+            }
+        }
+        _ => {
+            return Err(new_input_error(
+                "gif: GIF color model must be a color::Palette",
+            ))
+        }
+    }
+
+    let mut e = Encoder {
+        w: bufio::Writer::new(w),
+        g,
+        width,
+        height,
+        global_ct: 0,
+        global_color_table: [0; 3 * 256],
+        local_color_table: [0; 3 * 256],
+    };
+
+    e.write_header()?;
+    for (i, pm) in g.image.iter().enumerate() {
+        let disposal = g.disposal.get(i).copied().unwrap_or(0);
+        e.write_image_block(pm, g.delay[i], disposal)?;
+    }
+    e.w.write_byte(S_TRAILER)?;
+    e.w.flush()
+}
+
+/// encode writes the Image m to w in GIF format.
+pub fn encode(w: &mut dyn Write, m: &Img, o: &Options) -> std::io::Result<()> {
+    // Check for bounds and size restrictions.
+    let b = *m.bounds();
+    if b.dx() >= 1 << 16 || b.dy() >= 1 << 16 {
+        return Err(new_input_error("gif: image is too large to encode"));
+    }
+
+    let num_colors = if (1..=256).contains(&o.num_colors) {
+        o.num_colors
+    } else {
+        256
+    };
+
+    // The single-frame image is translated such that its top-left corner is
+    // (0, 0), so that the single frame completely fills the overall GIF's bounds.
+    let origin = rect(0, 0, b.dx() as isize, b.dy() as isize);
+    let pm = match m {
+        Img::Paletted(pm) if pm.palette.colors.len() <= num_colors => {
+            let mut dst = Paletted::new(&origin, pm.palette.clone());
+            for (y, row) in dst.pix.chunks_exact_mut(b.dx().max(1)).enumerate() {
+                let i = pm.pix_offset(b.min.x, b.min.y + y as isize);
+                row.copy_from_slice(&pm.pix[i..i + row.len()]);
+            }
+            dst
+        }
+        _ => {
+            // Set pm to be a palettedized copy of m.
+            //
+            // TODO: Pick a better sub-sample of the Plan 9 palette.
+            let mut palette = color::Palette {
+                colors: palette::PLAN9[..num_colors].to_vec(),
+            };
+            if let Some(quantizer) = &o.quantizer {
+                palette = quantizer.quantize(color::Palette::new(0), num_colors, m);
+            }
+            let mut dst = Img::Paletted(Paletted::new(&origin, palette));
+            match &o.drawer {
+                Some(drawer) => drawer.draw(&mut dst, &origin, m, &b.min),
+                None => FloydSteinberg.draw(&mut dst, &origin, m, &b.min),
+            }
+            let Img::Paletted(dst) = dst else {
+                unreachable!()
+            };
+            dst
+        }
+    };
+
+    let palette = pm.palette.clone();
+    encode_all(
+        w,
+        &GIF {
+            image: vec![pm],
+            delay: vec![0],
+            config: Config {
+                color_model: Model::Paletted(palette),
+                width: b.dx(),
+                height: b.dy(),
+            },
+            ..Default::default()
+        },
+    )
+}
@@ -0,0 +1,502 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2013 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::writer::{color_tables_match, encode_color_table};
+use super::{decode, decode_all, decode_config, encode, encode_all, Options, DISPOSAL_NONE, GIF};
+use crate::image::color::{self, palette, Color, ColorTrait, Model};
+use crate::image::draw::{Op, Quantizer};
+use crate::image::{self, rect, Config, Image, Img, Paletted, Point, Rectangle};
+
+fn read_img(filename: &str) -> Box<Img> {
+    let f = std::fs::File::open(filename).unwrap();
+    image::decode(&mut std::io::BufReader::new(f)).unwrap().0
+}
+
+fn read_gif(filename: &str) -> GIF {
+    let f = std::fs::File::open(filename).unwrap();
+    decode_all(&mut std::io::BufReader::new(f)).unwrap()
+}
+
+fn delta(u0: u32, u1: u32) -> i64 {
+    (u0 as i64 - u1 as i64).abs()
+}
+
+/// average_delta returns the average delta in RGB space. The two images must
+/// have the same bounds.
+fn average_delta(m0: &Img, m1: &Img) -> i64 {
+    let b = m0.bounds();
+    let (mut sum, mut n) = (0, 0);
+    for y in b.min.y..b.max.y {
+        for x in b.min.x..b.max.x {
+            let (r0, g0, b0, _) = m0.at(x, y).rgba();
+            let (r1, g1, b1, _) = m1.at(x, y).rgba();
+            sum += delta(r0, r1) + delta(g0, g1) + delta(b0, b1);
+            n += 3;
+        }
+    }
+    sum / n
+}
+
+fn new_paletted(r: &Rectangle, colors: &[Color]) -> Paletted {
+    Paletted::new(
+        r,
+        color::Palette {
+            colors: colors.to_vec(),
+        },
+    )
+}
+
+fn encode_decode(m: &Img, o: &Options) -> Box<Img> {
+    let mut buf = Vec::new();
+    encode(&mut buf, m, o).unwrap();
+    decode(&mut buf.as_slice()).unwrap()
+}
+
+#[test]
+fn test_writer() {
+    // (filename, tolerance)
+    let test_cases = [
+        ("src/image/testdata/video-001.png", 1 << 12),
+        ("src/image/testdata/video-001.gif", 0),
+        ("src/image/testdata/video-001.interlaced.gif", 0),
+    ];
+    for (filename, tolerance) in test_cases {
+        let m0 = read_img(filename);
+        let m1 = encode_decode(&m0, &Options::default());
+        assert!(m0.bounds() == m1.bounds(), "{}: bounds differ", filename);
+        // Compare the average delta to the tolerance level.
+        let avg_delta = average_delta(&m0, &m1);
+        assert!(
+            avg_delta <= tolerance,
+            "{}: average delta is too high. expected: {}, got {}",
+            filename,
+            tolerance,
+            avg_delta
+        );
+    }
+}
+
+/// palettes_equal reports whether two palettes are equal, ignoring any
+/// trailing opaque-black palette entries.
+fn palettes_equal(p: &[Color], q: &[Color]) -> bool {
+    let n = p.len().min(q.len());
+    for i in 0..n {
+        if p[i] != q[i] {
+            return false;
+        }
+    }
+    for c in p[n..].iter().chain(q[n..].iter()) {
+        if c.rgba() != (0, 0, 0, 0xffff) {
+            return false;
+        }
+    }
+    true
+}
+
+const FRAMES: &[&str] = &[
+    "src/image/testdata/video-001.gif",
+    "src/image/testdata/video-005.gray.gif",
+];
+
+fn test_encode_all(with_optional_fields: bool, use_global_color_model: bool) {
+    const WIDTH: usize = 150;
+    const HEIGHT: usize = 103;
+
+    let mut g0 = GIF {
+        loop_count: 5,
+        ..Default::default()
+    };
+    for f in FRAMES {
+        let m = read_gif(f).image.swap_remove(0);
+        assert_eq!(
+            (m.bounds().dx(), m.bounds().dy()),
+            (WIDTH, HEIGHT),
+            "frame {}: wrong bounds",
+            f
+        );
+        g0.image.push(m);
+        g0.delay.push(0);
+    }
+    // The disposal, config and background_index fields may be left at their
+    // default values, which should still produce valid GIFs.
+    let (global_palette, background_index) = if use_global_color_model {
+        (palette::WEB_SAFE.to_vec(), 1)
+    } else {
+        (Vec::new(), 0)
+    };
+    if with_optional_fields {
+        g0.disposal = vec![DISPOSAL_NONE; g0.image.len()];
+        g0.config = Config {
+            color_model: Model::Paletted(color::Palette {
+                colors: global_palette.clone(),
+            }),
+            width: WIDTH,
+            height: HEIGHT,
+        };
+        g0.background_index = background_index;
+    }
+
+    let mut encoded = Vec::new();
+    encode_all(&mut encoded, &g0).unwrap();
+    let config = decode_config(&mut encoded.as_slice()).unwrap();
+    let g1 = decode_all(&mut encoded.as_slice()).unwrap();
+
+    assert!(config.color_model == g1.config.color_model);
+    assert_eq!((config.width, config.height), (WIDTH, HEIGHT));
+    assert_eq!((g1.config.width, g1.config.height), (WIDTH, HEIGHT));
+    let Model::Paletted(p) = &g1.config.color_model else {
+        unreachable!()
+    };
+    if with_optional_fields && use_global_color_model {
+        assert!(
+            palettes_equal(&p.colors, &global_palette),
+            "global color model: got {:?}",
+            p
+        );
+        assert_eq!(background_index, g1.background_index);
+    } else {
+        assert!(p.colors.is_empty(), "got a global color model, want none");
+        assert_eq!(0, g1.background_index);
+    }
+    assert_eq!(g0.loop_count, g1.loop_count);
+    assert_eq!(g0.image.len(), g1.image.len());
+    assert_eq!(g1.image.len(), g1.delay.len());
+    assert_eq!(g1.image.len(), g1.disposal.len());
+
+    for i in 0..g0.image.len() {
+        let (m0, m1) = (&g0.image[i], &g1.image[i]);
+        assert!(m0.bounds() == m1.bounds(), "frame {}: bounds differ", i);
+        assert_eq!(m0.pix, m1.pix, "frame {}: pixels differ", i);
+        assert!(
+            palettes_equal(&m0.palette.colors, &m1.palette.colors),
+            "frame {}: palettes differ",
+            i
+        );
+        assert_eq!(g0.delay[i], g1.delay[i], "frame {}: delay differs", i);
+        let p0 = if with_optional_fields {
+            DISPOSAL_NONE
+        } else {
+            0
+        };
+        assert_eq!(p0, g1.disposal[i], "frame {}: disposal differs", i);
+    }
+}
+
+#[test]
+fn test_encode_all_defaults() {
+    test_encode_all(false, false);
+}
+
+#[test]
+fn test_encode_all_with_optional_fields() {
+    test_encode_all(true, false);
+}
+
+#[test]
+fn test_encode_all_global_color_model() {
+    test_encode_all(true, true);
+}
+
+#[test]
+fn test_encode_mismatch_delay() {
+    let images = vec![
+        new_paletted(&rect(0, 0, 5, 5), palette::PLAN9),
+        new_paletted(&rect(0, 0, 5, 5), palette::PLAN9),
+    ];
+
+    let g0 = GIF {
+        image: images,
+        delay: vec![0],
+        ..Default::default()
+    };
+    let err = encode_all(&mut Vec::new(), &g0).err().unwrap();
+    assert_eq!(err.to_string(), "gif: mismatched image and delay lengths");
+
+    let g1 = GIF {
+        delay: vec![0; 2],
+        disposal: vec![DISPOSAL_NONE],
+        ..g0
+    };
+    let err = encode_all(&mut Vec::new(), &g1).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "gif: mismatched image and disposal lengths"
+    );
+}
+
+#[test]
+fn test_encode_zero_gif() {
+    assert!(
+        encode_all(&mut Vec::new(), &GIF::default()).is_err(),
+        "expected error from providing empty gif"
+    );
+}
+
+#[test]
+fn test_encode_all_frames_out_of_bounds() {
+    for upper_bound in [6, 10] {
+        let g = GIF {
+            image: vec![
+                new_paletted(&rect(0, 0, 5, 5), palette::PLAN9),
+                new_paletted(&rect(2, 2, 8, 8), palette::PLAN9),
+                new_paletted(&rect(3, 3, 4, 4), palette::PLAN9),
+            ],
+            delay: vec![0; 3],
+            disposal: vec![0; 3],
+            config: Config {
+                color_model: Model::Paletted(color::Palette::new(0)),
+                width: upper_bound,
+                height: upper_bound,
+            },
+            ..Default::default()
+        };
+        let res = encode_all(&mut Vec::new(), &g);
+        if upper_bound >= 8 {
+            assert!(res.is_ok(), "upper_bound={}: {:?}", upper_bound, res);
+        } else {
+            assert!(
+                res.is_err(),
+                "upper_bound={}: got no error, want one",
+                upper_bound
+            );
+        }
+    }
+}
+
+#[test]
+fn test_encode_non_zero_min_point() {
+    let points = [
+        Point::new(-8, -9),
+        Point::new(-4, -4),
+        Point::new(-3, 3),
+        Point::new(0, 0),
+        Point::new(2, 2),
+    ];
+    for p in points {
+        let r = rect(p.x, p.y, p.x + 6, p.y + 6);
+        let src = Img::Paletted(new_paletted(&r, palette::PLAN9));
+        let m = encode_decode(&src, &Options::default());
+        assert!(*m.bounds() == rect(0, 0, 6, 6), "p={:?}", p);
+    }
+
+    // Also test having a source image (gray on the diagonal) that has a
+    // non-zero bounds().min, but isn't a Paletted image.
+    let p = Point::new(2, 2);
+    let mut src = Img::new_rgba(&rect(p.x, p.y, p.x + 6, p.y + 6));
+    for i in 0..6 {
+        let v = 0x22 + 0x11 * i as u8;
+        src.set(p.x + i, p.y + i, &Color::new_rgba(v, v, v, 0xff));
+    }
+    let m = encode_decode(&src, &Options::default());
+    assert!(*m.bounds() == rect(0, 0, 6, 6));
+
+    // The gray on the diagonal should be preserved, as the Plan 9 palette
+    // has 16 gray shades.
+    for i in 0..6 {
+        let want = src.at(p.x + i, p.y + i).rgba();
+        let got = m.at(i, i).rgba();
+        assert!(
+            delta(want.0, got.0) <= 0x1111,
+            "i={}: got {:?}, want {:?}",
+            i,
+            got,
+            want
+        );
+    }
+}
+
+#[test]
+fn test_encode_implicit_config_size() {
+    for lower_bound in [-1, 0, 1] {
+        let g = GIF {
+            image: vec![new_paletted(
+                &rect(lower_bound, lower_bound, 4, 4),
+                palette::PLAN9,
+            )],
+            delay: vec![0],
+            ..Default::default()
+        };
+        let res = encode_all(&mut Vec::new(), &g);
+        if lower_bound >= 0 {
+            assert!(res.is_ok(), "lower_bound={}: {:?}", lower_bound, res);
+        } else {
+            assert!(
+                res.is_err(),
+                "lower_bound={}: got no error, want one",
+                lower_bound
+            );
+        }
+    }
+}
+
+#[test]
+fn test_encode_palettes() {
+    const W: isize = 5;
+    const H: isize = 5;
+    let pals: [&[Color]; 4] = [
+        &[
+            Color::new_rgba(0x00, 0x00, 0x00, 0xff),
+            Color::new_rgba(0x01, 0x00, 0x00, 0xff),
+            Color::new_rgba(0x02, 0x00, 0x00, 0xff),
+        ],
+        &[
+            Color::new_rgba(0x00, 0x00, 0x00, 0xff),
+            Color::new_rgba(0x00, 0x01, 0x00, 0xff),
+        ],
+        &[
+            Color::new_rgba(0x00, 0x00, 0x03, 0xff),
+            Color::new_rgba(0x00, 0x00, 0x02, 0xff),
+            Color::new_rgba(0x00, 0x00, 0x01, 0xff),
+            Color::new_rgba(0x00, 0x00, 0x00, 0xff),
+        ],
+        &[
+            Color::new_rgba(0x10, 0x07, 0xf0, 0xff),
+            Color::new_rgba(0x20, 0x07, 0xf0, 0xff),
+            Color::new_rgba(0x30, 0x07, 0xf0, 0xff),
+            Color::new_rgba(0x40, 0x07, 0xf0, 0xff),
+            Color::new_rgba(0x50, 0x07, 0xf0, 0xff),
+        ],
+    ];
+    let g0 = GIF {
+        image: pals
+            .iter()
+            .map(|p| new_paletted(&rect(0, 0, W, H), p))
+            .collect(),
+        delay: vec![0; pals.len()],
+        disposal: vec![0; pals.len()],
+        config: Config {
+            color_model: Model::Paletted(color::Palette {
+                colors: pals[2].to_vec(),
+            }),
+            width: W as usize,
+            height: H as usize,
+        },
+        ..Default::default()
+    };
+
+    let mut buf = Vec::new();
+    encode_all(&mut buf, &g0).unwrap();
+    let g1 = decode_all(&mut buf.as_slice()).unwrap();
+    assert_eq!(g0.image.len(), g1.image.len());
+    for (i, m) in g1.image.iter().enumerate() {
+        assert!(
+            palettes_equal(&m.palette.colors, pals[i]),
+            "palette #{}:\ngot  {:?}\nwant {:?}",
+            i,
+            m.palette.colors,
+            pals[i]
+        );
+    }
+}
+
+#[test]
+fn test_encode_bad_palettes() {
+    const W: isize = 5;
+    const H: isize = 5;
+    for n in [256, 257] {
+        let mut pal = color::Palette::new(n);
+        for (i, c) in pal.colors.iter_mut().enumerate() {
+            *c = Color::new_rgba(i as u8, (i >> 8) as u8, 0x00, 0xff);
+        }
+        let g = GIF {
+            image: vec![Paletted::new(&rect(0, 0, W, H), pal)],
+            delay: vec![0],
+            ..Default::default()
+        };
+        let res = encode_all(&mut Vec::new(), &g);
+        if n <= 256 {
+            assert!(res.is_ok(), "n={}: {:?}", n, res);
+        } else {
+            assert!(res.is_err(), "n={}: got no error, want one", n);
+        }
+    }
+}
+
+#[test]
+fn test_color_tables_match() {
+    const TR_IDX: usize = 100;
+    let global = color::Palette {
+        colors: palette::PLAN9.to_vec(),
+    };
+    let (r, g, b, _) = global.colors[TR_IDX].rgba();
+    assert!(r != 0 || g != 0 || b != 0, "TR_IDX is already black");
+
+    // Make a copy of the palette, substituting TR_IDX's slot with transparent,
+    // just like the decoder does.
+    let mut local = global.clone();
+    local.colors[TR_IDX] = Color::new_rgba(0, 0, 0, 0);
+
+    const TEST_LEN: usize = 3 * 256;
+    const PADDED: usize = 7;
+    let mut global_color_table = [0; 3 * 256];
+    let mut local_color_table = [0; 3 * 256];
+    assert_eq!(
+        TEST_LEN,
+        encode_color_table(&mut global_color_table, &global, PADDED).unwrap()
+    );
+    assert_eq!(
+        TEST_LEN,
+        encode_color_table(&mut local_color_table, &local, PADDED).unwrap()
+    );
+    assert!(
+        global_color_table != local_color_table,
+        "encoded color tables are equal, expected mismatch"
+    );
+    assert!(color_tables_match(
+        &global_color_table,
+        &local_color_table,
+        local.colors.len(),
+        Some(TR_IDX)
+    ));
+}
+
+/// GrayQuantizer is a Quantizer that produces a palette of evenly spaced gray
+/// levels.
+struct GrayQuantizer;
+
+impl Quantizer for GrayQuantizer {
+    fn quantize(&self, mut p: color::Palette, num_colors: usize, _m: &Img) -> color::Palette {
+        for i in p.colors.len()..num_colors {
+            p.colors
+                .push(Color::new_gray((i * 0xff / (num_colors - 1)) as u8));
+        }
+        p
+    }
+}
+
+#[test]
+fn test_encode_options() {
+    let m0 = read_img("src/image/testdata/video-005.gray.png");
+    let o = Options {
+        num_colors: 16,
+        quantizer: Some(Box::new(GrayQuantizer)),
+        drawer: Some(Box::new(Op::Src)),
+    };
+    let m1 = encode_decode(&m0, &o);
+    assert!(m0.bounds() == m1.bounds());
+    let Img::Paletted(m1) = m1.as_ref() else {
+        panic!("want a Paletted image")
+    };
+    assert_eq!(m1.palette.colors.len(), 16);
+    // Quantizing to 16 evenly spaced gray levels without dithering moves each
+    // pixel by at most half a step.
+    let b = m0.bounds();
+    for y in b.min.y..b.max.y {
+        for x in b.min.x..b.max.x {
+            let (r0, _, _, _) = m0.at(x, y).rgba();
+            let (r1, _, _, _) = m1.at(x, y).rgba();
+            assert!(delta(r0, r1) <= 0x0888, "at ({}, {})", x, y);
+        }
+    }
+}
+
+#[test]
+fn test_encode_too_large() {
+    let m = Img::Paletted(new_paletted(&rect(0, 0, 1 << 16, 1), palette::PLAN9));
+    let err = encode(&mut Vec::new(), &m, &Options::default())
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "gif: image is too large to encode");
+}
@@ -5,6 +5,7 @@
 
 use super::color::{self, ColorTrait};
 use super::geom::{Point, Rectangle};
+use super::ycbcr::{NYCbCrA, YCbCr, YCbCrSubsampleRatio};
 
 /// Config holds an image's color model and dimensions.
 #[derive(Debug)]
@@ -32,8 +33,12 @@ pub trait Image {
     /// opaque scans the entire image and reports whether it is fully opaque.
     fn opaque(&self) -> bool;
     /// stride returns the pix stride (in bytes) between vertically adjacent pixels.
+    /// For the planar YCbCr and NYCbCrA images, it is the stride of the Y plane.
     fn stride(&self) -> usize;
     /// pix returns the image pixel data.  Format of the data depends on the image type.
+    /// For the planar YCbCr and NYCbCrA images, pix holds the Y, Cb, Cr (and A)
+    /// planes one after another, while stride, bytes_per_pixel and get_line_data
+    /// describe only the Y plane.
     fn pix(&self) -> &Vec<u8>;
     /// bytes_per_pixel return how many bytes is used per pixel
     fn bytes_per_pixel(&self) -> usize;
@@ -42,14 +47,22 @@ pub trait Image {
     /// Format of the data depends on the image type.
     fn get_pix_mutable(&mut self) -> &mut Vec<u8>;
 
+    /// subsample_ratio returns the chroma subsample ratio of the planar YCbCr
+    /// and NYCbCrA images, and None for the other images.
+    fn subsample_ratio(&self) -> Option<YCbCrSubsampleRatio> {
+        None
+    }
+
     /// deep_equal checks that two images are exactly the same
     fn deep_equal(&self, other: &dyn Image) -> bool {
         self.color_model() == other.color_model()
             && self.bounds() == other.bounds()
+            && self.subsample_ratio() == other.subsample_ratio()
             && self.pix() == other.pix()
     }
 
     /// get_line_data returns data that encode one line of pixels.
+    /// For the planar YCbCr and NYCbCrA images, it is a line of the Y plane.
     fn get_line_data(&self, y: isize) -> &[u8] {
         let b = self.bounds();
         let offset = (y - b.min.y) as usize * self.stride();
@@ -1371,7 +1384,7 @@ pub struct Paletted {
     /// rect is the image's bounds.
     rect: Rectangle,
     /// palette is the image's palette.
-    pub(super) palette: color::Palette,
+    pub palette: color::Palette,
 }
 
 impl Image for Paletted {
@@ -1538,6 +1551,8 @@ pub enum Img {
     Paletted(Paletted),
     RGBA(RGBA),
     RGBA64(RGBA64),
+    YCbCr(YCbCr),
+    NYCbCrA(NYCbCrA),
 }
 
 impl Img {
@@ -1557,6 +1572,14 @@ impl Img {
     pub fn new_rgba64(r: &Rectangle) -> Img {
         Img::RGBA64(RGBA64::new(r))
     }
+    /// new_ycbcr creates a new YCbCr image.
+    pub fn new_ycbcr(r: &Rectangle, subsample_ratio: YCbCrSubsampleRatio) -> Img {
+        Img::YCbCr(YCbCr::new(r, subsample_ratio))
+    }
+    /// new_nycbcra creates a new NYCbCrA image.
+    pub fn new_nycbcra(r: &Rectangle, subsample_ratio: YCbCrSubsampleRatio) -> Img {
+        Img::NYCbCrA(NYCbCrA::new(r, subsample_ratio))
+    }
 }
 
 impl Image for Img {
@@ -1571,6 +1594,15 @@ impl Image for Img {
             Img::Alpha16(img) => img.bytes_per_pixel(),
             Img::Gray(img) => img.bytes_per_pixel(),
             Img::Gray16(img) => img.bytes_per_pixel(),
+            Img::YCbCr(img) => img.bytes_per_pixel(),
+            Img::NYCbCrA(img) => img.bytes_per_pixel(),
+        }
+    }
+    fn subsample_ratio(&self) -> Option<YCbCrSubsampleRatio> {
+        match self {
+            Img::YCbCr(img) => img.subsample_ratio(),
+            Img::NYCbCrA(img) => img.subsample_ratio(),
+            _ => None,
         }
     }
     fn stride(&self) -> usize {
@@ -1584,6 +1616,8 @@ impl Image for Img {
             Img::Alpha16(img) => img.stride(),
             Img::Gray(img) => img.stride(),
             Img::Gray16(img) => img.stride(),
+            Img::YCbCr(img) => img.stride(),
+            Img::NYCbCrA(img) => img.stride(),
         }
     }
     fn bounds(&self) -> &Rectangle {
@@ -1597,6 +1631,8 @@ impl Image for Img {
             Img::Alpha16(img) => img.bounds(),
             Img::Gray(img) => img.bounds(),
             Img::Gray16(img) => img.bounds(),
+            Img::YCbCr(img) => img.bounds(),
+            Img::NYCbCrA(img) => img.bounds(),
         }
     }
     fn pix(&self) -> &Vec<u8> {
@@ -1610,6 +1646,8 @@ impl Image for Img {
             Img::Alpha16(img) => img.pix(),
             Img::Gray(img) => img.pix(),
             Img::Gray16(img) => img.pix(),
+            Img::YCbCr(img) => img.pix(),
+            Img::NYCbCrA(img) => img.pix(),
         }
     }
     fn get_pix_mutable(&mut self) -> &mut Vec<u8> {
@@ -1623,6 +1661,8 @@ impl Image for Img {
             Img::Alpha16(img) => img.get_pix_mutable(),
             Img::Gray(img) => img.get_pix_mutable(),
             Img::Gray16(img) => img.get_pix_mutable(),
+            Img::YCbCr(img) => img.get_pix_mutable(),
+            Img::NYCbCrA(img) => img.get_pix_mutable(),
         }
     }
 
@@ -1637,6 +1677,8 @@ impl Image for Img {
             Img::Alpha16(img) => img.color_model(),
             Img::Gray(img) => img.color_model(),
             Img::Gray16(img) => img.color_model(),
+            Img::YCbCr(img) => img.color_model(),
+            Img::NYCbCrA(img) => img.color_model(),
         }
     }
 
@@ -1651,6 +1693,8 @@ impl Image for Img {
             Img::Alpha16(img) => img.at(x, y),
             Img::Gray(img) => img.at(x, y),
             Img::Gray16(img) => img.at(x, y),
+            Img::YCbCr(img) => img.at(x, y),
+            Img::NYCbCrA(img) => img.at(x, y),
         }
     }
 
@@ -1665,6 +1709,8 @@ impl Image for Img {
             Img::Alpha16(img) => img.set(x, y, c),
             Img::Gray(img) => img.set(x, y, c),
             Img::Gray16(img) => img.set(x, y, c),
+            Img::YCbCr(img) => img.set(x, y, c),
+            Img::NYCbCrA(img) => img.set(x, y, c),
         }
     }
 
@@ -1679,6 +1725,8 @@ impl Image for Img {
             Img::Alpha16(img) => img.opaque(),
             Img::Gray(img) => img.opaque(),
             Img::Gray16(img) => img.opaque(),
+            Img::YCbCr(img) => img.opaque(),
+            Img::NYCbCrA(img) => img.opaque(),
         }
     }
 }
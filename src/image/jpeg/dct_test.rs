@@ -0,0 +1,197 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2012 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::fdct::fdct;
+use super::idct::{idct, Block, BLOCK_SIZE};
+use crate::math::rand;
+
+/// test_blocks returns a few fixed blocks and some randomly generated blocks
+/// of varying sparseness.
+fn test_blocks() -> Vec<Block> {
+    let mut blocks = Vec::new();
+    blocks.push([0; BLOCK_SIZE]);
+    let mut b = [0; BLOCK_SIZE];
+    b[0] = 255;
+    blocks.push(b);
+    let mut b = [0; BLOCK_SIZE];
+    for (i, v) in b.iter_mut().enumerate() {
+        *v = (i * 4) as i32;
+    }
+    blocks.push(b);
+    blocks.push([128; BLOCK_SIZE]);
+
+    let mut r = rand::Rand::new(rand::new_source(123));
+    for _ in 0..100 {
+        let mut b = [0; BLOCK_SIZE];
+        let n = r.int() as usize % 64;
+        for _ in 0..n {
+            b[r.int() as usize % BLOCK_SIZE] = r.int31() % 256;
+        }
+        blocks.push(b);
+    }
+    blocks
+}
+
+#[test]
+fn test_dct() {
+    let blocks = test_blocks();
+
+    // Check that the FDCT and IDCT functions are inverses, after a scale and
+    // level shift. Scaling reduces the rounding errors in the conversion from
+    // floats to ints.
+    for (i, b) in blocks.iter().enumerate() {
+        let mut got = *b;
+        let want = *b;
+        for v in got.iter_mut() {
+            *v = (*v - 128) * 8;
+        }
+        slow_fdct(&mut got);
+        slow_idct(&mut got);
+        for v in got.iter_mut() {
+            *v = *v / 8 + 128;
+        }
+        assert!(
+            !differ(&got, &want),
+            "i={}: IDCT(FDCT)\nsrc\n{}\ngot\n{}\nwant\n{}\n",
+            i,
+            block_string(b),
+            block_string(&got),
+            block_string(&want)
+        );
+    }
+
+    // Check that the optimized and slow FDCT implementations agree.
+    // The fdct function already does a scale and level shift.
+    for (i, b) in blocks.iter().enumerate() {
+        let mut got = *b;
+        let mut want = *b;
+        fdct(&mut got);
+        for v in want.iter_mut() {
+            *v = (*v - 128) * 8;
+        }
+        slow_fdct(&mut want);
+        assert!(
+            !differ(&got, &want),
+            "i={}: FDCT\nsrc\n{}\ngot\n{}\nwant\n{}\n",
+            i,
+            block_string(b),
+            block_string(&got),
+            block_string(&want)
+        );
+    }
+
+    // Check that the optimized and slow IDCT implementations agree.
+    for (i, b) in blocks.iter().enumerate() {
+        let mut got = *b;
+        let mut want = *b;
+        idct(&mut got);
+        slow_idct(&mut want);
+        assert!(
+            !differ(&got, &want),
+            "i={}: IDCT\nsrc\n{}\ngot\n{}\nwant\n{}\n",
+            i,
+            block_string(b),
+            block_string(&got),
+            block_string(&want)
+        );
+    }
+}
+
+/// differ reports whether any pair-wise elements in b0 and b1 differ by 2 or
+/// more. That tolerance is because there isn't a single definitive decoding of
+/// a given JPEG image, even before the YCbCr to RGB conversion; implementations
+/// can have different IDCT rounding errors.
+fn differ(b0: &Block, b1: &Block) -> bool {
+    b0.iter().zip(b1.iter()).any(|(x, y)| (x - y).abs() > 2)
+}
+
+/// alpha returns 1 if i is 0 and returns √2 otherwise.
+fn alpha(i: usize) -> f64 {
+    if i == 0 {
+        return 1.0;
+    }
+    std::f64::consts::SQRT_2
+}
+
+/// cosine returns cos(π/16 * i).
+fn cosine(i: usize) -> f64 {
+    (std::f64::consts::PI / 16.0 * i as f64).cos()
+}
+
+/// slow_fdct performs the 8*8 2-dimensional forward discrete cosine transform:
+///
+///     dst[u,v] = (1/8) * Σ_x Σ_y alpha(u) * alpha(v) * src[x,y] *
+///         cos((π/2) * (2*x + 1) * u / 8) *
+///         cos((π/2) * (2*y + 1) * v / 8)
+///
+/// x and y are in pixel space, and u and v are in transform space.
+///
+/// b acts as both dst and src.
+fn slow_fdct(b: &mut Block) {
+    let mut dst = [0.0; BLOCK_SIZE];
+    for v in 0..8 {
+        for u in 0..8 {
+            let mut sum = 0.0;
+            for y in 0..8 {
+                for x in 0..8 {
+                    sum += alpha(u)
+                        * alpha(v)
+                        * b[8 * y + x] as f64
+                        * cosine(((2 * x + 1) * u) % 32)
+                        * cosine(((2 * y + 1) * v) % 32);
+                }
+            }
+            dst[8 * v + u] = sum / 8.0;
+        }
+    }
+    // Convert from f64 to i32.
+    for (i, v) in dst.iter().enumerate() {
+        b[i] = (v + 0.5) as i32;
+    }
+}
+
+/// slow_idct performs the 8*8 2-dimensional inverse discrete cosine transform:
+///
+///     dst[x,y] = (1/8) * Σ_u Σ_v alpha(u) * alpha(v) * src[u,v] *
+///         cos((π/2) * (2*x + 1) * u / 8) *
+///         cos((π/2) * (2*y + 1) * v / 8)
+///
+/// x and y are in pixel space, and u and v are in transform space.
+///
+/// b acts as both dst and src.
+fn slow_idct(b: &mut Block) {
+    let mut dst = [0.0; BLOCK_SIZE];
+    for y in 0..8 {
+        for x in 0..8 {
+            let mut sum = 0.0;
+            for v in 0..8 {
+                for u in 0..8 {
+                    sum += alpha(u)
+                        * alpha(v)
+                        * b[8 * v + u] as f64
+                        * cosine(((2 * x + 1) * u) % 32)
+                        * cosine(((2 * y + 1) * v) % 32);
+                }
+            }
+            dst[8 * y + x] = sum / 8.0;
+        }
+    }
+    // Convert from f64 to i32.
+    for (i, v) in dst.iter().enumerate() {
+        b[i] = (v + 0.5) as i32;
+    }
+}
+
+fn block_string(b: &Block) -> String {
+    let mut s = String::new();
+    for y in 0..8 {
+        s.push('\t');
+        for x in 0..8 {
+            s.push_str(&format!("0x{:04x}, ", b[8 * y + x] as u16));
+        }
+        s.push('\n');
+    }
+    s
+}
@@ -0,0 +1,181 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// This file implements a Forward Discrete Cosine Transformation.
+
+/*
+It is based on the code in jfdctint.c from the Independent JPEG Group,
+found at http://www.ijg.org/files/jpegsrc.v8c.tar.gz.
+
+The "LEGAL ISSUES" section of the README in that archive says:
+
+In plain English:
+
+1. We don't promise that this software works.  (But if you find any bugs,
+   please let us know!)
+2. You can use this software for whatever you want.  You don't have to pay us.
+3. You may not pretend that you wrote this software.  If you use it in a
+   program, you must acknowledge somewhere in your documentation that
+   you've used the IJG code.
+
+In legalese:
+
+The authors make NO WARRANTY or representation, either express or implied,
+with respect to this software, its quality, accuracy, merchantability, or
+fitness for a particular purpose.  This software is provided "AS IS", and you,
+its user, assume the entire risk as to its quality and accuracy.
+
+This software is copyright (C) 1991-2011, Thomas G. Lane, Guido Vollbeding.
+All Rights Reserved except as specified below.
+
+Permission is hereby granted to use, copy, modify, and distribute this
+software (or portions thereof) for any purpose, without fee, subject to these
+conditions:
+(1) If any part of the source code for this software is distributed, then this
+README file must be included, with this copyright and no-warranty notice
+unaltered; and any additions, deletions, or changes to the original files
+must be clearly indicated in accompanying documentation.
+(2) If only executable code is distributed, then the accompanying
+documentation must state that "this software is based in part on the work of
+the Independent JPEG Group".
+(3) Permission for use of this software is granted only if the user accepts
+full responsibility for any undesirable consequences; the authors accept
+NO LIABILITY for damages of any kind.
+
+These conditions apply to any software derived from or based on the IJG code,
+not just to the unmodified library.  If you use our work, you ought to
+acknowledge us.
+
+Permission is NOT granted for the use of any IJG author's name or company name
+in advertising or publicity relating to this software or products derived from
+it.  This software may be referred to only as "the Independent JPEG Group's
+software".
+
+We specifically permit and encourage the use of this software as the basis of
+commercial products, provided that all warranty or liability claims are
+assumed by the product vendor.
+*/
+
+use super::idct::Block;
+
+// Trigonometric constants in 13-bit fixed point format.
+const FIX_0_298631336: i32 = 2446;
+const FIX_0_390180644: i32 = 3196;
+const FIX_0_541196100: i32 = 4433;
+const FIX_0_765366865: i32 = 6270;
+const FIX_0_899976223: i32 = 7373;
+const FIX_1_175875602: i32 = 9633;
+const FIX_1_501321110: i32 = 12299;
+const FIX_1_847759065: i32 = 15137;
+const FIX_1_961570560: i32 = 16069;
+const FIX_2_053119869: i32 = 16819;
+const FIX_2_562915447: i32 = 20995;
+const FIX_3_072711026: i32 = 25172;
+
+const CONST_BITS: u32 = 13;
+const PASS1_BITS: u32 = 2;
+const CENTER_J_SAMPLE: i32 = 128;
+
+/// fdct performs a forward DCT on an 8x8 block of coefficients, including a
+/// level shift.
+pub(super) fn fdct(b: &mut Block) {
+    // Pass 1: process rows.
+    for s in b.chunks_exact_mut(8) {
+        let (x0, x1, x2, x3) = (s[0], s[1], s[2], s[3]);
+        let (x4, x5, x6, x7) = (s[4], s[5], s[6], s[7]);
+
+        let mut tmp0 = x0 + x7;
+        let mut tmp1 = x1 + x6;
+        let mut tmp2 = x2 + x5;
+        let mut tmp3 = x3 + x4;
+
+        let mut tmp10 = tmp0 + tmp3;
+        let mut tmp12 = tmp0 - tmp3;
+        let mut tmp11 = tmp1 + tmp2;
+        let mut tmp13 = tmp1 - tmp2;
+
+        tmp0 = x0 - x7;
+        tmp1 = x1 - x6;
+        tmp2 = x2 - x5;
+        tmp3 = x3 - x4;
+
+        s[0] = (tmp10 + tmp11 - 8 * CENTER_J_SAMPLE) << PASS1_BITS;
+        s[4] = (tmp10 - tmp11) << PASS1_BITS;
+        let mut z1 = (tmp12 + tmp13) * FIX_0_541196100;
+        z1 += 1 << (CONST_BITS - PASS1_BITS - 1);
+        s[2] = (z1 + tmp12 * FIX_0_765366865) >> (CONST_BITS - PASS1_BITS);
+        s[6] = (z1 - tmp13 * FIX_1_847759065) >> (CONST_BITS - PASS1_BITS);
+
+        tmp10 = tmp0 + tmp3;
+        tmp11 = tmp1 + tmp2;
+        tmp12 = tmp0 + tmp2;
+        tmp13 = tmp1 + tmp3;
+        z1 = (tmp12 + tmp13) * FIX_1_175875602;
+        z1 += 1 << (CONST_BITS - PASS1_BITS - 1);
+        tmp0 *= FIX_1_501321110;
+        tmp1 *= FIX_3_072711026;
+        tmp2 *= FIX_2_053119869;
+        tmp3 *= FIX_0_298631336;
+        tmp10 *= -FIX_0_899976223;
+        tmp11 *= -FIX_2_562915447;
+        tmp12 *= -FIX_0_390180644;
+        tmp13 *= -FIX_1_961570560;
+
+        tmp12 += z1;
+        tmp13 += z1;
+        s[1] = (tmp0 + tmp10 + tmp12) >> (CONST_BITS - PASS1_BITS);
+        s[3] = (tmp1 + tmp11 + tmp13) >> (CONST_BITS - PASS1_BITS);
+        s[5] = (tmp2 + tmp11 + tmp12) >> (CONST_BITS - PASS1_BITS);
+        s[7] = (tmp3 + tmp10 + tmp13) >> (CONST_BITS - PASS1_BITS);
+    }
+    // Pass 2: process columns.
+    // We remove PASS1_BITS scaling, but leave results scaled up by an overall factor of 8.
+    for x in 0..8 {
+        let mut tmp0 = b[x] + b[7 * 8 + x];
+        let mut tmp1 = b[8 + x] + b[6 * 8 + x];
+        let mut tmp2 = b[2 * 8 + x] + b[5 * 8 + x];
+        let mut tmp3 = b[3 * 8 + x] + b[4 * 8 + x];
+
+        let mut tmp10 = tmp0 + tmp3 + (1 << (PASS1_BITS - 1));
+        let mut tmp12 = tmp0 - tmp3;
+        let mut tmp11 = tmp1 + tmp2;
+        let mut tmp13 = tmp1 - tmp2;
+
+        tmp0 = b[x] - b[7 * 8 + x];
+        tmp1 = b[8 + x] - b[6 * 8 + x];
+        tmp2 = b[2 * 8 + x] - b[5 * 8 + x];
+        tmp3 = b[3 * 8 + x] - b[4 * 8 + x];
+
+        b[x] = (tmp10 + tmp11) >> PASS1_BITS;
+        b[4 * 8 + x] = (tmp10 - tmp11) >> PASS1_BITS;
+
+        let mut z1 = (tmp12 + tmp13) * FIX_0_541196100;
+        z1 += 1 << (CONST_BITS + PASS1_BITS - 1);
+        b[2 * 8 + x] = (z1 + tmp12 * FIX_0_765366865) >> (CONST_BITS + PASS1_BITS);
+        b[6 * 8 + x] = (z1 - tmp13 * FIX_1_847759065) >> (CONST_BITS + PASS1_BITS);
+
+        tmp10 = tmp0 + tmp3;
+        tmp11 = tmp1 + tmp2;
+        tmp12 = tmp0 + tmp2;
+        tmp13 = tmp1 + tmp3;
+        z1 = (tmp12 + tmp13) * FIX_1_175875602;
+        z1 += 1 << (CONST_BITS + PASS1_BITS - 1);
+        tmp0 *= FIX_1_501321110;
+        tmp1 *= FIX_3_072711026;
+        tmp2 *= FIX_2_053119869;
+        tmp3 *= FIX_0_298631336;
+        tmp10 *= -FIX_0_899976223;
+        tmp11 *= -FIX_2_562915447;
+        tmp12 *= -FIX_0_390180644;
+        tmp13 *= -FIX_1_961570560;
+
+        tmp12 += z1;
+        tmp13 += z1;
+        b[8 + x] = (tmp0 + tmp10 + tmp12) >> (CONST_BITS + PASS1_BITS);
+        b[3 * 8 + x] = (tmp1 + tmp11 + tmp13) >> (CONST_BITS + PASS1_BITS);
+        b[5 * 8 + x] = (tmp2 + tmp11 + tmp12) >> (CONST_BITS + PASS1_BITS);
+        b[7 * 8 + x] = (tmp3 + tmp10 + tmp13) >> (CONST_BITS + PASS1_BITS);
+    }
+}
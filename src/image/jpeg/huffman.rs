@@ -0,0 +1,259 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2009 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::reader::{is_format_error, new_format_error, Decoder, ERR_MISSING_FF00, MAX_TC, MAX_TH};
+use std::io::Read;
+
+/// MAX_CODE_LENGTH is the maximum (inclusive) number of bits in a Huffman code.
+const MAX_CODE_LENGTH: usize = 16;
+
+/// MAX_N_CODES is the maximum (inclusive) number of codes in a Huffman tree.
+const MAX_N_CODES: usize = 256;
+
+/// LUT_SIZE is the log-2 size of the Huffman decoder's look-up table.
+const LUT_SIZE: i32 = 8;
+
+/// Huffman is a Huffman decoder, specified in section C.
+#[derive(Clone, Copy)]
+pub(super) struct Huffman {
+    /// n_codes is the number of codes in the tree.
+    n_codes: i32,
+    /// lut is the look-up table for the next LUT_SIZE bits in the bit-stream.
+    /// The high 8 bits of the u16 are the encoded value. The low 8 bits
+    /// are 1 plus the code length, or 0 if the value is too large to fit in
+    /// LUT_SIZE bits.
+    lut: [u16; 1 << LUT_SIZE],
+    /// vals are the decoded values, sorted by their encoding.
+    vals: [u8; MAX_N_CODES],
+    /// min_codes[i] is the minimum code of length i, or -1 if there are no
+    /// codes of that length.
+    min_codes: [i32; MAX_CODE_LENGTH],
+    /// max_codes[i] is the maximum code of length i, or -1 if there are no
+    /// codes of that length.
+    max_codes: [i32; MAX_CODE_LENGTH],
+    /// vals_indices[i] is the index into vals of min_codes[i].
+    vals_indices: [i32; MAX_CODE_LENGTH],
+}
+
+impl Huffman {
+    pub(super) const fn new() -> Self {
+        Self {
+            n_codes: 0,
+            lut: [0; 1 << LUT_SIZE],
+            vals: [0; MAX_N_CODES],
+            min_codes: [0; MAX_CODE_LENGTH],
+            max_codes: [0; MAX_CODE_LENGTH],
+            vals_indices: [0; MAX_CODE_LENGTH],
+        }
+    }
+}
+
+/// ERR_SHORT_HUFFMAN_DATA means that an unexpected EOF occurred while decoding
+/// Huffman data.
+pub(super) const ERR_SHORT_HUFFMAN_DATA: &str = "short Huffman data";
+
+impl<Input: Read> Decoder<'_, Input> {
+    /// ensure_n_bits reads bytes from the byte buffer to ensure that self.bits.n is at
+    /// least n. For best performance (avoiding function calls inside hot loops),
+    /// the caller is the one responsible for first checking that self.bits.n < n.
+    pub(super) fn ensure_n_bits(&mut self, n: i32) -> std::io::Result<()> {
+        loop {
+            let c = match self.read_byte_stuffed_byte() {
+                Ok(c) => c,
+                Err(err) => {
+                    if err.kind() == std::io::ErrorKind::UnexpectedEof {
+                        return Err(new_format_error(ERR_SHORT_HUFFMAN_DATA));
+                    }
+                    return Err(err);
+                }
+            };
+            self.bits.a = self.bits.a << 8 | c as u32;
+            self.bits.n += 8;
+            if self.bits.m == 0 {
+                self.bits.m = 1 << 7;
+            } else {
+                self.bits.m <<= 8;
+            }
+            if self.bits.n >= n {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// receive_extend is the composition of RECEIVE and EXTEND, specified in section
+    /// F.2.2.1.
+    pub(super) fn receive_extend(&mut self, t: u8) -> std::io::Result<i32> {
+        if self.bits.n < t as i32 {
+            self.ensure_n_bits(t as i32)?;
+        }
+        self.bits.n -= t as i32;
+        self.bits.m >>= t;
+        let s = 1_i32 << t;
+        let mut x = (self.bits.a >> self.bits.n) as i32 & (s - 1);
+        if x < s >> 1 {
+            x += (-1_i32 << t) + 1;
+        }
+        Ok(x)
+    }
+
+    /// process_dht processes a Define Huffman Table marker, and initializes a Huffman
+    /// struct from its contents. Specified in section B.2.4.2.
+    pub(super) fn process_dht(&mut self, mut n: usize) -> std::io::Result<()> {
+        while n > 0 {
+            if n < 17 {
+                return Err(new_format_error("DHT has wrong length"));
+            }
+            let mut tmp = [0; 17];
+            self.read_full(&mut tmp)?;
+            let tc = (tmp[0] >> 4) as usize;
+            if tc > MAX_TC {
+                return Err(new_format_error("bad Tc value"));
+            }
+            let th = (tmp[0] & 0x0f) as usize;
+            // The baseline th <= 1 restriction is specified in table B.5.
+            if th > MAX_TH || (self.baseline && th > 1) {
+                return Err(new_format_error("bad Th value"));
+            }
+
+            // Read n_codes and h.vals (and derive h.n_codes).
+            // n_codes[i] is the number of codes with code length i.
+            // h.n_codes is the total number of codes.
+            let mut h = Huffman::new();
+            let mut n_codes = [0_i32; MAX_CODE_LENGTH];
+            for (i, nc) in n_codes.iter_mut().enumerate() {
+                *nc = tmp[i + 1] as i32;
+                h.n_codes += *nc;
+            }
+            if h.n_codes == 0 {
+                return Err(new_format_error("Huffman table has zero length"));
+            }
+            if h.n_codes as usize > MAX_N_CODES {
+                return Err(new_format_error("Huffman table has excessive length"));
+            }
+            if n < h.n_codes as usize + 17 {
+                return Err(new_format_error("DHT has wrong length"));
+            }
+            n -= h.n_codes as usize + 17;
+            self.read_full(&mut h.vals[..h.n_codes as usize])?;
+
+            // Derive the look-up table.
+            let mut x = 0;
+            let mut code = 0_u32;
+            for i in 0..LUT_SIZE as u32 {
+                code <<= 1;
+                for _ in 0..n_codes[i as usize] {
+                    // The code_length is 1+i, so shift code by 8-(1+i) to
+                    // calculate the high bits for every 8-bit sequence
+                    // whose code_length's high bits matches code.
+                    // The high 8 bits of lut_value are the encoded value.
+                    // The low 8 bits are 1 plus the code_length.
+                    let base = (code << (7 - i)) as u8 as usize;
+                    let lut_value = (h.vals[x] as u16) << 8 | (2 + i) as u16;
+                    for k in 0..1 << (7 - i) {
+                        h.lut[base | k] = lut_value;
+                    }
+                    code += 1;
+                    x += 1;
+                }
+            }
+
+            // Derive min_codes, max_codes, and vals_indices.
+            let mut c = 0;
+            let mut index = 0;
+            for (i, &nc) in n_codes.iter().enumerate() {
+                if nc == 0 {
+                    h.min_codes[i] = -1;
+                    h.max_codes[i] = -1;
+                    h.vals_indices[i] = -1;
+                } else {
+                    h.min_codes[i] = c;
+                    h.max_codes[i] = c + nc - 1;
+                    h.vals_indices[i] = index;
+                    c += nc;
+                    index += nc;
+                }
+                c <<= 1;
+            }
+            self.huff[tc][th] = h;
+        }
+        Ok(())
+    }
+
+    /// decode_huffman returns the next Huffman-coded value from the bit-stream,
+    /// decoded according to the table self.huff[tc][th].
+    pub(super) fn decode_huffman(&mut self, tc: usize, th: usize) -> std::io::Result<u8> {
+        if self.huff[tc][th].n_codes == 0 {
+            return Err(new_format_error("uninitialized Huffman table"));
+        }
+
+        let mut slow_path = false;
+        if self.bits.n < 8 {
+            if let Err(err) = self.ensure_n_bits(8) {
+                if !is_format_error(&err, ERR_MISSING_FF00)
+                    && !is_format_error(&err, ERR_SHORT_HUFFMAN_DATA)
+                {
+                    return Err(err);
+                }
+                // There are no more bytes of data in this segment, but we may still
+                // be able to read the next symbol out of the previously read bits.
+                // First, undo the read_byte that the ensure_n_bits call made.
+                if self.bytes.n_unreadable != 0 {
+                    self.unread_byte_stuffed_byte();
+                }
+                slow_path = true;
+            }
+        }
+        if !slow_path {
+            let h = &self.huff[tc][th];
+            let v = h.lut[((self.bits.a >> (self.bits.n - LUT_SIZE)) & 0xff) as usize];
+            if v != 0 {
+                let n = (v & 0xff) - 1;
+                self.bits.n -= n as i32;
+                self.bits.m >>= n;
+                return Ok((v >> 8) as u8);
+            }
+        }
+
+        let mut code = 0_i32;
+        for i in 0..MAX_CODE_LENGTH {
+            if self.bits.n == 0 {
+                self.ensure_n_bits(1)?;
+            }
+            if self.bits.a & self.bits.m != 0 {
+                code |= 1;
+            }
+            self.bits.n -= 1;
+            self.bits.m >>= 1;
+            let h = &self.huff[tc][th];
+            if code <= h.max_codes[i] {
+                return Ok(h.vals[(h.vals_indices[i] + code - h.min_codes[i]) as usize]);
+            }
+            code <<= 1;
+        }
+        Err(new_format_error("bad Huffman code"))
+    }
+
+    pub(super) fn decode_bit(&mut self) -> std::io::Result<bool> {
+        if self.bits.n == 0 {
+            self.ensure_n_bits(1)?;
+        }
+        let ret = self.bits.a & self.bits.m != 0;
+        self.bits.n -= 1;
+        self.bits.m >>= 1;
+        Ok(ret)
+    }
+
+    pub(super) fn decode_bits(&mut self, n: i32) -> std::io::Result<u32> {
+        if self.bits.n < n {
+            self.ensure_n_bits(n)?;
+        }
+        let mut ret = self.bits.a >> (self.bits.n - n);
+        ret &= (1 << n) - 1;
+        self.bits.n -= n;
+        self.bits.m >>= n;
+        Ok(ret)
+    }
+}
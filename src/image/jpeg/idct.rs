@@ -0,0 +1,187 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2009 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// This is a Rust translation of idct.c from
+//
+// http://standards.iso.org/ittf/PubliclyAvailableStandards/ISO_IEC_13818-4_2004_Conformance_Testing/Video/verifier/mpeg2decode_960109.tar.gz
+//
+// which carries the following notice:
+
+/* Copyright (C) 1996, MPEG Software Simulation Group. All Rights Reserved. */
+
+/*
+ * Disclaimer of Warranty
+ *
+ * These software programs are available to the user without any license fee or
+ * royalty on an "as is" basis.  The MPEG Software Simulation Group disclaims
+ * any and all warranties, whether express, implied, or statuary, including any
+ * implied warranties or merchantability or of fitness for a particular
+ * purpose.  In no event shall the copyright-holder be liable for any
+ * incidental, punitive, or consequential damages of any kind whatsoever
+ * arising from the use of these programs.
+ *
+ * This disclaimer of warranty extends to the user of these programs and user's
+ * customers, employees, agents, transferees, successors, and assigns.
+ *
+ * The MPEG Software Simulation Group does not represent or warrant that the
+ * programs furnished hereunder are free of infringement of any third-party
+ * patents.
+ *
+ * Commercial implementations of MPEG-1 and MPEG-2 video, including shareware,
+ * are subject to royalty fees to patent holders.  Many of these patents are
+ * general enough such that they are unavoidable regardless of implementation
+ * design.
+ *
+ */
+
+/// A DCT block is 8x8.
+pub(super) const BLOCK_SIZE: usize = 64;
+
+pub(super) type Block = [i32; BLOCK_SIZE];
+
+const W1: i64 = 2841; // 2048*sqrt(2)*cos(1*pi/16)
+const W2: i64 = 2676; // 2048*sqrt(2)*cos(2*pi/16)
+const W3: i64 = 2408; // 2048*sqrt(2)*cos(3*pi/16)
+const W5: i64 = 1609; // 2048*sqrt(2)*cos(5*pi/16)
+const W6: i64 = 1108; // 2048*sqrt(2)*cos(6*pi/16)
+const W7: i64 = 565; // 2048*sqrt(2)*cos(7*pi/16)
+
+const W1PW7: i64 = W1 + W7;
+const W1MW7: i64 = W1 - W7;
+const W2PW6: i64 = W2 + W6;
+const W2MW6: i64 = W2 - W6;
+const W3PW5: i64 = W3 + W5;
+const W3MW5: i64 = W3 - W5;
+
+const R2: i64 = 181; // 256/sqrt(2)
+
+/// idct performs a 2-D Inverse Discrete Cosine Transformation.
+///
+/// The input coefficients should already have been multiplied by the
+/// appropriate quantization table. We use fixed-point computation, with the
+/// number of bits for the fractional component varying over the intermediate
+/// stages.
+///
+/// The intermediate values are computed with 64 bits, so that corrupt input
+/// cannot overflow. For valid input the results are the same as with the
+/// original 32-bit computation.
+///
+/// For more on the actual algorithm, see Z. Wang, "Fast algorithms for the
+/// discrete W transform and for the discrete Fourier transform", IEEE Trans. on
+/// ASSP, Vol. ASSP- 32, pp. 803-816, Aug. 1984.
+pub(super) fn idct(src: &mut Block) {
+    // Horizontal 1-D IDCT.
+    for y in 0..8 {
+        let s = &mut src[y * 8..y * 8 + 8];
+        // If all the AC components are zero, then the IDCT is trivial.
+        if s[1..].iter().all(|&v| v == 0) {
+            let dc = s[0] << 3;
+            s.fill(dc);
+            continue;
+        }
+
+        // Prescale.
+        let mut x0 = ((s[0] as i64) << 11) + 128;
+        let mut x1 = (s[4] as i64) << 11;
+        let mut x2 = s[6] as i64;
+        let mut x3 = s[2] as i64;
+        let mut x4 = s[1] as i64;
+        let mut x5 = s[7] as i64;
+        let mut x6 = s[5] as i64;
+        let mut x7 = s[3] as i64;
+
+        // Stage 1.
+        let mut x8 = W7 * (x4 + x5);
+        x4 = x8 + W1MW7 * x4;
+        x5 = x8 - W1PW7 * x5;
+        x8 = W3 * (x6 + x7);
+        x6 = x8 - W3MW5 * x6;
+        x7 = x8 - W3PW5 * x7;
+
+        // Stage 2.
+        x8 = x0 + x1;
+        x0 -= x1;
+        x1 = W6 * (x3 + x2);
+        x2 = x1 - W2PW6 * x2;
+        x3 = x1 + W2MW6 * x3;
+        x1 = x4 + x6;
+        x4 -= x6;
+        x6 = x5 + x7;
+        x5 -= x7;
+
+        // Stage 3.
+        x7 = x8 + x3;
+        x8 -= x3;
+        x3 = x0 + x2;
+        x0 -= x2;
+        x2 = (R2 * (x4 + x5) + 128) >> 8;
+        x4 = (R2 * (x4 - x5) + 128) >> 8;
+
+        // Stage 4.
+        s[0] = ((x7 + x1) >> 8) as i32;
+        s[1] = ((x3 + x2) >> 8) as i32;
+        s[2] = ((x0 + x4) >> 8) as i32;
+        s[3] = ((x8 + x6) >> 8) as i32;
+        s[4] = ((x8 - x6) >> 8) as i32;
+        s[5] = ((x0 - x4) >> 8) as i32;
+        s[6] = ((x3 - x2) >> 8) as i32;
+        s[7] = ((x7 - x1) >> 8) as i32;
+    }
+
+    // Vertical 1-D IDCT.
+    for x in 0..8 {
+        // Similar to the horizontal 1-D IDCT case, if all the AC components are zero, then the IDCT is trivial.
+        // However, after performing the horizontal 1-D IDCT, there are typically non-zero AC components, so
+        // we do not bother to check for the all-zero case.
+        let s = &mut src[x..x + 57];
+
+        // Prescale.
+        let mut y0 = ((s[0] as i64) << 8) + 8192;
+        let mut y1 = (s[8 * 4] as i64) << 8;
+        let mut y2 = s[8 * 6] as i64;
+        let mut y3 = s[8 * 2] as i64;
+        let mut y4 = s[8] as i64;
+        let mut y5 = s[8 * 7] as i64;
+        let mut y6 = s[8 * 5] as i64;
+        let mut y7 = s[8 * 3] as i64;
+
+        // Stage 1.
+        let mut y8 = W7 * (y4 + y5) + 4;
+        y4 = (y8 + W1MW7 * y4) >> 3;
+        y5 = (y8 - W1PW7 * y5) >> 3;
+        y8 = W3 * (y6 + y7) + 4;
+        y6 = (y8 - W3MW5 * y6) >> 3;
+        y7 = (y8 - W3PW5 * y7) >> 3;
+
+        // Stage 2.
+        y8 = y0 + y1;
+        y0 -= y1;
+        y1 = W6 * (y3 + y2) + 4;
+        y2 = (y1 - W2PW6 * y2) >> 3;
+        y3 = (y1 + W2MW6 * y3) >> 3;
+        y1 = y4 + y6;
+        y4 -= y6;
+        y6 = y5 + y7;
+        y5 -= y7;
+
+        // Stage 3.
+        y7 = y8 + y3;
+        y8 -= y3;
+        y3 = y0 + y2;
+        y0 -= y2;
+        y2 = (R2 * (y4 + y5) + 128) >> 8;
+        y4 = (R2 * (y4 - y5) + 128) >> 8;
+
+        // Stage 4.
+        s[0] = ((y7 + y1) >> 14) as i32;
+        s[8] = ((y3 + y2) >> 14) as i32;
+        s[8 * 2] = ((y0 + y4) >> 14) as i32;
+        s[8 * 3] = ((y8 + y6) >> 14) as i32;
+        s[8 * 4] = ((y8 - y6) >> 14) as i32;
+        s[8 * 5] = ((y0 - y4) >> 14) as i32;
+        s[8 * 6] = ((y3 - y2) >> 14) as i32;
+        s[8 * 7] = ((y7 - y1) >> 14) as i32;
+    }
+}
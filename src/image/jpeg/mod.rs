@@ -0,0 +1,25 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! Package jpeg implements a JPEG image decoder and encoder.
+//!
+//! JPEG is defined in ITU-T T.81: <https://www.w3.org/Graphics/JPEG/itu-t81.pdf>.
+
+mod fdct;
+mod huffman;
+mod idct;
+mod reader;
+mod scan;
+mod writer;
+
+pub use reader::{decode, decode_config};
+pub use writer::{encode, Options, DEFAULT_QUALITY};
+
+#[cfg(test)]
+mod dct_test;
+#[cfg(test)]
+mod reader_test;
+#[cfg(test)]
+mod writer_test;
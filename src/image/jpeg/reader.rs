@@ -0,0 +1,832 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2010 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::huffman::Huffman;
+use super::idct::{Block, BLOCK_SIZE};
+use crate::image::color::{self, Model};
+use crate::image::{self, Config, Gray, Img, YCbCr, YCbCrSubsampleRatio, RGBA};
+use std::io::Read;
+
+/// new_format_error reports that the input is not a valid JPEG.
+pub(super) fn new_format_error(msg: &str) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("invalid JPEG format: {}", msg),
+    )
+}
+
+/// new_unsupported_error reports that the input uses a valid but unimplemented JPEG feature.
+pub(super) fn new_unsupported_error(msg: &str) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("unsupported JPEG feature: {}", msg),
+    )
+}
+
+/// is_format_error reports whether err was created by new_format_error(msg).
+pub(super) fn is_format_error(err: &std::io::Error, msg: &str) -> bool {
+    err.kind() == std::io::ErrorKind::InvalidData
+        && err.to_string() == format!("invalid JPEG format: {}", msg)
+}
+
+const ERR_UNSUPPORTED_SUBSAMPLING_RATIO: &str = "luma/chroma subsampling ratio";
+
+/// Component specification, specified in section B.2.2.
+#[derive(Clone, Copy, Default)]
+pub(super) struct Component {
+    /// Horizontal sampling factor.
+    pub(super) h: usize,
+    /// Vertical sampling factor.
+    pub(super) v: usize,
+    /// Component identifier.
+    pub(super) c: u8,
+    /// Quantization table destination selector.
+    pub(super) tq: u8,
+}
+
+pub(super) const DC_TABLE: usize = 0;
+pub(super) const AC_TABLE: usize = 1;
+pub(super) const MAX_TC: usize = 1;
+pub(super) const MAX_TH: usize = 3;
+pub(super) const MAX_TQ: usize = 3;
+
+pub(super) const MAX_COMPONENTS: usize = 4;
+
+pub(super) const SOF0_MARKER: u8 = 0xc0; // Start Of Frame (Baseline Sequential).
+const SOF1_MARKER: u8 = 0xc1; // Start Of Frame (Extended Sequential).
+const SOF2_MARKER: u8 = 0xc2; // Start Of Frame (Progressive).
+pub(super) const DHT_MARKER: u8 = 0xc4; // Define Huffman Table.
+pub(super) const RST0_MARKER: u8 = 0xd0; // ReSTart (0).
+pub(super) const RST7_MARKER: u8 = 0xd7; // ReSTart (7).
+pub(super) const SOI_MARKER: u8 = 0xd8; // Start Of Image.
+pub(super) const EOI_MARKER: u8 = 0xd9; // End Of Image.
+pub(super) const SOS_MARKER: u8 = 0xda; // Start Of Scan.
+pub(super) const DQT_MARKER: u8 = 0xdb; // Define Quantization Table.
+const DRI_MARKER: u8 = 0xdd; // Define Restart Interval.
+const COM_MARKER: u8 = 0xfe; // COMment.
+                             // "APPlication specific" markers aren't part of the JPEG spec per se,
+                             // but in practice, their use is described at
+                             // https://www.sno.phy.queensu.ca/~phil/exiftool/TagNames/JPEG.html
+const APP0_MARKER: u8 = 0xe0;
+const APP14_MARKER: u8 = 0xee;
+const APP15_MARKER: u8 = 0xef;
+
+// See https://www.sno.phy.queensu.ca/~phil/exiftool/TagNames/JPEG.html#Adobe
+const ADOBE_TRANSFORM_UNKNOWN: u8 = 0;
+// const ADOBE_TRANSFORM_YCBCR: u8 = 1;
+// const ADOBE_TRANSFORM_YCBCRK: u8 = 2;
+
+/// UNZIG maps from the zig-zag ordering to the natural ordering. For example,
+/// UNZIG[3] is the column and row of the fourth element in zig-zag order. The
+/// value is 16, which means first column (16%8 == 0) and third row (16/8 == 2).
+pub(super) const UNZIG: [usize; BLOCK_SIZE] = [
+    0, 1, 8, 16, 9, 2, 3, 10, 17, 24, 32, 25, 18, 11, 4, 5, 12, 19, 26, 33, 40, 48, 41, 34, 27, 20,
+    13, 6, 7, 14, 21, 28, 35, 42, 49, 56, 57, 50, 43, 36, 29, 22, 15, 23, 30, 37, 44, 51, 58, 59,
+    52, 45, 38, 31, 39, 46, 53, 60, 61, 54, 47, 55, 62, 63,
+];
+
+/// Bits holds the unprocessed bits that have been taken from the byte-stream.
+/// The n least significant bits of a form the unread bits, to be read in MSB to
+/// LSB order.
+#[derive(Clone, Copy, Default)]
+pub(super) struct Bits {
+    /// accumulator.
+    pub(super) a: u32,
+    /// mask. m==1<<(n-1) when n>0, with m==0 when n==0.
+    pub(super) m: u32,
+    /// the number of unread bits in a.
+    pub(super) n: i32,
+}
+
+/// Bytes is a byte buffer, similar to a std::io::BufReader, except that it
+/// has to be able to unread more than 1 byte, due to byte stuffing.
+/// Byte stuffing is specified in section F.1.2.3.
+pub(super) struct Bytes {
+    /// buf[i..j] are the buffered bytes read from the underlying
+    /// reader that haven't yet been passed further on.
+    buf: [u8; 4096],
+    i: usize,
+    j: usize,
+    /// n_unreadable is the number of bytes to back up i after
+    /// overshooting. It can be 0, 1 or 2.
+    pub(super) n_unreadable: usize,
+}
+
+pub(super) struct Decoder<'a, Input: Read> {
+    r: &'a mut Input,
+    pub(super) bits: Bits,
+    pub(super) bytes: Bytes,
+    pub(super) width: usize,
+    pub(super) height: usize,
+
+    /// planes hold the decoded samples of every component. They cover
+    /// whole MCUs, so they may be larger than the image.
+    pub(super) planes: [Vec<u8>; MAX_COMPONENTS],
+    pub(super) strides: [usize; MAX_COMPONENTS],
+    pub(super) has_img: bool,
+
+    /// Restart Interval.
+    pub(super) ri: usize,
+    pub(super) n_comp: usize,
+
+    // As per section 4.5, there are four modes of operation (selected by the
+    // SOF? markers): sequential DCT, progressive DCT, lossless and
+    // hierarchical, although this implementation does not support the latter
+    // two non-DCT modes. Sequential DCT is further split into baseline and
+    // extended, as per section 4.11.
+    pub(super) baseline: bool,
+    pub(super) progressive: bool,
+
+    jfif: bool,
+    adobe_transform_valid: bool,
+    adobe_transform: u8,
+    /// End-of-Band run, specified in section G.1.2.2.
+    pub(super) eob_run: u16,
+
+    pub(super) comp: [Component; MAX_COMPONENTS],
+    /// Saved state between progressive-mode scans.
+    pub(super) prog_coeffs: [Vec<Block>; MAX_COMPONENTS],
+    pub(super) huff: [[Huffman; MAX_TH + 1]; MAX_TC + 1],
+    /// Quantization tables, in zig-zag order.
+    pub(super) quant: [Block; MAX_TQ + 1],
+}
+
+/// ERR_MISSING_FF00 means that read_byte_stuffed_byte encountered an 0xff byte (a
+/// marker byte) that wasn't the expected byte-stuffed sequence 0xff, 0x00.
+pub(super) const ERR_MISSING_FF00: &str = "missing 0xff00 sequence";
+
+impl<'a, Input: Read> Decoder<'a, Input> {
+    fn new(r: &'a mut Input) -> Self {
+        Self {
+            r,
+            bits: Bits::default(),
+            bytes: Bytes {
+                buf: [0; 4096],
+                i: 0,
+                j: 0,
+                n_unreadable: 0,
+            },
+            width: 0,
+            height: 0,
+            planes: Default::default(),
+            strides: [0; MAX_COMPONENTS],
+            has_img: false,
+            ri: 0,
+            n_comp: 0,
+            baseline: false,
+            progressive: false,
+            jfif: false,
+            adobe_transform_valid: false,
+            adobe_transform: 0,
+            eob_run: 0,
+            comp: [Component::default(); MAX_COMPONENTS],
+            prog_coeffs: Default::default(),
+            huff: [[Huffman::new(); MAX_TH + 1]; MAX_TC + 1],
+            quant: [[0; BLOCK_SIZE]; MAX_TQ + 1],
+        }
+    }
+
+    /// fill fills up the self.bytes.buf buffer from the underlying reader. It
+    /// should only be called when there are no unread bytes in self.bytes.
+    fn fill(&mut self) -> std::io::Result<()> {
+        if self.bytes.i != self.bytes.j {
+            panic!("jpeg: fill called when unread bytes exist");
+        }
+        // Move the last 2 bytes to the start of the buffer, in case we need
+        // to call unread_byte_stuffed_byte.
+        if self.bytes.j > 2 {
+            self.bytes.buf[0] = self.bytes.buf[self.bytes.j - 2];
+            self.bytes.buf[1] = self.bytes.buf[self.bytes.j - 1];
+            self.bytes.i = 2;
+            self.bytes.j = 2;
+        }
+        // Fill in the rest of the buffer.
+        loop {
+            match self.r.read(&mut self.bytes.buf[self.bytes.j..]) {
+                Ok(0) => return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof)),
+                Ok(n) => {
+                    self.bytes.j += n;
+                    return Ok(());
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// unread_byte_stuffed_byte undoes the most recent read_byte_stuffed_byte call,
+    /// giving a byte of data back from self.bits to self.bytes. The Huffman look-up table
+    /// requires at least 8 bits for look-up, which means that Huffman decoding can
+    /// sometimes overshoot and read one or two too many bytes. Two-byte overshoot
+    /// can happen when expecting to read a 0xff 0x00 byte-stuffed byte.
+    pub(super) fn unread_byte_stuffed_byte(&mut self) {
+        self.bytes.i -= self.bytes.n_unreadable;
+        self.bytes.n_unreadable = 0;
+        if self.bits.n >= 8 {
+            self.bits.a >>= 8;
+            self.bits.n -= 8;
+            self.bits.m >>= 8;
+        }
+    }
+
+    /// read_byte returns the next byte, whether buffered or not buffered. It does
+    /// not care about byte stuffing.
+    fn read_byte(&mut self) -> std::io::Result<u8> {
+        while self.bytes.i == self.bytes.j {
+            self.fill()?;
+        }
+        let x = self.bytes.buf[self.bytes.i];
+        self.bytes.i += 1;
+        self.bytes.n_unreadable = 0;
+        Ok(x)
+    }
+
+    /// read_byte_stuffed_byte is like read_byte but is for byte-stuffed Huffman data.
+    pub(super) fn read_byte_stuffed_byte(&mut self) -> std::io::Result<u8> {
+        // Take the fast path if self.bytes.buf contains at least two bytes.
+        if self.bytes.i + 2 <= self.bytes.j {
+            let x = self.bytes.buf[self.bytes.i];
+            self.bytes.i += 1;
+            self.bytes.n_unreadable = 1;
+            if x != 0xff {
+                return Ok(x);
+            }
+            if self.bytes.buf[self.bytes.i] != 0x00 {
+                return Err(new_format_error(ERR_MISSING_FF00));
+            }
+            self.bytes.i += 1;
+            self.bytes.n_unreadable = 2;
+            return Ok(0xff);
+        }
+
+        self.bytes.n_unreadable = 0;
+
+        let x = self.read_byte()?;
+        self.bytes.n_unreadable = 1;
+        if x != 0xff {
+            return Ok(x);
+        }
+
+        let x = self.read_byte()?;
+        self.bytes.n_unreadable = 2;
+        if x != 0x00 {
+            return Err(new_format_error(ERR_MISSING_FF00));
+        }
+        Ok(0xff)
+    }
+
+    /// read_full reads exactly p.len() bytes into p. It does not care about byte
+    /// stuffing.
+    pub(super) fn read_full(&mut self, mut p: &mut [u8]) -> std::io::Result<()> {
+        // Unread the overshot bytes, if any.
+        if self.bytes.n_unreadable != 0 {
+            if self.bits.n >= 8 {
+                self.unread_byte_stuffed_byte();
+            }
+            self.bytes.n_unreadable = 0;
+        }
+
+        loop {
+            let n = p.len().min(self.bytes.j - self.bytes.i);
+            p[..n].copy_from_slice(&self.bytes.buf[self.bytes.i..self.bytes.i + n]);
+            p = &mut p[n..];
+            self.bytes.i += n;
+            if p.is_empty() {
+                break;
+            }
+            self.fill()?;
+        }
+        Ok(())
+    }
+
+    /// ignore ignores the next n bytes.
+    fn ignore(&mut self, mut n: usize) -> std::io::Result<()> {
+        // Unread the overshot bytes, if any.
+        if self.bytes.n_unreadable != 0 {
+            if self.bits.n >= 8 {
+                self.unread_byte_stuffed_byte();
+            }
+            self.bytes.n_unreadable = 0;
+        }
+
+        loop {
+            let m = n.min(self.bytes.j - self.bytes.i);
+            self.bytes.i += m;
+            n -= m;
+            if n == 0 {
+                break;
+            }
+            self.fill()?;
+        }
+        Ok(())
+    }
+
+    /// process_sof processes a Start Of Frame marker. Specified in section B.2.2.
+    fn process_sof(&mut self, n: usize) -> std::io::Result<()> {
+        if self.n_comp != 0 {
+            return Err(new_format_error("multiple SOF markers"));
+        }
+        self.n_comp = match n {
+            // Grayscale image.
+            9 => 1,
+            // YCbCr or RGB image.
+            15 => 3,
+            // YCbCrK or CMYK image.
+            18 => 4,
+            _ => return Err(new_unsupported_error("number of components")),
+        };
+        let mut tmp = [0; 6 + 3 * MAX_COMPONENTS];
+        self.read_full(&mut tmp[..n])?;
+        // We only support 8-bit precision.
+        if tmp[0] != 8 {
+            return Err(new_unsupported_error("precision"));
+        }
+        self.height = (tmp[1] as usize) << 8 | tmp[2] as usize;
+        self.width = (tmp[3] as usize) << 8 | tmp[4] as usize;
+        if tmp[5] as usize != self.n_comp {
+            return Err(new_format_error("SOF has wrong length"));
+        }
+
+        for i in 0..self.n_comp {
+            self.comp[i].c = tmp[6 + 3 * i];
+            // Section B.2.2 states that "the value of C_i shall be different from
+            // the values of C_1 through C_(i-1)".
+            for j in 0..i {
+                if self.comp[i].c == self.comp[j].c {
+                    return Err(new_format_error("repeated component identifier"));
+                }
+            }
+
+            self.comp[i].tq = tmp[8 + 3 * i];
+            if self.comp[i].tq as usize > MAX_TQ {
+                return Err(new_format_error("bad Tq value"));
+            }
+
+            let hv = tmp[7 + 3 * i];
+            let mut h = (hv >> 4) as usize;
+            let mut v = (hv & 0x0f) as usize;
+            if !(1..=4).contains(&h) || !(1..=4).contains(&v) {
+                return Err(new_format_error("luma/chroma subsampling ratio"));
+            }
+            if h == 3 || v == 3 {
+                return Err(new_unsupported_error(ERR_UNSUPPORTED_SUBSAMPLING_RATIO));
+            }
+            match self.n_comp {
+                1 => {
+                    // If a JPEG image has only one component, section A.2 says "this data
+                    // is non-interleaved by definition" and section A.2.2 says "[in this
+                    // case...] the order of data units within a scan shall be left-to-right
+                    // and top-to-bottom... regardless of the values of H_1 and V_1". Section
+                    // 4.8.2 also says "[for non-interleaved data], the MCU is defined to be
+                    // one data unit". Similarly, section A.1.1 explains that it is the ratio
+                    // of H_i to max_j(H_j) that matters, and similarly for V. For grayscale
+                    // images, H_1 is the maximum H_j for all components j, so that ratio is
+                    // always 1. The component's (h, v) is effectively always (1, 1): even if
+                    // the nominal (h, v) is (2, 1), a 20x5 image is encoded in three 8x8
+                    // MCUs, not two 16x8 MCUs.
+                    h = 1;
+                    v = 1;
+                }
+                3 => {
+                    // For YCbCr images, we only support 4:4:4, 4:4:0, 4:2:2, 4:2:0,
+                    // 4:1:1 or 4:1:0 chroma subsampling ratios. This implies that the
+                    // (h, v) values for the Y component are either (1, 1), (1, 2),
+                    // (2, 1), (2, 2), (4, 1) or (4, 2), and the Y component's values
+                    // must be a multiple of the Cb and Cr component's values. We also
+                    // assume that the two chroma components have the same subsampling
+                    // ratio.
+                    let ok = match i {
+                        // Y.
+                        // We have already verified, above, that h and v are both
+                        // either 1, 2 or 4, so invalid (h, v) combinations are those
+                        // with v == 4.
+                        0 => v != 4,
+                        // Cb.
+                        1 => {
+                            let (h_rem, v_rem) = (self.comp[0].h % h, self.comp[0].v % v);
+                            h_rem == 0 && v_rem == 0
+                        }
+                        // Cr.
+                        _ => self.comp[1].h == h && self.comp[1].v == v,
+                    };
+                    if !ok {
+                        return Err(new_unsupported_error(ERR_UNSUPPORTED_SUBSAMPLING_RATIO));
+                    }
+                }
+                _ => {
+                    // For 4-component images (either CMYK or YCbCrK), we only support two
+                    // hv vectors: [0x11 0x11 0x11 0x11] and [0x22 0x11 0x11 0x22].
+                    // Theoretically, 4-component JPEG images could mix and match hv values
+                    // but in practice, those two combinations are the only ones in use,
+                    // and it simplifies the apply_black code below if we can assume that:
+                    //	- for CMYK, the C and K channels have full samples, and if the M
+                    //	  and Y channels subsample, they subsample both horizontally and
+                    //	  vertically.
+                    //	- for YCbCrK, the Y and K channels have full samples.
+                    let ok = match i {
+                        0 => hv == 0x11 || hv == 0x22,
+                        1 | 2 => hv == 0x11,
+                        _ => self.comp[0].h == h && self.comp[0].v == v,
+                    };
+                    if !ok {
+                        return Err(new_unsupported_error(ERR_UNSUPPORTED_SUBSAMPLING_RATIO));
+                    }
+                }
+            }
+
+            self.comp[i].h = h;
+            self.comp[i].v = v;
+        }
+        Ok(())
+    }
+
+    /// process_dqt processes a Define Quantization Table marker.
+    /// Specified in section B.2.4.1.
+    fn process_dqt(&mut self, mut n: usize) -> std::io::Result<()> {
+        while n > 0 {
+            n -= 1;
+            let x = self.read_byte()?;
+            let tq = (x & 0x0f) as usize;
+            if tq > MAX_TQ {
+                return Err(new_format_error("bad Tq value"));
+            }
+            match x >> 4 {
+                0 => {
+                    if n < BLOCK_SIZE {
+                        break;
+                    }
+                    n -= BLOCK_SIZE;
+                    let mut tmp = [0; BLOCK_SIZE];
+                    self.read_full(&mut tmp)?;
+                    for (q, t) in self.quant[tq].iter_mut().zip(tmp.iter()) {
+                        *q = *t as i32;
+                    }
+                }
+                1 => {
+                    if n < 2 * BLOCK_SIZE {
+                        break;
+                    }
+                    n -= 2 * BLOCK_SIZE;
+                    let mut tmp = [0; 2 * BLOCK_SIZE];
+                    self.read_full(&mut tmp)?;
+                    for (q, t) in self.quant[tq].iter_mut().zip(tmp.chunks(2)) {
+                        *q = (t[0] as i32) << 8 | t[1] as i32;
+                    }
+                }
+                _ => return Err(new_format_error("bad Pq value")),
+            }
+        }
+        if n != 0 {
+            return Err(new_format_error("DQT has wrong length"));
+        }
+        Ok(())
+    }
+
+    /// process_dri processes a Define Restart Interval marker.
+    /// Specified in section B.2.4.4.
+    fn process_dri(&mut self, n: usize) -> std::io::Result<()> {
+        if n != 2 {
+            return Err(new_format_error("DRI has wrong length"));
+        }
+        let mut tmp = [0; 2];
+        self.read_full(&mut tmp)?;
+        self.ri = (tmp[0] as usize) << 8 | tmp[1] as usize;
+        Ok(())
+    }
+
+    fn process_app0_marker(&mut self, mut n: usize) -> std::io::Result<()> {
+        if n < 5 {
+            return self.ignore(n);
+        }
+        let mut tmp = [0; 5];
+        self.read_full(&mut tmp)?;
+        n -= 5;
+
+        self.jfif = &tmp == b"JFIF\x00";
+
+        if n > 0 {
+            return self.ignore(n);
+        }
+        Ok(())
+    }
+
+    fn process_app14_marker(&mut self, mut n: usize) -> std::io::Result<()> {
+        if n < 12 {
+            return self.ignore(n);
+        }
+        let mut tmp = [0; 12];
+        self.read_full(&mut tmp)?;
+        n -= 12;
+
+        if &tmp[..5] == b"Adobe" {
+            self.adobe_transform_valid = true;
+            self.adobe_transform = tmp[11];
+        }
+
+        if n > 0 {
+            return self.ignore(n);
+        }
+        Ok(())
+    }
+
+    /// decode reads a JPEG image from self.r and returns it as an Img.
+    /// If config_only is true, it stops as soon as the image configuration
+    /// is known and returns None.
+    fn decode(&mut self, config_only: bool) -> std::io::Result<Option<Box<Img>>> {
+        // Check for the Start Of Image marker.
+        let mut tmp = [0; 2];
+        self.read_full(&mut tmp)?;
+        if tmp[0] != 0xff || tmp[1] != SOI_MARKER {
+            return Err(new_format_error("missing SOI marker"));
+        }
+
+        // Process the remaining segments until the End Of Image marker.
+        loop {
+            self.read_full(&mut tmp)?;
+            while tmp[0] != 0xff {
+                // Strictly speaking, this is a format error. However, libjpeg is
+                // liberal in what it accepts. As of version 9, next_marker in
+                // jdmarker.c treats this as a warning (JWRN_EXTRANEOUS_DATA) and
+                // continues to decode the stream. Even before next_marker sees
+                // extraneous data, jpeg_fill_bit_buffer in jdhuff.c reads as many
+                // bytes as it can, possibly past the end of a scan's data. It
+                // effectively puts back any markers that it overscanned (e.g. an
+                // "\xff\xd9" EOI marker), but it does not put back non-marker data,
+                // and thus it can silently ignore a small number of extraneous
+                // non-marker bytes before next_marker has a chance to see them (and
+                // print a warning).
+                //
+                // We are therefore also liberal in what we accept. Extraneous data
+                // is silently ignored.
+                //
+                // This is similar to, but not exactly the same as, the restart
+                // mechanism within a scan (the RST[0-7] markers).
+                //
+                // Note that extraneous 0xff bytes in e.g. SOS data are escaped as
+                // "\xff\x00", and so are detected a little further down below.
+                tmp[0] = tmp[1];
+                tmp[1] = self.read_byte()?;
+            }
+            let mut marker = tmp[1];
+            if marker == 0 {
+                // Treat "\xff\x00" as extraneous data.
+                continue;
+            }
+            while marker == 0xff {
+                // Section B.1.1.2 says, "Any marker may optionally be preceded by any
+                // number of fill bytes, which are bytes assigned code X'FF'".
+                marker = self.read_byte()?;
+            }
+            if marker == EOI_MARKER {
+                // End Of Image.
+                break;
+            }
+            if (RST0_MARKER..=RST7_MARKER).contains(&marker) {
+                // Figures B.2 and B.16 of the specification suggest that restart markers should
+                // only occur between Entropy Coded Segments and not after the final ECS.
+                // However, some encoders may generate incorrect JPEGs with a final restart
+                // marker. That restart marker will be seen here instead of inside the process_sos
+                // method, and is ignored as a harmless error. Restart markers have no extra data,
+                // so we check for this before we read the 16-bit length of the segment.
+                continue;
+            }
+
+            // Read the 16-bit length of the segment. The value includes the 2 bytes for the
+            // length itself, so we subtract 2 to get the number of remaining bytes.
+            self.read_full(&mut tmp)?;
+            let n = ((tmp[0] as usize) << 8 | tmp[1] as usize)
+                .checked_sub(2)
+                .ok_or_else(|| new_format_error("short segment length"))?;
+
+            match marker {
+                SOF0_MARKER | SOF1_MARKER | SOF2_MARKER => {
+                    self.baseline = marker == SOF0_MARKER;
+                    self.progressive = marker == SOF2_MARKER;
+                    self.process_sof(n)?;
+                    if config_only && self.jfif {
+                        return Ok(None);
+                    }
+                }
+                DHT_MARKER => {
+                    if config_only {
+                        self.ignore(n)?;
+                    } else {
+                        self.process_dht(n)?;
+                    }
+                }
+                DQT_MARKER => {
+                    if config_only {
+                        self.ignore(n)?;
+                    } else {
+                        self.process_dqt(n)?;
+                    }
+                }
+                SOS_MARKER => {
+                    if config_only {
+                        return Ok(None);
+                    }
+                    self.process_sos(n)?;
+                }
+                DRI_MARKER => {
+                    if config_only {
+                        self.ignore(n)?;
+                    } else {
+                        self.process_dri(n)?;
+                    }
+                }
+                APP0_MARKER => self.process_app0_marker(n)?,
+                APP14_MARKER => self.process_app14_marker(n)?,
+                _ => {
+                    if (APP0_MARKER..=APP15_MARKER).contains(&marker) || marker == COM_MARKER {
+                        self.ignore(n)?;
+                    } else if marker < 0xc0 {
+                        // See Table B.1 "Marker code assignments".
+                        return Err(new_format_error("unknown marker"));
+                    } else {
+                        return Err(new_unsupported_error("unknown marker"));
+                    }
+                }
+            }
+        }
+
+        if config_only {
+            return Ok(None);
+        }
+        if self.progressive {
+            self.reconstruct_progressive_image()?;
+        }
+        if !self.has_img {
+            return Err(new_format_error("missing SOS marker"));
+        }
+        let m = if self.n_comp == 1 {
+            Img::Gray(self.gray())
+        } else if self.n_comp == 4 {
+            Img::RGBA(self.apply_black()?)
+        } else if self.is_rgb() {
+            Img::RGBA(self.convert_to_rgb())
+        } else {
+            Img::YCbCr(self.ycbcr())
+        };
+        Ok(Some(Box::new(m)))
+    }
+
+    /// bounds returns the bounds of the decoded image.
+    fn bounds(&self) -> image::Rectangle {
+        image::rect(0, 0, self.width as isize, self.height as isize)
+    }
+
+    /// sample returns the sample of component t for the pixel at (x, y),
+    /// taking the component's subsampling into account.
+    fn sample(&self, t: usize, x: usize, y: usize) -> u8 {
+        let sy = y * self.comp[t].v / self.comp[0].v;
+        let sx = x * self.comp[t].h / self.comp[0].h;
+        self.planes[t][sy * self.strides[t] + sx]
+    }
+
+    /// gray crops the decoded samples of a 1-component image to the image bounds.
+    fn gray(&self) -> Gray {
+        let mut img = Gray::new(&self.bounds());
+        copy_plane(&mut img.pix, img.stride, &self.planes[0], self.strides[0]);
+        img
+    }
+
+    /// ycbcr crops the decoded samples of a 3-component image to the image bounds.
+    fn ycbcr(&self) -> YCbCr {
+        let h_ratio = self.comp[0].h / self.comp[1].h;
+        let v_ratio = self.comp[0].v / self.comp[1].v;
+        let subsample_ratio = match (h_ratio, v_ratio) {
+            (1, 1) => YCbCrSubsampleRatio::Ratio444,
+            (1, 2) => YCbCrSubsampleRatio::Ratio440,
+            (2, 1) => YCbCrSubsampleRatio::Ratio422,
+            (2, 2) => YCbCrSubsampleRatio::Ratio420,
+            (4, 1) => YCbCrSubsampleRatio::Ratio411,
+            (4, 2) => YCbCrSubsampleRatio::Ratio410,
+            _ => unreachable!(),
+        };
+        let mut img = YCbCr::new(&self.bounds(), subsample_ratio);
+        let (y_stride, c_stride) = (img.y_stride, img.c_stride);
+        let (y, cb, cr) = img.planes_mut();
+        copy_plane(y, y_stride, &self.planes[0], self.strides[0]);
+        copy_plane(cb, c_stride, &self.planes[1], self.strides[1]);
+        copy_plane(cr, c_stride, &self.planes[2], self.strides[2]);
+        img
+    }
+
+    /// apply_black combines the 4 decoded components into an RGBA image. The formula
+    /// used depends on whether the JPEG image is stored as CMYK or YCbCrK,
+    /// indicated by the APP14 (Adobe) metadata.
+    ///
+    /// Adobe CMYK JPEG images are inverted, where 255 means no ink instead of full
+    /// ink, so we apply "v = 255 - v" at various points. Note that a double
+    /// inversion is a no-op, so inversions might be implicit in the code below.
+    fn apply_black(&self) -> std::io::Result<RGBA> {
+        if !self.adobe_transform_valid {
+            return Err(new_unsupported_error(
+                "unknown color model: 4-component JPEG doesn't have Adobe APP14 metadata",
+            ));
+        }
+
+        let mut img = RGBA::new(&self.bounds());
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let (c, m, yy) = if self.adobe_transform != ADOBE_TRANSFORM_UNKNOWN {
+                    // If the 4-component JPEG image isn't explicitly marked as "Unknown (RGB
+                    // or CMYK)" as per
+                    // https://www.sno.phy.queensu.ca/~phil/exiftool/TagNames/JPEG.html#Adobe
+                    // we assume that it is YCbCrK. This matches libjpeg's jdapimin.c.
+                    //
+                    // Convert the YCbCr part of the YCbCrK to RGB, invert the RGB to get
+                    // CMY, and patch in the original K. The RGB to CMY inversion cancels
+                    // out the 'Adobe inversion' described in the apply_black doc comment
+                    // above, so in practice, only the fourth channel (black) is inverted.
+                    color::ycbcr_to_rgb(
+                        self.sample(0, x, y),
+                        self.sample(1, x, y),
+                        self.sample(2, x, y),
+                    )
+                } else {
+                    (
+                        255 - self.sample(0, x, y),
+                        255 - self.sample(1, x, y),
+                        255 - self.sample(2, x, y),
+                    )
+                };
+                let k = 255 - self.sample(3, x, y);
+                let (r, g, b) = color::cmyk_to_rgb(c, m, yy, k);
+                let i = img.pix_offset(x as isize, y as isize);
+                img.pix[i..i + 4].copy_from_slice(&[r, g, b, 255]);
+            }
+        }
+        Ok(img)
+    }
+
+    fn is_rgb(&self) -> bool {
+        if self.jfif {
+            return false;
+        }
+        if self.adobe_transform_valid && self.adobe_transform == ADOBE_TRANSFORM_UNKNOWN {
+            // https://www.sno.phy.queensu.ca/~phil/exiftool/TagNames/JPEG.html#Adobe
+            // says that 0 means Unknown (and in practice RGB) and 1 means YCbCr.
+            return true;
+        }
+        self.comp[0].c == b'R' && self.comp[1].c == b'G' && self.comp[2].c == b'B'
+    }
+
+    fn convert_to_rgb(&self) -> RGBA {
+        let mut img = RGBA::new(&self.bounds());
+        for y in 0..self.height {
+            let po = img.pix_offset(0, y as isize);
+            for x in 0..self.width {
+                img.pix[po + 4 * x] = self.sample(0, x, y);
+                img.pix[po + 4 * x + 1] = self.sample(1, x, y);
+                img.pix[po + 4 * x + 2] = self.sample(2, x, y);
+                img.pix[po + 4 * x + 3] = 255;
+            }
+        }
+        img
+    }
+}
+
+/// copy_plane copies the top-left part of src into dst, row by row.
+fn copy_plane(dst: &mut [u8], dst_stride: usize, src: &[u8], src_stride: usize) {
+    if dst_stride == 0 {
+        return;
+    }
+    for (y, row) in dst.chunks_mut(dst_stride).enumerate() {
+        row.copy_from_slice(&src[y * src_stride..y * src_stride + row.len()]);
+    }
+}
+
+/// decode reads a JPEG image from r and returns it as an Img.
+/// Grayscale images are returned as Img::Gray, YCbCr images as Img::YCbCr,
+/// and RGB, CMYK and YCbCrK images are converted to Img::RGBA.
+pub fn decode<Input: std::io::BufRead>(r: &mut Input) -> std::io::Result<Box<Img>> {
+    let mut d = Decoder::new(r);
+    Ok(d.decode(false)?.unwrap())
+}
+
+/// decode_config returns the color model and dimensions of a JPEG image without
+/// decoding the entire image.
+pub fn decode_config<Input: std::io::BufRead>(r: &mut Input) -> std::io::Result<Config> {
+    let mut d = Decoder::new(r);
+    d.decode(true)?;
+    let color_model = match d.n_comp {
+        1 => Model::GrayModel,
+        3 => {
+            if d.is_rgb() {
+                Model::RGBAModel
+            } else {
+                Model::YCbCrModel
+            }
+        }
+        4 => Model::RGBAModel,
+        _ => return Err(new_format_error("missing SOF marker")),
+    };
+    Ok(Config {
+        color_model,
+        width: d.width,
+        height: d.height,
+    })
+}
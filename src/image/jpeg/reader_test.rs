@@ -0,0 +1,233 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2012 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::{decode, decode_config};
+use crate::image::color::Model;
+use crate::image::{rect, Image, Img};
+use crate::math::rand;
+
+fn decode_file(filename: &str) -> std::io::Result<Box<Img>> {
+    let f = std::fs::File::open(filename)?;
+    decode(&mut std::io::BufReader::new(f))
+}
+
+/// test_decode_progressive tests that decoding the baseline and progressive
+/// versions of the same image result in exactly the same pixel data, in YCbCr
+/// space for color images, and Y space for grayscale images.
+#[test]
+fn test_decode_progressive() {
+    let test_cases = [
+        "src/image/testdata/video-001",
+        "src/image/testdata/video-001.q50.410",
+        "src/image/testdata/video-001.q50.411",
+        "src/image/testdata/video-001.q50.420",
+        "src/image/testdata/video-001.q50.422",
+        "src/image/testdata/video-001.q50.440",
+        "src/image/testdata/video-001.q50.444",
+        "src/image/testdata/video-005.gray.q50",
+        "src/image/testdata/video-005.gray.q50.2x2",
+        "src/image/testdata/video-001.separate.dc.progression",
+    ];
+    for tc in test_cases {
+        let m0 = decode_file(&format!("{}.jpeg", tc)).unwrap();
+        let m1 = decode_file(&format!("{}.progressive.jpeg", tc)).unwrap();
+        assert!(
+            m0.bounds() == m1.bounds(),
+            "{}: bounds differ: {:?} and {:?}",
+            tc,
+            m0.bounds(),
+            m1.bounds()
+        );
+        // All of the video-*.jpeg files are 150x103.
+        assert!(
+            *m0.bounds() == rect(0, 0, 150, 103),
+            "{}: bad bounds: {:?}",
+            tc,
+            m0.bounds()
+        );
+
+        match (m0.as_ref(), m1.as_ref()) {
+            (Img::YCbCr(m0), Img::YCbCr(m1)) => {
+                assert_eq!(m0.subsample_ratio, m1.subsample_ratio, "{}", tc);
+                assert!(m0.y() == m1.y(), "{} (Y): pixel data differs", tc);
+                assert!(m0.cb() == m1.cb(), "{} (Cb): pixel data differs", tc);
+                assert!(m0.cr() == m1.cr(), "{} (Cr): pixel data differs", tc);
+            }
+            (Img::Gray(m0), Img::Gray(m1)) => {
+                assert!(m0.pix == m1.pix, "{} (Y): pixel data differs", tc);
+            }
+            _ => panic!("{}: unexpected image types", tc),
+        }
+    }
+}
+
+#[test]
+fn test_decode_subsample_ratios() {
+    use crate::image::YCbCrSubsampleRatio;
+    let test_cases = [
+        ("video-001.q50.410", YCbCrSubsampleRatio::Ratio410),
+        ("video-001.q50.411", YCbCrSubsampleRatio::Ratio411),
+        ("video-001.q50.420", YCbCrSubsampleRatio::Ratio420),
+        ("video-001.q50.422", YCbCrSubsampleRatio::Ratio422),
+        ("video-001.q50.440", YCbCrSubsampleRatio::Ratio440),
+        ("video-001.q50.444", YCbCrSubsampleRatio::Ratio444),
+    ];
+    for (name, want) in test_cases {
+        let m = decode_file(&format!("src/image/testdata/{}.jpeg", name)).unwrap();
+        match m.as_ref() {
+            Img::YCbCr(m) => assert_eq!(m.subsample_ratio, want, "{}", name),
+            _ => panic!("{}: not a YCbCr image", name),
+        }
+    }
+}
+
+#[test]
+fn test_decode_config() {
+    let test_cases = [
+        ("video-001.jpeg", Model::YCbCrModel),
+        ("video-001.progressive.jpeg", Model::YCbCrModel),
+        ("video-001.cmyk.jpeg", Model::RGBAModel),
+        ("video-001.rgb.jpeg", Model::RGBAModel),
+        ("video-005.gray.jpeg", Model::GrayModel),
+    ];
+    for (name, want) in test_cases {
+        let filename = format!("src/image/testdata/{}", name);
+        let f = std::fs::File::open(&filename).unwrap();
+        let c = decode_config(&mut std::io::BufReader::new(f)).unwrap();
+        assert!(c.color_model == want, "{}: wrong color model", name);
+        let m = decode_file(&filename).unwrap();
+        assert!(m.color_model() == want, "{}: wrong image color model", name);
+        assert_eq!(c.width, m.bounds().dx(), "{}", name);
+        assert_eq!(c.height, m.bounds().dy(), "{}", name);
+    }
+}
+
+/// test_decode_eof tests that if reading a JPEG stops mid-way, we get an error.
+#[test]
+fn test_decode_eof() {
+    let data = std::fs::read("src/image/testdata/video-001.jpeg").unwrap();
+    let n = data.len();
+    let mut i = 0;
+    while i < n {
+        assert!(
+            decode(&mut &data[..i]).is_err(),
+            "i={}: expected error, got none",
+            i
+        );
+        // We don't need to check every truncation point.
+        i += if i < 1024 { 1 } else { 61 };
+    }
+    assert!(decode(&mut &data[..]).is_ok());
+}
+
+/// test_extraneous_data tests that some random junk between the last scan
+/// and the EOI marker does not affect the decoding.
+#[test]
+fn test_extraneous_data() {
+    let enc = std::fs::read("src/image/testdata/video-001.jpeg").unwrap();
+    assert!(enc.ends_with(b"\xff\xd9"));
+    let want = decode(&mut &enc[..]).unwrap();
+
+    let mut rnd = rand::Rand::new(rand::new_source(1));
+    for i in 0..100 {
+        // Write all but the trailing "\xff\xd9" EOI marker.
+        let mut buf = enc[..enc.len() - 2].to_vec();
+        // Write some random extraneous data.
+        for _ in 0..rnd.intn(10) {
+            let x = rnd.intn(256) as u8;
+            if x != 0xff {
+                buf.push(x);
+            } else {
+                // The JPEG format escapes a SOS 0xff data byte as "\xff\x00".
+                buf.extend_from_slice(b"\xff\x00");
+            }
+        }
+        // Write the "\xff\xd9" EOI marker.
+        buf.extend_from_slice(b"\xff\xd9");
+
+        // Check that we can still decode the resultant image.
+        let got = decode(&mut &buf[..]).unwrap();
+        assert!(got.pix() == want.pix(), "i={}: pixel data differs", i);
+    }
+}
+
+/// restart_image returns a 16x8 grayscale JPEG made of two blocks, with a
+/// restart interval of one MCU. Both blocks encode a DC difference of 8,
+/// which makes every pixel 129 when the DC predictor is reset at the
+/// restart marker. The given bytes are inserted between the blocks.
+fn restart_image(between: &[u8]) -> Vec<u8> {
+    let mut b = Vec::new();
+    // SOI.
+    b.extend_from_slice(b"\xff\xd8");
+    // DQT: all quantization factors are 1.
+    b.extend_from_slice(b"\xff\xdb\x00\x43\x00");
+    b.extend_from_slice(&[1; 64]);
+    // SOF0: 8-bit, 8 rows, 16 columns, 1 component.
+    b.extend_from_slice(b"\xff\xc0\x00\x0b\x08\x00\x08\x00\x10\x01\x01\x11\x00");
+    // DHT: DC table with the codes 00 (category 0) and 01 (category 4).
+    b.extend_from_slice(b"\xff\xc4\x00\x15\x00\x00\x02");
+    b.extend_from_slice(&[0; 14]);
+    b.extend_from_slice(b"\x00\x04");
+    // DHT: AC table with the single code 0 (EOB).
+    b.extend_from_slice(b"\xff\xc4\x00\x14\x10\x01");
+    b.extend_from_slice(&[0; 15]);
+    b.extend_from_slice(b"\x00");
+    // DRI: restart every MCU.
+    b.extend_from_slice(b"\xff\xdd\x00\x04\x00\x01");
+    // SOS.
+    b.extend_from_slice(b"\xff\xda\x00\x08\x01\x01\x00\x00\x3f\x00");
+    // The DC code 01, the difference 1000 and the AC code 0, padded with 1s.
+    b.push(0x61);
+    b.extend_from_slice(between);
+    b.push(0x61);
+    // EOI.
+    b.extend_from_slice(b"\xff\xd9");
+    b
+}
+
+#[test]
+fn test_restart_markers() {
+    for between in [
+        b"\xff\xd0".as_slice(),
+        // Junk before the restart marker is skipped.
+        b"\x12\x34\xff\x00\xff\xff\xd0",
+    ] {
+        let m = decode(&mut &restart_image(between)[..]).unwrap();
+        let m = match m.as_ref() {
+            Img::Gray(m) => m,
+            _ => panic!("not a Gray image"),
+        };
+        assert!(*m.bounds() == rect(0, 0, 16, 8));
+        assert!(
+            m.pix.iter().all(|&p| p == 129),
+            "between={:?}: got {:?}",
+            between,
+            m.pix
+        );
+    }
+
+    // Without the restart marker, the second block is decoded relative to the first.
+    let m = decode(&mut &restart_image(b"")[..]);
+    assert!(m.is_err());
+
+    // Unexpected restart markers are an error.
+    let err = decode(&mut &restart_image(b"\xff\xd1")[..]).err().unwrap();
+    assert_eq!(err.to_string(), "invalid JPEG format: bad RST marker");
+}
+
+#[test]
+fn test_decode_errors() {
+    let err = decode(&mut &b"\x89PNG\r\n\x1a\n"[..]).err().unwrap();
+    assert_eq!(err.to_string(), "invalid JPEG format: missing SOI marker");
+    let err = decode(&mut &b"\xff\xd8\xff\xd9"[..]).err().unwrap();
+    assert_eq!(err.to_string(), "invalid JPEG format: missing SOS marker");
+    let err = decode_config(&mut &b"\xff\xd8\xff\xd9"[..]).err().unwrap();
+    assert_eq!(err.to_string(), "invalid JPEG format: missing SOF marker");
+    // 12-bit precision.
+    let err = decode(&mut &b"\xff\xd8\xff\xc1\x00\x0b\x0c\x00\x08\x00\x08\x01\x01\x11\x00"[..])
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "unsupported JPEG feature: precision");
+}
@@ -0,0 +1,503 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2012 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::idct::{idct, Block, BLOCK_SIZE};
+use super::reader::{
+    new_format_error, new_unsupported_error, Bits, Decoder, AC_TABLE, DC_TABLE, MAX_COMPONENTS,
+    MAX_TH, RST0_MARKER, RST7_MARKER, UNZIG,
+};
+use std::io::Read;
+
+/// mcu_count returns the number of MCUs of the given size needed to cover
+/// n pixels.
+fn mcu_count(n: usize, mcu_size: usize) -> usize {
+    let rem = n % mcu_size;
+    n / mcu_size + usize::from(rem > 0)
+}
+
+/// ScanComponent is a component selected by a Start Of Scan marker.
+#[derive(Clone, Copy, Default)]
+struct ScanComponent {
+    comp_index: usize,
+    /// DC table selector.
+    td: usize,
+    /// AC table selector.
+    ta: usize,
+}
+
+impl<Input: Read> Decoder<'_, Input> {
+    /// make_img allocates and initializes the destination planes.
+    fn make_img(&mut self, mxx: usize, myy: usize) {
+        for i in 0..self.n_comp {
+            let (h, v) = (self.comp[i].h, self.comp[i].v);
+            self.strides[i] = 8 * h * mxx;
+            self.planes[i] = vec![0; 8 * h * mxx * 8 * v * myy];
+        }
+        self.has_img = true;
+    }
+
+    /// process_sos processes a Start Of Scan marker. Specified in section B.2.3.
+    pub(super) fn process_sos(&mut self, n: usize) -> std::io::Result<()> {
+        if self.n_comp == 0 {
+            return Err(new_format_error("missing SOF marker"));
+        }
+        if n < 6 || 4 + 2 * self.n_comp < n || n & 1 != 0 {
+            return Err(new_format_error("SOS has wrong length"));
+        }
+        let mut tmp = [0; 4 + 2 * MAX_COMPONENTS];
+        self.read_full(&mut tmp[..n])?;
+        let n_comp = tmp[0] as usize;
+        if n != 4 + 2 * n_comp {
+            return Err(new_format_error(
+                "SOS length inconsistent with number of components",
+            ));
+        }
+        let mut scan = [ScanComponent::default(); MAX_COMPONENTS];
+        let mut total_hv = 0;
+        for i in 0..n_comp {
+            // Component selector.
+            let cs = tmp[1 + 2 * i];
+            let comp_index = match self.comp[..self.n_comp].iter().rposition(|c| c.c == cs) {
+                Some(comp_index) => comp_index,
+                None => return Err(new_format_error("unknown component selector")),
+            };
+            scan[i].comp_index = comp_index;
+            // Section B.2.3 states that "the value of Cs_j shall be different from
+            // the values of Cs_1 through Cs_(j-1)". Since we have previously
+            // verified that a frame's component identifiers (C_i values in section
+            // B.2.2) are unique, it suffices to check that the implicit indexes
+            // into self.comp are unique.
+            for j in 0..i {
+                if scan[i].comp_index == scan[j].comp_index {
+                    return Err(new_format_error("repeated component selector"));
+                }
+            }
+            total_hv += self.comp[comp_index].h * self.comp[comp_index].v;
+
+            // The baseline t <= 1 restriction is specified in table B.3.
+            scan[i].td = (tmp[2 + 2 * i] >> 4) as usize;
+            let t = scan[i].td;
+            if t > MAX_TH || (self.baseline && t > 1) {
+                return Err(new_format_error("bad Td value"));
+            }
+            scan[i].ta = (tmp[2 + 2 * i] & 0x0f) as usize;
+            let t = scan[i].ta;
+            if t > MAX_TH || (self.baseline && t > 1) {
+                return Err(new_format_error("bad Ta value"));
+            }
+        }
+        // Section B.2.3 states that if there is more than one component then the
+        // total H*V values in a scan must be <= 10.
+        if self.n_comp > 1 && total_hv > 10 {
+            return Err(new_format_error("total sampling factors too large"));
+        }
+
+        // zig_start and zig_end are the spectral selection bounds.
+        // ah and al are the successive approximation high and low values.
+        // The spec calls these values Ss, Se, Ah and Al.
+        //
+        // For progressive JPEGs, these are the two more-or-less independent
+        // aspects of progression. Spectral selection progression is when not
+        // all of a block's 64 DCT coefficients are transmitted in one pass.
+        // For example, three passes could transmit coefficient 0 (the DC
+        // component), coefficients 1-5, and coefficients 6-63, in zig-zag
+        // order. Successive approximation is when not all of the bits of a
+        // band of coefficients are transmitted in one pass. For example,
+        // three passes could transmit the 6 most significant bits, followed
+        // by the second-least significant bit, followed by the least
+        // significant bit.
+        //
+        // For sequential JPEGs, these parameters are hard-coded to 0/63/0/0, as
+        // per table B.3.
+        let (mut zig_start, mut zig_end, mut ah, mut al) = (0, BLOCK_SIZE as i32 - 1, 0, 0);
+        if self.progressive {
+            zig_start = tmp[1 + 2 * n_comp] as i32;
+            zig_end = tmp[2 + 2 * n_comp] as i32;
+            ah = (tmp[3 + 2 * n_comp] >> 4) as u32;
+            al = (tmp[3 + 2 * n_comp] & 0x0f) as u32;
+            if (zig_start == 0 && zig_end != 0)
+                || zig_start > zig_end
+                || BLOCK_SIZE as i32 <= zig_end
+            {
+                return Err(new_format_error("bad spectral selection bounds"));
+            }
+            if zig_start != 0 && n_comp != 1 {
+                return Err(new_format_error(
+                    "progressive AC coefficients for more than one component",
+                ));
+            }
+            if ah != 0 && ah != al + 1 {
+                return Err(new_format_error("bad successive approximation values"));
+            }
+        }
+
+        // mxx and myy are the number of MCUs (Minimum Coded Units) in the image.
+        // The h and v values from the Y components.
+        let (h0, v0) = (self.comp[0].h, self.comp[0].v);
+        let mxx = mcu_count(self.width, 8 * h0);
+        let myy = mcu_count(self.height, 8 * v0);
+        if !self.has_img {
+            self.make_img(mxx, myy);
+        }
+        if self.progressive {
+            for s in &scan[..n_comp] {
+                let comp_index = s.comp_index;
+                if self.prog_coeffs[comp_index].is_empty() {
+                    self.prog_coeffs[comp_index] =
+                        vec![
+                            [0; BLOCK_SIZE];
+                            mxx * myy * self.comp[comp_index].h * self.comp[comp_index].v
+                        ];
+                }
+            }
+        }
+
+        self.bits = Bits::default();
+        let mut mcu = 0;
+        let mut expected_rst = RST0_MARKER;
+        let mut dc = [0_i32; MAX_COMPONENTS];
+        let mut block_count = 0;
+        for my in 0..myy {
+            for mx in 0..mxx {
+                for s in &scan[..n_comp] {
+                    let comp_index = s.comp_index;
+                    let hi = self.comp[comp_index].h;
+                    let vi = self.comp[comp_index].v;
+                    for j in 0..hi * vi {
+                        // The blocks are traversed one MCU at a time. For 4:2:0 chroma
+                        // subsampling, there are four Y 8x8 blocks in every 16x16 MCU.
+                        //
+                        // For a sequential 32x16 pixel image, the Y blocks visiting order is:
+                        //	0 1 4 5
+                        //	2 3 6 7
+                        //
+                        // For progressive images, the interleaved scans (those with n_comp > 1)
+                        // are traversed as above, but non-interleaved scans are traversed left
+                        // to right, top to bottom:
+                        //	0 1 2 3
+                        //	4 5 6 7
+                        // Only DC scans (zig_start == 0) can be interleaved. AC scans must have
+                        // only one component.
+                        //
+                        // To further complicate matters, for non-interleaved scans, there is no
+                        // data for any blocks that are inside the image at the MCU level but
+                        // outside the image at the pixel level. For example, a 24x16 pixel 4:2:0
+                        // progressive image consists of two 16x16 MCUs. The interleaved scans
+                        // will process 8 Y blocks:
+                        //	0 1 4 5
+                        //	2 3 6 7
+                        // The non-interleaved scans will process only 6 Y blocks:
+                        //	0 1 2
+                        //	3 4 5
+                        //
+                        // bx and by are the location of the current block, in units of 8x8
+                        // blocks: the third block in the first row has (bx, by) = (2, 0).
+                        let (bx, by) = if n_comp != 1 {
+                            (hi * mx + j % hi, vi * my + j / hi)
+                        } else {
+                            let q = mxx * hi;
+                            let (bx, by) = (block_count % q, block_count / q);
+                            block_count += 1;
+                            if bx * 8 >= self.width || by * 8 >= self.height {
+                                continue;
+                            }
+                            (bx, by)
+                        };
+
+                        // b is the decoded coefficients, in natural (not zig-zag) order.
+                        // Load the previous partially decoded coefficients, if applicable.
+                        let mut b: Block = if self.progressive {
+                            self.prog_coeffs[comp_index][by * mxx * hi + bx]
+                        } else {
+                            [0; BLOCK_SIZE]
+                        };
+
+                        if ah != 0 {
+                            self.refine(&mut b, s.ta, zig_start, zig_end, 1 << al)?;
+                        } else {
+                            let mut zig = zig_start;
+                            if zig == 0 {
+                                zig += 1;
+                                // Decode the DC coefficient, as specified in section F.2.2.1.
+                                let value = self.decode_huffman(DC_TABLE, s.td)?;
+                                if value > 16 {
+                                    return Err(new_unsupported_error("excessive DC component"));
+                                }
+                                let dc_delta = self.receive_extend(value)?;
+                                dc[comp_index] = dc[comp_index].wrapping_add(dc_delta);
+                                b[0] = dc[comp_index] << al;
+                            }
+
+                            if zig <= zig_end && self.eob_run > 0 {
+                                self.eob_run -= 1;
+                            } else {
+                                // Decode the AC coefficients, as specified in section F.2.2.2.
+                                while zig <= zig_end {
+                                    let value = self.decode_huffman(AC_TABLE, s.ta)?;
+                                    let val0 = value >> 4;
+                                    let val1 = value & 0x0f;
+                                    if val1 != 0 {
+                                        zig += val0 as i32;
+                                        if zig > zig_end {
+                                            break;
+                                        }
+                                        let ac = self.receive_extend(val1)?;
+                                        b[UNZIG[zig as usize]] = ac << al;
+                                    } else {
+                                        if val0 != 0x0f {
+                                            self.eob_run = 1 << val0;
+                                            if val0 != 0 {
+                                                let bits = self.decode_bits(val0 as i32)?;
+                                                self.eob_run |= bits as u16;
+                                            }
+                                            self.eob_run -= 1;
+                                            break;
+                                        }
+                                        zig += 0x0f;
+                                    }
+                                    zig += 1;
+                                }
+                            }
+                        }
+
+                        if self.progressive {
+                            // Save the coefficients.
+                            self.prog_coeffs[comp_index][by * mxx * hi + bx] = b;
+                            // At this point, we could call reconstruct_block to dequantize and perform the
+                            // inverse DCT, to save early stages of a progressive image to the image
+                            // buffers (the whole point of progressive encoding), but decode
+                            // does not return until the entire image is decoded, so we "continue"
+                            // here to avoid wasted computation. Instead, reconstruct_block is called on each
+                            // accumulated block by the reconstruct_progressive_image method after all of the
+                            // SOS markers are processed.
+                            continue;
+                        }
+                        self.reconstruct_block(b, bx, by, comp_index);
+                    } // for j
+                } // for i
+                mcu += 1;
+                if self.ri > 0 && mcu % self.ri == 0 && mcu < mxx * myy {
+                    // For well-formed input, the RST[0-7] restart marker follows
+                    // immediately. For corrupt input, call find_rst to try to
+                    // resynchronize.
+                    let mut tmp = [0; 2];
+                    self.read_full(&mut tmp)?;
+                    if tmp[0] != 0xff || tmp[1] != expected_rst {
+                        self.find_rst(expected_rst, tmp)?;
+                    }
+                    expected_rst += 1;
+                    if expected_rst == RST7_MARKER + 1 {
+                        expected_rst = RST0_MARKER;
+                    }
+                    // Reset the Huffman decoder.
+                    self.bits = Bits::default();
+                    // Reset the DC components, as per section F.2.1.3.1.
+                    dc = [0; MAX_COMPONENTS];
+                    // Reset the progressive decoder state, as per section G.1.2.2.
+                    self.eob_run = 0;
+                }
+            } // for mx
+        } // for my
+
+        Ok(())
+    }
+
+    /// refine decodes a successive approximation refinement block, as specified in
+    /// section G.1.2.
+    fn refine(
+        &mut self,
+        b: &mut Block,
+        ta: usize,
+        zig_start: i32,
+        zig_end: i32,
+        delta: i32,
+    ) -> std::io::Result<()> {
+        // Refining a DC component is trivial.
+        if zig_start == 0 {
+            if zig_end != 0 {
+                panic!("unreachable");
+            }
+            let bit = self.decode_bit()?;
+            if bit {
+                b[0] |= delta;
+            }
+            return Ok(());
+        }
+
+        // Refining AC components is more complicated; see sections G.1.2.2 and G.1.2.3.
+        let mut zig = zig_start;
+        if self.eob_run == 0 {
+            while zig <= zig_end {
+                let mut z = 0;
+                let value = self.decode_huffman(AC_TABLE, ta)?;
+                let val0 = value >> 4;
+                let val1 = value & 0x0f;
+
+                match val1 {
+                    0 => {
+                        if val0 != 0x0f {
+                            self.eob_run = 1 << val0;
+                            if val0 != 0 {
+                                let bits = self.decode_bits(val0 as i32)?;
+                                self.eob_run |= bits as u16;
+                            }
+                            break;
+                        }
+                    }
+                    1 => {
+                        z = delta;
+                        let bit = self.decode_bit()?;
+                        if !bit {
+                            z = -z;
+                        }
+                    }
+                    _ => return Err(new_format_error("unexpected Huffman code")),
+                }
+
+                zig = self.refine_non_zeroes(b, zig, zig_end, val0 as i32, delta)?;
+                if zig > zig_end {
+                    return Err(new_format_error("too many coefficients"));
+                }
+                if z != 0 {
+                    b[UNZIG[zig as usize]] = z;
+                }
+                zig += 1;
+            }
+        }
+        if self.eob_run > 0 {
+            self.eob_run -= 1;
+            self.refine_non_zeroes(b, zig, zig_end, -1, delta)?;
+        }
+        Ok(())
+    }
+
+    /// refine_non_zeroes refines non-zero entries of b in zig-zag order. If nz >= 0,
+    /// the first nz zero entries are skipped over.
+    fn refine_non_zeroes(
+        &mut self,
+        b: &mut Block,
+        mut zig: i32,
+        zig_end: i32,
+        mut nz: i32,
+        delta: i32,
+    ) -> std::io::Result<i32> {
+        while zig <= zig_end {
+            let u = UNZIG[zig as usize];
+            if b[u] == 0 {
+                if nz == 0 {
+                    break;
+                }
+                nz -= 1;
+                zig += 1;
+                continue;
+            }
+            let bit = self.decode_bit()?;
+            if bit {
+                if b[u] >= 0 {
+                    b[u] = b[u].wrapping_add(delta);
+                } else {
+                    b[u] = b[u].wrapping_sub(delta);
+                }
+            }
+            zig += 1;
+        }
+        Ok(zig)
+    }
+
+    pub(super) fn reconstruct_progressive_image(&mut self) -> std::io::Result<()> {
+        // The h0, mxx, by and bx variables have the same meaning as in the
+        // process_sos method.
+        let h0 = self.comp[0].h;
+        let mxx = mcu_count(self.width, 8 * h0);
+        for i in 0..self.n_comp {
+            if self.prog_coeffs[i].is_empty() {
+                continue;
+            }
+            let v = 8 * self.comp[0].v / self.comp[i].v;
+            let h = 8 * self.comp[0].h / self.comp[i].h;
+            let stride = mxx * self.comp[i].h;
+            let mut by = 0;
+            while by * v < self.height {
+                let mut bx = 0;
+                while bx * h < self.width {
+                    let b = self.prog_coeffs[i][by * stride + bx];
+                    self.reconstruct_block(b, bx, by, i);
+                    bx += 1;
+                }
+                by += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// reconstruct_block dequantizes, performs the inverse DCT and stores the block
+    /// to the image.
+    fn reconstruct_block(&mut self, mut b: Block, bx: usize, by: usize, comp_index: usize) {
+        let qt = &self.quant[self.comp[comp_index].tq as usize];
+        for zig in 0..BLOCK_SIZE {
+            b[UNZIG[zig]] = b[UNZIG[zig]].wrapping_mul(qt[zig]);
+        }
+        idct(&mut b);
+        let stride = self.strides[comp_index];
+        let dst = &mut self.planes[comp_index][8 * (by * stride + bx)..];
+        // Level shift by +128, clip to [0, 255], and write to dst.
+        for y in 0..8 {
+            let y8 = y * 8;
+            let y_stride = y * stride;
+            for x in 0..8 {
+                let mut c = b[y8 + x];
+                if c < -128 {
+                    c = 0;
+                } else if c > 127 {
+                    c = 255;
+                } else {
+                    c += 128;
+                }
+                dst[y_stride + x] = c as u8;
+            }
+        }
+    }
+
+    /// find_rst advances past the next RST restart marker that matches expected_rst.
+    /// Other than I/O errors, it is also an error if we encounter an {0xFF, M}
+    /// two-byte marker sequence where M is not 0x00, 0xFF or the expected_rst.
+    ///
+    /// This is similar to libjpeg's jdmarker.c's next_marker function.
+    /// <https://github.com/libjpeg-turbo/libjpeg-turbo/blob/2dfe6c0fe9e18671105e94f7cbf044d4a1d157e6/jdmarker.c#L892-L935>
+    ///
+    /// Precondition: tmp holds the next two bytes of JPEG-encoded input
+    /// (input in the self.read_full sense).
+    fn find_rst(&mut self, expected_rst: u8, mut tmp: [u8; 2]) -> std::io::Result<()> {
+        loop {
+            // i is the index such that, at the bottom of the loop, we read 2-i
+            // bytes into tmp[i..2], maintaining the invariant that tmp
+            // holds the next two bytes of JPEG-encoded input. It is either 0 or 1,
+            // so that each iteration advances by 1 or 2 bytes (or returns).
+            let mut i = 0;
+
+            if tmp[0] == 0xff {
+                if tmp[1] == expected_rst {
+                    return Ok(());
+                } else if tmp[1] == 0xff {
+                    i = 1;
+                } else if tmp[1] != 0x00 {
+                    // libjpeg's jdmarker.c's jpeg_resync_to_restart does something
+                    // fancy here, treating RST markers within two (modulo 8) of
+                    // expected_rst differently from RST markers that are 'more
+                    // distant'. Until we see evidence that recovering from such
+                    // cases is frequent enough to be worth the complexity, we take
+                    // a simpler approach for now. Any marker that's not 0x00, 0xff
+                    // or expected_rst is a fatal format error.
+                    return Err(new_format_error("bad RST marker"));
+                }
+            } else if tmp[1] == 0xff {
+                tmp[0] = 0xff;
+                i = 1;
+            }
+
+            self.read_full(&mut tmp[i..2])?;
+        }
+    }
+}
@@ -0,0 +1,593 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::fdct::fdct;
+use super::idct::{Block, BLOCK_SIZE};
+use super::reader::{
+    DHT_MARKER, DQT_MARKER, EOI_MARKER, SOF0_MARKER, SOI_MARKER, SOS_MARKER, UNZIG,
+};
+use crate::bufio;
+use crate::image::color::{self, Color, Model};
+use crate::image::{Gray, Image, Img, Point, YCbCr, RGBA};
+use std::io::Write;
+use std::sync::OnceLock;
+
+/// div returns a/b rounded to the nearest integer, instead of rounded to zero.
+fn div(a: i32, b: i32) -> i32 {
+    if a >= 0 {
+        return (a + (b >> 1)) / b;
+    }
+    -((-a + (b >> 1)) / b)
+}
+
+/// bit_count returns the number of bits needed to represent a.
+fn bit_count(a: i32) -> u32 {
+    32 - (a as u32).leading_zeros()
+}
+
+type QuantIndex = usize;
+
+const QUANT_INDEX_LUMINANCE: QuantIndex = 0;
+const QUANT_INDEX_CHROMINANCE: QuantIndex = 1;
+pub(super) const N_QUANT_INDEX: usize = 2;
+
+/// UNSCALED_QUANT are the unscaled quantization tables in zig-zag order. Each
+/// encoder copies and scales the tables according to its quality parameter.
+/// The values are derived from section K.1 after converting from natural to
+/// zig-zag order.
+pub(super) const UNSCALED_QUANT: [[u8; BLOCK_SIZE]; N_QUANT_INDEX] = [
+    // Luminance.
+    [
+        16, 11, 12, 14, 12, 10, 16, 14, //
+        13, 14, 18, 17, 16, 19, 24, 40, //
+        26, 24, 22, 22, 24, 49, 35, 37, //
+        29, 40, 58, 51, 61, 60, 57, 51, //
+        56, 55, 64, 72, 92, 78, 64, 68, //
+        87, 69, 55, 56, 80, 109, 81, 87, //
+        95, 98, 103, 104, 103, 62, 77, 113, //
+        121, 112, 100, 120, 92, 101, 103, 99, //
+    ],
+    // Chrominance.
+    [
+        17, 18, 18, 24, 21, 24, 47, 26, //
+        26, 47, 99, 66, 56, 66, 99, 99, //
+        99, 99, 99, 99, 99, 99, 99, 99, //
+        99, 99, 99, 99, 99, 99, 99, 99, //
+        99, 99, 99, 99, 99, 99, 99, 99, //
+        99, 99, 99, 99, 99, 99, 99, 99, //
+        99, 99, 99, 99, 99, 99, 99, 99, //
+        99, 99, 99, 99, 99, 99, 99, 99, //
+    ],
+];
+
+type HuffIndex = usize;
+
+const N_HUFF_INDEX: usize = 4;
+
+/// HuffmanSpec specifies a Huffman encoding.
+struct HuffmanSpec {
+    /// count[i] is the number of codes of length i+1 bits.
+    count: [u8; 16],
+    /// value[i] is the decoded value of the i'th codeword.
+    value: &'static [u8],
+}
+
+/// THE_HUFFMAN_SPEC is the Huffman encoding specifications.
+///
+/// This encoder uses the same Huffman encoding for all images. It is also the
+/// same Huffman encoding used by section K.3 of the spec.
+///
+/// The DC tables have 12 decoded values, called categories.
+///
+/// The AC tables have 162 decoded values: bytes that pack a 4-bit Run and a
+/// 4-bit Size. There are 16 valid Runs and 10 valid Sizes, plus two special R|S
+/// cases: 0|0 (meaning EOB) and F|0 (meaning ZRL).
+const THE_HUFFMAN_SPEC: [HuffmanSpec; N_HUFF_INDEX] = [
+    // Luminance DC.
+    HuffmanSpec {
+        count: [0, 1, 5, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0],
+        value: &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+    },
+    // Luminance AC.
+    HuffmanSpec {
+        count: [0, 2, 1, 3, 3, 2, 4, 3, 5, 5, 4, 4, 0, 0, 1, 125],
+        value: &[
+            0x01, 0x02, 0x03, 0x00, 0x04, 0x11, 0x05, 0x12, //
+            0x21, 0x31, 0x41, 0x06, 0x13, 0x51, 0x61, 0x07, //
+            0x22, 0x71, 0x14, 0x32, 0x81, 0x91, 0xa1, 0x08, //
+            0x23, 0x42, 0xb1, 0xc1, 0x15, 0x52, 0xd1, 0xf0, //
+            0x24, 0x33, 0x62, 0x72, 0x82, 0x09, 0x0a, 0x16, //
+            0x17, 0x18, 0x19, 0x1a, 0x25, 0x26, 0x27, 0x28, //
+            0x29, 0x2a, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, //
+            0x3a, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, //
+            0x4a, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, 0x59, //
+            0x5a, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, //
+            0x6a, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, //
+            0x7a, 0x83, 0x84, 0x85, 0x86, 0x87, 0x88, 0x89, //
+            0x8a, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97, 0x98, //
+            0x99, 0x9a, 0xa2, 0xa3, 0xa4, 0xa5, 0xa6, 0xa7, //
+            0xa8, 0xa9, 0xaa, 0xb2, 0xb3, 0xb4, 0xb5, 0xb6, //
+            0xb7, 0xb8, 0xb9, 0xba, 0xc2, 0xc3, 0xc4, 0xc5, //
+            0xc6, 0xc7, 0xc8, 0xc9, 0xca, 0xd2, 0xd3, 0xd4, //
+            0xd5, 0xd6, 0xd7, 0xd8, 0xd9, 0xda, 0xe1, 0xe2, //
+            0xe3, 0xe4, 0xe5, 0xe6, 0xe7, 0xe8, 0xe9, 0xea, //
+            0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, //
+            0xf9, 0xfa, //
+        ],
+    },
+    // Chrominance DC.
+    HuffmanSpec {
+        count: [0, 3, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0],
+        value: &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+    },
+    // Chrominance AC.
+    HuffmanSpec {
+        count: [0, 2, 1, 2, 4, 4, 3, 4, 7, 5, 4, 4, 0, 1, 2, 119],
+        value: &[
+            0x00, 0x01, 0x02, 0x03, 0x11, 0x04, 0x05, 0x21, //
+            0x31, 0x06, 0x12, 0x41, 0x51, 0x07, 0x61, 0x71, //
+            0x13, 0x22, 0x32, 0x81, 0x08, 0x14, 0x42, 0x91, //
+            0xa1, 0xb1, 0xc1, 0x09, 0x23, 0x33, 0x52, 0xf0, //
+            0x15, 0x62, 0x72, 0xd1, 0x0a, 0x16, 0x24, 0x34, //
+            0xe1, 0x25, 0xf1, 0x17, 0x18, 0x19, 0x1a, 0x26, //
+            0x27, 0x28, 0x29, 0x2a, 0x35, 0x36, 0x37, 0x38, //
+            0x39, 0x3a, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, //
+            0x49, 0x4a, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, //
+            0x59, 0x5a, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, //
+            0x69, 0x6a, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, //
+            0x79, 0x7a, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87, //
+            0x88, 0x89, 0x8a, 0x92, 0x93, 0x94, 0x95, 0x96, //
+            0x97, 0x98, 0x99, 0x9a, 0xa2, 0xa3, 0xa4, 0xa5, //
+            0xa6, 0xa7, 0xa8, 0xa9, 0xaa, 0xb2, 0xb3, 0xb4, //
+            0xb5, 0xb6, 0xb7, 0xb8, 0xb9, 0xba, 0xc2, 0xc3, //
+            0xc4, 0xc5, 0xc6, 0xc7, 0xc8, 0xc9, 0xca, 0xd2, //
+            0xd3, 0xd4, 0xd5, 0xd6, 0xd7, 0xd8, 0xd9, 0xda, //
+            0xe2, 0xe3, 0xe4, 0xe5, 0xe6, 0xe7, 0xe8, 0xe9, //
+            0xea, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, //
+            0xf9, 0xfa, //
+        ],
+    },
+];
+
+/// HuffmanLUT is a compiled look-up table representation of a HuffmanSpec.
+/// Each value maps to a u32 of which the 8 most significant bits hold the
+/// codeword size in bits and the 24 least significant bits hold the codeword.
+/// The maximum codeword size is 16 bits.
+type HuffmanLUT = Vec<u32>;
+
+fn new_huffman_lut(s: &HuffmanSpec) -> HuffmanLUT {
+    let max_value = s.value.iter().copied().max().unwrap_or(0) as usize;
+    let mut h = vec![0; max_value + 1];
+    let (mut code, mut k) = (0_u32, 0);
+    for (i, &count) in s.count.iter().enumerate() {
+        let n_bits = ((i + 1) as u32) << 24;
+        for _ in 0..count {
+            h[s.value[k] as usize] = n_bits | code;
+            code += 1;
+            k += 1;
+        }
+        code <<= 1;
+    }
+    h
+}
+
+/// the_huffman_lut returns the compiled representations of THE_HUFFMAN_SPEC.
+fn the_huffman_lut() -> &'static [HuffmanLUT; N_HUFF_INDEX] {
+    static LUT: OnceLock<[HuffmanLUT; N_HUFF_INDEX]> = OnceLock::new();
+    LUT.get_or_init(|| {
+        [
+            new_huffman_lut(&THE_HUFFMAN_SPEC[0]),
+            new_huffman_lut(&THE_HUFFMAN_SPEC[1]),
+            new_huffman_lut(&THE_HUFFMAN_SPEC[2]),
+            new_huffman_lut(&THE_HUFFMAN_SPEC[3]),
+        ]
+    })
+}
+
+/// Encoder encodes an image to the JPEG format.
+struct Encoder<'a> {
+    w: bufio::Writer<'a>,
+    /// bits and n_bits are accumulated bits to write to w.
+    bits: u32,
+    n_bits: u32,
+    /// quant is the scaled quantization tables, in zig-zag order.
+    quant: [[u8; BLOCK_SIZE]; N_QUANT_INDEX],
+}
+
+impl Encoder<'_> {
+    /// emit emits the least significant n_bits bits of bits to the bit-stream.
+    /// The precondition is bits < 1<<n_bits && n_bits <= 16.
+    fn emit(&mut self, bits: u32, n_bits: u32) -> std::io::Result<()> {
+        let mut n_bits = n_bits + self.n_bits;
+        let mut bits = bits << (32 - n_bits) | self.bits;
+        while n_bits >= 8 {
+            let b = (bits >> 24) as u8;
+            self.w.write_byte(b)?;
+            if b == 0xff {
+                self.w.write_byte(0x00)?;
+            }
+            bits <<= 8;
+            n_bits -= 8;
+        }
+        self.bits = bits;
+        self.n_bits = n_bits;
+        Ok(())
+    }
+
+    /// emit_huff emits the given value with the given Huffman encoder.
+    fn emit_huff(&mut self, h: HuffIndex, value: i32) -> std::io::Result<()> {
+        let x = the_huffman_lut()[h][value as usize];
+        self.emit(x & ((1 << 24) - 1), x >> 24)
+    }
+
+    /// emit_huff_rle emits a run of run_length copies of value encoded with the given
+    /// Huffman encoder.
+    fn emit_huff_rle(&mut self, h: HuffIndex, run_length: i32, value: i32) -> std::io::Result<()> {
+        let (mut a, mut b) = (value, value);
+        if a < 0 {
+            a = -value;
+            b = value - 1;
+        }
+        let n_bits = bit_count(a);
+        self.emit_huff(h, run_length << 4 | n_bits as i32)?;
+        if n_bits > 0 {
+            self.emit(b as u32 & ((1 << n_bits) - 1), n_bits)?;
+        }
+        Ok(())
+    }
+
+    /// write_marker_header writes the header for a marker with the given length.
+    fn write_marker_header(&mut self, marker: u8, markerlen: usize) -> std::io::Result<()> {
+        self.w
+            .write_all(&[0xff, marker, (markerlen >> 8) as u8, markerlen as u8])
+    }
+
+    /// write_dqt writes the Define Quantization Table marker.
+    fn write_dqt(&mut self) -> std::io::Result<()> {
+        const MARKERLEN: usize = 2 + N_QUANT_INDEX * (1 + BLOCK_SIZE);
+        self.write_marker_header(DQT_MARKER, MARKERLEN)?;
+        for i in 0..N_QUANT_INDEX {
+            self.w.write_byte(i as u8)?;
+            self.w.write_all(&self.quant[i])?;
+        }
+        Ok(())
+    }
+
+    /// write_sof0 writes the Start Of Frame (Baseline Sequential) marker.
+    fn write_sof0(&mut self, size: Point, n_component: usize) -> std::io::Result<()> {
+        let markerlen = 8 + 3 * n_component;
+        self.write_marker_header(SOF0_MARKER, markerlen)?;
+        let mut buf = vec![
+            8, // 8-bit color.
+            (size.y >> 8) as u8,
+            size.y as u8,
+            (size.x >> 8) as u8,
+            size.x as u8,
+            n_component as u8,
+        ];
+        if n_component == 1 {
+            // No subsampling for grayscale image.
+            buf.extend_from_slice(&[1, 0x11, 0x00]);
+        } else {
+            for i in 0..n_component {
+                buf.push(i as u8 + 1);
+                // We use 4:2:0 chroma subsampling.
+                buf.push(b"\x22\x11\x11"[i]);
+                buf.push(b"\x00\x01\x01"[i]);
+            }
+        }
+        self.w.write_all(&buf)
+    }
+
+    /// write_dht writes the Define Huffman Table marker.
+    fn write_dht(&mut self, n_component: usize) -> std::io::Result<()> {
+        let mut specs = &THE_HUFFMAN_SPEC[..];
+        if n_component == 1 {
+            // Drop the Chrominance tables.
+            specs = &specs[..2];
+        }
+        let markerlen = 2 + specs.iter().map(|s| 1 + 16 + s.value.len()).sum::<usize>();
+        self.write_marker_header(DHT_MARKER, markerlen)?;
+        for (i, s) in specs.iter().enumerate() {
+            self.w.write_byte(b"\x00\x10\x01\x11"[i])?;
+            self.w.write_all(&s.count)?;
+            self.w.write_all(s.value)?;
+        }
+        Ok(())
+    }
+
+    /// write_block writes a block of pixel data using the given quantization table,
+    /// returning the post-quantized DC value of the DCT-transformed block. b is in
+    /// natural (not zig-zag) order.
+    fn write_block(&mut self, b: &mut Block, q: QuantIndex, prev_dc: i32) -> std::io::Result<i32> {
+        fdct(b);
+        // Emit the DC delta.
+        let dc = div(b[0], 8 * self.quant[q][0] as i32);
+        self.emit_huff_rle(2 * q, 0, dc - prev_dc)?;
+        // Emit the AC components.
+        let h = 2 * q + 1;
+        let mut run_length = 0;
+        for zig in 1..BLOCK_SIZE {
+            let ac = div(b[UNZIG[zig]], 8 * self.quant[q][zig] as i32);
+            if ac == 0 {
+                run_length += 1;
+            } else {
+                while run_length > 15 {
+                    self.emit_huff(h, 0xf0)?;
+                    run_length -= 16;
+                }
+                self.emit_huff_rle(h, run_length, ac)?;
+                run_length = 0;
+            }
+        }
+        if run_length > 0 {
+            self.emit_huff(h, 0x00)?;
+        }
+        Ok(dc)
+    }
+
+    /// write_sos writes the StartOfScan marker.
+    fn write_sos(&mut self, m: &Img) -> std::io::Result<()> {
+        // Scratch buffers to hold the YCbCr values.
+        // The blocks are in natural (not zig-zag) order.
+        let mut b: Block = [0; BLOCK_SIZE];
+        let mut cb: [Block; 4] = [[0; BLOCK_SIZE]; 4];
+        let mut cr: [Block; 4] = [[0; BLOCK_SIZE]; 4];
+        // DC components are delta-encoded.
+        let (mut prev_dc_y, mut prev_dc_cb, mut prev_dc_cr) = (0, 0, 0);
+        let bounds = m.bounds();
+        if is_gray(m) {
+            self.w.write_all(SOS_HEADER_Y)?;
+            for y in (bounds.min.y..bounds.max.y).step_by(8) {
+                for x in (bounds.min.x..bounds.max.x).step_by(8) {
+                    let p = Point::new(x, y);
+                    match m {
+                        Img::Gray(m) => gray_to_y(m, p, &mut b),
+                        _ => to_y(m, p, &mut b),
+                    }
+                    prev_dc_y = self.write_block(&mut b, QUANT_INDEX_LUMINANCE, prev_dc_y)?;
+                }
+            }
+        } else {
+            self.w.write_all(SOS_HEADER_YCBCR)?;
+            for y in (bounds.min.y..bounds.max.y).step_by(16) {
+                for x in (bounds.min.x..bounds.max.x).step_by(16) {
+                    for i in 0..4 {
+                        let x_off = (i & 1) as isize * 8;
+                        let y_off = (i & 2) as isize * 4;
+                        let p = Point::new(x + x_off, y + y_off);
+                        match m {
+                            Img::RGBA(m) => rgba_to_ycbcr(m, p, &mut b, &mut cb[i], &mut cr[i]),
+                            Img::YCbCr(m) => ycbcr_to_ycbcr(m, p, &mut b, &mut cb[i], &mut cr[i]),
+                            _ => to_ycbcr(m, p, &mut b, &mut cb[i], &mut cr[i]),
+                        }
+                        prev_dc_y = self.write_block(&mut b, QUANT_INDEX_LUMINANCE, prev_dc_y)?;
+                    }
+                    scale(&mut b, &cb);
+                    prev_dc_cb = self.write_block(&mut b, QUANT_INDEX_CHROMINANCE, prev_dc_cb)?;
+                    scale(&mut b, &cr);
+                    prev_dc_cr = self.write_block(&mut b, QUANT_INDEX_CHROMINANCE, prev_dc_cr)?;
+                }
+            }
+        }
+        // Pad the last byte with 1's.
+        self.emit(0x7f, 7)
+    }
+}
+
+/// is_gray reports whether m is encoded with a single (luminance) component.
+fn is_gray(m: &Img) -> bool {
+    matches!(m.color_model(), Model::GrayModel | Model::Gray16Model)
+}
+
+/// to_ycbcr converts the 8x8 region of m whose top-left corner is p to its
+/// YCbCr values.
+fn to_ycbcr(m: &Img, p: Point, y_block: &mut Block, cb_block: &mut Block, cr_block: &mut Block) {
+    let b = m.bounds();
+    let xmax = b.max.x - 1;
+    let ymax = b.max.y - 1;
+    for j in 0..8 {
+        for i in 0..8 {
+            let c = m.at((p.x + i).min(xmax), (p.y + j).min(ymax));
+            let c = match Model::YCbCrModel.convert(&c) {
+                Color::YCbCr(c) => c,
+                _ => unreachable!(),
+            };
+            let k = (8 * j + i) as usize;
+            y_block[k] = c.y as i32;
+            cb_block[k] = c.cb as i32;
+            cr_block[k] = c.cr as i32;
+        }
+    }
+}
+
+/// to_y stores the luminance of the 8x8 region of m whose top-left corner is p
+/// in y_block.
+fn to_y(m: &Img, p: Point, y_block: &mut Block) {
+    let b = m.bounds();
+    let xmax = b.max.x - 1;
+    let ymax = b.max.y - 1;
+    for j in 0..8 {
+        for i in 0..8 {
+            let c = m.at((p.x + i).min(xmax), (p.y + j).min(ymax));
+            let c = match Model::GrayModel.convert(&c) {
+                Color::Gray(c) => c,
+                _ => unreachable!(),
+            };
+            y_block[(8 * j + i) as usize] = c.y as i32;
+        }
+    }
+}
+
+/// gray_to_y stores the 8x8 region of m whose top-left corner is p in y_block.
+fn gray_to_y(m: &Gray, p: Point, y_block: &mut Block) {
+    let b = m.bounds();
+    let xmax = b.max.x - 1;
+    let ymax = b.max.y - 1;
+    for j in 0..8 {
+        for i in 0..8 {
+            let idx = m.pix_offset((p.x + i).min(xmax), (p.y + j).min(ymax));
+            y_block[(8 * j + i) as usize] = m.pix[idx] as i32;
+        }
+    }
+}
+
+/// rgba_to_ycbcr is a specialized version of to_ycbcr for RGBA images.
+fn rgba_to_ycbcr(
+    m: &RGBA,
+    p: Point,
+    y_block: &mut Block,
+    cb_block: &mut Block,
+    cr_block: &mut Block,
+) {
+    let b = m.bounds();
+    let xmax = b.max.x - 1;
+    let ymax = b.max.y - 1;
+    for j in 0..8 {
+        let sy = (p.y + j).min(ymax);
+        for i in 0..8 {
+            let sx = (p.x + i).min(xmax);
+            let pix = &m.pix[m.pix_offset(sx, sy)..];
+            let (yy, cb, cr) = color::rgb_to_ycbcr(pix[0], pix[1], pix[2]);
+            let k = (8 * j + i) as usize;
+            y_block[k] = yy as i32;
+            cb_block[k] = cb as i32;
+            cr_block[k] = cr as i32;
+        }
+    }
+}
+
+/// ycbcr_to_ycbcr is a specialized version of to_ycbcr for YCbCr images.
+fn ycbcr_to_ycbcr(
+    m: &YCbCr,
+    p: Point,
+    y_block: &mut Block,
+    cb_block: &mut Block,
+    cr_block: &mut Block,
+) {
+    let b = m.bounds();
+    let xmax = b.max.x - 1;
+    let ymax = b.max.y - 1;
+    let (y_plane, cb_plane, cr_plane) = (m.y(), m.cb(), m.cr());
+    for j in 0..8 {
+        let sy = (p.y + j).min(ymax);
+        for i in 0..8 {
+            let sx = (p.x + i).min(xmax);
+            let yi = m.y_offset(sx, sy);
+            let ci = m.c_offset(sx, sy);
+            let k = (8 * j + i) as usize;
+            y_block[k] = y_plane[yi] as i32;
+            cb_block[k] = cb_plane[ci] as i32;
+            cr_block[k] = cr_plane[ci] as i32;
+        }
+    }
+}
+
+/// scale scales the 16x16 region represented by the 4 src blocks to the 8x8
+/// dst block.
+fn scale(dst: &mut Block, src: &[Block; 4]) {
+    for (i, s) in src.iter().enumerate() {
+        let dst_off = (i & 2) << 4 | (i & 1) << 2;
+        for y in 0..4 {
+            for x in 0..4 {
+                let j = 16 * y + 2 * x;
+                let sum = s[j] + s[j + 1] + s[j + 8] + s[j + 9];
+                dst[8 * y + x + dst_off] = (sum + 2) >> 2;
+            }
+        }
+    }
+}
+
+/// SOS_HEADER_Y is the SOS marker "\xff\xda" followed by 8 bytes:
+///   - the marker length "\x00\x08",
+///   - the number of components "\x01",
+///   - component 1 uses DC table 0 and AC table 0 "\x01\x00",
+///   - the bytes "\x00\x3f\x00". Section B.2.3 of the spec says that for
+///     sequential DCTs, those bytes (8-bit Ss, 8-bit Se, 4-bit Ah, 4-bit Al)
+///     should be 0x00, 0x3f, 0x00<<4 | 0x00.
+const SOS_HEADER_Y: &[u8] = &[
+    0xff, SOS_MARKER, 0x00, 0x08, 0x01, 0x01, 0x00, 0x00, 0x3f, 0x00,
+];
+
+/// SOS_HEADER_YCBCR is the SOS marker "\xff\xda" followed by 12 bytes:
+///   - the marker length "\x00\x0c",
+///   - the number of components "\x03",
+///   - component 1 uses DC table 0 and AC table 0 "\x01\x00",
+///   - component 2 uses DC table 1 and AC table 1 "\x02\x11",
+///   - component 3 uses DC table 1 and AC table 1 "\x03\x11",
+///   - the bytes "\x00\x3f\x00". Section B.2.3 of the spec says that for
+///     sequential DCTs, those bytes (8-bit Ss, 8-bit Se, 4-bit Ah, 4-bit Al)
+///     should be 0x00, 0x3f, 0x00<<4 | 0x00.
+const SOS_HEADER_YCBCR: &[u8] = &[
+    0xff, SOS_MARKER, 0x00, 0x0c, 0x03, 0x01, 0x00, 0x02, 0x11, 0x03, 0x11, 0x00, 0x3f, 0x00,
+];
+
+/// DEFAULT_QUALITY is the default quality encoding parameter.
+pub const DEFAULT_QUALITY: isize = 75;
+
+/// Options are the encoding parameters.
+/// quality ranges from 1 to 100 inclusive, higher is better.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Options {
+    pub quality: isize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            quality: DEFAULT_QUALITY,
+        }
+    }
+}
+
+/// encode writes the Image m to w in JPEG 4:2:0 baseline format with the given
+/// options. Any Img variant may be encoded: grayscale images are written with
+/// a single component, everything else is converted to YCbCr.
+pub fn encode(w: &mut dyn std::io::Write, m: &Img, o: Options) -> std::io::Result<()> {
+    let b = m.bounds();
+    if b.dx() >= 1 << 16 || b.dy() >= 1 << 16 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "jpeg: image is too large to encode",
+        ));
+    }
+    // Clip quality to [1, 100].
+    let quality = o.quality.clamp(1, 100);
+    // Convert from a quality rating to a scaling factor.
+    let scale = if quality < 50 {
+        5000 / quality
+    } else {
+        200 - quality * 2
+    };
+    // Initialize the quantization tables.
+    let mut quant = [[0; BLOCK_SIZE]; N_QUANT_INDEX];
+    for (q, unscaled) in quant.iter_mut().zip(UNSCALED_QUANT.iter()) {
+        for (x, &u) in q.iter_mut().zip(unscaled.iter()) {
+            *x = ((u as isize * scale + 50) / 100).clamp(1, 255) as u8;
+        }
+    }
+    let mut e = Encoder {
+        w: bufio::Writer::new(w),
+        bits: 0,
+        n_bits: 0,
+        quant,
+    };
+
+    // Compute number of components based on input image type.
+    let n_component = if is_gray(m) { 1 } else { 3 };
+    // Write the Start Of Image marker.
+    e.w.write_all(&[0xff, SOI_MARKER])?;
+    // Write the quantization tables.
+    e.write_dqt()?;
+    // Write the image dimensions.
+    e.write_sof0(b.size(), n_component)?;
+    // Write the Huffman tables.
+    e.write_dht(n_component)?;
+    // Write the image data.
+    e.write_sos(m)?;
+    // Write the End Of Image marker.
+    e.w.write_all(&[0xff, EOI_MARKER])?;
+    e.w.flush()
+}
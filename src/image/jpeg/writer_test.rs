@@ -0,0 +1,208 @@
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::reader::UNZIG;
+use super::writer::UNSCALED_QUANT;
+use super::{decode, encode, Options};
+use crate::image::color::{self, ColorTrait};
+use crate::image::{
+    png, rect, Alpha, Alpha16, Gray, Gray16, Image, Img, Paletted, YCbCrSubsampleRatio,
+};
+
+#[test]
+fn test_unscaled_quant() {
+    // The quantization tables from section K.1, in natural order.
+    let want: [[u8; 64]; 2] = [
+        [
+            16, 11, 10, 16, 24, 40, 51, 61, //
+            12, 12, 14, 19, 26, 58, 60, 55, //
+            14, 13, 16, 24, 40, 57, 69, 56, //
+            14, 17, 22, 29, 51, 87, 80, 62, //
+            18, 22, 37, 56, 68, 109, 103, 77, //
+            24, 35, 55, 64, 81, 104, 113, 92, //
+            49, 64, 78, 87, 103, 121, 120, 101, //
+            72, 92, 95, 98, 112, 100, 103, 99, //
+        ],
+        [
+            17, 18, 24, 47, 99, 99, 99, 99, //
+            18, 21, 26, 66, 99, 99, 99, 99, //
+            24, 26, 56, 99, 99, 99, 99, 99, //
+            47, 66, 99, 99, 99, 99, 99, 99, //
+            99, 99, 99, 99, 99, 99, 99, 99, //
+            99, 99, 99, 99, 99, 99, 99, 99, //
+            99, 99, 99, 99, 99, 99, 99, 99, //
+            99, 99, 99, 99, 99, 99, 99, 99, //
+        ],
+    ];
+    for (i, q) in UNSCALED_QUANT.iter().enumerate() {
+        for (zig, &v) in q.iter().enumerate() {
+            assert_eq!(v, want[i][UNZIG[zig]], "table {}, zig {}", i, zig);
+        }
+    }
+}
+
+fn read_png(filename: &str) -> Box<Img> {
+    let f = std::fs::File::open(filename).unwrap();
+    png::decode(&mut std::io::BufReader::new(f)).unwrap()
+}
+
+fn encode_decode(m: &Img, o: Options) -> Box<Img> {
+    let mut buf = Vec::new();
+    encode(&mut buf, m, o).unwrap();
+    decode(&mut buf.as_slice()).unwrap()
+}
+
+fn delta(u0: u32, u1: u32) -> i64 {
+    (u0 as i64 - u1 as i64).abs()
+}
+
+/// average_delta returns the average delta in RGB space. The two images must
+/// have the same bounds.
+fn average_delta(m0: &Img, m1: &Img) -> i64 {
+    let b = m0.bounds();
+    let (mut sum, mut n) = (0, 0);
+    for y in b.min.y..b.max.y {
+        for x in b.min.x..b.max.x {
+            let (r0, g0, b0, _) = m0.at(x, y).rgba();
+            let (r1, g1, b1, _) = m1.at(x, y).rgba();
+            sum += delta(r0, r1) + delta(g0, g1) + delta(b0, b1);
+            n += 3;
+        }
+    }
+    sum / n
+}
+
+#[test]
+fn test_writer() {
+    // (quality, tolerance)
+    let test_cases = [
+        (1, 24 << 8),
+        (20, 12 << 8),
+        (60, 8 << 8),
+        (80, 6 << 8),
+        (90, 4 << 8),
+        (100, 2 << 8),
+    ];
+    let m0 = read_png("src/image/testdata/video-001.png");
+    for (quality, tolerance) in test_cases {
+        let m1 = encode_decode(&m0, Options { quality });
+        assert!(
+            m0.bounds() == m1.bounds(),
+            "quality={}: bounds differ: {:?} and {:?}",
+            quality,
+            m0.bounds(),
+            m1.bounds()
+        );
+        let got = average_delta(&m0, &m1);
+        assert!(
+            got <= tolerance,
+            "quality={}: average delta is too high: {} > {}",
+            quality,
+            got,
+            tolerance
+        );
+    }
+}
+
+/// test_write_grayscale tests that a grayscale images survives a round-trip
+/// through encode/decode cycle.
+#[test]
+fn test_write_grayscale() {
+    let mut m0 = Gray::new(&rect(0, 0, 32, 32));
+    for (i, p) in m0.pix.iter_mut().enumerate() {
+        *p = i as u8;
+    }
+    let m0 = Img::Gray(m0);
+    let m1 = encode_decode(&m0, Options::default());
+    assert!(m0.bounds() == m1.bounds());
+    assert!(matches!(m1.as_ref(), Img::Gray(_)), "want a Gray image");
+    let got = average_delta(&m0, &m1);
+    assert!(got <= 2 << 8, "average delta is too high: {}", got);
+}
+
+/// convert returns a copy of src in the pixel format of dst.
+fn convert(src: &Img, mut dst: Img) -> Img {
+    let b = src.bounds();
+    for y in b.min.y..b.max.y {
+        for x in b.min.x..b.max.x {
+            dst.set(x, y, &src.at(x, y));
+        }
+    }
+    dst
+}
+
+/// test_encode_all_variants tests that every Img variant can be encoded.
+#[test]
+fn test_encode_all_variants() {
+    let src = read_png("src/image/testdata/video-001.png");
+    let r = *src.bounds();
+    let palette = color::Palette {
+        colors: vec![
+            color::Color::new_rgba(0x00, 0x00, 0x00, 0xff),
+            color::Color::new_rgba(0xff, 0x00, 0x00, 0xff),
+            color::Color::new_rgba(0x00, 0xff, 0x00, 0xff),
+            color::Color::new_rgba(0x00, 0x00, 0xff, 0xff),
+            color::Color::new_rgba(0x80, 0x80, 0x80, 0xff),
+            color::Color::new_rgba(0xff, 0xff, 0xff, 0xff),
+        ],
+    };
+    let images = [
+        Img::new_rgba(&r),
+        Img::new_rgba64(&r),
+        Img::new_nrgba(&r),
+        Img::new_nrgba64(&r),
+        Img::Alpha(Alpha::new(&r)),
+        Img::Alpha16(Alpha16::new(&r)),
+        Img::Gray(Gray::new(&r)),
+        Img::Gray16(Gray16::new(&r)),
+        Img::Paletted(Paletted::new(&r, palette)),
+        Img::new_ycbcr(&r, YCbCrSubsampleRatio::Ratio444),
+        Img::new_ycbcr(&r, YCbCrSubsampleRatio::Ratio420),
+        Img::new_nycbcra(&r, YCbCrSubsampleRatio::Ratio420),
+    ];
+    for dst in images {
+        let model = dst.color_model();
+        let m0 = convert(&src, dst);
+        let m1 = encode_decode(&m0, Options { quality: 90 });
+        assert!(m0.bounds() == m1.bounds(), "{:?}: bounds differ", model);
+        let want_gray = matches!(model, color::Model::GrayModel | color::Model::Gray16Model);
+        assert_eq!(
+            matches!(m1.as_ref(), Img::Gray(_)),
+            want_gray,
+            "{:?}: wrong decoded image type",
+            model
+        );
+        let got = average_delta(&m0, &m1);
+        assert!(
+            got <= 6 << 8,
+            "{:?}: average delta is too high: {}",
+            model,
+            got
+        );
+    }
+}
+
+/// test_encode_ycbcr tests that an RGBA image and the equivalent YCbCr 4:4:4
+/// image are encoded identically.
+#[test]
+fn test_encode_ycbcr() {
+    let m0 = read_png("src/image/testdata/video-001.png");
+    let r = *m0.bounds();
+    let rgba = convert(&m0, Img::new_rgba(&r));
+    let ycbcr = convert(&rgba, Img::new_ycbcr(&r, YCbCrSubsampleRatio::Ratio444));
+    let (mut b0, mut b1) = (Vec::new(), Vec::new());
+    encode(&mut b0, &rgba, Options::default()).unwrap();
+    encode(&mut b1, &ycbcr, Options::default()).unwrap();
+    assert!(b0 == b1, "RGBA and YCbCr encodings differ");
+}
+
+#[test]
+fn test_encode_too_large() {
+    let m = Img::Gray(Gray::new(&rect(0, 0, 1 << 16, 1)));
+    let err = encode(&mut Vec::new(), &m, Options::default())
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "jpeg: image is too large to encode");
+}
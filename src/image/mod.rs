@@ -12,30 +12,37 @@
 //! as NewRGBA and NewPaletted, or by calling Decode on an io.Reader containing
 //! image data in a format such as GIF, JPEG or PNG. Decoding any particular
 //! image format requires the prior registration of a decoder function.
-// // Registration is typically automatic as a side effect of initializing that
-// // format's package so that, to decode a PNG image, it suffices to have
-// //
-// //    import _ "image/png"
-// //
-// // in a program's main package. The _ means to import a package purely for its
-// // initialization side effects.
-// //
-// // See "The Go image package" for more details:
-// // https://golang.org/doc/articles/image_package.html
+//! The formats implemented in this library are registered by default,
+//! other formats can be added with register_format.
+//!
+//! See "The Go image package" for more details:
+//! <https://golang.org/doc/articles/image_package.html>
 
 pub mod color;
 pub mod draw;
+pub mod gif;
+pub mod jpeg;
 pub mod png;
 
+mod format;
 mod geom;
 mod image;
+mod ycbcr;
 
+pub use format::{
+    decode, decode_config, register_format, DecodeConfigFn, DecodeFn, ERR_FORMAT_MSG,
+};
 pub use geom::{rect, Point, Rectangle, ZR};
 pub use image::{
     Alpha, Alpha16, Config, Gray, Gray16, Image, Img, Paletted, NRGBA, NRGBA64, RGBA, RGBA64,
 };
+pub use ycbcr::{NYCbCrA, YCbCr, YCbCrSubsampleRatio};
 
+#[cfg(test)]
+mod decode_test;
 #[cfg(test)]
 mod geom_test;
 #[cfg(test)]
 mod image_test;
+#[cfg(test)]
+mod ycbcr_test;
@@ -429,10 +429,7 @@ impl Decoder {
 
         let img = {
             let mut br = std::io::BufReader::new(&mut idat_reader);
-            let mut zlib_reader = match zlib::Reader::new(&mut br) {
-                Ok(reader) => reader,
-                Err(err) => return Err(err.to_stdio_error()),
-            };
+            let mut zlib_reader = zlib::Reader::new(&mut br)?;
             let img = match self.interlace {
                 InterlaceType::None => {
                     let mut img = allocate_image(&dp, &pal);
@@ -453,8 +450,8 @@ impl Decoder {
 
             // Check for EOF, to verify the zlib checksum.
             let mut tmp = [0; 1];
-            if let Err(err) = zlib_reader.read(&mut tmp) {
-                return Err(err.to_stdio_error());
+            if zlib_reader.read(&mut tmp)? != 0 {
+                return Err(new_format_error("too much pixel data"));
             }
             img
         };
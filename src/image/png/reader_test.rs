@@ -376,6 +376,7 @@ fn sng(w: &mut dyn std::io::Write, filename: &str, png: &Img) -> std::io::Result
             }
             Model::AlphaModel => todo!(),
             Model::Alpha16Model => todo!(),
+            Model::YCbCrModel | Model::NYCbCrAModel => unreachable!(),
         }
         ggio::write_string(w, "\n")?;
     }
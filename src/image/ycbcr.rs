@@ -1,329 +1,376 @@
-// // Copyright 2011 The Go Authors. All rights reserved.
-// // Use of this source code is governed by a BSD-style
-// // license that can be found in the LICENSE file.
-
-// package image
-
-// import (
-// 	"image/color"
-// )
-
-// // YCbCrSubsampleRatio is the chroma subsample ratio used in a YCbCr image.
-// type YCbCrSubsampleRatio int
-
-// const (
-// 	YCbCrSubsampleRatio444 YCbCrSubsampleRatio = iota
-// 	YCbCrSubsampleRatio422
-// 	YCbCrSubsampleRatio420
-// 	YCbCrSubsampleRatio440
-// 	YCbCrSubsampleRatio411
-// 	YCbCrSubsampleRatio410
-// )
-
-// func (s YCbCrSubsampleRatio) String() string {
-// 	switch s {
-// 	case YCbCrSubsampleRatio444:
-// 		return "YCbCrSubsampleRatio444"
-// 	case YCbCrSubsampleRatio422:
-// 		return "YCbCrSubsampleRatio422"
-// 	case YCbCrSubsampleRatio420:
-// 		return "YCbCrSubsampleRatio420"
-// 	case YCbCrSubsampleRatio440:
-// 		return "YCbCrSubsampleRatio440"
-// 	case YCbCrSubsampleRatio411:
-// 		return "YCbCrSubsampleRatio411"
-// 	case YCbCrSubsampleRatio410:
-// 		return "YCbCrSubsampleRatio410"
-// 	}
-// 	return "YCbCrSubsampleRatioUnknown"
-// }
-
-// // YCbCr is an in-memory image of Y'CbCr colors. There is one Y sample per
-// // pixel, but each Cb and Cr sample can span one or more pixels.
-// // YStride is the Y slice index delta between vertically adjacent pixels.
-// // CStride is the Cb and Cr slice index delta between vertically adjacent pixels
-// // that map to separate chroma samples.
-// // It is not an absolute requirement, but YStride and len(Y) are typically
-// // multiples of 8, and:
-// //
-// //	For 4:4:4, CStride == YStride/1 && len(Cb) == len(Cr) == len(Y)/1.
-// //	For 4:2:2, CStride == YStride/2 && len(Cb) == len(Cr) == len(Y)/2.
-// //	For 4:2:0, CStride == YStride/2 && len(Cb) == len(Cr) == len(Y)/4.
-// //	For 4:4:0, CStride == YStride/1 && len(Cb) == len(Cr) == len(Y)/2.
-// //	For 4:1:1, CStride == YStride/4 && len(Cb) == len(Cr) == len(Y)/4.
-// //	For 4:1:0, CStride == YStride/4 && len(Cb) == len(Cr) == len(Y)/8.
-// type YCbCr struct {
-// 	Y, Cb, Cr      []uint8
-// 	YStride        int
-// 	CStride        int
-// 	SubsampleRatio YCbCrSubsampleRatio
-// 	Rect           Rectangle
-// }
-
-// func (p *YCbCr) ColorModel() color::Model {
-// 	return color.YCbCrModel
-// }
-
-// func (p *YCbCr) Bounds() Rectangle {
-// 	return p.Rect
-// }
-
-// func (p *YCbCr) At(x, y int) color.Color {
-// 	return p.YCbCrAt(x, y)
-// }
-
-// func (p *YCbCr) RGBA64At(x, y int) color.RGBA64 {
-// 	r, g, b, a := p.YCbCrAt(x, y).rgba()
-// 	return color.RGBA64{uint16(r), uint16(g), uint16(b), uint16(a)}
-// }
-
-// func (p *YCbCr) YCbCrAt(x, y int) color.YCbCr {
-// 	if !(Point{x, y}.inside(p.Rect)) {
-// 		return color.YCbCr{}
-// 	}
-// 	yi := p.YOffset(x, y)
-// 	ci := p.COffset(x, y)
-// 	return color.YCbCr{
-// 		p.y[yi],
-// 		p.Cb[ci],
-// 		p.Cr[ci],
-// 	}
-// }
-
-// // YOffset returns the index of the first element of Y that corresponds to
-// // the pixel at (x, y).
-// func (p *YCbCr) YOffset(x, y int) int {
-// 	return (y-p.Rect.min.y)*p.YStride + (x - p.Rect.min.x)
-// }
-
-// // COffset returns the index of the first element of Cb or Cr that corresponds
-// // to the pixel at (x, y).
-// func (p *YCbCr) COffset(x, y int) int {
-// 	switch p.SubsampleRatio {
-// 	case YCbCrSubsampleRatio422:
-// 		return (y-p.Rect.min.y)*p.CStride + (x/2 - p.Rect.min.x/2)
-// 	case YCbCrSubsampleRatio420:
-// 		return (y/2-p.Rect.min.y/2)*p.CStride + (x/2 - p.Rect.min.x/2)
-// 	case YCbCrSubsampleRatio440:
-// 		return (y/2-p.Rect.min.y/2)*p.CStride + (x - p.Rect.min.x)
-// 	case YCbCrSubsampleRatio411:
-// 		return (y-p.Rect.min.y)*p.CStride + (x/4 - p.Rect.min.x/4)
-// 	case YCbCrSubsampleRatio410:
-// 		return (y/2-p.Rect.min.y/2)*p.CStride + (x/4 - p.Rect.min.x/4)
-// 	}
-// 	// Default to 4:4:4 subsampling.
-// 	return (y-p.Rect.min.y)*p.CStride + (x - p.Rect.min.x)
-// }
-
-// // SubImage returns an image representing the portion of the image p visible
-// // through r. The returned value shares pixels with the original image.
-// func (p *YCbCr) SubImage(r Rectangle) Image {
-// 	r = r.intersect(p.Rect)
-// 	// If r1 and r2 are Rectangles, r1.intersect(r2) is not guaranteed to be inside
-// 	// either r1 or r2 if the intersection is empty. Without explicitly checking for
-// 	// this, the Pix[i:] expression below can panic.
-// 	if r.Empty() {
-// 		return &YCbCr{
-// 			SubsampleRatio: p.SubsampleRatio,
-// 		}
-// 	}
-// 	yi := p.YOffset(r.min.x, r.min.y)
-// 	ci := p.COffset(r.min.x, r.min.y)
-// 	return &YCbCr{
-// 		Y:              p.y[yi:],
-// 		Cb:             p.Cb[ci:],
-// 		Cr:             p.Cr[ci:],
-// 		SubsampleRatio: p.SubsampleRatio,
-// 		YStride:        p.YStride,
-// 		CStride:        p.CStride,
-// 		Rect:           r,
-// 	}
-// }
-
-// func (p *YCbCr) Opaque() bool {
-// 	return true
-// }
-
-// func yCbCrSize(r Rectangle, subsampleRatio YCbCrSubsampleRatio) (w, h, cw, ch int) {
-// 	w, h = r.Dx(), r.Dy()
-// 	switch subsampleRatio {
-// 	case YCbCrSubsampleRatio422:
-// 		cw = (r.max.x+1)/2 - r.min.x/2
-// 		ch = h
-// 	case YCbCrSubsampleRatio420:
-// 		cw = (r.max.x+1)/2 - r.min.x/2
-// 		ch = (r.max.y+1)/2 - r.min.y/2
-// 	case YCbCrSubsampleRatio440:
-// 		cw = w
-// 		ch = (r.max.y+1)/2 - r.min.y/2
-// 	case YCbCrSubsampleRatio411:
-// 		cw = (r.max.x+3)/4 - r.min.x/4
-// 		ch = h
-// 	case YCbCrSubsampleRatio410:
-// 		cw = (r.max.x+3)/4 - r.min.x/4
-// 		ch = (r.max.y+1)/2 - r.min.y/2
-// 	default:
-// 		// Default to 4:4:4 subsampling.
-// 		cw = w
-// 		ch = h
-// 	}
-// 	return
-// }
-
-// // NewYCbCr returns a new YCbCr image with the given bounds and subsample
-// // ratio.
-// func NewYCbCr(r Rectangle, subsampleRatio YCbCrSubsampleRatio) *YCbCr {
-// 	w, h, cw, ch := yCbCrSize(r, subsampleRatio)
-
-// 	// totalLength should be the same as i2, below, for a valid Rectangle r.
-// 	totalLength := add2NonNeg(
-// 		mul3NonNeg(1, w, h),
-// 		mul3NonNeg(2, cw, ch),
-// 	)
-// 	if totalLength < 0 {
-// 		panic("image: NewYCbCr Rectangle has huge or negative dimensions")
-// 	}
-
-// 	i0 := w*h + 0*cw*ch
-// 	i1 := w*h + 1*cw*ch
-// 	i2 := w*h + 2*cw*ch
-// 	b := make([]byte, i2)
-// 	return &YCbCr{
-// 		Y:              b[:i0:i0],
-// 		Cb:             b[i0:i1:i1],
-// 		Cr:             b[i1:i2:i2],
-// 		SubsampleRatio: subsampleRatio,
-// 		YStride:        w,
-// 		CStride:        cw,
-// 		Rect:           r,
-// 	}
-// }
-
-// // NYCbCrA is an in-memory image of non-alpha-premultiplied Y'CbCr-with-alpha
-// // colors. A and AStride are analogous to the Y and YStride fields of the
-// // embedded YCbCr.
-// type NYCbCrA struct {
-// 	YCbCr
-// 	A       []uint8
-// 	AStride int
-// }
-
-// func (p *NYCbCrA) ColorModel() color::Model {
-// 	return color.NYCbCrAModel
-// }
-
-// func (p *NYCbCrA) At(x, y int) color.Color {
-// 	return p.NYCbCrAAt(x, y)
-// }
-
-// func (p *NYCbCrA) RGBA64At(x, y int) color.RGBA64 {
-// 	r, g, b, a := p.NYCbCrAAt(x, y).rgba()
-// 	return color.RGBA64{uint16(r), uint16(g), uint16(b), uint16(a)}
-// }
-
-// func (p *NYCbCrA) NYCbCrAAt(x, y int) color.NYCbCrA {
-// 	if !(Point{X: x, Y: y}.inside(p.Rect)) {
-// 		return color.NYCbCrA{}
-// 	}
-// 	yi := p.YOffset(x, y)
-// 	ci := p.COffset(x, y)
-// 	ai := p.AOffset(x, y)
-// 	return color.NYCbCrA{
-// 		color.YCbCr{
-// 			Y:  p.y[yi],
-// 			Cb: p.Cb[ci],
-// 			Cr: p.Cr[ci],
-// 		},
-// 		p.A[ai],
-// 	}
-// }
-
-// // AOffset returns the index of the first element of A that corresponds to the
-// // pixel at (x, y).
-// func (p *NYCbCrA) AOffset(x, y int) int {
-// 	return (y-p.Rect.min.y)*p.AStride + (x - p.Rect.min.x)
-// }
-
-// // SubImage returns an image representing the portion of the image p visible
-// // through r. The returned value shares pixels with the original image.
-// func (p *NYCbCrA) SubImage(r Rectangle) Image {
-// 	r = r.intersect(p.Rect)
-// 	// If r1 and r2 are Rectangles, r1.intersect(r2) is not guaranteed to be inside
-// 	// either r1 or r2 if the intersection is empty. Without explicitly checking for
-// 	// this, the Pix[i:] expression below can panic.
-// 	if r.Empty() {
-// 		return &NYCbCrA{
-// 			YCbCr: YCbCr{
-// 				SubsampleRatio: p.SubsampleRatio,
-// 			},
-// 		}
-// 	}
-// 	yi := p.YOffset(r.min.x, r.min.y)
-// 	ci := p.COffset(r.min.x, r.min.y)
-// 	ai := p.AOffset(r.min.x, r.min.y)
-// 	return &NYCbCrA{
-// 		YCbCr: YCbCr{
-// 			Y:              p.y[yi:],
-// 			Cb:             p.Cb[ci:],
-// 			Cr:             p.Cr[ci:],
-// 			SubsampleRatio: p.SubsampleRatio,
-// 			YStride:        p.YStride,
-// 			CStride:        p.CStride,
-// 			Rect:           r,
-// 		},
-// 		A:       p.A[ai:],
-// 		AStride: p.AStride,
-// 	}
-// }
-
-// // Opaque scans the entire image and reports whether it is fully opaque.
-// func (p *NYCbCrA) Opaque() bool {
-// 	if p.Rect.Empty() {
-// 		return true
-// 	}
-// 	i0, i1 := 0, p.Rect.Dx()
-// 	for y := p.Rect.min.y; y < p.Rect.max.y; y++ {
-// 		for _, a := range p.A[i0:i1] {
-// 			if a != 0xff {
-// 				return false
-// 			}
-// 		}
-// 		i0 += p.AStride
-// 		i1 += p.AStride
-// 	}
-// 	return true
-// }
-
-// // NewNYCbCrA returns a new NYCbCrA image with the given bounds and subsample
-// // ratio.
-// func NewNYCbCrA(r Rectangle, subsampleRatio YCbCrSubsampleRatio) *NYCbCrA {
-// 	w, h, cw, ch := yCbCrSize(r, subsampleRatio)
-
-// 	// totalLength should be the same as i3, below, for a valid Rectangle r.
-// 	totalLength := add2NonNeg(
-// 		mul3NonNeg(2, w, h),
-// 		mul3NonNeg(2, cw, ch),
-// 	)
-// 	if totalLength < 0 {
-// 		panic("image: NewNYCbCrA Rectangle has huge or negative dimension")
-// 	}
-
-// 	i0 := 1*w*h + 0*cw*ch
-// 	i1 := 1*w*h + 1*cw*ch
-// 	i2 := 1*w*h + 2*cw*ch
-// 	i3 := 2*w*h + 2*cw*ch
-// 	b := make([]byte, i3)
-// 	return &NYCbCrA{
-// 		YCbCr: YCbCr{
-// 			Y:              b[:i0:i0],
-// 			Cb:             b[i0:i1:i1],
-// 			Cr:             b[i1:i2:i2],
-// 			SubsampleRatio: subsampleRatio,
-// 			YStride:        w,
-// 			CStride:        cw,
-// 			Rect:           r,
-// 		},
-// 		A:       b[i2:],
-// 		AStride: w,
-// 	}
-// }
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2011 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::color;
+use super::geom::{Point, Rectangle};
+use super::image::Image;
+
+/// YCbCrSubsampleRatio is the chroma subsample ratio used in a YCbCr image.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum YCbCrSubsampleRatio {
+    Ratio444,
+    Ratio422,
+    Ratio420,
+    Ratio440,
+    Ratio411,
+    Ratio410,
+}
+
+impl std::fmt::Display for YCbCrSubsampleRatio {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            YCbCrSubsampleRatio::Ratio444 => "YCbCrSubsampleRatio444",
+            YCbCrSubsampleRatio::Ratio422 => "YCbCrSubsampleRatio422",
+            YCbCrSubsampleRatio::Ratio420 => "YCbCrSubsampleRatio420",
+            YCbCrSubsampleRatio::Ratio440 => "YCbCrSubsampleRatio440",
+            YCbCrSubsampleRatio::Ratio411 => "YCbCrSubsampleRatio411",
+            YCbCrSubsampleRatio::Ratio410 => "YCbCrSubsampleRatio410",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// YCbCr is an in-memory image of Y'CbCr colors. There is one Y sample per
+/// pixel, but each Cb and Cr sample can span one or more pixels.
+/// y_stride is the Y slice index delta between vertically adjacent pixels.
+/// c_stride is the Cb and Cr slice index delta between vertically adjacent pixels
+/// that map to separate chroma samples.
+/// It is not an absolute requirement, but y_stride and y().len() are typically
+/// multiples of 8, and:
+///
+///  For 4:4:4, c_stride == y_stride/1 && cb().len() == cr().len() == y().len()/1.
+///  For 4:2:2, c_stride == y_stride/2 && cb().len() == cr().len() == y().len()/2.
+///  For 4:2:0, c_stride == y_stride/2 && cb().len() == cr().len() == y().len()/4.
+///  For 4:4:0, c_stride == y_stride/1 && cb().len() == cr().len() == y().len()/2.
+///  For 4:1:1, c_stride == y_stride/4 && cb().len() == cr().len() == y().len()/4.
+///  For 4:1:0, c_stride == y_stride/4 && cb().len() == cr().len() == y().len()/8.
+///
+/// rust ggstd: the Y, Cb and Cr planes are stored one after another in
+/// a single buffer returned by pix().  Use y(), cb(), cr() and their
+/// mutable counterparts to access the individual planes.
+#[derive(Debug)]
+pub struct YCbCr {
+    pix: Vec<u8>,
+    pub y_stride: usize,
+    pub c_stride: usize,
+    pub subsample_ratio: YCbCrSubsampleRatio,
+    rect: Rectangle,
+    cb_start: usize, // index of the first Cb sample in pix
+    cr_start: usize, // index of the first Cr sample in pix
+}
+
+impl Image for YCbCr {
+    fn color_model(&self) -> color::Model {
+        color::Model::YCbCrModel
+    }
+
+    fn bounds(&self) -> &Rectangle {
+        &self.rect
+    }
+
+    fn at(&self, x: isize, y: isize) -> color::Color {
+        color::Color::YCbCr(self.ycbcr_at(x, y))
+    }
+
+    /// set sets the color of the pixel at (x, y).  When chroma is subsampled,
+    /// the Cb and Cr samples are shared with the neighbouring pixels and get
+    /// overwritten for all of them.
+    fn set(&mut self, x: isize, y: isize, c: &color::Color) {
+        self.set_ycbcr(x, y, &color::YCbCr::new_from(c));
+    }
+
+    fn opaque(&self) -> bool {
+        true
+    }
+
+    fn stride(&self) -> usize {
+        self.y_stride
+    }
+
+    fn pix(&self) -> &Vec<u8> {
+        &self.pix
+    }
+
+    fn get_pix_mutable(&mut self) -> &mut Vec<u8> {
+        &mut self.pix
+    }
+
+    fn bytes_per_pixel(&self) -> usize {
+        1
+    }
+
+    fn subsample_ratio(&self) -> Option<YCbCrSubsampleRatio> {
+        Some(self.subsample_ratio)
+    }
+}
+
+impl YCbCr {
+    /// new returns a new YCbCr image with the given bounds and subsample
+    /// ratio.
+    pub fn new(r: &Rectangle, subsample_ratio: YCbCrSubsampleRatio) -> Self {
+        let (w, h, cw, ch) = ycbcr_size(r, subsample_ratio);
+        Self::with_extra(r, subsample_ratio, w, cw, ch, w * h, 0)
+    }
+
+    /// with_extra creates a YCbCr image with extra bytes after the Cr plane.
+    fn with_extra(
+        r: &Rectangle,
+        subsample_ratio: YCbCrSubsampleRatio,
+        y_stride: usize,
+        c_stride: usize,
+        ch: usize,
+        y_len: usize,
+        extra: usize,
+    ) -> Self {
+        let i0 = y_len;
+        let i1 = i0 + c_stride * ch;
+        let i2 = i1 + c_stride * ch;
+        Self {
+            pix: vec![0; i2 + extra],
+            y_stride,
+            c_stride,
+            subsample_ratio,
+            rect: *r,
+            cb_start: i0,
+            cr_start: i1,
+        }
+    }
+
+    /// y returns the Y plane.
+    pub fn y(&self) -> &[u8] {
+        &self.pix[..self.cb_start]
+    }
+
+    /// cb returns the Cb plane.
+    pub fn cb(&self) -> &[u8] {
+        &self.pix[self.cb_start..self.cr_start]
+    }
+
+    /// cr returns the Cr plane.
+    pub fn cr(&self) -> &[u8] {
+        &self.pix[self.cr_start..self.cr_end()]
+    }
+
+    /// y_mut returns the mutable Y plane.
+    pub fn y_mut(&mut self) -> &mut [u8] {
+        &mut self.pix[..self.cb_start]
+    }
+
+    /// cb_mut returns the mutable Cb plane.
+    pub fn cb_mut(&mut self) -> &mut [u8] {
+        &mut self.pix[self.cb_start..self.cr_start]
+    }
+
+    /// cr_mut returns the mutable Cr plane.
+    pub fn cr_mut(&mut self) -> &mut [u8] {
+        let end = self.cr_end();
+        &mut self.pix[self.cr_start..end]
+    }
+
+    /// planes_mut returns the mutable Y, Cb and Cr planes at once.
+    pub fn planes_mut(&mut self) -> (&mut [u8], &mut [u8], &mut [u8]) {
+        let (y, cb, cr, _) = self.split_pix_mut();
+        (y, cb, cr)
+    }
+
+    fn cr_end(&self) -> usize {
+        self.cr_start + (self.cr_start - self.cb_start)
+    }
+
+    /// split_pix_mut splits pix into the Y, Cb, Cr planes and the rest.
+    fn split_pix_mut(&mut self) -> (&mut [u8], &mut [u8], &mut [u8], &mut [u8]) {
+        let c_len = self.cr_start - self.cb_start;
+        let (y, rest) = self.pix.split_at_mut(self.cb_start);
+        let (cb, rest) = rest.split_at_mut(c_len);
+        let (cr, rest) = rest.split_at_mut(c_len);
+        (y, cb, cr, rest)
+    }
+
+    pub fn ycbcr_at(&self, x: isize, y: isize) -> color::YCbCr {
+        if !(Point::new(x, y).inside(&self.rect)) {
+            return color::YCbCr::default();
+        }
+        let yi = self.y_offset(x, y);
+        let ci = self.c_offset(x, y);
+        color::YCbCr::new(
+            self.pix[yi],
+            self.pix[self.cb_start + ci],
+            self.pix[self.cr_start + ci],
+        )
+    }
+
+    fn set_ycbcr(&mut self, x: isize, y: isize, c: &color::YCbCr) {
+        if !(Point::new(x, y).inside(&self.rect)) {
+            return;
+        }
+        let yi = self.y_offset(x, y);
+        let ci = self.c_offset(x, y);
+        self.pix[yi] = c.y;
+        self.pix[self.cb_start + ci] = c.cb;
+        self.pix[self.cr_start + ci] = c.cr;
+    }
+
+    /// y_offset returns the index of the first element of Y that corresponds to
+    /// the pixel at (x, y).
+    pub fn y_offset(&self, x: isize, y: isize) -> usize {
+        (y - self.rect.min.y) as usize * self.y_stride + (x - self.rect.min.x) as usize
+    }
+
+    /// c_offset returns the index of the first element of Cb or Cr that corresponds
+    /// to the pixel at (x, y).
+    pub fn c_offset(&self, x: isize, y: isize) -> usize {
+        let r = &self.rect;
+        let (row, col) = match self.subsample_ratio {
+            YCbCrSubsampleRatio::Ratio422 => (y - r.min.y, x / 2 - r.min.x / 2),
+            YCbCrSubsampleRatio::Ratio420 => (y / 2 - r.min.y / 2, x / 2 - r.min.x / 2),
+            YCbCrSubsampleRatio::Ratio440 => (y / 2 - r.min.y / 2, x - r.min.x),
+            YCbCrSubsampleRatio::Ratio411 => (y - r.min.y, x / 4 - r.min.x / 4),
+            YCbCrSubsampleRatio::Ratio410 => (y / 2 - r.min.y / 2, x / 4 - r.min.x / 4),
+            YCbCrSubsampleRatio::Ratio444 => (y - r.min.y, x - r.min.x),
+        };
+        row as usize * self.c_stride + col as usize
+    }
+}
+
+/// ycbcr_size returns the width and height of the luma and chroma planes
+/// of an image with the given bounds and subsample ratio.
+fn ycbcr_size(r: &Rectangle, subsample_ratio: YCbCrSubsampleRatio) -> (usize, usize, usize, usize) {
+    let (w, h) = (r.dx(), r.dy());
+    let (cw, ch) = match subsample_ratio {
+        YCbCrSubsampleRatio::Ratio422 => ((r.max.x + 1) / 2 - r.min.x / 2, h as isize),
+        YCbCrSubsampleRatio::Ratio420 => (
+            (r.max.x + 1) / 2 - r.min.x / 2,
+            (r.max.y + 1) / 2 - r.min.y / 2,
+        ),
+        YCbCrSubsampleRatio::Ratio440 => (w as isize, (r.max.y + 1) / 2 - r.min.y / 2),
+        YCbCrSubsampleRatio::Ratio411 => ((r.max.x + 3) / 4 - r.min.x / 4, h as isize),
+        YCbCrSubsampleRatio::Ratio410 => (
+            (r.max.x + 3) / 4 - r.min.x / 4,
+            (r.max.y + 1) / 2 - r.min.y / 2,
+        ),
+        YCbCrSubsampleRatio::Ratio444 => (w as isize, h as isize),
+    };
+    (w, h, cw.max(0) as usize, ch.max(0) as usize)
+}
+
+/// NYCbCrA is an in-memory image of non-alpha-premultiplied Y'CbCr-with-alpha
+/// colors. a() and a_stride are analogous to the y() and y_stride fields of the
+/// embedded YCbCr.
+#[derive(Debug)]
+pub struct NYCbCrA {
+    /// ycbcr holds the color planes; its pix buffer also contains the alpha plane.
+    pub ycbcr: YCbCr,
+    pub a_stride: usize,
+}
+
+impl Image for NYCbCrA {
+    fn color_model(&self) -> color::Model {
+        color::Model::NYCbCrAModel
+    }
+
+    fn bounds(&self) -> &Rectangle {
+        &self.ycbcr.rect
+    }
+
+    fn at(&self, x: isize, y: isize) -> color::Color {
+        color::Color::NYCbCrA(self.nycbcra_at(x, y))
+    }
+
+    /// set sets the color of the pixel at (x, y).  When chroma is subsampled,
+    /// the Cb and Cr samples are shared with the neighbouring pixels and get
+    /// overwritten for all of them.
+    fn set(&mut self, x: isize, y: isize, c: &color::Color) {
+        if !(Point::new(x, y).inside(&self.ycbcr.rect)) {
+            return;
+        }
+        let c = color::NYCbCrA::new_from(c);
+        self.ycbcr.set_ycbcr(x, y, &c.ycbcr);
+        let ai = self.a_offset(x, y);
+        self.a_mut()[ai] = c.a;
+    }
+
+    /// opaque scans the entire image and reports whether it is fully opaque.
+    fn opaque(&self) -> bool {
+        let r = &self.ycbcr.rect;
+        if r.empty() {
+            return true;
+        }
+        let a = self.a();
+        let (mut i0, mut i1) = (0, r.dx());
+        for _y in r.min.y..r.max.y {
+            if a[i0..i1].iter().any(|v| *v != 0xff) {
+                return false;
+            }
+            i0 += self.a_stride;
+            i1 += self.a_stride;
+        }
+        true
+    }
+
+    fn stride(&self) -> usize {
+        self.ycbcr.y_stride
+    }
+
+    fn pix(&self) -> &Vec<u8> {
+        &self.ycbcr.pix
+    }
+
+    fn get_pix_mutable(&mut self) -> &mut Vec<u8> {
+        &mut self.ycbcr.pix
+    }
+
+    fn bytes_per_pixel(&self) -> usize {
+        1
+    }
+
+    fn subsample_ratio(&self) -> Option<YCbCrSubsampleRatio> {
+        Some(self.ycbcr.subsample_ratio)
+    }
+}
+
+impl NYCbCrA {
+    /// new returns a new NYCbCrA image with the given bounds and subsample
+    /// ratio.
+    pub fn new(r: &Rectangle, subsample_ratio: YCbCrSubsampleRatio) -> Self {
+        let (w, h, cw, ch) = ycbcr_size(r, subsample_ratio);
+        Self {
+            ycbcr: YCbCr::with_extra(r, subsample_ratio, w, cw, ch, w * h, w * h),
+            a_stride: w,
+        }
+    }
+
+    /// a returns the alpha plane.
+    pub fn a(&self) -> &[u8] {
+        &self.ycbcr.pix[self.ycbcr.cr_end()..]
+    }
+
+    /// a_mut returns the mutable alpha plane.
+    pub fn a_mut(&mut self) -> &mut [u8] {
+        let start = self.ycbcr.cr_end();
+        &mut self.ycbcr.pix[start..]
+    }
+
+    /// planes_mut returns the mutable Y, Cb, Cr and alpha planes at once.
+    pub fn planes_mut(&mut self) -> (&mut [u8], &mut [u8], &mut [u8], &mut [u8]) {
+        self.ycbcr.split_pix_mut()
+    }
+
+    pub fn nycbcra_at(&self, x: isize, y: isize) -> color::NYCbCrA {
+        if !(Point::new(x, y).inside(&self.ycbcr.rect)) {
+            return color::NYCbCrA::default();
+        }
+        color::NYCbCrA {
+            ycbcr: self.ycbcr.ycbcr_at(x, y),
+            a: self.a()[self.a_offset(x, y)],
+        }
+    }
+
+    /// a_offset returns the index of the first element of A that corresponds to the
+    /// pixel at (x, y).
+    pub fn a_offset(&self, x: isize, y: isize) -> usize {
+        let r = &self.ycbcr.rect;
+        (y - r.min.y) as usize * self.a_stride + (x - r.min.x) as usize
+    }
+}
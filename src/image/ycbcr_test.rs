@@ -1,133 +1,228 @@
-// // Copyright 2012 The Go Authors. All rights reserved.
-// // Use of this source code is governed by a BSD-style
-// // license that can be found in the LICENSE file.
-
-// package image
-
-// import (
-// 	"image/color"
-// 	"testing"
-// )
-
-// func TestYCbCr(t *testing.T) {
-// 	rects := []Rectangle{
-// 		Rect(0, 0, 16, 16),
-// 		Rect(1, 0, 16, 16),
-// 		Rect(0, 1, 16, 16),
-// 		Rect(1, 1, 16, 16),
-// 		Rect(1, 1, 15, 16),
-// 		Rect(1, 1, 16, 15),
-// 		Rect(1, 1, 15, 15),
-// 		Rect(2, 3, 14, 15),
-// 		Rect(7, 0, 7, 16),
-// 		Rect(0, 8, 16, 8),
-// 		Rect(0, 0, 10, 11),
-// 		Rect(5, 6, 16, 16),
-// 		Rect(7, 7, 8, 8),
-// 		Rect(7, 8, 8, 9),
-// 		Rect(8, 7, 9, 8),
-// 		Rect(8, 8, 9, 9),
-// 		Rect(7, 7, 17, 17),
-// 		Rect(8, 8, 17, 17),
-// 		Rect(9, 9, 17, 17),
-// 		Rect(10, 10, 17, 17),
-// 	}
-// 	subsampleRatios := []YCbCrSubsampleRatio{
-// 		YCbCrSubsampleRatio444,
-// 		YCbCrSubsampleRatio422,
-// 		YCbCrSubsampleRatio420,
-// 		YCbCrSubsampleRatio440,
-// 		YCbCrSubsampleRatio411,
-// 		YCbCrSubsampleRatio410,
-// 	}
-// 	deltas := []Point{
-// 		Pt(0, 0),
-// 		Pt(1000, 1001),
-// 		Pt(5001, -400),
-// 		Pt(-701, -801),
-// 	}
-// 	for _, r := range rects {
-// 		for _, subsampleRatio := range subsampleRatios {
-// 			for _, delta := range deltas {
-// 				testYCbCr(t, r, subsampleRatio, delta)
-// 			}
-// 		}
-// 		if testing.Short() {
-// 			break
-// 		}
-// 	}
-// }
-
-// func testYCbCr(t *testing.T, r Rectangle, subsampleRatio YCbCrSubsampleRatio, delta Point) {
-// 	// Create a YCbCr image m, whose bounds are r translated by (delta.x, delta.y).
-// 	r1 := r.Add(delta)
-// 	m := NewYCbCr(r1, subsampleRatio)
-
-// 	// Test that the image buffer is reasonably small even if (delta.x, delta.y) is far from the origin.
-// 	if len(m.y) > 100*100 {
-// 		t.Errorf("r=%v, subsampleRatio=%v, delta=%v: image buffer is too large",
-// 			r, subsampleRatio, delta)
-// 		return
-// 	}
-
-// 	// Initialize m's pixels. For 422 and 420 subsampling, some of the Cb and Cr elements
-// 	// will be set multiple times. That's OK. We just want to avoid a uniform image.
-// 	for y := r1.min.y; y < r1.max.y; y++ {
-// 		for x := r1.min.x; x < r1.max.x; x++ {
-// 			yi := m.YOffset(x, y)
-// 			ci := m.COffset(x, y)
-// 			m.y[yi] = uint8(16*y + x)
-// 			m.Cb[ci] = uint8(y + 16*x)
-// 			m.Cr[ci] = uint8(y + 16*x)
-// 		}
-// 	}
-
-// 	// Make various sub-images of m.
-// 	for y0 := delta.y + 3; y0 < delta.y+7; y0++ {
-// 		for y1 := delta.y + 8; y1 < delta.y+13; y1++ {
-// 			for x0 := delta.x + 3; x0 < delta.x+7; x0++ {
-// 				for x1 := delta.x + 8; x1 < delta.x+13; x1++ {
-// 					subRect := Rect(x0, y0, x1, y1)
-// 					sub := m.SubImage(subRect).(*YCbCr)
-
-// 					// For each point in the sub-image's bounds, check that m.At(x, y) equals sub.At(x, y).
-// 					for y := sub.Rect.min.y; y < sub.Rect.max.y; y++ {
-// 						for x := sub.Rect.min.x; x < sub.Rect.max.x; x++ {
-// 							color0 := m.At(x, y).(color.YCbCr)
-// 							color1 := sub.At(x, y).(color.YCbCr)
-// 							if color0 != color1 {
-// 								t.Errorf("r=%v, subsampleRatio=%v, delta=%v, x=%d, y=%d, color0=%v, color1=%v",
-// 									r, subsampleRatio, delta, x, y, color0, color1)
-// 								return
-// 							}
-// 						}
-// 					}
-// 				}
-// 			}
-// 		}
-// 	}
-// }
-
-// func TestYCbCrSlicesDontOverlap(t *testing.T) {
-// 	m := NewYCbCr(Rect(0, 0, 8, 8), YCbCrSubsampleRatio420)
-// 	names := []string{"Y", "Cb", "Cr"}
-// 	slices := [][]byte{
-// 		m.y[:cap(m.y)],
-// 		m.Cb[:cap(m.Cb)],
-// 		m.Cr[:cap(m.Cr)],
-// 	}
-// 	for i, slice := range slices {
-// 		want := uint8(10 + i)
-// 		for j := range slice {
-// 			slice[j] = want
-// 		}
-// 	}
-// 	for i, slice := range slices {
-// 		want := uint8(10 + i)
-// 		for j, got := range slice {
-// 			if got != want {
-// 				t.Fatalf("m.%s[%d]: got %d, want %d", names[i], j, got, want)
-// 			}
-// 		}
-// 	}
-// }
+// Copyright 2023 The rust-ggstd authors. All rights reserved.
+// Copyright 2012 The Go Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use super::color;
+use super::{rect, Image, NYCbCrA, Point, Rectangle, YCbCr, YCbCrSubsampleRatio};
+
+const SUBSAMPLE_RATIOS: &[YCbCrSubsampleRatio] = &[
+    YCbCrSubsampleRatio::Ratio444,
+    YCbCrSubsampleRatio::Ratio422,
+    YCbCrSubsampleRatio::Ratio420,
+    YCbCrSubsampleRatio::Ratio440,
+    YCbCrSubsampleRatio::Ratio411,
+    YCbCrSubsampleRatio::Ratio410,
+];
+
+#[test]
+fn test_ycbcr() {
+    let rects = &[
+        rect(0, 0, 16, 16),
+        rect(1, 0, 16, 16),
+        rect(0, 1, 16, 16),
+        rect(1, 1, 16, 16),
+        rect(1, 1, 15, 16),
+        rect(1, 1, 16, 15),
+        rect(1, 1, 15, 15),
+        rect(2, 3, 14, 15),
+        rect(7, 0, 7, 16),
+        rect(0, 8, 16, 8),
+        rect(0, 0, 10, 11),
+        rect(5, 6, 16, 16),
+        rect(7, 7, 8, 8),
+        rect(7, 8, 8, 9),
+        rect(8, 7, 9, 8),
+        rect(8, 8, 9, 9),
+        rect(7, 7, 17, 17),
+        rect(8, 8, 17, 17),
+        rect(9, 9, 17, 17),
+        rect(10, 10, 17, 17),
+    ];
+    let deltas = &[
+        Point::new(0, 0),
+        Point::new(1000, 1001),
+        Point::new(5001, -400),
+        Point::new(-701, -801),
+    ];
+    for r in rects {
+        for subsample_ratio in SUBSAMPLE_RATIOS {
+            for delta in deltas {
+                test_ycbcr_with(r, *subsample_ratio, delta);
+            }
+        }
+    }
+}
+
+fn test_ycbcr_with(r: &Rectangle, subsample_ratio: YCbCrSubsampleRatio, delta: &Point) {
+    // Create a YCbCr image m, whose bounds are r translated by (delta.x, delta.y).
+    let r1 = r.add(delta);
+    let mut m = YCbCr::new(&r1, subsample_ratio);
+
+    // Test that the image buffer is reasonably small even if (delta.x, delta.y) is far from the origin.
+    assert!(
+        m.y().len() <= 100 * 100,
+        "r={:?}, subsample_ratio={}, delta={:?}: image buffer is too large",
+        r,
+        subsample_ratio,
+        delta
+    );
+
+    // Initialize m's pixels. For 422 and 420 subsampling, some of the Cb and Cr elements
+    // will be set multiple times. That's OK. We just want to avoid a uniform image.
+    for y in r1.min.y..r1.max.y {
+        for x in r1.min.x..r1.max.x {
+            let yi = m.y_offset(x, y);
+            let ci = m.c_offset(x, y);
+            let (py, pcb, pcr) = m.planes_mut();
+            py[yi] = (16 * y + x) as u8;
+            pcb[ci] = (y + 16 * x) as u8;
+            pcr[ci] = (y + 16 * x + 1) as u8;
+        }
+    }
+
+    // Check that at() returns the samples for every pixel, and that
+    // each chroma sample is shared by the expected block of pixels.
+    // Like c_offset, the blocks are computed with the division rounding
+    // towards zero.
+    let (sx, sy) = match subsample_ratio {
+        YCbCrSubsampleRatio::Ratio444 => (1, 1),
+        YCbCrSubsampleRatio::Ratio422 => (2, 1),
+        YCbCrSubsampleRatio::Ratio420 => (2, 2),
+        YCbCrSubsampleRatio::Ratio440 => (1, 2),
+        YCbCrSubsampleRatio::Ratio411 => (4, 1),
+        YCbCrSubsampleRatio::Ratio410 => (4, 2),
+    };
+    let mut chroma = std::collections::HashMap::new();
+    for y in r1.min.y..r1.max.y {
+        for x in r1.min.x..r1.max.x {
+            chroma.insert((x / sx, y / sy), (y + 16 * x) as u8);
+        }
+    }
+    for y in r1.min.y..r1.max.y {
+        for x in r1.min.x..r1.max.x {
+            let c = match m.at(x, y) {
+                color::Color::YCbCr(c) => c,
+                c => panic!("unexpected color type {:?}", c),
+            };
+            assert_eq!(c.y, (16 * y + x) as u8);
+            let want = chroma[&(x / sx, y / sy)];
+            assert_eq!(
+                (c.cb, c.cr),
+                (want, want.wrapping_add(1)),
+                "r={:?}, subsample_ratio={}, delta={:?}, x={}, y={}",
+                r,
+                subsample_ratio,
+                delta,
+                x,
+                y
+            );
+        }
+    }
+}
+
+#[test]
+fn test_ycbcr_slices_dont_overlap() {
+    let mut m = YCbCr::new(&rect(0, 0, 8, 8), YCbCrSubsampleRatio::Ratio420);
+    let names = ["Y", "Cb", "Cr"];
+    {
+        let (y, cb, cr) = m.planes_mut();
+        for (i, slice) in [y, cb, cr].into_iter().enumerate() {
+            let want = (10 + i) as u8;
+            slice.fill(want);
+        }
+    }
+    for (i, slice) in [m.y(), m.cb(), m.cr()].into_iter().enumerate() {
+        let want = (10 + i) as u8;
+        for (j, got) in slice.iter().enumerate() {
+            assert_eq!(
+                *got, want,
+                "m.{}[{}]: got {}, want {}",
+                names[i], j, got, want
+            );
+        }
+    }
+    assert_eq!(m.y().len(), 64);
+    assert_eq!(m.cb().len(), 16);
+    assert_eq!(m.cr().len(), 16);
+}
+
+#[test]
+fn test_ycbcr_set() {
+    for subsample_ratio in SUBSAMPLE_RATIOS {
+        let mut m = YCbCr::new(&rect(0, 0, 4, 4), *subsample_ratio);
+        let c = color::Color::YCbCr(color::YCbCr::new(10, 20, 30));
+        m.set(1, 1, &c);
+        assert_eq!(m.at(1, 1), c);
+        assert_eq!(m.color_model().convert(&c), c);
+        assert!(m.opaque());
+        // outside of the bounds
+        m.set(4, 4, &c);
+        assert_eq!(m.at(4, 4), color::Color::YCbCr(color::YCbCr::default()));
+    }
+}
+
+#[test]
+fn test_nycbcra() {
+    for subsample_ratio in SUBSAMPLE_RATIOS {
+        let r = rect(3, 5, 9, 10);
+        let mut m = NYCbCrA::new(&r, *subsample_ratio);
+        assert_eq!(m.a().len(), r.dx() * r.dy());
+        assert!(!m.opaque());
+        m.a_mut().fill(0xff);
+        assert!(m.opaque());
+
+        let c = color::Color::NYCbCrA(color::NYCbCrA::new(10, 20, 30, 40));
+        m.set(4, 6, &c);
+        assert_eq!(m.at(4, 6), c);
+        assert!(!m.opaque());
+        assert_eq!(m.a()[m.a_offset(4, 6)], 40);
+
+        // The alpha plane does not overlap the color planes.
+        let (y, cb, cr, a) = m.planes_mut();
+        y.fill(1);
+        cb.fill(2);
+        cr.fill(3);
+        a.fill(4);
+        assert_eq!(
+            m.at(8, 9),
+            color::Color::NYCbCrA(color::NYCbCrA::new(1, 2, 3, 4))
+        );
+        assert_eq!(m.ycbcr.y().len(), r.dx() * r.dy());
+    }
+}
+
+#[test]
+fn test_ycbcr_deep_equal() {
+    // 4:2:2 and 4:4:0 images of the same bounds have planes of the same
+    // size, so only the subsample ratio tells them apart.
+    let r = rect(0, 0, 4, 4);
+    let m422 = YCbCr::new(&r, YCbCrSubsampleRatio::Ratio422);
+    let m440 = YCbCr::new(&r, YCbCrSubsampleRatio::Ratio440);
+    assert_eq!(m422.pix(), m440.pix());
+    assert!(m422.deep_equal(&YCbCr::new(&r, YCbCrSubsampleRatio::Ratio422)));
+    assert!(!m422.deep_equal(&m440));
+    assert!(!m440.deep_equal(&m422));
+
+    let a422 = NYCbCrA::new(&r, YCbCrSubsampleRatio::Ratio422);
+    let a440 = NYCbCrA::new(&r, YCbCrSubsampleRatio::Ratio440);
+    assert!(a422.deep_equal(&NYCbCrA::new(&r, YCbCrSubsampleRatio::Ratio422)));
+    assert!(!a422.deep_equal(&a440));
+    let i422 = super::Img::YCbCr(YCbCr::new(&r, YCbCrSubsampleRatio::Ratio422));
+    let i440 = super::Img::YCbCr(YCbCr::new(&r, YCbCrSubsampleRatio::Ratio440));
+    assert!(i422.deep_equal(&m422));
+    assert!(!i422.deep_equal(&i440));
+
+    // get_line_data returns a line of the Y plane.
+    let mut m = YCbCr::new(&r, YCbCrSubsampleRatio::Ratio420);
+    m.set(2, 1, &color::Color::YCbCr(color::YCbCr::new(10, 20, 30)));
+    assert_eq!(m.get_line_data(1), &[0, 0, 10, 0]);
+}
+
+#[test]
+fn test_subsample_ratio_string() {
+    assert_eq!(
+        YCbCrSubsampleRatio::Ratio420.to_string(),
+        "YCbCrSubsampleRatio420"
+    );
+}
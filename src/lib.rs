@@ -3,6 +3,7 @@
 
 #![allow(clippy::module_inception)]
 
+pub mod archive;
 pub mod bufio;
 pub mod builtin;
 pub mod bytes;
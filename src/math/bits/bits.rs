@@ -198,23 +198,20 @@ use super::bits_tables;
 ///
 /// This function's execution time does not depend on the inputs.
 pub fn rotate_left32(x: u32, k: isize) -> u32 {
-    if k % 32 == 0 {
-        return x;
-    }
     let n = 32;
     let s = (k as usize) & (n - 1);
-    x << s | x >> (n - s)
+    x << s | x >> ((n - s) & (n - 1))
 }
 
-// // RotateLeft64 returns the value of x rotated left by (k mod 64) bits.
-// // To rotate x right by k bits, call RotateLeft64(x, -k).
-// //
-// // This function's execution time does not depend on the inputs.
-// fn RotateLeft64(x uint64, k int) uint64 {
-// 	const n = 64
-// 	s := uint(k) & (n - 1)
-// 	return x<<s | x>>(n-s)
-// }
+/// rotate_left64 returns the value of x rotated left by (k mod 64) bits.
+/// To rotate x right by k bits, call rotate_left64(x, -k).
+///
+/// This function's execution time does not depend on the inputs.
+pub fn rotate_left64(x: u64, k: isize) -> u64 {
+    let n = 64;
+    let s = (k as usize) & (n - 1);
+    x << s | x >> ((n - s) & (n - 1))
+}
 
 // // --- Reverse ---
 
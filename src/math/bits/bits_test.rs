@@ -385,16 +385,12 @@ fn test_rotate_left() {
         // 			}
         // 		}
 
-        // 		let x64 = u64(m);
-        // 		let got64 = RotateLeft64(x64, k as isize);
-        // 		let want64 = x64<<(k&0x3f) | x64>>(64-k&0x3f);
-        // 		if got64 != want64 {
-        // 			t.Fatalf("RotateLeft64(%#016x, {}) == %#016x; want %#016x", x64, k, got64, want64)
-        // 		}
-        // 		got64 = RotateLeft64(want64, -k as isize);
-        // 		if got64 != x64 {
-        // 			t.Fatalf("RotateLeft64(%#016x, -{}) == %#016x; want %#016x", want64, k, got64, x64)
-        // 		}
+        let x64 = m;
+        let got64 = bits::rotate_left64(x64, k as isize);
+        let want64 = x64 << (k & 0x3f) | x64 >> ((64 - k) & 0x3f);
+        assert_eq!(want64, got64);
+        let got64 = bits::rotate_left64(want64, -k as isize);
+        assert_eq!(x64, got64);
         // 		if UintSize == 64 {
         // 			let x = uint(m);
         // 			let got = RotateLeft(x, k as isize);
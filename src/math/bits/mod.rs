@@ -15,7 +15,7 @@
 mod bits;
 mod bits_tables;
 
-pub use bits::{reverse16, reverse8, rotate_left32};
+pub use bits::{reverse16, reverse8, rotate_left32, rotate_left64};
 
 #[cfg(test)]
 mod bits_test;
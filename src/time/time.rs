@@ -129,7 +129,7 @@
 // // clock reading.
 
 /// A Time represents an instant in time with nanosecond precision.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct Time {
     // wall and ext encode the wall time seconds, wall time nanoseconds,
     // and optional monotonic clock reading in nanoseconds.
@@ -511,11 +511,11 @@ pub struct HMS {
 }
 
 impl Time {
-    // // IsZero reports whether t represents the zero time instant,
-    // // January 1, year 1, 00:00:00 UTC.
-    // fn IsZero(&self) bool {
-    // 	return t.sec() == 0 && t.nsec() == 0
-    // }
+    /// is_zero reports whether t represents the zero time instant,
+    /// January 1, year 1, 00:00:00 UTC.
+    pub fn is_zero(&self) -> bool {
+        self.sec() == 0 && self.nsec() == 0
+    }
 
     // abs returns the time t as an absolute time, adjusted by the zone offset.
     // It is called when computing a presentation property like Month or Hour.
@@ -1620,7 +1620,8 @@ pub fn date(
     }
 
     // Add in days before today.
-    d += (day - 1) as u64;
+    // day may be 0 here, so wrap around as Go's uint64 arithmetic does.
+    d = d.wrapping_add((day - 1) as u64);
 
     // Add in time elapsed today.
     let mut abs = d * SECONDS_PER_DAY;